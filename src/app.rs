@@ -1,20 +1,55 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use uuid::Uuid;
 
-use crate::instance::{Instance, InstanceManager};
+use crate::i18n::{plural, tr, trf};
+use crate::backup::{Backup, BackupManager, BackupReason};
+use crate::import::PendingDownload;
+use crate::instance::{CloneOptions, EffectiveSettings, ExportFormat, Instance, InstanceLink, InstanceLinks, InstanceManager, InstanceSort, LaunchHook, ModLoader};
+use crate::loaders::LoaderVersion;
+use crate::modpack::{ModpackFormat, ModpackUpdate};
 use crate::assets::AssetsManager;
-use crate::auth::{AuthManager, Account};
-use crate::java::JavaManager;
+use crate::auth::{AuthManager, Account, AccountType};
+use crate::skins::SkinCache;
+use crate::curseforge::CurseForgeClient;
+use crate::modrinth::{ModBrowser, ModrinthClient};
+use crate::java::{JavaInstallation, JavaManager};
+use crate::jvm_lint::{LintContext, LintIssue};
 use crate::profile::{Profile, ProfileManager};
+use crate::quickplay::QuickPlay;
 use crate::network::NetworkManager;
-use crate::settings::{Settings, SettingsManager, Language};
-use crate::launch::LaunchManager;
-use crate::mods::ModManager;
-use crate::version::{MinecraftVersion, VersionManager};
-use crate::logs::LogManager;
+use crate::settings::{SettingField, SettingValue, Settings, SettingsManager};
+use crate::launch::{DirectoryConflict, LaunchManager, LaunchOptions, PreparedLaunch, SessionConsole};
+use crate::mods::{DependencyCheck, Mod, ModManager, ModUpdate, ResolvedDependency, UpdateSource, UpdateTarget};
+use crate::github::{GitHubClient, GitHubMod, GitHubSource};
+use crate::version::{LoadedManifest, MinecraftVersion, VersionChannels, VersionFilter, VersionInstaller, VersionManager, VerifyReport};
+use crate::logs::{LogManager, LogView};
+use crate::crash::{CrashReport, GameExit};
+use crate::error::UserHint;
+use crate::events::{AppEvent, EventBus, LauncherEvent, Notifier, Readiness};
+use crate::watch::DirWatcher;
+use crate::packs::{PackBrowser, ResourcePackManager};
+use crate::bisect::BisectSession;
+use crate::status::{Connectivity, MojangStatus, ServiceHealth};
+use crate::motd::{Motd, RemoteMotd};
+use crate::news::PatchNote;
+use crate::updater::Update;
+use crate::shared::SharedSources;
+use crate::platform::SystemInfo;
+use crate::shortcut::ShortcutKind;
+use crate::storage::{Cleanup, CleanupResult, DiskUsage, StorageCategory, StorageRoots, UsageTarget};
+use crate::tasks::{TaskKind, TaskRegistry, TaskReporter, TaskStage};
+use crate::wizard::Wizard;
+use crate::utils::DataDirSource;
 use crate::Result;
+use futures_util::StreamExt;
+use tracing::Instrument;
+
+pub const LOG_SCROLLBACK: usize = 1000;
+/// Instances listed on the main menu, launched with keys 1 to this.
+pub const RECENT_INSTANCES: usize = 3;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppState {
@@ -24,6 +59,109 @@ pub enum AppState {
     Launcher,
     AccountManager,
     EditInstance,
+    CrashReport,
+    ModBisect,
+    ModBrowser,
+    CommandApproval,
+    AccountPicker,
+    PackManager,
+    ModList,
+    Backups,
+    LaunchConflict,
+    Storage,
+    LaunchVariants,
+    QuickPlay,
+    CloneInstance,
+    Logs,
+    Console,
+    Wizard,
+    News,
+    SystemInfo,
+    ModpackUpdate,
+    LoaderVersions,
+    Java,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommandApproval {
+    pub instance_id: Uuid,
+    pub instance_name: String,
+    pub commands: Vec<(LaunchHook, String)>,
+    pub options: LaunchOptions,
+    pub return_state: AppState,
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountPicker {
+    pub instance_id: Uuid,
+    pub instance_name: String,
+    pub accounts: Vec<Uuid>,
+    pub focus: Option<usize>,
+    pub options: LaunchOptions,
+    pub return_state: AppState,
+}
+
+// Worlds or servers of one instance, offered by "Launch into world/server".
+#[derive(Debug, Clone, Default)]
+pub struct OfficialImport {
+    pub instances: usize,
+    pub reused_files: usize,
+    pub account_hints: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct QuickPlayPicker {
+    pub instance_id: Uuid,
+    pub instance_name: String,
+    pub worlds: bool,
+    pub targets: Vec<(String, QuickPlay)>,
+}
+
+// Builds of the editing instance's loader, offered by its loader version
+// field. `query` narrows the list while typed.
+#[derive(Debug, Clone)]
+pub struct LoaderVersionPicker {
+    pub loader: ModLoader,
+    pub game_version: String,
+    pub versions: Vec<LoaderVersion>,
+    pub ready: Readiness,
+    pub query: String,
+    pub editing: bool,
+}
+
+impl LoaderVersionPicker {
+    pub fn visible(&self) -> Vec<&LoaderVersion> {
+        self.versions.iter().filter(|version| version.id.contains(self.query.trim())).collect()
+    }
+
+    pub fn is_latest(&self, version: &LoaderVersion) -> bool {
+        self.versions.first() == Some(version)
+    }
+}
+
+// The instance's Java is older than its Minecraft version needs.
+// `suggestions` are installations that would run it, closest fit first.
+#[derive(Debug, Clone)]
+pub struct JavaMismatch<'a> {
+    pub required: u32,
+    pub current: Option<&'a JavaInstallation>,
+    pub suggestions: Vec<&'a JavaInstallation>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CloneDialog {
+    pub source: Uuid,
+    pub name: String,
+    pub options: CloneOptions,
+}
+
+#[derive(Debug, Clone)]
+pub struct LaunchConflict {
+    pub instance_id: Uuid,
+    pub instance_name: String,
+    pub conflicts: Vec<DirectoryConflict>,
+    pub options: LaunchOptions,
+    pub return_state: AppState,
 }
 
 #[derive(Debug, Clone)]
@@ -34,6 +172,8 @@ pub enum Focus {
 
 
 
+const CONNECTIVITY_RETRY: Duration = Duration::from_secs(30);
+
 pub struct App {
     pub should_quit: bool,
     pub state: AppState,
@@ -47,59 +187,130 @@ pub struct App {
     pub version_manager: VersionManager,
     pub assets_manager: AssetsManager,
     pub auth_manager: AuthManager,
+    pub skin_cache: SkinCache,
     pub launch_manager: LaunchManager,
     pub mod_manager: ModManager,
     pub log_manager: LogManager,
-    pub current_motd: String,
-    pub current_profile: Option<String>,
-    pub profiles: HashMap<String, Profile>,
-    pub language: Language,
+    pub motd: Motd,
     pub data_dir: PathBuf,
     pub show_logs: bool,
+    pub log_scroll: usize,
+    pub log_view: LogView,
+    pub console: Option<(Uuid, SessionConsole)>,
+    pub console_input: Option<String>,
+    pub console_follow: bool,
     pub editing_instance_id: Option<Uuid>,
     pub show_installed_only: bool,
+    pub tick: u64,
+    pub last_crash: Option<CrashReport>,
+    pub bisect: Option<BisectSession>,
+    pub mojang_status: Option<MojangStatus>,
+    pub mod_browser: Option<ModBrowser>,
+    pub command_approval: Option<CommandApproval>,
+    pub account_picker: Option<AccountPicker>,
+    pub launch_conflict: Option<LaunchConflict>,
+    pub mod_updates: Option<(Uuid, Vec<ModUpdate>)>,
+    pub dependency_plan: Option<(Uuid, Vec<ResolvedDependency>)>,
+    pub modpack_update: Option<ModpackUpdate>,
+    pub dev_watchers: HashMap<Uuid, DirWatcher>,
+    pub dev_changes: HashMap<Uuid, usize>,
+    pub pending_relaunch: Option<Uuid>,
+    pub pack_browser: Option<PackBrowser>,
+    pub installed_mods: Option<(Uuid, ModManager)>,
+    mods_watcher: Option<DirWatcher>,
+    pub marked: HashSet<String>,
+    mark_scope: Option<AppState>,
+    pub group_input: Option<String>,
+    pub backup_manager: BackupManager,
+    pub backups: Vec<Backup>,
+    pub java_args_input: Option<String>,
+    pub events: EventBus,
+    pub notifier: Notifier,
+    launcher_events: tokio::sync::broadcast::Receiver<LauncherEvent>,
+    pub tasks: TaskRegistry,
+    pub java_ready: Readiness,
+    pub versions_ready: Readiness,
+    pub offline_mode: bool,
+    pub connectivity: Connectivity,
+    next_connectivity_check: Option<Instant>,
+    startup_complete: bool,
+    pub disk_usage: DiskUsage,
+    pub variants_instance: Option<Uuid>,
+    pub quick_play: Option<QuickPlayPicker>,
+    pub loader_picker: Option<LoaderVersionPicker>,
+    pub clone_dialog: Option<CloneDialog>,
+    pub instance_query: String,
+    pub instance_search_editing: bool,
+    pub version_filter: VersionFilter,
+    pub version_search_editing: bool,
+    pub wizard: Option<Wizard>,
+    pub news: Vec<PatchNote>,
+    pub news_ready: Readiness,
+    pub news_focus: Option<String>,
+    pub news_return: AppState,
+    pub error_hint: Option<UserHint>,
+    pub system_info: Option<SystemInfo>,
+    /// A newer launcher release, shown in the footer.
+    pub update: Option<Update>,
+    pub update_installed: bool,
 }
 
 impl App {
     pub async fn new() -> Result<Self> {
         let data_dir = crate::utils::get_data_dir()?;
-        std::fs::create_dir_all(&data_dir)?;
         
         let settings_manager = SettingsManager::new(data_dir.join("settings.toml"))?;
         let settings = settings_manager.get().clone();
+        let first_run = settings_manager.is_first_run();
+        crate::i18n::set_language(&settings.general.language);
         
-        let network_manager = NetworkManager::new(
-            data_dir.join("cache"),
-            settings.network.max_concurrent_downloads as usize
-        );
-        let java_manager = JavaManager::new(Some(settings.general.java_directory.clone()))?;
-        let instance_manager = InstanceManager::new(data_dir.join("instances"))?;
+        let network_manager = NetworkManager::new(data_dir.join("cache"), &settings.network)?;
+        let mut java_manager = JavaManager::new(Some(settings.general.java_directory.clone()))?;
+        java_manager.set_preferences(&settings.java);
+        let instance_manager = InstanceManager::new(settings.general.instances_directory.clone())?;
         let profile_manager = ProfileManager::new(data_dir.join("profiles"))?;
-        let version_manager = VersionManager::new(
-            data_dir.join("versions"), 
-            network_manager.clone(),
-            settings.network.max_concurrent_downloads as usize
-        )?;
+        let mut version_manager = VersionManager::new(data_dir.join("versions"), network_manager.clone())?;
+        version_manager.set_max_concurrent_verifications(settings.network.max_concurrent_verifications as usize);
         let log_manager = if settings.advanced.save_logs_to_file {
             LogManager::with_file_logging(
                 settings.advanced.console_max_lines as usize,
                 settings.advanced.logs_directory.clone(),
-                true
+                true,
+                settings.advanced.log_rotation()
             )
         } else {
             LogManager::new(settings.advanced.console_max_lines as usize)
         };
+        log_manager.set_max_bytes(settings.advanced.log_memory_limit_mb as usize * 1024 * 1024);
+        crate::logging::init(log_manager.clone(), &settings.advanced.log_level);
         
-        let assets_manager = AssetsManager::new(data_dir.join("assets"), network_manager.clone());
-        let auth_manager = AuthManager::new_with_file(data_dir.join("accounts.json"));
+        let mut assets_manager = AssetsManager::new(data_dir.join("assets"), network_manager.clone());
+        let shared = SharedSources::new(settings.general.shared_data_dir.clone());
+        version_manager.set_shared_sources(shared.clone());
+        assets_manager.set_shared(shared);
+        let notifier = Notifier::new();
+        log_manager.follow(notifier.subscribe());
+        version_manager.set_notifier(notifier.clone());
+        let mut auth_manager = AuthManager::new_with_file(data_dir.join("accounts.json"));
+        auth_manager.set_notifier(notifier.clone());
+        let skin_cache = SkinCache::new(data_dir.join("cache").join("heads"))?;
         let mut launch_manager = LaunchManager::new();
         launch_manager.set_log_manager(log_manager.clone());
+        launch_manager.set_notifier(notifier.clone());
         let mod_manager = ModManager::new(data_dir.join("mods"))?;
+        let backup_manager = BackupManager::new(data_dir.join("backups"))?;
+        let disk_usage = DiskUsage::new(StorageRoots {
+            assets: assets_manager.get_assets_dir().to_path_buf(),
+            versions: data_dir.join("versions"),
+            libraries: version_manager.get_libraries_dir(),
+            instances: settings.general.instances_directory.clone(),
+            cache: data_dir.join("cache"),
+        });
 
         Ok(Self {
             should_quit: false,
-            state: AppState::MainMenu,
-            current_state: "Загрузка...".to_string(),
+            state: if first_run { AppState::Wizard } else { AppState::MainMenu },
+            current_state: tr("status.loading").to_string(),
             focus: Focus::InstanceList,
             instance_manager,
             profile_manager,
@@ -109,261 +320,3066 @@ impl App {
             version_manager,
             assets_manager,
             auth_manager,
+            skin_cache,
             launch_manager,
             mod_manager,
             log_manager,
-            current_motd: "Добро пожаловать в MangoLauncher!".to_string(),
-            current_profile: None,
-            profiles: HashMap::new(),
-            language: settings.general.language.clone(),
+            motd: Motd::default(),
             data_dir,
             show_logs: false,
+            log_scroll: 0,
+            log_view: LogView::default(),
+            console: None,
+            console_input: None,
+            console_follow: true,
             editing_instance_id: None,
             show_installed_only: true,
+            tick: 0,
+            last_crash: None,
+            bisect: None,
+            mojang_status: None,
+            mod_browser: None,
+            command_approval: None,
+            account_picker: None,
+            launch_conflict: None,
+            mod_updates: None,
+            dependency_plan: None,
+            modpack_update: None,
+            dev_watchers: HashMap::new(),
+            dev_changes: HashMap::new(),
+            pending_relaunch: None,
+            pack_browser: None,
+            installed_mods: None,
+            mods_watcher: None,
+            marked: HashSet::new(),
+            mark_scope: None,
+            group_input: None,
+            backup_manager,
+            backups: Vec::new(),
+            java_args_input: None,
+            events: EventBus::new(),
+            launcher_events: notifier.subscribe(),
+            notifier,
+            tasks: TaskRegistry::default(),
+            java_ready: Readiness::Pending,
+            versions_ready: Readiness::Pending,
+            offline_mode: false,
+            connectivity: Connectivity::Unknown,
+            next_connectivity_check: None,
+            startup_complete: false,
+            disk_usage,
+            variants_instance: None,
+            quick_play: None,
+            loader_picker: None,
+            clone_dialog: None,
+            instance_query: String::new(),
+            instance_search_editing: false,
+            version_filter: VersionFilter::with_channels(settings.ui.version_channels()),
+            version_search_editing: false,
+            wizard: first_run.then(Wizard::new),
+            news: Vec::new(),
+            news_ready: Readiness::Pending,
+            news_focus: None,
+            news_return: AppState::MainMenu,
+            error_hint: None,
+            system_info: None,
+            update: None,
+            update_installed: false,
         })
     }
 
-    pub async fn init(&mut self) -> Result<()> {
-        self.log_launcher("Инициализация MangoLauncher...".to_string(), None);
-        
-        self.log_info("Сканирование Java...".to_string(), Some("JavaManager".to_string()));
-        if let Err(e) = self.scan_java_installations().await {
-            self.log_warning(format!("Java не найдена: {} (можно добавить вручную)", e), Some("JavaManager".to_string()));
-        }
-        
-        self.log_info("Загрузка списка версий Minecraft...".to_string(), Some("VersionManager".to_string()));
-        self.version_manager.load_versions().await?;
-        self.log_info(format!("Загружено {} версий", self.version_manager.get_versions().len()), Some("VersionManager".to_string()));
-        
-        self.current_state = "Готов".to_string();
-        self.log_launcher("Инициализация завершена".to_string(), None);
-        Ok(())
+    pub async fn init(&mut self) -> Result<()> {
+        self.log_launcher(tr("log.init").to_string(), None);
+        self.log_launcher(
+            trf("log.data_dir", &[("path", &crate::utils::data_dir().display()), ("source", &format!("{:?}", crate::utils::data_dir_source()))]),
+            None,
+        );
+        
+        self.recover_safe_mode_instances();
+
+        let watched: Vec<Uuid> = self.instance_manager.list_instances()
+            .iter()
+            .filter(|instance| instance.dev_watch)
+            .map(|instance| instance.id)
+            .collect();
+        for id in watched {
+            self.start_dev_watch(id);
+        }
+
+        let stale_sessions = LaunchManager::cleanup_stale_sessions(&self.data_dir);
+        if stale_sessions > 0 {
+            self.log_info(trf("log.stale_sessions_removed", &[("count", &stale_sessions)]), Some("LaunchManager".to_string()));
+        }
+
+        self.start_connectivity_check();
+        self.start_java_scan();
+        self.start_version_load(false);
+        self.start_head_refresh();
+        self.start_system_detection();
+        self.start_motd_load();
+
+        crate::updater::cleanup_previous();
+        if self.settings_manager.get().general.check_for_updates {
+            self.start_update_check();
+        }
+        Ok(())
+    }
+
+    fn begin_task(&mut self, kind: TaskKind) -> TaskReporter {
+        let id = self.tasks.begin(kind);
+        TaskReporter::new(id, self.events.sender())
+    }
+
+    pub fn start_java_scan(&mut self) {
+        if self.tasks.is_running(&TaskKind::ScanJava) {
+            return;
+        }
+        self.java_ready = Readiness::Pending;
+        self.log_info(tr("log.java_scan").to_string(), Some("JavaManager".to_string()));
+        let java_directory = self.settings_manager.get().general.java_directory.clone();
+        let java_settings = self.settings_manager.get().java.clone();
+        let reporter = self.begin_task(TaskKind::ScanJava);
+        tokio::spawn(async move {
+            let (manager, result) = match JavaManager::new(Some(java_directory)) {
+                Ok(mut manager) => {
+                    manager.set_preferences(&java_settings);
+                    let result = manager.scan_java_installations().await;
+                    (Some(manager), result)
+                }
+                Err(e) => (None, Err(e)),
+            };
+            reporter.send(AppEvent::JavaScanned { task: reporter.id(), manager, result });
+        });
+    }
+
+    pub fn start_version_load(&mut self, force: bool) {
+        if self.tasks.is_running(&TaskKind::LoadVersions) {
+            return;
+        }
+        self.versions_ready = Readiness::Pending;
+        if force {
+            self.log_info(tr("log.versions_refresh").to_string(), Some("VersionManager".to_string()));
+        } else {
+            self.log_info(tr("log.versions_loading").to_string(), Some("VersionManager".to_string()));
+        }
+        let (network, versions_dir) = self.version_manager.manifest_source();
+        let reporter = self.begin_task(TaskKind::LoadVersions);
+        tokio::spawn(async move {
+            let result = VersionManager::fetch_manifest(network.clone(), versions_dir, force).await;
+            let status = match &result {
+                Ok(loaded) if !loaded.stale => None,
+                _ => Some(MojangStatus::check(&network).await),
+            };
+            reporter.send(AppEvent::ManifestLoaded { task: reporter.id(), result, status });
+        });
+    }
+
+    pub fn start_head_refresh(&mut self) {
+        let accounts: Vec<Account> = self.auth_manager.list_accounts().into_iter().cloned().collect();
+        for account in accounts {
+            let cached = self.skin_cache.load_cached(&account);
+            if cached && self.skin_cache.is_fresh(&account) {
+                continue;
+            }
+            let network = self.network_manager.clone();
+            let cache_dir = self.skin_cache.cache_dir().to_path_buf();
+            let sender = self.events.sender();
+            tokio::spawn(async move {
+                let result = SkinCache::download(&network, &cache_dir, &account).await;
+                let _ = sender.send(AppEvent::HeadFetched { account: Box::new(account), result });
+            });
+        }
+    }
+
+    pub fn process_events(&mut self) -> bool {
+        let mut changed = false;
+        while let Some(event) = self.events.try_recv() {
+            changed = true;
+            match event {
+                AppEvent::JavaScanned { task, manager, result } => {
+                    self.tasks.finish(task);
+                    self.finish_java_scan(manager, result);
+                }
+                AppEvent::JavaDownloaded { task, version, result } => {
+                    self.tasks.finish(task);
+                    self.finish_java_download(version, result);
+                }
+                AppEvent::ManifestLoaded { task, result, status } => {
+                    self.tasks.finish(task);
+                    self.finish_version_load(result, status);
+                }
+                AppEvent::DiskUsageMeasured { target, bytes } => self.disk_usage.record(target, bytes),
+                AppEvent::TaskProgress { task, update } => self.tasks.update(task, update),
+                AppEvent::VersionDownloaded { task, version_id, result, assets_error, status } => {
+                    self.tasks.finish(task);
+                    self.finish_version_download(version_id, result, assets_error, status);
+                }
+                AppEvent::VersionVerified { task, version_id, result } => {
+                    self.tasks.finish(task);
+                    self.finish_version_verify(version_id, result);
+                }
+                AppEvent::NewsLoaded { task, result } => {
+                    self.tasks.finish(task);
+                    self.finish_news_load(result);
+                }
+                AppEvent::MotdLoaded { task, result } => {
+                    self.tasks.finish(task);
+                    self.finish_motd_load(result);
+                }
+                AppEvent::LoaderVersionsLoaded { task, loader, game_version, result } => {
+                    self.tasks.finish(task);
+                    self.finish_loader_versions(loader, game_version, result);
+                }
+                AppEvent::SystemDetected { task, info } => {
+                    self.tasks.finish(task);
+                    self.log_info(trf("log.system", &[("summary", &info.summary().join("; "))]), Some("Platform".to_string()));
+                    self.system_info = Some(*info);
+                }
+                AppEvent::LaunchStaged { task, instance_id, result } => {
+                    self.tasks.finish(task);
+                    self.finish_launch(instance_id, result);
+                }
+                AppEvent::ConnectivityChecked { task, connectivity } => {
+                    self.tasks.finish(task);
+                    self.finish_connectivity_check(connectivity);
+                }
+                AppEvent::UpdateChecked { task, result } => {
+                    self.tasks.finish(task);
+                    self.finish_update_check(result);
+                }
+                AppEvent::UpdateInstalled { task, result } => {
+                    self.tasks.finish(task);
+                    self.finish_update_install(result);
+                }
+                AppEvent::DependenciesChecked { task, instance_id, result } => {
+                    self.tasks.finish(task);
+                    self.finish_dependency_check(instance_id, result);
+                }
+                AppEvent::DependenciesInstalled { task, instance_id, result } => {
+                    self.tasks.finish(task);
+                    self.finish_dependency_install(instance_id, result);
+                }
+                AppEvent::CleanupDone { task, cleanup, result } => {
+                    self.tasks.finish(task);
+                    self.finish_cleanup(cleanup, result);
+                }
+                AppEvent::ModpackApplied { task, update, result } => {
+                    self.tasks.finish(task);
+                    self.finish_modpack_update(*update, result);
+                }
+                AppEvent::HeadFetched { account, result } => match result {
+                    Ok(head) => self.skin_cache.store(&account, head),
+                    Err(e) => self.log_warning(trf("log.avatar_failed", &[("name", &account.display_name), ("error", &e)]), Some("AuthManager".to_string())),
+                },
+            }
+        }
+        while let Ok(event) = self.launcher_events.try_recv() {
+            changed = true;
+            self.show_launcher_event(event);
+        }
+        changed
+    }
+
+    // Failures are left out: whoever hit them reports them with a hint.
+    fn show_launcher_event(&mut self, event: LauncherEvent) {
+        match event {
+            LauncherEvent::DownloadStarted { version_id } => {
+                self.current_state = trf("status.version_download_started", &[("version", &version_id)]);
+            }
+            LauncherEvent::LaunchStarted { name, .. } => {
+                self.current_state = trf("status.instance_started", &[("name", &name)]);
+            }
+            LauncherEvent::AccountAdded { name } => {
+                self.current_state = trf("status.account_added", &[("name", &name)]);
+            }
+            LauncherEvent::AccountRemoved { name } => {
+                self.current_state = trf("status.account_removed", &[("name", &name)]);
+            }
+            LauncherEvent::VersionInstalled { .. }
+            | LauncherEvent::VersionInstallFailed { .. }
+            | LauncherEvent::LaunchFailed { .. } => {}
+        }
+    }
+
+    pub fn open_storage(&mut self) {
+        self.state = AppState::Storage;
+        self.refresh_disk_usage();
+    }
+
+    // Gathers what the cleanup needs from the app, then runs it as a
+    // background task; duplicate assets are hashed one by one.
+    pub fn start_cleanup(&mut self, cleanup: Cleanup) -> Result<()> {
+        if self.tasks.active().iter().any(|task| matches!(task.kind, TaskKind::Cleanup(_))) {
+            return Err(crate::Error::Other("Wait for the running cleanup to finish".to_string()));
+        }
+        let versions_dir = self.version_manager.get_versions_dir().to_path_buf();
+        let libraries_dir = self.version_manager.get_libraries_dir();
+        let assets_dir = self.assets_manager.get_assets_dir().to_path_buf();
+        let job = match cleanup {
+            Cleanup::UnusedVersions => {
+                let mut used: HashSet<String> = self.instance_manager.list_instances()
+                    .iter()
+                    .map(|instance| instance.minecraft_version.clone())
+                    .collect();
+                used.extend(self.tasks.active().iter().filter_map(|task| match &task.kind {
+                    TaskKind::DownloadVersion(id) | TaskKind::VerifyVersion(id) => Some(id.clone()),
+                    _ => None,
+                }));
+                CleanupJob::Remove(crate::storage::unused_versions(&versions_dir, &used))
+            }
+            Cleanup::OrphanedLibraries => {
+                if self.tasks.active().iter().any(|task| matches!(task.kind, TaskKind::DownloadVersion(_) | TaskKind::VerifyVersion(_))) {
+                    return Err(crate::Error::Other("Wait for running downloads to finish".to_string()));
+                }
+                let referenced: HashSet<PathBuf> = self.version_manager.installed_details()
+                    .iter()
+                    .flat_map(|details| details.libraries.iter().flatten())
+                    .flat_map(|library| library.all_artifacts())
+                    .map(|artifact| libraries_dir.join(&artifact.path))
+                    .collect();
+                CleanupJob::OrphanedLibraries(libraries_dir, referenced)
+            }
+            Cleanup::StaleVirtualAssets => {
+                let used: HashSet<String> = self.version_manager.installed_details()
+                    .into_iter()
+                    .filter_map(|details| details.assets)
+                    .collect();
+                CleanupJob::Remove(crate::storage::stale_virtual_assets(&assets_dir, &used))
+            }
+            Cleanup::ExpiredCache => CleanupJob::ExpiredCache(self.network_manager.clone()),
+            Cleanup::DuplicateAssets => CleanupJob::DuplicateAssets(assets_dir),
+        };
+        let reporter = self.begin_task(TaskKind::Cleanup(cleanup));
+        tokio::spawn(async move {
+            let result = job.run(&reporter).await;
+            reporter.send(AppEvent::CleanupDone { task: reporter.id(), cleanup, result });
+        });
+        Ok(())
+    }
+
+    fn finish_cleanup(&mut self, cleanup: Cleanup, result: Result<CleanupResult>) {
+        let result = result.and_then(|result| {
+            if cleanup == Cleanup::UnusedVersions {
+                self.version_manager.prune_install_index()?;
+            }
+            Ok(result)
+        });
+        self.rescan_disk_usage();
+        match result {
+            Ok(result) => {
+                self.log_info(
+                    trf("log.cleanup", &[("kind", &format!("{:?}", cleanup)), ("count", &result.removed), ("size", &crate::utils::format_size(result.freed))]),
+                    Some("Storage".to_string()),
+                );
+                self.current_state = trf("status.cleanup_done", &[
+                    ("count", &result.removed),
+                    ("size", &crate::utils::format_size(result.freed)),
+                ]);
+            }
+            Err(e) => self.report_error(trf("status.error", &[("error", &e)]), &e),
+        }
+    }
+
+    pub fn rescan_disk_usage(&mut self) {
+        for category in StorageCategory::ALL {
+            self.disk_usage.invalidate(UsageTarget::Category(category));
+        }
+        for instance in self.instance_manager.list_instances() {
+            self.disk_usage.invalidate(UsageTarget::Instance(instance.id));
+        }
+        self.refresh_disk_usage();
+    }
+
+    pub fn refresh_disk_usage(&mut self) {
+        let instance_ids: Vec<Uuid> = self.instance_manager.list_instances().iter().map(|i| i.id).collect();
+        for target in self.disk_usage.stale_targets(&instance_ids) {
+            let path = self.disk_usage.begin_scan(target);
+            let roots = self.disk_usage.roots().clone();
+            let sender = self.events.sender();
+            tokio::task::spawn_blocking(move || {
+                let bytes = crate::storage::measure(target, &path, &roots);
+                let _ = sender.send(AppEvent::DiskUsageMeasured { target, bytes });
+            });
+        }
+    }
+
+    fn finish_java_scan(&mut self, manager: Option<JavaManager>, result: Result<()>) {
+        if let Some(manager) = manager {
+            self.java_manager = manager;
+        }
+        match result {
+            Ok(()) => {
+                let count = self.java_manager.get_installations().len();
+                self.log_info(trf("log.java_found", &[("count", &count)]), Some("JavaManager".to_string()));
+                self.java_ready = Readiness::Ready;
+                if self.startup_complete {
+                    self.current_state = plural("plural.java_found", count as u64, &[]);
+                }
+            }
+            Err(e) => {
+                self.log_warning(trf("log.java_not_found", &[("error", &e)]), Some("JavaManager".to_string()));
+                self.java_ready = Readiness::Failed(e.to_string());
+                if self.startup_complete {
+                    self.report_error(trf("status.java_scan_failed", &[("error", &e)]), &e);
+                }
+            }
+        }
+        self.report_startup_progress();
+    }
+
+    fn finish_version_load(&mut self, result: anyhow::Result<LoadedManifest>, status: Option<MojangStatus>) {
+        let health = status.map(|status| self.record_mojang_status(status));
+        self.set_offline_mode(health == Some(ServiceHealth::Offline));
+        match result {
+            Ok(loaded) => {
+                let stale = loaded.stale;
+                self.version_manager.apply_manifest(loaded);
+                self.log_info(trf("log.versions_loaded", &[("count", &self.version_manager.get_versions().len())]), Some("VersionManager".to_string()));
+                self.versions_ready = Readiness::Ready;
+                if self.startup_complete {
+                    self.current_state = tr("status.versions_updated").to_string();
+                }
+                self.report_startup_progress();
+                if stale {
+                    self.current_state = match health {
+                        Some(ServiceHealth::Offline) => tr("status.offline_cached").to_string(),
+                        _ => tr("status.mojang_down_cached").to_string(),
+                    };
+                }
+            }
+            Err(e) => {
+                let error = match health {
+                    Some(health) => self.explain_with_health(e.into(), health),
+                    None => e.into(),
+                };
+                self.log_error(trf("log.versions_failed", &[("error", &error)]), Some("VersionManager".to_string()));
+                self.report_error(trf("status.version_list_failed", &[("error", &error)]), &error);
+                self.versions_ready = Readiness::Failed(error.to_string());
+            }
+        }
+    }
+
+    fn report_startup_progress(&mut self) {
+        if self.startup_complete || self.java_ready.is_pending() || self.versions_ready.is_pending() {
+            return;
+        }
+        self.startup_complete = true;
+        if self.versions_ready == Readiness::Ready {
+            self.current_state = tr("status.ready").to_string();
+        }
+        self.log_launcher(tr("log.init_done").to_string(), None);
+    }
+
+    pub fn get_instances(&self) -> Vec<&Instance> {
+        self.instance_manager.list_instances()
+    }
+
+    pub fn instance_sort(&self) -> InstanceSort {
+        InstanceSort::parse(&self.settings_manager.get().ui.sort_mode)
+    }
+
+    pub fn displayed_instances(&self) -> Vec<&Instance> {
+        let mut instances: Vec<&Instance> = self.instance_manager.list_instances()
+            .into_iter()
+            .filter(|instance| instance.matches_query(&self.instance_query))
+            .collect();
+        self.instance_sort().sort(&mut instances);
+        instances
+    }
+
+    /// The last played instances, newest first. Ones never played are left
+    /// out: the instance list is the place to find those.
+    pub fn recent_instances(&self) -> Vec<&Instance> {
+        let mut instances: Vec<&Instance> = self.instance_manager.list_instances()
+            .into_iter()
+            .filter(|instance| instance.last_played.is_some())
+            .collect();
+        InstanceSort::LastPlayed.sort(&mut instances);
+        instances.truncate(RECENT_INSTANCES);
+        instances
+    }
+
+    pub fn cycle_instance_sort(&mut self) -> Result<InstanceSort> {
+        let sort = self.instance_sort().next();
+        self.update_setting(SettingField::SortMode, SettingValue::Text(sort.key().to_string()))?;
+        Ok(sort)
+    }
+
+    pub fn create_instance(&mut self, name: String, version: String) -> Result<Uuid> {
+        self.log_info(trf("log.instance_creating", &[("name", &name), ("version", &version)]), Some("InstanceManager".to_string()));
+        match self.instance_manager.create_instance(name.clone(), version.clone()) {
+            Ok(id) => {
+                self.log_info(trf("log.instance_created", &[("name", &name)]), Some("InstanceManager".to_string()));
+                Ok(id)
+            }
+            Err(e) => {
+                self.log_error(trf("log.instance_create_failed", &[("name", &name), ("error", &e)]), Some("InstanceManager".to_string()));
+                Err(e)
+            }
+        }
+    }
+
+    pub fn sync_marks(&mut self) {
+        if self.mark_scope.as_ref() != Some(&self.state) {
+            self.marked.clear();
+            self.mark_scope = Some(self.state.clone());
+        }
+    }
+
+    pub fn mark_key(&self, index: usize) -> Option<String> {
+        match self.state {
+            AppState::InstanceList => self.displayed_instances().get(index).map(|i| i.id.to_string()),
+            AppState::Launcher => self.get_displayed_versions().get(index).map(|v| v.id.clone()),
+            AppState::ModList => self.installed_mod_list().get(index).map(|m| m.filename.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn toggle_mark(&mut self, index: usize) -> bool {
+        self.sync_marks();
+        let Some(key) = self.mark_key(index) else {
+            return false;
+        };
+        if !self.marked.remove(&key) {
+            self.marked.insert(key);
+        }
+        true
+    }
+
+    fn marked_or_selected(&self, selected: Option<usize>) -> Vec<String> {
+        if self.marked.is_empty() {
+            selected.and_then(|index| self.mark_key(index)).into_iter().collect()
+        } else {
+            let mut keys: Vec<String> = self.marked.iter().cloned().collect();
+            keys.sort();
+            keys
+        }
+    }
+
+    pub fn delete_marked_instances(&mut self, selected: Option<usize>) -> Result<usize> {
+        let ids: Vec<Uuid> = self.marked_or_selected(selected)
+            .iter()
+            .filter_map(|key| Uuid::parse_str(key).ok())
+            .collect();
+        for id in &ids {
+            self.delete_instance(*id)?;
+        }
+        self.marked.clear();
+        Ok(ids.len())
+    }
+
+    pub fn assign_group_to_marked(&mut self, selected: Option<usize>, group: &str) -> Result<usize> {
+        let group = Some(group.trim()).filter(|g| !g.is_empty());
+        let ids: Vec<Uuid> = self.marked_or_selected(selected)
+            .iter()
+            .filter_map(|key| Uuid::parse_str(key).ok())
+            .collect();
+        for id in &ids {
+            self.instance_manager.set_instance_group(*id, group)?;
+        }
+        match group {
+            Some(group) => self.log_info(trf("log.instances_grouped", &[("group", &group), ("count", &ids.len())]), Some("InstanceManager".to_string())),
+            None => self.log_info(trf("log.instances_ungrouped", &[("count", &ids.len())]), Some("InstanceManager".to_string())),
+        }
+        self.marked.clear();
+        Ok(ids.len())
+    }
+
+    pub fn start_marked_downloads(&mut self, selected: Option<usize>) -> Result<usize> {
+        let versions: Vec<String> = self.marked_or_selected(selected)
+            .into_iter()
+            .filter(|id| !self.version_manager.is_version_installed(id))
+            .filter(|id| !self.tasks.is_running(&TaskKind::DownloadVersion(id.clone())))
+            .collect();
+        self.marked.clear();
+
+        let mut queue = Vec::new();
+        for version_id in &versions {
+            let version = self.find_version(version_id)?;
+            self.log_info(trf("log.version_queued", &[("version", &version_id)]), Some("VersionManager".to_string()));
+            queue.push((version, self.begin_task(TaskKind::DownloadVersion(version_id.clone()))));
+        }
+        let job = self.version_download_job();
+        tokio::spawn(async move {
+            for (version, reporter) in queue {
+                job.run(version, reporter).await;
+            }
+        });
+        Ok(versions.len())
+    }
+
+    pub fn open_installed_mods(&mut self, instance_id: Uuid) -> Result<()> {
+        let manager = ModManager::new(self.instance_mods_dir(instance_id))?;
+        self.installed_mods = Some((instance_id, manager));
+        self.mods_watcher = Some(DirWatcher::new(self.instance_manager.game_dir(instance_id), &["mods"]));
+        self.state = AppState::ModList;
+        Ok(())
+    }
+
+    pub fn close_installed_mods(&mut self) {
+        self.installed_mods = None;
+        self.mods_watcher = None;
+    }
+
+    // Picks up jars copied in or removed by a file manager while the mod
+    // list is on screen. The launcher's own changes reset the watcher first.
+    fn poll_mods_watcher(&mut self) -> bool {
+        if self.state != AppState::ModList {
+            return false;
+        }
+        let Some(changes) = self.mods_watcher.as_mut().and_then(|watcher| watcher.poll()) else {
+            return false;
+        };
+        let Some((_, manager)) = self.installed_mods.as_mut() else {
+            return false;
+        };
+        match manager.scan_mods() {
+            Ok(()) => {
+                self.log_info(trf("log.mods_changed", &[("count", &changes.len())]), Some("ModManager".to_string()));
+                self.current_state = trf("status.mods_rescanned", &[("count", &changes.len())]);
+            }
+            Err(e) => self.log_warning(trf("log.mods_rescan_failed", &[("error", &e)]), Some("ModManager".to_string())),
+        }
+        true
+    }
+
+    fn reset_mods_watcher(&mut self) {
+        if let Some(watcher) = self.mods_watcher.as_mut() {
+            watcher.reset();
+        }
+    }
+
+    pub fn installed_mod_list(&self) -> Vec<&Mod> {
+        let Some((_, manager)) = &self.installed_mods else {
+            return Vec::new();
+        };
+        let mut mods = manager.list_mods();
+        mods.sort_by_key(|m| m.filename.to_lowercase());
+        mods
+    }
+
+    pub fn toggle_installed_mod(&mut self, index: usize) -> Result<bool> {
+        let (id, enabled) = self.installed_mod_list()
+            .get(index)
+            .map(|m| (m.id, m.enabled))
+            .ok_or_else(|| crate::Error::Mod("No mod selected".to_string()))?;
+        let (_, manager) = self.installed_mods.as_mut()
+            .ok_or_else(|| crate::Error::Mod("Mod list is not open".to_string()))?;
+        if enabled {
+            manager.disable_mod(id)?;
+        } else {
+            manager.enable_mod(id)?;
+        }
+        self.reset_mods_watcher();
+        Ok(!enabled)
+    }
+
+    fn marked_mod_ids(&self, selected: Option<usize>) -> Vec<Uuid> {
+        let files = self.marked_or_selected(selected);
+        self.installed_mod_list()
+            .into_iter()
+            .filter(|m| files.contains(&m.filename))
+            .map(|m| m.id)
+            .collect()
+    }
+
+    pub fn set_marked_mods_enabled(&mut self, selected: Option<usize>, enabled: bool) -> Result<usize> {
+        let ids = self.marked_mod_ids(selected);
+        let (_, manager) = self.installed_mods.as_mut()
+            .ok_or_else(|| crate::Error::Mod("Mod list is not open".to_string()))?;
+        for id in &ids {
+            if enabled {
+                manager.enable_mod(*id)?;
+            } else {
+                manager.disable_mod(*id)?;
+            }
+        }
+        self.reset_mods_watcher();
+        self.log_info(
+            trf(if enabled { "log.mods_enabled" } else { "log.mods_disabled" }, &[("count", &ids.len())]),
+            Some("ModManager".to_string()),
+        );
+        Ok(ids.len())
+    }
+
+    pub fn delete_marked_mods(&mut self, selected: Option<usize>) -> Result<usize> {
+        let ids = self.marked_mod_ids(selected);
+        let (_, manager) = self.installed_mods.as_mut()
+            .ok_or_else(|| crate::Error::Mod("Mod list is not open".to_string()))?;
+        for id in &ids {
+            manager.delete_mod(*id)?;
+        }
+        self.reset_mods_watcher();
+        self.log_warning(trf("log.mods_removed", &[("count", &ids.len())]), Some("ModManager".to_string()));
+        self.marked.clear();
+        Ok(ids.len())
+    }
+
+    pub fn find_instance(&self, selector: &str) -> Option<&Instance> {
+        self.instance_manager.list_instances()
+            .into_iter()
+            .find(|i| i.id.to_string() == selector || i.name.eq_ignore_ascii_case(selector))
+    }
+
+    pub fn find_account(&self, selector: &str) -> Option<&Account> {
+        self.auth_manager.list_accounts()
+            .into_iter()
+            .find(|a| a.id.to_string() == selector || a.username.eq_ignore_ascii_case(selector) || a.display_name.eq_ignore_ascii_case(selector))
+    }
+
+    pub fn open_clone_dialog(&mut self, id: Uuid) {
+        let Some(instance) = self.instance_manager.get_instance(id) else { return };
+        self.clone_dialog = Some(CloneDialog {
+            source: id,
+            name: trf("instances.copy_name", &[("name", &instance.name)]),
+            options: CloneOptions::default(),
+        });
+        self.state = AppState::CloneInstance;
+    }
+
+    pub fn confirm_clone(&mut self) -> Result<Uuid> {
+        let dialog = self.clone_dialog.clone()
+            .ok_or_else(|| crate::Error::Instance("Nothing to clone".to_string()))?;
+        let source_name = self.instance_manager.get_instance(dialog.source)
+            .map(|instance| instance.name.clone())
+            .unwrap_or_default();
+        match self.instance_manager.clone_instance(dialog.source, dialog.name.clone(), &dialog.options) {
+            Ok(id) => {
+                self.log_info(trf("log.instance_cloned", &[("name", &source_name), ("clone", &dialog.name)]), Some("InstanceManager".to_string()));
+                self.clone_dialog = None;
+                self.state = AppState::InstanceList;
+                Ok(id)
+            }
+            Err(e) => {
+                self.log_error(trf("log.instance_clone_failed", &[("name", &source_name), ("error", &e)]), Some("InstanceManager".to_string()));
+                Err(e)
+            }
+        }
+    }
+
+    pub fn delete_instance(&mut self, id: Uuid) -> Result<()> {
+        if let Some(instance) = self.instance_manager.get_instance(id) {
+            let name = instance.name.clone();
+            if let Err(e) = self.backup_instance(id, BackupReason::Delete) {
+                self.log_error(trf("log.instance_delete_no_backup", &[("name", &name)]), Some("InstanceManager".to_string()));
+                return Err(e);
+            }
+            self.log_warning(trf("log.instance_deleting", &[("name", &name)]), Some("InstanceManager".to_string()));
+            match self.instance_manager.delete_instance(id) {
+                Ok(_) => {
+                    self.dev_watchers.remove(&id);
+                    self.dev_changes.remove(&id);
+                    self.disk_usage.forget_instance(id);
+                    self.log_info(trf("log.instance_deleted", &[("name", &name)]), Some("InstanceManager".to_string()));
+                    Ok(())
+                }
+                Err(e) => {
+                    self.log_error(trf("log.instance_delete_failed", &[("name", &name), ("error", &e)]), Some("InstanceManager".to_string()));
+                    Err(e)
+                }
+            }
+        } else {
+            self.log_error(tr("log.instance_delete_missing").to_string(), Some("InstanceManager".to_string()));
+            Err(crate::Error::Other("Instance not found".to_string()))
+        }
+    }
+
+    pub fn launch_instance(&mut self, id: Uuid) -> Result<()> {
+        self.launch_instance_with(id, LaunchOptions::default())
+    }
+
+    pub fn launch_instance_safe_mode(&mut self, id: Uuid) -> Result<()> {
+        self.launch_instance_with(id, LaunchOptions { safe_mode: true, ..LaunchOptions::default() })
+    }
+
+    pub fn launch_instance_variant(&mut self, id: Uuid, variant: Option<String>) -> Result<()> {
+        self.launch_instance_with(id, LaunchOptions { variant, ..LaunchOptions::default() })
+    }
+
+    pub fn launch_instance_with(&mut self, id: Uuid, mut options: LaunchOptions) -> Result<()> {
+        if self.tasks.is_running(&TaskKind::Launch(id)) {
+            return Err(crate::Error::Launch("Instance is already being launched".to_string()));
+        }
+        // Two games writing to the same saves and options would clobber each
+        // other; other instances can run alongside.
+        if self.launch_manager.is_running(id) {
+            return Err(crate::Error::Launch("Instance is already running".to_string()));
+        }
+        let variant = match options.variant.as_deref() {
+            Some(name) => Some(
+                self.instance_manager.get_instance(id)
+                    .and_then(|instance| instance.launch_variant(name))
+                    .cloned()
+                    .ok_or_else(|| crate::Error::Launch(format!("Launch variant '{}' not found", name)))?,
+            ),
+            None => None,
+        };
+        // Global variables first, then the instance's own, then the variant's.
+        options.env = self.settings_manager.get().advanced.environment_variables.iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        if let Some(instance) = self.instance_manager.get_instance(id) {
+            options.env.extend(instance.env.clone());
+        }
+        if let Some(variant) = &variant {
+            if variant.wrapper_command.is_some() {
+                options.wrapper_command = variant.wrapper_command.clone();
+            }
+            options.env.extend(variant.env.clone());
+        }
+        if options.wrapper_command.is_none() {
+            options.wrapper_command = self.profile_manager.get_active_profile()
+                .and_then(|profile| profile.wrapper_command.clone())
+                .or_else(|| self.settings_manager.get().minecraft.wrapper_command.clone());
+        }
+        options.legacy_resource_proxy = self.settings_manager.get().minecraft.legacy_resource_proxy.clone();
+        options.game_args = self.profile_manager.get_active_profile()
+            .and_then(|profile| profile.game_args.as_deref())
+            .map(|args| args.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default();
+
+        if let Some(mut instance) = self.instance_manager.get_instance(id).cloned() {
+            let instance_name = instance.name.clone();
+            if let Some(variant) = &variant {
+                instance.apply_variant(variant);
+            }
+            instance.apply_inherited(self.profile_manager.get_active_profile(), self.settings_manager.get());
+            if options.demo {
+                instance.demo_mode = true;
+            }
+            if options.quick_play.is_some() {
+                instance.quick_play = options.quick_play.clone();
+            }
+
+            if !options.skip_commands {
+                let commands = instance.unapproved_commands();
+                if !commands.is_empty() {
+                    self.log_warning(
+                        trf("log.launch_needs_approval", &[("name", &instance_name)]),
+                        Some("LaunchManager".to_string()),
+                    );
+                    self.current_state = tr("status.approve_pack_commands").to_string();
+                    self.command_approval = Some(CommandApproval {
+                        instance_id: id,
+                        instance_name,
+                        commands,
+                        options,
+                        return_state: self.state.clone(),
+                    });
+                    self.state = AppState::CommandApproval;
+                    return Ok(());
+                }
+                options.pre_launch_command = instance.approved_command(LaunchHook::PreLaunch);
+                options.post_exit_command = instance.approved_command(LaunchHook::PostExit);
+            }
+
+            if !options.allow_shared_directories {
+                let game_dir = self.instance_manager.game_dir(id);
+                let conflicts = self.launch_manager.directory_conflicts(&game_dir);
+                if !conflicts.is_empty() {
+                    for conflict in &conflicts {
+                        self.log_warning(
+                            trf("log.directory_in_use", &[("path", &conflict.directory.display()), ("name", &conflict.instance_name)]),
+                            Some("LaunchManager".to_string()),
+                        );
+                    }
+                    self.current_state = tr("status.directory_in_use").to_string();
+                    self.launch_conflict = Some(LaunchConflict {
+                        instance_id: id,
+                        instance_name,
+                        conflicts,
+                        options,
+                        return_state: self.state.clone(),
+                    });
+                    self.state = AppState::LaunchConflict;
+                    return Ok(());
+                }
+            }
+
+            if options.account.is_none() && self.settings_manager.get().general.account_picker {
+                let accounts: Vec<Uuid> = self.auth_manager.list_accounts().iter().map(|account| account.id).collect();
+                if accounts.len() > 1 {
+                    // The account this instance was last played with is
+                    // preselected, so relaunching with it is a single Enter.
+                    let focus = instance.last_account
+                        .or_else(|| self.auth_manager.get_default_account().map(|account| account.id))
+                        .and_then(|preferred| accounts.iter().position(|account| *account == preferred));
+                    self.account_picker = Some(AccountPicker {
+                        instance_id: id,
+                        instance_name,
+                        accounts,
+                        focus: Some(focus.unwrap_or(0)),
+                        options,
+                        return_state: self.state.clone(),
+                    });
+                    self.state = AppState::AccountPicker;
+                    return Ok(());
+                }
+            }
+
+            self.current_state = trf("status.launching", &[("name", &instance_name)]);
+            if options.safe_mode {
+                self.log_info(trf("log.launching_safe", &[("name", &instance_name)]), Some("LaunchManager".to_string()));
+            } else if let Some(variant) = &variant {
+                self.log_info(trf("log.launching_variant", &[("name", &instance_name), ("variant", &variant.name)]), Some("LaunchManager".to_string()));
+            } else {
+                self.log_info(trf("log.launching", &[("name", &instance_name)]), Some("LaunchManager".to_string()));
+            }
+            
+            if !self.version_manager.is_version_installed(&instance.minecraft_version) {
+                self.current_state = trf("status.version_not_installed", &[("version", &instance.minecraft_version)]);
+                self.log_error(trf("log.version_not_installed", &[("version", &instance.minecraft_version), ("name", &instance_name)]), Some("LaunchManager".to_string()));
+                return Err(crate::Error::Other(format!("Version {} not installed", instance.minecraft_version)));
+            }
+            
+            let account = match options.account {
+                Some(account_id) => self.auth_manager.get_account(account_id)
+                    .ok_or_else(|| crate::Error::Auth("The chosen account no longer exists".to_string()))?,
+                None => self.launch_account_for(&instance)
+                    .ok_or_else(|| crate::Error::Auth("No default account set".to_string()))?,
+            };
+            if account.account_type == AccountType::Microsoft && !account.is_token_valid() && !account.needs_refresh() {
+                return Err(crate::Error::AuthExpired { account: account.display_name.clone() });
+            }
+            if self.offline_mode && account.account_type != AccountType::Offline {
+                self.log_warning(
+                    trf("log.offline_session", &[("name", &account.username)]),
+                    Some("LaunchManager".to_string()),
+                );
+            }
+            
+            if self.java_ready.is_pending() && self.instance_java(&instance).is_none() {
+                return Err(crate::Error::Java("Java scan is still running".to_string()));
+            }
+            let java = self.instance_java(&instance).ok_or_else(|| crate::Error::JavaMissing {
+                required: self.version_manager.get_version_details(&instance.minecraft_version).ok()
+                    .and_then(|details| details.java_version)
+                    .map(|version| version.major_version.max(0) as u32),
+            })?;
+
+            if let Some(warning) = crate::platform::natives_warning() {
+                self.log_warning(warning, Some("LaunchManager".to_string()));
+            }
+            let java_arch = crate::platform::Arch::parse(&java.architecture);
+            if java_arch != crate::platform::Arch::current() {
+                self.log_warning(
+                    trf("log.java_arch_mismatch", &[("version", &java.version), ("java_arch", &java.architecture), ("arch", &std::env::consts::ARCH)]),
+                    Some("LaunchManager".to_string()),
+                );
+            }
+            
+            let prepared = match self.launch_manager.prepare_launch(&instance, account, java, &self.version_manager, &self.data_dir, &options) {
+                Ok(prepared) => prepared,
+                Err(e) => {
+                    self.report_error(trf("status.instance_launch_failed", &[("name", &instance_name), ("error", &e)]), &e);
+                    self.log_error(trf("log.launch_failed", &[("name", &instance_name), ("error", &e)]), Some("LaunchManager".to_string()));
+                    return Err(e);
+                }
+            };
+            let reporter = self.begin_task(TaskKind::Launch(id));
+            let span = tracing::info_span!("stage_launch", instance = %instance_name);
+            tokio::spawn(async move {
+                let result = prepared.stage().await.map(|()| Box::new(prepared));
+                reporter.send(AppEvent::LaunchStaged { task: reporter.id(), instance_id: id, result });
+            }.instrument(span));
+        } else {
+            return Err(crate::Error::Instance("Instance not found".to_string()));
+        }
+        Ok(())
+    }
+
+    fn finish_launch(&mut self, instance_id: Uuid, result: Result<Box<PreparedLaunch>>) {
+        let instance_name = self.instance_manager.get_instance(instance_id)
+            .map(|instance| instance.name.clone())
+            .unwrap_or_default();
+        let account_id = result.as_ref().ok().map(|prepared| prepared.account_id());
+        let result = result.inspect_err(|e| {
+            self.notifier.emit(LauncherEvent::LaunchFailed { instance_id, name: instance_name.clone(), error: e.to_string() });
+        });
+        match result.and_then(|prepared| self.launch_manager.start_prepared(*prepared)) {
+            Ok(()) => {
+                if let Some(account_id) = account_id {
+                    self.record_account_use(instance_id, account_id);
+                }
+                if let Some(watcher) = self.dev_watchers.get_mut(&instance_id) {
+                    watcher.reset();
+                }
+            }
+            Err(e) => {
+                if let Some(session) = self.bisect.as_mut().filter(|s| s.instance_id == instance_id && s.running) {
+                    session.running = false;
+                }
+                self.report_error(trf("status.instance_launch_failed", &[("name", &instance_name), ("error", &e)]), &e);
+            }
+        }
+    }
+
+    fn find_version(&self, version_id: &str) -> Result<MinecraftVersion> {
+        self.version_manager.get_versions()
+            .iter()
+            .find(|v| v.id == version_id)
+            .cloned()
+            .ok_or_else(|| crate::Error::Version(format!("Version {} not found", version_id)))
+    }
+
+    fn version_download_job(&self) -> VersionDownloadJob {
+        VersionDownloadJob {
+            installer: self.version_manager.installer(),
+            network: self.network_manager.clone(),
+            assets_dir: self.assets_manager.get_assets_dir().to_path_buf(),
+            shared: SharedSources::new(self.settings_manager.get().general.shared_data_dir.clone()),
+        }
+    }
+
+    pub fn start_version_download(&mut self, version_id: &str) -> Result<()> {
+        let kind = TaskKind::DownloadVersion(version_id.to_string());
+        if self.tasks.is_running(&kind) {
+            return Err(crate::Error::Version(format!("Version {} is already downloading", version_id)));
+        }
+        let version = self.find_version(version_id)?;
+        let job = self.version_download_job();
+        let reporter = self.begin_task(kind);
+        tokio::spawn(async move {
+            job.run(version, reporter).await;
+        });
+        Ok(())
+    }
+
+    pub fn start_version_verify(&mut self, version_id: &str) -> Result<()> {
+        let kind = TaskKind::VerifyVersion(version_id.to_string());
+        if self.tasks.is_running(&kind) || self.tasks.is_running(&TaskKind::DownloadVersion(version_id.to_string())) {
+            return Err(crate::Error::Version(format!("Version {} is busy", version_id)));
+        }
+        self.log_info(trf("log.verifying_version", &[("version", &version_id)]), Some("VersionManager".to_string()));
+        let installer = self.version_manager.installer();
+        let assets_dir = self.assets_manager.get_assets_dir().to_path_buf();
+        let listed = self.version_manager.get_versions().iter().find(|version| version.id == version_id).cloned();
+        let version_id = version_id.to_string();
+        let reporter = self.begin_task(kind);
+        tokio::spawn(async move {
+            let result = installer.verify(&version_id, listed.as_ref(), &assets_dir, &reporter).await.map_err(crate::Error::from);
+            reporter.send(AppEvent::VersionVerified { task: reporter.id(), version_id, result });
+        });
+        Ok(())
+    }
+
+    pub fn start_system_detection(&mut self) {
+        if self.tasks.is_running(&TaskKind::DetectSystem) {
+            return;
+        }
+        let reporter = self.begin_task(TaskKind::DetectSystem);
+        tokio::spawn(async move {
+            let info = tokio::task::spawn_blocking(SystemInfo::detect).await
+                .unwrap_or_else(|_| SystemInfo::detect_basic());
+            reporter.send(AppEvent::SystemDetected { task: reporter.id(), info: Box::new(info) });
+        });
+    }
+
+    pub fn open_news(&mut self, version: Option<String>) {
+        self.news_return = self.state.clone();
+        self.news_focus = version;
+        self.state = AppState::News;
+        if self.news.is_empty() {
+            self.start_news_load();
+        }
+    }
+
+    pub fn start_news_load(&mut self) {
+        if self.tasks.is_running(&TaskKind::LoadNews) {
+            return;
+        }
+        self.news_ready = Readiness::Pending;
+        self.log_info(tr("log.news_loading").to_string(), Some("News".to_string()));
+        let network = self.network_manager.clone();
+        let reporter = self.begin_task(TaskKind::LoadNews);
+        tokio::spawn(async move {
+            let result = crate::news::fetch_patch_notes(&network).await;
+            reporter.send(AppEvent::NewsLoaded { task: reporter.id(), result });
+        });
+    }
+
+    fn finish_news_load(&mut self, result: Result<Vec<PatchNote>>) {
+        match result {
+            Ok(entries) => {
+                self.log_info(trf("log.news_loaded", &[("count", &entries.len())]), Some("News".to_string()));
+                self.news = entries;
+                self.news_ready = Readiness::Ready;
+            }
+            Err(e) => {
+                self.log_warning(trf("log.news_failed", &[("error", &e)]), Some("News".to_string()));
+                self.report_error(trf("status.news_failed", &[("error", &e)]), &e);
+                self.news_ready = Readiness::Failed(e.to_string());
+            }
+        }
+    }
+
+    /// Fetches the MOTD from `ui.motd_url`; without one only the built-in
+    /// tips are shown.
+    pub fn start_motd_load(&mut self) {
+        let Some(url) = self.settings_manager.get().ui.motd_url.clone() else {
+            self.motd.set_remote(RemoteMotd::default());
+            return;
+        };
+        if self.tasks.is_running(&TaskKind::LoadMotd) {
+            return;
+        }
+        let network = self.network_manager.clone();
+        let reporter = self.begin_task(TaskKind::LoadMotd);
+        tokio::spawn(async move {
+            let result = crate::motd::fetch(&network, &url).await;
+            reporter.send(AppEvent::MotdLoaded { task: reporter.id(), result });
+        });
+    }
+
+    fn finish_motd_load(&mut self, result: Result<RemoteMotd>) {
+        match result {
+            Ok(motd) => {
+                self.log_info(trf("log.motd_loaded", &[("messages", &motd.messages.len()), ("art", &motd.art.len())]), Some("Motd".to_string()));
+                self.motd.set_remote(motd);
+            }
+            Err(e) => self.log_warning(trf("log.motd_failed", &[("error", &e)]), Some("Motd".to_string())),
+        }
+    }
+
+    /// Probes Mojang's servers in the background; also what F5 does.
+    pub fn start_connectivity_check(&mut self) {
+        if self.tasks.is_running(&TaskKind::CheckConnectivity) {
+            return;
+        }
+        self.next_connectivity_check = None;
+        let network = self.network_manager.clone();
+        let reporter = self.begin_task(TaskKind::CheckConnectivity);
+        tokio::spawn(async move {
+            let connectivity = Connectivity::probe(&network).await;
+            reporter.send(AppEvent::ConnectivityChecked { task: reporter.id(), connectivity });
+        });
+    }
+
+    fn finish_connectivity_check(&mut self, connectivity: Connectivity) {
+        let reconnected = self.set_offline_mode(connectivity == Connectivity::Offline);
+        // What failed while offline is fetched again now.
+        if reconnected && !matches!(self.versions_ready, Readiness::Ready) {
+            self.start_version_load(false);
+        }
+    }
+
+    // Offline, the launcher keeps probing in the background and switches
+    // back by itself once the connection returns. Returns whether it did.
+    fn set_offline_mode(&mut self, offline: bool) -> bool {
+        let reconnected = self.offline_mode && !offline;
+        if offline && !self.offline_mode {
+            self.log_warning(tr("log.offline").to_string(), Some("Network".to_string()));
+        } else if reconnected {
+            self.log_info(tr("log.online").to_string(), Some("Network".to_string()));
+        }
+        self.offline_mode = offline;
+        self.connectivity = if offline { Connectivity::Offline } else { Connectivity::Online };
+        self.next_connectivity_check = offline.then(|| Instant::now() + CONNECTIVITY_RETRY);
+        reconnected
+    }
+
+    pub fn start_update_check(&mut self) {
+        if self.tasks.is_running(&TaskKind::CheckUpdate) {
+            return;
+        }
+        let network = self.network_manager.clone();
+        let reporter = self.begin_task(TaskKind::CheckUpdate);
+        tokio::spawn(async move {
+            let result = crate::updater::check(&network).await.map(|update| update.map(Box::new));
+            reporter.send(AppEvent::UpdateChecked { task: reporter.id(), result });
+        });
+    }
+
+    // A failed check is not worth interrupting anyone over; it is only logged.
+    fn finish_update_check(&mut self, result: Result<Option<Box<Update>>>) {
+        match result {
+            Ok(Some(update)) => {
+                self.log_info(
+                    trf("log.update_available", &[("version", &update.version), ("current", &crate::VERSION)]),
+                    Some("Updater".to_string()),
+                );
+                let install = self.settings_manager.get().general.install_updates && update.asset.is_some();
+                self.update = Some(*update);
+                if install {
+                    self.start_update_install();
+                }
+            }
+            Ok(None) => self.log_info(tr("log.up_to_date").to_string(), Some("Updater".to_string())),
+            Err(e) => self.log_warning(trf("log.update_check_failed", &[("error", &e)]), Some("Updater".to_string())),
+        }
+    }
+
+    pub fn start_update_install(&mut self) {
+        let Some(update) = self.update.clone() else {
+            return;
+        };
+        if self.update_installed || self.tasks.is_running(&TaskKind::InstallUpdate) {
+            return;
+        }
+        let network = self.network_manager.clone();
+        let reporter = self.begin_task(TaskKind::InstallUpdate);
+        let span = tracing::info_span!("install_update", version = %update.version);
+        tokio::spawn(async move {
+            let result = crate::updater::install(&network, &update).await;
+            reporter.send(AppEvent::UpdateInstalled { task: reporter.id(), result });
+        }.instrument(span));
+    }
+
+    fn finish_update_install(&mut self, result: Result<std::path::PathBuf>) {
+        let version = self.update.as_ref().map(|update| update.version.clone()).unwrap_or_default();
+        match result {
+            Ok(_) => {
+                self.update_installed = true;
+                self.current_state = trf("status.update_installed", &[("version", &version)]);
+            }
+            Err(e) => {
+                self.log_error(trf("log.update_install_failed", &[("version", &version), ("error", &e)]), Some("Updater".to_string()));
+                self.report_error(trf("status.update_failed", &[("error", &e)]), &e);
+            }
+        }
+    }
+
+    // The focused version is only applied once its entry has loaded, so the
+    // selection jumps there even when the screen opened before the fetch.
+    pub fn take_news_focus(&mut self) -> Option<usize> {
+        let index = crate::news::find_version(&self.news, self.news_focus.as_deref()?)?;
+        self.news_focus = None;
+        Some(index)
+    }
+
+    fn finish_version_verify(&mut self, version_id: String, result: Result<VerifyReport>) {
+        for category in [StorageCategory::Versions, StorageCategory::Libraries, StorageCategory::Assets] {
+            self.disk_usage.invalidate(UsageTarget::Category(category));
+        }
+        let report = match result {
+            Ok(report) => report,
+            Err(e) => {
+                self.log_error(trf("log.verify_failed", &[("version", &version_id), ("error", &e)]), Some("VersionManager".to_string()));
+                self.report_error(trf("status.verify_failed", &[("version", &version_id), ("error", &e)]), &e);
+                return;
+            }
+        };
+        for path in &report.missing {
+            self.log_warning(trf("log.file_missing", &[("path", &path.display())]), Some("VersionManager".to_string()));
+        }
+        for path in &report.corrupt {
+            self.log_warning(trf("log.file_corrupt", &[("path", &path.display())]), Some("VersionManager".to_string()));
+        }
+        for (path, e) in &report.failed {
+            self.log_error(trf("log.file_repair_failed", &[("path", &path.display()), ("error", &e)]), Some("VersionManager".to_string()));
+        }
+        self.log_info(
+            trf("log.verify_report", &[("version", &version_id), ("checked", &report.checked), ("missing", &report.missing.len()), ("corrupt", &report.corrupt.len()), ("repaired", &report.repaired())]),
+            Some("VersionManager".to_string()),
+        );
+        if let Err(e) = self.version_manager.set_version_installed(&version_id, report.failed.is_empty()) {
+            self.log_warning(trf("log.installed_index_failed", &[("error", &e)]), Some("VersionManager".to_string()));
+        }
+        self.current_state = if report.broken() == 0 {
+            trf("status.verify_clean", &[("version", &version_id), ("count", &report.checked)])
+        } else if report.failed.is_empty() {
+            trf("status.verify_repaired", &[("version", &version_id), ("count", &report.repaired())])
+        } else {
+            trf("status.verify_incomplete", &[("version", &version_id), ("count", &report.failed.len())])
+        };
+    }
+
+    fn finish_version_download(&mut self, version_id: String, result: Result<()>, assets_error: Option<String>, status: Option<MojangStatus>) {
+        for category in [StorageCategory::Versions, StorageCategory::Libraries, StorageCategory::Assets] {
+            self.disk_usage.invalidate(UsageTarget::Category(category));
+        }
+        match result {
+            Ok(()) => {
+                match assets_error {
+                    Some(e) => self.log_warning(trf("log.assets_failed", &[("version", &version_id), ("error", &e)]), Some("AssetsManager".to_string())),
+                    None => self.log_info(trf("log.assets_downloaded", &[("version", &version_id)]), Some("AssetsManager".to_string())),
+                }
+                if let Err(e) = self.version_manager.set_version_installed(&version_id, true) {
+                    self.log_warning(trf("log.installed_index_failed", &[("error", &e)]), Some("VersionManager".to_string()));
+                }
+                self.current_state = trf("status.version_downloaded", &[("version", &version_id)]);
+            }
+            Err(e) => {
+                let error = match status {
+                    Some(status) => {
+                        let health = self.record_mojang_status(status);
+                        self.explain_with_health(e, health)
+                    }
+                    None => e,
+                };
+                self.report_error(trf("status.version_download_failed", &[("version", &version_id), ("error", &error)]), &error);
+            }
+        }
+    }
+
+    pub fn get_available_versions(&self) -> &[MinecraftVersion] {
+        self.version_manager.get_versions()
+    }
+
+    pub fn get_profiles(&self) -> Vec<&Profile> {
+        self.profile_manager.list_profiles()
+    }
+
+    pub fn get_active_profile(&self) -> Option<&Profile> {
+        self.profile_manager.get_active_profile()
+    }
+
+    pub fn create_profile(&mut self, name: String) -> Result<Uuid> {
+        self.profile_manager.create_profile(name)
+    }
+
+    pub fn delete_profile(&mut self, id: Uuid) -> Result<()> {
+        self.profile_manager.delete_profile(id)
+    }
+
+    pub fn get_settings(&self) -> &Settings {
+        self.settings_manager.get()
+    }
+
+    pub fn get_settings_mut(&mut self) -> &mut Settings {
+        self.settings_manager.get_mut()
+    }
+
+    pub fn save_settings(&mut self) -> Result<()> {
+        self.settings_manager.save()
+    }
+
+    pub fn update_setting(&mut self, field: SettingField, value: SettingValue) -> Result<()> {
+        self.settings_manager.update(field, value)?;
+        self.log_info(trf("log.setting_changed", &[("key", &field.key())]), Some("Settings".to_string()));
+
+        match field {
+            SettingField::Language => crate::i18n::set_language(&self.settings_manager.get().general.language),
+            SettingField::JavaDirectory => {
+                self.java_manager.update_java_directory(Some(self.settings_manager.get().general.java_directory.clone()));
+                self.start_java_scan();
+            }
+            SettingField::InstancesDirectory => self.reload_instances(),
+            SettingField::LibraryDownloads
+            | SettingField::AssetDownloads
+            | SettingField::Verifications
+            | SettingField::UseProxy
+            | SettingField::ProxyType
+            | SettingField::ProxyHost
+            | SettingField::ProxyPort
+            | SettingField::ProxyUsername
+            | SettingField::ProxyPassword
+            | SettingField::Timeout
+            | SettingField::UserAgent
+            | SettingField::MetaUrl
+            | SettingField::ResourcesUrl
+            | SettingField::LibrariesUrl
+            | SettingField::FallbackResourcesUrls
+            | SettingField::FallbackLibrariesUrls => self.update_network_settings(),
+            SettingField::SaveLogsToFile
+            | SettingField::LogsDirectory
+            | SettingField::LogRetentionHours
+            | SettingField::LogMaxFileSize
+            | SettingField::CompressRotatedLogs => self.update_file_logging(),
+            SettingField::LogMemoryLimit => self.update_log_memory_limit(),
+            SettingField::SharedDataDir => self.update_shared_sources(),
+            SettingField::DefaultJava => {
+                let path = self.settings_manager.get().java.default_installation.clone();
+                self.java_manager.prefer_default(path);
+            }
+            SettingField::ShowSnapshots | SettingField::ShowLegacyVersions | SettingField::ShowAprilFools => {
+                self.version_filter.set_channels(self.settings_manager.get().ui.version_channels());
+            }
+            SettingField::MotdUrl => self.start_motd_load(),
+            SettingField::LogLevel => crate::logging::set_filter(&self.settings_manager.get().advanced.log_level),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn reload_instances(&mut self) {
+        let instances_dir = self.settings_manager.get().general.instances_directory.clone();
+        match InstanceManager::new(instances_dir.clone()) {
+            Ok(manager) => {
+                self.instance_manager = manager;
+                self.marked.clear();
+                self.disk_usage.set_instances_root(instances_dir);
+                self.log_info(trf("log.instances_dir_changed", &[("count", &self.instance_manager.list_instances().len())]), Some("InstanceManager".to_string()));
+            }
+            Err(e) => {
+                self.log_error(trf("log.instances_dir_failed", &[("path", &format!("{:?}", instances_dir)), ("error", &e)]), Some("InstanceManager".to_string()));
+            }
+        }
+    }
+
+    pub fn wizard_add_account(&mut self, username: String) -> Result<()> {
+        let id = self.add_offline_account(username.clone())?;
+        self.set_default_account(id)?;
+        self.start_head_refresh();
+        if let Some(wizard) = self.wizard.as_mut() {
+            wizard.account = Some(username);
+        }
+        Ok(())
+    }
+
+    pub fn wizard_create_instance(&mut self, name: String) -> Result<()> {
+        let version = self.version_manager.latest_release().unwrap_or("1.21").to_string();
+        self.create_instance(name.clone(), version.clone())?;
+        if !self.offline_mode && !self.version_manager.is_version_installed(&version) {
+            if let Err(e) = self.start_version_download(&version) {
+                self.log_warning(trf("log.wizard_download_failed", &[("version", &version), ("error", &e)]), Some("Wizard".to_string()));
+            }
+        }
+        if let Some(wizard) = self.wizard.as_mut() {
+            wizard.instance = Some(format!("{} ({})", name, version));
+        }
+        Ok(())
+    }
+
+    /// The Java major version the latest release needs, which setup offers
+    /// to download.
+    pub fn wizard_java_major(&self) -> u8 {
+        self.version_manager.latest_release()
+            .and_then(|version| self.java_manager.get_recommended_java_for_minecraft(version))
+            .unwrap_or(21)
+    }
+
+    /// Points later starts at `dir`, at the folder next to the executable
+    /// when `portable`, or back at the platform's folder when neither is
+    /// given, then closes the launcher so it restarts there. Setup begins
+    /// again in the new folder.
+    pub fn wizard_choose_data_dir(&mut self, dir: Option<PathBuf>, portable: bool) -> Result<()> {
+        if matches!(crate::utils::data_dir_source(), DataDirSource::Flag | DataDirSource::Environment) {
+            return Err(crate::Error::Settings(format!(
+                "The data directory is set by --data-dir or {}",
+                crate::utils::DATA_DIR_ENV,
+            )));
+        }
+        let target = if portable {
+            crate::utils::write_portable_marker()?
+        } else {
+            crate::utils::choose_data_dir(dir.as_deref())?;
+            dir.unwrap_or_else(crate::utils::platform_data_dir)
+        };
+        if std::path::absolute(&target)? == self.data_dir {
+            return Ok(());
+        }
+        self.log_info(trf("log.data_dir_chosen", &[("path", &target.display())]), Some("Wizard".to_string()));
+        crate::utils::request_restart();
+        self.quit();
+        Ok(())
+    }
+
+    pub fn finish_wizard(&mut self) {
+        self.wizard = None;
+        self.state = AppState::MainMenu;
+        self.current_state = tr("status.wizard_done").to_string();
+        self.log_info(tr("log.wizard_done").to_string(), Some("Wizard".to_string()));
+    }
+
+    pub fn quit(&mut self) {
+        self.should_quit = true;
+    }
+
+    pub fn tick_rate(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.get_settings().ui.tick_rate_ms.max(16))
+    }
+
+    pub fn animations_enabled(&self) -> bool {
+        !self.get_settings().ui.reduced_motion
+    }
+
+    pub fn on_tick(&mut self) -> bool {
+        if self.next_connectivity_check.is_some_and(|at| Instant::now() >= at) {
+            self.start_connectivity_check();
+        }
+        let mut changed = self.poll_game_exits();
+        changed |= self.poll_dev_watchers();
+        changed |= self.poll_mods_watcher();
+        if self.state == AppState::Storage {
+            self.refresh_disk_usage();
+        }
+        let rotate_art = self.animations_enabled() && self.get_settings().ui.rotate_art;
+        changed |= self.motd.rotate(rotate_art);
+        if self.animations_enabled() && (self.get_settings().ui.rotate_art || !self.tasks.is_empty()) {
+            self.tick = self.tick.wrapping_add(1);
+            changed = true;
+        }
+        changed
+    }
+
+    pub fn poll_game_exits(&mut self) -> bool {
+        let exits = self.launch_manager.poll_exits();
+        let changed = !exits.is_empty();
+        for exit in exits {
+            self.handle_game_exit(exit);
+        }
+        changed
+    }
+
+    fn start_dev_watch(&mut self, id: Uuid) {
+        let game_dir = self.instance_manager.game_dir(id);
+        self.dev_watchers.insert(id, DirWatcher::new(game_dir, &["mods", "config"]));
+    }
+
+    pub fn create_shortcut(&mut self, id: Uuid, kind: ShortcutKind) -> Result<PathBuf> {
+        let instance = self.instance_manager.get_instance(id)
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        let path = crate::shortcut::create_shortcut(instance, kind, &self.data_dir)?;
+        let instance_name = instance.name.clone();
+        self.log_info(trf("log.shortcut_created", &[("name", &instance_name), ("path", &path.display())]), Some("InstanceManager".to_string()));
+        Ok(path)
+    }
+
+    pub fn toggle_dev_watch(&mut self, id: Uuid) -> Result<bool> {
+        let mut instance = self.instance_manager.get_instance(id).cloned()
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        instance.dev_watch = !instance.dev_watch;
+        let enabled = instance.dev_watch;
+        let name = instance.name.clone();
+        self.instance_manager.update_instance(instance)?;
+
+        if enabled {
+            self.start_dev_watch(id);
+            self.log_info(trf("log.dev_mode_on", &[("name", &name)]), Some("InstanceManager".to_string()));
+        } else {
+            self.dev_watchers.remove(&id);
+            self.dev_changes.remove(&id);
+            self.log_info(trf("log.dev_mode_off", &[("name", &name)]), Some("InstanceManager".to_string()));
+        }
+        Ok(enabled)
+    }
+
+    fn poll_dev_watchers(&mut self) -> bool {
+        let mut detected = Vec::new();
+        for (id, watcher) in self.dev_watchers.iter_mut() {
+            if let Some(changes) = watcher.poll().filter(|c| !c.is_empty()) {
+                detected.push((*id, changes));
+            }
+        }
+
+        for (id, changes) in &detected {
+            let name = self.instance_manager.get_instance(*id)
+                .map(|instance| instance.name.clone())
+                .unwrap_or_default();
+            let listed: Vec<String> = changes.iter().take(3).map(|p| p.display().to_string()).collect();
+            let more = changes.len().saturating_sub(listed.len());
+            self.log_info(
+                if more > 0 {
+                    trf("log.dev_changed_more", &[("name", &name), ("files", &listed.join(", ")), ("more", &more)])
+                } else {
+                    trf("log.dev_changed", &[("name", &name), ("files", &listed.join(", "))])
+                },
+                Some("InstanceManager".to_string()),
+            );
+            *self.dev_changes.entry(*id).or_insert(0) += changes.len();
+            self.current_state = trf("status.dev_files_changed", &[("name", &name)]);
+        }
+        !detected.is_empty()
+    }
+
+    pub fn relaunch_instance(&mut self, id: Uuid) -> Result<()> {
+        let stopped = self.launch_manager.stop_instance(id);
+        if stopped > 0 {
+            self.log_info(trf("log.sessions_stopping", &[("count", &stopped)]), Some("LaunchManager".to_string()));
+            self.current_state = tr("status.relaunch_pending").to_string();
+            self.pending_relaunch = Some(id);
+            return Ok(());
+        }
+        self.dev_changes.remove(&id);
+        if let Some(watcher) = self.dev_watchers.get_mut(&id) {
+            watcher.reset();
+        }
+        self.launch_instance(id)
+    }
+
+    pub fn process_pending_relaunch(&mut self) -> bool {
+        let Some(id) = self.pending_relaunch else {
+            return false;
+        };
+        if self.launch_manager.running_session_count(id) > 0 {
+            return false;
+        }
+        self.pending_relaunch = None;
+        if let Err(e) = self.relaunch_instance(id) {
+            self.report_error(trf("status.launch_failed", &[("error", &e)]), &e);
+        }
+        true
+    }
+
+    fn handle_game_exit(&mut self, exit: GameExit) {
+        self.disk_usage.invalidate(UsageTarget::Instance(exit.instance_id));
+        if let Some(watcher) = self.dev_watchers.get_mut(&exit.instance_id) {
+            watcher.reset();
+        }
+
+        match self.instance_manager.record_play_session(exit.instance_id, exit.started_at, exit.ended_at) {
+            Ok(session) => self.log_info(
+                trf("log.session_length", &[("name", &exit.instance_name), ("duration", &crate::utils::format_duration(session))]),
+                Some("InstanceManager".to_string()),
+            ),
+            Err(e) => self.log_warning(
+                trf("log.playtime_save_failed", &[("name", &exit.instance_name), ("error", &e)]),
+                Some("InstanceManager".to_string()),
+            ),
+        }
+
+        if let Some(session) = self.bisect.as_mut().filter(|s| s.instance_id == exit.instance_id && s.running) {
+            session.running = false;
+            session.tested = true;
+            session.detected_crash = Some(exit.is_crash());
+            let round = session.round;
+            self.log_info(
+                trf("log.bisect_exit", &[("round", &round), ("code", &format!("{:?}", exit.exit_code))]),
+                Some("ModManager".to_string()),
+            );
+            self.current_state = tr("status.bisect_did_it_crash").to_string();
+            self.state = AppState::ModBisect;
+            return;
+        }
+
+        if exit.safe_mode {
+            self.log_info(trf("log.safe_mode_restored", &[("name", &exit.instance_name)]), Some("ModManager".to_string()));
+        }
+
+        if !exit.is_crash() {
+            self.current_state = trf("status.game_exited", &[("name", &exit.instance_name)]);
+            self.log_info(trf("log.instance_exited", &[("name", &exit.instance_name)]), Some("LaunchManager".to_string()));
+            return;
+        }
+
+        let report = CrashReport::analyze(&exit, self.system_info.as_ref());
+        self.log_error(
+            trf("log.crashed", &[("name", &exit.instance_name), ("headline", &report.headline())]),
+            Some("LaunchManager".to_string()),
+        );
+        if let Some(path) = &report.report_path {
+            self.log_error(trf("log.crash_report", &[("path", &path.display())]), Some("LaunchManager".to_string()));
+        }
+        self.current_state = trf("status.game_crashed", &[("name", &exit.instance_name)]);
+        self.last_crash = Some(report);
+        self.state = AppState::CrashReport;
+    }
+
+    pub async fn export_instance(&mut self, id: Uuid, mrpack: bool) -> Result<PathBuf> {
+        let instance = self.instance_manager.get_instance(id)
+            .cloned()
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+
+        let format = if mrpack {
+            let remote_files = match crate::mrpack::resolve_remote_mods(&self.network_manager, &self.instance_mods_dir(id)).await {
+                Ok(files) => files,
+                Err(e) => {
+                    self.log_warning(trf("log.export_lookup_failed", &[("error", &e)]), Some("InstanceManager".to_string()));
+                    Default::default()
+                }
+            };
+            ExportFormat::Mrpack { remote_files }
+        } else {
+            ExportFormat::Zip
+        };
+
+        let extension = if mrpack { "mrpack" } else { "zip" };
+        let export_path = self.data_dir
+            .join("exports")
+            .join(format!("{}.{}", crate::utils::sanitize_file_name(&instance.name), extension));
+
+        self.log_info(trf("log.exporting", &[("name", &instance.name), ("path", &export_path.display())]), Some("InstanceManager".to_string()));
+        match self.instance_manager.export_instance(id, &export_path, &format) {
+            Ok(()) => {
+                self.log_info(trf("log.exported", &[("name", &instance.name)]), Some("InstanceManager".to_string()));
+                Ok(export_path)
+            }
+            Err(e) => {
+                self.log_error(trf("log.export_failed", &[("name", &instance.name), ("error", &e)]), Some("InstanceManager".to_string()));
+                Err(e)
+            }
+        }
+    }
+
+    pub fn import_dir(&self) -> PathBuf {
+        self.data_dir.join("import")
+    }
+
+    pub async fn import_instance(&mut self, path: &std::path::Path) -> Result<Uuid> {
+        self.log_info(trf("log.importing", &[("path", &path.display())]), Some("InstanceManager".to_string()));
+        let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let reporter = self.begin_task(TaskKind::ImportPack(name));
+        let result = self.import_pack(path, &reporter).await;
+        self.tasks.finish(reporter.id());
+        result
+    }
+
+    async fn import_pack(&mut self, path: &Path, progress: &TaskReporter) -> Result<Uuid> {
+        progress.plan(&[(TaskStage::Unpack, 1), (TaskStage::PackFiles, 4)]);
+        progress.report(TaskStage::Unpack, 0, 0);
+        let imported = match self.instance_manager.import_instance(path) {
+            Ok(imported) => imported,
+            Err(e) => {
+                self.log_error(trf("log.import_failed", &[("path", &path.display()), ("error", &e)]), Some("InstanceManager".to_string()));
+                return Err(e);
+            }
+        };
+
+        let game_dir = self.instance_manager.game_dir(imported.id);
+        let api_key = self.settings_manager.get().network.curseforge_api_key.clone();
+        let failed = Self::download_pack_files(&self.network_manager, api_key, &game_dir, imported.pending_downloads, progress).await;
+
+        if let Some(mut instance) = self.instance_manager.get_instance(imported.id).cloned() {
+            self.log_info(trf("log.imported", &[("name", &instance.name)]), Some("InstanceManager".to_string()));
+            let mut changed = false;
+            if let Some(modpack) = instance.modpack.as_mut().filter(|_| !failed.is_empty()) {
+                for path in &failed {
+                    modpack.files.remove(path);
+                }
+                changed = true;
+            }
+            if let Some(format) = instance.modpack.as_ref().map(|pack| pack.format) {
+                match self.lookup_pack_project(path, format).await {
+                    Ok((project_id, links)) => {
+                        if instance.links.is_empty() {
+                            instance.links = links;
+                        }
+                        if let Some(modpack) = instance.modpack.as_mut() {
+                            modpack.project_id = Some(project_id);
+                        }
+                        changed = true;
+                    }
+                    Err(e) => self.log_warning(trf("log.pack_links_failed", &[("name", &instance.name), ("error", &e)]), Some("InstanceManager".to_string())),
+                }
+            }
+            if changed {
+                self.instance_manager.update_instance(instance)?;
+            }
+        }
+        Ok(imported.id)
+    }
+
+    // Pack archives name neither their project nor its pages, so the
+    // project is found on the site the pack came from by the archive's hash.
+    async fn pack_project_id(&self, path: &Path, format: ModpackFormat) -> Result<String> {
+        match format {
+            ModpackFormat::Modrinth => {
+                let sha1 = crate::import::sha1_hex(std::fs::File::open(path)?)?;
+                let client = ModrinthClient::new(self.network_manager.clone());
+                let version = client.versions_from_hashes(std::slice::from_ref(&sha1)).await?.remove(&sha1)
+                    .ok_or_else(|| crate::Error::Other("The pack is not published on Modrinth".to_string()))?;
+                Ok(version.project_id)
+            }
+            ModpackFormat::CurseForge => {
+                let client = CurseForgeClient::new(self.network_manager.clone(), self.curseforge_api_key()?);
+                let fingerprint = crate::curseforge::fingerprint(&std::fs::read(path)?);
+                let file = client.files_from_fingerprints(&[fingerprint]).await?.into_iter().next()
+                    .ok_or_else(|| crate::Error::Other("The pack is not published on CurseForge".to_string()))?;
+                Ok(file.mod_id.to_string())
+            }
+        }
+    }
+
+    async fn lookup_pack_project(&self, path: &Path, format: ModpackFormat) -> Result<(String, InstanceLinks)> {
+        let project_id = self.pack_project_id(path, format).await?;
+        let links = match format {
+            ModpackFormat::Modrinth => ModrinthClient::new(self.network_manager.clone()).project(&project_id).await?.links(),
+            ModpackFormat::CurseForge => {
+                let mod_id = project_id.parse()
+                    .map_err(|_| crate::Error::Other(format!("Invalid CurseForge mod ID {}", project_id)))?;
+                CurseForgeClient::new(self.network_manager.clone(), self.curseforge_api_key()?).mod_info(mod_id).await?.links()
+            }
+        };
+        Ok((project_id, links))
+    }
+
+    fn curseforge_api_key(&self) -> Result<String> {
+        self.settings_manager.get().network.curseforge_api_key.clone()
+            .ok_or_else(|| crate::Error::Other("No CurseForge API key is set".to_string()))
+    }
+
+    // The instance already holding the pack at `path`, matched by the
+    // project it is published as. Packs that cannot be looked up, or that
+    // only share a name with an installed one, are not matched.
+    async fn installed_pack_instance(&self, path: &Path) -> Option<Uuid> {
+        let format = crate::import::read_plan(path).ok().and_then(|plan| ModpackFormat::from_import(plan.kind))?;
+        let installed: Vec<(Uuid, String)> = self.instance_manager.list_instances().into_iter()
+            .filter_map(|instance| instance.modpack.as_ref()
+                .filter(|pack| pack.format == format)
+                .and_then(|pack| pack.project_id.clone())
+                .map(|project_id| (instance.id, project_id)))
+            .collect();
+        // Without a candidate there is nothing to look the archive up for.
+        if installed.is_empty() {
+            return None;
+        }
+        let project_id = self.pack_project_id(path, format).await.ok()?;
+        installed.into_iter().find(|(_, id)| *id == project_id).map(|(id, _)| id)
+    }
+
+    /// Imports `path` as a new instance, unless it is a version of a pack
+    /// that is already installed. Then the update screen is opened so the
+    /// user can confirm importing over that instance, and None is returned.
+    pub async fn import_or_review(&mut self, path: &Path) -> Result<Option<Uuid>> {
+        if let Some(id) = self.installed_pack_instance(path).await {
+            if self.modpack_update.is_none() {
+                let instance = self.instance_manager.get_instance(id)
+                    .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+                let update = crate::modpack::plan_import(instance, path)?;
+                self.log_info(trf("log.import_over", &[("name", &instance.name)]), Some("InstanceManager".to_string()));
+                self.modpack_update = Some(update);
+                self.state = AppState::ModpackUpdate;
+            }
+            return Ok(None);
+        }
+        self.import_instance(path).await.map(Some)
+    }
+
+    /// Turns every profile of the official launcher into an instance and
+    /// takes over the versions it already downloaded. Without `root` the
+    /// platform's default `.minecraft` is read.
+    pub fn import_official_launcher(&mut self, root: Option<&Path>) -> Result<OfficialImport> {
+        let root = match root {
+            Some(root) => root.to_path_buf(),
+            None => crate::official::default_dir()
+                .ok_or_else(|| crate::Error::Instance("Could not locate the official launcher's folder".to_string()))?,
+        };
+        self.log_info(trf("log.official_importing", &[("path", &root.display())]), Some("InstanceManager".to_string()));
+        let launcher = crate::official::read(
+            &root,
+            self.version_manager.latest_release(),
+            self.version_manager.latest_snapshot(),
+        )?;
+
+        let versions_dir = self.data_dir.join("versions");
+        let assets_dir = self.assets_manager.get_assets_dir().to_path_buf();
+        let mut summary = OfficialImport { account_hints: launcher.account_hints, ..OfficialImport::default() };
+        let mut reused_versions = HashSet::new();
+        for plan in launcher.plans {
+            let name = plan.name.clone();
+            let version = plan.minecraft_version.clone();
+            if let Err(e) = self.instance_manager.import_plan(plan) {
+                self.log_error(trf("log.official_profile_failed", &[("name", &name), ("error", &e)]), Some("InstanceManager".to_string()));
+                continue;
+            }
+            summary.instances += 1;
+            self.log_info(trf("log.official_profile_imported", &[("name", &name), ("version", &version)]), Some("InstanceManager".to_string()));
+
+            if reused_versions.insert(version.clone()) {
+                match crate::official::reuse_version(&launcher.root, &version, &versions_dir, &assets_dir) {
+                    Ok(files) => summary.reused_files += files,
+                    Err(e) => self.log_warning(
+                        trf("log.official_reuse_failed", &[("version", &version), ("error", &e)]),
+                        Some("VersionManager".to_string()),
+                    ),
+                }
+            }
+        }
+        if summary.reused_files > 0 {
+            self.log_info(trf("log.official_reused", &[("count", &summary.reused_files)]), Some("VersionManager".to_string()));
+        }
+        if !summary.account_hints.is_empty() {
+            self.log_info(
+                trf("log.official_accounts", &[("accounts", &summary.account_hints.join(", "))]),
+                Some("AuthManager".to_string()),
+            );
+        }
+        Ok(summary)
+    }
+
+    /// Imports every instance of a PrismLauncher/MultiMC data folder and,
+    /// unless another folder is already set, reuses its libraries and assets
+    /// from then on. Returns how many instances were imported.
+    pub async fn import_prism_instances(&mut self, data_dir: Option<&Path>) -> Result<usize> {
+        let data_dir = match data_dir {
+            Some(dir) => dir.to_path_buf(),
+            None => crate::shared::default_prism_dir()
+                .ok_or_else(|| crate::Error::Instance("PrismLauncher's data folder was not found".to_string()))?,
+        };
+        let instance_dirs = crate::shared::instance_dirs(&data_dir);
+        if instance_dirs.is_empty() {
+            return Err(crate::Error::Instance(format!("No instances found in {}", data_dir.display())));
+        }
+
+        let mut imported = 0;
+        for dir in instance_dirs {
+            if self.import_instance(&dir).await.is_ok() {
+                imported += 1;
+            }
+        }
+        if self.settings_manager.get().general.shared_data_dir.is_none() {
+            self.update_setting(SettingField::SharedDataDir, SettingValue::OptionalPath(Some(data_dir)))?;
+        }
+        Ok(imported)
+    }
+
+    // Returns the game-relative paths that could not be downloaded.
+    // Runs off the UI loop while a pack update applies, so it only takes
+    // what it needs from the app.
+    async fn download_pack_files(network: &NetworkManager, api_key: Option<String>, game_dir: &Path, mut pending: Vec<PendingDownload>, progress: &TaskReporter) -> Vec<String> {
+        let total = pending.len();
+        let mut failed = Vec::new();
+        if total == 0 {
+            return failed;
+        }
+        progress.report(TaskStage::PackFiles, 0, total as u64);
+        Self::fill_curseforge_hashes(network, api_key, &mut pending).await;
+
+        tracing::info!(source = "NetworkManager", "{}", trf("log.pack_files_downloading", &[("count", &total)]));
+        let mut downloads = futures_util::stream::iter(pending)
+            .map(|download| async move {
+                let result = crate::import::fetch_pending(network, &download, game_dir).await;
+                (download.path, result)
+            })
+            .buffer_unordered(network.get_max_concurrent_downloads().max(1));
+
+        let mut done = 0;
+        while let Some((path, result)) = downloads.next().await {
+            done += 1;
+            progress.report(TaskStage::PackFiles, done as u64, total as u64);
+            match result {
+                Ok(()) => tracing::info!(source = "NetworkManager", "[{}/{}] {}", done, total, path.display()),
+                Err(e) => {
+                    tracing::warn!(source = "NetworkManager", "{}", trf("log.pack_file_failed", &[("done", &done), ("total", &total), ("path", &path.display()), ("error", &e)]));
+                    failed.push(crate::import::game_path(&path));
+                }
+            }
+        }
+
+        if !failed.is_empty() {
+            tracing::warn!(source = "NetworkManager", "{}", trf("log.pack_files_partial", &[("done", &(total - failed.len())), ("total", &total)]));
+        }
+        failed
+    }
+
+    // Looks up the hashes and direct links of CurseForge pack files, which
+    // the manifest leaves out. Without an API key there is nothing to check
+    // them against, so they are downloaded unchecked.
+    async fn fill_curseforge_hashes(network: &NetworkManager, api_key: Option<String>, pending: &mut [PendingDownload]) {
+        let file_ids: Vec<u64> = pending.iter()
+            .filter(|download| download.sha1.is_none())
+            .filter_map(|download| download.curseforge_file)
+            .collect();
+        if file_ids.is_empty() {
+            return;
+        }
+        let Some(api_key) = api_key else {
+            tracing::warn!(source = "NetworkManager", "{}", trf("log.curseforge_unverified", &[("count", &file_ids.len())]));
+            return;
+        };
+        let client = CurseForgeClient::new(network.clone(), api_key);
+        let files = match client.files(&file_ids).await {
+            Ok(files) => files,
+            Err(e) => {
+                tracing::warn!(source = "NetworkManager", "{}", trf("log.curseforge_lookup_failed", &[("count", &file_ids.len()), ("error", &e)]));
+                return;
+            }
+        };
+        for download in pending.iter_mut() {
+            let Some(file) = files.iter().find(|file| Some(u64::from(file.id)) == download.curseforge_file) else {
+                continue;
+            };
+            download.sha1 = file.sha1().map(str::to_string);
+            download.fingerprint = Some(file.file_fingerprint);
+            if let Some(url) = &file.download_url {
+                download.urls.insert(0, url.clone());
+            }
+        }
+    }
+
+    // Without an explicit archive the newest matching pack in the import
+    // folder is used, so updates are dropped in the same place as imports.
+    pub fn plan_modpack_update(&self, id: Uuid, archive: Option<&Path>) -> Result<ModpackUpdate> {
+        let instance = self.instance_manager.get_instance(id)
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        let archive = match archive {
+            Some(archive) => archive.to_path_buf(),
+            None => {
+                let modpack = instance.modpack.as_ref()
+                    .ok_or_else(|| crate::Error::Instance(format!("'{}' was not imported from a modpack", instance.name)))?;
+                crate::modpack::find_update(instance, &self.import_dir())
+                    .ok_or_else(|| crate::Error::Instance(format!(
+                        "No newer version of {} found in {}",
+                        modpack.name,
+                        self.import_dir().display()
+                    )))?
+            }
+        };
+        crate::modpack::plan_update(instance, &archive)
+    }
+
+    pub fn open_modpack_update(&mut self, id: Uuid) -> Result<()> {
+        self.modpack_update = Some(self.plan_modpack_update(id, None)?);
+        self.state = AppState::ModpackUpdate;
+        Ok(())
+    }
+
+    // Returns the new pack version and how many files failed to download.
+    pub async fn apply_modpack_update(&mut self, update: ModpackUpdate) -> Result<(String, usize)> {
+        let (game_dir, reporter) = self.begin_modpack_update(&update)?;
+        let api_key = self.settings_manager.get().network.curseforge_api_key.clone();
+        let result = Self::apply_pack_files(&self.network_manager, api_key, &update, &game_dir, &reporter).await;
+        self.tasks.finish(reporter.id());
+        self.complete_modpack_update(update, result)
+    }
+
+    // Applies the update as a background task; the outcome is reported once
+    // its files are in place.
+    pub fn start_modpack_update(&mut self, update: ModpackUpdate) -> Result<()> {
+        let (game_dir, reporter) = self.begin_modpack_update(&update)?;
+        let network = self.network_manager.clone();
+        let api_key = self.settings_manager.get().network.curseforge_api_key.clone();
+        tokio::spawn(async move {
+            let result = Self::apply_pack_files(&network, api_key, &update, &game_dir, &reporter).await;
+            reporter.send(AppEvent::ModpackApplied { task: reporter.id(), update: Box::new(update), result });
+        });
+        Ok(())
+    }
+
+    fn finish_modpack_update(&mut self, update: ModpackUpdate, result: Result<Vec<String>>) {
+        match self.complete_modpack_update(update, result) {
+            Ok((version, 0)) => self.current_state = trf("status.modpack_updated", &[("version", &version)]),
+            Ok((version, failed)) => self.current_state = trf("status.modpack_updated_partial", &[
+                ("version", &version),
+                ("count", &failed),
+            ]),
+            Err(e) => self.report_error(trf("status.modpack_update_failed", &[("error", &e)]), &e),
+        }
+    }
+
+    // Backs the instance up and registers the task; returns its game folder.
+    fn begin_modpack_update(&mut self, update: &ModpackUpdate) -> Result<(PathBuf, TaskReporter)> {
+        let instance = self.instance_manager.get_instance(update.instance_id).cloned()
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        self.log_info(
+            trf("log.modpack_updating", &[("name", &instance.name), ("from", &update.from_version.as_deref().unwrap_or("?")), ("to", &update.source.display_version())]),
+            Some("InstanceManager".to_string()),
+        );
+        let reason = if update.from_import { BackupReason::Import } else { BackupReason::ModpackUpdate };
+        self.backup_stored_instance(&instance, reason)?;
+        let game_dir = instance.path.join(".minecraft");
+        Ok((game_dir, self.begin_task(TaskKind::ImportPack(instance.name))))
+    }
+
+    // Returns the game paths of the pack files that failed to download.
+    async fn apply_pack_files(network: &NetworkManager, api_key: Option<String>, update: &ModpackUpdate, game_dir: &Path, progress: &TaskReporter) -> Result<Vec<String>> {
+        progress.plan(&[(TaskStage::Unpack, 1), (TaskStage::PackFiles, 4)]);
+        progress.report(TaskStage::Unpack, 0, 0);
+        update.apply_files(game_dir)?;
+        Ok(Self::download_pack_files(network, api_key, game_dir, update.downloads.clone(), progress).await)
+    }
+
+    fn complete_modpack_update(&mut self, update: ModpackUpdate, result: Result<Vec<String>>) -> Result<(String, usize)> {
+        let mut instance = self.instance_manager.get_instance(update.instance_id).cloned()
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        let failed = match result {
+            Ok(failed) => failed,
+            Err(e) => {
+                self.log_error(trf("log.modpack_update_failed", &[("name", &instance.name), ("error", &e)]), Some("InstanceManager".to_string()));
+                return Err(e);
+            }
+        };
+        let version = update.source.display_version().to_string();
+        let archive = update.archive.clone();
+        update.finish(&mut instance, &failed);
+        self.instance_manager.update_instance(instance)?;
+        if archive.parent() == Some(self.import_dir().as_path()) {
+            let done_dir = self.import_dir().join("done");
+            std::fs::create_dir_all(&done_dir)?;
+            if let Some(name) = archive.file_name() {
+                let _ = std::fs::rename(&archive, done_dir.join(name));
+            }
+        }
+        self.log_info(trf("log.modpack_updated", &[("version", &version)]), Some("InstanceManager".to_string()));
+        Ok((version, failed.len()))
+    }
+
+    pub async fn import_from_inbox(&mut self) -> Result<usize> {
+        let import_dir = self.import_dir();
+        let done_dir = import_dir.join("done");
+        std::fs::create_dir_all(&done_dir)?;
+
+        let mut candidates: Vec<PathBuf> = std::fs::read_dir(&import_dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path != &done_dir)
+            .collect();
+        candidates.sort();
+
+        let mut imported = 0;
+        for path in candidates {
+            // Packs of installed instances stay in the inbox until the user
+            // has reviewed importing over them.
+            if let Ok(Some(_)) = self.import_or_review(&path).await {
+                imported += 1;
+                if let Some(name) = path.file_name() {
+                    let _ = std::fs::rename(&path, done_dir.join(name));
+                }
+            }
+        }
+        Ok(imported)
+    }
+
+    pub fn open_mod_browser(&mut self, instance_id: Uuid) -> Result<()> {
+        let instance = self.instance_manager.get_instance(instance_id)
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        let loader = instance.mod_loader.as_ref().map(|l| crate::modrinth::loader_name(l).to_string());
+        self.mod_browser = Some(ModBrowser::new(instance_id, loader, Some(instance.minecraft_version.clone())));
+        self.state = AppState::ModBrowser;
+        Ok(())
+    }
+
+    pub async fn search_mod_browser(&mut self, offset: usize) -> Result<()> {
+        let Some(browser) = self.mod_browser.as_ref() else { return Ok(()) };
+        let client = ModrinthClient::new(self.network_manager.clone());
+        let results = client.search(&browser.query, browser.loader.as_deref(), browser.game_version.as_deref(), offset).await?;
+
+        if let Some(browser) = self.mod_browser.as_mut() {
+            browser.offset = results.offset;
+            browser.total_hits = results.total_hits;
+            browser.results = results.hits;
+            browser.editing = false;
+        }
+        Ok(())
+    }
+
+    pub async fn install_from_mod_browser(&mut self, index: usize) -> Result<String> {
+        let browser = self.mod_browser.as_ref()
+            .ok_or_else(|| crate::Error::Mod("Mod browser is not open".to_string()))?;
+        let hit = browser.results.get(index)
+            .cloned()
+            .ok_or_else(|| crate::Error::Mod("No project selected".to_string()))?;
+        let instance_id = browser.instance_id;
+        let client = ModrinthClient::new(self.network_manager.clone());
+
+        let versions = client.project_versions(&hit.project_id, browser.loader.as_deref(), browser.game_version.as_deref()).await?;
+        let version = versions.first()
+            .ok_or_else(|| crate::Error::Mod(format!("{} has no compatible versions", hit.title)))?;
+        let file = version.primary_file()
+            .ok_or_else(|| crate::Error::Mod(format!("{} {} has no files", hit.title, version.version_number)))?;
+
+        self.log_info(trf("log.mod_installing", &[("name", &hit.title), ("version", &version.version_number)]), Some("ModManager".to_string()));
+        let download_dir = self.data_dir.join("cache").join("downloads");
+        let downloaded = client.download_file(file, &download_dir).await?;
+
+        let mut instance_mods = ModManager::new(self.instance_mods_dir(instance_id))?;
+        let result = instance_mods.install_mod(&downloaded);
+        let _ = std::fs::remove_file(&downloaded);
+        result?;
+
+        self.log_info(trf("log.mod_installed", &[("file", &file.filename)]), Some("ModManager".to_string()));
+        Ok(format!("{} {}", hit.title, version.version_number))
+    }
+
+    // Missing dependencies found on Modrinth are kept for
+    // start_dependency_install.
+    pub fn start_dependency_check(&mut self, instance_id: Uuid) -> Result<()> {
+        if self.tasks.is_running(&TaskKind::CheckDependencies(instance_id)) {
+            return Ok(());
+        }
+        let instance = self.instance_manager.get_instance(instance_id)
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        let target = UpdateTarget {
+            game_version: instance.minecraft_version.clone(),
+            loader: instance.mod_loader.clone(),
+            curseforge_api_key: None,
+            github_mods: Vec::new(),
+        };
+        let mods_dir = self.instance_mods_dir(instance_id);
+        let network = self.network_manager.clone();
+        let reporter = self.begin_task(TaskKind::CheckDependencies(instance_id));
+        tokio::spawn(async move {
+            let result = match ModManager::new(mods_dir) {
+                Ok(instance_mods) => Ok(instance_mods.check_and_resolve_dependencies(&network, &target).await),
+                Err(e) => Err(e),
+            };
+            reporter.send(AppEvent::DependenciesChecked { task: reporter.id(), instance_id, result });
+        });
+        Ok(())
+    }
+
+    fn finish_dependency_check(&mut self, instance_id: Uuid, result: Result<DependencyCheck>) {
+        let check = match result {
+            Ok(check) => check,
+            Err(e) => {
+                self.report_error(trf("status.error", &[("error", &e)]), &e);
+                return;
+            }
+        };
+        for missing in &check.report.missing {
+            self.log_warning(
+                trf("log.dependency_missing", &[("mod", &missing.required_by), ("dependency", &missing.mod_id), ("range", &missing.version_range)]),
+                Some("ModManager".to_string()),
+            );
+        }
+        for conflict in &check.report.conflicts {
+            self.log_warning(
+                trf("log.dependency_conflict", &[("mod", &conflict.required_by), ("dependency", &conflict.mod_id), ("range", &conflict.version_range), ("installed", &conflict.installed_version)]),
+                Some("ModManager".to_string()),
+            );
+        }
+        for mod_id in &check.unresolved {
+            self.log_warning(trf("log.dependency_not_found", &[("dependency", &mod_id)]), Some("ModManager".to_string()));
+        }
+
+        self.current_state = if check.report.is_clean() {
+            tr("status.dependencies_ok").to_string()
+        } else {
+            trf("status.dependency_issues", &[
+                ("missing", &check.report.missing_ids().len()),
+                ("conflicts", &check.report.conflicts.len()),
+                ("resolved", &check.resolved.len()),
+            ])
+        };
+        self.dependency_plan = (!check.resolved.is_empty()).then_some((instance_id, check.resolved));
+    }
+
+    pub fn start_dependency_install(&mut self, instance_id: Uuid) -> Result<()> {
+        if self.tasks.is_running(&TaskKind::InstallDependencies(instance_id)) {
+            return Ok(());
+        }
+        let plan = match self.dependency_plan.take() {
+            Some((id, plan)) if id == instance_id => plan,
+            _ => return Err(crate::Error::Mod("Check dependencies first".to_string())),
+        };
+        let network = self.network_manager.clone();
+        let download_dir = self.data_dir.join("cache").join("downloads");
+        let mods_dir = self.instance_mods_dir(instance_id);
+        let reporter = self.begin_task(TaskKind::InstallDependencies(instance_id));
+        tokio::spawn(async move {
+            let result = Self::install_dependencies(network, &download_dir, mods_dir, &plan).await;
+            reporter.send(AppEvent::DependenciesInstalled { task: reporter.id(), instance_id, result });
+        });
+        Ok(())
+    }
+
+    async fn install_dependencies(network: NetworkManager, download_dir: &Path, mods_dir: PathBuf, plan: &[ResolvedDependency]) -> Result<usize> {
+        let client = ModrinthClient::new(network);
+        let mut instance_mods = ModManager::new(mods_dir)?;
+        let mut installed = 0;
+        for dependency in plan {
+            let Some(file) = dependency.version.primary_file() else { continue };
+            let downloaded = client.download_file(file, download_dir).await?;
+            let result = instance_mods.install_mod(&downloaded);
+            let _ = std::fs::remove_file(&downloaded);
+            result?;
+            tracing::info!(source = "ModManager", "{}", trf("log.dependency_installed", &[("dependency", &dependency.mod_id), ("version", &dependency.version.version_number)]));
+            installed += 1;
+        }
+        Ok(installed)
+    }
+
+    fn finish_dependency_install(&mut self, instance_id: Uuid, result: Result<usize>) {
+        // Whatever got installed before a failure shows up in the list too.
+        if let Some((_, manager)) = self.installed_mods.as_mut().filter(|(id, _)| *id == instance_id) {
+            if let Err(e) = manager.scan_mods() {
+                self.log_warning(trf("log.mods_rescan_failed", &[("error", &e)]), Some("ModManager".to_string()));
+            }
+            self.reset_mods_watcher();
+        }
+        match result {
+            Ok(count) => self.current_state = trf("status.dependencies_installed", &[("count", &count)]),
+            Err(e) => self.report_error(trf("status.error", &[("error", &e)]), &e),
+        }
+    }
+
+    pub async fn check_mod_updates(&mut self, instance_id: Uuid) -> Result<usize> {
+        let instance = self.instance_manager.get_instance(instance_id)
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        let target = UpdateTarget {
+            game_version: instance.minecraft_version.clone(),
+            loader: instance.mod_loader.clone(),
+            curseforge_api_key: self.settings_manager.get().network.curseforge_api_key.clone(),
+            github_mods: instance.github_mods.clone(),
+        };
+        if target.curseforge_api_key.is_none() {
+            self.log_info(tr("log.curseforge_no_key").to_string(), Some("ModManager".to_string()));
+        }
+
+        self.log_info(trf("log.mod_updates_checking", &[("name", &instance.name)]), Some("ModManager".to_string()));
+        let instance_mods = ModManager::new(self.instance_mods_dir(instance_id))?;
+        let updates = instance_mods.check_updates(&self.network_manager, &target).await?;
+
+        for update in &updates {
+            self.log_info(
+                trf("log.mod_update_available", &[("name", &update.mod_name), ("current", &update.current_version), ("new", &update.new_version)]),
+                Some("ModManager".to_string()),
+            );
+        }
+        let count = updates.len();
+        self.mod_updates = Some((instance_id, updates));
+        Ok(count)
+    }
+
+    pub async fn apply_mod_updates(&mut self, instance_id: Uuid) -> Result<(usize, usize)> {
+        let updates = match self.mod_updates.take() {
+            Some((id, updates)) if id == instance_id => updates,
+            _ => return Err(crate::Error::Mod("Check for updates first".to_string())),
+        };
+
+        let download_dir = self.data_dir.join("cache").join("downloads");
+        let mut instance_mods = ModManager::new(self.instance_mods_dir(instance_id))?;
+        let results = instance_mods.apply_updates(&self.network_manager, &updates, &download_dir).await;
+
+        let mut applied = 0;
+        let mut failed = 0;
+        for (update, result) in results {
+            match result {
+                Ok(()) => {
+                    applied += 1;
+                    if let UpdateSource::GitHub { tag, .. } = &update.source {
+                        self.track_github_mod_update(instance_id, &update.filename, tag, &update.new_filename)?;
+                    }
+                    self.log_info(trf("log.mod_updated", &[("name", &update.mod_name), ("version", &update.new_version)]), Some("ModManager".to_string()));
+                }
+                Err(e) => {
+                    failed += 1;
+                    self.log_error(trf("log.mod_update_failed", &[("name", &update.mod_name), ("error", &e)]), Some("ModManager".to_string()));
+                }
+            }
+        }
+        Ok((applied, failed))
+    }
+
+    fn track_github_mod_update(&mut self, instance_id: Uuid, old_filename: &str, tag: &str, new_filename: &str) -> Result<()> {
+        let mut instance = self.instance_manager.get_instance(instance_id).cloned()
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        if let Some(tracked) = instance.github_mods.iter_mut().find(|m| m.filename == old_filename) {
+            tracked.tag = tag.to_string();
+            tracked.filename = new_filename.to_string();
+        }
+        self.instance_manager.update_instance(instance)
+    }
+
+    pub async fn add_github_mod(&mut self, instance_id: Uuid, source: GitHubSource) -> Result<GitHubMod> {
+        let mut instance = self.instance_manager.get_instance(instance_id).cloned()
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        if instance.github_mods.iter().any(|m| m.source.owner == source.owner && m.source.repo == source.repo) {
+            return Err(crate::Error::Mod(format!("{} is already installed", source.display())));
+        }
+
+        self.log_info(trf("log.github_downloading", &[("source", &source.display())]), Some("ModManager".to_string()));
+        let client = GitHubClient::new(self.network_manager.clone());
+        let download_dir = self.data_dir.join("cache").join("downloads");
+        let download = client.download_latest(&source, &download_dir).await?;
+        if !download.verified {
+            self.log_warning(
+                trf("log.github_no_checksums", &[("source", &source.display()), ("tag", &download.tag)]),
+                Some("ModManager".to_string()),
+            );
+        }
+
+        let mut instance_mods = ModManager::new(self.instance_mods_dir(instance_id))?;
+        let result = instance_mods.install_mod(&download.path);
+        let _ = std::fs::remove_file(&download.path);
+        result?;
+
+        let filename = download.path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let tracked = GitHubMod {
+            source,
+            tag: download.tag,
+            filename,
+        };
+        instance.github_mods.push(tracked.clone());
+        self.instance_manager.update_instance(instance)?;
+        self.log_info(
+            trf("log.github_installed", &[("source", &tracked.source.display()), ("tag", &tracked.tag)]),
+            Some("ModManager".to_string()),
+        );
+        Ok(tracked)
+    }
+
+    // Installs the client, or updates it when the source has a release with
+    // a newer tag. Returns None when the installed client is the latest.
+    pub async fn install_github_client(&mut self, instance_id: Uuid, source: GitHubSource) -> Result<Option<GitHubMod>> {
+        let mut instance = self.instance_manager.get_instance(instance_id).cloned()
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        let client = GitHubClient::new(self.network_manager.clone());
+        let release = client.latest_release(&source).await?;
+        let installed = instance.github_client.as_ref().filter(|tracked| tracked.source.owner == source.owner && tracked.source.repo == source.repo);
+        if installed.is_some_and(|tracked| tracked.tag == release.tag_name) && instance.client_jar().is_some_and(|jar| jar.exists()) {
+            return Ok(None);
+        }
+        let asset = GitHubClient::find_asset(&release, &source)
+            .ok_or_else(|| crate::Error::Other(format!(
+                "Release {} of {} has no asset matching '{}'",
+                release.tag_name, source.display(), source.asset_pattern
+            )))?;
+
+        self.log_info(trf("log.github_downloading", &[("source", &source.display())]), Some("InstanceManager".to_string()));
+        // Downloaded aside first, so a jar that fails its checksum never
+        // replaces the one the instance launches with.
+        let download_dir = self.data_dir.join("cache").join("downloads");
+        let download = client.download_asset(&release, asset, &download_dir).await?;
+        if !download.verified {
+            self.log_warning(
+                trf("log.github_no_checksums", &[("source", &source.display()), ("tag", &download.tag)]),
+                Some("InstanceManager".to_string()),
+            );
+        }
+        let filename = download.path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let client_dir = instance.path.join("client");
+        std::fs::create_dir_all(&client_dir)?;
+        std::fs::copy(&download.path, client_dir.join(&filename))?;
+        let _ = std::fs::remove_file(&download.path);
+        if let Some(previous) = instance.client_jar().filter(|previous| previous != &client_dir.join(&filename)) {
+            let _ = std::fs::remove_file(previous);
+        }
+
+        let tracked = GitHubMod {
+            source,
+            tag: download.tag,
+            filename,
+        };
+        instance.github_client = Some(tracked.clone());
+        self.instance_manager.update_instance(instance)?;
+        self.log_info(
+            trf("log.github_client_installed", &[("source", &tracked.source.display()), ("tag", &tracked.tag)]),
+            Some("InstanceManager".to_string()),
+        );
+        Ok(Some(tracked))
+    }
+
+    fn pack_manager(&self) -> Result<ResourcePackManager> {
+        let browser = self.pack_browser.as_ref()
+            .ok_or_else(|| crate::Error::Other("Pack manager is not open".to_string()))?;
+        let game_dir = self.instance_manager.game_dir(browser.instance_id);
+        ResourcePackManager::new(&game_dir, browser.kind)
+    }
+
+    pub fn open_pack_manager(&mut self, instance_id: Uuid) -> Result<()> {
+        self.pack_browser = Some(PackBrowser::new(instance_id));
+        self.refresh_packs()?;
+        self.state = AppState::PackManager;
+        Ok(())
+    }
+
+    pub fn refresh_packs(&mut self) -> Result<()> {
+        let packs = self.pack_manager()?.list_packs()?;
+        if let Some(browser) = self.pack_browser.as_mut() {
+            browser.packs = packs;
+        }
+        Ok(())
+    }
+
+    pub fn switch_pack_kind(&mut self) -> Result<()> {
+        if let Some(browser) = self.pack_browser.as_mut() {
+            browser.kind = browser.kind.toggle();
+        }
+        self.refresh_packs()
+    }
+
+    pub fn toggle_pack(&mut self, index: usize) -> Result<bool> {
+        let manager = self.pack_manager()?;
+        let pack = self.pack_browser.as_ref()
+            .and_then(|browser| browser.packs.get(index))
+            .cloned()
+            .ok_or_else(|| crate::Error::Other("No pack selected".to_string()))?;
+        manager.set_enabled(&pack, !pack.enabled)?;
+        self.log_info(
+            trf(if pack.enabled { "log.pack_disabled" } else { "log.pack_enabled" }, &[("file", &pack.file_name)]),
+            Some("InstanceManager".to_string()),
+        );
+        self.refresh_packs()?;
+        Ok(!pack.enabled)
+    }
+
+    pub fn remove_pack(&mut self, index: usize) -> Result<String> {
+        let manager = self.pack_manager()?;
+        let pack = self.pack_browser.as_ref()
+            .and_then(|browser| browser.packs.get(index))
+            .cloned()
+            .ok_or_else(|| crate::Error::Other("No pack selected".to_string()))?;
+        manager.remove_pack(&pack)?;
+        self.log_warning(trf("log.pack_removed", &[("file", &pack.file_name)]), Some("InstanceManager".to_string()));
+        self.refresh_packs()?;
+        Ok(pack.file_name)
+    }
+
+    pub fn install_pack(&mut self, source: &str) -> Result<String> {
+        let source = crate::utils::expand_home(source.trim().trim_matches('"'));
+        let installed = self.pack_manager()?.install_pack(&source)?;
+        self.log_info(trf("log.pack_installed", &[("file", &installed)]), Some("InstanceManager".to_string()));
+        self.refresh_packs()?;
+        Ok(installed)
+    }
+
+    fn backup_retention(&self) -> usize {
+        self.settings_manager.get().advanced.backup_retention as usize
+    }
+
+    pub fn backup_instance(&mut self, id: Uuid, reason: BackupReason) -> Result<Backup> {
+        let instance = self.instance_manager.get_instance(id).cloned()
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        self.backup_stored_instance(&instance, reason)
+    }
+
+    fn backup_stored_instance(&mut self, instance: &Instance, reason: BackupReason) -> Result<Backup> {
+        match self.backup_manager.create_backup(instance, reason, self.backup_retention()) {
+            Ok(backup) => {
+                self.log_info(
+                    trf("log.backup_created", &[("name", &instance.name), ("path", &backup.path.display()), ("size", &crate::utils::format_size(backup.size))]),
+                    Some("BackupManager".to_string()),
+                );
+                Ok(backup)
+            }
+            Err(e) => {
+                self.log_error(trf("log.backup_failed", &[("name", &instance.name), ("error", &e)]), Some("BackupManager".to_string()));
+                Err(e)
+            }
+        }
+    }
+
+    pub fn open_console(&mut self, id: Uuid) -> Result<()> {
+        let console = self.launch_manager.console(id)
+            .ok_or_else(|| crate::Error::Launch("Instance has not been launched yet".to_string()))?;
+        self.console = Some((id, console));
+        self.console_input = None;
+        self.console_follow = true;
+        self.state = AppState::Console;
+        Ok(())
+    }
+
+    pub fn close_console(&mut self) {
+        self.console = None;
+        self.console_input = None;
+        self.state = AppState::InstanceList;
+    }
+
+    pub fn send_console_command(&mut self) -> Result<()> {
+        let command = self.console_input.take().unwrap_or_default();
+        if command.trim().is_empty() {
+            return Ok(());
+        }
+        let (id, console) = self.console.as_ref()
+            .ok_or_else(|| crate::Error::Launch("Console is not open".to_string()))?;
+        console.send(command.trim())?;
+        let name = self.instance_manager.get_instance(*id).map(|i| i.name.clone()).unwrap_or_default();
+        self.log_info(trf("log.console_command", &[("name", &name), ("command", &command.trim())]), Some("LaunchManager".to_string()));
+        Ok(())
+    }
+
+    pub fn save_console_log(&self) -> Result<PathBuf> {
+        let (id, console) = self.console.as_ref()
+            .ok_or_else(|| crate::Error::Launch("Console is not open".to_string()))?;
+        let instance = self.instance_manager.get_instance(*id)
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        let path = instance.path
+            .join(".minecraft")
+            .join("logs")
+            .join(format!("console-{}.log", chrono::Local::now().format("%Y%m%d-%H%M%S")));
+        console.save(&path)?;
+        self.log_info(trf("log.console_saved", &[("path", &path.display())]), Some("LaunchManager".to_string()));
+        Ok(path)
+    }
+
+    pub fn open_launch_variants(&mut self, id: Uuid) {
+        self.variants_instance = Some(id);
+        self.state = AppState::LaunchVariants;
+    }
+
+    // Returns how many worlds or servers were found; nothing is opened when
+    // there are none.
+    pub fn open_quick_play(&mut self, id: Uuid, worlds: bool) -> usize {
+        let Some(instance) = self.instance_manager.get_instance(id) else { return 0 };
+        let game_dir = instance.path.join(".minecraft");
+        let targets: Vec<(String, QuickPlay)> = if worlds {
+            crate::quickplay::list_worlds(&game_dir)
+                .into_iter()
+                .map(|name| (name.clone(), QuickPlay::World(name)))
+                .collect()
+        } else {
+            crate::quickplay::list_servers(&game_dir)
+                .into_iter()
+                .map(|server| (format!("{} — {}", server.name, server.address), QuickPlay::Server(server.address)))
+                .collect()
+        };
+        let count = targets.len();
+        if count > 0 {
+            self.quick_play = Some(QuickPlayPicker { instance_id: id, instance_name: instance.name.clone(), worlds, targets });
+            self.state = AppState::QuickPlay;
+        }
+        count
+    }
+
+    pub fn close_quick_play(&mut self) {
+        self.quick_play = None;
+        self.state = AppState::InstanceList;
+    }
+
+    pub fn launch_quick_play(&mut self, index: usize) -> Result<()> {
+        let Some(picker) = self.quick_play.take() else { return Ok(()) };
+        self.state = AppState::InstanceList;
+        let Some((_, target)) = picker.targets.get(index).cloned() else { return Ok(()) };
+        self.launch_instance_with(picker.instance_id, LaunchOptions { quick_play: Some(target), ..LaunchOptions::default() })
+    }
+
+    /// Makes the chosen world or server where the instance always starts, or
+    /// goes back to the title screen if it already was. Returns the new
+    /// target.
+    pub fn toggle_default_quick_play(&mut self, index: usize) -> Result<Option<QuickPlay>> {
+        let Some((_, target)) = self.quick_play.as_ref().and_then(|picker| picker.targets.get(index)).cloned() else {
+            return Ok(None);
+        };
+        let id = self.quick_play.as_ref().map(|picker| picker.instance_id).unwrap_or_default();
+        let mut instance = self.instance_manager.get_instance(id).cloned()
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        instance.quick_play = if instance.quick_play.as_ref() == Some(&target) { None } else { Some(target) };
+        let quick_play = instance.quick_play.clone();
+        self.instance_manager.update_instance(instance)?;
+        Ok(quick_play)
+    }
+
+    pub fn launch_variant_names(&self) -> Vec<String> {
+        self.variants_instance
+            .and_then(|id| self.instance_manager.get_instance(id))
+            .map(|instance| instance.launch_variants.iter().map(|v| v.name.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn save_launch_variant(&mut self, id: Uuid, name: String) -> Result<()> {
+        let mut instance = self.instance_manager.get_instance(id).cloned()
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        let mut variant = instance.snapshot_variant(name.clone());
+        match instance.launch_variants.iter_mut().find(|v| v.name == name) {
+            Some(existing) => {
+                variant.wrapper_command = existing.wrapper_command.take();
+                variant.env = std::mem::take(&mut existing.env);
+                *existing = variant;
+            }
+            None => instance.launch_variants.push(variant),
+        }
+        let instance_name = instance.name.clone();
+        self.instance_manager.update_instance(instance)?;
+        self.log_info(trf("log.variant_saved", &[("variant", &name), ("name", &instance_name)]), Some("InstanceManager".to_string()));
+        Ok(())
+    }
+
+    pub fn delete_launch_variant(&mut self, id: Uuid, index: usize) -> Result<String> {
+        let mut instance = self.instance_manager.get_instance(id).cloned()
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        if index >= instance.launch_variants.len() {
+            return Err(crate::Error::Instance("Launch variant not found".to_string()));
+        }
+        let variant = instance.launch_variants.remove(index);
+        self.instance_manager.update_instance(instance)?;
+        self.log_info(trf("log.variant_removed", &[("variant", &variant.name)]), Some("InstanceManager".to_string()));
+        Ok(variant.name)
+    }
+
+    pub fn open_backups(&mut self) -> Result<()> {
+        self.refresh_backups()?;
+        self.state = AppState::Backups;
+        Ok(())
+    }
+
+    pub fn refresh_backups(&mut self) -> Result<()> {
+        self.backups = self.backup_manager.list_backups()?;
+        Ok(())
+    }
+
+    pub fn restore_backup(&mut self, index: usize) -> Result<String> {
+        let backup = self.backups.get(index).cloned()
+            .ok_or_else(|| crate::Error::Other("No backup selected".to_string()))?;
+        if self.launch_manager.running_session_count(backup.instance_id) > 0 {
+            return Err(crate::Error::Instance(format!("Instance '{}' is running", backup.instance_name)));
+        }
+
+        // The safety copy is pruned only after the restore, so restoring the
+        // oldest backup at the retention limit cannot delete it first.
+        if let Some(current) = self.instance_manager.get_instance(backup.instance_id).cloned() {
+            match self.backup_manager.write_backup(&current, BackupReason::Restore) {
+                Ok(safety) => self.log_info(
+                    trf("log.backup_created", &[("name", &current.name), ("path", &safety.path.display()), ("size", &crate::utils::format_size(safety.size))]),
+                    Some("BackupManager".to_string()),
+                ),
+                Err(e) => {
+                    self.log_error(trf("log.backup_failed", &[("name", &current.name), ("error", &e)]), Some("BackupManager".to_string()));
+                    return Err(e);
+                }
+            }
+        }
+
+        let instances_dir = self.instance_manager.instances_dir().to_path_buf();
+        let restored = self.backup_manager.restore_backup(&backup, &instances_dir);
+        if let Err(e) = self.backup_manager.prune(backup.instance_id, self.backup_retention()) {
+            self.log_warning(trf("log.backup_prune_failed", &[("error", &e)]), Some("BackupManager".to_string()));
+        }
+        let instance = match restored {
+            Ok(instance) => instance,
+            Err(e) => {
+                self.log_error(trf("log.restore_failed", &[("name", &backup.instance_name), ("error", &e)]), Some("BackupManager".to_string()));
+                return Err(e);
+            }
+        };
+        let name = instance.name.clone();
+        self.instance_manager.update_instance(instance)?;
+        self.disk_usage.invalidate(UsageTarget::Instance(backup.instance_id));
+        if let Some(watcher) = self.dev_watchers.get_mut(&backup.instance_id) {
+            watcher.reset();
+        }
+
+        self.log_info(
+            trf("log.restored", &[("name", &name), ("date", &backup.created_at.format("%Y-%m-%d %H:%M:%S"))]),
+            Some("BackupManager".to_string()),
+        );
+        self.refresh_backups()?;
+        Ok(name)
+    }
+
+    pub fn delete_backup(&mut self, index: usize) -> Result<()> {
+        let backup = self.backups.get(index).cloned()
+            .ok_or_else(|| crate::Error::Other("No backup selected".to_string()))?;
+        self.backup_manager.delete_backup(&backup)?;
+        self.log_warning(trf("log.backup_removed", &[("path", &backup.path.display())]), Some("BackupManager".to_string()));
+        self.refresh_backups()
     }
 
-    pub async fn force_refresh_versions(&mut self) -> Result<()> {
-        self.log_info("Принудительное обновление списка версий...".to_string(), Some("VersionManager".to_string()));
-        self.version_manager.force_refresh_manifest().await?;
-        self.log_info(format!("Список версий обновлен! Загружено {} версий", self.version_manager.get_versions().len()), Some("VersionManager".to_string()));
+    pub fn open_instance_link(&mut self, id: Uuid, link: InstanceLink) -> Result<()> {
+        let instance = self.instance_manager.get_instance(id)
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        let url = instance.links.get(link)
+            .ok_or_else(|| crate::Error::Instance(format!("Instance '{}' has no {:?} link", instance.name, link)))?
+            .to_string();
+
+        crate::platform::open_url(&url)?;
+        self.log_info(trf("log.link_opened", &[("url", &url)]), Some("InstanceManager".to_string()));
+        self.current_state = trf("status.opened_url", &[("url", &url)]);
         Ok(())
     }
 
-    pub fn get_instances(&self) -> Vec<&Instance> {
-        self.instance_manager.list_instances()
+    pub async fn check_mojang_status(&mut self) -> ServiceHealth {
+        self.log_info(tr("log.mojang_checking").to_string(), Some("NetworkManager".to_string()));
+        let status = MojangStatus::check(&self.network_manager).await;
+        self.record_mojang_status(status)
     }
 
-    pub fn create_instance(&mut self, name: String, version: String) -> Result<Uuid> {
-        self.log_info(format!("Создание экземпляра '{}' версии {}", name, version), Some("InstanceManager".to_string()));
-        match self.instance_manager.create_instance(name.clone(), version.clone()) {
-            Ok(id) => {
-                self.log_info(format!("Экземпляр '{}' успешно создан", name), Some("InstanceManager".to_string()));
-                Ok(id)
+    fn record_mojang_status(&mut self, status: MojangStatus) -> ServiceHealth {
+        let health = status.health();
+
+        for endpoint in status.failing() {
+            self.log_warning(trf("log.mojang_endpoint_down", &[("name", &endpoint.name), ("url", &endpoint.url), ("detail", &endpoint.detail)]), Some("NetworkManager".to_string()));
+        }
+        match health {
+            ServiceHealth::Operational => self.log_info(tr("log.mojang_up").to_string(), Some("NetworkManager".to_string())),
+            ServiceHealth::Degraded => self.log_warning(tr("log.mojang_degraded").to_string(), Some("NetworkManager".to_string())),
+            ServiceHealth::Down => self.log_error(tr("log.mojang_down").to_string(), Some("NetworkManager".to_string())),
+            ServiceHealth::Offline => self.log_error(tr("log.mojang_offline").to_string(), Some("NetworkManager".to_string())),
+        }
+
+        self.mojang_status = Some(status);
+        health
+    }
+
+    // A request that could not connect is the first sign of a dropped
+    // connection: it triggers a probe, and while offline the raw network
+    // error gives way to an explanation.
+    pub fn report_error(&mut self, status: String, error: &crate::Error) {
+        if let crate::Error::Network(e) = error {
+            if self.offline_mode {
+                let offline = crate::Error::Offline(e.to_string());
+                self.current_state = tr("status.offline_unavailable").to_string();
+                self.error_hint = offline.user_hint();
+                return;
             }
-            Err(e) => {
-                self.log_error(format!("Ошибка создания экземпляра '{}': {}", name, e), Some("InstanceManager".to_string()));
-                Err(e)
+            if e.is_connect() || e.is_timeout() {
+                self.start_connectivity_check();
             }
         }
+        self.current_state = status;
+        self.error_hint = error.user_hint();
     }
 
-    pub fn delete_instance(&mut self, id: Uuid) -> Result<()> {
-        if let Some(instance) = self.instance_manager.get_instance(id) {
-            let name = instance.name.clone();
-            self.log_warning(format!("Удаление экземпляра '{}'", name), Some("InstanceManager".to_string()));
-            match self.instance_manager.delete_instance(id) {
-                Ok(_) => {
-                    self.log_info(format!("Экземпляр '{}' успешно удален", name), Some("InstanceManager".to_string()));
-                    Ok(())
-                }
-                Err(e) => {
-                    self.log_error(format!("Ошибка удаления экземпляра '{}': {}", name, e), Some("InstanceManager".to_string()));
-                    Err(e)
-                }
+    fn explain_with_health(&self, error: crate::Error, health: ServiceHealth) -> crate::Error {
+        if !matches!(error, crate::Error::Network(_) | crate::Error::Other(_)) {
+            return error;
+        }
+
+        match health {
+            ServiceHealth::Down | ServiceHealth::Degraded => {
+                let summary = self.mojang_status.as_ref().map(|s| s.summary()).unwrap_or_default();
+                crate::Error::ServiceOutage(format!("{} ({})", summary, error))
             }
-        } else {
-            self.log_error("Попытка удалить несуществующий экземпляр".to_string(), Some("InstanceManager".to_string()));
-            Err(crate::Error::Other("Instance not found".to_string()))
+            ServiceHealth::Offline => crate::Error::Other(format!("No internet connection ({})", error)),
+            ServiceHealth::Operational => error,
         }
     }
 
-    pub async fn launch_instance(&mut self, id: Uuid) -> Result<()> {
-        if let Some(instance) = self.instance_manager.get_instance(id).cloned() {
-            let instance_name = instance.name.clone();
-            self.current_state = format!("Запуск {}...", instance_name);
-            self.log_info(format!("Запуск экземпляра '{}'", instance_name), Some("LaunchManager".to_string()));
-            
-            if !self.version_manager.is_version_installed(&instance.minecraft_version) {
-                self.current_state = format!("Версия {} не скачана!", instance.minecraft_version);
-                self.log_error(format!("Версия {} не установлена для экземпляра '{}'", instance.minecraft_version, instance_name), Some("LaunchManager".to_string()));
-                return Err(crate::Error::Other(format!("Version {} not installed", instance.minecraft_version)));
-            }
-            
-            let account = self.auth_manager.get_default_account()
-                .ok_or_else(|| crate::Error::Auth("No default account set".to_string()))?;
-            
-            let java = self.java_manager.get_default_installation()
-                .ok_or_else(|| crate::Error::Java("No Java installation found".to_string()))?;
-            
-            match self.launch_manager.launch_minecraft(&instance, account, java, &self.version_manager, &self.data_dir).await {
-                Ok(_) => {
-                    self.current_state = format!("{} запущен!", instance_name);
-                    self.log_info(format!("Экземпляр '{}' успешно запущен", instance_name), Some("LaunchManager".to_string()));
-                }
-                Err(e) => {
-                    self.current_state = format!("Ошибка запуска {}: {}", instance_name, e);
-                    self.log_error(format!("Ошибка запуска экземпляра '{}': {}", instance_name, e), Some("LaunchManager".to_string()));
-                    return Err(e);
-                    }
-                }
-        } else {
-            return Err(crate::Error::Instance("Instance not found".to_string()));
+    fn instance_mods_dir(&self, id: Uuid) -> PathBuf {
+        self.instance_manager.game_dir(id).join("mods")
+    }
+
+    pub fn start_mod_bisect(&mut self, id: Uuid) -> Result<()> {
+        if self.bisect.as_ref().is_some_and(|s| s.instance_id == id) {
+            self.state = AppState::ModBisect;
+            return Ok(());
         }
+        self.abort_mod_bisect();
+
+        let instance = self.instance_manager.get_instance(id)
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        let session = BisectSession::start(id, instance.name.clone(), self.instance_mods_dir(id))?;
+        self.log_info(
+            trf("log.bisect_started", &[("name", &session.instance_name), ("count", &session.total_mods)]),
+            Some("ModManager".to_string()),
+        );
+        self.current_state = trf("status.bisect_round", &[("round", &session.round)]);
+        self.bisect = Some(session);
+        self.state = AppState::ModBisect;
         Ok(())
     }
 
-    pub async fn download_version(&mut self, version_id: &str) -> Result<()> {
-        self.log_info(format!("Начинаю загрузку версии {}", version_id), Some("VersionManager".to_string()));
-        
-        let version = self.version_manager.get_versions()
-            .iter()
-            .find(|v| v.id == version_id)
-            .ok_or_else(|| crate::Error::Version(format!("Version {} not found", version_id)))?
-            .clone();
-        
-        match self.version_manager.download_version(&version).await {
-            Ok(_) => {
-                self.log_info(format!("Версия {} успешно загружена", version_id), Some("VersionManager".to_string()));
-                
-                if let Ok(version_details) = self.version_manager.get_version_details(version_id) {
-                    if let Some(assets_id) = &version_details.assets {
-                        self.log_info(format!("Загрузка ассетов для версии {}", version_id), Some("AssetsManager".to_string()));
-                        let assets_url = format!("https://launchermeta.mojang.com/v1/packages/{}/legacy.json", assets_id);
-                        
-                        match self.assets_manager.download_assets(assets_id, &assets_url).await {
-                            Ok(_) => {
-                                self.log_info(format!("Ассеты для версии {} успешно загружены", version_id), Some("AssetsManager".to_string()));
-                            }
-                            Err(e) => {
-                                self.log_warning(format!("Ошибка загрузки ассетов для версии {}: {}", version_id, e), Some("AssetsManager".to_string()));
-                            }
-                        }
-                    }
-                }
-                
-                Ok(())
+    pub fn launch_bisect_round(&mut self) -> Result<()> {
+        let session = self.bisect.as_ref()
+            .ok_or_else(|| crate::Error::Mod("No bisect session".to_string()))?;
+        if session.running || session.finished {
+            return Ok(());
+        }
+
+        let instance_id = session.instance_id;
+        if self.instance_manager.get_instance(instance_id).is_some_and(|i| !i.unapproved_commands().is_empty()) {
+            return Err(crate::Error::Launch(
+                "Instance has unreviewed pack commands; launch it normally once to approve or skip them".to_string(),
+            ));
+        }
+        let round = session.round;
+        let testing = session.testing.clone();
+        session.apply()?;
+        self.log_info(
+            trf("log.bisect_round", &[("round", &round), ("count", &testing.len()), ("mods", &testing.join(", "))]),
+            Some("ModManager".to_string()),
+        );
+
+        self.launch_instance(instance_id)?;
+        if let Some(session) = self.bisect.as_mut() {
+            session.running = true;
+        }
+        Ok(())
+    }
+
+    pub fn answer_bisect(&mut self, crashed: bool) {
+        let Some(session) = self.bisect.as_mut() else { return };
+        if session.running || !session.tested || session.finished {
+            return;
+        }
+
+        let round = session.round;
+        session.record_result(crashed);
+        let finished = session.finished;
+        let culprit = session.culprit.clone();
+        let suspects = session.suspects.len();
+        let instance_name = session.instance_name.clone();
+
+        self.log_info(
+            trf(if crashed { "log.bisect_crashed" } else { "log.bisect_passed" }, &[("round", &round)]),
+            Some("ModManager".to_string()),
+        );
+
+        if !finished {
+            self.current_state = trf("status.bisect_suspects_left", &[("count", &suspects)]);
+            return;
+        }
+
+        match culprit {
+            Some(name) => {
+                self.log_warning(trf("log.bisect_found", &[("name", &instance_name), ("mod", &name)]), Some("ModManager".to_string()));
+                self.current_state = trf("status.bisect_culprit_found", &[("name", &name)]);
             }
-            Err(e) => {
-                self.log_error(format!("Ошибка загрузки версии {}: {}", version_id, e), Some("VersionManager".to_string()));
-                Err(e.into())
+            None => {
+                self.log_warning(
+                    trf("log.bisect_not_found", &[("name", &instance_name)]),
+                    Some("ModManager".to_string()),
+                );
+                self.current_state = tr("status.bisect_no_culprit").to_string();
             }
         }
+        self.restore_bisect_mods();
     }
 
-    pub fn get_available_versions(&self) -> &[MinecraftVersion] {
-        self.version_manager.get_versions()
+    pub fn abort_mod_bisect(&mut self) {
+        if let Some(session) = &self.bisect {
+            if !session.finished {
+                self.log_info(trf("log.bisect_aborted", &[("name", &session.instance_name)]), Some("ModManager".to_string()));
+                self.restore_bisect_mods();
+            }
+        }
+        self.bisect = None;
     }
 
-    pub fn get_profiles(&self) -> Vec<&Profile> {
-        self.profile_manager.list_profiles()
+    fn restore_bisect_mods(&mut self) {
+        let Some(session) = &self.bisect else { return };
+        match session.finish() {
+            Ok(restored) => self.log_info(trf("log.bisect_restored", &[("count", &restored)]), Some("ModManager".to_string())),
+            Err(e) => self.log_error(trf("log.bisect_restore_failed", &[("error", &e)]), Some("ModManager".to_string())),
+        }
     }
 
-    pub fn get_active_profile(&self) -> Option<&Profile> {
-        self.profile_manager.get_active_profile()
+    pub fn resolve_command_approval(&mut self, approve: bool) -> Result<()> {
+        let Some(approval) = self.command_approval.take() else {
+            return Ok(());
+        };
+        self.state = approval.return_state;
+        let mut options = approval.options;
+
+        if approve {
+            let mut instance = self.instance_manager.get_instance(approval.instance_id).cloned()
+                .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+            for (_, command) in &approval.commands {
+                self.log_warning(
+                    trf("log.command_approved", &[("name", &approval.instance_name), ("command", &command)]),
+                    Some("LaunchManager".to_string()),
+                );
+                instance.approved_commands.push(command.clone());
+            }
+            self.instance_manager.update_instance(instance)?;
+        } else {
+            options.skip_commands = true;
+            self.log_info(
+                trf("log.commands_skipped", &[("name", &approval.instance_name)]),
+                Some("LaunchManager".to_string()),
+            );
+        }
+
+        self.launch_instance_with(approval.instance_id, options)
     }
 
-    pub fn create_profile(&mut self, name: String) -> Result<Uuid> {
-        self.profile_manager.create_profile(name, "Player".to_string())
+    pub fn resolve_launch_conflict(&mut self, proceed: bool) -> Result<()> {
+        let Some(conflict) = self.launch_conflict.take() else {
+            return Ok(());
+        };
+        self.state = conflict.return_state;
+        if !proceed {
+            self.current_state = tr("status.launch_cancelled").to_string();
+            return Ok(());
+        }
+
+        self.log_warning(
+            trf("log.directory_shared", &[("name", &conflict.instance_name)]),
+            Some("LaunchManager".to_string()),
+        );
+        let mut options = conflict.options;
+        options.allow_shared_directories = true;
+        self.launch_instance_with(conflict.instance_id, options)
     }
 
-    pub fn delete_profile(&mut self, id: Uuid) -> Result<()> {
-        self.profile_manager.delete_profile(id)
+    pub fn take_account_picker_focus(&mut self) -> Option<usize> {
+        self.account_picker.as_mut().and_then(|picker| picker.focus.take())
     }
 
-    pub fn get_settings(&self) -> &Settings {
-        self.settings_manager.get()
+    pub fn pick_launch_account(&mut self, index: usize) -> Result<()> {
+        let Some(picker) = self.account_picker.take_if(|picker| index < picker.accounts.len()) else {
+            return Ok(());
+        };
+        self.state = picker.return_state;
+        let mut options = picker.options;
+        options.account = Some(picker.accounts[index]);
+        self.launch_instance_with(picker.instance_id, options)
     }
 
-    pub fn get_settings_mut(&mut self) -> &mut Settings {
-        self.settings_manager.get_mut()
+    pub fn cancel_account_picker(&mut self) {
+        if let Some(picker) = self.account_picker.take() {
+            self.state = picker.return_state;
+            self.current_state = tr("status.launch_cancelled").to_string();
+        }
     }
 
-    pub fn save_settings(&mut self) -> Result<()> {
-        self.settings_manager.save()
+    pub fn cancel_command_approval(&mut self) {
+        if let Some(approval) = self.command_approval.take() {
+            self.state = approval.return_state;
+            self.current_state = tr("status.launch_cancelled").to_string();
+        }
     }
 
-    pub fn quit(&mut self) {
-        self.should_quit = true;
+    fn recover_safe_mode_instances(&mut self) {
+        let mods_dirs: Vec<(String, PathBuf)> = self.instance_manager.list_instances()
+            .iter()
+            .map(|instance| (instance.name.clone(), self.instance_mods_dir(instance.id)))
+            .filter(|(_, mods_dir)| ModManager::is_in_safe_mode(mods_dir))
+            .collect();
+
+        for (name, mods_dir) in mods_dirs {
+            match ModManager::exit_safe_mode(&mods_dir) {
+                Ok(restored) => self.log_warning(
+                    trf("log.safe_mode_kept", &[("name", &name), ("count", &restored)]),
+                    Some("ModManager".to_string()),
+                ),
+                Err(e) => self.log_error(
+                    trf("log.safe_mode_restore_instance_failed", &[("name", &name), ("error", &e)]),
+                    Some("ModManager".to_string()),
+                ),
+            }
+        }
+    }
+
+    pub fn open_crash_report(&mut self) -> Result<()> {
+        let target = self.last_crash
+            .as_ref()
+            .and_then(|report| report.open_target())
+            .map(|path| path.to_path_buf())
+            .ok_or_else(|| crate::Error::Other("No crash report available".to_string()))?;
+        crate::platform::open_path(&target)?;
+        self.current_state = trf("status.opened_path", &[("path", &target.display())]);
+        Ok(())
     }
 
     pub fn toggle_logs(&mut self) {
         self.show_logs = !self.show_logs;
+        self.log_scroll = 0;
+    }
+
+    // Scroll offset counts lines back from the newest entry.
+    pub fn scroll_logs(&mut self, older: bool, lines: usize) {
+        let max = self.log_manager.get_recent_entries(LOG_SCROLLBACK).len();
+        self.log_scroll = if older {
+            (self.log_scroll + lines).min(max.saturating_sub(1))
+        } else {
+            self.log_scroll.saturating_sub(lines)
+        };
     }
 
+    // These go through tracing like the rest of the launcher, so
+    // `advanced.log_level` filters them too; `source` is what the log shows
+    // in place of the module.
     pub fn log_info(&self, message: String, source: Option<String>) {
-        self.log_manager.info(message, source);
+        tracing::info!(source = source.as_deref(), "{}", message);
     }
 
     pub fn log_warning(&self, message: String, source: Option<String>) {
-        self.log_manager.warning(message, source);
+        tracing::warn!(source = source.as_deref(), "{}", message);
     }
 
     pub fn log_error(&self, message: String, source: Option<String>) {
-        self.log_manager.error(message, source);
+        tracing::error!(source = source.as_deref(), "{}", message);
     }
 
     pub fn log_debug(&self, message: String, source: Option<String>) {
-        self.log_manager.debug(message, source);
+        tracing::debug!(source = source.as_deref(), "{}", message);
     }
 
     pub fn log_launcher(&self, message: String, source: Option<String>) {
-        self.log_manager.launcher(message, source);
+        tracing::info!(launcher = true, source = source.as_deref(), "{}", message);
     }
 
     pub fn add_offline_account(&mut self, username: String) -> Result<Uuid> {
         let account = Account::new_offline(username.clone());
-        self.log_info(format!("Добавление offline аккаунта '{}'", username), Some("AuthManager".to_string()));
-        match self.auth_manager.add_account(account) {
-            Ok(id) => {
-                self.log_info(format!("Offline аккаунт '{}' успешно добавлен", username), Some("AuthManager".to_string()));
-                Ok(id)
-            }
-            Err(e) => {
-                self.log_error(format!("Ошибка добавления offline аккаунта '{}': {}", username, e), Some("AuthManager".to_string()));
-                Err(e)
-            }
-        }
+        self.log_info(trf("log.offline_account_adding", &[("name", &username)]), Some("AuthManager".to_string()));
+        self.auth_manager.add_account(account).inspect_err(|e| {
+            self.log_error(trf("log.offline_account_failed", &[("name", &username), ("error", &e)]), Some("AuthManager".to_string()));
+        })
     }
 
     pub fn add_microsoft_account(&mut self, username: String, display_name: String) -> Result<Uuid> {
         let account = Account::new_microsoft(username.clone(), display_name.clone());
-        self.log_info(format!("Добавление Microsoft аккаунта '{}'", display_name), Some("AuthManager".to_string()));
-        match self.auth_manager.add_account(account) {
-            Ok(id) => {
-                self.log_info(format!("Microsoft аккаунт '{}' успешно добавлен", display_name), Some("AuthManager".to_string()));
-                Ok(id)
-            }
-            Err(e) => {
-                self.log_error(format!("Ошибка добавления Microsoft аккаунта '{}': {}", display_name, e), Some("AuthManager".to_string()));
-                Err(e)
-            }
-        }
+        self.log_info(trf("log.microsoft_account_adding", &[("name", &display_name)]), Some("AuthManager".to_string()));
+        self.auth_manager.add_account(account).inspect_err(|e| {
+            self.log_error(trf("log.microsoft_account_failed", &[("name", &display_name), ("error", &e)]), Some("AuthManager".to_string()));
+        })
     }
 
     pub fn remove_account(&mut self, account_id: Uuid) -> Result<()> {
         if let Some(account) = self.auth_manager.get_account(account_id) {
             let display_name = account.display_name.clone();
-            self.log_warning(format!("Удаление аккаунта '{}'", display_name), Some("AuthManager".to_string()));
-            match self.auth_manager.remove_account(account_id) {
-                Ok(_) => {
-                    self.log_info(format!("Аккаунт '{}' успешно удален", display_name), Some("AuthManager".to_string()));
-                    Ok(())
-                }
-                Err(e) => {
-                    self.log_error(format!("Ошибка удаления аккаунта '{}': {}", display_name, e), Some("AuthManager".to_string()));
-                    Err(e)
-                }
-            }
+            self.skin_cache.invalidate(account);
+            self.log_warning(trf("log.account_removing", &[("name", &display_name)]), Some("AuthManager".to_string()));
+            self.auth_manager.remove_account(account_id).inspect_err(|e| {
+                self.log_error(trf("log.account_remove_failed", &[("name", &display_name), ("error", &e)]), Some("AuthManager".to_string()));
+            })
         } else {
-            self.log_error("Попытка удалить несуществующий аккаунт".to_string(), Some("AuthManager".to_string()));
+            self.log_error(tr("log.account_remove_missing").to_string(), Some("AuthManager".to_string()));
             Err(crate::Error::Auth("Account not found".to_string()))
         }
     }
@@ -371,14 +3387,14 @@ impl App {
     pub fn set_default_account(&mut self, account_id: Uuid) -> Result<()> {
         if let Some(account) = self.auth_manager.get_account(account_id) {
             let display_name = account.display_name.clone();
-            self.log_info(format!("Установка аккаунта '{}' как основного", display_name), Some("AuthManager".to_string()));
+            self.log_info(trf("log.account_activating", &[("name", &display_name)]), Some("AuthManager".to_string()));
             match self.auth_manager.set_default_account(account_id) {
                 Ok(_) => {
-                    self.log_info(format!("Аккаунт '{}' установлен как основной", display_name), Some("AuthManager".to_string()));
+                    self.log_info(trf("log.account_activated", &[("name", &display_name)]), Some("AuthManager".to_string()));
                     Ok(())
                 }
                 Err(e) => {
-                    self.log_error(format!("Ошибка установки аккаунта '{}' как основного: {}", display_name, e), Some("AuthManager".to_string()));
+                    self.log_error(trf("log.account_activate_failed", &[("name", &display_name), ("error", &e)]), Some("AuthManager".to_string()));
                     Err(e)
                 }
             }
@@ -395,6 +3411,24 @@ impl App {
         self.auth_manager.get_default_account()
     }
 
+    // With `remember_instance_account` on, each instance relaunches with the
+    // account it was last played with, as long as that account still exists.
+    pub fn launch_account_for(&self, instance: &Instance) -> Option<&Account> {
+        let remembered = instance.last_account
+            .filter(|_| self.settings_manager.get().general.remember_instance_account)
+            .and_then(|id| self.auth_manager.get_account(id));
+        remembered.or_else(|| self.auth_manager.get_default_account())
+    }
+
+    fn record_account_use(&mut self, instance_id: Uuid, account_id: Uuid) {
+        if let Err(e) = self.auth_manager.update_account_last_used(account_id) {
+            self.log_warning(trf("log.account_usage_failed", &[("error", &e)]), Some("AuthManager".to_string()));
+        }
+        if let Err(e) = self.instance_manager.record_account(instance_id, account_id) {
+            self.log_warning(trf("log.instance_account_failed", &[("error", &e)]), Some("InstanceManager".to_string()));
+        }
+    }
+
     pub async fn authenticate_microsoft_account(&mut self, account_id: Uuid) -> Result<()> {
         self.auth_manager.authenticate_microsoft_account(account_id).await
     }
@@ -420,11 +3454,88 @@ impl App {
             .and_then(|id| self.instance_manager.get_instance_mut(id))
     }
 
+    /// Opens the loader version picker for the editing instance and fetches
+    /// the builds of its loader for its Minecraft version.
+    pub fn open_loader_versions(&mut self) -> Result<()> {
+        let instance = self.get_editing_instance()
+            .ok_or_else(|| crate::Error::Instance("Instance not found".to_string()))?;
+        let loader = instance.mod_loader.clone()
+            .ok_or_else(|| crate::Error::Instance("The instance has no mod loader".to_string()))?;
+        let game_version = instance.minecraft_version.clone();
+
+        self.loader_picker = Some(LoaderVersionPicker {
+            loader: loader.clone(),
+            game_version: game_version.clone(),
+            versions: Vec::new(),
+            ready: Readiness::Pending,
+            query: String::new(),
+            editing: false,
+        });
+        self.state = AppState::LoaderVersions;
+        self.log_info(trf("log.loader_versions_loading", &[("loader", &format!("{:?}", loader)), ("version", &game_version)]), Some("Loaders".to_string()));
+
+        let network = self.network_manager.clone();
+        let reporter = self.begin_task(TaskKind::LoadLoaderVersions);
+        tokio::spawn(async move {
+            let result = crate::loaders::fetch_versions(&network, &loader, &game_version).await;
+            reporter.send(AppEvent::LoaderVersionsLoaded { task: reporter.id(), loader, game_version, result });
+        });
+        Ok(())
+    }
+
+    // Results for a picker that was closed, or reopened for another loader,
+    // are dropped.
+    fn finish_loader_versions(&mut self, loader: ModLoader, game_version: String, result: Result<Vec<LoaderVersion>>) {
+        let Some(picker) = self.loader_picker.as_mut()
+            .filter(|picker| picker.loader == loader && picker.game_version == game_version)
+        else {
+            return;
+        };
+        match result {
+            Ok(versions) => {
+                let count = versions.len();
+                picker.versions = versions;
+                picker.ready = Readiness::Ready;
+                self.log_info(trf("log.loader_versions_loaded", &[("loader", &format!("{:?}", loader)), ("version", &game_version), ("count", &count)]), Some("Loaders".to_string()));
+            }
+            Err(e) => {
+                picker.ready = Readiness::Failed(e.to_string());
+                self.log_warning(trf("log.loader_versions_failed", &[("loader", &format!("{:?}", loader)), ("error", &e)]), Some("Loaders".to_string()));
+                self.report_error(trf("status.loader_versions_failed", &[("error", &e)]), &e);
+            }
+        }
+    }
+
+    /// Sets the editing instance's loader version to the `index`th listed
+    /// build and returns to the editor.
+    pub fn pick_loader_version(&mut self, index: usize) -> Option<String> {
+        let version = self.loader_picker.as_ref()?.visible().get(index)?.id.clone();
+        if let Some(instance) = self.get_editing_instance_mut() {
+            instance.mod_loader_version = Some(version.clone());
+        }
+        self.close_loader_versions();
+        Some(version)
+    }
+
+    pub fn close_loader_versions(&mut self) {
+        self.loader_picker = None;
+        self.state = AppState::EditInstance;
+    }
+
     pub fn save_instance_changes(&mut self) -> Result<()> {
         if let Some(instance_id) = self.editing_instance_id {
             if let Some(instance) = self.instance_manager.get_instance(instance_id).cloned() {
+                let issues = self.lint_java_args(&instance, instance.java_args.as_deref().unwrap_or_default());
+                if let Some(issue) = issues.iter().find(|issue| issue.severity() == crate::jvm_lint::LintSeverity::Error) {
+                    return Err(crate::Error::Instance(format!("Invalid JVM arguments: {}", issue.describe())));
+                }
+                if let Ok(stored) = self.instance_manager.load_stored_instance(instance_id) {
+                    if stored.mod_loader != instance.mod_loader {
+                        self.backup_stored_instance(&stored, BackupReason::LoaderChange)?;
+                    }
+                }
                 self.instance_manager.update_instance(instance)?;
-                self.log_info("Изменения экземпляра сохранены".to_string(), Some("InstanceManager".to_string()));
+                self.log_info(tr("log.instance_saved").to_string(), Some("InstanceManager".to_string()));
                 Ok(())
             } else {
                 Err(crate::Error::Instance("Instance not found".to_string()))
@@ -434,23 +3545,200 @@ impl App {
         }
     }
 
+    pub fn instance_java(&self, instance: &Instance) -> Option<&crate::java::JavaInstallation> {
+        instance.java_path.as_ref()
+            .or_else(|| self.profile_manager.get_active_profile()?.java_path.as_ref())
+            .and_then(|path| self.get_java_installations().values().find(|java| &java.path == path))
+            .or_else(|| self.get_default_java())
+    }
+
+    pub fn effective_settings(&self, instance: &Instance) -> EffectiveSettings {
+        instance.effective_settings(self.profile_manager.get_active_profile(), self.settings_manager.get())
+    }
+
+    pub fn instance_java_major(&self, instance: &Instance) -> Option<u32> {
+        self.instance_java(instance).and_then(|java| java.major_version())
+    }
+
+    /// Java major the instance's Minecraft version needs: what its version
+    /// JSON asks for once installed, an estimate from the version id before.
+    pub fn required_java_major(&self, instance: &Instance) -> Option<u32> {
+        self.version_manager.get_version_details(&instance.minecraft_version).ok()
+            .and_then(|details| details.java_version)
+            .map(|version| version.major_version.max(0) as u32)
+            .or_else(|| self.java_manager.get_recommended_java_for_minecraft(&instance.minecraft_version).map(u32::from))
+    }
+
+    pub fn java_mismatch(&self, instance: &Instance) -> Option<JavaMismatch<'_>> {
+        if self.java_ready.is_pending() {
+            return None;
+        }
+        let required = self.required_java_major(instance)?;
+        let current = self.instance_java(instance);
+        match current.map(|java| java.major_version()) {
+            Some(None) => return None,
+            Some(Some(major)) if major >= required => return None,
+            _ => {}
+        }
+        let mut suggestions: Vec<_> = self.get_java_installations().values()
+            .filter(|java| java.major_version().is_some_and(|major| major >= required))
+            .collect();
+        suggestions.sort_by(|a, b| a.major_version().cmp(&b.major_version()).then_with(|| a.path.cmp(&b.path)));
+        Some(JavaMismatch { required, current, suggestions })
+    }
+
+    pub fn instance_mod_count(&self, id: Uuid) -> usize {
+        ModManager::list_enabled_mod_files(&self.instance_mods_dir(id))
+            .map(|files| files.len())
+            .unwrap_or(0)
+    }
+
+    // The preset's flags are linted together with the custom arguments so a
+    // second collector or a flag the chosen Java lacks shows up here.
+    pub fn lint_java_args(&self, instance: &Instance, args: &str) -> Vec<LintIssue> {
+        let java_major = self.instance_java_major(instance);
+        let effective = self.effective_settings(instance);
+        let memory_max_mb = effective.memory_max.value;
+        let preset_args = instance.gc_preset
+            .map(|preset| preset.args(java_major, memory_max_mb).join(" "))
+            .unwrap_or_default();
+        let context = LintContext {
+            java_major,
+            memory_min_mb: effective.memory_min.value,
+            memory_max_mb,
+        };
+        crate::jvm_lint::lint_jvm_args(&format!("{} {}", preset_args, args), &context)
+    }
+
+    pub fn editing_java_args_lint(&self) -> Vec<LintIssue> {
+        let Some(instance) = self.get_editing_instance() else { return Vec::new() };
+        let args = self.java_args_input.clone()
+            .unwrap_or_else(|| self.effective_settings(instance).java_args.value);
+        self.lint_java_args(instance, &args)
+    }
+
+    pub fn apply_java_args_input(&mut self) -> Vec<LintIssue> {
+        let Some(input) = self.java_args_input.take() else { return Vec::new() };
+        let args = Some(input.trim().to_string()).filter(|args| !args.is_empty());
+        if let Some(instance) = self.get_editing_instance_mut() {
+            instance.java_args = args;
+        }
+        self.editing_java_args_lint()
+    }
+
     pub fn cancel_instance_editing(&mut self) {
+        self.java_args_input = None;
         self.editing_instance_id = None;
         self.state = AppState::InstanceList;
     }
 
     pub async fn scan_java_installations(&mut self) -> Result<()> {
-        self.log_info("Сканирование установок Java...".to_string(), Some("JavaManager".to_string()));
+        self.log_info(tr("log.java_scanning").to_string(), Some("JavaManager".to_string()));
         self.java_manager.update_java_directory(Some(self.settings_manager.get().general.java_directory.clone()));
+        self.java_manager.set_preferences(&self.settings_manager.get().java);
         match self.java_manager.scan_java_installations().await {
             Ok(_) => {
                 let count = self.java_manager.get_installations().len();
-                self.log_info(format!("Найдено {} установок Java", count), Some("JavaManager".to_string()));
+                self.log_info(trf("log.java_found", &[("count", &count)]), Some("JavaManager".to_string()));
                 Ok(())
             }
             Err(e) => {
-                self.log_error(format!("Ошибка сканирования Java: {}", e), Some("JavaManager".to_string()));
-                Err(e.into())
+                self.log_error(trf("log.java_scan_failed", &[("error", &e)]), Some("JavaManager".to_string()));
+                Err(e)
+            }
+        }
+    }
+
+    /// Checks the Java at `path` by running it and adds it to the list, which
+    /// keeps it across scans. Returns the key it is listed under.
+    pub async fn add_java_installation(&mut self, path: PathBuf) -> Result<String> {
+        let installation = self.java_manager.inspect(&path).await?;
+        self.log_info(trf("log.java_added", &[("version", &installation.version), ("path", &installation.path.display())]), Some("JavaManager".to_string()));
+        self.remember_java(&installation.path)?;
+        Ok(self.java_manager.insert_installation(installation))
+    }
+
+    // Custom installations are stored by executable so a scan finds the
+    // same entry again; one removed earlier is no longer hidden.
+    fn remember_java(&mut self, path: &Path) -> Result<()> {
+        let path = path.to_path_buf();
+        self.settings_manager.set_java_setting(|java| {
+            java.hidden_installations.retain(|hidden| *hidden != path);
+            if !java.custom_installations.contains(&path) {
+                java.custom_installations.push(path);
+            }
+        });
+        self.settings_manager.save()?;
+        self.java_manager.set_preferences(&self.settings_manager.get().java);
+        Ok(())
+    }
+
+    /// Takes an installation off the list. One added by hand is forgotten;
+    /// a detected one is hidden from later scans.
+    pub fn remove_java_installation(&mut self, key: &str) -> Result<()> {
+        let installation = self.java_manager.remove_installation(key)
+            .ok_or_else(|| crate::Error::Java("Installation not found".to_string()))?;
+        let path = installation.path;
+        self.settings_manager.set_java_setting(|java| {
+            let custom = java.custom_installations.len();
+            java.custom_installations.retain(|custom| *custom != path);
+            if java.custom_installations.len() == custom && !java.hidden_installations.contains(&path) {
+                java.hidden_installations.push(path.clone());
+            }
+            if java.default_installation.as_ref() == Some(&path) {
+                java.default_installation = None;
+            }
+        });
+        self.settings_manager.save()?;
+        self.java_manager.set_preferences(&self.settings_manager.get().java);
+        self.log_info(trf("log.java_removed", &[("path", &path.display())]), Some("JavaManager".to_string()));
+        Ok(())
+    }
+
+    pub fn set_default_java(&mut self, key: &str) -> Result<()> {
+        let path = self.java_manager.get_installation(key)
+            .map(|installation| installation.path.clone())
+            .ok_or_else(|| crate::Error::Java("Installation not found".to_string()))?;
+        self.update_setting(SettingField::DefaultJava, SettingValue::OptionalPath(Some(path)))
+    }
+
+    /// Downloads a Temurin JRE of `version` from Adoptium into the Java
+    /// directory in the background and adds it once it runs.
+    pub fn start_java_download(&mut self, version: u8) {
+        let kind = TaskKind::DownloadJava(version);
+        if self.tasks.is_running(&kind) {
+            return;
+        }
+        self.log_info(trf("log.java_downloading", &[("version", &version)]), Some("JavaManager".to_string()));
+        let network = self.network_manager.clone();
+        let directory = self.settings_manager.get().general.java_directory.clone();
+        let reporter = self.begin_task(kind);
+        let span = tracing::info_span!("download_java", version);
+        tokio::spawn(async move {
+            let result = match crate::java::download_java(&network, &directory, version, &reporter).await {
+                Ok(home) => match JavaManager::new(None) {
+                    Ok(manager) => manager.inspect(&home).await,
+                    Err(e) => Err(e),
+                },
+                Err(e) => Err(e),
+            };
+            reporter.send(AppEvent::JavaDownloaded { task: reporter.id(), version, result });
+        }.instrument(span));
+    }
+
+    fn finish_java_download(&mut self, version: u8, result: Result<crate::java::JavaInstallation>) {
+        let added = result.and_then(|installation| {
+            self.remember_java(&installation.path)?;
+            Ok(self.java_manager.insert_installation(installation))
+        });
+        match added {
+            Ok(key) => {
+                self.java_ready = Readiness::Ready;
+                self.current_state = trf("status.java_downloaded", &[("java", &key)]);
+            }
+            Err(e) => {
+                self.log_error(trf("log.java_download_failed", &[("version", &version), ("error", &e)]), Some("JavaManager".to_string()));
+                self.report_error(trf("status.java_download_failed", &[("error", &e)]), &e);
             }
         }
     }
@@ -466,28 +3754,45 @@ impl App {
     pub fn toggle_version_mode(&mut self) {
         self.show_installed_only = !self.show_installed_only;
         if self.show_installed_only {
-            self.current_state = "Показываются скачанные версии".to_string();
+            self.current_state = tr("status.showing_installed").to_string();
         } else {
-            self.current_state = "Показываются все доступные версии".to_string();
+            self.current_state = tr("status.showing_all").to_string();
         }
     }
 
     pub fn get_displayed_versions(&self) -> Vec<MinecraftVersion> {
-        if self.show_installed_only {
+        let versions = if self.show_installed_only {
             self.version_manager.get_installed_versions()
         } else {
             self.version_manager.get_versions().to_vec()
+        };
+        let mut versions: Vec<MinecraftVersion> = versions.into_iter()
+            .filter(|version| self.version_filter.matches(version))
+            .collect();
+        crate::version::group_by_channel(&mut versions);
+        versions
+    }
+
+    pub fn version_channels_for(&self, instance: &Instance) -> VersionChannels {
+        instance.version_channels.unwrap_or_else(|| self.settings_manager.get().ui.version_channels())
+    }
+
+    pub fn toggle_experimental_versions(&mut self) -> Result<VersionChannels> {
+        let enable = !self.settings_manager.get().ui.version_channels().is_experimental();
+        for field in [SettingField::ShowSnapshots, SettingField::ShowLegacyVersions, SettingField::ShowAprilFools] {
+            self.update_setting(field, SettingValue::Bool(enable))?;
         }
+        Ok(self.settings_manager.get().ui.version_channels())
     }
 
     pub fn change_account_name(&mut self, account_id: Uuid, new_name: String) -> Result<()> {
         match self.auth_manager.change_account_name(account_id, new_name.clone()) {
             Ok(_) => {
-                self.log_info(format!("Ник аккаунта изменен на '{}'", new_name), Some("AuthManager".to_string()));
+                self.log_info(trf("log.nickname_changed", &[("name", &new_name)]), Some("AuthManager".to_string()));
                 Ok(())
             }
             Err(e) => {
-                self.log_error(format!("Ошибка изменения ника: {}", e), Some("AuthManager".to_string()));
+                self.log_error(trf("log.nickname_failed", &[("error", &e)]), Some("AuthManager".to_string()));
                 Err(e)
             }
         }
@@ -499,13 +3804,104 @@ impl App {
             settings.advanced.save_logs_to_file,
             Some(settings.advanced.logs_directory.clone())
         );
+        self.log_manager.set_rotation(settings.advanced.log_rotation());
+    }
+
+    pub fn update_log_memory_limit(&self) {
+        let limit_mb = self.settings_manager.get().advanced.log_memory_limit_mb as usize;
+        self.log_manager.set_max_bytes(limit_mb * 1024 * 1024);
+    }
+
+    pub fn update_shared_sources(&mut self) {
+        let shared = SharedSources::new(self.settings_manager.get().general.shared_data_dir.clone());
+        if let Some(dir) = self.settings_manager.get().general.shared_data_dir.as_ref().filter(|_| shared.is_empty()) {
+            self.log_warning(trf("log.shared_dir_missing", &[("path", &dir.display())]), Some("LibraryStore".to_string()));
+        }
+        self.version_manager.set_shared_sources(shared.clone());
+        self.assets_manager.set_shared(shared);
     }
 
     pub fn update_network_settings(&mut self) {
-        let settings = self.settings_manager.get();
-        let max_concurrent = settings.network.max_concurrent_downloads as usize;
-        
-        self.network_manager.set_max_concurrent_downloads(max_concurrent);
-        self.version_manager.set_max_concurrent_downloads(max_concurrent);
+        let settings = self.settings_manager.get().clone();
+        if let Err(e) = self.network_manager.apply_settings(&settings.network) {
+            self.log_error(trf("log.network_settings_failed", &[("error", &e)]), Some("NetworkManager".to_string()));
+        }
+        self.version_manager.set_network(self.network_manager.clone());
+        self.assets_manager.set_network(self.network_manager.clone());
+        self.version_manager.set_max_concurrent_verifications(settings.network.max_concurrent_verifications as usize);
+    }
+} 
+
+struct VersionDownloadJob {
+    installer: VersionInstaller,
+    network: NetworkManager,
+    assets_dir: PathBuf,
+    shared: SharedSources,
+}
+
+impl VersionDownloadJob {
+    async fn run(&self, version: MinecraftVersion, reporter: TaskReporter) {
+        let span = tracing::info_span!("download_version", version = %version.id);
+        self.install(version, reporter).instrument(span).await
     }
-} 
\ No newline at end of file
+
+    async fn install(&self, version: MinecraftVersion, reporter: TaskReporter) {
+        reporter.plan(&[
+            (TaskStage::Metadata, 1),
+            (TaskStage::Client, 3),
+            (TaskStage::Libraries, 4),
+            (TaskStage::Assets, 6),
+        ]);
+        let (result, assets_error) = match self.installer.install(&version, &reporter).await {
+            Ok(details) => {
+                let assets_error = match &details.assets {
+                    Some(assets_id) => {
+                        let assets_url = match &details.asset_index {
+                            Some(index) => index.url.clone(),
+                            None => format!("https://launchermeta.mojang.com/v1/packages/{}/legacy.json", assets_id),
+                        };
+                        let mut assets = AssetsManager::new(self.assets_dir.clone(), self.network.clone());
+                        assets.set_shared(self.shared.clone());
+                        assets.download_assets(assets_id, &assets_url, &reporter.scoped(TaskStage::Assets)).await.err().map(|e| e.to_string())
+                    }
+                    None => None,
+                };
+                (Ok(()), assets_error)
+            }
+            Err(e) => (Err(crate::Error::from(e)), None),
+        };
+        let status = match &result {
+            Err(crate::Error::Network(_) | crate::Error::Other(_)) => Some(MojangStatus::check(&self.network).await),
+            _ => None,
+        };
+        reporter.send(AppEvent::VersionDownloaded { task: reporter.id(), version_id: version.id, result, assets_error, status });
+    }
+}
+
+// A cleanup with everything it needs taken from the app, so it can run off
+// the UI loop.
+enum CleanupJob {
+    Remove(Vec<PathBuf>),
+    OrphanedLibraries(PathBuf, HashSet<PathBuf>),
+    ExpiredCache(NetworkManager),
+    DuplicateAssets(PathBuf),
+}
+
+impl CleanupJob {
+    async fn run(self, reporter: &TaskReporter) -> Result<CleanupResult> {
+        match self {
+            CleanupJob::Remove(paths) => Ok(crate::storage::remove_paths(&paths)),
+            CleanupJob::OrphanedLibraries(libraries_dir, referenced) => {
+                Ok(crate::storage::remove_paths(&crate::storage::orphaned_libraries(&libraries_dir, &referenced)))
+            }
+            CleanupJob::ExpiredCache(network) => {
+                let (removed, freed) = network.cleanup_expired_cache().await?;
+                Ok(CleanupResult { removed, freed })
+            }
+            CleanupJob::DuplicateAssets(assets_dir) => {
+                reporter.plan(&[(TaskStage::AssetObjects, 1)]);
+                crate::assets::dedupe_virtual_assets(&assets_dir, reporter).await
+            }
+        }
+    }
+}
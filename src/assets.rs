@@ -1,17 +1,118 @@
 use std::collections::HashMap;
 
 use std::path::{Path, PathBuf};
+use futures_util::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
+use crate::i18n::trf;
+use crate::utils::file_sha1;
 use crate::Result;
 use crate::network::NetworkManager;
+use crate::shared::{is_linked, link_shared, Link, SharedSources};
+use crate::storage::CleanupResult;
+use crate::tasks::{TaskReporter, TaskStage};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetIndex {
     pub objects: HashMap<String, AssetObject>,
+    #[serde(rename = "virtual")]
     pub virtual_: Option<bool>,
     pub map_to_resources: Option<bool>,
 }
 
+impl AssetIndex {
+    pub fn load(assets_dir: &Path, assets_id: &str) -> Result<Self> {
+        let index_path = assets_dir.join("indexes").join(format!("{}.json", assets_id));
+        let index_content = std::fs::read_to_string(&index_path)?;
+        Ok(serde_json::from_str(&index_content)?)
+    }
+
+    pub fn is_virtual(&self) -> bool {
+        self.virtual_.unwrap_or(false)
+    }
+
+    pub fn maps_to_resources(&self) -> bool {
+        self.map_to_resources.unwrap_or(false)
+    }
+}
+
+/// Directory passed to the game as `${game_assets}`: the hashed object store
+/// for modern indexes, or the flat copy under `virtual/` for legacy ones.
+pub fn game_assets_dir(assets_dir: &Path, assets_id: &str, index: &AssetIndex) -> PathBuf {
+    if index.is_virtual() || index.maps_to_resources() {
+        assets_dir.join("virtual").join(assets_id)
+    } else {
+        assets_dir.to_path_buf()
+    }
+}
+
+/// Links a `map_to_resources` asset tree into `<game dir>/resources`, where
+/// applet-era clients look for sounds and music. Existing files are kept.
+pub fn install_resources(virtual_dir: &Path, game_directory: &Path) -> Result<usize> {
+    let resources_dir = game_directory.join("resources");
+    let mut copied = 0;
+    for entry in walkdir::WalkDir::new(virtual_dir).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(virtual_dir) else { continue };
+        let target = resources_dir.join(relative);
+        if target.exists() {
+            continue;
+        }
+        let source = std::fs::canonicalize(entry.path())?;
+        link_shared(&source, &target)?;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
+/// Replaces full copies under `virtual/`, left by older launcher versions,
+/// with links to the hashed objects. Only files whose hash matches their
+/// index entry are touched; anything else may have been edited on purpose.
+pub async fn dedupe_virtual_assets(assets_dir: &Path, progress: &TaskReporter) -> Result<CleanupResult> {
+    let mut result = CleanupResult::default();
+    let indexes: Vec<(String, AssetIndex)> = std::fs::read_dir(assets_dir.join("virtual"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter_map(|version| match AssetIndex::load(assets_dir, &version) {
+            Ok(index) => Some((version, index)),
+            Err(e) => {
+                tracing::warn!("{}", trf("log.asset_index_unreadable", &[("version", &version), ("error", &e)]));
+                None
+            }
+        })
+        .collect();
+    let total: u64 = indexes.iter().map(|(_, index)| index.objects.len() as u64).sum();
+    let mut done = 0;
+    progress.report(TaskStage::AssetObjects, 0, total);
+    for (version, index) in &indexes {
+        let virtual_dir = assets_dir.join("virtual").join(version);
+        for (name, object) in &index.objects {
+            done += 1;
+            progress.report(TaskStage::AssetObjects, done, total);
+            let copy = virtual_dir.join(name);
+            let is_copy = std::fs::symlink_metadata(&copy).is_ok_and(|metadata| metadata.is_file());
+            let Some(prefix) = object.hash.get(..2) else { continue };
+            let object_path = assets_dir.join("objects").join(prefix).join(&object.hash);
+            if !is_copy || is_linked(&object_path, &copy) || file_sha1(&copy).await? != object.hash {
+                continue;
+            }
+            if !object_path.exists() {
+                // The copy is the only one; it becomes the object instead.
+                link_shared(&copy, &object_path)?;
+                continue;
+            }
+            if link_shared(&object_path, &copy)? != Link::Copy {
+                result.removed += 1;
+                result.freed += object.size;
+            }
+        }
+    }
+    Ok(result)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetObject {
     pub hash: String,
@@ -22,6 +123,7 @@ pub struct AssetsManager {
     assets_dir: PathBuf,
     network: NetworkManager,
     indices_cache: HashMap<String, AssetIndex>,
+    shared: SharedSources,
 }
 
 impl AssetsManager {
@@ -35,36 +137,61 @@ impl AssetsManager {
             assets_dir,
             network,
             indices_cache: HashMap::new(),
+            shared: SharedSources::default(),
         }
     }
 
-    pub async fn download_assets(&mut self, version: &str, asset_index_url: &str) -> Result<()> {
+    pub fn set_network(&mut self, network: NetworkManager) {
+        self.network = network;
+    }
+
+    pub fn set_shared(&mut self, shared: SharedSources) {
+        self.shared = shared;
+    }
+
+    pub async fn download_assets(&mut self, version: &str, asset_index_url: &str, progress: &TaskReporter) -> Result<()> {
+        progress.plan(&[(TaskStage::AssetIndex, 1), (TaskStage::AssetObjects, 9)]);
+        progress.report(TaskStage::AssetIndex, 0, 1);
         let asset_index = self.download_asset_index(version, asset_index_url).await?;
-        
+        progress.report(TaskStage::AssetIndex, 1, 1);
 
-        let objects = asset_index.objects.clone();
-        
-        for (_name, object) in objects {
-            let hash = &object.hash;
-            let asset_path = self.get_asset_path(hash);
-            
-            if !asset_path.exists() {
+        let missing: Vec<(String, PathBuf, String)> = asset_index.objects
+            .values()
+            .map(|object| (object.hash.clone(), self.get_asset_path(&object.hash)))
+            .filter(|(_, asset_path)| !asset_path.exists())
+            .map(|(hash, asset_path)| {
                 let download_url = format!(
                     "https://resources.download.minecraft.net/{}/{}",
                     &hash[..2],
                     hash
                 );
-                
-                self.network.download_file(
-                    &download_url,
-                    &asset_path,
-                    Some(hash),
-                    None,
-                ).await?;
-            }
+                (download_url, asset_path, hash)
+            })
+            .collect();
+
+        let total = missing.len() as u64;
+        progress.report(TaskStage::AssetObjects, 0, total);
+        let network = &self.network;
+        let shared = &self.shared;
+        let mut downloads = stream::iter(missing)
+            .map(|(download_url, asset_path, hash)| async move {
+                if let Some(source) = shared.asset(&hash).await {
+                    if crate::shared::link_shared(&source, &asset_path).is_ok() {
+                        return Ok(());
+                    }
+                }
+                network.download_file(&download_url, &asset_path, Some(&hash), None).await
+            })
+            .buffer_unordered(self.network.get_max_concurrent_asset_downloads().max(1));
+
+        let mut done = 0;
+        while let Some(result) = downloads.next().await {
+            result?;
+            done += 1;
+            progress.report(TaskStage::AssetObjects, done, total);
         }
 
-        if asset_index.virtual_.unwrap_or(false) || asset_index.map_to_resources.unwrap_or(false) {
+        if asset_index.is_virtual() || asset_index.maps_to_resources() {
             self.create_virtual_assets(version, &asset_index).await?;
         }
 
@@ -89,6 +216,8 @@ impl AssetsManager {
         Ok(asset_index)
     }
 
+    // Legacy versions want the assets under their names; those are links to
+    // the hashed objects, so every legacy version shares the same files.
     async fn create_virtual_assets(&self, version: &str, asset_index: &AssetIndex) -> Result<()> {
         let virtual_dir = self.assets_dir.join("virtual").join(version);
         std::fs::create_dir_all(&virtual_dir)?;
@@ -96,13 +225,8 @@ impl AssetsManager {
         for (name, object) in &asset_index.objects {
             let asset_path = self.get_asset_path(&object.hash);
             let virtual_path = virtual_dir.join(name);
-            
-            if let Some(parent) = virtual_path.parent() {
-                std::fs::create_dir_all(parent)?;
-            }
-            
             if asset_path.exists() && !virtual_path.exists() {
-                std::fs::copy(&asset_path, &virtual_path)?;
+                link_shared(&asset_path, &virtual_path)?;
             }
         }
 
@@ -116,6 +240,17 @@ impl AssetsManager {
             .join(hash)
     }
 
+    pub fn verify_assets(&self, assets_id: &str) -> Result<(usize, usize)> {
+        let asset_index = AssetIndex::load(&self.assets_dir, assets_id)?;
+
+        let total = asset_index.objects.len();
+        let missing = asset_index.objects
+            .values()
+            .filter(|object| !self.get_asset_path(&object.hash).exists())
+            .count();
+        Ok((total, missing))
+    }
+
     pub fn get_virtual_assets_dir(&self, version: &str) -> PathBuf {
         self.assets_dir.join("virtual").join(version)
     }
@@ -137,27 +272,11 @@ impl AssetsManager {
         Ok(())
     }
 
-    pub fn get_assets_size(&self) -> Result<u64> {
-        let mut total_size = 0;
-        
-        fn dir_size(path: &Path) -> std::io::Result<u64> {
-            let mut size = 0;
-            for entry in std::fs::read_dir(path)? {
-                let entry = entry?;
-                let metadata = entry.metadata()?;
-                if metadata.is_dir() {
-                    size += dir_size(&entry.path())?;
-                } else {
-                    size += metadata.len();
-                }
-            }
-            Ok(size)
-        }
-
-        if self.assets_dir.exists() {
-            total_size = dir_size(&self.assets_dir)?;
-        }
+    pub fn get_assets_dir(&self) -> &Path {
+        &self.assets_dir
+    }
 
-        Ok(total_size)
+    pub fn get_assets_size(&self) -> Result<u64> {
+        Ok(crate::storage::directory_size(&self.assets_dir))
     }
 } 
\ No newline at end of file
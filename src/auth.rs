@@ -1,9 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::{Error, Result};
+use crate::credentials::CredentialStore;
+use crate::events::{LauncherEvent, Notifier};
+use crate::schema;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AccountType {
@@ -22,9 +25,23 @@ pub struct Account {
     pub refresh_token: Option<String>,
     pub created_at: DateTime<Utc>,
     pub last_used: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub launch_count: u32,
     pub profile_picture_url: Option<String>,
     pub is_default: bool,
     pub microsoft_data: Option<MicrosoftAccountData>,
+    #[serde(default)]
+    pub secrets_in_keyring: bool,
+}
+
+/// Token fields that are kept out of accounts.json when a keyring is available.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AccountSecrets {
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+    xbox_user_token: Option<String>,
+    xbox_api_token: Option<String>,
+    mojang_token: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,9 +78,11 @@ impl Account {
             refresh_token: None,
             created_at: Utc::now(),
             last_used: None,
+            launch_count: 0,
             profile_picture_url: None,
             is_default: false,
             microsoft_data: None,
+            secrets_in_keyring: false,
         }
     }
 
@@ -78,6 +97,7 @@ impl Account {
             refresh_token: None,
             created_at: Utc::now(),
             last_used: None,
+            launch_count: 0,
             profile_picture_url: None,
             is_default: false,
             microsoft_data: Some(MicrosoftAccountData {
@@ -88,6 +108,37 @@ impl Account {
                 expires_at: None,
                 gamertag: None,
             }),
+            secrets_in_keyring: false,
+        }
+    }
+
+    // Offline accounts only carry the placeholder "0" token.
+    fn has_secrets(&self) -> bool {
+        self.account_type == AccountType::Microsoft
+            && (self.access_token.is_some() || self.refresh_token.is_some() || self.microsoft_data.is_some())
+    }
+
+    fn take_secrets(&mut self) -> AccountSecrets {
+        let mut secrets = AccountSecrets {
+            access_token: self.access_token.take(),
+            refresh_token: self.refresh_token.take(),
+            ..AccountSecrets::default()
+        };
+        if let Some(data) = self.microsoft_data.as_mut() {
+            secrets.xbox_user_token = data.xbox_user_token.take();
+            secrets.xbox_api_token = data.xbox_api_token.take();
+            secrets.mojang_token = data.mojang_token.take();
+        }
+        secrets
+    }
+
+    fn restore_secrets(&mut self, secrets: AccountSecrets) {
+        self.access_token = secrets.access_token;
+        self.refresh_token = secrets.refresh_token;
+        if let Some(data) = self.microsoft_data.as_mut() {
+            data.xbox_user_token = secrets.xbox_user_token;
+            data.xbox_api_token = secrets.xbox_api_token;
+            data.mojang_token = secrets.mojang_token;
         }
     }
 
@@ -167,6 +218,28 @@ pub struct AuthManager {
     accounts: HashMap<Uuid, Account>,
     default_account: Option<Uuid>,
     accounts_file: PathBuf,
+    credentials: CredentialStore,
+    // The secrets each account has in the keyring, as stored, so a save only
+    // talks to the keyring for tokens that changed.
+    keyring_entries: HashMap<Uuid, String>,
+    // Accounts whose tokens are in the keyring but could not be read, e.g.
+    // while it is locked. Their flag and entry are left alone on save, so
+    // the tokens are found again once the keyring is reachable.
+    unresolved: HashSet<Uuid>,
+    // Removed here, so a save does not bring them back from the file.
+    removed: HashSet<Uuid>,
+    notifier: Notifier,
+}
+
+impl Default for AuthManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct AccountsFile {
+    accounts: Vec<Account>,
 }
 
 impl AuthManager {
@@ -175,6 +248,11 @@ impl AuthManager {
             accounts: HashMap::new(),
             default_account: None,
             accounts_file: PathBuf::from("accounts.json"),
+            credentials: CredentialStore::File,
+            keyring_entries: HashMap::new(),
+            unresolved: HashSet::new(),
+            removed: HashSet::new(),
+            notifier: Notifier::new(),
         }
     }
 
@@ -183,10 +261,15 @@ impl AuthManager {
             accounts: HashMap::new(),
             default_account: None,
             accounts_file,
+            credentials: CredentialStore::detect(),
+            keyring_entries: HashMap::new(),
+            unresolved: HashSet::new(),
+            removed: HashSet::new(),
+            notifier: Notifier::new(),
         };
         
         if let Err(e) = manager.load_accounts() {
-            log::warn!("Failed to load accounts: {}", e);
+            tracing::warn!("Failed to load accounts: {}", e);
         }
         
         manager
@@ -199,13 +282,28 @@ impl AuthManager {
         }
         
         let id = account.id;
+        let name = account.display_name.clone();
         self.accounts.insert(id, account);
         self.save_accounts()?;
+        self.notifier.emit(LauncherEvent::AccountAdded { name });
         Ok(id)
     }
 
+    pub fn set_notifier(&mut self, notifier: Notifier) {
+        self.notifier = notifier;
+    }
+
     pub fn remove_account(&mut self, account_id: Uuid) -> Result<()> {
-        if let Some(account) = self.accounts.remove(&account_id) {
+        let removed = self.accounts.remove(&account_id);
+        self.removed.insert(account_id);
+        if let Some(account) = &removed {
+            let cached = self.keyring_entries.remove(&account_id).is_some();
+            let unresolved = self.unresolved.remove(&account_id);
+            if cached || unresolved || account.has_secrets() {
+                if let Err(e) = self.credentials.delete(&account_id.to_string()) {
+                    tracing::warn!("Failed to remove tokens for account {}: {}", account.username, e);
+                }
+            }
             if account.is_default {
                 self.default_account = None;
         
@@ -215,6 +313,9 @@ impl AuthManager {
             }
         }
         self.save_accounts()?;
+        if let Some(account) = removed {
+            self.notifier.emit(LauncherEvent::AccountRemoved { name: account.display_name });
+        }
         Ok(())
     }
 
@@ -265,6 +366,7 @@ impl AuthManager {
     pub fn update_account_last_used(&mut self, account_id: Uuid) -> Result<()> {
         if let Some(account) = self.accounts.get_mut(&account_id) {
             account.last_used = Some(Utc::now());
+            account.launch_count = account.launch_count.saturating_add(1);
             self.save_accounts()?;
         }
         Ok(())
@@ -301,25 +403,106 @@ impl AuthManager {
         }
 
         let content = std::fs::read_to_string(&self.accounts_file)?;
-        let accounts_data: Vec<Account> = serde_json::from_str(&content)?;
+        let (stored, mut migrate): (AccountsFile, bool) =
+            schema::ACCOUNTS.load(&self.accounts_file, serde_json::from_str(&content)?)?;
+        let accounts_data = stored.accounts;
         
-        for account in accounts_data {
+        for mut account in accounts_data {
+            if account.secrets_in_keyring {
+                match self.load_secrets(account.id) {
+                    Ok(Some(secrets)) => account.restore_secrets(secrets),
+                    Ok(None) => {
+                        tracing::warn!("Tokens for account {} are missing from the {}", account.username, self.credentials.label());
+                        self.unresolved.insert(account.id);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to read tokens for account {}: {}", account.username, e);
+                        self.unresolved.insert(account.id);
+                    }
+                }
+            } else if self.credentials.is_keyring() && account.has_secrets() {
+                migrate = true;
+            }
             if account.is_default {
                 self.default_account = Some(account.id);
             }
             self.accounts.insert(account.id, account);
         }
-        
+
+        if migrate {
+            tracing::info!("Moving stored account tokens to the {}", self.credentials.label());
+            self.save_accounts()?;
+        }
         Ok(())
     }
 
-    fn save_accounts(&self) -> Result<()> {
-        let accounts_vec: Vec<&Account> = self.accounts.values().collect();
-        let content = serde_json::to_string_pretty(&accounts_vec)?;
-        std::fs::write(&self.accounts_file, content)?;
+    fn load_secrets(&mut self, account_id: Uuid) -> Result<Option<AccountSecrets>> {
+        match self.credentials.get(&account_id.to_string())? {
+            Some(json) => {
+                let secrets = serde_json::from_str(&json)?;
+                self.keyring_entries.insert(account_id, json);
+                Ok(Some(secrets))
+            }
+            None => Ok(None),
+        }
+    }
+
+    // Tokens go to the keyring when one is available; an account whose
+    // secrets cannot be stored there keeps them in the file instead.
+    fn save_accounts(&mut self) -> Result<()> {
+        // Signing in again gives an unresolved account new tokens, which
+        // replace whatever the keyring still holds.
+        self.unresolved.retain(|id| self.accounts.get(id).is_some_and(|account| !account.has_secrets()));
+        let mut accounts_vec = Vec::with_capacity(self.accounts.len());
+        for account in self.accounts.values() {
+            let mut account = account.clone();
+            account.secrets_in_keyring = self.unresolved.contains(&account.id);
+            if self.credentials.is_keyring() && account.has_secrets() {
+                let secrets = account.take_secrets();
+                let stored = serde_json::to_string(&secrets)
+                    .map_err(Error::from)
+                    .and_then(|json| {
+                        if self.keyring_entries.get(&account.id) != Some(&json) {
+                            self.credentials.set(&account.id.to_string(), &json)?;
+                            self.keyring_entries.insert(account.id, json);
+                        }
+                        Ok(())
+                    });
+                match stored {
+                    Ok(()) => account.secrets_in_keyring = true,
+                    Err(e) => {
+                        tracing::warn!("Failed to store tokens for account {} in the {}: {}", account.username, self.credentials.label(), e);
+                        account.restore_secrets(secrets);
+                    }
+                }
+            }
+            accounts_vec.push(account);
+        }
+
+        // Accounts another launcher added since this one loaded the file
+        // are kept as they are stored.
+        let _lock = crate::utils::lock_data_dir()?;
+        for mut account in self.stored_accounts() {
+            if !self.accounts.contains_key(&account.id) && !self.removed.contains(&account.id) {
+                account.is_default &= self.default_account.is_none();
+                accounts_vec.push(account);
+            }
+        }
+        let mut value = serde_json::to_value(AccountsFile { accounts: accounts_vec })?;
+        schema::ACCOUNTS.stamp(&mut value);
+        crate::utils::save_data_file(&self.accounts_file, serde_json::to_string_pretty(&value)?)?;
         Ok(())
     }
 
+    fn stored_accounts(&self) -> Vec<Account> {
+        std::fs::read_to_string(&self.accounts_file)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .and_then(|value| schema::ACCOUNTS.load::<AccountsFile>(&self.accounts_file, value).ok())
+            .map(|(stored, _)| stored.accounts)
+            .unwrap_or_default()
+    }
+
     pub fn count(&self) -> usize {
         self.accounts.len()
     }
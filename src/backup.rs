@@ -0,0 +1,275 @@
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use uuid::Uuid;
+
+use crate::instance::{Instance, EXPORT_EXCLUDED_DIRS};
+use crate::{Error, Result};
+
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+const INSTANCE_ENTRY: &str = "instance.json";
+const GAME_DIR_ENTRY: &str = ".minecraft";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BackupReason {
+    Delete,
+    LoaderChange,
+    Import,
+    Restore,
+    ModpackUpdate,
+}
+
+impl BackupReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackupReason::Delete => "delete",
+            BackupReason::LoaderChange => "loader",
+            BackupReason::Import => "import",
+            BackupReason::Restore => "restore",
+            BackupReason::ModpackUpdate => "modpack",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "delete" => Some(BackupReason::Delete),
+            "loader" => Some(BackupReason::LoaderChange),
+            "import" => Some(BackupReason::Import),
+            "restore" => Some(BackupReason::Restore),
+            "modpack" => Some(BackupReason::ModpackUpdate),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Backup {
+    pub instance_id: Uuid,
+    pub instance_name: String,
+    pub reason: Option<BackupReason>,
+    pub created_at: DateTime<Utc>,
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupManager {
+    backups_dir: PathBuf,
+}
+
+impl BackupManager {
+    pub fn new(backups_dir: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&backups_dir)?;
+        Ok(Self { backups_dir })
+    }
+
+    pub fn create_backup(&self, instance: &Instance, reason: BackupReason, retention: usize) -> Result<Backup> {
+        let backup = self.write_backup(instance, reason)?;
+        self.prune(instance.id, retention)?;
+        Ok(backup)
+    }
+
+    // Writes a backup without pruning, for callers that still need one of
+    // the older archives and prune once they are done with it.
+    pub fn write_backup(&self, instance: &Instance, reason: BackupReason) -> Result<Backup> {
+        let dir = self.backups_dir.join(instance.id.to_string());
+        std::fs::create_dir_all(&dir)?;
+
+        let created_at = Utc::now();
+        let mut path = dir.join(format!("{}-{}.zip", created_at.format(TIMESTAMP_FORMAT), reason.as_str()));
+        let mut suffix = 1;
+        while path.exists() {
+            path = dir.join(format!("{}-{}-{}.zip", created_at.format(TIMESTAMP_FORMAT), reason.as_str(), suffix));
+            suffix += 1;
+        }
+
+        if let Err(e) = write_archive(instance, &path) {
+            let _ = std::fs::remove_file(&path);
+            return Err(e);
+        }
+
+        Ok(Backup {
+            instance_id: instance.id,
+            instance_name: instance.name.clone(),
+            reason: Some(reason),
+            created_at,
+            size: std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+            path,
+        })
+    }
+
+    pub fn list_backups(&self) -> Result<Vec<Backup>> {
+        let mut backups = Vec::new();
+        for entry in std::fs::read_dir(&self.backups_dir)? {
+            let dir = entry?.path();
+            let Some(instance_id) = dir.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| Uuid::parse_str(n).ok()) else {
+                continue;
+            };
+            backups.extend(self.instance_backups(instance_id, &dir)?);
+        }
+        backups.sort_by_key(|backup| std::cmp::Reverse(backup.created_at));
+        Ok(backups)
+    }
+
+    fn instance_backups(&self, instance_id: Uuid, dir: &Path) -> Result<Vec<Backup>> {
+        let mut backups = Vec::new();
+        if !dir.is_dir() {
+            return Ok(backups);
+        }
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "zip") {
+                continue;
+            }
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else { continue };
+            let Some(created_at) = stem.get(..15)
+                .and_then(|ts| NaiveDateTime::parse_from_str(ts, TIMESTAMP_FORMAT).ok())
+                .map(|ts| ts.and_utc()) else {
+                continue;
+            };
+            let reason = stem.get(16..)
+                .and_then(|rest| rest.split('-').next())
+                .and_then(BackupReason::parse);
+            let instance_name = read_instance(&path)
+                .map(|instance| instance.name)
+                .unwrap_or_else(|_| instance_id.to_string());
+
+            backups.push(Backup {
+                instance_id,
+                instance_name,
+                reason,
+                created_at,
+                size: std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+                path,
+            });
+        }
+        // Backups taken within the same second share a timestamp; fall back to mtime.
+        backups.sort_by_cached_key(|backup| {
+            let modified = std::fs::metadata(&backup.path).and_then(|m| m.modified()).ok();
+            std::cmp::Reverse((backup.created_at, modified))
+        });
+        Ok(backups)
+    }
+
+    pub fn prune(&self, instance_id: Uuid, retention: usize) -> Result<usize> {
+        let dir = self.backups_dir.join(instance_id.to_string());
+        let mut removed = 0;
+        for backup in self.instance_backups(instance_id, &dir)?.into_iter().skip(retention.max(1)) {
+            std::fs::remove_file(&backup.path)?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    pub fn delete_backup(&self, backup: &Backup) -> Result<()> {
+        std::fs::remove_file(&backup.path)?;
+        let dir = self.backups_dir.join(backup.instance_id.to_string());
+        if std::fs::read_dir(&dir).is_ok_and(|mut entries| entries.next().is_none()) {
+            std::fs::remove_dir(&dir)?;
+        }
+        Ok(())
+    }
+
+    pub fn restore_backup(&self, backup: &Backup, instances_dir: &Path) -> Result<Instance> {
+        let mut instance = read_instance(&backup.path)?;
+        let target = instances_dir.join(instance.id.to_string());
+        let staging = instances_dir.join(format!(".{}.restore", instance.id));
+        if staging.exists() {
+            std::fs::remove_dir_all(&staging)?;
+        }
+
+        if let Err(e) = extract_game_dir(&backup.path, &staging) {
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+
+        std::fs::create_dir_all(&target)?;
+        let game_dir = target.join(GAME_DIR_ENTRY);
+        if game_dir.exists() {
+            std::fs::remove_dir_all(&game_dir)?;
+        }
+        std::fs::rename(staging.join(GAME_DIR_ENTRY), &game_dir)?;
+        std::fs::remove_dir_all(&staging)?;
+
+        instance.path = target;
+        Ok(instance)
+    }
+}
+
+fn write_archive(instance: &Instance, path: &Path) -> Result<()> {
+    let game_dir = instance.path.join(GAME_DIR_ENTRY);
+    let file = std::fs::File::create(path)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file(INSTANCE_ENTRY, options)?;
+    zip.write_all(serde_json::to_string_pretty(instance)?.as_bytes())?;
+
+    if game_dir.exists() {
+        for entry in walkdir::WalkDir::new(&game_dir).min_depth(1) {
+            let entry = entry?;
+            let Ok(relative) = entry.path().strip_prefix(&game_dir) else { continue };
+            if is_excluded(relative) {
+                continue;
+            }
+
+            let archive_name = format!("{}/{}", GAME_DIR_ENTRY, relative.to_string_lossy().replace('\\', "/"));
+            if entry.file_type().is_dir() {
+                zip.add_directory(archive_name, options)?;
+            } else if entry.file_type().is_file() {
+                zip.start_file(archive_name, options)?;
+                let mut source = std::fs::File::open(entry.path())?;
+                std::io::copy(&mut source, &mut zip)?;
+            }
+        }
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+fn is_excluded(relative: &Path) -> bool {
+    let top = relative.components().next().map(|c| c.as_os_str().to_string_lossy());
+    top.is_some_and(|top| EXPORT_EXCLUDED_DIRS.contains(&top.as_ref()))
+        || relative.components().any(|c| c.as_os_str().to_string_lossy().starts_with(".safe-mode"))
+}
+
+fn read_instance(path: &Path) -> Result<Instance> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    let mut entry = archive.by_name(INSTANCE_ENTRY)?;
+    let mut content = String::new();
+    entry.read_to_string(&mut content)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+fn extract_game_dir(path: &Path, target: &Path) -> Result<()> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    std::fs::create_dir_all(target.join(GAME_DIR_ENTRY))?;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let Some(relative) = entry.enclosed_name().map(Path::to_path_buf) else {
+            return Err(Error::Other(format!("Unsafe path in backup: {}", entry.name())));
+        };
+        if !relative.starts_with(GAME_DIR_ENTRY) {
+            continue;
+        }
+
+        let destination = target.join(&relative);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&destination)?;
+        } else {
+            if let Some(parent) = destination.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut output = std::fs::File::create(&destination)?;
+            std::io::copy(&mut entry, &mut output)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use crate::mods::ModManager;
+use crate::{Error, Result};
+
+#[derive(Debug, Clone)]
+pub struct BisectSession {
+    pub instance_id: Uuid,
+    pub instance_name: String,
+    pub mods_dir: PathBuf,
+    pub total_mods: usize,
+    pub suspects: Vec<String>,
+    pub testing: Vec<String>,
+    pub round: usize,
+    pub running: bool,
+    pub tested: bool,
+    pub detected_crash: Option<bool>,
+    pub finished: bool,
+    pub culprit: Option<String>,
+}
+
+impl BisectSession {
+    pub fn start(instance_id: Uuid, instance_name: String, mods_dir: PathBuf) -> Result<Self> {
+        if ModManager::is_in_safe_mode(&mods_dir) {
+            ModManager::exit_safe_mode(&mods_dir)?;
+        }
+
+        let suspects = if mods_dir.exists() {
+            ModManager::list_enabled_mod_files(&mods_dir)?
+        } else {
+            Vec::new()
+        };
+        if suspects.is_empty() {
+            return Err(Error::Mod("No enabled mods to bisect".to_string()));
+        }
+
+        let mut session = Self {
+            instance_id,
+            instance_name,
+            mods_dir,
+            total_mods: suspects.len(),
+            suspects,
+            testing: Vec::new(),
+            round: 0,
+            running: false,
+            tested: false,
+            detected_crash: None,
+            finished: false,
+            culprit: None,
+        };
+        session.next_round();
+        Ok(session)
+    }
+
+    fn next_round(&mut self) {
+        let half = self.suspects.len().div_ceil(2);
+        self.testing = self.suspects[..half].to_vec();
+        self.round += 1;
+        self.tested = false;
+        self.detected_crash = None;
+    }
+
+    pub fn apply(&self) -> Result<usize> {
+        ModManager::exit_safe_mode(&self.mods_dir)?;
+        ModManager::stash_mods_except(&self.mods_dir, &self.testing)
+    }
+
+    pub fn record_result(&mut self, crashed: bool) {
+        if crashed {
+            self.suspects = self.testing.clone();
+        } else {
+            self.suspects.retain(|name| !self.testing.contains(name));
+        }
+
+        match self.suspects.len() {
+            0 => self.finished = true,
+            1 => {
+                self.finished = true;
+                self.culprit = self.suspects.first().cloned();
+            }
+            _ => self.next_round(),
+        }
+    }
+
+    pub fn remaining_rounds(&self) -> usize {
+        if self.finished {
+            return 0;
+        }
+        let mut rounds = 0;
+        let mut left = self.suspects.len();
+        while left > 1 {
+            left = left.div_ceil(2);
+            rounds += 1;
+        }
+        rounds.max(1)
+    }
+
+    pub fn finish(&self) -> Result<usize> {
+        ModManager::exit_safe_mode(&self.mods_dir)
+    }
+}
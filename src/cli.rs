@@ -0,0 +1,361 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+use crate::app::App;
+use crate::launch::LaunchOptions;
+use crate::launcher::Launcher;
+use crate::quickplay::QuickPlay;
+use crate::shortcut::ShortcutKind;
+use crate::progress::{ProgressTracker, TrackedTask};
+use crate::{github, updater, verify, Error, Result};
+
+#[derive(Parser, Debug)]
+#[command(name = "mango", version = crate::VERSION, about = "A modern Minecraft launcher written in Rust")]
+pub struct Cli {
+    #[arg(long, global = true, value_name = "DIR", help = "Keep all launcher data in this directory")]
+    pub data_dir: Option<PathBuf>,
+    #[arg(long, global = true, conflicts_with = "data_dir", help = "Keep data in a folder next to the executable from now on")]
+    pub portable: bool,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    #[command(about = "Launch an instance and wait for the game to exit")]
+    Launch {
+        instance: String,
+        #[arg(long, help = "Start without mods")]
+        safe_mode: bool,
+        #[arg(long, help = "Use a saved launch variant")]
+        variant: Option<String>,
+        #[arg(long, help = "Skip unreviewed pack commands")]
+        skip_commands: bool,
+        #[arg(long, help = "Allow sharing the game directory with a running session")]
+        allow_shared: bool,
+        #[arg(long, help = "Start the game in demo mode")]
+        demo: bool,
+        #[arg(long, help = "Account to play with (default: the instance's last account or the default one)")]
+        account: Option<String>,
+        #[arg(long, conflicts_with = "server", help = "Open a world (save folder name) straight away")]
+        world: Option<String>,
+        #[arg(long, help = "Join a server (host[:port]) straight away")]
+        server: Option<String>,
+    },
+    #[command(about = "Download a Minecraft version with its libraries and assets")]
+    Download {
+        version: String,
+    },
+    #[command(about = "Check an installed version's files and re-download broken ones")]
+    Repair {
+        version: String,
+    },
+    #[command(subcommand, about = "Manage instances")]
+    Instance(InstanceCommand),
+    #[command(subcommand, about = "Manage accounts")]
+    Account(AccountCommand),
+    #[command(about = "Check that an instance is ready to launch")]
+    Verify {
+        instance: String,
+        #[arg(long, help = "Also spawn the JVM with the launch arguments")]
+        spawn: bool,
+    },
+    #[command(about = "Install a mod or client jar from GitHub releases")]
+    Github {
+        instance: String,
+        repository: String,
+        asset_pattern: Option<String>,
+        #[arg(long, help = "Allow prereleases")]
+        pre: bool,
+        #[arg(long, help = "Install or update the jar the game is launched with instead of a mod")]
+        client: bool,
+    },
+    #[command(about = "Check for a newer MangoLauncher release")]
+    SelfUpdate {
+        #[arg(long, help = "Download the release and replace this binary")]
+        install: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum InstanceCommand {
+    #[command(about = "List instances")]
+    List,
+    #[command(about = "Create an instance")]
+    Create {
+        name: String,
+        version: String,
+    },
+    #[command(about = "Delete an instance (a backup is kept)")]
+    Delete {
+        instance: String,
+    },
+    #[command(about = "Create a menu entry (Linux) or launch script for an instance")]
+    Shortcut {
+        instance: String,
+        #[arg(long, help = "Write a .sh/.bat script even where menu entries are supported")]
+        script: bool,
+    },
+    #[command(about = "Update an instance imported from a modpack to a newer pack version")]
+    UpdatePack {
+        instance: String,
+        #[arg(help = "New .mrpack or CurseForge zip (default: newest match in the import folder)")]
+        pack: Option<PathBuf>,
+        #[arg(long, help = "Replace config files you edited when the pack changed them too")]
+        overwrite_configs: bool,
+    },
+    #[command(about = "Import PrismLauncher/MultiMC instances and reuse that launcher's libraries and assets")]
+    ImportPrism {
+        #[arg(help = "The launcher's data folder (default: PrismLauncher's standard location)")]
+        dir: Option<PathBuf>,
+    },
+    #[command(about = "Import the profiles and downloaded versions of the official launcher")]
+    ImportOfficial {
+        #[arg(help = "The launcher's .minecraft folder (default: the standard location)")]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AccountCommand {
+    #[command(about = "Add an offline account")]
+    AddOffline {
+        name: String,
+    },
+}
+
+pub async fn execute(command: Command) -> Result<()> {
+    match command {
+        Command::Launch { instance, safe_mode, variant, skip_commands, allow_shared, demo, account, world, server } => {
+            let options = LaunchOptions {
+                safe_mode,
+                variant,
+                skip_commands,
+                allow_shared_directories: allow_shared,
+                demo,
+                quick_play: world.map(QuickPlay::World).or(server.map(QuickPlay::Server)),
+                ..LaunchOptions::default()
+            };
+            run_launch(&instance, account.as_deref(), options).await
+        }
+        Command::Download { version } => run_download(&version).await,
+        Command::Repair { version } => run_repair(&version).await,
+        Command::Instance(command) => run_instance(command).await,
+        Command::Account(AccountCommand::AddOffline { name }) => {
+            let mut app = App::new().await?;
+            let id = app.add_offline_account(name.clone())?;
+            println!("Added offline account {} ({})", name, id);
+            Ok(())
+        }
+        Command::Verify { instance, spawn } => run_verify(&instance, spawn).await,
+        Command::Github { instance, repository, asset_pattern, pre, client } => {
+            run_github_add(&instance, &repository, asset_pattern.as_deref(), pre, client).await
+        }
+        Command::SelfUpdate { install } => run_self_update(install).await,
+    }
+}
+
+// Keeps the running tasks on one stderr line, redrawn only when it changes.
+#[derive(Default)]
+struct ProgressLine {
+    last: String,
+}
+
+impl ProgressLine {
+    fn update(&mut self, progress: &ProgressTracker) {
+        let line = progress.tasks().iter()
+            .map(TrackedTask::describe)
+            .collect::<Vec<_>>()
+            .join(" | ");
+        if line != self.last {
+            eprint!("\r\x1b[2K{}", line);
+            let _ = std::io::stderr().flush();
+            self.last = line;
+        }
+    }
+
+    fn finish(self) {
+        if !self.last.is_empty() {
+            eprintln!();
+        }
+    }
+}
+
+async fn run_launch(selector: &str, account: Option<&str>, mut options: LaunchOptions) -> Result<()> {
+    let mut launcher = Launcher::new().await?;
+    let instance_id = launcher.find_instance(selector)
+        .map(|instance| instance.id)
+        .ok_or_else(|| Error::Instance(format!("Instance '{}' not found", selector)))?;
+    // The command line never shows the account picker.
+    if let Some(account) = account {
+        options.account = Some(launcher.app().find_account(account)
+            .map(|account| account.id)
+            .ok_or_else(|| Error::Auth(format!("Account '{}' not found", account)))?);
+    }
+
+    launcher.launch(instance_id, options).await?;
+    println!("{}", launcher.status());
+    let crash = launcher.wait_for_exit(instance_id).await;
+    println!("{}", launcher.status());
+    if let Some(report) = crash {
+        return Err(Error::Launch(report.headline()));
+    }
+    Ok(())
+}
+
+async fn run_download(version_id: &str) -> Result<()> {
+    let mut launcher = Launcher::new().await?;
+    launcher.list_versions().await?;
+    if launcher.is_version_installed(version_id) {
+        println!("Version {} is already installed", version_id);
+        return Ok(());
+    }
+    let mut progress = ProgressLine::default();
+    let result = launcher.install_version(version_id, |tasks| progress.update(tasks)).await;
+    progress.finish();
+    result?;
+    println!("Version {} installed", version_id);
+    Ok(())
+}
+
+async fn run_repair(version_id: &str) -> Result<()> {
+    let mut launcher = Launcher::new().await?;
+    launcher.app().start_version_verify(version_id)?;
+    let mut progress = ProgressLine::default();
+    launcher.wait_for_tasks(|tasks| progress.update(tasks)).await;
+    progress.finish();
+    println!("{}", launcher.status());
+    if !launcher.is_version_installed(version_id) {
+        return Err(Error::Version(format!("Version {} is still incomplete", version_id)));
+    }
+    Ok(())
+}
+
+async fn run_instance(command: InstanceCommand) -> Result<()> {
+    let mut app = App::new().await?;
+    match command {
+        InstanceCommand::List => {
+            for instance in app.get_instances() {
+                let group = instance.group.as_deref().map(|g| format!(" [{}]", g)).unwrap_or_default();
+                println!("{}  {:<24} {:<12}{}", instance.id, instance.name, instance.minecraft_version, group);
+            }
+        }
+        InstanceCommand::Create { name, version } => {
+            let id = app.create_instance(name.clone(), version)?;
+            println!("Created instance '{}' ({})", name, id);
+        }
+        InstanceCommand::Delete { instance } => {
+            let (id, name) = app.find_instance(&instance)
+                .map(|instance| (instance.id, instance.name.clone()))
+                .ok_or_else(|| Error::Instance(format!("Instance '{}' not found", instance)))?;
+            app.delete_instance(id)?;
+            println!("Deleted instance '{}'", name);
+        }
+        InstanceCommand::Shortcut { instance, script } => {
+            let id = app.find_instance(&instance)
+                .map(|instance| instance.id)
+                .ok_or_else(|| Error::Instance(format!("Instance '{}' not found", instance)))?;
+            let kind = if script { ShortcutKind::Script } else { ShortcutKind::platform_default() };
+            let path = app.create_shortcut(id, kind)?;
+            println!("Created shortcut {}", path.display());
+        }
+        InstanceCommand::ImportPrism { dir } => {
+            let imported = app.import_prism_instances(dir.as_deref()).await?;
+            println!("Imported {} instances", imported);
+            if let Some(shared) = &app.get_settings().general.shared_data_dir {
+                println!("Libraries and assets are reused from {}", shared.display());
+            }
+        }
+        InstanceCommand::ImportOfficial { dir } => {
+            // Profiles that follow the latest release or snapshot need the
+            // version list; the cached copy does when offline.
+            if let Err(e) = app.version_manager.load_versions().await {
+                eprintln!("Version list unavailable: {}", e);
+            }
+            let summary = app.import_official_launcher(dir.as_deref())?;
+            println!("Imported {} profiles, reused {} downloaded files", summary.instances, summary.reused_files);
+            if !summary.account_hints.is_empty() {
+                println!("Accounts to sign in to again: {}", summary.account_hints.join(", "));
+            }
+        }
+        InstanceCommand::UpdatePack { instance, pack, overwrite_configs } => {
+            let id = app.find_instance(&instance)
+                .map(|instance| instance.id)
+                .ok_or_else(|| Error::Instance(format!("Instance '{}' not found", instance)))?;
+            let mut update = app.plan_modpack_update(id, pack.as_deref())?;
+            println!(
+                "{} {} -> {}: {} new, {} updated, {} removed mods; {} config files updated, {} removed",
+                update.source.name,
+                update.from_version.as_deref().unwrap_or("?"),
+                update.source.display_version(),
+                update.added(),
+                update.updated,
+                update.removed.len(),
+                update.overrides.len(),
+                update.dropped_overrides.len(),
+            );
+            for conflict in &mut update.conflicts {
+                conflict.keep_local = !overwrite_configs;
+                println!("  {} {}", if conflict.keep_local { "keeping" } else { "replacing" }, conflict.path);
+            }
+            let (version, failed) = app.apply_modpack_update(update).await?;
+            if failed > 0 {
+                return Err(Error::Instance(format!("Updated to {}, but {} files failed to download", version, failed)));
+            }
+            println!("Updated to {}", version);
+        }
+    }
+    Ok(())
+}
+
+async fn run_verify(selector: &str, spawn: bool) -> Result<()> {
+    let mut app = App::new().await?;
+    if let Err(e) = app.scan_java_installations().await {
+        tracing::warn!("Java scan failed: {}", e);
+    }
+
+    let report = verify::verify_instance(&mut app, selector, spawn).await?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if !report.ok {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn run_github_add(selector: &str, repository: &str, asset_pattern: Option<&str>, allow_prerelease: bool, client: bool) -> Result<()> {
+    let source = github::GitHubSource::parse(repository, asset_pattern, allow_prerelease)?;
+
+    let mut app = App::new().await?;
+    let instance_id = app.find_instance(selector)
+        .map(|instance| instance.id)
+        .ok_or_else(|| Error::Instance(format!("Instance '{}' not found", selector)))?;
+
+    if client {
+        match app.install_github_client(instance_id, source.clone()).await? {
+            Some(tracked) => println!("Installed client {} {} ({})", tracked.source.display(), tracked.tag, tracked.filename),
+            None => println!("Client {} is up to date", source.display()),
+        }
+        return Ok(());
+    }
+    let tracked = app.add_github_mod(instance_id, source).await?;
+    println!("Installed {} {} ({})", tracked.source.display(), tracked.tag, tracked.filename);
+    Ok(())
+}
+
+async fn run_self_update(install: bool) -> Result<()> {
+    let app = App::new().await?;
+    let Some(update) = updater::check(&app.network_manager).await? else {
+        println!("MangoLauncher {} is up to date", crate::VERSION);
+        return Ok(());
+    };
+    println!("MangoLauncher {} is available (running {})", update.version, crate::VERSION);
+    if !install {
+        println!("Run with --install to update");
+        return Ok(());
+    }
+    let path = updater::install(&app.network_manager, &update).await?;
+    println!("Installed {} to {}", update.version, path.display());
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::platform::SystemInfo;
+
+const MAX_SUMMARY_LINES: usize = 12;
+const MAX_LOG_LINES: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct GameExit {
+    pub session_id: Uuid,
+    pub instance_id: Uuid,
+    pub instance_name: String,
+    pub game_directory: PathBuf,
+    pub exit_code: Option<i32>,
+    pub stopped: bool,
+    pub safe_mode: bool,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+}
+
+impl GameExit {
+    pub fn is_crash(&self) -> bool {
+        !self.stopped && self.exit_code != Some(0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    pub instance_id: Uuid,
+    pub instance_name: String,
+    pub exit_code: Option<i32>,
+    pub report_path: Option<PathBuf>,
+    pub log_path: Option<PathBuf>,
+    pub description: Option<String>,
+    pub summary: Vec<String>,
+    pub log_excerpt: Vec<String>,
+    pub system: Vec<String>,
+}
+
+impl CrashReport {
+    pub fn analyze(exit: &GameExit, system: Option<&SystemInfo>) -> Self {
+        let started: SystemTime = exit.started_at.into();
+        let report_path = find_latest_crash_report(&exit.game_directory.join("crash-reports"), started);
+        let log_path = Some(exit.game_directory.join("logs").join("latest.log"))
+            .filter(|p| p.exists());
+
+        let (description, summary) = report_path
+            .as_deref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .map(|content| summarize_crash_report(&content))
+            .unwrap_or_default();
+
+        let log_excerpt = log_path
+            .as_deref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .map(|content| extract_log_errors(&content))
+            .unwrap_or_default();
+
+        Self {
+            instance_id: exit.instance_id,
+            instance_name: exit.instance_name.clone(),
+            exit_code: exit.exit_code,
+            report_path,
+            log_path,
+            description,
+            summary,
+            log_excerpt,
+            system: system.map(SystemInfo::summary).unwrap_or_default(),
+        }
+    }
+
+    pub fn headline(&self) -> String {
+        let code = self.exit_code
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| "signal".to_string());
+        match &self.description {
+            Some(description) => format!("{} (exit code {})", description, code),
+            None => format!("Minecraft exited with code {}", code),
+        }
+    }
+
+    pub fn open_target(&self) -> Option<&Path> {
+        self.report_path.as_deref().or(self.log_path.as_deref())
+    }
+}
+
+fn find_latest_crash_report(dir: &Path, not_before: SystemTime) -> Option<PathBuf> {
+    let entries = std::fs::read_dir(dir).ok()?;
+
+    entries
+        .flatten()
+        .filter(|entry| {
+            entry.path().extension().and_then(|s| s.to_str()) == Some("txt")
+        })
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            (modified >= not_before).then(|| (modified, entry.path()))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+fn summarize_crash_report(content: &str) -> (Option<String>, Vec<String>) {
+    let mut description = None;
+    let mut summary = Vec::new();
+    let mut in_trace = false;
+
+    for line in content.lines() {
+        if let Some(rest) = line.strip_prefix("Description:") {
+            description = Some(rest.trim().to_string());
+            in_trace = true;
+            continue;
+        }
+
+        if in_trace {
+            if line.trim().is_empty() {
+                if !summary.is_empty() {
+                    break;
+                }
+                continue;
+            }
+            summary.push(line.trim_end().to_string());
+            if summary.len() >= MAX_SUMMARY_LINES {
+                break;
+            }
+        }
+    }
+
+    (description, summary)
+}
+
+fn extract_log_errors(content: &str) -> Vec<String> {
+    let errors: Vec<&str> = content
+        .lines()
+        .filter(|line| {
+            line.contains("/ERROR]") || line.contains("/FATAL]") || line.contains("Exception")
+        })
+        .collect();
+
+    errors
+        .iter()
+        .skip(errors.len().saturating_sub(MAX_LOG_LINES))
+        .map(|line| line.to_string())
+        .collect()
+}
@@ -0,0 +1,164 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+use crate::{Error, Result};
+
+const SERVICE: &str = "mango-launcher";
+
+/// Where account tokens live. The OS keyring is reached through its command
+/// line front end: `secret-tool` for the Secret Service, `security` for the
+/// macOS Keychain and PowerShell's `PasswordVault` for the Windows Credential
+/// Manager. Systems without any of them use the accounts file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CredentialStore {
+    SecretService,
+    Keychain,
+    CredentialManager,
+    File,
+}
+
+// `cmdkey` can store a credential but never read one back; the WinRT
+// password vault can do both and keeps its entries in the Credential Manager.
+const VAULT: &str = "[void][Windows.Security.Credentials.PasswordVault,Windows.Security.Credentials,ContentType=WindowsRuntime]\n\
+    $vault = New-Object Windows.Security.Credentials.PasswordVault\n";
+
+impl CredentialStore {
+    pub fn detect() -> Self {
+        let (candidate, program) = if cfg!(target_os = "macos") {
+            (CredentialStore::Keychain, "security")
+        } else if cfg!(unix) {
+            (CredentialStore::SecretService, "secret-tool")
+        } else if cfg!(windows) {
+            (CredentialStore::CredentialManager, "powershell")
+        } else {
+            return CredentialStore::File;
+        };
+        let probe = if candidate == CredentialStore::CredentialManager { ["-NoProfile", "-Command", "exit"].as_slice() } else { ["--help"].as_slice() };
+        match Command::new(program).args(probe).stdout(Stdio::null()).stderr(Stdio::null()).status() {
+            Ok(_) => candidate,
+            Err(_) => {
+                tracing::info!("{} not found, account tokens stay in the accounts file", program);
+                CredentialStore::File
+            }
+        }
+    }
+
+    pub fn is_keyring(&self) -> bool {
+        *self != CredentialStore::File
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CredentialStore::SecretService => "Secret Service",
+            CredentialStore::Keychain => "Keychain",
+            CredentialStore::CredentialManager => "Credential Manager",
+            CredentialStore::File => "file",
+        }
+    }
+
+    // Secrets are stored base64-encoded so the Keychain's line-based
+    // interactive mode and the PowerShell script never have to quote them.
+    // Both read the secret from stdin, so it never shows up in a process list.
+    pub fn set(&self, key: &str, secret: &str) -> Result<()> {
+        let encoded = STANDARD.encode(secret);
+        let (program, args, input): (&str, Vec<String>, String) = match self {
+            CredentialStore::SecretService => (
+                "secret-tool",
+                vec![
+                    "store".to_string(),
+                    format!("--label=MangoLauncher {}", key),
+                    "service".to_string(), SERVICE.to_string(),
+                    "account".to_string(), key.to_string(),
+                ],
+                encoded,
+            ),
+            CredentialStore::Keychain => (
+                "security",
+                vec!["-i".to_string()],
+                format!("add-generic-password -U -s {} -a {} -w {}\n", SERVICE, key, encoded),
+            ),
+            CredentialStore::CredentialManager => (
+                "powershell",
+                vec!["-NoProfile".to_string(), "-NonInteractive".to_string(), "-Command".to_string(), "-".to_string()],
+                format!(
+                    "{}$vault.Add((New-Object Windows.Security.Credentials.PasswordCredential('{}', '{}', '{}')))\n",
+                    VAULT, SERVICE, key, encoded,
+                ),
+            ),
+            CredentialStore::File => return Err(Error::Auth("No keyring available".to_string())),
+        };
+
+        let mut child = Command::new(program)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes())?;
+        }
+        if !child.wait()?.success() {
+            return Err(Error::Auth(format!("{} refused to store the secret", self.label())));
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        let output = match self {
+            CredentialStore::SecretService => Command::new("secret-tool")
+                .args(["lookup", "service", SERVICE, "account", key])
+                .stderr(Stdio::null())
+                .output()?,
+            CredentialStore::Keychain => Command::new("security")
+                .args(["find-generic-password", "-s", SERVICE, "-a", key, "-w"])
+                .stderr(Stdio::null())
+                .output()?,
+            CredentialStore::CredentialManager => Command::new("powershell")
+                .args(["-NoProfile", "-NonInteractive", "-Command"])
+                .arg(format!(
+                    "{}$entry = $vault.Retrieve('{}', '{}'); $entry.RetrievePassword(); $entry.Password",
+                    VAULT, SERVICE, key,
+                ))
+                .stderr(Stdio::null())
+                .output()?,
+            CredentialStore::File => return Ok(None),
+        };
+        if !output.status.success() {
+            return Ok(None);
+        }
+        let encoded = String::from_utf8_lossy(&output.stdout);
+        let decoded = STANDARD.decode(encoded.trim())
+            .map_err(|e| Error::Auth(format!("Corrupt {} entry: {}", self.label(), e)))?;
+        String::from_utf8(decoded)
+            .map(Some)
+            .map_err(|e| Error::Auth(format!("Corrupt {} entry: {}", self.label(), e)))
+    }
+
+    pub fn delete(&self, key: &str) -> Result<()> {
+        let status = match self {
+            CredentialStore::SecretService => Command::new("secret-tool")
+                .args(["clear", "service", SERVICE, "account", key])
+                .stderr(Stdio::null())
+                .status()?,
+            CredentialStore::Keychain => Command::new("security")
+                .args(["delete-generic-password", "-s", SERVICE, "-a", key])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()?,
+            CredentialStore::CredentialManager => Command::new("powershell")
+                .args(["-NoProfile", "-NonInteractive", "-Command"])
+                .arg(format!("{}$vault.Remove($vault.Retrieve('{}', '{}'))", VAULT, SERVICE, key))
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()?,
+            CredentialStore::File => return Ok(()),
+        };
+        if !status.success() {
+            tracing::debug!("No {} entry to remove for {}", self.label(), key);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+use serde::Deserialize;
+
+use crate::instance::{InstanceLinks, ModLoader};
+use crate::network::NetworkManager;
+use crate::{Error, Result};
+
+const CURSEFORGE_API_URL: &str = "https://api.curseforge.com/v1";
+const MINECRAFT_GAME_ID: u32 = 432;
+const HASH_ALGO_SHA1: u32 = 1;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeFile {
+    pub id: u32,
+    pub mod_id: u32,
+    pub display_name: String,
+    pub file_name: String,
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub hashes: Vec<CurseForgeHash>,
+    pub file_fingerprint: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseForgeHash {
+    pub value: String,
+    pub algo: u32,
+}
+
+impl CurseForgeFile {
+    pub fn sha1(&self) -> Option<&str> {
+        self.hashes.iter()
+            .find(|h| h.algo == HASH_ALGO_SHA1)
+            .map(|h| h.value.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseForgeMod {
+    pub id: u32,
+    pub links: CurseForgeLinks,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeLinks {
+    pub website_url: Option<String>,
+    pub wiki_url: Option<String>,
+    pub issues_url: Option<String>,
+}
+
+impl CurseForgeMod {
+    // CurseForge sends empty strings for links a project has not set.
+    pub fn links(&self) -> InstanceLinks {
+        let link = |url: &Option<String>| url.clone().filter(|url| !url.is_empty());
+        InstanceLinks {
+            project_url: link(&self.links.website_url),
+            issues_url: link(&self.links.issues_url),
+            wiki_url: link(&self.links.wiki_url),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DataResponse<T> {
+    data: T,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FingerprintMatches {
+    #[serde(default)]
+    exact_matches: Vec<FingerprintMatch>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FingerprintMatch {
+    file: CurseForgeFile,
+}
+
+pub fn mod_loader_type(loader: &ModLoader) -> u32 {
+    match loader {
+        ModLoader::Forge => 1,
+        ModLoader::Fabric => 4,
+        ModLoader::Quilt => 5,
+        ModLoader::NeoForge => 6,
+    }
+}
+
+pub fn fingerprint(data: &[u8]) -> u32 {
+    let filtered: Vec<u8> = data.iter()
+        .copied()
+        .filter(|b| !matches!(b, 9 | 10 | 13 | 32))
+        .collect();
+    murmur2(&filtered, 1)
+}
+
+fn murmur2(data: &[u8], seed: u32) -> u32 {
+    const M: u32 = 0x5bd1_e995;
+
+    let mut h = seed ^ data.len() as u32;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> 24;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M) ^ k;
+    }
+
+    let rest = chunks.remainder();
+    if rest.len() >= 3 {
+        h ^= (rest[2] as u32) << 16;
+    }
+    if rest.len() >= 2 {
+        h ^= (rest[1] as u32) << 8;
+    }
+    if !rest.is_empty() {
+        h ^= rest[0] as u32;
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h
+}
+
+#[derive(Debug, Clone)]
+pub struct CurseForgeClient {
+    network: NetworkManager,
+    api_key: String,
+}
+
+impl CurseForgeClient {
+    pub fn new(network: NetworkManager, api_key: String) -> Self {
+        Self { network, api_key }
+    }
+
+    pub async fn files_from_fingerprints(&self, fingerprints: &[u32]) -> Result<Vec<CurseForgeFile>> {
+        let body = serde_json::json!({ "fingerprints": fingerprints });
+        let response: DataResponse<FingerprintMatches> = self.network
+            .post_json_with_headers(
+                &format!("{}/fingerprints/{}", CURSEFORGE_API_URL, MINECRAFT_GAME_ID),
+                &[("x-api-key", &self.api_key)],
+                &body,
+            )
+            .await?;
+        Ok(response.data.exact_matches.into_iter().map(|m| m.file).collect())
+    }
+
+    pub async fn files(&self, file_ids: &[u64]) -> Result<Vec<CurseForgeFile>> {
+        let body = serde_json::json!({ "fileIds": file_ids });
+        let response: DataResponse<Vec<CurseForgeFile>> = self.network
+            .post_json_with_headers(&format!("{}/mods/files", CURSEFORGE_API_URL), &[("x-api-key", &self.api_key)], &body)
+            .await?;
+        Ok(response.data)
+    }
+
+    pub async fn mod_info(&self, mod_id: u32) -> Result<CurseForgeMod> {
+        let response: DataResponse<CurseForgeMod> = self.network
+            .get_json_with_headers(&format!("{}/mods/{}", CURSEFORGE_API_URL, mod_id), &[("x-api-key", &self.api_key)])
+            .await?;
+        Ok(response.data)
+    }
+
+    pub async fn latest_file(
+        &self,
+        mod_id: u32,
+        loader: Option<&ModLoader>,
+        game_version: &str,
+    ) -> Result<Option<CurseForgeFile>> {
+        let mut params = vec![
+            ("gameVersion", game_version.to_string()),
+            ("pageSize", "1".to_string()),
+        ];
+        if let Some(loader) = loader {
+            params.push(("modLoaderType", mod_loader_type(loader).to_string()));
+        }
+
+        let url = url::Url::parse_with_params(
+            &format!("{}/mods/{}/files", CURSEFORGE_API_URL, mod_id),
+            &params,
+        ).map_err(|e| Error::Other(format!("Invalid CurseForge URL: {}", e)))?;
+
+        let response: DataResponse<Vec<CurseForgeFile>> = self.network
+            .get_json_with_headers(url.as_str(), &[("x-api-key", &self.api_key)])
+            .await?;
+        Ok(response.data.into_iter().next())
+    }
+}
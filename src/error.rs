@@ -1,13 +1,26 @@
 use thiserror::Error;
+use std::path::PathBuf;
 use std::time::SystemTimeError;
 use zip::result::ZipError;
 
+use crate::i18n::{tr, trf};
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("IO error: {0}")]
-    Io(#[from] std::io::Error),
+    Io(std::io::Error),
+
+    #[error("Disk is full: {0}")]
+    DiskFull(std::io::Error),
+
+    #[error("Hash mismatch for {}: expected {expected}, got {actual}", path.display())]
+    HashMismatch {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
 
     #[error("Network error: {0}")]
     Network(#[from] reqwest::Error),
@@ -45,9 +58,19 @@ pub enum Error {
     #[error("Java error: {0}")]
     Java(String),
 
+    #[error("No Java installation found")]
+    JavaMissing {
+        required: Option<u32>,
+    },
+
     #[error("Authentication error: {0}")]
     Auth(String),
 
+    #[error("Session of account '{account}' has expired")]
+    AuthExpired {
+        account: String,
+    },
+
     #[error("Settings error: {0}")]
     Settings(String),
 
@@ -57,6 +80,12 @@ pub enum Error {
     #[error("Mod error: {0}")]
     Mod(String),
 
+    #[error("Mojang services appear down: {0}")]
+    ServiceOutage(String),
+
+    #[error("No internet connection: {0}")]
+    Offline(String),
+
     #[error("Platform error: {0}")]
     Platform(String),
 
@@ -67,8 +96,72 @@ pub enum Error {
     Other(String),
 }
 
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        if err.kind() == std::io::ErrorKind::StorageFull {
+            Error::DiskFull(err)
+        } else {
+            Error::Io(err)
+        }
+    }
+}
+
 impl From<anyhow::Error> for Error {
     fn from(err: anyhow::Error) -> Self {
-        Error::Other(err.to_string())
+        // Managers that return anyhow still mostly fail with our own errors;
+        // keep those intact so their hints survive the round trip.
+        match err.downcast::<Error>() {
+            Ok(err) => err,
+            Err(err) => match err.downcast::<std::io::Error>() {
+                Ok(err) => err.into(),
+                Err(err) => Error::Other(err.to_string()),
+            },
+        }
     }
-} 
\ No newline at end of file
+}
+
+/// What went wrong in the user's terms, plus what they can do about it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserHint {
+    pub hint: String,
+    pub action: String,
+}
+
+impl UserHint {
+    fn new(hint: String, action: &'static str) -> Self {
+        Self { hint, action: tr(action).to_string() }
+    }
+}
+
+impl Error {
+    pub fn user_hint(&self) -> Option<UserHint> {
+        let hint = match self {
+            Error::Network(e) if e.is_timeout() => UserHint::new(tr("hint.network_timeout").to_string(), "hint.action_network_timeout"),
+            Error::Network(e) => match e.status() {
+                Some(status) => UserHint::new(trf("hint.network_status", &[("status", &status)]), "hint.action_retry_later"),
+                None => UserHint::new(tr("hint.network_unreachable").to_string(), "hint.action_check_connection"),
+            },
+            Error::ServiceOutage(_) => UserHint::new(tr("hint.service_outage").to_string(), "hint.action_retry_later"),
+            Error::Offline(_) => UserHint::new(tr("hint.offline").to_string(), "hint.action_offline"),
+            Error::DiskFull(_) => UserHint::new(tr("hint.disk_full").to_string(), "hint.action_free_space"),
+            Error::HashMismatch { path, .. } => UserHint::new(
+                trf("hint.hash_mismatch", &[("file", &path.file_name().unwrap_or_default().to_string_lossy())]),
+                "hint.action_redownload",
+            ),
+            Error::JavaMissing { required: Some(version) } => UserHint::new(
+                trf("hint.java_missing_version", &[("version", version)]),
+                "hint.action_install_java",
+            ),
+            Error::JavaMissing { required: None } => UserHint::new(tr("hint.java_missing").to_string(), "hint.action_install_java"),
+            Error::AuthExpired { account } => UserHint::new(
+                trf("hint.auth_expired", &[("account", account)]),
+                "hint.action_relogin",
+            ),
+            Error::Io(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                UserHint::new(tr("hint.permission_denied").to_string(), "hint.action_check_permissions")
+            }
+            _ => return None,
+        };
+        Some(hint)
+    }
+}
@@ -0,0 +1,233 @@
+use tokio::sync::broadcast;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use uuid::Uuid;
+
+use crate::i18n::trf;
+use crate::auth::Account;
+use crate::java::{JavaInstallation, JavaManager};
+use crate::launch::PreparedLaunch;
+use crate::logs::LogLevel;
+use crate::instance::ModLoader;
+use crate::loaders::LoaderVersion;
+use crate::modpack::ModpackUpdate;
+use crate::mods::DependencyCheck;
+use crate::motd::RemoteMotd;
+use crate::news::PatchNote;
+use crate::platform::SystemInfo;
+use crate::skins::HeadImage;
+use crate::status::{Connectivity, MojangStatus};
+use crate::storage::{Cleanup, CleanupResult, UsageTarget};
+use crate::progress::ProgressUpdate;
+use crate::tasks::TaskId;
+use crate::updater::Update;
+use crate::version::{LoadedManifest, VerifyReport};
+use crate::Result;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Readiness {
+    Pending,
+    Ready,
+    Failed(String),
+}
+
+impl Readiness {
+    pub fn is_pending(&self) -> bool {
+        *self == Readiness::Pending
+    }
+}
+
+pub enum AppEvent {
+    JavaScanned {
+        task: TaskId,
+        manager: Option<JavaManager>,
+        result: Result<()>,
+    },
+    JavaDownloaded {
+        task: TaskId,
+        version: u8,
+        result: Result<JavaInstallation>,
+    },
+    ManifestLoaded {
+        task: TaskId,
+        result: anyhow::Result<LoadedManifest>,
+        status: Option<MojangStatus>,
+    },
+    HeadFetched {
+        account: Box<Account>,
+        result: Result<HeadImage>,
+    },
+    DiskUsageMeasured {
+        target: UsageTarget,
+        bytes: u64,
+    },
+    TaskProgress {
+        task: TaskId,
+        update: ProgressUpdate,
+    },
+    VersionDownloaded {
+        task: TaskId,
+        version_id: String,
+        result: Result<()>,
+        assets_error: Option<String>,
+        status: Option<MojangStatus>,
+    },
+    VersionVerified {
+        task: TaskId,
+        version_id: String,
+        result: Result<VerifyReport>,
+    },
+    NewsLoaded {
+        task: TaskId,
+        result: Result<Vec<PatchNote>>,
+    },
+    MotdLoaded {
+        task: TaskId,
+        result: Result<RemoteMotd>,
+    },
+    LoaderVersionsLoaded {
+        task: TaskId,
+        loader: ModLoader,
+        game_version: String,
+        result: Result<Vec<LoaderVersion>>,
+    },
+    SystemDetected {
+        task: TaskId,
+        info: Box<SystemInfo>,
+    },
+    LaunchStaged {
+        task: TaskId,
+        instance_id: Uuid,
+        result: Result<Box<PreparedLaunch>>,
+    },
+    UpdateChecked {
+        task: TaskId,
+        result: Result<Option<Box<Update>>>,
+    },
+    UpdateInstalled {
+        task: TaskId,
+        result: Result<std::path::PathBuf>,
+    },
+    ConnectivityChecked {
+        task: TaskId,
+        connectivity: Connectivity,
+    },
+    DependenciesChecked {
+        task: TaskId,
+        instance_id: Uuid,
+        result: Result<DependencyCheck>,
+    },
+    DependenciesInstalled {
+        task: TaskId,
+        instance_id: Uuid,
+        result: Result<usize>,
+    },
+    CleanupDone {
+        task: TaskId,
+        cleanup: Cleanup,
+        result: Result<CleanupResult>,
+    },
+    ModpackApplied {
+        task: TaskId,
+        update: Box<ModpackUpdate>,
+        result: Result<Vec<String>>,
+    },
+}
+
+pub struct EventBus {
+    sender: UnboundedSender<AppEvent>,
+    receiver: UnboundedReceiver<AppEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self { sender, receiver }
+    }
+
+    pub fn sender(&self) -> UnboundedSender<AppEvent> {
+        self.sender.clone()
+    }
+
+    pub fn try_recv(&mut self) -> Option<AppEvent> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Something that happened in the launcher, whatever is showing it. Managers
+/// emit these through a `Notifier`; the UI turns them into status lines and
+/// the log manager into log entries, and another frontend can subscribe the
+/// same way.
+#[derive(Debug, Clone)]
+pub enum LauncherEvent {
+    DownloadStarted { version_id: String },
+    VersionInstalled { version_id: String },
+    VersionInstallFailed { version_id: String, error: String },
+    LaunchStarted { instance_id: Uuid, name: String },
+    LaunchFailed { instance_id: Uuid, name: String, error: String },
+    AccountAdded { name: String },
+    AccountRemoved { name: String },
+}
+
+impl LauncherEvent {
+    pub fn log_entry(&self) -> (LogLevel, String, &'static str) {
+        match self {
+            LauncherEvent::DownloadStarted { version_id } => {
+                (LogLevel::Info, trf("log.version_download_started", &[("version", &version_id)]), "VersionManager")
+            }
+            LauncherEvent::VersionInstalled { version_id } => {
+                (LogLevel::Info, trf("log.version_downloaded", &[("version", &version_id)]), "VersionManager")
+            }
+            LauncherEvent::VersionInstallFailed { version_id, error } => {
+                (LogLevel::Error, trf("log.version_download_failed", &[("version", &version_id), ("error", &error)]), "VersionManager")
+            }
+            LauncherEvent::LaunchStarted { name, .. } => {
+                (LogLevel::Info, trf("log.instance_launched", &[("name", &name)]), "LaunchManager")
+            }
+            LauncherEvent::LaunchFailed { name, error, .. } => {
+                (LogLevel::Error, trf("log.launch_failed", &[("name", &name), ("error", &error)]), "LaunchManager")
+            }
+            LauncherEvent::AccountAdded { name } => {
+                (LogLevel::Info, trf("log.account_added", &[("name", &name)]), "AuthManager")
+            }
+            LauncherEvent::AccountRemoved { name } => {
+                (LogLevel::Info, trf("log.account_removed", &[("name", &name)]), "AuthManager")
+            }
+        }
+    }
+}
+
+const NOTIFIER_CAPACITY: usize = 256;
+
+// A subscriber that falls behind loses the oldest events instead of holding
+// up the manager that sends them.
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    sender: broadcast::Sender<LauncherEvent>,
+}
+
+impl Notifier {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(NOTIFIER_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn emit(&self, event: LauncherEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<LauncherEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,232 @@
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use sha2::{Sha256, Sha512};
+
+use crate::network::NetworkManager;
+use crate::{Error, Result};
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+const CHECKSUM_EXTENSIONS: &[&str] = &["sha1", "sha256", "sha512", "md5", "asc", "sig"];
+const CHECKSUM_FILES: &[&str] = &["checksums.txt", "sha256sums", "sha256sums.txt", "sha512sums", "sha512sums.txt", "sha1sums", "sha1sums.txt"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitHubSource {
+    pub owner: String,
+    pub repo: String,
+    pub asset_pattern: String,
+    #[serde(default)]
+    pub allow_prerelease: bool,
+}
+
+impl GitHubSource {
+    pub fn parse(repository: &str, asset_pattern: Option<&str>, allow_prerelease: bool) -> Result<Self> {
+        let repository = repository
+            .trim()
+            .trim_start_matches("https://github.com/")
+            .trim_end_matches('/');
+        let (owner, repo) = repository.split_once('/')
+            .filter(|(owner, repo)| !owner.is_empty() && !repo.is_empty() && !repo.contains('/'))
+            .ok_or_else(|| Error::Other(format!("Expected owner/repo, got '{}'", repository)))?;
+
+        Ok(Self {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            asset_pattern: asset_pattern.unwrap_or("*.jar").to_string(),
+            allow_prerelease,
+        })
+    }
+
+    pub fn display(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubMod {
+    pub source: GitHubSource,
+    pub tag: String,
+    pub filename: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubRelease {
+    pub tag_name: String,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub assets: Vec<GitHubAsset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubAsset {
+    pub name: String,
+    pub browser_download_url: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Checksum {
+    Sha1(String),
+    Sha256(String),
+    Sha512(String),
+}
+
+impl Checksum {
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let hex = hex.to_ascii_lowercase();
+        match hex.len() {
+            40 => Some(Self::Sha1(hex)),
+            64 => Some(Self::Sha256(hex)),
+            128 => Some(Self::Sha512(hex)),
+            _ => None,
+        }
+    }
+
+    pub fn verify(&self, path: &Path) -> Result<()> {
+        let data = std::fs::read(path)?;
+        let (expected, actual) = match self {
+            Self::Sha1(expected) => (expected, hex::encode(Sha1::digest(&data))),
+            Self::Sha256(expected) => (expected, hex::encode(Sha256::digest(&data))),
+            Self::Sha512(expected) => (expected, hex::encode(Sha512::digest(&data))),
+        };
+        if *expected != actual {
+            return Err(Error::Other(format!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                path.display(), expected, actual
+            )));
+        }
+        Ok(())
+    }
+}
+
+pub fn matches_pattern(name: &str, pattern: &str) -> bool {
+    fn matches(name: &[char], pattern: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|i| matches(&name[i..], rest)),
+            Some(('?', rest)) => !name.is_empty() && matches(&name[1..], rest),
+            Some((c, rest)) => name.first().is_some_and(|n| n.eq_ignore_ascii_case(c)) && matches(&name[1..], rest),
+        }
+    }
+    let name: Vec<char> = name.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    matches(&name, &pattern)
+}
+
+fn is_checksum_asset(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    CHECKSUM_FILES.contains(&lower.as_str())
+        || lower.rsplit_once('.').is_some_and(|(_, ext)| CHECKSUM_EXTENSIONS.contains(&ext))
+}
+
+fn parse_checksum_file(content: &str, asset_name: &str) -> Option<Checksum> {
+    let lines: Vec<&str> = content.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    for line in &lines {
+        let mut parts = line.split_whitespace();
+        let (Some(hash), file) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        match file.map(|f| f.trim_start_matches('*')) {
+            Some(file) if file == asset_name => return Checksum::from_hex(hash),
+            None if lines.len() == 1 => return Checksum::from_hex(hash),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone)]
+pub struct GitHubDownload {
+    pub tag: String,
+    pub path: PathBuf,
+    pub verified: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct GitHubClient {
+    network: NetworkManager,
+}
+
+impl GitHubClient {
+    pub fn new(network: NetworkManager) -> Self {
+        Self { network }
+    }
+
+    fn headers() -> [(&'static str, &'static str); 2] {
+        [
+            ("User-Agent", "mango-launcher"),
+            ("Accept", "application/vnd.github+json"),
+        ]
+    }
+
+    pub async fn latest_release(&self, source: &GitHubSource) -> Result<GitHubRelease> {
+        let url = format!("{}/repos/{}/{}/releases?per_page=20", GITHUB_API_URL, source.owner, source.repo);
+        let releases: Vec<GitHubRelease> = self.network.get_json_with_headers(&url, &Self::headers()).await?;
+        releases
+            .into_iter()
+            .find(|release| !release.draft && (source.allow_prerelease || !release.prerelease))
+            .ok_or_else(|| Error::Other(format!("{} has no published releases", source.display())))
+    }
+
+    pub fn find_asset<'a>(release: &'a GitHubRelease, source: &GitHubSource) -> Option<&'a GitHubAsset> {
+        release.assets.iter()
+            .filter(|asset| !is_checksum_asset(&asset.name))
+            .find(|asset| matches_pattern(&asset.name, &source.asset_pattern))
+    }
+
+    pub async fn checksum(&self, release: &GitHubRelease, asset: &GitHubAsset) -> Result<Option<Checksum>> {
+        let candidates = release.assets.iter().filter(|candidate| {
+            let lower = candidate.name.to_ascii_lowercase();
+            CHECKSUM_FILES.contains(&lower.as_str())
+                || candidate.name.strip_prefix(asset.name.as_str())
+                    .is_some_and(|ext| matches!(ext, ".sha1" | ".sha256" | ".sha512"))
+        });
+
+        for candidate in candidates {
+            let content = self.network.get(&candidate.browser_download_url).await?;
+            if let Some(checksum) = parse_checksum_file(&content, &asset.name) {
+                return Ok(Some(checksum));
+            }
+        }
+        Ok(None)
+    }
+
+    pub async fn download_latest(&self, source: &GitHubSource, target_dir: &Path) -> Result<GitHubDownload> {
+        let release = self.latest_release(source).await?;
+        let asset = Self::find_asset(&release, source)
+            .ok_or_else(|| Error::Other(format!(
+                "Release {} of {} has no asset matching '{}'",
+                release.tag_name, source.display(), source.asset_pattern
+            )))?;
+        self.download_asset(&release, asset, target_dir).await
+    }
+
+    pub async fn download_asset(&self, release: &GitHubRelease, asset: &GitHubAsset, target_dir: &Path) -> Result<GitHubDownload> {
+        let file_name = Path::new(&asset.name)
+            .file_name()
+            .ok_or_else(|| Error::Other(format!("Invalid asset name '{}'", asset.name)))?;
+        let path = target_dir.join(file_name);
+        self.network.download_file(&asset.browser_download_url, &path, None, None).await?;
+
+        let checksum = self.checksum(release, asset).await?;
+        if let Some(checksum) = &checksum {
+            if let Err(e) = checksum.verify(&path) {
+                let _ = std::fs::remove_file(&path);
+                return Err(e);
+            }
+        }
+
+        Ok(GitHubDownload {
+            tag: release.tag_name.clone(),
+            path,
+            verified: checksum.is_some(),
+        })
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::LazyLock;
+
+use crate::settings::Language;
+
+static ENGLISH: LazyLock<Catalog> = LazyLock::new(|| Catalog::parse(include_str!("../locales/en.toml")));
+static RUSSIAN: LazyLock<Catalog> = LazyLock::new(|| Catalog::parse(include_str!("../locales/ru.toml")));
+static RUSSIAN_ACTIVE: AtomicBool = AtomicBool::new(true);
+
+struct Catalog {
+    strings: HashMap<String, String>,
+}
+
+impl Catalog {
+    fn parse(source: &str) -> Self {
+        let table: toml::Table = source.parse().expect("embedded locale must be valid TOML");
+        let mut strings = HashMap::new();
+        Self::flatten("", &table, &mut strings);
+        Self { strings }
+    }
+
+    fn flatten(prefix: &str, table: &toml::Table, strings: &mut HashMap<String, String>) {
+        for (key, value) in table {
+            let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+            match value {
+                toml::Value::Table(nested) => Self::flatten(&path, nested, strings),
+                toml::Value::String(text) => {
+                    strings.insert(path, text.clone());
+                }
+                other => {
+                    strings.insert(path, other.to_string());
+                }
+            }
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.strings.get(key).map(String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PluralForm {
+    One,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralForm {
+    fn for_count(language: &Language, count: u64) -> Self {
+        match language {
+            Language::English if count == 1 => PluralForm::One,
+            Language::English => PluralForm::Other,
+            Language::Russian => match (count % 10, count % 100) {
+                (1, rem) if rem != 11 => PluralForm::One,
+                (2..=4, rem) if !(12..=14).contains(&rem) => PluralForm::Few,
+                _ => PluralForm::Many,
+            },
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            PluralForm::One => "one",
+            PluralForm::Few => "few",
+            PluralForm::Many => "many",
+            PluralForm::Other => "other",
+        }
+    }
+}
+
+pub fn set_language(language: &Language) {
+    RUSSIAN_ACTIVE.store(*language == Language::Russian, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    if RUSSIAN_ACTIVE.load(Ordering::Relaxed) {
+        Language::Russian
+    } else {
+        Language::English
+    }
+}
+
+fn catalog() -> &'static Catalog {
+    match language() {
+        Language::Russian => &RUSSIAN,
+        Language::English => &ENGLISH,
+    }
+}
+
+fn lookup(key: &str) -> Option<&'static str> {
+    catalog().get(key).or_else(|| ENGLISH.get(key))
+}
+
+pub fn tr(key: &'static str) -> &'static str {
+    lookup(key).unwrap_or_else(|| {
+        tracing::warn!("Missing translation: {}", key);
+        key
+    })
+}
+
+pub fn trf(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    substitute(tr(key), args)
+}
+
+pub fn plural(key: &'static str, count: u64, args: &[(&str, &dyn Display)]) -> String {
+    let form = PluralForm::for_count(&language(), count);
+    let template = [form.key(), "other", "many"]
+        .iter()
+        .find_map(|form| lookup(&format!("{}.{}", key, form)))
+        .unwrap_or(key);
+    substitute(template, args).replace("{count}", &count.to_string())
+}
+
+fn substitute(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter().fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), &value.to_string())
+    })
+}
@@ -0,0 +1,581 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+
+use crate::instance::{Instance, InstanceLinks, ModLoader};
+use crate::mrpack::{MrpackIndex, MRPACK_INDEX, MRPACK_OVERRIDES};
+use crate::network::NetworkManager;
+use crate::{Error, Result};
+
+const CURSEFORGE_DOWNLOAD_URL: &str = "https://www.curseforge.com/api/v1/mods";
+const LAUNCHER_EXPORT_ENTRY: &str = "instance.json";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportKind {
+    Launcher,
+    MultiMc,
+    CurseForge,
+    Modrinth,
+    Official,
+}
+
+#[derive(Debug, Clone)]
+pub enum ImportSource {
+    Directory(PathBuf),
+    // A game directory the official launcher shares with its versions,
+    // libraries and assets; those stay behind.
+    LauncherRoot(PathBuf),
+    Zip { archive: PathBuf, prefix: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingDownload {
+    pub urls: Vec<String>,
+    pub path: PathBuf,
+    pub sha1: Option<String>,
+    pub sha512: Option<String>,
+    // CurseForge manifests carry no hashes, only the file ID to look them
+    // up by; the fingerprint is checked when the file has no SHA-1.
+    pub curseforge_file: Option<u64>,
+    pub fingerprint: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportPlan {
+    pub kind: ImportKind,
+    pub name: String,
+    pub pack_version: Option<String>,
+    pub minecraft_version: String,
+    pub mod_loader: Option<ModLoader>,
+    pub mod_loader_version: Option<String>,
+    pub java_args: Option<String>,
+    pub memory_min: Option<u32>,
+    pub memory_max: Option<u32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fullscreen: Option<bool>,
+    pub notes: Option<String>,
+    pub icon: Option<String>,
+    pub pre_launch_command: Option<String>,
+    pub post_exit_command: Option<String>,
+    pub links: InstanceLinks,
+    pub game_files: Vec<ImportSource>,
+    pub pending_downloads: Vec<PendingDownload>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MmcPack {
+    components: Vec<MmcComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MmcComponent {
+    uid: String,
+    version: Option<String>,
+    cached_version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurseForgeManifest {
+    minecraft: CurseForgeMinecraft,
+    name: String,
+    version: Option<String>,
+    author: Option<String>,
+    #[serde(default)]
+    files: Vec<CurseForgeFile>,
+    overrides: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeMinecraft {
+    version: String,
+    #[serde(default)]
+    mod_loaders: Vec<CurseForgeLoader>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurseForgeLoader {
+    id: String,
+    #[serde(default)]
+    primary: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeFile {
+    #[serde(rename = "projectID")]
+    project_id: u64,
+    #[serde(rename = "fileID")]
+    file_id: u64,
+    #[serde(default = "default_required")]
+    required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+pub fn read_plan(path: &Path) -> Result<ImportPlan> {
+    if path.is_dir() {
+        if path.join("instance.cfg").exists() {
+            return read_multimc_dir(path);
+        }
+        return Err(Error::Instance(format!("{} is not a MultiMC/Prism instance folder", path.display())));
+    }
+
+    let mut archive = zip::ZipArchive::new(File::open(path)?)?;
+    if let Ok(content) = read_zip_text(&mut archive, MRPACK_INDEX) {
+        return read_mrpack_index(path, &content);
+    }
+    if let Ok(mut manifest) = archive.by_name("manifest.json") {
+        let mut content = String::new();
+        manifest.read_to_string(&mut content)?;
+        return read_curseforge_manifest(path, &content);
+    }
+    if let Ok(content) = read_zip_text(&mut archive, LAUNCHER_EXPORT_ENTRY) {
+        return read_launcher_export(path, &content);
+    }
+
+    let cfg_entry = archive.file_names()
+        .filter(|name| name.rsplit('/').next() == Some("instance.cfg"))
+        .min_by_key(|name| name.len())
+        .map(|name| name.to_string());
+    if let Some(cfg_entry) = cfg_entry {
+        let prefix = cfg_entry.trim_end_matches("instance.cfg").to_string();
+        let cfg = read_zip_text(&mut archive, &cfg_entry)?;
+        let pack = read_zip_text(&mut archive, &format!("{}mmc-pack.json", prefix)).ok();
+        let game_prefix = [".minecraft/", "minecraft/"]
+            .iter()
+            .map(|dir| format!("{}{}", prefix, dir))
+            .find(|dir| archive.file_names().any(|name| name.starts_with(dir.as_str())));
+
+        let game_files = game_prefix
+            .map(|prefix| ImportSource::Zip { archive: path.to_path_buf(), prefix })
+            .into_iter()
+            .collect();
+        return build_multimc_plan(&cfg, pack.as_deref(), game_files);
+    }
+
+    Err(Error::Instance(format!("{} is neither a modpack nor a launcher or MultiMC/Prism export", path.display())))
+}
+
+fn read_zip_text(archive: &mut zip::ZipArchive<File>, name: &str) -> Result<String> {
+    let mut entry = archive.by_name(name)?;
+    let mut content = String::new();
+    entry.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+fn read_multimc_dir(path: &Path) -> Result<ImportPlan> {
+    let cfg = std::fs::read_to_string(path.join("instance.cfg"))?;
+    let pack = std::fs::read_to_string(path.join("mmc-pack.json")).ok();
+    let game_files = [".minecraft", "minecraft"]
+        .iter()
+        .map(|dir| path.join(dir))
+        .find(|dir| dir.is_dir())
+        .map(ImportSource::Directory)
+        .into_iter()
+        .collect();
+    build_multimc_plan(&cfg, pack.as_deref(), game_files)
+}
+
+fn parse_instance_cfg(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter(|line| !line.starts_with('['))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().trim_matches('"').to_string()))
+        .collect()
+}
+
+fn build_multimc_plan(cfg: &str, pack: Option<&str>, game_files: Vec<ImportSource>) -> Result<ImportPlan> {
+    let cfg = parse_instance_cfg(cfg);
+    let enabled = |key: &str| cfg.get(key).is_some_and(|v| v == "true");
+    let number = |key: &str| cfg.get(key).and_then(|v| v.parse::<u32>().ok());
+
+    let mut minecraft_version = cfg.get("IntendedVersion").cloned();
+    let mut mod_loader = None;
+    let mut mod_loader_version = None;
+
+    if let Some(pack) = pack {
+        let pack: MmcPack = serde_json::from_str(pack)?;
+        for component in pack.components {
+            let version = component.version.or(component.cached_version);
+            let loader = match component.uid.as_str() {
+                "net.minecraft" => {
+                    minecraft_version = version;
+                    continue;
+                }
+                "net.minecraftforge" => ModLoader::Forge,
+                "net.neoforged" => ModLoader::NeoForge,
+                "net.fabricmc.fabric-loader" => ModLoader::Fabric,
+                "org.quiltmc.quilt-loader" => ModLoader::Quilt,
+                _ => continue,
+            };
+            mod_loader = Some(loader);
+            mod_loader_version = version;
+        }
+    }
+
+    let minecraft_version = minecraft_version
+        .ok_or_else(|| Error::Instance("Could not determine the Minecraft version of the instance".to_string()))?;
+
+    Ok(ImportPlan {
+        kind: ImportKind::MultiMc,
+        name: cfg.get("name").cloned().unwrap_or_else(|| "Imported instance".to_string()),
+        pack_version: None,
+        minecraft_version,
+        mod_loader,
+        mod_loader_version,
+        java_args: cfg.get("JvmArgs").filter(|_| enabled("OverrideJavaArgs")).cloned(),
+        memory_min: number("MinMemAlloc").filter(|_| enabled("OverrideMemory")),
+        memory_max: number("MaxMemAlloc").filter(|_| enabled("OverrideMemory")),
+        width: number("MinecraftWinWidth").filter(|_| enabled("OverrideWindow")),
+        height: number("MinecraftWinHeight").filter(|_| enabled("OverrideWindow")),
+        fullscreen: enabled("OverrideWindow").then(|| enabled("LaunchMaximized")),
+        notes: cfg.get("notes").filter(|n| !n.is_empty()).cloned(),
+        icon: cfg.get("iconKey").and_then(|key| multimc_icon(key)).map(str::to_string),
+        pre_launch_command: cfg.get("PreLaunchCommand").filter(|c| enabled("OverrideCommands") && !c.is_empty()).cloned(),
+        post_exit_command: cfg.get("PostExitCommand").filter(|c| enabled("OverrideCommands") && !c.is_empty()).cloned(),
+        links: InstanceLinks::default(),
+        game_files,
+        pending_downloads: Vec::new(),
+    })
+}
+
+// MultiMC/Prism ship bitmap icons under these keys; the closest glyph keeps
+// instances recognisable in the list.
+pub(crate) fn multimc_icon(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "grass" | "dirt" | "planks" => "🌲",
+        "stone" | "brick" => "🧱",
+        "diamond" | "gold" | "iron" => "💎",
+        "creeper" | "tnt" => "💥",
+        "enderman" | "enderpearl" => "👁",
+        "flame" | "meat" => "🔥",
+        "netherstar" | "infinity" => "⭐",
+        "skeleton" => "🏹",
+        "squid" => "🦑",
+        "chicken" => "🐔",
+        "magitech" | "ftb-logo" | "ftb-glow" => "⚙",
+        "herobrine" | "steve" => "⛏",
+        _ => return None,
+    })
+}
+
+// Zip exports made by this launcher: instance.json next to the game folder.
+fn read_launcher_export(path: &Path, content: &str) -> Result<ImportPlan> {
+    let instance: Instance = serde_json::from_str(content)?;
+    Ok(ImportPlan {
+        kind: ImportKind::Launcher,
+        icon: instance.icon_glyph().map(str::to_string),
+        name: instance.name,
+        pack_version: None,
+        minecraft_version: instance.minecraft_version,
+        mod_loader: instance.mod_loader,
+        mod_loader_version: instance.mod_loader_version,
+        java_args: instance.java_args,
+        memory_min: instance.memory_min,
+        memory_max: instance.memory_max,
+        width: instance.width,
+        height: instance.height,
+        fullscreen: instance.fullscreen,
+        notes: instance.notes,
+        pre_launch_command: instance.pre_launch_command,
+        post_exit_command: instance.post_launch_command,
+        links: instance.links,
+        game_files: vec![ImportSource::Zip { archive: path.to_path_buf(), prefix: ".minecraft/".to_string() }],
+        pending_downloads: Vec::new(),
+    })
+}
+
+fn read_curseforge_manifest(path: &Path, content: &str) -> Result<ImportPlan> {
+    let manifest: CurseForgeManifest = serde_json::from_str(content)?;
+
+    let loader = manifest.minecraft.mod_loaders
+        .iter()
+        .find(|l| l.primary)
+        .or_else(|| manifest.minecraft.mod_loaders.first());
+    let (mod_loader, mod_loader_version) = match loader.and_then(|l| l.id.split_once('-')) {
+        Some(("forge", version)) => (Some(ModLoader::Forge), Some(version.to_string())),
+        Some(("neoforge", version)) => (Some(ModLoader::NeoForge), Some(version.to_string())),
+        Some(("fabric", version)) => (Some(ModLoader::Fabric), Some(version.to_string())),
+        Some(("quilt", version)) => (Some(ModLoader::Quilt), Some(version.to_string())),
+        _ => (None, None),
+    };
+
+    let pending_downloads = manifest.files
+        .iter()
+        .filter(|file| file.required)
+        .map(|file| PendingDownload {
+            urls: vec![format!("{}/{}/files/{}/download", CURSEFORGE_DOWNLOAD_URL, file.project_id, file.file_id)],
+            path: PathBuf::from("mods").join(format!("curseforge-{}-{}.jar", file.project_id, file.file_id)),
+            sha1: None,
+            sha512: None,
+            curseforge_file: Some(file.file_id),
+            fingerprint: None,
+        })
+        .collect();
+
+    let notes = match (&manifest.version, &manifest.author) {
+        (Some(version), Some(author)) => Some(format!("{} {} by {}", manifest.name, version, author)),
+        (Some(version), None) => Some(format!("{} {}", manifest.name, version)),
+        _ => None,
+    };
+    let overrides = manifest.overrides.clone().unwrap_or_else(|| "overrides".to_string());
+
+    Ok(ImportPlan {
+        kind: ImportKind::CurseForge,
+        name: manifest.name,
+        pack_version: manifest.version,
+        minecraft_version: manifest.minecraft.version,
+        mod_loader,
+        mod_loader_version,
+        java_args: None,
+        memory_min: None,
+        memory_max: None,
+        width: None,
+        height: None,
+        fullscreen: None,
+        notes,
+        icon: None,
+        pre_launch_command: None,
+        post_exit_command: None,
+        links: InstanceLinks::default(),
+        game_files: vec![ImportSource::Zip {
+            archive: path.to_path_buf(),
+            prefix: format!("{}/", overrides.trim_end_matches('/')),
+        }],
+        pending_downloads,
+    })
+}
+
+fn read_mrpack_index(path: &Path, content: &str) -> Result<ImportPlan> {
+    let index: MrpackIndex = serde_json::from_str(content)?;
+    if index.game != "minecraft" {
+        return Err(Error::Instance(format!("Unsupported modpack game '{}'", index.game)));
+    }
+
+    let minecraft_version = index.dependencies.get("minecraft")
+        .cloned()
+        .ok_or_else(|| Error::Instance("Modpack does not declare a Minecraft version".to_string()))?;
+
+    let loader = [
+        ("forge", ModLoader::Forge),
+        ("neoforge", ModLoader::NeoForge),
+        ("fabric-loader", ModLoader::Fabric),
+        ("quilt-loader", ModLoader::Quilt),
+    ]
+    .into_iter()
+    .find_map(|(key, loader)| index.dependencies.get(key).map(|version| (loader, version.clone())));
+
+    let mut pending_downloads = Vec::new();
+    for file in &index.files {
+        if file.env.as_ref().is_some_and(|env| env.client == "unsupported") {
+            continue;
+        }
+
+        let relative = Path::new(&file.path);
+        if relative.is_absolute() || relative.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
+            return Err(Error::Instance(format!("Modpack file path escapes the instance: {}", file.path)));
+        }
+        if file.downloads.is_empty() {
+            tracing::warn!("Modpack file {} has no download URLs", file.path);
+            continue;
+        }
+
+        pending_downloads.push(PendingDownload {
+            urls: file.downloads.clone(),
+            path: relative.to_path_buf(),
+            sha1: file.hashes.get("sha1").cloned(),
+            sha512: file.hashes.get("sha512").cloned(),
+            curseforge_file: None,
+            fingerprint: None,
+        });
+    }
+
+    let game_files = [MRPACK_OVERRIDES, "client-overrides"]
+        .iter()
+        .map(|dir| ImportSource::Zip { archive: path.to_path_buf(), prefix: format!("{}/", dir) })
+        .collect();
+
+    Ok(ImportPlan {
+        kind: ImportKind::Modrinth,
+        name: index.name,
+        pack_version: Some(index.version_id),
+        minecraft_version,
+        mod_loader: loader.as_ref().map(|(loader, _)| loader.clone()),
+        mod_loader_version: loader.map(|(_, version)| version),
+        java_args: None,
+        memory_min: None,
+        memory_max: None,
+        width: None,
+        height: None,
+        fullscreen: None,
+        notes: index.summary,
+        icon: None,
+        pre_launch_command: None,
+        post_exit_command: None,
+        links: InstanceLinks::default(),
+        game_files,
+        pending_downloads,
+    })
+}
+
+pub async fn fetch_pending(network: &NetworkManager, download: &PendingDownload, game_dir: &Path) -> Result<()> {
+    let target = game_dir.join(&download.path);
+    let mut last_error = None;
+
+    for url in &download.urls {
+        match network.download_file(url, &target, download.sha1.as_deref(), None).await {
+            Ok(()) => {
+                if let Some(expected) = &download.sha512 {
+                    let (_, actual) = crate::mrpack::hash_file(&target)?;
+                    if !actual.eq_ignore_ascii_case(expected) {
+                        let _ = std::fs::remove_file(&target);
+                        last_error = Some(Error::Other(format!("SHA-512 mismatch for {}", download.path.display())));
+                        continue;
+                    }
+                }
+                if let Some(expected) = download.fingerprint.filter(|_| download.sha1.is_none()) {
+                    if crate::curseforge::fingerprint(&std::fs::read(&target)?) != expected {
+                        let _ = std::fs::remove_file(&target);
+                        last_error = Some(Error::Other(format!("Fingerprint mismatch for {}", download.path.display())));
+                        continue;
+                    }
+                }
+                return Ok(());
+            }
+            Err(e) => {
+                tracing::warn!("Download from {} failed: {}", url, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| Error::Other(format!("No download URLs for {}", download.path.display()))))
+}
+
+// Paths inside the game directory are keyed with forward slashes so that
+// instance.json reads the same on every platform.
+pub fn game_path(relative: &Path) -> String {
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+pub fn copy_game_files(source: &ImportSource, game_dir: &Path) -> Result<usize> {
+    copy_game_files_matching(source, game_dir, |_| true)
+}
+
+pub fn copy_game_files_matching(source: &ImportSource, game_dir: &Path, filter: impl Fn(&str) -> bool) -> Result<usize> {
+    std::fs::create_dir_all(game_dir)?;
+    let mut copied = 0;
+
+    match source {
+        ImportSource::Directory(dir) | ImportSource::LauncherRoot(dir) => {
+            for entry in walk_source(source, dir) {
+                let entry = entry?;
+                let Ok(relative) = entry.path().strip_prefix(dir) else { continue };
+                let target = game_dir.join(relative);
+                if entry.file_type().is_dir() {
+                    std::fs::create_dir_all(&target)?;
+                } else if entry.file_type().is_file() && filter(&game_path(relative)) {
+                    std::fs::copy(entry.path(), &target)?;
+                    copied += 1;
+                }
+            }
+        }
+        ImportSource::Zip { archive, prefix } => {
+            let mut archive = zip::ZipArchive::new(File::open(archive)?)?;
+            for i in 0..archive.len() {
+                let mut entry = archive.by_index(i)?;
+                let Some(name) = entry.enclosed_name().map(|p| p.to_path_buf()) else { continue };
+                let Ok(relative) = name.strip_prefix(prefix.trim_end_matches('/')) else { continue };
+                if relative.as_os_str().is_empty() {
+                    continue;
+                }
+
+                let target = game_dir.join(relative);
+                if entry.is_dir() {
+                    std::fs::create_dir_all(&target)?;
+                } else if filter(&game_path(relative)) {
+                    if let Some(parent) = target.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    let mut output = File::create(&target)?;
+                    std::io::copy(&mut entry, &mut output)?;
+                    copied += 1;
+                }
+            }
+        }
+    }
+
+    Ok(copied)
+}
+
+fn walk_source<'a>(source: &ImportSource, dir: &'a Path) -> impl Iterator<Item = walkdir::Result<walkdir::DirEntry>> + 'a {
+    let launcher_root = matches!(source, ImportSource::LauncherRoot(_));
+    walkdir::WalkDir::new(dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(move |entry| !(launcher_root && entry.depth() == 1 && is_launcher_file(&entry.file_name().to_string_lossy())))
+}
+
+// Top-level entries of the official launcher's folder that belong to the
+// launcher rather than to the game.
+fn is_launcher_file(name: &str) -> bool {
+    const LAUNCHER_DIRS: &[&str] = &["versions", "libraries", "assets", "runtime", "bin", "webcache", "webcache2", "logs", "crash-reports"];
+    LAUNCHER_DIRS.contains(&name)
+        || name.starts_with("launcher_")
+        || name.starts_with("treatment_tags")
+        || name.ends_with(".log")
+}
+
+// SHA-1 of every file a source would copy, by game-relative path. Later
+// sources win, matching the order `copy_game_files` writes them in.
+pub fn hash_game_files(sources: &[ImportSource]) -> Result<BTreeMap<String, String>> {
+    let mut hashes = BTreeMap::new();
+    for source in sources {
+        match source {
+            ImportSource::Directory(dir) | ImportSource::LauncherRoot(dir) => {
+                for entry in walk_source(source, dir) {
+                    let entry = entry?;
+                    let Ok(relative) = entry.path().strip_prefix(dir) else { continue };
+                    if entry.file_type().is_file() {
+                        hashes.insert(game_path(relative), sha1_hex(File::open(entry.path())?)?);
+                    }
+                }
+            }
+            ImportSource::Zip { archive, prefix } => {
+                let mut archive = zip::ZipArchive::new(File::open(archive)?)?;
+                for i in 0..archive.len() {
+                    let entry = archive.by_index(i)?;
+                    let Some(name) = entry.enclosed_name().map(|p| p.to_path_buf()) else { continue };
+                    let Ok(relative) = name.strip_prefix(prefix.trim_end_matches('/')) else { continue };
+                    if relative.as_os_str().is_empty() || entry.is_dir() {
+                        continue;
+                    }
+                    hashes.insert(game_path(relative), sha1_hex(entry)?);
+                }
+            }
+        }
+    }
+    Ok(hashes)
+}
+
+pub fn sha1_hex(mut reader: impl Read) -> Result<String> {
+    let mut hasher = Sha1::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
@@ -1,10 +1,27 @@
  
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use crate::{Error, Result};
+use crate::i18n::tr;
+use crate::github::GitHubMod;
+use crate::jvm_lint::GcPreset;
+use crate::modpack::ModpackSource;
+use crate::import::PendingDownload;
+use crate::profile::Profile;
+use crate::quickplay::QuickPlay;
+use crate::schema;
+use crate::settings::Settings;
+use crate::version::VersionChannels;
+use crate::mrpack::{MrpackFile, MrpackIndex, MRPACK_INDEX, MRPACK_OVERRIDES};
+
+pub const EXPORT_EXCLUDED_DIRS: &[&str] = &["natives", "logs", "crash-reports", "assets", "libraries"];
+pub const ICON_PRESETS: &[&str] = &["⛏", "⚔", "🏹", "💎", "🌲", "🔥", "❄", "🌋", "🏰", "🚀", "⚙", "🧪", "⭐", "🐉", "🧱", "🍄"];
+pub const ICON_MAX_CHARS: usize = 3;
 
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,14 +44,275 @@ pub struct Instance {
     pub memory_max: Option<u32>,
     pub width: Option<u32>,
     pub height: Option<u32>,
-    pub fullscreen: bool,
+    /// Unset follows the profile, then `minecraft.fullscreen` in settings.
+    pub fullscreen: Option<bool>,
     pub auto_connect: Option<String>,
     pub pre_launch_command: Option<String>,
     pub post_launch_command: Option<String>,
     pub disabled: bool,
+    #[serde(default)]
+    pub links: InstanceLinks,
+    #[serde(default)]
+    pub approved_commands: Vec<String>,
+    #[serde(default)]
+    pub dev_watch: bool,
+    #[serde(default)]
+    pub github_mods: Vec<GitHubMod>,
+    /// A client jar from GitHub releases, launched in place of the game's
+    /// own jar.
+    #[serde(default)]
+    pub github_client: Option<GitHubMod>,
+    #[serde(default)]
+    pub launch_variants: Vec<LaunchVariant>,
+    #[serde(default)]
+    pub version_channels: Option<VersionChannels>,
+    #[serde(default)]
+    pub demo_mode: bool,
+    #[serde(default)]
+    pub last_account: Option<Uuid>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub gc_preset: Option<GcPreset>,
+    #[serde(default)]
+    pub modpack: Option<ModpackSource>,
+    #[serde(default)]
+    pub quick_play: Option<QuickPlay>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Which layer a launch setting was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingSource {
+    Instance,
+    Profile,
+    Global,
+}
+
+impl SettingSource {
+    /// Suffix shown next to a value the instance inherits; empty for its own.
+    pub fn marker(&self) -> String {
+        match self {
+            SettingSource::Instance => String::new(),
+            SettingSource::Profile => format!(" ({})", tr("edit.inherited_profile")),
+            SettingSource::Global => format!(" ({})", tr("edit.inherited_global")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layered<T> {
+    pub value: T,
+    pub source: SettingSource,
+}
+
+impl<T> Layered<T> {
+    fn resolve(instance: Option<T>, profile: Option<T>, global: T) -> Self {
+        match (instance, profile) {
+            (Some(value), _) => Self { value, source: SettingSource::Instance },
+            (None, Some(value)) => Self { value, source: SettingSource::Profile },
+            (None, None) => Self { value: global, source: SettingSource::Global },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EffectiveSettings {
+    pub memory_min: Layered<u32>,
+    pub memory_max: Layered<u32>,
+    pub resolution: Layered<(u32, u32)>,
+    pub fullscreen: Layered<bool>,
+    pub java_args: Layered<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LaunchVariant {
+    pub name: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub fullscreen: Option<bool>,
+    pub memory_min: Option<u32>,
+    pub memory_max: Option<u32>,
+    pub java_args: Option<String>,
+    pub wrapper_command: Option<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstanceLinks {
+    pub project_url: Option<String>,
+    pub issues_url: Option<String>,
+    pub wiki_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InstanceLink {
+    Project,
+    Issues,
+    Wiki,
+}
+
+impl InstanceLinks {
+    pub fn get(&self, link: InstanceLink) -> Option<&str> {
+        match link {
+            InstanceLink::Project => self.project_url.as_deref(),
+            InstanceLink::Issues => self.issues_url.as_deref(),
+            InstanceLink::Wiki => self.wiki_url.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, link: InstanceLink, url: Option<String>) {
+        let slot = match link {
+            InstanceLink::Project => &mut self.project_url,
+            InstanceLink::Issues => &mut self.issues_url,
+            InstanceLink::Wiki => &mut self.wiki_url,
+        };
+        *slot = url;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.project_url.is_none() && self.issues_url.is_none() && self.wiki_url.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LaunchHook {
+    PreLaunch,
+    PostExit,
+}
+
+impl Instance {
+    pub fn client_jar(&self) -> Option<PathBuf> {
+        self.github_client.as_ref().map(|client| self.path.join("client").join(&client.filename))
+    }
+
+    pub fn launch_commands(&self) -> Vec<(LaunchHook, &str)> {
+        [
+            (LaunchHook::PreLaunch, self.pre_launch_command.as_deref()),
+            (LaunchHook::PostExit, self.post_launch_command.as_deref()),
+        ]
+        .into_iter()
+        .filter_map(|(hook, command)| command.map(str::trim).filter(|c| !c.is_empty()).map(|c| (hook, c)))
+        .collect()
+    }
+
+    pub fn unapproved_commands(&self) -> Vec<(LaunchHook, String)> {
+        self.launch_commands()
+            .into_iter()
+            .filter(|(_, command)| !self.approved_commands.iter().any(|approved| approved == command))
+            .map(|(hook, command)| (hook, command.to_string()))
+            .collect()
+    }
+
+    pub fn launch_variant(&self, name: &str) -> Option<&LaunchVariant> {
+        self.launch_variants.iter().find(|variant| variant.name == name)
+    }
+
+    pub fn apply_variant(&mut self, variant: &LaunchVariant) {
+        if variant.width.is_some() {
+            self.width = variant.width;
+        }
+        if variant.height.is_some() {
+            self.height = variant.height;
+        }
+        if variant.fullscreen.is_some() {
+            self.fullscreen = variant.fullscreen;
+        }
+        if variant.memory_min.is_some() {
+            self.memory_min = variant.memory_min;
+        }
+        if variant.memory_max.is_some() {
+            self.memory_max = variant.memory_max;
+        }
+        if variant.java_args.is_some() {
+            self.java_args = variant.java_args.clone();
+        }
+    }
+
+    /// The memory, resolution and Java arguments this instance launches
+    /// with: its own values where set, then the active profile's, then the
+    /// global settings.
+    pub fn effective_settings(&self, profile: Option<&Profile>, settings: &Settings) -> EffectiveSettings {
+        let java = &settings.java;
+        // An explicit GC preset replaces the collector flags from settings.
+        let global_args = match self.gc_preset {
+            Some(_) => java.additional_args.clone(),
+            None => format!("{} {}", java.gc_args, java.additional_args),
+        };
+        EffectiveSettings {
+            memory_min: Layered::resolve(self.memory_min, profile.and_then(|p| p.memory_min), java.memory_min),
+            memory_max: Layered::resolve(self.memory_max, profile.and_then(|p| p.memory_max), java.memory_max),
+            resolution: Layered::resolve(
+                self.width.zip(self.height),
+                profile.and_then(|p| p.resolution_width.zip(p.resolution_height)),
+                (settings.minecraft.default_width, settings.minecraft.default_height),
+            ),
+            // A profile can only switch fullscreen on.
+            fullscreen: Layered::resolve(
+                self.fullscreen,
+                profile.and_then(|p| p.fullscreen.then_some(true)),
+                settings.minecraft.fullscreen,
+            ),
+            java_args: Layered::resolve(
+                self.java_args.clone().filter(|args| !args.trim().is_empty()),
+                profile.and_then(|p| p.java_args.clone()).filter(|args| !args.trim().is_empty()),
+                global_args.trim().to_string(),
+            ),
+        }
+    }
+
+    // Fills in what the instance leaves unset, so the launch command is
+    // built from the resolved values. Demo mode can only be switched on by
+    // the profile, not off.
+    pub fn apply_inherited(&mut self, profile: Option<&Profile>, settings: &Settings) {
+        let effective = self.effective_settings(profile, settings);
+        self.memory_min = Some(effective.memory_min.value);
+        self.memory_max = Some(effective.memory_max.value);
+        let (width, height) = effective.resolution.value;
+        self.width = Some(width);
+        self.height = Some(height);
+        self.fullscreen = Some(effective.fullscreen.value);
+        self.java_args = Some(effective.java_args.value).filter(|args| !args.is_empty());
+        if let Some(profile) = profile {
+            self.demo_mode |= profile.demo_mode;
+        }
+    }
+
+    // Captures the current launch settings so they can be restored by name later.
+    pub fn snapshot_variant(&self, name: String) -> LaunchVariant {
+        LaunchVariant {
+            name,
+            width: self.width,
+            height: self.height,
+            fullscreen: self.fullscreen,
+            memory_min: self.memory_min,
+            memory_max: self.memory_max,
+            java_args: self.java_args.clone(),
+            wrapper_command: None,
+            env: BTreeMap::new(),
+        }
+    }
+
+    // `icon` may also hold an image path (used for desktop entries); only
+    // short text is drawn in the terminal.
+    pub fn icon_glyph(&self) -> Option<&str> {
+        self.icon.as_deref().filter(|icon| is_glyph_icon(icon))
+    }
+
+    pub fn approved_command(&self, hook: LaunchHook) -> Option<String> {
+        self.launch_commands()
+            .into_iter()
+            .find(|(h, command)| *h == hook && self.approved_commands.iter().any(|approved| approved == command))
+            .map(|(_, command)| command.to_string())
+    }
+}
+
+pub fn is_glyph_icon(icon: &str) -> bool {
+    let chars = icon.chars().count();
+    chars > 0 && chars <= ICON_MAX_CHARS
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ModLoader {
     Forge,
     Fabric,
@@ -42,6 +320,136 @@ pub enum ModLoader {
     NeoForge,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InstanceSort {
+    Name,
+    LastPlayed,
+    Version,
+    PlayTime,
+}
+
+impl InstanceSort {
+    pub const ALL: [InstanceSort; 4] = [
+        InstanceSort::Name,
+        InstanceSort::LastPlayed,
+        InstanceSort::Version,
+        InstanceSort::PlayTime,
+    ];
+
+    pub fn parse(value: &str) -> Self {
+        Self::ALL.into_iter()
+            .find(|sort| sort.key() == value)
+            .unwrap_or(InstanceSort::Name)
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            InstanceSort::Name => "name",
+            InstanceSort::LastPlayed => "last_played",
+            InstanceSort::Version => "version",
+            InstanceSort::PlayTime => "play_time",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        tr(match self {
+            InstanceSort::Name => "instances.sort_name",
+            InstanceSort::LastPlayed => "instances.sort_last_played",
+            InstanceSort::Version => "instances.sort_version",
+            InstanceSort::PlayTime => "instances.sort_play_time",
+        })
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|sort| sort == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn sort(&self, instances: &mut [&Instance]) {
+        instances.sort_by(|a, b| {
+            let primary = match self {
+                InstanceSort::Name => Ordering::Equal,
+                InstanceSort::LastPlayed => b.last_played.cmp(&a.last_played),
+                InstanceSort::Version => compare_versions(&b.minecraft_version, &a.minecraft_version),
+                InstanceSort::PlayTime => b.play_time.cmp(&a.play_time),
+            };
+            primary.then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+    }
+}
+
+// Compares dotted version strings numerically so that 1.20 sorts after 1.9.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts = |version: &str| -> Vec<u64> {
+        version.split(|c: char| !c.is_ascii_digit())
+            .filter(|part| !part.is_empty())
+            .filter_map(|part| part.parse().ok())
+            .collect()
+    };
+    parts(a).cmp(&parts(b)).then_with(|| a.cmp(b))
+}
+
+impl Instance {
+    pub fn matches_query(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.name.to_lowercase().contains(&query)
+            || self.minecraft_version.to_lowercase().contains(&query)
+            || self.group.as_deref().is_some_and(|group| group.to_lowercase().contains(&query))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CloneOptions {
+    pub mods: bool,
+    pub configs: bool,
+    pub saves: bool,
+    pub resource_packs: bool,
+}
+
+impl Default for CloneOptions {
+    fn default() -> Self {
+        Self {
+            mods: true,
+            configs: true,
+            saves: false,
+            resource_packs: true,
+        }
+    }
+}
+
+impl CloneOptions {
+    fn paths(&self) -> Vec<&'static str> {
+        // The client jar goes with the configuration that launches it.
+        let mut paths = vec!["client"];
+        if self.mods {
+            paths.extend(["mods", ".minecraft/mods"]);
+        }
+        if self.configs {
+            paths.extend([".minecraft/config", ".minecraft/options.txt", ".minecraft/optionsof.txt", ".minecraft/servers.dat"]);
+        }
+        if self.saves {
+            paths.extend(["saves", ".minecraft/saves"]);
+        }
+        if self.resource_packs {
+            paths.extend([".minecraft/resourcepacks", ".minecraft/shaderpacks"]);
+        }
+        paths
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedInstance {
+    pub id: Uuid,
+    pub pending_downloads: Vec<PendingDownload>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ExportFormat {
+    Zip,
+    Mrpack { remote_files: HashMap<String, MrpackFile> },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstanceGroup {
     pub name: String,
@@ -73,12 +481,10 @@ impl InstanceManager {
         let id = Uuid::new_v4();
         let instance_path = self.instances_dir.join(id.to_string());
         
-        std::fs::create_dir_all(&instance_path)?;
-        std::fs::create_dir_all(instance_path.join(".minecraft"))?;
-        std::fs::create_dir_all(instance_path.join("mods"))?;
-        std::fs::create_dir_all(instance_path.join("resourcepacks"))?;
-        std::fs::create_dir_all(instance_path.join("shaderpacks"))?;
-        std::fs::create_dir_all(instance_path.join("saves"))?;
+        let game_dir = instance_path.join(".minecraft");
+        for dir in ["mods", "resourcepacks", "shaderpacks", "saves"] {
+            std::fs::create_dir_all(game_dir.join(dir))?;
+        }
         
         let instance = Instance {
             id,
@@ -99,11 +505,24 @@ impl InstanceManager {
             memory_max: None,
             width: None,
             height: None,
-            fullscreen: false,
+            fullscreen: None,
             auto_connect: None,
             pre_launch_command: None,
             post_launch_command: None,
             disabled: false,
+            links: InstanceLinks::default(),
+            approved_commands: Vec::new(),
+            dev_watch: false,
+            github_mods: Vec::new(),
+            github_client: None,
+            launch_variants: Vec::new(),
+            version_channels: None,
+            demo_mode: false,
+            last_account: None,
+            env: BTreeMap::new(),
+            gc_preset: None,
+            modpack: None,
+            quick_play: None,
         };
         
         self.save_instance(&instance)?;
@@ -112,6 +531,14 @@ impl InstanceManager {
         Ok(id)
     }
 
+    pub fn instances_dir(&self) -> &Path {
+        &self.instances_dir
+    }
+
+    pub fn game_dir(&self, id: Uuid) -> PathBuf {
+        self.instances_dir.join(id.to_string()).join(".minecraft")
+    }
+
     pub fn delete_instance(&mut self, id: Uuid) -> Result<()> {
         if let Some(instance) = self.instances.remove(&id) {
             std::fs::remove_dir_all(&instance.path)?;
@@ -119,6 +546,43 @@ impl InstanceManager {
         Ok(())
     }
 
+    pub fn clone_instance(&mut self, source_id: Uuid, name: String, options: &CloneOptions) -> Result<Uuid> {
+        let source = self.get_instance(source_id)
+            .cloned()
+            .ok_or_else(|| Error::Instance(format!("Instance {} not found", source_id)))?;
+        let id = self.create_instance(name.clone(), source.minecraft_version.clone())?;
+        let path = self.instances_dir.join(id.to_string());
+
+        for relative in options.paths() {
+            let from = source.path.join(relative);
+            if !from.exists() {
+                continue;
+            }
+            if let Err(e) = copy_tree(&from, &path.join(relative)) {
+                self.delete_instance(id)?;
+                return Err(e);
+            }
+        }
+
+        let group = source.group.clone();
+        let instance = Instance {
+            id,
+            name,
+            group: None,
+            path,
+            created_at: Utc::now(),
+            last_played: None,
+            play_time: 0,
+            dev_watch: false,
+            ..source
+        };
+        self.update_instance(instance)?;
+        if let Some(group) = group {
+            self.set_instance_group(id, Some(&group))?;
+        }
+        Ok(id)
+    }
+
     pub fn get_instance(&self, id: Uuid) -> Option<&Instance> {
         self.instances.get(&id)
     }
@@ -137,6 +601,37 @@ impl InstanceManager {
         Ok(())
     }
 
+    // Changes the stored instance.json rather than the copy in memory, so
+    // a game launched from a shortcut and the TUI running next to it both
+    // see each other's play time.
+    fn update_stored(&mut self, instance_id: Uuid, change: impl FnOnce(&mut Instance)) -> Result<()> {
+        let _lock = crate::utils::lock_data_dir()?;
+        let mut instance = match self.load_stored_instance(instance_id) {
+            Ok(instance) => instance,
+            Err(_) => self.get_instance(instance_id)
+                .cloned()
+                .ok_or_else(|| Error::Instance(format!("Instance {} not found", instance_id)))?,
+        };
+        change(&mut instance);
+        self.update_instance(instance)
+    }
+
+    pub fn record_play_session(&mut self, instance_id: Uuid, started_at: DateTime<Utc>, ended_at: DateTime<Utc>) -> Result<u64> {
+        let session = (ended_at - started_at).num_seconds().max(0) as u64;
+        self.update_stored(instance_id, |instance| {
+            instance.play_time = instance.play_time.saturating_add(session);
+            instance.last_played = Some(ended_at);
+        })?;
+        Ok(session)
+    }
+
+    pub fn record_account(&mut self, instance_id: Uuid, account_id: Uuid) -> Result<()> {
+        if self.get_instance(instance_id).is_some_and(|instance| instance.last_account == Some(account_id)) {
+            return Ok(());
+        }
+        self.update_stored(instance_id, |instance| instance.last_account = Some(account_id))
+    }
+
     pub fn create_group(&mut self, name: String) -> Result<()> {
         if self.groups.contains_key(&name) {
             return Err(Error::Instance(format!("Group '{}' already exists", name)));
@@ -196,6 +691,30 @@ impl InstanceManager {
         Ok(())
     }
 
+    pub fn set_instance_group(&mut self, instance_id: Uuid, group_name: Option<&str>) -> Result<()> {
+        match group_name {
+            Some(name) => {
+                if !self.groups.contains_key(name) {
+                    self.create_group(name.to_string())?;
+                }
+                self.add_instance_to_group(instance_id, name)
+            }
+            None => {
+                let instance = self.instances.get_mut(&instance_id)
+                    .ok_or_else(|| Error::Instance("Instance not found".to_string()))?;
+                if let Some(old_group) = instance.group.take() {
+                    if let Some(group) = self.groups.get_mut(&old_group) {
+                        group.instances.retain(|&id| id != instance_id);
+                    }
+                }
+                if let Some(instance) = self.instances.get(&instance_id) {
+                    self.save_instance(instance)?;
+                }
+                self.save_groups()
+            }
+        }
+    }
+
     pub fn get_grouped_instances(&self) -> HashMap<Option<String>, Vec<&Instance>> {
         let mut grouped = HashMap::new();
         
@@ -224,7 +743,7 @@ impl InstanceManager {
                             self.instances.insert(instance.id, instance);
                         }
                         Err(e) => {
-                            log::warn!("Failed to load instance from {:?}: {}", config_path, e);
+                            tracing::warn!("Failed to load instance from {:?}: {}", config_path, e);
                         }
                     }
                 }
@@ -235,16 +754,32 @@ impl InstanceManager {
         Ok(())
     }
 
+    pub fn load_stored_instance(&self, id: Uuid) -> Result<Instance> {
+        let instance = self.get_instance(id)
+            .ok_or_else(|| Error::Instance("Instance not found".to_string()))?;
+        self.load_instance(&instance.path.join("instance.json"))
+    }
+
     fn load_instance(&self, config_path: &Path) -> Result<Instance> {
         let content = std::fs::read_to_string(config_path)?;
-        let instance: Instance = serde_json::from_str(&content)?;
+        let (mut instance, migrated): (Instance, bool) =
+            schema::INSTANCE.load(config_path, serde_json::from_str(&content)?)?;
+        // The stored path goes stale when the data folder moves; the folder
+        // the config was found in is always right.
+        if let Some(dir) = config_path.parent() {
+            instance.path = dir.to_path_buf();
+        }
+        if migrated {
+            self.save_instance(&instance)?;
+        }
         Ok(instance)
     }
 
     fn save_instance(&self, instance: &Instance) -> Result<()> {
         let config_path = instance.path.join("instance.json");
-        let content = serde_json::to_string_pretty(instance)?;
-        std::fs::write(config_path, content)?;
+        let mut value = serde_json::to_value(instance)?;
+        schema::INSTANCE.stamp(&mut value);
+        crate::utils::save_data_file(&config_path, serde_json::to_string_pretty(&value)?)?;
         Ok(())
     }
 
@@ -260,27 +795,175 @@ impl InstanceManager {
     fn save_groups(&self) -> Result<()> {
         let groups_path = self.instances_dir.join("groups.json");
         let content = serde_json::to_string_pretty(&self.groups)?;
-        std::fs::write(groups_path, content)?;
+        crate::utils::save_data_file(&groups_path, content)?;
         Ok(())
     }
 
     pub fn get_instance_mods_dir(&self, instance_id: Uuid) -> Option<PathBuf> {
-        self.get_instance(instance_id).map(|i| i.path.join("mods"))
+        self.get_instance(instance_id).map(|_| self.game_dir(instance_id).join("mods"))
     }
 
     pub fn get_instance_resourcepacks_dir(&self, instance_id: Uuid) -> Option<PathBuf> {
-        self.get_instance(instance_id).map(|i| i.path.join("resourcepacks"))
+        self.get_instance(instance_id).map(|_| self.game_dir(instance_id).join("resourcepacks"))
     }
 
     pub fn get_instance_saves_dir(&self, instance_id: Uuid) -> Option<PathBuf> {
-        self.get_instance(instance_id).map(|i| i.path.join("saves"))
+        self.get_instance(instance_id).map(|_| self.game_dir(instance_id).join("saves"))
     }
 
-    pub fn import_instance(&mut self, _import_path: &Path) -> Result<Uuid> {
-        Err(Error::Instance("Import not implemented yet".to_string()))
+    pub fn import_instance(&mut self, import_path: &Path) -> Result<ImportedInstance> {
+        self.import_plan(crate::import::read_plan(import_path)?)
     }
 
-    pub fn export_instance(&self, _instance_id: Uuid, _export_path: &Path) -> Result<()> {
-        Err(Error::Instance("Export not implemented yet".to_string()))
+    pub fn import_plan(&mut self, plan: crate::import::ImportPlan) -> Result<ImportedInstance> {
+        let id = self.create_instance(plan.name.clone(), plan.minecraft_version.clone())?;
+
+        let mut instance = self.get_instance(id).cloned()
+            .ok_or_else(|| Error::Instance("Instance not found".to_string()))?;
+
+        for source in &plan.game_files {
+            if let Err(e) = crate::import::copy_game_files(source, &instance.path.join(".minecraft")) {
+                self.delete_instance(id)?;
+                return Err(e);
+            }
+        }
+
+        let modpack = ModpackSource::from_plan(&plan).unwrap_or_else(|e| {
+            tracing::warn!("Failed to record modpack files for {}: {}", plan.name, e);
+            None
+        });
+        instance.mod_loader = plan.mod_loader;
+        instance.mod_loader_version = plan.mod_loader_version;
+        instance.java_args = plan.java_args;
+        instance.memory_min = plan.memory_min;
+        instance.memory_max = plan.memory_max;
+        instance.width = plan.width;
+        instance.height = plan.height;
+        instance.fullscreen = plan.fullscreen;
+        instance.notes = plan.notes;
+        instance.icon = plan.icon;
+        instance.pre_launch_command = plan.pre_launch_command;
+        instance.post_launch_command = plan.post_exit_command;
+        instance.links = plan.links;
+        instance.approved_commands.clear();
+        instance.modpack = modpack;
+        self.update_instance(instance)?;
+
+        Ok(ImportedInstance {
+            id,
+            pending_downloads: plan.pending_downloads,
+        })
     }
-} 
\ No newline at end of file
+
+    pub fn export_instance(&self, instance_id: Uuid, export_path: &Path, format: &ExportFormat) -> Result<()> {
+        let instance = self.get_instance(instance_id)
+            .ok_or_else(|| Error::Instance("Instance not found".to_string()))?;
+        let game_dir = instance.path.join(".minecraft");
+
+        if let Some(parent) = export_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::File::create(export_path)?;
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::FileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+
+        let prefix = match format {
+            ExportFormat::Zip => {
+                zip.start_file("instance.json", options)?;
+                zip.write_all(serde_json::to_string_pretty(instance)?.as_bytes())?;
+                ".minecraft"
+            }
+            ExportFormat::Mrpack { remote_files } => {
+                let index = Self::build_mrpack_index(instance, remote_files);
+                zip.start_file(MRPACK_INDEX, options)?;
+                zip.write_all(serde_json::to_string_pretty(&index)?.as_bytes())?;
+                MRPACK_OVERRIDES
+            }
+        };
+
+        if game_dir.exists() {
+            for entry in walkdir::WalkDir::new(&game_dir).min_depth(1) {
+                let entry = entry?;
+                let relative = match entry.path().strip_prefix(&game_dir) {
+                    Ok(relative) => relative,
+                    Err(_) => continue,
+                };
+                if Self::is_excluded_from_export(relative, format) {
+                    continue;
+                }
+
+                let relative_name = relative.to_string_lossy().replace('\\', "/");
+                if let ExportFormat::Mrpack { remote_files } = format {
+                    if remote_files.contains_key(&relative_name) {
+                        continue;
+                    }
+                }
+
+                let archive_name = format!("{}/{}", prefix, relative_name);
+                if entry.file_type().is_dir() {
+                    zip.add_directory(archive_name, options)?;
+                } else if entry.file_type().is_file() {
+                    zip.start_file(archive_name, options)?;
+                    let mut source = std::fs::File::open(entry.path())?;
+                    std::io::copy(&mut source, &mut zip)?;
+                }
+            }
+        }
+
+        zip.finish()?;
+        Ok(())
+    }
+
+    fn is_excluded_from_export(relative: &Path, format: &ExportFormat) -> bool {
+        let mut components = relative.components().map(|c| c.as_os_str().to_string_lossy());
+        let Some(top) = components.next() else { return true };
+
+        if EXPORT_EXCLUDED_DIRS.contains(&top.as_ref()) {
+            return true;
+        }
+        if matches!(format, ExportFormat::Mrpack { .. }) && top == "saves" {
+            return true;
+        }
+        relative.components().any(|c| c.as_os_str().to_string_lossy().starts_with(".safe-mode"))
+    }
+
+    fn build_mrpack_index(instance: &Instance, remote_files: &HashMap<String, MrpackFile>) -> MrpackIndex {
+        let mut dependencies = HashMap::new();
+        dependencies.insert("minecraft".to_string(), instance.minecraft_version.clone());
+        if let (Some(loader), Some(version)) = (&instance.mod_loader, &instance.mod_loader_version) {
+            dependencies.insert(crate::mrpack::loader_dependency_key(loader).to_string(), version.clone());
+        }
+
+        let mut files: Vec<MrpackFile> = remote_files.values().cloned().collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        MrpackIndex {
+            format_version: 1,
+            game: "minecraft".to_string(),
+            version_id: instance.last_played.unwrap_or(instance.created_at).format("%Y.%m.%d").to_string(),
+            name: instance.name.clone(),
+            summary: instance.notes.clone(),
+            files,
+            dependencies,
+        }
+    }
+}
+
+fn copy_tree(source: &Path, target: &Path) -> Result<()> {
+    for entry in walkdir::WalkDir::new(source) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let destination = target.join(relative);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&destination)?;
+        } else {
+            if let Some(parent) = destination.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(entry.path(), &destination)?;
+        }
+    }
+    Ok(())
+}
@@ -1,9 +1,17 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fs::File;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use tokio::process::Command as AsyncCommand;
+use crate::i18n::trf;
+use crate::github::Checksum;
+use crate::network::NetworkManager;
+use crate::settings::JavaSettings;
+use crate::tasks::{TaskReporter, TaskStage};
 use crate::{Result, Error};
 
+const ADOPTIUM_API: &str = "https://api.adoptium.net/v3/assets/latest";
+
 
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +37,9 @@ pub struct JavaManager {
     installations: HashMap<String, JavaInstallation>,
     java_directory: Option<PathBuf>,
     default_installation: Option<String>,
+    custom_paths: Vec<PathBuf>,
+    hidden_paths: Vec<PathBuf>,
+    preferred_default: Option<PathBuf>,
 }
 
 impl JavaManager {
@@ -37,9 +48,20 @@ impl JavaManager {
             installations: HashMap::new(),
             java_directory,
             default_installation: None,
+            custom_paths: Vec::new(),
+            hidden_paths: Vec::new(),
+            preferred_default: None,
         })
     }
 
+    /// Takes the installations added and removed by hand, and the default
+    /// picked in settings, into account on the next scan.
+    pub fn set_preferences(&mut self, settings: &JavaSettings) {
+        self.custom_paths = settings.custom_installations.clone();
+        self.hidden_paths = settings.hidden_installations.clone();
+        self.preferred_default = settings.default_installation.clone();
+    }
+
     pub async fn scan_java_installations(&mut self) -> Result<()> {
         self.installations.clear();
         
@@ -50,6 +72,16 @@ impl JavaManager {
                 self.scan_directory_recursive(&path).await?;
             }
         }
+
+        for path in self.custom_paths.clone() {
+            match self.inspect(&path).await {
+                Ok(installation) => {
+                    let key = format!("{} {}", installation.vendor, installation.version);
+                    self.installations.insert(key, installation);
+                }
+                Err(e) => tracing::warn!("{}", trf("log.java_unavailable", &[("path", &path.display()), ("error", &e)])),
+            }
+        }
         
         if self.installations.is_empty() {
             return Err(Error::Java("No Java installations found".to_string()));
@@ -59,8 +91,8 @@ impl JavaManager {
         Ok(())
     }
 
-    async fn scan_directory_recursive(&mut self, dir_path: &PathBuf) -> Result<()> {
-        let mut dirs_to_scan = vec![dir_path.clone()];
+    async fn scan_directory_recursive(&mut self, dir_path: &Path) -> Result<()> {
+        let mut dirs_to_scan = vec![dir_path.to_path_buf()];
         
         while let Some(current_dir) = dirs_to_scan.pop() {
             if current_dir.components().count() > 8 {
@@ -72,6 +104,9 @@ impl JavaManager {
                     let path = entry.path();
                     
                     if self.is_java_executable(&path) {
+                        if self.hidden_paths.contains(&path) {
+                            continue;
+                        }
                         if let Ok(installation) = self.create_java_installation(path).await {
                             let key = format!("{} {}", installation.vendor, installation.version);
                             self.installations.insert(key, installation);
@@ -120,6 +155,15 @@ impl JavaManager {
     }
 
     fn select_default_installation(&mut self) {
+        let preferred = self.preferred_default.as_ref().and_then(|path| {
+            self.installations.iter().find(|(_, installation)| &installation.path == path)
+        });
+        if let Some((key, _)) = preferred {
+            let key = key.clone();
+            let _ = self.set_default_installation(&key);
+            return;
+        }
+
         let mut best_installation: Option<(String, u32)> = None;
         
         for (key, installation) in &self.installations {
@@ -175,7 +219,7 @@ impl JavaManager {
         score
     }
 
-    fn is_java_executable(&self, path: &PathBuf) -> bool {
+    fn is_java_executable(&self, path: &Path) -> bool {
         if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
             #[cfg(windows)]
             return file_name == "java.exe" || file_name == "javaw.exe";
@@ -260,7 +304,7 @@ impl JavaManager {
                         return Ok(quoted.to_string());
                     }
                     
-                    if let Some(unquoted) = version_part.trim().split_whitespace().next() {
+                    if let Some(unquoted) = version_part.split_whitespace().next() {
                         return Ok(unquoted.to_string());
                     }
                 }
@@ -288,7 +332,7 @@ impl JavaManager {
         return "x86".to_string();
         
         #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64", target_arch = "x86")))]
-        "unknown".to_string()
+        std::env::consts::ARCH.to_string()
     }
 
     fn detect_max_memory(&self, properties_output: &str) -> Option<u64> {
@@ -304,7 +348,7 @@ impl JavaManager {
         None
     }
 
-    async fn check_javafx_support(&self, java_path: &PathBuf) -> bool {
+    async fn check_javafx_support(&self, java_path: &Path) -> bool {
         let result = AsyncCommand::new(java_path)
             .args(["-cp", ".", "-version"])
             .output()
@@ -410,6 +454,7 @@ impl JavaManager {
                 PathBuf::from("/usr/lib/jvm/openjdk-17"),
                 PathBuf::from("/usr/lib/jvm/openjdk-21"),
             ]);
+            paths.extend(crate::platform::get_arch_java_paths());
             
             if let Ok(home) = std::env::var("HOME") {
                 let home_path = PathBuf::from(home);
@@ -429,29 +474,46 @@ impl JavaManager {
         paths.into_iter().filter(|p| p.exists()).collect()
     }
 
-    pub async fn download_java(&mut self, version: u8) -> Result<JavaInstallation> {
-        let os = if cfg!(target_os = "windows") {
-            "windows"
-        } else if cfg!(target_os = "macos") {
-            "mac"
+    /// Runs the Java at `path`, the executable or the folder it is installed
+    /// in, to check that it works and read what it is.
+    pub async fn inspect(&self, path: &Path) -> Result<JavaInstallation> {
+        let executable = if path.is_dir() {
+            ["bin", "Contents/Home/bin"].iter()
+                .map(|bin| path.join(bin).join(JAVA_EXECUTABLE))
+                .find(|candidate| candidate.is_file())
+                .ok_or_else(|| Error::Java(format!("No {} in {}", JAVA_EXECUTABLE, path.display())))?
+        } else if path.is_file() {
+            path.to_path_buf()
         } else {
-            "linux"
+            return Err(Error::Java(format!("{} does not exist", path.display())));
         };
-        
-        let arch = if cfg!(target_arch = "x86_64") {
-            "x64"
-        } else if cfg!(target_arch = "aarch64") {
-            "aarch64"
-        } else {
-            "x86"
-        };
-        
-        let _url = format!(
-            "https://api.adoptium.net/v3/assets/latest/{}/hotspot?architecture={}&image_type=jdk&os={}",
-            version, arch, os
-        );
-        
-        Err(Error::Java("Java download not implemented yet".to_string()))
+        self.create_java_installation(executable).await
+    }
+
+    /// Adds an installation found outside a scan and returns its key. It
+    /// becomes the default when there was none.
+    pub fn insert_installation(&mut self, installation: JavaInstallation) -> String {
+        let key = format!("{} {}", installation.vendor, installation.version);
+        self.installations.insert(key.clone(), installation);
+        if self.get_default_installation().is_none() {
+            let _ = self.set_default_installation(&key);
+        }
+        key
+    }
+
+    pub fn remove_installation(&mut self, key: &str) -> Option<JavaInstallation> {
+        let removed = self.installations.remove(key)?;
+        if self.default_installation.as_deref() == Some(key) {
+            self.default_installation = None;
+            self.select_default_installation();
+        }
+        Some(removed)
+    }
+
+    /// Makes the installation at `path` the default, now and after scans.
+    pub fn prefer_default(&mut self, path: Option<PathBuf>) {
+        self.preferred_default = path;
+        self.select_default_installation();
     }
 
     pub fn get_installations(&self) -> &HashMap<String, JavaInstallation> {
@@ -486,6 +548,15 @@ impl JavaManager {
         Ok(())
     }
 
+    /// Installations sorted newest Java first, for listing.
+    pub fn sorted_installations(&self) -> Vec<(&String, &JavaInstallation)> {
+        let mut installations: Vec<_> = self.installations.iter().collect();
+        installations.sort_by(|(_, a), (_, b)| {
+            b.major_version().cmp(&a.major_version()).then_with(|| a.path.cmp(&b.path))
+        });
+        installations
+    }
+
     pub fn update_java_directory(&mut self, new_directory: Option<PathBuf>) {
         self.java_directory = new_directory;
     }
@@ -502,21 +573,33 @@ impl JavaManager {
         false
     }
 
+    /// Java major a release needs, going by its version id: 21 from 1.20.5,
+    /// 17 from 1.18, 16 for 1.17 and 8 before. Snapshots and other ids that
+    /// are not `1.x` releases are left to their version JSON.
     pub fn get_recommended_java_for_minecraft(&self, minecraft_version: &str) -> Option<u8> {
-        let version_parts: Vec<&str> = minecraft_version.split('.').collect();
-        
-        if version_parts.len() >= 2 {
-            let major: u8 = version_parts[1].parse().unwrap_or(0);
-            let minor: u8 = if version_parts.len() > 2 {
-                version_parts[2].parse().unwrap_or(0)
-            } else { 0 };
-            
-            if major > 1 || (major == 1 && minor >= 17) {
-                return Some(17);
-            }
+        let mut parts = minecraft_version.strip_prefix("1.")?.split('.');
+        let major: u32 = parts.next()?.parse().ok()?;
+        let minor: u32 = match parts.next() {
+            Some(minor) => minor.parse().ok()?,
+            None => 0,
+        };
+
+        Some(match (major, minor) {
+            (21.., _) | (20, 5..) => 21,
+            (18.., _) => 17,
+            (17, _) => 16,
+            _ => 8,
+        })
+    }
+}
+
+impl JavaInstallation {
+    pub fn major_version(&self) -> Option<u32> {
+        let mut parts = self.version.split(['.', '_', '-', '+']);
+        match parts.next()?.parse::<u32>().ok()? {
+            1 => parts.next()?.parse().ok(),
+            major => Some(major),
         }
-        
-        Some(8)
     }
 }
 
@@ -529,4 +612,116 @@ impl Default for JavaCapabilities {
             supports_awt: true,
         }
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(windows)]
+const JAVA_EXECUTABLE: &str = "java.exe";
+#[cfg(not(windows))]
+const JAVA_EXECUTABLE: &str = "java";
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumRelease {
+    binary: AdoptiumBinary,
+    release_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumBinary {
+    package: AdoptiumPackage,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumPackage {
+    checksum: String,
+    link: String,
+    name: String,
+}
+
+/// Downloads the latest Eclipse Temurin JRE of major `version` from Adoptium
+/// and unpacks it into `directory`. Returns the folder it was unpacked to.
+pub async fn download_java(network: &NetworkManager, directory: &Path, version: u8, progress: &TaskReporter) -> Result<PathBuf> {
+    let os = if cfg!(target_os = "windows") {
+        "windows"
+    } else if cfg!(target_os = "macos") {
+        "mac"
+    } else {
+        "linux"
+    };
+    
+    let arch = match crate::platform::Arch::current() {
+        crate::platform::Arch::X86_64 => "x64",
+        crate::platform::Arch::Aarch64 => "aarch64",
+        crate::platform::Arch::Arm => "arm",
+        crate::platform::Arch::Riscv64 => "riscv64",
+        crate::platform::Arch::X86 | crate::platform::Arch::Other => "x86",
+    };
+    
+    let url = format!(
+        "{}/{}/hotspot?architecture={}&image_type=jre&os={}",
+        ADOPTIUM_API, version, arch, os
+    );
+    progress.plan(&[(TaskStage::Metadata, 1), (TaskStage::Runtime, 8), (TaskStage::Unpack, 2)]);
+    progress.report(TaskStage::Metadata, 0, 1);
+    let releases: Vec<AdoptiumRelease> = network.get_json(&url).await?;
+    let release = releases.into_iter().next()
+        .ok_or_else(|| Error::Java(format!("Adoptium has no Java {} for {} {}", version, os, arch)))?;
+    let package = release.binary.package;
+
+    let staging = std::env::temp_dir().join(format!("mango-java-{}", version));
+    std::fs::create_dir_all(&staging)?;
+    let archive = staging.join(&package.name);
+    let reporter = progress.clone();
+    let callback: crate::network::ProgressCallback = Box::new(move |done, total| {
+        reporter.report(TaskStage::Runtime, done, total);
+    });
+    let downloaded = network.download_file(&package.link, &archive, None, Some(callback)).await
+        .and_then(|()| match Checksum::from_hex(&package.checksum) {
+            Some(checksum) => checksum.verify(&archive),
+            None => Ok(()),
+        });
+    if let Err(e) = downloaded {
+        let _ = std::fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    progress.report(TaskStage::Unpack, 0, 1);
+    std::fs::create_dir_all(directory)?;
+    let unpacked = unpack_archive(&archive, directory);
+    let _ = std::fs::remove_dir_all(&staging);
+    let top = unpacked?.unwrap_or_else(|| PathBuf::from(&release.release_name));
+    progress.report(TaskStage::Unpack, 1, 1);
+    tracing::info!("{}", trf("log.java_installed", &[("version", &version), ("release", &release.release_name), ("path", &directory.join(&top).display())]));
+    Ok(directory.join(top))
+}
+
+// Adoptium archives hold a single folder; its name is returned.
+fn unpack_archive(archive: &Path, target: &Path) -> Result<Option<PathBuf>> {
+    let mut top = None;
+    if archive.extension().is_some_and(|ext| ext == "zip") {
+        let mut zip = zip::ZipArchive::new(File::open(archive)?)?;
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i)?;
+            let Some(relative) = entry.enclosed_name().map(Path::to_path_buf) else {
+                return Err(Error::Java(format!("Unsafe path in {}: {}", archive.display(), entry.name())));
+            };
+            top = top.or_else(|| relative.components().next().map(|part| PathBuf::from(part.as_os_str())));
+            let destination = target.join(&relative);
+            if entry.is_dir() {
+                std::fs::create_dir_all(&destination)?;
+            } else {
+                if let Some(parent) = destination.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::io::copy(&mut entry, &mut File::create(&destination)?)?;
+            }
+        }
+    } else {
+        let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(File::open(archive)?));
+        for entry in tar.entries()? {
+            let mut entry = entry?;
+            top = top.or_else(|| entry.path().ok()?.components().next().map(|part| PathBuf::from(part.as_os_str())));
+            entry.unpack_in(target)?;
+        }
+    }
+    Ok(top)
+}
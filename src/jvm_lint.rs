@@ -0,0 +1,389 @@
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, trf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintSeverity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LintIssue {
+    UnbalancedQuotes,
+    QuotedArgument(String),
+    NotAFlag(String),
+    UnknownFlag(String),
+    InvalidSyntax(String),
+    ConflictsWithLauncher(String),
+    InvalidMemorySize(String),
+    DuplicateMemory(String),
+    OverridesMemorySetting(String),
+    HeapMinAboveMax,
+    MultipleCollectors(Vec<String>),
+    DeprecatedFlag { flag: String, since: u32 },
+    RemovedFlag { flag: String, since: u32 },
+    RequiresNewerJava { flag: String, since: u32 },
+    RequiresUnlock { flag: String, unlock: &'static str },
+}
+
+const GARBAGE_COLLECTORS: &[&str] = &[
+    "UseSerialGC",
+    "UseParallelGC",
+    "UseParallelOldGC",
+    "UseConcMarkSweepGC",
+    "UseG1GC",
+    "UseZGC",
+    "UseShenandoahGC",
+    "UseEpsilonGC",
+];
+
+// (option, deprecated in, removed in)
+const RETIRED_OPTIONS: &[(&str, u32, u32)] = &[
+    ("UseConcMarkSweepGC", 9, 14),
+    ("CMS", 9, 14),
+    ("UseParNewGC", 9, 10),
+    ("PermSize", 8, 17),
+    ("MaxPermSize", 8, 17),
+    ("AggressiveOpts", 11, 13),
+    ("UseParallelOldGC", 14, 15),
+    ("UseBiasedLocking", 15, 18),
+];
+
+// (option, first release, last release still needing the unlock flag)
+const NEWER_OPTIONS: &[(&str, u32, Option<u32>)] = &[
+    ("UseZGC", 11, Some(14)),
+    ("UseShenandoahGC", 12, Some(14)),
+    ("UseEpsilonGC", 11, Some(u32::MAX)),
+    ("ZGenerational", 21, None),
+];
+
+const KNOWN_X_OPTIONS: &[&str] = &[
+    "Xint", "Xmixed", "Xcomp", "Xbatch", "Xrs", "Xnoclassgc", "Xincgc", "Xfuture",
+    "Xdiag", "Xcheck:jni", "Xshare:", "Xverify:", "Xlog", "Xloggc:", "Xbootclasspath",
+    "Xss", "Xmn", "XstartOnFirstThread", "Xdock:", "Xshareclasses", "Xtune:",
+];
+
+const KNOWN_OPTIONS: &[&str] = &[
+    "server", "client", "ea", "da", "esa", "dsa", "enableassertions", "disableassertions",
+    "verbose", "javaagent:", "agentlib:", "agentpath:", "splash:", "showversion",
+];
+
+const MODULE_OPTIONS: &[&str] = &[
+    "--add-opens", "--add-exports", "--add-modules", "--add-reads", "--enable-preview",
+    "--enable-native-access", "--illegal-access",
+];
+
+const LAUNCHER_OPTIONS: &[&str] = &["-cp", "-classpath", "--class-path", "-jar"];
+
+/// Named collector configurations. Unlike raw flag strings they know which
+/// Java releases can run them and emit the unlock flags those releases need.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GcPreset {
+    G1,
+    Aikar,
+    Zgc,
+    Shenandoah,
+}
+
+impl GcPreset {
+    pub const ALL: [GcPreset; 4] = [GcPreset::G1, GcPreset::Aikar, GcPreset::Zgc, GcPreset::Shenandoah];
+
+    pub fn label(&self) -> &'static str {
+        tr(match self {
+            GcPreset::G1 => "gc.g1",
+            GcPreset::Aikar => "gc.aikar",
+            GcPreset::Zgc => "gc.zgc",
+            GcPreset::Shenandoah => "gc.shenandoah",
+        })
+    }
+
+    pub fn min_java(&self) -> u32 {
+        match self {
+            GcPreset::G1 | GcPreset::Aikar => 8,
+            GcPreset::Zgc => 11,
+            GcPreset::Shenandoah => 12,
+        }
+    }
+
+    // Unknown Java versions are given the benefit of the doubt; the launch
+    // falls back to G1 if the real runtime turns out to be too old.
+    pub fn supports(&self, java_major: Option<u32>) -> bool {
+        java_major.is_none_or(|major| major >= self.min_java())
+    }
+
+    pub fn available(java_major: Option<u32>) -> Vec<GcPreset> {
+        Self::ALL.into_iter().filter(|preset| preset.supports(java_major)).collect()
+    }
+
+    pub fn args(&self, java_major: Option<u32>, memory_max_mb: u32) -> Vec<String> {
+        let major = java_major.unwrap_or(8);
+        let args: Vec<&str> = match self {
+            GcPreset::G1 => vec!["-XX:+UseG1GC", "-XX:+ParallelRefProcEnabled", "-XX:MaxGCPauseMillis=200"],
+            GcPreset::Aikar => {
+                // https://docs.papermc.io/paper/aikars-flags, with the large heap variant above 12 GB.
+                let large = memory_max_mb >= 12 * 1024;
+                vec![
+                    "-XX:+UseG1GC",
+                    "-XX:+ParallelRefProcEnabled",
+                    "-XX:MaxGCPauseMillis=200",
+                    "-XX:+UnlockExperimentalVMOptions",
+                    "-XX:+DisableExplicitGC",
+                    "-XX:+AlwaysPreTouch",
+                    if large { "-XX:G1NewSizePercent=40" } else { "-XX:G1NewSizePercent=30" },
+                    if large { "-XX:G1MaxNewSizePercent=50" } else { "-XX:G1MaxNewSizePercent=40" },
+                    if large { "-XX:G1HeapRegionSize=16M" } else { "-XX:G1HeapRegionSize=8M" },
+                    if large { "-XX:G1ReservePercent=15" } else { "-XX:G1ReservePercent=20" },
+                    "-XX:G1HeapWastePercent=5",
+                    "-XX:G1MixedGCCountTarget=4",
+                    if large { "-XX:InitiatingHeapOccupancyPercent=20" } else { "-XX:InitiatingHeapOccupancyPercent=15" },
+                    "-XX:G1MixedGCLiveThresholdPercent=90",
+                    "-XX:G1RSetUpdatingPauseTimePercent=5",
+                    "-XX:SurvivorRatio=32",
+                    "-XX:+PerfDisableSharedMem",
+                    "-XX:MaxTenuringThreshold=1",
+                ]
+            }
+            GcPreset::Zgc => {
+                let mut args = Vec::new();
+                if major < 15 {
+                    args.push("-XX:+UnlockExperimentalVMOptions");
+                }
+                args.push("-XX:+UseZGC");
+                // Generational ZGC is opt-in on 21 and 22, the default from 23.
+                if (21..23).contains(&major) {
+                    args.push("-XX:+ZGenerational");
+                }
+                args
+            }
+            GcPreset::Shenandoah => {
+                let mut args = Vec::new();
+                if major < 15 {
+                    args.push("-XX:+UnlockExperimentalVMOptions");
+                }
+                args.push("-XX:+UseShenandoahGC");
+                args
+            }
+        };
+        args.into_iter().map(str::to_string).collect()
+    }
+}
+
+pub fn has_collector(args: &str) -> bool {
+    args.split_whitespace()
+        .filter_map(|token| token.strip_prefix("-XX:+"))
+        .any(|name| GARBAGE_COLLECTORS.contains(&name))
+}
+
+impl LintIssue {
+    pub fn severity(&self) -> LintSeverity {
+        match self {
+            LintIssue::UnbalancedQuotes
+            | LintIssue::NotAFlag(_)
+            | LintIssue::InvalidSyntax(_)
+            | LintIssue::ConflictsWithLauncher(_)
+            | LintIssue::InvalidMemorySize(_)
+            | LintIssue::HeapMinAboveMax
+            | LintIssue::MultipleCollectors(_)
+            | LintIssue::RemovedFlag { .. }
+            | LintIssue::RequiresNewerJava { .. }
+            | LintIssue::RequiresUnlock { .. } => LintSeverity::Error,
+            LintIssue::QuotedArgument(_)
+            | LintIssue::UnknownFlag(_)
+            | LintIssue::DuplicateMemory(_)
+            | LintIssue::OverridesMemorySetting(_)
+            | LintIssue::DeprecatedFlag { .. } => LintSeverity::Warning,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            LintIssue::UnbalancedQuotes => tr("lint.unbalanced_quotes").to_string(),
+            LintIssue::QuotedArgument(arg) => trf("lint.quoted_argument", &[("arg", arg)]),
+            LintIssue::NotAFlag(arg) => trf("lint.not_a_flag", &[("arg", arg)]),
+            LintIssue::UnknownFlag(arg) => trf("lint.unknown_flag", &[("arg", arg)]),
+            LintIssue::InvalidSyntax(arg) => trf("lint.invalid_syntax", &[("arg", arg)]),
+            LintIssue::ConflictsWithLauncher(arg) => trf("lint.conflicts_with_launcher", &[("arg", arg)]),
+            LintIssue::InvalidMemorySize(arg) => trf("lint.invalid_memory_size", &[("arg", arg)]),
+            LintIssue::DuplicateMemory(flag) => trf("lint.duplicate_memory", &[("flag", flag)]),
+            LintIssue::OverridesMemorySetting(flag) => trf("lint.overrides_memory_setting", &[("flag", flag)]),
+            LintIssue::HeapMinAboveMax => tr("lint.heap_min_above_max").to_string(),
+            LintIssue::MultipleCollectors(collectors) => trf("lint.multiple_collectors", &[("collectors", &collectors.join(", "))]),
+            LintIssue::DeprecatedFlag { flag, since } => trf("lint.deprecated_flag", &[("flag", flag), ("since", since)]),
+            LintIssue::RemovedFlag { flag, since } => trf("lint.removed_flag", &[("flag", flag), ("since", since)]),
+            LintIssue::RequiresNewerJava { flag, since } => trf("lint.requires_newer_java", &[("flag", flag), ("since", since)]),
+            LintIssue::RequiresUnlock { flag, unlock } => trf("lint.requires_unlock", &[("flag", flag), ("unlock", unlock)]),
+        }
+    }
+}
+
+pub struct LintContext {
+    pub java_major: Option<u32>,
+    pub memory_min_mb: u32,
+    pub memory_max_mb: u32,
+}
+
+pub fn lint_jvm_args(args: &str, context: &LintContext) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    if !args.matches('"').count().is_multiple_of(2) || !args.matches('\'').count().is_multiple_of(2) {
+        issues.push(LintIssue::UnbalancedQuotes);
+    }
+
+    let tokens: Vec<&str> = args.split_whitespace().collect();
+    let mut heap_min: Vec<&str> = Vec::new();
+    let mut heap_max: Vec<&str> = Vec::new();
+    let mut collectors: Vec<String> = Vec::new();
+    let unlocked = |unlock: &str| tokens.iter().any(|t| t.strip_prefix("-XX:+") == Some(unlock));
+
+    let mut iter = tokens.iter();
+    while let Some(&token) = iter.next() {
+        if token.contains('"') || token.contains('\'') {
+            issues.push(LintIssue::QuotedArgument(token.to_string()));
+        }
+
+        if LAUNCHER_OPTIONS.contains(&token) {
+            issues.push(LintIssue::ConflictsWithLauncher(token.to_string()));
+            iter.next();
+            continue;
+        }
+        if let Some(option) = MODULE_OPTIONS.iter().find(|o| token.split('=').next() == Some(**o)) {
+            if context.java_major.is_some_and(|major| major < 9) {
+                issues.push(LintIssue::RequiresNewerJava { flag: option.to_string(), since: 9 });
+            }
+            if !token.contains('=') && *option != "--enable-preview" {
+                iter.next();
+            }
+            continue;
+        }
+
+        if let Some(size) = token.strip_prefix("-Xmx") {
+            check_memory(token, size, &mut issues);
+            heap_max.push(size);
+        } else if let Some(size) = token.strip_prefix("-Xms") {
+            check_memory(token, size, &mut issues);
+            heap_min.push(size);
+        } else if let Some(option) = token.strip_prefix("-XX:") {
+            let (enabled, name) = match option.chars().next() {
+                Some('+') => (true, &option[1..]),
+                Some('-') => (false, &option[1..]),
+                _ => match option.split_once('=') {
+                    Some((name, _)) => (true, name),
+                    None => {
+                        issues.push(LintIssue::InvalidSyntax(token.to_string()));
+                        continue;
+                    }
+                },
+            };
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                issues.push(LintIssue::InvalidSyntax(token.to_string()));
+                continue;
+            }
+            if enabled && GARBAGE_COLLECTORS.contains(&name) {
+                collectors.push(name.to_string());
+            }
+            check_version(token, name, enabled, context.java_major, &unlocked, &mut issues);
+        } else if token.starts_with("-D") || token.starts_with("-verbose:") {
+            continue;
+        } else if let Some(option) = token.strip_prefix("-X") {
+            let option = format!("X{}", option);
+            if !KNOWN_X_OPTIONS.iter().any(|known| option.starts_with(known)) {
+                issues.push(LintIssue::UnknownFlag(token.to_string()));
+            }
+        } else if let Some(option) = token.strip_prefix('-') {
+            let known = KNOWN_OPTIONS.iter().any(|known| {
+                if known.ends_with(':') { option.starts_with(known) } else { option == *known || option.starts_with(&format!("{}:", known)) }
+            });
+            if !known {
+                issues.push(LintIssue::UnknownFlag(token.to_string()));
+            }
+        } else {
+            issues.push(LintIssue::NotAFlag(token.to_string()));
+        }
+    }
+
+    for (flag, values) in [("-Xms", &heap_min), ("-Xmx", &heap_max)] {
+        if values.len() > 1 {
+            issues.push(LintIssue::DuplicateMemory(flag.to_string()));
+        }
+        if !values.is_empty() {
+            issues.push(LintIssue::OverridesMemorySetting(flag.to_string()));
+        }
+    }
+
+    let effective_min = heap_min.last().and_then(|size| parse_memory_mb(size)).unwrap_or(context.memory_min_mb as u64);
+    let effective_max = heap_max.last().and_then(|size| parse_memory_mb(size)).unwrap_or(context.memory_max_mb as u64);
+    if effective_min > effective_max {
+        issues.push(LintIssue::HeapMinAboveMax);
+    }
+
+    collectors.dedup();
+    if collectors.len() > 1 {
+        issues.push(LintIssue::MultipleCollectors(collectors));
+    }
+
+    issues.sort_by_key(|issue| std::cmp::Reverse(issue.severity()));
+    issues
+}
+
+pub fn has_errors(issues: &[LintIssue]) -> bool {
+    issues.iter().any(|issue| issue.severity() == LintSeverity::Error)
+}
+
+fn check_memory(token: &str, size: &str, issues: &mut Vec<LintIssue>) {
+    if parse_memory_mb(size).is_none_or(|mb| mb == 0) {
+        issues.push(LintIssue::InvalidMemorySize(token.to_string()));
+    }
+}
+
+fn check_version(
+    token: &str,
+    name: &str,
+    enabled: bool,
+    java_major: Option<u32>,
+    unlocked: &dyn Fn(&str) -> bool,
+    issues: &mut Vec<LintIssue>,
+) {
+    let retired = RETIRED_OPTIONS.iter().find(|(option, _, _)| {
+        if *option == "CMS" { name.starts_with("CMS") } else { name == *option }
+    });
+    if let Some((_, deprecated, removed)) = retired {
+        let issue = match java_major {
+            Some(major) if major >= *removed => LintIssue::RemovedFlag { flag: token.to_string(), since: *removed },
+            _ => LintIssue::DeprecatedFlag { flag: token.to_string(), since: *deprecated },
+        };
+        issues.push(issue);
+        return;
+    }
+
+    if !enabled {
+        return;
+    }
+    if let Some((_, since, experimental_until)) = NEWER_OPTIONS.iter().find(|(option, _, _)| name == *option) {
+        match java_major {
+            Some(major) if major < *since => {
+                issues.push(LintIssue::RequiresNewerJava { flag: token.to_string(), since: *since });
+            }
+            major => {
+                let experimental = experimental_until.is_some_and(|until| until == u32::MAX || major.is_some_and(|major| major <= until));
+                let unlock = "-XX:+UnlockExperimentalVMOptions";
+                if experimental && !unlocked("UnlockExperimentalVMOptions") {
+                    issues.push(LintIssue::RequiresUnlock { flag: token.to_string(), unlock });
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn parse_memory_mb(size: &str) -> Option<u64> {
+    let (digits, multiplier) = match size.chars().last()? {
+        'k' | 'K' => (&size[..size.len() - 1], 1.0 / 1024.0),
+        'm' | 'M' => (&size[..size.len() - 1], 1.0),
+        'g' | 'G' => (&size[..size.len() - 1], 1024.0),
+        't' | 'T' => (&size[..size.len() - 1], 1024.0 * 1024.0),
+        c if c.is_ascii_digit() => (size, 1.0 / (1024.0 * 1024.0)),
+        _ => return None,
+    };
+    let value: u64 = digits.parse().ok()?;
+    Some((value as f64 * multiplier) as u64)
+}
@@ -1,341 +1,388 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 
+use chrono::Utc;
 use uuid::Uuid;
-use tokio::process::{Child, Command};
+use tokio::process::Command;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::sync::Notify;
+use crate::i18n::{tr, trf};
 use crate::Result;
 use crate::instance::Instance;
-use crate::profile::{Profile, LaunchProfile};
+use crate::quickplay::QuickPlay;
 use crate::java::JavaInstallation;
 use crate::logs::{LogManager, LogLevel};
-use tokio::io::{AsyncBufReadExt, BufReader};
+use crate::crash::GameExit;
+use crate::events::{LauncherEvent, Notifier};
+use crate::assets::AssetIndex;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 
+pub mod args;
+
+const SESSIONS_DIR: &str = "sessions";
+const STALE_SESSION_AGE: std::time::Duration = std::time::Duration::from_secs(48 * 60 * 60);
+const CONSOLE_MAX_LINES: usize = 5000;
 
 #[derive(Debug, Clone)]
-pub struct LaunchContext {
-    pub instance: Instance,
-    pub profile: Profile,
-    pub java_installation: JavaInstallation,
+pub struct LaunchCommand {
+    pub java: PathBuf,
+    pub jvm_args: Vec<String>,
+    pub main_class: String,
+    pub game_args: Vec<String>,
     pub game_directory: PathBuf,
-    pub assets_directory: PathBuf,
-    pub libraries_directory: PathBuf,
-    pub natives_directory: PathBuf,
-    pub version_jar_path: PathBuf,
-    pub offline_mode: bool,
-    pub demo_mode: bool,
+    pub session_id: Uuid,
+    pub session_directory: PathBuf,
+    pub missing_libraries: Vec<PathBuf>,
+    pub native_jars: Vec<PathBuf>,
+    pub legacy_resources: Option<PathBuf>,
+    pub applet_era: bool,
 }
 
-pub struct LaunchTask {
-    pub context: LaunchContext,
-    pub steps: Vec<Box<dyn LaunchStep>>,
-    pub current_step: usize,
-    pub process: Option<Child>,
-}
+impl LaunchCommand {
+    pub fn natives_directory(&self) -> PathBuf {
+        self.session_directory.join("natives")
+    }
 
-impl std::fmt::Debug for LaunchTask {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("LaunchTask")
-            .field("context", &self.context)
-            .field("current_step", &self.current_step)
-            .field("steps_count", &self.steps.len())
-            .finish()
+    pub fn temp_directory(&self) -> PathBuf {
+        self.session_directory.join("tmp")
+    }
+
+    pub fn to_command(&self) -> Command {
+        let mut cmd = Command::new(&self.java);
+        cmd.args(&self.jvm_args);
+        cmd.arg(&self.main_class);
+        cmd.args(&self.game_args);
+        cmd
+    }
+
+    pub fn redacted(&self) -> Vec<String> {
+        let mut args = vec![self.java.to_string_lossy().to_string()];
+        args.extend(self.jvm_args.iter().cloned());
+        args.push(self.main_class.clone());
+
+        let mut hide_next = false;
+        for arg in &self.game_args {
+            if hide_next {
+                args.push("<redacted>".to_string());
+                hide_next = false;
+            } else if arg.starts_with("token:") {
+                args.push("<redacted>".to_string());
+            } else {
+                hide_next = arg == "--accessToken" || arg == "--session";
+                args.push(arg.clone());
+            }
+        }
+        args
     }
 }
 
-#[async_trait::async_trait]
-pub trait LaunchStep: Send + Sync {
-    async fn execute(&mut self, context: &LaunchContext) -> Result<()>;
-    fn name(&self) -> &str;
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+    pub safe_mode: bool,
+    pub wrapper_command: Option<String>,
+    pub skip_commands: bool,
+    pub pre_launch_command: Option<String>,
+    pub post_exit_command: Option<String>,
+    pub allow_shared_directories: bool,
+    pub variant: Option<String>,
+    pub env: BTreeMap<String, String>,
+    pub legacy_resource_proxy: Option<String>,
+    pub demo: bool,
+    pub account: Option<Uuid>,
+    // Extra game arguments from the active profile.
+    pub game_args: Vec<String>,
+    // Overrides the instance's own quick play target for this launch.
+    pub quick_play: Option<QuickPlay>,
 }
 
-pub struct CreateDirectoriesStep;
+#[derive(Debug)]
+pub struct PreparedLaunch {
+    instance: Instance,
+    account_id: Uuid,
+    java_path: PathBuf,
+    launch_command: LaunchCommand,
+    options: LaunchOptions,
+}
 
-#[async_trait::async_trait]
-impl LaunchStep for CreateDirectoriesStep {
-    async fn execute(&mut self, context: &LaunchContext) -> Result<()> {
-        tokio::fs::create_dir_all(&context.game_directory).await?;
-        tokio::fs::create_dir_all(&context.assets_directory).await?;
-        tokio::fs::create_dir_all(&context.libraries_directory).await?;
-        tokio::fs::create_dir_all(&context.natives_directory).await?;
-        Ok(())
+impl PreparedLaunch {
+    pub fn account_id(&self) -> Uuid {
+        self.account_id
     }
 
-    fn name(&self) -> &str {
-        "Создание директорий"
+    fn hook_env(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("INST_NAME", self.instance.name.clone()),
+            ("INST_ID", self.instance.id.to_string()),
+            ("INST_DIR", self.instance.path.to_string_lossy().to_string()),
+            ("INST_MC_DIR", self.launch_command.game_directory.to_string_lossy().to_string()),
+            ("INST_JAVA", self.java_path.to_string_lossy().to_string()),
+        ]
     }
-}
 
-pub struct ExtractNativesStep {
-    pub libraries: Vec<PathBuf>,
-}
+    pub async fn stage(&self) -> Result<()> {
+        let minecraft_dir = &self.launch_command.game_directory;
+        tokio::fs::create_dir_all(minecraft_dir).await?;
+        tokio::fs::create_dir_all(minecraft_dir.join("natives")).await?;
+        LaunchManager::prepare_session(&self.launch_command, &minecraft_dir.join("natives")).await?;
+        if let Some(virtual_dir) = &self.launch_command.legacy_resources {
+            let copied = crate::assets::install_resources(virtual_dir, minecraft_dir)?;
+            if copied > 0 {
+                tracing::info!("{}", trf("log.resources_copied", &[("count", &copied), ("path", &minecraft_dir.join("resources").display())]));
+            }
+        }
 
-#[async_trait::async_trait]
-impl LaunchStep for ExtractNativesStep {
-    async fn execute(&mut self, context: &LaunchContext) -> Result<()> {
-        for library_path in &self.libraries {
-            if library_path.extension().and_then(|s| s.to_str()) == Some("jar") {
-                let file = std::fs::File::open(library_path)?;
-                let mut archive = zip::ZipArchive::new(file)?;
-                
-                for i in 0..archive.len() {
-                    let mut file = archive.by_index(i)?;
-                    if file.name().ends_with(".dll") || 
-                       file.name().ends_with(".so") || 
-                       file.name().ends_with(".dylib") {
-                        
-                        let output_path = context.natives_directory.join(
-                            Path::new(file.name()).file_name().unwrap()
-                        );
-                        
-                        let mut output_file = std::fs::File::create(output_path)?;
-                        std::io::copy(&mut file, &mut output_file)?;
-                    }
-                }
+        if let Some(command) = &self.options.pre_launch_command {
+            if let Err(e) = LaunchManager::run_hook("Pre-launch", command, &self.hook_env(), minecraft_dir).await {
+                LaunchManager::remove_session(&self.launch_command.session_directory);
+                return Err(e);
             }
         }
         Ok(())
     }
-
-    fn name(&self) -> &str {
-        "Извлечение нативных библиотек"
-    }
 }
 
-pub struct BuildClasspathStep {
-    pub libraries: Vec<PathBuf>,
-    pub classpath: Vec<PathBuf>,
+#[derive(Debug, Clone)]
+struct ActiveSession {
+    instance_id: Uuid,
+    instance_name: String,
+    stop: Arc<Notify>,
+    console: SessionConsole,
 }
 
-#[async_trait::async_trait]
-impl LaunchStep for BuildClasspathStep {
-    async fn execute(&mut self, context: &LaunchContext) -> Result<()> {
-        self.classpath.clear();
-        
-        for library in &self.libraries {
-            self.classpath.push(library.clone());
-        }
-        
-        self.classpath.push(context.version_jar_path.clone());
-        
-        Ok(())
-    }
-
-    fn name(&self) -> &str {
-        "Построение classpath"
-    }
+#[derive(Debug, Clone)]
+pub struct ConsoleLine {
+    pub text: String,
+    pub stderr: bool,
 }
 
-pub struct LaunchMinecraftStep {
-    pub launch_profile: LaunchProfile,
+// Raw stdout/stderr of one game process plus a handle to its stdin. Clones
+// share the buffer, so a console stays readable after the process exits.
+#[derive(Debug, Clone)]
+pub struct SessionConsole {
+    lines: Arc<Mutex<VecDeque<ConsoleLine>>>,
+    input: UnboundedSender<String>,
+    running: Arc<std::sync::atomic::AtomicBool>,
 }
 
-#[async_trait::async_trait]
-impl LaunchStep for LaunchMinecraftStep {
-    async fn execute(&mut self, context: &LaunchContext) -> Result<()> {
-        let java_path = &context.java_installation.path;
-        
-        let mut command = std::process::Command::new(java_path);
-        
-        let classpath_str = self.launch_profile.classpath
-            .iter()
-            .map(|p| p.to_string_lossy())
-            .collect::<Vec<_>>()
-            .join(if cfg!(windows) { ";" } else { ":" });
-        
-        let mut jvm_args = self.launch_profile.jvm_arguments.clone();
-        
-        for arg in &mut jvm_args {
-            *arg = arg
-                .replace("${natives_directory}", &context.natives_directory.to_string_lossy())
-                .replace("${classpath}", &classpath_str)
-                .replace("${launcher_name}", "mango-launcher")
-                .replace("${launcher_version}", "1.0.0");
+impl SessionConsole {
+    fn new(input: UnboundedSender<String>) -> Self {
+        Self {
+            lines: Arc::new(Mutex::new(VecDeque::new())),
+            input,
+            running: Arc::new(std::sync::atomic::AtomicBool::new(true)),
         }
-        
-        command.args(&jvm_args);
-        command.arg(&self.launch_profile.main_class);
-        
-        let mut minecraft_args = self.launch_profile.minecraft_arguments.clone();
-        for arg in &mut minecraft_args {
-            *arg = arg
-                .replace("${auth_player_name}", &context.profile.username)
-                .replace("${version_name}", &self.launch_profile.minecraft_version)
-                .replace("${game_directory}", &context.game_directory.to_string_lossy())
-                .replace("${assets_root}", &context.assets_directory.to_string_lossy())
-                .replace("${assets_index_name}", &self.launch_profile.assets_index)
-                .replace("${auth_uuid}", "00000000-0000-0000-0000-000000000000")
-                .replace("${auth_access_token}", "0")
-                .replace("${clientid}", "00000000-0000-0000-0000-000000000000")
-                .replace("${auth_xuid}", "0")
-                .replace("${user_type}", "legacy");
+    }
+
+    fn push(&self, text: &str, stderr: bool) {
+        if let Ok(mut lines) = self.lines.lock() {
+            lines.push_back(ConsoleLine { text: text.to_string(), stderr });
+            if lines.len() > CONSOLE_MAX_LINES {
+                lines.pop_front();
+            }
         }
-        
-        command.args(&minecraft_args);
-        command.current_dir(&context.game_directory);
-        command.stdout(Stdio::piped());
-        command.stderr(Stdio::piped());
-        
-        log::info!("Запуск Minecraft: {:?}", command);
-        
-        Ok(())
     }
 
-    fn name(&self) -> &str {
-        "Запуск Minecraft"
+    pub fn lines(&self) -> Vec<ConsoleLine> {
+        self.lines.lock().map(|lines| lines.iter().cloned().collect()).unwrap_or_default()
     }
-}
 
-impl LaunchTask {
-    pub fn new(context: LaunchContext) -> Self {
-        Self {
-            context,
-            steps: Vec::new(),
-            current_step: 0,
-            process: None,
-        }
+    pub fn len(&self) -> usize {
+        self.lines.lock().map(|lines| lines.len()).unwrap_or(0)
     }
 
-    pub fn add_step(&mut self, step: Box<dyn LaunchStep>) {
-        self.steps.push(step);
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
-    pub async fn execute(&mut self) -> Result<()> {
-        for (i, step) in self.steps.iter_mut().enumerate() {
-            self.current_step = i;
-            log::info!("Выполнение шага: {}", step.name());
-            step.execute(&self.context).await?;
+    pub fn is_running(&self) -> bool {
+        self.running.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    pub fn send(&self, command: &str) -> Result<()> {
+        if !self.is_running() {
+            return Err(crate::Error::Launch("Game process has exited".to_string()));
         }
+        self.input.send(command.to_string())
+            .map_err(|_| crate::Error::Launch("Game process does not accept console input".to_string()))?;
+        self.push(&format!("> {}", command), false);
         Ok(())
     }
 
-    pub fn progress(&self) -> f32 {
-        if self.steps.is_empty() {
-            return 1.0;
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
         }
-        self.current_step as f32 / self.steps.len() as f32
+        let content: String = self.lines().iter().map(|line| format!("{}\n", line.text)).collect();
+        std::fs::write(path, content)?;
+        Ok(())
     }
+}
 
-    pub fn current_step_name(&self) -> Option<&str> {
-        self.steps.get(self.current_step).map(|step| step.name())
-    }
+#[derive(Debug, Clone)]
+pub struct DirectoryConflict {
+    pub directory: PathBuf,
+    pub instance_id: Uuid,
+    pub instance_name: String,
 }
 
 pub struct LaunchManager {
-    running_instances: HashMap<Uuid, LaunchTask>,
+    sessions: HashMap<Uuid, ActiveSession>,
+    run_locks: HashMap<PathBuf, Vec<Uuid>>,
+    finished_consoles: HashMap<Uuid, SessionConsole>,
     log_manager: Option<LogManager>,
+    notifier: Notifier,
+    exit_sender: UnboundedSender<GameExit>,
+    exit_receiver: UnboundedReceiver<GameExit>,
+}
+
+impl Default for LaunchManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl LaunchManager {
     pub fn new() -> Self {
+        let (exit_sender, exit_receiver) = mpsc::unbounded_channel();
         Self {
-            running_instances: HashMap::new(),
+            sessions: HashMap::new(),
+            run_locks: HashMap::new(),
+            finished_consoles: HashMap::new(),
             log_manager: None,
+            notifier: Notifier::new(),
+            exit_sender,
+            exit_receiver,
         }
     }
 
-    pub fn set_log_manager(&mut self, log_manager: LogManager) {
-        self.log_manager = Some(log_manager);
+    pub fn poll_exits(&mut self) -> Vec<GameExit> {
+        let mut exits = Vec::new();
+        while let Ok(exit) = self.exit_receiver.try_recv() {
+            if let Some(session) = self.sessions.remove(&exit.session_id) {
+                session.console.running.store(false, std::sync::atomic::Ordering::Relaxed);
+                self.finished_consoles.insert(exit.instance_id, session.console);
+            }
+            self.release_run_locks(exit.session_id);
+            exits.push(exit);
+        }
+        exits
     }
 
-    pub async fn launch_instance(
-        &mut self,
-        instance: Instance,
-        profile: Profile,
-        java_installation: JavaInstallation,
-        offline_mode: bool,
-        demo_mode: bool,
-    ) -> Result<Uuid> {
-        let launch_id = Uuid::new_v4();
-        
-        let game_directory = instance.path.join(".minecraft");
-        let assets_directory = game_directory.join("assets");
-        let libraries_directory = game_directory.join("libraries");
-        let natives_directory = game_directory.join("natives");
-        let version_jar_path = libraries_directory
-            .join("versions")
-            .join(&instance.minecraft_version)
-            .join(format!("{}.jar", instance.minecraft_version));
-
-        let context = LaunchContext {
-            instance,
-            profile,
-            java_installation,
-            game_directory,
-            assets_directory,
-            libraries_directory,
-            natives_directory,
-            version_jar_path,
-            offline_mode,
-            demo_mode,
-        };
+    // Instances can point at the same world data through a symlinked game
+    // directory or saves folder, so locks are keyed by the resolved path.
+    pub fn shared_directories(game_directory: &Path) -> Vec<PathBuf> {
+        let game_directory = std::fs::canonicalize(game_directory).unwrap_or_else(|_| game_directory.to_path_buf());
+        let mut directories = vec![game_directory.clone()];
+        if let Ok(saves) = std::fs::canonicalize(game_directory.join("saves")) {
+            if !saves.starts_with(&game_directory) {
+                directories.push(saves);
+            }
+        }
+        directories
+    }
 
-        let mut task = LaunchTask::new(context);
-        
-        task.add_step(Box::new(CreateDirectoriesStep));
-        task.add_step(Box::new(ExtractNativesStep { libraries: Vec::new() }));
-        task.add_step(Box::new(BuildClasspathStep { 
-            libraries: Vec::new(),
-            classpath: Vec::new(),
-        }));
-        
-        self.running_instances.insert(launch_id, task);
-        
-        Ok(launch_id)
+    pub fn directory_conflicts(&self, game_directory: &Path) -> Vec<DirectoryConflict> {
+        let mut conflicts = Vec::new();
+        for directory in Self::shared_directories(game_directory) {
+            let Some(holders) = self.run_locks.get(&directory) else { continue };
+            for session in holders.iter().filter_map(|id| self.sessions.get(id)) {
+                if !conflicts.iter().any(|c: &DirectoryConflict| c.directory == directory && c.instance_id == session.instance_id) {
+                    conflicts.push(DirectoryConflict {
+                        directory: directory.clone(),
+                        instance_id: session.instance_id,
+                        instance_name: session.instance_name.clone(),
+                    });
+                }
+            }
+        }
+        conflicts
     }
 
-    pub async fn execute_launch(&mut self, launch_id: Uuid) -> Result<()> {
-        if let Some(task) = self.running_instances.get_mut(&launch_id) {
-            task.execute().await?;
+    fn acquire_run_locks(&mut self, session_id: Uuid, game_directory: &Path) {
+        for directory in Self::shared_directories(game_directory) {
+            self.run_locks.entry(directory).or_default().push(session_id);
         }
-        Ok(())
     }
 
-    pub fn get_launch_progress(&self, launch_id: Uuid) -> Option<f32> {
-        self.running_instances.get(&launch_id).map(|task| task.progress())
+    fn release_run_locks(&mut self, session_id: Uuid) {
+        self.run_locks.retain(|_, holders| {
+            holders.retain(|id| *id != session_id);
+            !holders.is_empty()
+        });
+    }
+
+    // The console of the running session, or of the last session that exited.
+    pub fn console(&self, instance_id: Uuid) -> Option<SessionConsole> {
+        self.sessions.values()
+            .find(|s| s.instance_id == instance_id)
+            .map(|s| s.console.clone())
+            .or_else(|| self.finished_consoles.get(&instance_id).cloned())
+    }
+
+    pub fn running_session_count(&self, instance_id: Uuid) -> usize {
+        self.sessions.values().filter(|s| s.instance_id == instance_id).count()
     }
 
-    pub fn get_current_step(&self, launch_id: Uuid) -> Option<&str> {
-        self.running_instances
-            .get(&launch_id)
-            .and_then(|task| task.current_step_name())
+    pub fn is_running(&self, instance_id: Uuid) -> bool {
+        self.sessions.values().any(|s| s.instance_id == instance_id)
     }
 
-    pub fn kill_instance(&mut self, launch_id: Uuid) -> Result<()> {
-        if let Some(mut task) = self.running_instances.remove(&launch_id) {
-            if let Some(mut process) = task.process.take() {
-                let _ = process.kill();
+    pub fn stop_instance(&self, instance_id: Uuid) -> usize {
+        let sessions: Vec<&ActiveSession> = self.sessions.values()
+            .filter(|s| s.instance_id == instance_id)
+            .collect();
+        for session in &sessions {
+            session.stop.notify_one();
+        }
+        sessions.len()
+    }
+
+    pub fn cleanup_stale_sessions(data_dir: &Path) -> usize {
+        let Ok(entries) = std::fs::read_dir(data_dir.join(SESSIONS_DIR)) else {
+            return 0;
+        };
+
+        let mut removed = 0;
+        for entry in entries.flatten() {
+            let stale = entry.metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age >= STALE_SESSION_AGE);
+            if stale && std::fs::remove_dir_all(entry.path()).is_ok() {
+                removed += 1;
             }
         }
-        Ok(())
+        removed
     }
 
-    pub fn is_running(&self, launch_id: Uuid) -> bool {
-        self.running_instances.contains_key(&launch_id)
+    pub fn set_log_manager(&mut self, log_manager: LogManager) {
+        self.log_manager = Some(log_manager);
     }
 
-    pub fn list_running(&self) -> Vec<Uuid> {
-        self.running_instances.keys().copied().collect()
+    pub fn set_notifier(&mut self, notifier: Notifier) {
+        self.notifier = notifier;
     }
 
-    pub async fn launch_minecraft(
-        &mut self,
+    pub fn build_command(
         instance: &Instance,
         account: &crate::auth::Account,
         java: &JavaInstallation,
         version_manager: &crate::version::VersionManager,
-        data_dir: &PathBuf,
-    ) -> Result<()> {
-        let instance_dir = data_dir.join("instances").join(instance.id.to_string());
-        let minecraft_dir = instance_dir.join(".minecraft");
-        let natives_dir = minecraft_dir.join("natives");
-        
-        tokio::fs::create_dir_all(&minecraft_dir).await?;
-        tokio::fs::create_dir_all(&natives_dir).await?;
+        data_dir: &Path,
+    ) -> Result<LaunchCommand> {
+        let minecraft_dir = instance.path.join(".minecraft");
+        let session_id = Uuid::new_v4();
+        let session_directory = data_dir.join(SESSIONS_DIR).join(session_id.to_string());
+        let natives_dir = session_directory.join("natives");
+        let temp_dir = session_directory.join("tmp");
         
         let version_details = version_manager.get_version_details(&instance.minecraft_version)?;
-        let version_jar = version_manager.get_version_jar_path(&instance.minecraft_version);
+        let version_jar = instance.client_jar()
+            .unwrap_or_else(|| version_manager.get_version_jar_path(&instance.minecraft_version));
         
         if !version_jar.exists() {
             return Err(crate::Error::Other(format!("Version JAR not found: {}", version_jar.display())));
@@ -343,19 +390,31 @@ impl LaunchManager {
         
         let libraries_dir = version_manager.get_libraries_dir();
         let mut classpath_entries = Vec::new();
+        let mut missing_libraries = Vec::new();
+        let mut native_jars = Vec::new();
         
         if let Some(libraries) = &version_details.libraries {
-            for library in libraries {
+            for library in libraries.iter().filter(|library| library.is_allowed()) {
                 if let Some(downloads) = &library.downloads {
                     if let Some(artifact) = &downloads.artifact {
                         let lib_path = libraries_dir.join(&artifact.path);
                         if lib_path.exists() {
                             classpath_entries.push(lib_path);
                         } else {
-                            log::warn!("Library not found: {}", lib_path.display());
+                            tracing::warn!("Library not found: {}", lib_path.display());
+                            missing_libraries.push(lib_path);
                         }
                     }
                 }
+                if let Some(artifact) = library.native_artifact() {
+                    let lib_path = libraries_dir.join(&artifact.path);
+                    if lib_path.exists() {
+                        native_jars.push(lib_path);
+                    } else {
+                        tracing::warn!("Natives not found: {}", lib_path.display());
+                        missing_libraries.push(lib_path);
+                    }
+                }
             }
         }
         
@@ -365,148 +424,518 @@ impl LaunchManager {
             .iter()
             .map(|p| p.to_string_lossy())
             .collect::<Vec<_>>()
-            .join(if cfg!(windows) { ";" } else { ":" });
-        
-        let mut cmd = Command::new(&java.path);
-        
-        #[cfg(target_os = "macos")]
-        cmd.arg("-XstartOnFirstThread");
-        
-        cmd.arg(format!("-Djava.library.path={}", natives_dir.to_string_lossy()));
-        cmd.arg(format!("-Xms{}M", instance.memory_min.unwrap_or(1024)));
-        cmd.arg(format!("-Xmx{}M", instance.memory_max.unwrap_or(4096)));
+            .join(crate::platform::get_classpath_separator());
+        
+        let main_class = version_details.main_class
+            .clone()
+            .unwrap_or_else(|| "net.minecraft.client.main.Main".to_string());
+        
+        let assets_dir = data_dir.join("assets");
+        let assets_id = version_details.assets.clone().unwrap_or_else(|| "legacy".to_string());
+        let asset_index = AssetIndex::load(&assets_dir, &assets_id).ok();
+        let game_assets = asset_index.as_ref()
+            .map(|index| crate::assets::game_assets_dir(&assets_dir, &assets_id, index))
+            .unwrap_or_else(|| assets_dir.clone());
+        let legacy_resources = asset_index.as_ref()
+            .filter(|index| index.maps_to_resources())
+            .map(|_| game_assets.clone());
+
+        let mut environment = args::Environment::current();
+        if instance.demo_mode {
+            environment = environment.with_feature(args::DEMO_USER);
+        }
+        if instance.width.is_some() || instance.height.is_some() {
+            environment = environment.with_feature(args::CUSTOM_RESOLUTION);
+        }
+        let user_type = if account.account_type == crate::auth::AccountType::Offline { "legacy" } else { "msa" };
+        let uuid = account.uuid.clone().unwrap_or_else(|| Uuid::nil().simple().to_string());
+        let token = account.access_token.clone().unwrap_or_else(|| "0".to_string());
+        let session = match &account.access_token {
+            Some(token) => format!("token:{}:{}", token, uuid),
+            None => "-".to_string(),
+        };
+        let placeholders = [
+            ("${auth_player_name}", account.display_name.clone()),
+            ("${auth_session}", session),
+            ("${auth_uuid}", uuid),
+            ("${auth_access_token}", token),
+            ("${auth_xuid}", "0".to_string()),
+            ("${clientid}", String::new()),
+            ("${user_type}", user_type.to_string()),
+            ("${user_properties}", "{}".to_string()),
+            ("${version_name}", instance.minecraft_version.clone()),
+            ("${version_type}", version_details.r#type.clone()),
+            // A resolution set on one side only keeps the game's default
+            // for the other.
+            ("${resolution_width}", instance.width.unwrap_or(854).to_string()),
+            ("${resolution_height}", instance.height.unwrap_or(480).to_string()),
+            ("${launcher_name}", "mango-launcher".to_string()),
+            ("${launcher_version}", crate::VERSION.to_string()),
+            ("${game_directory}", minecraft_dir.to_string_lossy().to_string()),
+            ("${game_assets}", game_assets.to_string_lossy().to_string()),
+            ("${assets_root}", assets_dir.to_string_lossy().to_string()),
+            ("${assets_index_name}", assets_id.clone()),
+            ("${natives_directory}", natives_dir.to_string_lossy().to_string()),
+            ("${library_directory}", libraries_dir.to_string_lossy().to_string()),
+            ("${classpath_separator}", crate::platform::get_classpath_separator().to_string()),
+            ("${classpath}", classpath),
+        ];
+
+        let mut jvm_args = args::jvm_arguments(&version_details, &environment, &placeholders);
+        let mut game_args = args::game_arguments(&version_details, &environment, &placeholders);
+        let unresolved = args::unresolved(&jvm_args).into_iter().chain(args::unresolved(&game_args)).collect::<Vec<_>>();
+        if !unresolved.is_empty() {
+            tracing::warn!("{}", trf("log.unknown_placeholders", &[("version", &instance.minecraft_version), ("placeholders", &unresolved.join(", "))]));
+        }
+
+        // Set after the version's own arguments so these win over the
+        // natives directory some versions also use as their temp directory.
+        jvm_args.push(format!("-Dorg.lwjgl.system.SharedLibraryExtractPath={}", natives_dir.to_string_lossy()));
+        jvm_args.push(format!("-Djava.io.tmpdir={}", temp_dir.to_string_lossy()));
+        jvm_args.push(format!("-Djna.tmpdir={}", temp_dir.to_string_lossy()));
+        if version_details.is_applet_era() {
+            jvm_args.push(format!("-Dminecraft.applet.TargetDirectory={}", minecraft_dir.to_string_lossy()));
+            jvm_args.push("-Djava.util.Arrays.useLegacyMergeSort=true".to_string());
+        }
+        // Unset values are filled from the profile and global settings by
+        // `Instance::apply_inherited` before the command is built.
+        if let Some(memory_min) = instance.memory_min {
+            jvm_args.push(format!("-Xms{}M", memory_min));
+        }
+        if let Some(memory_max) = instance.memory_max {
+            jvm_args.push(format!("-Xmx{}M", memory_max));
+        }
         
-        if let Some(java_args) = &instance.java_args {
-            for arg in java_args.split_whitespace() {
-                cmd.arg(arg);
+        if let Some(preset) = instance.gc_preset {
+            if instance.java_args.as_deref().is_some_and(crate::jvm_lint::has_collector) {
+                tracing::warn!("{}", trf("log.gc_preset_overridden", &[("preset", &preset.label())]));
+            } else {
+                let major = java.major_version();
+                let preset = if preset.supports(major) {
+                    preset
+                } else {
+                    tracing::warn!("{}", trf("log.gc_preset_needs_java", &[("preset", &preset.label()), ("java", &preset.min_java())]));
+                    crate::jvm_lint::GcPreset::G1
+                };
+                jvm_args.extend(preset.args(major, instance.memory_max.unwrap_or_default()));
             }
         }
-        
-        cmd.arg("-cp").arg(&classpath);
-        
-        if let Some(main_class) = &version_details.main_class {
-            cmd.arg(main_class);
-        } else {
-            cmd.arg("net.minecraft.client.main.Main");
+        if let Some(java_args) = &instance.java_args {
+            jvm_args.extend(java_args.split_whitespace().map(str::to_string));
         }
-        
-        cmd.arg("--username").arg(&account.display_name);
-        cmd.arg("--version").arg(&instance.minecraft_version);
-        cmd.arg("--gameDir").arg(minecraft_dir.to_string_lossy().as_ref());
-        cmd.arg("--userType").arg(if account.account_type == crate::auth::AccountType::Offline { "legacy" } else { "msa" });
-        
-        if let Some(uuid) = &account.uuid {
-            cmd.arg("--uuid").arg(uuid);
+
+        if instance.fullscreen == Some(true) {
+            game_args.push("--fullscreen".to_string());
         }
-        
-        if let Some(token) = &account.access_token {
-            cmd.arg("--accessToken").arg(token);
+        // `auto_connect` predates quick play and only ever named a server.
+        let quick_play = instance.quick_play.clone()
+            .or_else(|| instance.auto_connect.clone().map(QuickPlay::Server));
+        if let Some(target) = quick_play {
+            game_args.extend(target.game_args(version_details.supports_quick_play())?);
         }
         
-        if let Some(width) = instance.width {
-            cmd.arg("--width").arg(width.to_string());
+        Ok(LaunchCommand {
+            java: java.path.clone(),
+            jvm_args,
+            main_class,
+            game_args,
+            game_directory: minecraft_dir,
+            session_id,
+            session_directory,
+            missing_libraries,
+            native_jars,
+            legacy_resources,
+            applet_era: version_details.is_applet_era(),
+        })
+    }
+
+    pub fn prepare_launch(
+        &self,
+        instance: &Instance,
+        account: &crate::auth::Account,
+        java: &JavaInstallation,
+        version_manager: &crate::version::VersionManager,
+        data_dir: &Path,
+        options: &LaunchOptions,
+    ) -> Result<PreparedLaunch> {
+        let mut launch_command = Self::build_command(instance, account, java, version_manager, data_dir)?;
+        launch_command.game_args.extend(options.game_args.iter().cloned());
+        // Applet-era clients fetch sounds and skins from long-gone hosts; a
+        // compatible proxy serves them instead.
+        if launch_command.applet_era {
+            if let Some((host, port)) = options.legacy_resource_proxy.as_deref().and_then(crate::settings::split_host_port) {
+                launch_command.jvm_args.push(format!("-Dhttp.proxyHost={}", host));
+                launch_command.jvm_args.push(format!("-Dhttp.proxyPort={}", port));
+            }
+        }
+        self.check_launch_allowed(instance.id, &launch_command.game_directory, options)?;
+        Ok(PreparedLaunch {
+            instance: instance.clone(),
+            account_id: account.id,
+            java_path: java.path.clone(),
+            launch_command,
+            options: options.clone(),
+        })
+    }
+
+    fn check_launch_allowed(&self, instance_id: Uuid, game_directory: &Path, options: &LaunchOptions) -> Result<()> {
+        if let Some(running) = self.sessions.values().find(|s| s.instance_id == instance_id) {
+            return Err(crate::Error::Launch(format!("Instance '{}' is already running", running.instance_name)));
+        }
+
+        if !options.allow_shared_directories {
+            if let Some(conflict) = self.directory_conflicts(game_directory).first() {
+                return Err(crate::Error::Launch(format!(
+                    "{} is in use by running instance '{}'",
+                    conflict.directory.display(), conflict.instance_name
+                )));
+            }
         }
-        if let Some(height) = instance.height {
-            cmd.arg("--height").arg(height.to_string());
+        Ok(())
+    }
+
+    // Launch failures that happen before this point are reported by the
+    // caller, which is the only one that sees them.
+    pub fn start_prepared(&mut self, prepared: PreparedLaunch) -> Result<()> {
+        let (instance_id, name) = (prepared.instance.id, prepared.instance.name.clone());
+        let result = self.spawn_prepared(prepared);
+        self.notifier.emit(match &result {
+            Ok(()) => LauncherEvent::LaunchStarted { instance_id, name },
+            Err(e) => LauncherEvent::LaunchFailed { instance_id, name, error: e.to_string() },
+        });
+        result
+    }
+
+    fn spawn_prepared(&mut self, prepared: PreparedLaunch) -> Result<()> {
+        let hook_env = prepared.hook_env();
+        let PreparedLaunch { instance, launch_command, options, .. } = prepared;
+        let minecraft_dir = launch_command.game_directory.clone();
+        let session_directory = launch_command.session_directory.clone();
+        if let Err(e) = self.check_launch_allowed(instance.id, &minecraft_dir, &options) {
+            Self::remove_session(&session_directory);
+            return Err(e);
         }
-        if instance.fullscreen {
-            cmd.arg("--fullscreen");
+
+        let mut cmd = launch_command.to_command();
+        
+        if let Some(wrapper) = options.wrapper_command.as_deref().filter(|w| !w.trim().is_empty()) {
+            let placeholders = [
+                ("${instance_name}", instance.name.clone()),
+                ("${game_directory}", minecraft_dir.to_string_lossy().to_string()),
+                ("${version_name}", instance.minecraft_version.clone()),
+            ];
+            cmd = Self::wrap_command(cmd, wrapper, &placeholders)?;
         }
         
         cmd.current_dir(&minecraft_dir);
+        for (key, value) in &options.env {
+            let value = Self::expand_env_value(value, &hook_env);
+            tracing::debug!("{}", trf("log.env_var", &[("key", &key), ("value", &value)]));
+            cmd.env(key, value);
+        }
+        cmd.stdin(Stdio::piped());
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
         
-        log::info!("Запуск Minecraft: {:?}", cmd);
+        tracing::info!("{}", trf("log.launch_command", &[("command", &launch_command.redacted().join(" "))]));
         
-        let mut child = cmd.spawn()?;
+        let mods_dir = minecraft_dir.join("mods");
+        if options.safe_mode && mods_dir.exists() {
+            let moved = crate::mods::ModManager::enter_safe_mode(&mods_dir)?;
+            tracing::info!("{}", trf("log.safe_mode_disabled_mods", &[("count", &moved)]));
+        }
+
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                if options.safe_mode {
+                    Self::restore_safe_mode(&mods_dir);
+                }
+                Self::remove_session(&session_directory);
+                return Err(e.into());
+            }
+        };
         
+        let (input_sender, mut input_receiver) = mpsc::unbounded_channel::<String>();
+        let console = SessionConsole::new(input_sender);
+        if let Some(mut stdin) = child.stdin.take() {
+            tokio::spawn(async move {
+                while let Some(command) = input_receiver.recv().await {
+                    if stdin.write_all(format!("{}\n", command).as_bytes()).await.is_err() || stdin.flush().await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        // Several games can run at once, so every line carries the name of
+        // the instance it came from.
         let log_manager_stdout = self.log_manager.clone();
+        let console_stdout = console.clone();
+        let name_stdout = instance.name.clone();
         if let Some(stdout) = child.stdout.take() {
             tokio::spawn(async move {
                 let reader = BufReader::new(stdout);
                 let mut lines = reader.lines();
                 
                 while let Ok(Some(line)) = lines.next_line().await {
+                    console_stdout.push(&line, false);
                     if let Some(ref log_manager) = log_manager_stdout {
-                        Self::parse_and_log_with_manager(log_manager, &line, false);
+                        Self::parse_and_log_with_manager(log_manager, &name_stdout, &line, false);
                     } else {
-                        Self::parse_and_log_minecraft_line(&line, false);
+                        Self::parse_and_log_minecraft_line(&name_stdout, &line, false);
                     }
                 }
             });
         }
         
         let log_manager_stderr = self.log_manager.clone();
+        let console_stderr = console.clone();
+        let name_stderr = instance.name.clone();
         if let Some(stderr) = child.stderr.take() {
             tokio::spawn(async move {
                 let reader = BufReader::new(stderr);
                 let mut lines = reader.lines();
                 
                 while let Ok(Some(line)) = lines.next_line().await {
+                    console_stderr.push(&line, true);
                     if let Some(ref log_manager) = log_manager_stderr {
-                        Self::parse_and_log_with_manager(log_manager, &line, true);
+                        Self::parse_and_log_with_manager(log_manager, &name_stderr, &line, true);
                     } else {
-                        Self::parse_and_log_minecraft_line(&line, true);
+                        Self::parse_and_log_minecraft_line(&name_stderr, &line, true);
                     }
                 }
             });
         }
         
+        let session_id = launch_command.session_id;
+        let stop = Arc::new(Notify::new());
+        self.sessions.insert(session_id, ActiveSession {
+            instance_id: instance.id,
+            instance_name: instance.name.clone(),
+            stop: stop.clone(),
+            console,
+        });
+        self.acquire_run_locks(session_id, &minecraft_dir);
+        tracing::info!("{}", trf("log.session_started", &[("session", &session_id), ("name", &instance.name), ("count", &self.running_session_count(instance.id))]));
+
+        let exit_sender = self.exit_sender.clone();
+        let instance_id = instance.id;
+        let instance_name = instance.name.clone();
+        let game_directory = minecraft_dir.clone();
+        let started_at = Utc::now();
+        let safe_mode = options.safe_mode;
+        let post_exit_command = options.post_exit_command.clone();
         tokio::spawn(async move {
-            let _ = child.wait().await;
-            log::info!("Minecraft процесс завершен");
+            let mut stopped = false;
+            let status = tokio::select! {
+                status = child.wait() => status,
+                _ = stop.notified() => {
+                    stopped = true;
+                    tracing::info!("{}", tr("log.stop_requested"));
+                    if let Err(e) = child.start_kill() {
+                        tracing::error!("{}", trf("log.stop_failed", &[("error", &e)]));
+                    }
+                    child.wait().await
+                }
+            };
+            let exit_code = match status {
+                Ok(status) => status.code(),
+                Err(e) => {
+                    tracing::error!("{}", trf("log.wait_failed", &[("error", &e)]));
+                    None
+                }
+            };
+            tracing::info!("{}", trf("log.process_exited", &[("code", &format!("{:?}", exit_code))]));
+            if safe_mode {
+                Self::restore_safe_mode(&mods_dir);
+            }
+            Self::remove_session(&session_directory);
+            if let Some(command) = &post_exit_command {
+                if let Err(e) = Self::run_hook("Post-exit", command, &hook_env, &game_directory).await {
+                    tracing::error!("{}", e);
+                }
+            }
+            let _ = exit_sender.send(GameExit {
+                session_id,
+                instance_id,
+                instance_name,
+                game_directory,
+                exit_code,
+                stopped,
+                safe_mode,
+                started_at,
+                ended_at: Utc::now(),
+            });
         });
         
         Ok(())
     }
 
-    fn parse_and_log_with_manager(log_manager: &LogManager, line: &str, is_stderr: bool) {
+    fn wrap_command(cmd: Command, wrapper: &str, placeholders: &[(&str, String)]) -> Result<Command> {
+        let tokens: Vec<String> = crate::utils::split_command_line(wrapper)
+            .into_iter()
+            .map(|token| {
+                placeholders.iter().fold(token, |token, (key, value)| token.replace(key, value))
+            })
+            .collect();
+
+        let (program, wrapper_args) = tokens.split_first()
+            .ok_or_else(|| crate::Error::Launch("Wrapper command is empty".to_string()))?;
+
+        let inner = cmd.as_std();
+        let mut inner_args = vec![inner.get_program().to_os_string()];
+        inner_args.extend(inner.get_args().map(|arg| arg.to_os_string()));
+
+        let mut wrapped = Command::new(program);
+        let mut command_inserted = false;
+        for arg in wrapper_args {
+            if arg == "%command%" {
+                wrapped.args(&inner_args);
+                command_inserted = true;
+            } else {
+                wrapped.arg(arg);
+            }
+        }
+        if !command_inserted {
+            wrapped.args(&inner_args);
+        }
+
+        tracing::info!("{}", trf("log.wrapper_used", &[("program", &program)]));
+        Ok(wrapped)
+    }
+
+    // Expands `${INST_*}` placeholders and falls back to the launcher's own
+    // environment, so `${PATH}:/opt/bin` extends rather than replaces.
+    // Unknown names are left untouched.
+    fn expand_env_value(value: &str, placeholders: &[(&str, String)]) -> String {
+        let mut result = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(start) = rest.find("${") {
+            result.push_str(&rest[..start]);
+            let Some(len) = rest[start + 2..].find('}') else {
+                rest = &rest[start..];
+                break;
+            };
+            let name = &rest[start + 2..start + 2 + len];
+            let replacement = placeholders.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.clone())
+                .or_else(|| std::env::var(name).ok());
+            match replacement {
+                Some(replacement) => result.push_str(&replacement),
+                None => result.push_str(&rest[start..start + 3 + len]),
+            }
+            rest = &rest[start + 3 + len..];
+        }
+        result.push_str(rest);
+        result
+    }
+
+    async fn prepare_session(launch_command: &LaunchCommand, shared_natives: &Path) -> Result<()> {
+        let natives_dir = launch_command.natives_directory();
+        tokio::fs::create_dir_all(&natives_dir).await?;
+        tokio::fs::create_dir_all(launch_command.temp_directory()).await?;
+
+        let mut entries = tokio::fs::read_dir(shared_natives).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_type().await?.is_file() {
+                tokio::fs::copy(entry.path(), natives_dir.join(entry.file_name())).await?;
+            }
+        }
+
+        for jar in &launch_command.native_jars {
+            Self::extract_natives(jar, &natives_dir)?;
+        }
+        Ok(())
+    }
+
+    fn extract_natives(jar: &Path, natives_dir: &Path) -> Result<()> {
+        let file = std::fs::File::open(jar)?;
+        let mut archive = zip::ZipArchive::new(file)?;
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            let name = entry.name().to_string();
+            if name.starts_with("META-INF/") || !(name.ends_with(".dll") || name.ends_with(".so") || name.ends_with(".dylib") || name.ends_with(".jnilib")) {
+                continue;
+            }
+            let Some(file_name) = Path::new(&name).file_name() else { continue };
+            let mut output = std::fs::File::create(natives_dir.join(file_name))?;
+            std::io::copy(&mut entry, &mut output)?;
+        }
+        Ok(())
+    }
+
+    async fn run_hook(label: &str, command: &str, env: &[(&str, String)], working_dir: &Path) -> Result<()> {
+        let tokens = crate::utils::split_command_line(command);
+        let (program, args) = tokens.split_first()
+            .ok_or_else(|| crate::Error::Launch(format!("{} command is empty", label)))?;
+
+        tracing::info!("{}", trf("log.running_command", &[("label", &label), ("command", &command)]));
+        let status = Command::new(program)
+            .args(args)
+            .envs(env.iter().map(|(key, value)| (*key, value)))
+            .current_dir(working_dir)
+            .stdin(Stdio::null())
+            .status()
+            .await
+            .map_err(|e| crate::Error::Launch(format!("{} command failed to start: {}", label, e)))?;
+
+        if !status.success() {
+            return Err(crate::Error::Launch(format!("{} command exited with {}", label, status)));
+        }
+        Ok(())
+    }
+
+    fn remove_session(session_directory: &Path) {
+        if let Err(e) = std::fs::remove_dir_all(session_directory) {
+            tracing::warn!("{}", trf("log.session_dir_remove_failed", &[("path", &session_directory.display()), ("error", &e)]));
+        }
+    }
+
+    fn restore_safe_mode(mods_dir: &Path) {
+        match crate::mods::ModManager::exit_safe_mode(mods_dir) {
+            Ok(restored) => tracing::info!("{}", trf("log.safe_mode_finished", &[("count", &restored)])),
+            Err(e) => tracing::error!("{}", trf("log.safe_mode_restore_failed", &[("error", &e)])),
+        }
+    }
+
+    // The source is the instance name, followed by the game thread when the
+    // line has one: "Survival/Render thread".
+    fn parse_and_log_with_manager(log_manager: &LogManager, instance_name: &str, line: &str, is_stderr: bool) {
         if let Some(parsed) = Self::parse_minecraft_log_line(line) {
             let level = LogLevel::from_minecraft_level(&parsed.level);
             let source = if parsed.source.is_empty() { 
-                "Minecraft".to_string() 
+                instance_name.to_string()
             } else { 
-                format!("Minecraft/{}", parsed.source) 
+                format!("{}/{}", instance_name, parsed.source) 
             };
             
             let formatted = format!("!![{}]! {}", parsed.level.to_uppercase(), parsed.message);
             log_manager.log(level, formatted, Some(source));
         } else {
             if is_stderr {
-                log_manager.log(LogLevel::Error, format!("!![ERROR]! {}", line), Some("Minecraft".to_string()));
+                log_manager.log(LogLevel::Error, format!("!![ERROR]! {}", line), Some(instance_name.to_string()));
             } else {
-                log_manager.log(LogLevel::Info, format!("!![INFO]! {}", line), Some("Minecraft".to_string()));
+                log_manager.log(LogLevel::Info, format!("!![INFO]! {}", line), Some(instance_name.to_string()));
             }
         }
     }
 
-    fn parse_and_log_minecraft_line(line: &str, is_stderr: bool) {
-    
+    fn parse_and_log_minecraft_line(instance_name: &str, line: &str, is_stderr: bool) {
         if let Some(parsed) = Self::parse_minecraft_log_line(line) {
-            let level_str = parsed.level.to_uppercase();
-            let _source = if parsed.source.is_empty() { 
-                "Minecraft".to_string() 
-            } else { 
-                format!("Minecraft/{}", parsed.source) 
-            };
-            
-        
-            let formatted = format!("!![{}]! {}", level_str, parsed.message);
+            let formatted = format!("[{}] !![{}]! {}", instance_name, parsed.level.to_uppercase(), parsed.message);
             
             match parsed.level.to_lowercase().as_str() {
-                "error" | "fatal" => log::error!("{}", formatted),
-                "warn" | "warning" => log::warn!("{}", formatted),
-                "debug" => log::debug!("{}", formatted),
-                _ => log::info!("{}", formatted),
+                "error" | "fatal" => tracing::error!("{}", formatted),
+                "warn" | "warning" => tracing::warn!("{}", formatted),
+                "debug" => tracing::debug!("{}", formatted),
+                _ => tracing::info!("{}", formatted),
             }
+        } else if is_stderr {
+            tracing::warn!("[{}] !![ERROR]! {}", instance_name, line);
         } else {
-        
-            if is_stderr {
-                log::warn!("!![ERROR]! {}", line);
-            } else {
-                log::info!("!![INFO]! {}", line);
-            }
+            tracing::info!("[{}] !![INFO]! {}", instance_name, line);
         }
     }
     
@@ -532,10 +961,9 @@ impl LaunchManager {
                             if let Some(source_end) = after_thread[source_start..].find(']') {
                                 let source = &after_thread[source_start+1..source_start+source_end];
                                 let message = &after_thread[source_start+source_end+1..].trim_start();
-                                let message = if message.starts_with(':') {
-                                    message[1..].trim()
-                                } else {
-                                    message
+                                let message = match message.strip_prefix(':') {
+                                    Some(rest) => rest.trim(),
+                                    None => message,
                                 };
                                 (source.to_string(), message.to_string())
                             } else {
@@ -563,4 +991,4 @@ struct MinecraftLogEntry {
     level: String,
     source: String,
     message: String,
-} 
\ No newline at end of file
+} 
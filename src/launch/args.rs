@@ -0,0 +1,173 @@
+// Turns the argument templates of a version JSON into the command line the
+// game is started with. Nothing here touches the system: the machine and the
+// launcher's choices come in through `Environment` and the placeholder
+// values, so a version's arguments can be checked without launching Java.
+
+use regex::Regex;
+
+use crate::platform::Arch;
+use crate::version::{Argument, ArgumentValue, OsRule, Rule, VersionDetails};
+
+/// Enabled when a resolution is set, so `--width`/`--height` are passed.
+pub const CUSTOM_RESOLUTION: &str = "has_custom_resolution";
+/// Enabled for demo instances, so `--demo` is passed.
+pub const DEMO_USER: &str = "is_demo_user";
+
+// Used for versions whose JSON carries neither `arguments` nor
+// `minecraftArguments`.
+const DEFAULT_GAME_TEMPLATE: &str = "--username ${auth_player_name} --version ${version_name} \
+    --gameDir ${game_directory} --assetsDir ${assets_root} --assetIndex ${assets_index_name} \
+    --uuid ${auth_uuid} --accessToken ${auth_access_token} --userType ${user_type}";
+
+// Pre-1.13 versions declare no JVM arguments at all; these are what the
+// official launcher has always passed them.
+const LEGACY_JVM_TEMPLATE: &str = "-Djava.library.path=${natives_directory} -cp ${classpath}";
+
+/// What argument rules are checked against: the system the game runs on and
+/// the launcher features that are switched on for this launch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Environment {
+    /// `windows`, `osx` or `linux`, as Mojang names them.
+    pub os: String,
+    /// Matched against the regex in `os.version` rules; rules that check
+    /// the version never apply while it is unknown.
+    pub os_version: Option<String>,
+    pub arch: Arch,
+    pub features: Vec<String>,
+}
+
+impl Environment {
+    pub fn new(os: &str, arch: Arch) -> Self {
+        Self { os: os.to_string(), os_version: None, arch, features: Vec::new() }
+    }
+
+    pub fn current() -> Self {
+        Self {
+            os_version: crate::platform::os_version(),
+            ..Self::new(crate::platform::minecraft_os_name(), Arch::current())
+        }
+    }
+
+    pub fn with_feature(mut self, feature: &str) -> Self {
+        if !self.has_feature(feature) {
+            self.features.push(feature.to_string());
+        }
+        self
+    }
+
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|enabled| enabled == feature)
+    }
+
+    // Newer library entries name the architecture in the OS itself, e.g.
+    // `linux-arm64`; `arch` only ever says "x86", meaning a 32-bit JVM.
+    pub fn matches_os(&self, rule: &OsRule) -> bool {
+        let name_matches = rule.name.as_deref().is_none_or(|name| {
+            name == self.os
+                || self.arch.natives_suffix().is_some_and(|suffix| name == format!("{}-{}", self.os, suffix))
+        });
+        let version_matches = rule.version.as_deref().is_none_or(|pattern| {
+            let version = self.os_version.as_deref();
+            Regex::new(pattern).is_ok_and(|regex| version.is_some_and(|version| regex.is_match(version)))
+        });
+        let arch_matches = rule.arch.as_deref().is_none_or(|arch| Arch::parse(arch) == self.arch);
+        name_matches && version_matches && arch_matches
+    }
+
+    fn matches(&self, rule: &Rule) -> bool {
+        let os_matches = rule.os.as_ref().is_none_or(|os| self.matches_os(os));
+        let features_match = rule.features.iter().flatten()
+            .all(|(feature, wanted)| self.has_feature(feature) == *wanted);
+        os_matches && features_match
+    }
+}
+
+/// Evaluates a rule list the way the official launcher does: nothing is
+/// allowed until a rule says so, and the last rule that applies wins.
+pub fn rules_allow(rules: &[Rule], environment: &Environment) -> bool {
+    rules.iter()
+        .rev()
+        .find(|rule| environment.matches(rule))
+        .is_some_and(|rule| rule.action == "allow")
+}
+
+/// Replaces every `${name}` placeholder in `argument` in one left-to-right
+/// pass, so a value that itself contains `${...}` is passed on as it is.
+/// Placeholders without a value are left as they are.
+pub fn substitute(argument: &str, placeholders: &[(&str, String)]) -> String {
+    let mut result = String::with_capacity(argument.len());
+    let mut rest = argument;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let placeholder = &rest[start..=start + len];
+        match placeholders.iter().find(|(key, _)| *key == placeholder) {
+            Some((_, value)) => result.push_str(value),
+            None => result.push_str(placeholder),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Renders a structured `arguments.game` or `arguments.jvm` list, dropping
+/// the entries whose rules do not allow them.
+pub fn render(arguments: &[Argument], environment: &Environment, placeholders: &[(&str, String)]) -> Vec<String> {
+    let mut rendered = Vec::new();
+    for argument in arguments {
+        match argument {
+            Argument::String(value) => rendered.push(substitute(value, placeholders)),
+            Argument::Object { rules, value } if rules_allow(rules, environment) => match value {
+                ArgumentValue::String(value) => rendered.push(substitute(value, placeholders)),
+                ArgumentValue::Array(values) => rendered.extend(values.iter().map(|value| substitute(value, placeholders))),
+            },
+            Argument::Object { .. } => {}
+        }
+    }
+    rendered
+}
+
+/// Renders a flat `minecraftArguments` template. Values are substituted after
+/// splitting, so a player name or path with spaces stays one argument.
+pub fn render_legacy(template: &str, placeholders: &[(&str, String)]) -> Vec<String> {
+    template.split_whitespace().map(|argument| substitute(argument, placeholders)).collect()
+}
+
+/// The game arguments of `details`. Legacy templates have no rules, so the
+/// demo and resolution features are appended by hand for them.
+pub fn game_arguments(details: &VersionDetails, environment: &Environment, placeholders: &[(&str, String)]) -> Vec<String> {
+    if let Some(arguments) = &details.arguments {
+        return render(&arguments.game, environment, placeholders);
+    }
+    let template = details.minecraft_arguments.as_deref().unwrap_or(DEFAULT_GAME_TEMPLATE);
+    let mut rendered = render_legacy(template, placeholders);
+    if environment.has_feature(DEMO_USER) {
+        rendered.push("--demo".to_string());
+    }
+    if environment.has_feature(CUSTOM_RESOLUTION) {
+        rendered.extend(render_legacy("--width ${resolution_width} --height ${resolution_height}", placeholders));
+    }
+    rendered
+}
+
+/// The JVM arguments of `details`, ending with the classpath. The launcher's
+/// own memory and user arguments are not part of these.
+pub fn jvm_arguments(details: &VersionDetails, environment: &Environment, placeholders: &[(&str, String)]) -> Vec<String> {
+    match &details.arguments {
+        Some(arguments) => render(&arguments.jvm, environment, placeholders),
+        None => render_legacy(LEGACY_JVM_TEMPLATE, placeholders),
+    }
+}
+
+/// Placeholders still present after rendering, e.g. ones a new version
+/// introduced that the launcher does not fill yet.
+pub fn unresolved(arguments: &[String]) -> Vec<&str> {
+    arguments.iter()
+        .flat_map(|argument| argument.match_indices("${").map(move |(start, _)| &argument[start..]))
+        .filter_map(|rest| rest.find('}').map(|end| &rest[..=end]))
+        .collect()
+}
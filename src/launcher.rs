@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::app::App;
+use crate::crash::CrashReport;
+use crate::events::{LauncherEvent, Readiness};
+use crate::instance::Instance;
+use crate::launch::LaunchOptions;
+use crate::progress::ProgressTracker;
+use crate::version::MinecraftVersion;
+use crate::{Error, Result};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The launcher without a frontend: installs versions, manages instances and
+/// starts the game. A GUI, the command line or any other frontend drives the
+/// same managers the terminal UI does through this type, and listens to
+/// `events()` to show what happens.
+pub struct Launcher {
+    app: App,
+}
+
+impl Launcher {
+    /// Opens the launcher's data directory, loading settings, accounts and
+    /// instances.
+    pub async fn new() -> Result<Self> {
+        Ok(Self { app: App::new().await? })
+    }
+
+    /// Everything the launcher reports while working. Each call returns a
+    /// new subscription that sees events from that point on.
+    pub fn events(&self) -> broadcast::Receiver<LauncherEvent> {
+        self.app.notifier.subscribe()
+    }
+
+    /// Background work still running, with its progress.
+    pub fn progress(&self) -> &ProgressTracker {
+        self.app.tasks.progress()
+    }
+
+    /// Fetches the version manifest, falling back to the cached copy when
+    /// offline, and returns every known version, newest first.
+    pub async fn list_versions(&mut self) -> Result<Vec<MinecraftVersion>> {
+        self.app.start_version_load(false);
+        self.wait_for_tasks(|_| {}).await;
+        if let Readiness::Failed(e) = &self.app.versions_ready {
+            return Err(Error::Version(e.clone()));
+        }
+        Ok(self.app.version_manager.get_versions().to_vec())
+    }
+
+    pub fn is_version_installed(&self, version_id: &str) -> bool {
+        self.app.version_manager.is_version_installed(version_id)
+    }
+
+    /// Downloads the client, libraries and assets of a version. Returns once
+    /// the download has finished; `on_progress` is called as it goes.
+    pub async fn install_version(&mut self, version_id: &str, on_progress: impl FnMut(&ProgressTracker)) -> Result<()> {
+        if self.is_version_installed(version_id) {
+            return Ok(());
+        }
+        if self.app.version_manager.get_versions().is_empty() {
+            self.list_versions().await?;
+        }
+        self.app.start_version_download(version_id)?;
+        self.wait_for_tasks(on_progress).await;
+        if !self.is_version_installed(version_id) {
+            return Err(Error::Version(self.app.current_state.clone()));
+        }
+        Ok(())
+    }
+
+    pub fn instances(&self) -> Vec<&Instance> {
+        self.app.get_instances()
+    }
+
+    /// Looks an instance up by id or name.
+    pub fn find_instance(&self, selector: &str) -> Option<&Instance> {
+        self.app.find_instance(selector)
+    }
+
+    pub fn create_instance(&mut self, name: String, version: String) -> Result<Uuid> {
+        self.app.create_instance(name, version)
+    }
+
+    /// Starts the game and returns once it is running. Without an account in
+    /// `options` the instance's last account, or the default one, is used.
+    pub async fn launch(&mut self, instance_id: Uuid, mut options: LaunchOptions) -> Result<()> {
+        let instance = self.app.instance_manager.get_instance(instance_id)
+            .ok_or_else(|| Error::Instance(format!("Instance {} not found", instance_id)))?;
+        if options.account.is_none() {
+            options.account = self.app.launch_account_for(instance).map(|account| account.id);
+        }
+        if self.app.java_ready.is_pending() {
+            self.app.start_java_scan();
+            self.wait_for_tasks(|_| {}).await;
+        }
+
+        self.app.launch_instance_with(instance_id, options)?;
+        if self.app.command_approval.take().is_some() {
+            return Err(Error::Launch(
+                "Instance has unreviewed pack commands; approve them or launch with skip_commands".to_string(),
+            ));
+        }
+        if self.app.launch_conflict.take().is_some() {
+            return Err(Error::Launch(
+                "Game directory is in use by a running instance; launch with allow_shared_directories".to_string(),
+            ));
+        }
+        self.wait_for_tasks(|_| {}).await;
+        if self.app.launch_manager.running_session_count(instance_id) == 0 {
+            return Err(Error::Launch(self.app.current_state.clone()));
+        }
+        Ok(())
+    }
+
+    /// Waits until every session of the instance has exited. Returns the
+    /// crash report when the game crashed.
+    pub async fn wait_for_exit(&mut self, instance_id: Uuid) -> Option<CrashReport> {
+        self.app.last_crash = None;
+        while self.app.launch_manager.running_session_count(instance_id) > 0 {
+            self.app.poll_game_exits();
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        self.app.poll_game_exits();
+        self.app.last_crash.take()
+    }
+
+    /// The latest status line, as the terminal UI would show it.
+    pub fn status(&self) -> &str {
+        &self.app.current_state
+    }
+
+    pub async fn wait_for_tasks(&mut self, mut on_progress: impl FnMut(&ProgressTracker)) {
+        loop {
+            self.app.process_events();
+            if self.app.tasks.is_empty() {
+                break;
+            }
+            on_progress(self.app.tasks.progress());
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// The application state behind the facade, for everything it does not
+    /// cover yet.
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+}
@@ -1,28 +1,92 @@
 pub mod error;
+pub mod i18n;
 pub mod utils;
+pub mod schema;
 pub mod platform;
 pub mod settings;
 pub mod java;
+pub mod jvm_lint;
 pub mod network;
 pub mod assets;
 pub mod auth;
+pub mod credentials;
+pub mod skins;
 pub mod instance;
+pub mod import;
+pub mod official;
+pub mod backup;
 pub mod profile;
 pub mod launch;
+pub mod quickplay;
 pub mod mods;
+pub mod packs;
+pub mod mrpack;
+pub mod modpack;
+pub mod modrinth;
+pub mod curseforge;
+pub mod github;
+pub mod updater;
 pub mod version;
+pub mod libraries;
+pub mod loaders;
+pub mod shared;
 pub mod progress;
+pub mod events;
+pub mod tasks;
 pub mod logs;
+pub mod logging;
+pub mod status;
+pub mod news;
+pub mod motd;
+pub mod storage;
+pub mod crash;
+pub mod shortcut;
+pub mod watch;
+pub mod bisect;
+pub mod verify;
+pub mod wizard;
+pub mod cli;
 pub mod app;
+pub mod launcher;
+#[cfg(feature = "tui")]
 pub mod ui;
 
 pub use error::{Error, Result};
-use crate::app::App;
+pub use launcher::Launcher;
 
 pub const VERSION: &str = "2.0.0";
 
-pub async fn run() -> Result<()> {
-    let mut app = App::new().await?;
+pub async fn run(cli: cli::Cli) -> Result<()> {
+    if let Some(dir) = &cli.data_dir {
+        utils::set_data_dir(dir, utils::DataDirSource::Flag)?;
+    } else if cli.portable {
+        utils::enable_portable()?;
+    }
+
+    if let Some(command) = cli.command {
+        return cli::execute(command).await;
+    }
+
+    run_tui().await
+}
+
+#[cfg(feature = "tui")]
+async fn run_tui() -> Result<()> {
+    let mut app = app::App::new().await?;
     app.init().await?;
-    ui::run_ui(app).await
-} 
\ No newline at end of file
+    ui::run_ui(app).await?;
+    // The new process picks the data directory again, so a folder chosen
+    // during setup is opened without the user starting the launcher twice.
+    if utils::restart_requested() {
+        let status = std::process::Command::new(std::env::current_exe()?)
+            .args(std::env::args_os().skip(1))
+            .status()?;
+        std::process::exit(status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "tui"))]
+async fn run_tui() -> Result<()> {
+    Err(Error::Other("Built without the terminal interface; run a subcommand instead".to_string()))
+}
@@ -0,0 +1,212 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use futures_util::stream::{self, StreamExt};
+
+use crate::network::NetworkManager;
+use crate::shared::SharedSources;
+use crate::tasks::{TaskReporter, TaskStage};
+use crate::utils::file_sha1;
+use crate::version::Artifact;
+
+/// The `versions/libraries` directory shared by every installed version.
+/// Installs running at the same time go through one store, so a library
+/// several versions need is downloaded once and counted once.
+#[derive(Clone)]
+pub struct LibraryStore {
+    inner: Arc<StoreInner>,
+}
+
+struct StoreInner {
+    root: PathBuf,
+    locks: Mutex<HashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>,
+    verified: Mutex<Verified>,
+    progress: Mutex<CombinedProgress>,
+    shared: Mutex<SharedSources>,
+}
+
+// Files whose SHA-1 has been checked since the launcher started, so a
+// library shared by several versions is hashed once per session.
+#[derive(Default)]
+struct Verified {
+    by_path: HashMap<PathBuf, String>,
+    by_hash: HashMap<String, PathBuf>,
+}
+
+// Progress over every batch in flight. Paths are counted once no matter how
+// many versions asked for them.
+#[derive(Default)]
+struct CombinedProgress {
+    batches: usize,
+    requested: HashSet<PathBuf>,
+    finished: HashSet<PathBuf>,
+}
+
+impl CombinedProgress {
+    fn counts(&self) -> (u64, u64) {
+        (self.finished.len() as u64, self.requested.len() as u64)
+    }
+}
+
+struct Batch<'a> {
+    store: &'a LibraryStore,
+}
+
+impl Drop for Batch<'_> {
+    fn drop(&mut self) {
+        let mut progress = self.store.inner.progress.lock().unwrap();
+        progress.batches -= 1;
+        if progress.batches == 0 {
+            *progress = CombinedProgress::default();
+        }
+    }
+}
+
+impl LibraryStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            inner: Arc::new(StoreInner {
+                root,
+                locks: Mutex::new(HashMap::new()),
+                verified: Mutex::new(Verified::default()),
+                progress: Mutex::new(CombinedProgress::default()),
+                shared: Mutex::new(SharedSources::default()),
+            }),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.inner.root
+    }
+
+    pub fn set_shared(&self, shared: SharedSources) {
+        *self.inner.shared.lock().unwrap() = shared;
+    }
+
+    pub fn path_of(&self, artifact: &Artifact) -> PathBuf {
+        self.inner.root.join(&artifact.path)
+    }
+
+    // Makes sure every artifact is in the store with the right hash. The
+    // reported progress covers all installs currently using the store.
+    pub async fn install(
+        &self,
+        artifacts: &[&Artifact],
+        network: &NetworkManager,
+        progress: &TaskReporter,
+    ) -> Result<()> {
+        std::fs::create_dir_all(&self.inner.root)?;
+        let mut seen = HashSet::new();
+        let artifacts: Vec<Artifact> = artifacts
+            .iter()
+            .filter(|artifact| seen.insert(artifact.path.clone()))
+            .map(|artifact| (*artifact).clone())
+            .collect();
+
+        let _batch = self.begin_batch(&artifacts);
+        let (done, total) = self.inner.progress.lock().unwrap().counts();
+        progress.report(TaskStage::Libraries, done, total);
+
+        let mut downloads = stream::iter(artifacts)
+            .map(|artifact| async move {
+                self.ensure(&artifact, network).await.map(|_| self.path_of(&artifact))
+            })
+            .buffer_unordered(network.get_max_concurrent_downloads().max(1));
+
+        while let Some(result) = downloads.next().await {
+            let path = result?;
+            let (done, total) = {
+                let mut combined = self.inner.progress.lock().unwrap();
+                combined.requested.insert(path.clone());
+                combined.finished.insert(path);
+                combined.counts()
+            };
+            progress.report(TaskStage::Libraries, done, total);
+        }
+        Ok(())
+    }
+
+    // Only one task works on a given path at a time. Whoever gets the lock
+    // second finds the file already verified and returns straight away.
+    pub async fn ensure(&self, artifact: &Artifact, network: &NetworkManager) -> Result<()> {
+        let path = self.path_of(artifact);
+        let lock = self.lock_for(&path);
+        let _guard = lock.lock().await;
+
+        if self.is_verified(&path, &artifact.sha1) {
+            return Ok(());
+        }
+        if path.is_file() && file_sha1(&path).await? == artifact.sha1 {
+            self.mark_verified(path, &artifact.sha1);
+            return Ok(());
+        }
+        if let Some(source) = self.verified_with_hash(&artifact.sha1) {
+            if link_or_copy(&source, &path).is_ok() {
+                self.mark_verified(path, &artifact.sha1);
+                return Ok(());
+            }
+        }
+        let shared = self.inner.shared.lock().unwrap().clone();
+        if let Some(source) = shared.library(&artifact.path, &artifact.sha1).await {
+            if crate::shared::link_shared(&source, &path).is_ok() {
+                self.mark_verified(path, &artifact.sha1);
+                return Ok(());
+            }
+        }
+
+        network.download_file(&artifact.url, &path, Some(&artifact.sha1), None).await?;
+        self.mark_verified(path, &artifact.sha1);
+        Ok(())
+    }
+
+    fn begin_batch(&self, artifacts: &[Artifact]) -> Batch<'_> {
+        let mut progress = self.inner.progress.lock().unwrap();
+        progress.batches += 1;
+        progress.requested.extend(artifacts.iter().map(|artifact| self.path_of(artifact)));
+        Batch { store: self }
+    }
+
+    fn lock_for(&self, path: &Path) -> Arc<tokio::sync::Mutex<()>> {
+        self.inner.locks
+            .lock()
+            .unwrap()
+            .entry(path.to_path_buf())
+            .or_default()
+            .clone()
+    }
+
+    fn is_verified(&self, path: &Path, sha1: &str) -> bool {
+        self.inner.verified.lock().unwrap().by_path.get(path).is_some_and(|hash| hash == sha1)
+            && path.is_file()
+    }
+
+    fn verified_with_hash(&self, sha1: &str) -> Option<PathBuf> {
+        self.inner.verified
+            .lock()
+            .unwrap()
+            .by_hash
+            .get(sha1)
+            .filter(|path| path.is_file())
+            .cloned()
+    }
+
+    fn mark_verified(&self, path: PathBuf, sha1: &str) {
+        let mut verified = self.inner.verified.lock().unwrap();
+        verified.by_hash.entry(sha1.to_string()).or_insert_with(|| path.clone());
+        verified.by_path.insert(path, sha1.to_string());
+    }
+}
+
+// The same jar published under two coordinates only needs to be fetched
+// once; a hard link keeps it from taking the space twice.
+pub(crate) fn link_or_copy(source: &Path, target: &Path) -> std::io::Result<()> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if target.exists() {
+        std::fs::remove_file(target)?;
+    }
+    std::fs::hard_link(source, target).or_else(|_| std::fs::copy(source, target).map(|_| ()))
+}
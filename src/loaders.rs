@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::i18n::trf;
+use crate::instance::ModLoader;
+use crate::network::NetworkManager;
+use crate::Result;
+
+const FABRIC_LOADERS_URL: &str = "https://meta.fabricmc.net/v2/versions/loader";
+const QUILT_LOADERS_URL: &str = "https://meta.quiltmc.org/v3/versions/loader";
+const FORGE_VERSIONS_URL: &str = "https://files.minecraftforge.net/net/minecraftforge/forge/maven-metadata.json";
+const FORGE_PROMOTIONS_URL: &str = "https://files.minecraftforge.net/net/minecraftforge/forge/promotions_slim.json";
+const NEOFORGE_VERSIONS_URL: &str = "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged";
+
+/// A loader build that can run on one Minecraft version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoaderVersion {
+    pub id: String,
+    /// Betas and pre-releases are not.
+    pub stable: bool,
+    /// The build the loader's authors point users to, where they name one.
+    pub recommended: bool,
+}
+
+/// Loader builds for `loader` on Minecraft `game_version`, newest first, so
+/// the first entry is the latest. Loaders that do not name a recommended
+/// build get their newest stable one marked instead.
+pub async fn fetch_versions(network: &NetworkManager, loader: &ModLoader, game_version: &str) -> Result<Vec<LoaderVersion>> {
+    let mut versions = match loader {
+        ModLoader::Fabric => fabric_like(network, FABRIC_LOADERS_URL, game_version).await?,
+        ModLoader::Quilt => fabric_like(network, QUILT_LOADERS_URL, game_version).await?,
+        ModLoader::Forge => forge(network, game_version).await?,
+        ModLoader::NeoForge => neoforge(network, game_version).await?,
+    };
+    if !versions.iter().any(|version| version.recommended) {
+        if let Some(version) = versions.iter_mut().find(|version| version.stable) {
+            version.recommended = true;
+        }
+    }
+    Ok(versions)
+}
+
+#[derive(Debug, Deserialize)]
+struct MetaEntry {
+    loader: MetaLoader,
+}
+
+// Quilt's meta has no `stable` flag; its betas carry a suffix instead.
+#[derive(Debug, Deserialize)]
+struct MetaLoader {
+    version: String,
+    stable: Option<bool>,
+}
+
+async fn fabric_like(network: &NetworkManager, base: &str, game_version: &str) -> Result<Vec<LoaderVersion>> {
+    let entries: Vec<MetaEntry> = network.get_json(&format!("{}/{}", base, game_version)).await?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| LoaderVersion {
+            stable: entry.loader.stable.unwrap_or(!entry.loader.version.contains('-')),
+            id: entry.loader.version,
+            recommended: false,
+        })
+        .collect())
+}
+
+#[derive(Debug, Deserialize)]
+struct ForgePromotions {
+    promos: HashMap<String, String>,
+}
+
+// The version list is keyed by Minecraft version and holds full maven
+// versions like `1.20.1-47.2.0`, oldest first.
+async fn forge(network: &NetworkManager, game_version: &str) -> Result<Vec<LoaderVersion>> {
+    let mut all: HashMap<String, Vec<String>> = network.get_json(FORGE_VERSIONS_URL).await?;
+    let recommended = match network.get_json::<ForgePromotions>(FORGE_PROMOTIONS_URL).await {
+        Ok(mut promotions) => promotions.promos.remove(&format!("{}-recommended", game_version)),
+        Err(e) => {
+            tracing::warn!("{}", trf("log.forge_promotions_failed", &[("error", &e)]));
+            None
+        }
+    };
+    let prefix = format!("{}-", game_version);
+    Ok(all
+        .remove(game_version)
+        .unwrap_or_default()
+        .into_iter()
+        .rev()
+        .map(|full| {
+            let id = full.strip_prefix(&prefix).unwrap_or(&full).to_string();
+            LoaderVersion { recommended: recommended.as_ref() == Some(&id), stable: true, id }
+        })
+        .collect())
+}
+
+#[derive(Debug, Deserialize)]
+struct MavenVersions {
+    versions: Vec<String>,
+}
+
+// NeoForge numbers builds after the Minecraft version without its leading
+// `1.`: 21.1.x is for 1.21.1, 21.0.x for 1.21. Builds for 1.20.1 were
+// published under the old `forge` artifact with full version names.
+async fn neoforge(network: &NetworkManager, game_version: &str) -> Result<Vec<LoaderVersion>> {
+    let (artifact, prefix) = if game_version == "1.20.1" {
+        ("forge", format!("{}-", game_version))
+    } else {
+        ("neoforge", neoforge_prefix(game_version))
+    };
+    let maven: MavenVersions = network.get_json(&format!("{}/{}", NEOFORGE_VERSIONS_URL, artifact)).await?;
+    Ok(maven
+        .versions
+        .into_iter()
+        .rev()
+        .filter(|version| version.starts_with(&prefix))
+        .map(|version| LoaderVersion {
+            stable: !version.contains("beta") && !version.contains("alpha"),
+            id: version,
+            recommended: false,
+        })
+        .collect())
+}
+
+fn neoforge_prefix(game_version: &str) -> String {
+    match game_version.strip_prefix("1.") {
+        Some(rest) => {
+            let mut parts = rest.split('.');
+            let major = parts.next().unwrap_or_default();
+            let minor = parts.next().unwrap_or("0");
+            format!("{}.{}.", major, minor)
+        }
+        None => format!("{}.", game_version),
+    }
+}
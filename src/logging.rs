@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+use std::fmt::{self, Write as _};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, OnceLock, RwLock};
+use std::time::Instant;
+
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+use crate::i18n::{tr, trf};
+use crate::logs::{LogLevel, LogManager};
+
+const CRATE_TARGET: &str = "mango_launcher";
+
+static FILTER: LazyLock<RwLock<LogFilter>> = LazyLock::new(|| RwLock::new(LogFilter::default()));
+static SINK: RwLock<Option<LogManager>> = RwLock::new(None);
+static INSTALLED: OnceLock<()> = OnceLock::new();
+
+/// Which events are kept, parsed from `advanced.log_level`: a default level
+/// and per-module overrides, e.g. `info,network=debug,launch=trace`. Modules
+/// of the launcher can be named without the `mango_launcher::` prefix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogFilter {
+    default: LevelFilter,
+    directives: Vec<(String, LevelFilter)>,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self { default: LevelFilter::INFO, directives: Vec::new() }
+    }
+}
+
+impl LogFilter {
+    pub fn parse(spec: &str) -> std::result::Result<Self, String> {
+        let mut filter = Self::default();
+        for part in spec.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+            match part.split_once('=') {
+                Some((target, level)) => {
+                    let level = parse_level(level)?;
+                    filter.directives.push((target.trim().to_string(), level));
+                }
+                None => filter.default = parse_level(part)?,
+            }
+        }
+        // The longest matching target wins, so more specific ones go first.
+        filter.directives.sort_by_key(|(target, _)| std::cmp::Reverse(target.len()));
+        Ok(filter)
+    }
+
+    pub fn enabled(&self, target: &str, level: &Level) -> bool {
+        let allowed = self.directives.iter()
+            .find(|(prefix, _)| matches_target(target, prefix))
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default);
+        allowed >= *level
+    }
+
+    fn max(&self) -> LevelFilter {
+        self.directives.iter().map(|(_, level)| *level).fold(self.default, Ord::max)
+    }
+}
+
+fn parse_level(level: &str) -> std::result::Result<LevelFilter, String> {
+    LevelFilter::from_str(level.trim()).map_err(|_| format!("Unknown log level '{}'", level.trim()))
+}
+
+fn matches_target(target: &str, prefix: &str) -> bool {
+    let within = |prefix: &str| {
+        target == prefix || target.strip_prefix(prefix).is_some_and(|rest| rest.starts_with("::"))
+    };
+    within(prefix) || within(&format!("{}::{}", CRATE_TARGET, prefix))
+}
+
+/// Sends tracing events from here on to `log_manager`, keeping those that
+/// pass `spec`. Can be called again for a new log manager; only the first
+/// call installs the subscriber.
+pub fn init(log_manager: LogManager, spec: &str) {
+    if let Ok(mut sink) = SINK.write() {
+        *sink = Some(log_manager);
+    }
+    set_filter(spec);
+    if INSTALLED.set(()).is_ok() && tracing::subscriber::set_global_default(LogSubscriber::default()).is_err() {
+        eprintln!("{}", tr("log.subscriber_exists"));
+    }
+}
+
+/// Applies a new `advanced.log_level`; an invalid one keeps the old filter.
+pub fn set_filter(spec: &str) {
+    match LogFilter::parse(spec) {
+        Ok(filter) => {
+            if let Ok(mut current) = FILTER.write() {
+                *current = filter;
+            }
+            tracing::callsite::rebuild_interest_cache();
+        }
+        Err(e) => tracing::warn!("{}", trf("log.filter_unchanged", &[("error", &e)])),
+    }
+}
+
+struct SpanData {
+    name: &'static str,
+    target: &'static str,
+    level: Level,
+    fields: String,
+    started: Instant,
+    refs: usize,
+}
+
+// Spans are only kept to time them: when the last handle to one closes, how
+// long it was open is logged at the span's own level.
+struct LogSubscriber {
+    next_id: AtomicU64,
+    spans: Mutex<HashMap<u64, SpanData>>,
+}
+
+impl Default for LogSubscriber {
+    fn default() -> Self {
+        Self { next_id: AtomicU64::new(1), spans: Mutex::new(HashMap::new()) }
+    }
+}
+
+impl Subscriber for LogSubscriber {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        FILTER.read().map(|filter| filter.enabled(metadata.target(), metadata.level())).unwrap_or(true)
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        FILTER.read().ok().map(|filter| filter.max())
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut fields = FieldVisitor::default();
+        span.record(&mut fields);
+        let metadata = span.metadata();
+        if let Ok(mut spans) = self.spans.lock() {
+            spans.insert(id, SpanData {
+                name: metadata.name(),
+                target: metadata.target(),
+                level: *metadata.level(),
+                fields: fields.rest,
+                started: Instant::now(),
+                refs: 1,
+            });
+        }
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut fields = FieldVisitor::default();
+        values.record(&mut fields);
+        if let Ok(mut spans) = self.spans.lock() {
+            if let Some(data) = spans.get_mut(&span.into_u64()) {
+                data.fields.push_str(&fields.rest);
+            }
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = FieldVisitor::default();
+        event.record(&mut fields);
+        let level = if fields.launcher {
+            LogLevel::Launcher
+        } else {
+            log_level(event.metadata().level())
+        };
+        let source = fields.source.unwrap_or_else(|| short_target(event.metadata().target()));
+        send(level, format!("{}{}", fields.message, fields.rest), source);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+
+    fn clone_span(&self, id: &Id) -> Id {
+        if let Ok(mut spans) = self.spans.lock() {
+            if let Some(data) = spans.get_mut(&id.into_u64()) {
+                data.refs += 1;
+            }
+        }
+        id.clone()
+    }
+
+    fn try_close(&self, id: Id) -> bool {
+        let closed = match self.spans.lock() {
+            Ok(mut spans) => match spans.get_mut(&id.into_u64()) {
+                Some(data) if data.refs > 1 => {
+                    data.refs -= 1;
+                    None
+                }
+                Some(_) => spans.remove(&id.into_u64()),
+                None => None,
+            },
+            Err(_) => None,
+        };
+        match closed {
+            Some(data) => {
+                let message = format!("{}{} — {:.1?}", data.name, data.fields, data.started.elapsed());
+                send(log_level(&data.level), message, short_target(data.target));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn send(level: LogLevel, message: String, source: String) {
+    if let Ok(sink) = SINK.read() {
+        if let Some(log_manager) = sink.as_ref() {
+            log_manager.log(level, message, Some(source));
+        }
+    }
+}
+
+fn log_level(level: &Level) -> LogLevel {
+    match *level {
+        Level::ERROR => LogLevel::Error,
+        Level::WARN => LogLevel::Warning,
+        Level::INFO => LogLevel::Info,
+        _ => LogLevel::Debug,
+    }
+}
+
+// `mango_launcher::network` is shown as `network`; other crates in full.
+fn short_target(target: &str) -> String {
+    target.strip_prefix(CRATE_TARGET)
+        .and_then(|rest| rest.strip_prefix("::"))
+        .unwrap_or(target)
+        .to_string()
+}
+
+// `message` is the text, `source` overrides the module shown next to it and
+// `launcher = true` marks launcher lifecycle messages. Other fields are
+// appended as `key=value`.
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    source: Option<String>,
+    launcher: bool,
+    rest: String,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "source" => self.source = Some(value.to_string()),
+            "message" => self.message.push_str(value),
+            name => {
+                let _ = write!(self.rest, " {}={}", name, value);
+            }
+        }
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        match field.name() {
+            "launcher" => self.launcher = value,
+            name => {
+                let _ = write!(self.rest, " {}={}", name, value);
+            }
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => {
+                let _ = write!(self.message, "{:?}", value);
+            }
+            name => {
+                let _ = write!(self.rest, " {}={:?}", name, value);
+            }
+        }
+    }
+}
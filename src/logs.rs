@@ -2,10 +2,38 @@ use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 use std::path::PathBuf;
 use std::fs::{self, File, OpenOptions};
-use std::io::Write;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use chrono::{DateTime, Local, Duration};
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::i18n::trf;
+use crate::events::LauncherEvent;
+
+const DEFAULT_MAX_BYTES: usize = 64 * 1024 * 1024;
+const MAX_MESSAGE_BYTES: usize = 8 * 1024;
+const LOG_PREFIX: &str = "mango-launcher-";
+
+/// How launcher log files are rotated and how long old ones are kept.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogRotation {
+    pub max_file_bytes: u64,
+    pub retention_hours: u32,
+    pub compress: bool,
+}
+
+impl Default for LogRotation {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: 10 * 1024 * 1024,
+            retention_hours: 24,
+            compress: false,
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LogLevel {
@@ -26,16 +54,6 @@ impl LogLevel {
             LogLevel::Launcher => "LAUNCHER",
         }
     }
-
-    pub fn color(&self) -> ratatui::style::Color {
-        match self {
-            LogLevel::Info => ratatui::style::Color::White,
-            LogLevel::Warning => ratatui::style::Color::Yellow,
-            LogLevel::Error => ratatui::style::Color::Red,
-            LogLevel::Debug => ratatui::style::Color::Gray,
-            LogLevel::Launcher => ratatui::style::Color::Cyan,
-        }
-    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +74,32 @@ impl LogEntry {
         }
     }
 
+    // Game output is logged with the instance name as source, optionally
+    // followed by "/thread".
+    pub fn is_from_instance(&self, name: &str) -> bool {
+        self.source.as_deref().is_some_and(|source| {
+            source.strip_prefix(name).is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+
+    // Approximate heap footprint, used for the in-memory buffer cap.
+    fn size(&self) -> usize {
+        std::mem::size_of::<LogEntry>() + self.message.len() + self.source.as_ref().map_or(0, String::len)
+    }
+
+    fn truncate(&mut self) {
+        if self.message.len() <= MAX_MESSAGE_BYTES {
+            return;
+        }
+        let mut end = MAX_MESSAGE_BYTES;
+        while !self.message.is_char_boundary(end) {
+            end -= 1;
+        }
+        let dropped = self.message.len() - end;
+        self.message.truncate(end);
+        self.message.push_str(&format!("… (+{} bytes)", dropped));
+    }
+
     pub fn format(&self) -> String {
         let time_str = self.timestamp.format("%H:%M:%S").to_string();
         let source_str = self.source.as_ref()
@@ -75,27 +119,38 @@ impl LogEntry {
 pub struct LogManager {
     entries: Arc<Mutex<VecDeque<LogEntry>>>,
     max_entries: usize,
+    max_bytes: Arc<AtomicUsize>,
+    used_bytes: Arc<AtomicUsize>,
     log_dir: Arc<Mutex<Option<PathBuf>>>,
     current_log_file: Arc<Mutex<Option<(PathBuf, File)>>>,
+    current_file_bytes: Arc<AtomicUsize>,
+    rotation: Arc<Mutex<LogRotation>>,
     file_logging_enabled: Arc<AtomicBool>,
 }
 
 impl LogManager {
     pub fn new(max_entries: usize) -> Self {
         Self {
-            entries: Arc::new(Mutex::new(VecDeque::with_capacity(max_entries))),
+            entries: Arc::new(Mutex::new(VecDeque::with_capacity(max_entries.min(4096)))),
             max_entries,
+            max_bytes: Arc::new(AtomicUsize::new(DEFAULT_MAX_BYTES)),
+            used_bytes: Arc::new(AtomicUsize::new(0)),
             log_dir: Arc::new(Mutex::new(None)),
             current_log_file: Arc::new(Mutex::new(None)),
+            current_file_bytes: Arc::new(AtomicUsize::new(0)),
+            rotation: Arc::new(Mutex::new(LogRotation::default())),
             file_logging_enabled: Arc::new(AtomicBool::new(false)),
         }
     }
 
-    pub fn with_file_logging(max_entries: usize, log_dir: PathBuf, enabled: bool) -> Self {
+    pub fn with_file_logging(max_entries: usize, log_dir: PathBuf, enabled: bool, rotation: LogRotation) -> Self {
         let manager = Self::new(max_entries);
         if let Ok(mut dir) = manager.log_dir.lock() {
             *dir = Some(log_dir);
         }
+        if let Ok(mut current) = manager.rotation.lock() {
+            *current = rotation;
+        }
         manager.file_logging_enabled.store(enabled, Ordering::Relaxed);
         if enabled {
             manager.ensure_log_file();
@@ -122,6 +177,19 @@ impl LogManager {
         }
     }
 
+    pub fn set_rotation(&self, rotation: LogRotation) {
+        if let Ok(mut current) = self.rotation.lock() {
+            *current = rotation;
+        }
+        if self.file_logging_enabled.load(Ordering::Relaxed) {
+            self.cleanup_old_logs();
+        }
+    }
+
+    fn rotation(&self) -> LogRotation {
+        self.rotation.lock().map(|r| *r).unwrap_or_default()
+    }
+
     fn ensure_log_file(&self) {
         if !self.file_logging_enabled.load(Ordering::Relaxed) {
             return;
@@ -137,13 +205,19 @@ impl LogManager {
             return;
         };
         
-        if let Err(_) = fs::create_dir_all(&log_dir) {
+        if fs::create_dir_all(&log_dir).is_err() {
             return;
         }
 
         let now = Local::now();
-        let log_filename = format!("mango-launcher-{}.log", now.format("%Y-%m-%d_%H-%M-%S"));
-        let log_path = log_dir.join(log_filename);
+        let stamp = now.format("%Y-%m-%d_%H-%M-%S").to_string();
+        let mut log_path = log_dir.join(format!("{}{}.log", LOG_PREFIX, stamp));
+        // A rotation within the same second must not append to the file it just closed.
+        let mut part = 1;
+        while log_path.exists() || log_path.with_extension("log.gz").exists() {
+            part += 1;
+            log_path = log_dir.join(format!("{}{}-{}.log", LOG_PREFIX, stamp, part));
+        }
 
         if let Ok(file) = OpenOptions::new()
             .create(true)
@@ -152,10 +226,31 @@ impl LogManager {
         {
             if let Ok(mut current_file) = self.current_log_file.lock() {
                 *current_file = Some((log_path, file));
+                self.current_file_bytes.store(0, Ordering::Relaxed);
             }
         }
     }
 
+    // Closes the current file. The closed file is compressed on a background
+    // thread so logging never waits on gzip. Old logs are cleaned up by the
+    // caller once it has let go of `current_log_file`, which the cleanup
+    // locks to skip the file in use.
+    fn rotate(&self, current_file: &mut Option<(PathBuf, File)>) {
+        let Some((path, file)) = current_file.take() else {
+            return;
+        };
+        drop(file);
+
+        let rotation = self.rotation();
+        if rotation.compress {
+            std::thread::spawn(move || {
+                if let Err(e) = compress_log(&path) {
+                    tracing::warn!("{}", trf("log.log_compress_failed", &[("path", &path.display()), ("error", &e)]));
+                }
+            });
+        }
+    }
+
     fn cleanup_old_logs(&self) {
         let log_dir = if let Ok(dir) = self.log_dir.lock() {
             if let Some(ref d) = *dir {
@@ -167,14 +262,14 @@ impl LogManager {
             return;
         };
         
-        let cutoff_time = Local::now() - Duration::hours(24);
+        let cutoff_time = Local::now() - Duration::hours(self.rotation().retention_hours as i64);
+        let current = self.current_log_file.lock().ok()
+            .and_then(|file| file.as_ref().map(|(path, _)| path.clone()));
         
         if let Ok(entries) = fs::read_dir(&log_dir) {
             for entry in entries.flatten() {
                 let path = entry.path();
-                if path.is_file() && 
-                   path.extension().and_then(|s| s.to_str()) == Some("log") &&
-                   path.file_stem().and_then(|s| s.to_str()).unwrap_or("").starts_with("mango-launcher-") {
+                if path.is_file() && is_launcher_log(&path) && current.as_ref() != Some(&path) {
                     
                     if let Ok(metadata) = entry.metadata() {
                         if let Ok(modified) = metadata.modified() {
@@ -201,28 +296,102 @@ impl LogManager {
                 current_file = self.current_log_file.lock().unwrap();
             }
 
+            let formatted = format!("{}\n", entry.format());
+            let written = self.current_file_bytes.load(Ordering::Relaxed);
+            let limit = self.rotation().max_file_bytes as usize;
+            if written > 0 && written + formatted.len() > limit {
+                self.rotate(&mut current_file);
+                drop(current_file);
+                self.ensure_log_file();
+                self.cleanup_old_logs();
+                current_file = self.current_log_file.lock().unwrap();
+            }
+
             if let Some((_, ref mut file)) = *current_file {
-                let formatted = format!("{}\n", entry.format());
-                let _ = file.write_all(formatted.as_bytes());
+                if file.write_all(formatted.as_bytes()).is_ok() {
+                    self.current_file_bytes.fetch_add(formatted.len(), Ordering::Relaxed);
+                }
                 let _ = file.flush();
             }
         }
     }
 
     pub fn log(&self, level: LogLevel, message: String, source: Option<String>) {
-        let entry = LogEntry::new(level, message, source);
+        let mut entry = LogEntry::new(level, message, source);
         
         self.write_to_file(&entry);
+        entry.truncate();
         
         if let Ok(mut entries) = self.entries.lock() {
+            self.used_bytes.fetch_add(entry.size(), Ordering::Relaxed);
+            entries.push_back(entry);
+            self.trim(&mut entries);
+        }
+    }
+
+    /// Records a panic from inside the panic hook. The panicking thread may
+    /// hold the log's locks, so nothing here waits on them. Returns the file
+    /// the panic went to, if one was open.
+    pub fn log_panic(&self, message: String) -> Option<PathBuf> {
+        let mut entry = LogEntry::new(LogLevel::Error, message, Some("Panic".to_string()));
+        let mut written = None;
+        if let Ok(mut current_file) = self.current_log_file.try_lock() {
+            if let Some((path, file)) = current_file.as_mut() {
+                if file.write_all(format!("{}\n", entry.format()).as_bytes()).is_ok() && file.flush().is_ok() {
+                    written = Some(path.clone());
+                }
+            }
+        }
+        entry.truncate();
+        if let Ok(mut entries) = self.entries.try_lock() {
+            self.used_bytes.fetch_add(entry.size(), Ordering::Relaxed);
             entries.push_back(entry);
-            
-            if entries.len() > self.max_entries {
-                entries.pop_front();
+            self.trim(&mut entries);
+        }
+        written
+    }
+
+    fn trim(&self, entries: &mut VecDeque<LogEntry>) {
+        let max_bytes = self.max_bytes.load(Ordering::Relaxed);
+        while entries.len() > 1
+            && (entries.len() > self.max_entries || self.used_bytes.load(Ordering::Relaxed) > max_bytes)
+        {
+            if let Some(removed) = entries.pop_front() {
+                self.used_bytes.fetch_sub(removed.size(), Ordering::Relaxed);
             }
         }
     }
 
+    pub fn set_max_bytes(&self, max_bytes: usize) {
+        self.max_bytes.store(max_bytes, Ordering::Relaxed);
+        if let Ok(mut entries) = self.entries.lock() {
+            self.trim(&mut entries);
+        }
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        self.used_bytes.load(Ordering::Relaxed)
+    }
+
+    // Logs launcher events until every notifier is dropped.
+    pub fn follow(&self, mut events: broadcast::Receiver<LauncherEvent>) {
+        let log_manager = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        let (level, message, source) = event.log_entry();
+                        log_manager.log(level, message, Some(source.to_string()));
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        log_manager.warning(trf("log.events_skipped", &[("count", &skipped)]), None);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
     pub fn info(&self, message: String, source: Option<String>) {
         self.log(LogLevel::Info, message, source);
     }
@@ -269,6 +438,7 @@ impl LogManager {
     pub fn clear(&self) {
         if let Ok(mut entries) = self.entries.lock() {
             entries.clear();
+            self.used_bytes.store(0, Ordering::Relaxed);
         }
     }
 
@@ -298,7 +468,7 @@ impl LogManager {
             entries.iter()
                 .filter(|entry| {
                     entry.message.to_lowercase().contains(&query_lower) ||
-                    entry.source.as_ref().map_or(false, |s| s.to_lowercase().contains(&query_lower))
+                    entry.source.as_ref().is_some_and(|s| s.to_lowercase().contains(&query_lower))
                 })
                 .cloned()
                 .collect()
@@ -308,6 +478,76 @@ impl LogManager {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct LogView {
+    pub level: Option<LogLevel>,
+    // Only lines from this game instance, matched by log source.
+    pub instance: Option<String>,
+    pub query: String,
+    pub editing: bool,
+    pub follow: bool,
+}
+
+impl Default for LogView {
+    fn default() -> Self {
+        Self {
+            level: None,
+            instance: None,
+            query: String::new(),
+            editing: false,
+            follow: true,
+        }
+    }
+}
+
+impl LogView {
+    pub fn cycle_level(&mut self) {
+        self.level = match self.level {
+            None => Some(LogLevel::Info),
+            Some(LogLevel::Info) => Some(LogLevel::Warning),
+            Some(LogLevel::Warning) => Some(LogLevel::Error),
+            Some(LogLevel::Error) => Some(LogLevel::Debug),
+            Some(_) => None,
+        };
+    }
+
+    pub fn entries(&self, manager: &LogManager) -> Vec<LogEntry> {
+        let entries = if self.query.trim().is_empty() {
+            manager.get_entries()
+        } else {
+            manager.search(self.query.trim())
+        };
+        entries
+            .into_iter()
+            .filter(|entry| self.level.as_ref().is_none_or(|level| entry.level == *level))
+            .filter(|entry| self.instance.as_deref().is_none_or(|name| entry.is_from_instance(name)))
+            .collect()
+    }
+
+    // Cycles through `names`, then back to every source.
+    pub fn cycle_instance(&mut self, names: &[String]) {
+        let next = match self.instance.as_ref().and_then(|current| names.iter().position(|name| name == current)) {
+            Some(index) => names.get(index + 1),
+            None => names.first(),
+        };
+        self.instance = next.cloned();
+    }
+}
+
+fn is_launcher_log(path: &std::path::Path) -> bool {
+    let name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+    name.starts_with(LOG_PREFIX) && (name.ends_with(".log") || name.ends_with(".log.gz"))
+}
+
+fn compress_log(path: &std::path::Path) -> io::Result<()> {
+    let target = path.with_extension("log.gz");
+    let mut input = File::open(path)?;
+    let mut encoder = GzEncoder::new(File::create(&target)?, Compression::default());
+    io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?;
+    fs::remove_file(path)
+}
+
 impl Default for LogManager {
     fn default() -> Self {
         Self::new(1000)
@@ -1,6 +1,8 @@
+use clap::Parser;
+use mango_launcher::cli::Cli;
 use mango_launcher::Result;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    mango_launcher::run().await
-} 
\ No newline at end of file
+    mango_launcher::run(Cli::parse()).await
+}
@@ -0,0 +1,263 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::import::{self, ImportKind, ImportPlan, ImportSource, PendingDownload};
+use crate::instance::{Instance, ModLoader};
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModpackFormat {
+    Modrinth,
+    CurseForge,
+}
+
+impl ModpackFormat {
+    pub fn from_import(kind: ImportKind) -> Option<Self> {
+        match kind {
+            ImportKind::Modrinth => Some(ModpackFormat::Modrinth),
+            ImportKind::CurseForge => Some(ModpackFormat::CurseForge),
+            ImportKind::Launcher | ImportKind::MultiMc | ImportKind::Official => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ModpackFormat::Modrinth => "Modrinth",
+            ModpackFormat::CurseForge => "CurseForge",
+        }
+    }
+}
+
+/// The pack an instance was imported from and what it put into the game
+/// directory, so a newer version of the same pack can be applied on top.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModpackSource {
+    pub format: ModpackFormat,
+    pub name: String,
+    pub version: Option<String>,
+    // Downloaded files by game-relative path, with the SHA-1 the pack declared.
+    #[serde(default)]
+    pub files: BTreeMap<String, Option<String>>,
+    // Override files as the pack shipped them. A file on disk with another
+    // hash has been edited by the user since.
+    #[serde(default)]
+    pub overrides: BTreeMap<String, String>,
+    // The Modrinth project or CurseForge mod the pack is published as,
+    // found by the archive's hash when it was imported.
+    #[serde(default)]
+    pub project_id: Option<String>,
+}
+
+impl ModpackSource {
+    pub fn from_plan(plan: &ImportPlan) -> Result<Option<Self>> {
+        let Some(format) = ModpackFormat::from_import(plan.kind) else { return Ok(None) };
+        Ok(Some(Self {
+            format,
+            name: plan.name.clone(),
+            version: plan.pack_version.clone(),
+            files: plan.pending_downloads
+                .iter()
+                .map(|download| (import::game_path(&download.path), download.sha1.clone()))
+                .collect(),
+            overrides: import::hash_game_files(&plan.game_files)?,
+            project_id: None,
+        }))
+    }
+
+    pub fn display_version(&self) -> &str {
+        self.version.as_deref().unwrap_or("?")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigConflict {
+    pub path: String,
+    pub keep_local: bool,
+}
+
+/// Everything that changes when an instance moves to a newer pack version.
+/// Built by `plan_update`, reviewed on the update screen, then applied.
+#[derive(Debug, Clone)]
+pub struct ModpackUpdate {
+    pub instance_id: Uuid,
+    pub archive: PathBuf,
+    pub from_version: Option<String>,
+    pub source: ModpackSource,
+    pub minecraft_version: String,
+    pub mod_loader: Option<ModLoader>,
+    pub mod_loader_version: Option<String>,
+    pub downloads: Vec<PendingDownload>,
+    pub updated: usize,
+    pub removed: Vec<String>,
+    pub overrides: Vec<String>,
+    pub dropped_overrides: Vec<String>,
+    pub conflicts: Vec<ConfigConflict>,
+    /// Opened by importing the pack rather than by asking for an update.
+    pub from_import: bool,
+    game_files: Vec<ImportSource>,
+}
+
+impl ModpackUpdate {
+    pub fn added(&self) -> usize {
+        self.downloads.len() - self.updated
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.downloads.is_empty()
+            && self.removed.is_empty()
+            && self.overrides.is_empty()
+            && self.dropped_overrides.is_empty()
+            && self.conflicts.is_empty()
+    }
+
+    // Removes dropped files and writes the new override files. Conflicting
+    // configs are only replaced where the user chose the pack's version.
+    pub fn apply_files(&self, game_dir: &Path) -> Result<()> {
+        for path in self.removed.iter().chain(&self.dropped_overrides) {
+            let target = game_dir.join(path);
+            if target.is_file() {
+                std::fs::remove_file(&target)?;
+            }
+        }
+
+        let selected: HashSet<&str> = self.overrides
+            .iter()
+            .map(String::as_str)
+            .chain(self.conflicts.iter().filter(|c| !c.keep_local).map(|c| c.path.as_str()))
+            .collect();
+        if selected.is_empty() {
+            return Ok(());
+        }
+        for source in &self.game_files {
+            import::copy_game_files_matching(source, game_dir, |path| selected.contains(path))?;
+        }
+        Ok(())
+    }
+
+    // Moves the instance to the new pack version. Files that failed to
+    // download stay out of the manifest so the next update retries them.
+    pub fn finish(self, instance: &mut Instance, failed: &[String]) {
+        let mut source = self.source;
+        source.project_id = instance.modpack.as_ref().and_then(|pack| pack.project_id.clone());
+        for path in failed {
+            source.files.remove(path);
+        }
+        instance.minecraft_version = self.minecraft_version;
+        instance.mod_loader = self.mod_loader;
+        instance.mod_loader_version = self.mod_loader_version;
+        instance.modpack = Some(source);
+    }
+}
+
+pub fn plan_update(instance: &Instance, archive: &Path) -> Result<ModpackUpdate> {
+    plan_over(instance, archive, false)
+}
+
+/// Plans importing `archive` over `instance`. The caller has already matched
+/// the archive to the instance's project, so a pack renamed since it was
+/// installed is still accepted.
+pub fn plan_import(instance: &Instance, archive: &Path) -> Result<ModpackUpdate> {
+    plan_over(instance, archive, true)
+}
+
+fn plan_over(instance: &Instance, archive: &Path, from_import: bool) -> Result<ModpackUpdate> {
+    let current = instance.modpack.as_ref()
+        .ok_or_else(|| Error::Instance(format!("'{}' was not imported from a modpack", instance.name)))?;
+    let plan = import::read_plan(archive)?;
+    let source = ModpackSource::from_plan(&plan)?
+        .filter(|source| source.format == current.format && (from_import || source.name == current.name))
+        .ok_or_else(|| Error::Instance(format!("{} is not a version of {}", archive.display(), current.name)))?;
+    let game_dir = instance.path.join(".minecraft");
+
+    let mut downloads = Vec::new();
+    let mut updated = 0;
+    for download in plan.pending_downloads {
+        match current.files.get(&import::game_path(&download.path)) {
+            Some(sha1) if *sha1 == download.sha1 && game_dir.join(&download.path).is_file() => continue,
+            Some(_) => updated += 1,
+            None => {}
+        }
+        downloads.push(download);
+    }
+
+    let removed = current.files
+        .keys()
+        .filter(|path| !source.files.contains_key(*path) && !source.overrides.contains_key(*path))
+        .filter(|path| game_dir.join(path).is_file())
+        .cloned()
+        .collect();
+
+    let mut overrides = Vec::new();
+    let mut conflicts = Vec::new();
+    for (path, hash) in &source.overrides {
+        let local = game_dir.join(path);
+        if !local.is_file() {
+            overrides.push(path.clone());
+            continue;
+        }
+        let local_hash = import::sha1_hex(File::open(&local)?)?;
+        let original = current.overrides.get(path);
+        if local_hash == *hash || original == Some(hash) {
+            // Already up to date, or the pack did not touch a file the user edited.
+            continue;
+        }
+        if original == Some(&local_hash) {
+            overrides.push(path.clone());
+        } else {
+            conflicts.push(ConfigConflict { path: path.clone(), keep_local: true });
+        }
+    }
+
+    // Old override files are only removed while they are still exactly what
+    // the pack shipped; anything the user edited stays.
+    let mut dropped_overrides = Vec::new();
+    for (path, original) in &current.overrides {
+        if source.overrides.contains_key(path) || source.files.contains_key(path) {
+            continue;
+        }
+        let local = game_dir.join(path);
+        if local.is_file() && import::sha1_hex(File::open(&local)?)? == *original {
+            dropped_overrides.push(path.clone());
+        }
+    }
+
+    Ok(ModpackUpdate {
+        instance_id: instance.id,
+        archive: archive.to_path_buf(),
+        from_version: current.version.clone(),
+        source,
+        minecraft_version: plan.minecraft_version,
+        mod_loader: plan.mod_loader,
+        mod_loader_version: plan.mod_loader_version,
+        downloads,
+        updated,
+        removed,
+        overrides,
+        dropped_overrides,
+        conflicts,
+        from_import,
+        game_files: plan.game_files,
+    })
+}
+
+// The newest pack in `dir` that is a version of the instance's modpack.
+pub fn find_update(instance: &Instance, dir: &Path) -> Option<PathBuf> {
+    let current = instance.modpack.as_ref()?;
+    std::fs::read_dir(dir).ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter(|entry| {
+            import::read_plan(&entry.path())
+                .is_ok_and(|plan| {
+                    ModpackFormat::from_import(plan.kind) == Some(current.format)
+                        && plan.name == current.name
+                        && (plan.pack_version.is_none() || plan.pack_version != current.version)
+                })
+        })
+        .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        .map(|entry| entry.path())
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::instance::InstanceLinks;
+use crate::network::NetworkManager;
+use crate::{Error, Result};
+
+const MODRINTH_API_URL: &str = "https://api.modrinth.com/v2";
+pub const SEARCH_PAGE_SIZE: usize = 20;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchResults {
+    pub hits: Vec<SearchHit>,
+    pub offset: usize,
+    pub limit: usize,
+    pub total_hits: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchHit {
+    pub project_id: String,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub author: String,
+    pub downloads: u64,
+    #[serde(default)]
+    pub categories: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModrinthVersion {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    pub version_number: String,
+    #[serde(default)]
+    pub game_versions: Vec<String>,
+    #[serde(default)]
+    pub loaders: Vec<String>,
+    pub files: Vec<ModrinthFile>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModrinthFile {
+    pub hashes: HashMap<String, String>,
+    pub url: String,
+    pub filename: String,
+    #[serde(default)]
+    pub primary: bool,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModrinthProject {
+    pub slug: String,
+    pub project_type: String,
+    pub issues_url: Option<String>,
+    pub wiki_url: Option<String>,
+}
+
+impl ModrinthProject {
+    pub fn links(&self) -> InstanceLinks {
+        InstanceLinks {
+            project_url: Some(format!("https://modrinth.com/{}/{}", self.project_type, self.slug)),
+            issues_url: self.issues_url.clone(),
+            wiki_url: self.wiki_url.clone(),
+        }
+    }
+}
+
+impl ModrinthVersion {
+    pub fn primary_file(&self) -> Option<&ModrinthFile> {
+        self.files.iter().find(|f| f.primary).or_else(|| self.files.first())
+    }
+}
+
+pub fn loader_name(loader: &crate::instance::ModLoader) -> &'static str {
+    match loader {
+        crate::instance::ModLoader::Forge => "forge",
+        crate::instance::ModLoader::Fabric => "fabric",
+        crate::instance::ModLoader::Quilt => "quilt",
+        crate::instance::ModLoader::NeoForge => "neoforge",
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ModrinthClient {
+    network: NetworkManager,
+}
+
+impl ModrinthClient {
+    pub fn new(network: NetworkManager) -> Self {
+        Self { network }
+    }
+
+    pub async fn search(
+        &self,
+        query: &str,
+        loader: Option<&str>,
+        game_version: Option<&str>,
+        offset: usize,
+    ) -> Result<SearchResults> {
+        let mut facets = vec![vec!["project_type:mod".to_string()]];
+        if let Some(loader) = loader {
+            facets.push(vec![format!("categories:{}", loader)]);
+        }
+        if let Some(game_version) = game_version {
+            facets.push(vec![format!("versions:{}", game_version)]);
+        }
+
+        let url = url::Url::parse_with_params(
+            &format!("{}/search", MODRINTH_API_URL),
+            &[
+                ("query", query.to_string()),
+                ("facets", serde_json::to_string(&facets)?),
+                ("offset", offset.to_string()),
+                ("limit", SEARCH_PAGE_SIZE.to_string()),
+            ],
+        ).map_err(|e| Error::Other(format!("Invalid Modrinth URL: {}", e)))?;
+
+        self.network.get_json(url.as_str()).await
+    }
+
+    pub async fn project_versions(
+        &self,
+        project_id: &str,
+        loader: Option<&str>,
+        game_version: Option<&str>,
+    ) -> Result<Vec<ModrinthVersion>> {
+        let mut params = Vec::new();
+        if let Some(loader) = loader {
+            params.push(("loaders", serde_json::to_string(&[loader])?));
+        }
+        if let Some(game_version) = game_version {
+            params.push(("game_versions", serde_json::to_string(&[game_version])?));
+        }
+
+        let url = url::Url::parse_with_params(
+            &format!("{}/project/{}/version", MODRINTH_API_URL, project_id),
+            &params,
+        ).map_err(|e| Error::Other(format!("Invalid Modrinth URL: {}", e)))?;
+
+        self.network.get_json(url.as_str()).await
+    }
+
+    pub async fn project(&self, project_id: &str) -> Result<ModrinthProject> {
+        self.network.get_json(&format!("{}/project/{}", MODRINTH_API_URL, project_id)).await
+    }
+
+    pub async fn versions_from_hashes(&self, sha1_hashes: &[String]) -> Result<HashMap<String, ModrinthVersion>> {
+        let body = serde_json::json!({
+            "hashes": sha1_hashes,
+            "algorithm": "sha1",
+        });
+        self.network.post_json(&format!("{}/version_files", MODRINTH_API_URL), &body).await
+    }
+
+    pub async fn latest_versions_from_hashes(
+        &self,
+        sha512_hashes: &[String],
+        loader: Option<&str>,
+        game_version: &str,
+    ) -> Result<HashMap<String, ModrinthVersion>> {
+        let body = serde_json::json!({
+            "hashes": sha512_hashes,
+            "algorithm": "sha512",
+            "loaders": loader.into_iter().collect::<Vec<_>>(),
+            "game_versions": [game_version],
+        });
+        self.network.post_json(&format!("{}/version_files/update", MODRINTH_API_URL), &body).await
+    }
+
+    pub async fn download_file(&self, file: &ModrinthFile, target_dir: &Path) -> Result<PathBuf> {
+        let file_name = Path::new(&file.filename)
+            .file_name()
+            .ok_or_else(|| Error::Mod(format!("Invalid file name '{}'", file.filename)))?;
+        let target = target_dir.join(file_name);
+        self.network.download_file(&file.url, &target, file.hashes.get("sha1").map(String::as_str), None).await?;
+        Ok(target)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ModBrowser {
+    pub instance_id: Uuid,
+    pub loader: Option<String>,
+    pub game_version: Option<String>,
+    pub query: String,
+    pub editing: bool,
+    pub offset: usize,
+    pub total_hits: usize,
+    pub results: Vec<SearchHit>,
+}
+
+impl ModBrowser {
+    pub fn new(instance_id: Uuid, loader: Option<String>, game_version: Option<String>) -> Self {
+        Self {
+            instance_id,
+            loader,
+            game_version,
+            query: String::new(),
+            editing: true,
+            offset: 0,
+            total_hits: 0,
+            results: Vec::new(),
+        }
+    }
+
+    pub fn page(&self) -> usize {
+        self.offset / SEARCH_PAGE_SIZE + 1
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.total_hits.div_ceil(SEARCH_PAGE_SIZE).max(1)
+    }
+}
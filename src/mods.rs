@@ -6,6 +6,10 @@ use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::curseforge::CurseForgeClient;
+use crate::github::{Checksum, GitHubClient, GitHubMod, GitHubSource};
+use crate::modrinth::ModrinthClient;
+use crate::network::NetworkManager;
 use crate::Result;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +25,8 @@ pub struct Mod {
     pub mod_loader: ModLoader,
     pub minecraft_versions: Vec<String>,
     pub dependencies: Vec<ModDependency>,
+    #[serde(default)]
+    pub provides: Vec<String>,
     pub size: u64,
     pub hash: String,
     pub source: ModSource,
@@ -42,6 +48,60 @@ pub struct ModDependency {
     pub required: bool,
 }
 
+// Ids supplied by the game or the loader itself rather than by a mod jar.
+const PLATFORM_IDS: &[&str] = &[
+    "minecraft", "java", "fabricloader", "fabric-loader", "quilt_loader", "forge", "neoforge", "javafml", "lowcodefml",
+];
+
+#[derive(Debug, Clone)]
+pub struct MissingDependency {
+    pub required_by: String,
+    pub mod_id: String,
+    pub version_range: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DependencyConflict {
+    pub required_by: String,
+    pub mod_id: String,
+    pub version_range: String,
+    pub installed_version: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DependencyReport {
+    pub missing: Vec<MissingDependency>,
+    pub conflicts: Vec<DependencyConflict>,
+}
+
+impl DependencyReport {
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.conflicts.is_empty()
+    }
+
+    pub fn missing_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.missing.iter().map(|m| m.mod_id.clone()).collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolvedDependency {
+    pub mod_id: String,
+    pub version: crate::modrinth::ModrinthVersion,
+}
+
+// A dependency report along with which of the missing ids were found on
+// Modrinth and which were not.
+#[derive(Debug, Clone)]
+pub struct DependencyCheck {
+    pub report: DependencyReport,
+    pub resolved: Vec<ResolvedDependency>,
+    pub unresolved: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ModSource {
     CurseForge { project_id: u32, file_id: u32 },
@@ -50,6 +110,36 @@ pub enum ModSource {
     Unknown,
 }
 
+#[derive(Debug, Clone)]
+pub enum UpdateSource {
+    Modrinth { project_id: String, version_id: String },
+    CurseForge { mod_id: u32, file_id: u32 },
+    GitHub { source: GitHubSource, tag: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct ModUpdate {
+    pub mod_name: String,
+    pub filename: String,
+    pub enabled: bool,
+    pub current_version: String,
+    pub new_version: String,
+    pub new_filename: String,
+    pub download_url: Option<String>,
+    pub checksum: Option<Checksum>,
+    pub source: UpdateSource,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdateTarget {
+    pub game_version: String,
+    pub loader: Option<crate::instance::ModLoader>,
+    pub curseforge_api_key: Option<String>,
+    pub github_mods: Vec<GitHubMod>,
+}
+
+const SAFE_MODE_DIR: &str = ".safe-mode";
+
 pub struct ModManager {
     mods_dir: PathBuf,
     mods: HashMap<Uuid, Mod>,
@@ -127,6 +217,7 @@ impl ModManager {
             mod_loader: ModLoader::Forge,
             minecraft_versions: Vec::new(),
             dependencies: Vec::new(),
+            provides: Vec::new(),
             size: metadata.len(),
             hash,
             source: ModSource::Local,
@@ -152,11 +243,14 @@ impl ModManager {
         }
         
         if !found {
-            if let Ok(mut neoforge_file) = archive.by_name("META-INF/mods.toml") {
-                let mut content = String::new();
-                neoforge_file.read_to_string(&mut content)?;
-                drop(neoforge_file); 
-                self.parse_neoforge_mod_from_content(&content, &mut mod_info)?;
+            for name in ["META-INF/neoforge.mods.toml", "META-INF/mods.toml"] {
+                if let Ok(mut neoforge_file) = archive.by_name(name) {
+                    let mut content = String::new();
+                    neoforge_file.read_to_string(&mut content)?;
+                    drop(neoforge_file); 
+                    self.parse_neoforge_mod_from_content(&content, &mut mod_info)?;
+                    break;
+                }
             }
         }
         
@@ -189,6 +283,25 @@ impl ModManager {
                 }
             }
         }
+
+        if let Some(provides) = json["provides"].as_array() {
+            mod_info.provides = provides.iter().filter_map(|id| id.as_str()).map(str::to_string).collect();
+        }
+
+        // A range is a string or an array of alternatives.
+        for (key, required) in [("depends", true), ("recommends", false)] {
+            let Some(depends) = json[key].as_object() else { continue };
+            for (id, range) in depends {
+                let version_range = match range {
+                    serde_json::Value::Array(alternatives) => alternatives.iter()
+                        .filter_map(|r| r.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" || "),
+                    other => other.as_str().unwrap_or("*").to_string(),
+                };
+                mod_info.dependencies.push(ModDependency { mod_id: id.clone(), version_range, required });
+            }
+        }
         
         mod_info.mod_loader = ModLoader::Fabric;
         
@@ -231,8 +344,46 @@ impl ModManager {
         Ok(())
     }
 
-    fn parse_neoforge_mod_from_content(&self, _content: &str, mod_info: &mut Mod) -> Result<()> {
+    fn parse_neoforge_mod_from_content(&self, content: &str, mod_info: &mut Mod) -> Result<()> {
         mod_info.mod_loader = ModLoader::NeoForge;
+        let Ok(toml) = content.parse::<toml::Table>() else {
+            return Ok(());
+        };
+
+        let first = toml.get("mods").and_then(|mods| mods.as_array()).and_then(|mods| mods.first());
+        if let Some(first) = first {
+            if let Some(id) = first.get("modId").and_then(|v| v.as_str()) {
+                mod_info.mod_id = Some(id.to_string());
+            }
+            if let Some(name) = first.get("displayName").and_then(|v| v.as_str()) {
+                mod_info.name = name.to_string();
+            }
+            // "${file.jarVersion}" is filled in from the manifest at runtime.
+            if let Some(version) = first.get("version").and_then(|v| v.as_str()).filter(|v| !v.starts_with("${")) {
+                mod_info.version = version.to_string();
+            }
+            if let Some(description) = first.get("description").and_then(|v| v.as_str()) {
+                mod_info.description = Some(description.trim().to_string());
+            }
+        }
+
+        let own_id = mod_info.mod_id.clone().unwrap_or_default();
+        let dependencies = toml.get("dependencies")
+            .and_then(|deps| deps.get(&own_id))
+            .and_then(|deps| deps.as_array());
+        for dependency in dependencies.into_iter().flatten() {
+            let Some(id) = dependency.get("modId").and_then(|v| v.as_str()) else { continue };
+            // Forge uses `mandatory`, NeoForge `type = "required"`.
+            let required = dependency.get("mandatory").and_then(|v| v.as_bool())
+                .or_else(|| dependency.get("type").and_then(|v| v.as_str()).map(|t| t == "required"))
+                .unwrap_or(false);
+            // A bare Maven version is only a recommendation, not a constraint.
+            let version_range = dependency.get("versionRange").and_then(|v| v.as_str())
+                .filter(|range| range.starts_with(['[', '(']))
+                .unwrap_or("*")
+                .to_string();
+            mod_info.dependencies.push(ModDependency { mod_id: id.to_string(), version_range, required });
+        }
         Ok(())
     }
 
@@ -327,34 +478,92 @@ impl ModManager {
             .collect()
     }
 
-    pub fn check_dependencies(&self) -> HashMap<Uuid, Vec<String>> {
-        let mut missing_deps = HashMap::new();
-        
-        for (mod_id, mod_info) in &self.mods {
-            if !mod_info.enabled {
+    // Reports required ids nothing provides, and installed mods whose version
+    // falls outside the range a dependent asks for.
+    pub fn check_dependencies(&self) -> DependencyReport {
+        let mut report = DependencyReport::default();
+        let enabled: Vec<&Mod> = self.mods.values().filter(|m| m.enabled).collect();
+
+        for mod_info in &enabled {
+            for dep in mod_info.dependencies.iter().filter(|dep| dep.required) {
+                if PLATFORM_IDS.contains(&dep.mod_id.as_str()) {
+                    continue;
+                }
+                let Some(provider) = find_provider(&enabled, &dep.mod_id) else {
+                    report.missing.push(MissingDependency {
+                        required_by: mod_info.name.clone(),
+                        mod_id: dep.mod_id.clone(),
+                        version_range: dep.version_range.clone(),
+                    });
+                    continue;
+                };
+                if version_in_range(&provider.version, &dep.version_range) == Some(false) {
+                    report.conflicts.push(DependencyConflict {
+                        required_by: mod_info.name.clone(),
+                        mod_id: dep.mod_id.clone(),
+                        version_range: dep.version_range.clone(),
+                        installed_version: provider.version.clone(),
+                    });
+                }
+            }
+        }
+
+        report.missing.sort_by(|a, b| a.mod_id.cmp(&b.mod_id));
+        report
+    }
+
+    // Mod ids usually match Modrinth slugs; ids with underscores are tried
+    // with dashes too (cloth_config → cloth-config).
+    pub async fn resolve_dependencies(
+        &self,
+        network: &NetworkManager,
+        target: &UpdateTarget,
+        missing: &[String],
+    ) -> (Vec<ResolvedDependency>, Vec<String>) {
+        let modrinth = ModrinthClient::new(network.clone());
+        let loader = target.loader.as_ref().map(crate::modrinth::loader_name);
+        let mut resolved: Vec<ResolvedDependency> = Vec::new();
+        let mut unresolved = Vec::new();
+
+        for mod_id in missing {
+            let slug = if mod_id.starts_with("fabric-") && mod_id != "fabric-api" { "fabric-api".to_string() } else { mod_id.clone() };
+            if resolved.iter().any(|r| r.mod_id == slug) {
                 continue;
             }
-            
-            let mut missing = Vec::new();
-            
-            for dep in &mod_info.dependencies {
-                if dep.required {
-                    let found = self.mods.values().any(|m| {
-                        m.enabled && m.mod_id.as_ref() == Some(&dep.mod_id)
-                    });
-                    
-                    if !found {
-                        missing.push(dep.mod_id.clone());
+            let mut candidates = vec![slug.clone()];
+            if slug.contains('_') {
+                candidates.push(slug.replace('_', "-"));
+            }
+
+            let mut found = None;
+            for candidate in candidates {
+                match modrinth.project_versions(&candidate, loader, Some(&target.game_version)).await {
+                    Ok(versions) => {
+                        if let Some(version) = versions.into_iter().next() {
+                            found = Some(version);
+                            break;
+                        }
                     }
+                    Err(e) => tracing::debug!("Modrinth has no project '{}': {}", candidate, e),
                 }
             }
-            
-            if !missing.is_empty() {
-                missing_deps.insert(*mod_id, missing);
+            match found {
+                Some(version) => resolved.push(ResolvedDependency { mod_id: slug, version }),
+                None => unresolved.push(mod_id.clone()),
             }
         }
-        
-        missing_deps
+        (resolved, unresolved)
+    }
+
+    pub async fn check_and_resolve_dependencies(&self, network: &NetworkManager, target: &UpdateTarget) -> DependencyCheck {
+        let report = self.check_dependencies();
+        let missing = report.missing_ids();
+        let (resolved, unresolved) = if missing.is_empty() {
+            (Vec::new(), Vec::new())
+        } else {
+            self.resolve_dependencies(network, target, &missing).await
+        };
+        DependencyCheck { report, resolved, unresolved }
     }
 
     pub fn get_mods_by_loader(&self, loader: &ModLoader) -> Vec<&Mod> {
@@ -363,4 +572,364 @@ impl ModManager {
             .filter(|m| std::mem::discriminant(&m.mod_loader) == std::mem::discriminant(loader))
             .collect()
     }
-} 
\ No newline at end of file
+
+    fn mod_path(&self, mod_info: &Mod) -> PathBuf {
+        if mod_info.enabled {
+            self.mods_dir.join(&mod_info.filename)
+        } else {
+            self.disabled_dir.join(&mod_info.filename)
+        }
+    }
+
+    pub async fn check_updates(&self, network: &NetworkManager, target: &UpdateTarget) -> Result<Vec<ModUpdate>> {
+        let mut updates = self.check_github_updates(network, target).await?;
+
+        let mut installed = Vec::new();
+        let tracked = |mod_info: &Mod| target.github_mods.iter().any(|m| m.filename == mod_info.filename);
+        for mod_info in self.mods.values().filter(|m| !tracked(m)) {
+            let path = self.mod_path(mod_info);
+            let (_, sha512) = crate::mrpack::hash_file(&path)?;
+            installed.push((mod_info, path, sha512));
+        }
+
+        let mut unmatched = Vec::new();
+
+        let modrinth = ModrinthClient::new(network.clone());
+        let hashes: Vec<String> = installed.iter().map(|(_, _, sha512)| sha512.clone()).collect();
+        let loader = target.loader.as_ref().map(crate::modrinth::loader_name);
+        let latest = if hashes.is_empty() {
+            HashMap::new()
+        } else {
+            modrinth.latest_versions_from_hashes(&hashes, loader, &target.game_version).await?
+        };
+
+        for (mod_info, path, sha512) in installed {
+            let Some(version) = latest.get(&sha512) else {
+                unmatched.push((mod_info, path));
+                continue;
+            };
+            let Some(file) = version.primary_file() else {
+                continue;
+            };
+            if file.hashes.get("sha512") == Some(&sha512) {
+                continue;
+            }
+            updates.push(ModUpdate {
+                mod_name: mod_info.name.clone(),
+                filename: mod_info.filename.clone(),
+                enabled: mod_info.enabled,
+                current_version: mod_info.version.clone(),
+                new_version: version.version_number.clone(),
+                new_filename: file.filename.clone(),
+                download_url: Some(file.url.clone()),
+                checksum: file.hashes.get("sha1").and_then(|h| Checksum::from_hex(h)),
+                source: UpdateSource::Modrinth {
+                    project_id: version.project_id.clone(),
+                    version_id: version.id.clone(),
+                },
+            });
+        }
+
+        let Some(api_key) = target.curseforge_api_key.as_ref().filter(|k| !k.is_empty()) else {
+            return Ok(updates);
+        };
+        if unmatched.is_empty() {
+            return Ok(updates);
+        }
+
+        let curseforge = CurseForgeClient::new(network.clone(), api_key.clone());
+        let mut fingerprints = HashMap::new();
+        for (mod_info, path) in unmatched {
+            let fingerprint = crate::curseforge::fingerprint(&std::fs::read(&path)?);
+            fingerprints.insert(fingerprint, mod_info);
+        }
+
+        let keys: Vec<u32> = fingerprints.keys().copied().collect();
+        for current in curseforge.files_from_fingerprints(&keys).await? {
+            let Some(mod_info) = fingerprints.get(&current.file_fingerprint) else {
+                continue;
+            };
+            let Some(file) = curseforge.latest_file(current.mod_id, target.loader.as_ref(), &target.game_version).await? else {
+                continue;
+            };
+            if file.id == current.id {
+                continue;
+            }
+            updates.push(ModUpdate {
+                mod_name: mod_info.name.clone(),
+                filename: mod_info.filename.clone(),
+                enabled: mod_info.enabled,
+                current_version: mod_info.version.clone(),
+                new_version: file.display_name.clone(),
+                new_filename: file.file_name.clone(),
+                download_url: file.download_url.clone(),
+                checksum: file.sha1().and_then(Checksum::from_hex),
+                source: UpdateSource::CurseForge {
+                    mod_id: file.mod_id,
+                    file_id: file.id,
+                },
+            });
+        }
+
+        Ok(updates)
+    }
+
+    async fn check_github_updates(&self, network: &NetworkManager, target: &UpdateTarget) -> Result<Vec<ModUpdate>> {
+        let client = GitHubClient::new(network.clone());
+        let mut updates = Vec::new();
+
+        for tracked in &target.github_mods {
+            let Some(mod_info) = self.mods.values().find(|m| m.filename == tracked.filename) else {
+                continue;
+            };
+            let release = match client.latest_release(&tracked.source).await {
+                Ok(release) => release,
+                Err(e) => {
+                    tracing::warn!("Failed to check {} for updates: {}", tracked.source.display(), e);
+                    continue;
+                }
+            };
+            if release.tag_name == tracked.tag {
+                continue;
+            }
+            let Some(asset) = GitHubClient::find_asset(&release, &tracked.source) else {
+                continue;
+            };
+            updates.push(ModUpdate {
+                mod_name: mod_info.name.clone(),
+                filename: mod_info.filename.clone(),
+                enabled: mod_info.enabled,
+                current_version: tracked.tag.clone(),
+                new_version: release.tag_name.clone(),
+                new_filename: asset.name.clone(),
+                download_url: Some(asset.browser_download_url.clone()),
+                checksum: client.checksum(&release, asset).await?,
+                source: UpdateSource::GitHub {
+                    source: tracked.source.clone(),
+                    tag: release.tag_name.clone(),
+                },
+            });
+        }
+        Ok(updates)
+    }
+
+    pub async fn apply_update(&self, network: &NetworkManager, update: &ModUpdate, download_dir: &Path) -> Result<()> {
+        let url = update.download_url.as_deref()
+            .ok_or_else(|| crate::Error::Mod(format!("{} does not allow third-party downloads", update.new_filename)))?;
+        let new_name = Path::new(&update.new_filename)
+            .file_name()
+            .ok_or_else(|| crate::Error::Mod(format!("Invalid file name '{}'", update.new_filename)))?;
+
+        let downloaded = download_dir.join(new_name);
+        network.download_file(url, &downloaded, None, None).await?;
+        if let Some(checksum) = &update.checksum {
+            if let Err(e) = checksum.verify(&downloaded) {
+                let _ = std::fs::remove_file(&downloaded);
+                return Err(e);
+            }
+        }
+
+        let dir = if update.enabled { &self.mods_dir } else { &self.disabled_dir };
+        let old_path = dir.join(&update.filename);
+        let new_path = dir.join(new_name);
+        let result = std::fs::copy(&downloaded, &new_path);
+        let _ = std::fs::remove_file(&downloaded);
+        result?;
+
+        if old_path != new_path && old_path.exists() {
+            std::fs::remove_file(old_path)?;
+        }
+        Ok(())
+    }
+
+    pub async fn apply_updates(
+        &mut self,
+        network: &NetworkManager,
+        updates: &[ModUpdate],
+        download_dir: &Path,
+    ) -> Vec<(ModUpdate, Result<()>)> {
+        let mut results = Vec::new();
+        for update in updates {
+            let result = self.apply_update(network, update, download_dir).await;
+            results.push((update.clone(), result));
+        }
+        if let Err(e) = self.scan_mods() {
+            tracing::warn!("Failed to rescan mods after update: {}", e);
+        }
+        results
+    }
+
+    pub fn enter_safe_mode(mods_dir: &Path) -> Result<usize> {
+        Self::stash_mods_except(mods_dir, &[])
+    }
+
+    pub fn list_enabled_mod_files(mods_dir: &Path) -> Result<Vec<String>> {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(mods_dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().is_some_and(|e| e == "jar" || e == "zip") {
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                    files.push(name.to_string());
+                }
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    pub fn stash_mods_except(mods_dir: &Path, keep: &[String]) -> Result<usize> {
+        let stash_dir = mods_dir.join(SAFE_MODE_DIR);
+        std::fs::create_dir_all(&stash_dir)?;
+
+        let mut moved = 0;
+        for name in Self::list_enabled_mod_files(mods_dir)? {
+            if keep.contains(&name) {
+                continue;
+            }
+            std::fs::rename(mods_dir.join(&name), stash_dir.join(&name))?;
+            moved += 1;
+        }
+        Ok(moved)
+    }
+
+    pub fn exit_safe_mode(mods_dir: &Path) -> Result<usize> {
+        let stash_dir = mods_dir.join(SAFE_MODE_DIR);
+        if !stash_dir.exists() {
+            return Ok(0);
+        }
+
+        let mut restored = 0;
+        for entry in std::fs::read_dir(&stash_dir)? {
+            let path = entry?.path();
+            if let Some(name) = path.file_name() {
+                let target = mods_dir.join(name);
+                if target.exists() {
+                    tracing::warn!("Mod {:?} reappeared during safe mode, keeping the stashed copy aside", name);
+                    continue;
+                }
+                std::fs::rename(&path, target)?;
+                restored += 1;
+            }
+        }
+
+        if std::fs::read_dir(&stash_dir)?.next().is_none() {
+            std::fs::remove_dir(&stash_dir)?;
+        }
+        Ok(restored)
+    }
+
+    pub fn is_in_safe_mode(mods_dir: &Path) -> bool {
+        mods_dir.join(SAFE_MODE_DIR).exists()
+    }
+}
+
+fn find_provider<'a>(mods: &[&'a Mod], id: &str) -> Option<&'a Mod> {
+    mods.iter()
+        .find(|m| m.mod_id.as_deref() == Some(id) || m.provides.iter().any(|p| p == id))
+        // Fabric API bundles its modules (fabric-networking-api-v1 etc.) as
+        // nested jars, which are not unpacked here.
+        .or_else(|| id.starts_with("fabric-").then(|| {
+            mods.iter().find(|m| matches!(m.mod_id.as_deref(), Some("fabric-api") | Some("fabric")))
+        }).flatten())
+        .copied()
+}
+
+fn version_parts(version: &str) -> Option<Vec<u64>> {
+    let core = version.split(['-', '+']).next()?;
+    let parts: Vec<u64> = core.split('.')
+        .map_while(|part| part.parse().ok())
+        .collect();
+    (!parts.is_empty()).then_some(parts)
+}
+
+fn compare_versions(a: &[u64], b: &[u64]) -> std::cmp::Ordering {
+    let len = a.len().max(b.len());
+    let pad = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+    (0..len)
+        .map(|i| pad(a, i).cmp(&pad(b, i)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+/// Checks a version against a Fabric (`>=1.2 <2`, `~1.2`, `^1`, `1.20.x`,
+/// alternatives joined by `||`) or Maven (`[1.2,2.0)`) range. `None` means
+/// the version or range could not be understood and should not be reported.
+pub fn version_in_range(version: &str, range: &str) -> Option<bool> {
+    let range = range.trim();
+    if range.is_empty() || range == "*" {
+        return Some(true);
+    }
+    let version = version_parts(version)?;
+    if range.starts_with('[') || range.starts_with('(') {
+        return maven_range_matches(&version, range);
+    }
+    let mut any_understood = false;
+    for alternative in range.split("||") {
+        let mut all = true;
+        for predicate in alternative.split_whitespace() {
+            match predicate_matches(&version, predicate) {
+                Some(matches) => all &= matches,
+                None => return None,
+            }
+        }
+        any_understood = true;
+        if all {
+            return Some(true);
+        }
+    }
+    any_understood.then_some(false)
+}
+
+fn predicate_matches(version: &[u64], predicate: &str) -> Option<bool> {
+    use std::cmp::Ordering::*;
+    let (op, rest) = ["<=", ">=", "<", ">", "=", "~", "^"].iter()
+        .find_map(|op| predicate.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("", predicate));
+    if op.is_empty() && (rest.ends_with(".x") || rest.ends_with(".X") || rest.ends_with(".*")) {
+        let prefix = version_parts(&rest[..rest.len() - 2])?;
+        return Some(version.len() >= prefix.len() && version[..prefix.len()] == prefix[..]);
+    }
+    let target = version_parts(rest)?;
+    let ordering = compare_versions(version, &target);
+    Some(match op {
+        "<=" => ordering != Greater,
+        ">=" => ordering != Less,
+        "<" => ordering == Less,
+        ">" => ordering == Greater,
+        "~" => ordering != Less && version.get(..2.min(target.len())) == target.get(..2.min(target.len())),
+        "^" => ordering != Less && version.first() == target.first(),
+        _ => ordering == Equal,
+    })
+}
+
+fn maven_range_matches(version: &[u64], range: &str) -> Option<bool> {
+    use std::cmp::Ordering::*;
+    // Several ranges may be listed: "[1.0,1.2),[1.3,)".
+    let mut rest = range;
+    while !rest.is_empty() {
+        let end = rest.find([']', ')'])?;
+        let (body, tail) = rest.split_at(end + 1);
+        let inclusive_low = body.starts_with('[');
+        let inclusive_high = body.ends_with(']');
+        let inner = &body[1..body.len() - 1];
+        let matches = match inner.split_once(',') {
+            None => compare_versions(version, &version_parts(inner)?) == Equal,
+            Some((low, high)) => {
+                let low_ok = low.trim().is_empty() || {
+                    let ordering = compare_versions(version, &version_parts(low.trim())?);
+                    ordering == Greater || (inclusive_low && ordering == Equal)
+                };
+                let high_ok = high.trim().is_empty() || {
+                    let ordering = compare_versions(version, &version_parts(high.trim())?);
+                    ordering == Less || (inclusive_high && ordering == Equal)
+                };
+                low_ok && high_ok
+            }
+        };
+        if matches {
+            return Some(true);
+        }
+        rest = tail.trim_start_matches([',', ' ']);
+    }
+    Some(false)
+}
@@ -0,0 +1,162 @@
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use crate::i18n::{self, tr};
+use crate::network::NetworkManager;
+use crate::settings::Language;
+use crate::Result;
+
+/// How long one line and one piece of art stay up before the next.
+const ROTATE_EVERY: Duration = Duration::from_secs(45);
+// Remote art larger than this would not fit the left panel.
+const MAX_ART_WIDTH: usize = 56;
+const MAX_ART_HEIGHT: usize = 12;
+
+// The welcome line comes first, so every start opens with it.
+const TIPS: [&str; 8] = [
+    "main.motd",
+    "motd.tip_help",
+    "motd.tip_recent",
+    "motd.tip_logs",
+    "motd.tip_connection",
+    "motd.tip_java",
+    "motd.tip_loader",
+    "motd.tip_marks",
+];
+
+// Lines starting with `░` shimmer while the art rotates.
+const MANGO_ART: [&str; 8] = [
+    "  ░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░",
+    "      ███╗   ███╗ ██████╗ ███╗   ██╗ ██████╗  ██████╗ ",
+    "      ████╗ ████║██╔══██╗████╗  ██║██╔════╝ ██╔═══██╗",
+    "      ██╔████╔██║███████║██╔██╗ ██║██║  ███╗██║   ██║",
+    "      ██║╚██╔╝██║██╔══██║██║╚██╗██║██║   ██║██║   ██║",
+    "      ██║ ╚═╝ ██║██║  ██║██║ ╚████║╚██████╔╝╚██████╔╝",
+    "      ╚═╝     ╚═╝╚═╝  ╚═╝╚═╝  ╚═══╝ ╚═════╝  ╚═════╝ ",
+    "  ░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░",
+];
+
+const FRUIT_ART: [&str; 9] = [
+    "  ░░░░░░░░░░░░░░░░░░░░░░░░░░",
+    "              ▄▄",
+    "          ▄▄▄███▀",
+    "       ▄██████████▄",
+    "     ▄██████████████▄",
+    "     ████████████████",
+    "      ▀████████████▀",
+    "         ▀▀▀▀▀▀▀▀",
+    "  ░░░░░░░░░░░░░░░░░░░░░░░░░░",
+];
+
+const COMPACT_ART: [&str; 6] = [
+    "  ░░░░░░░░░░░░░░░░░░░░░░░░",
+    "  ╔╦╗╔═╗╔╗╔╔═╗╔═╗",
+    "  ║║║╠═╣║║║║ ╦║ ║",
+    "  ╩ ╩╩ ╩╝╚╝╚═╝╚═╝",
+    "    L A U N C H E R",
+    "  ░░░░░░░░░░░░░░░░░░░░░░░░",
+];
+
+const BUILTIN_ART: [&[&str]; 3] = [&MANGO_ART, &FRUIT_ART, &COMPACT_ART];
+
+/// A MOTD document served from `ui.motd_url`: lines shown before the
+/// built-in tips, optionally only in one language, and extra art.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RemoteMotd {
+    #[serde(default)]
+    pub messages: Vec<RemoteMessage>,
+    #[serde(default)]
+    pub art: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteMessage {
+    pub text: String,
+    /// `en` or `ru`; shown in every language when missing.
+    #[serde(default)]
+    pub lang: Option<String>,
+}
+
+impl RemoteMessage {
+    fn is_for(&self, language: &Language) -> bool {
+        let code = match language {
+            Language::English => "en",
+            Language::Russian => "ru",
+        };
+        self.lang.as_deref().is_none_or(|lang| lang.eq_ignore_ascii_case(code))
+    }
+}
+
+// Served through the HTTP cache, so the last fetched MOTD stays up offline.
+pub async fn fetch(network: &NetworkManager, url: &str) -> Result<RemoteMotd> {
+    let mut motd: RemoteMotd = network.get_json(url).await?;
+    motd.messages.retain(|message| !message.text.trim().is_empty());
+    motd.art.retain(|art| {
+        !art.is_empty()
+            && art.len() <= MAX_ART_HEIGHT
+            && art.iter().all(|line| line.chars().count() <= MAX_ART_WIDTH)
+    });
+    Ok(motd)
+}
+
+/// The line and art in the left panel, taking turns through the remote
+/// MOTD and the built-in pool.
+#[derive(Debug, Clone)]
+pub struct Motd {
+    remote: RemoteMotd,
+    line: usize,
+    art: usize,
+    shown_at: Instant,
+}
+
+impl Default for Motd {
+    fn default() -> Self {
+        Self { remote: RemoteMotd::default(), line: 0, art: 0, shown_at: Instant::now() }
+    }
+}
+
+impl Motd {
+    // Remote lines go first, so a fresh MOTD is shown right away.
+    pub fn set_remote(&mut self, remote: RemoteMotd) {
+        self.remote = remote;
+        self.line = 0;
+        self.shown_at = Instant::now();
+    }
+
+    pub fn text(&self) -> String {
+        let language = i18n::language();
+        let remote: Vec<&str> = self.remote.messages.iter()
+            .filter(|message| message.is_for(&language))
+            .map(|message| message.text.as_str())
+            .collect();
+        let index = self.line % (remote.len() + TIPS.len());
+        match remote.get(index) {
+            Some(text) => text.to_string(),
+            None => tr(TIPS[index - remote.len()]).to_string(),
+        }
+    }
+
+    pub fn art(&self) -> Vec<&str> {
+        let count = BUILTIN_ART.len() + self.remote.art.len();
+        match BUILTIN_ART.get(self.art % count) {
+            Some(art) => art.to_vec(),
+            None => self.remote.art[self.art % count - BUILTIN_ART.len()].iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// Moves on to the next line, and the next art when `art` is set, once
+    /// the current ones have been up long enough. Returns whether anything
+    /// changed.
+    pub fn rotate(&mut self, art: bool) -> bool {
+        if self.shown_at.elapsed() < ROTATE_EVERY {
+            return false;
+        }
+        self.line = self.line.wrapping_add(1);
+        if art {
+            self.art = self.art.wrapping_add(1);
+        }
+        self.shown_at = Instant::now();
+        true
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use sha2::Sha512;
+
+use crate::instance::ModLoader;
+use crate::modrinth::ModrinthClient;
+use crate::network::NetworkManager;
+use crate::Result;
+
+pub const MRPACK_INDEX: &str = "modrinth.index.json";
+pub const MRPACK_OVERRIDES: &str = "overrides";
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrpackIndex {
+    pub format_version: u32,
+    pub game: String,
+    pub version_id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    pub files: Vec<MrpackFile>,
+    pub dependencies: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrpackFile {
+    pub path: String,
+    pub hashes: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<MrpackEnv>,
+    pub downloads: Vec<String>,
+    pub file_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MrpackEnv {
+    pub client: String,
+    pub server: String,
+}
+
+pub fn loader_dependency_key(loader: &ModLoader) -> &'static str {
+    match loader {
+        ModLoader::Forge => "forge",
+        ModLoader::Fabric => "fabric-loader",
+        ModLoader::Quilt => "quilt-loader",
+        ModLoader::NeoForge => "neoforge",
+    }
+}
+
+pub fn hash_file(path: &Path) -> Result<(String, String)> {
+    let mut file = std::fs::File::open(path)?;
+    let mut sha1 = Sha1::new();
+    let mut sha512 = Sha512::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        sha1.update(&buffer[..read]);
+        sha512.update(&buffer[..read]);
+    }
+
+    Ok((hex::encode(sha1.finalize()), hex::encode(sha512.finalize())))
+}
+
+pub async fn resolve_remote_mods(network: &NetworkManager, mods_dir: &Path) -> Result<HashMap<String, MrpackFile>> {
+    let mut local = HashMap::new();
+    if mods_dir.exists() {
+        for name in crate::mods::ModManager::list_enabled_mod_files(mods_dir)? {
+            let (sha1, _) = hash_file(&mods_dir.join(&name))?;
+            local.insert(sha1, name);
+        }
+    }
+    if local.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let hashes: Vec<String> = local.keys().cloned().collect();
+    let versions = ModrinthClient::new(network.clone()).versions_from_hashes(&hashes).await?;
+
+    let mut remote = HashMap::new();
+    for (sha1, version) in versions {
+        let Some(name) = local.get(&sha1) else { continue };
+        let Some(file) = version.files.into_iter().find(|f| f.hashes.get("sha1") == Some(&sha1)) else { continue };
+
+        let path = format!("mods/{}", name);
+        remote.insert(path.clone(), MrpackFile {
+            path,
+            hashes: file.hashes,
+            env: None,
+            downloads: vec![file.url],
+            file_size: file.size,
+        });
+    }
+    Ok(remote)
+}
@@ -1,31 +1,215 @@
  
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 use sha1::{Sha1, Digest};
 use tokio::io::AsyncWriteExt;
 use crate::{Error, Result};
-use crate::progress::ProgressDialog;
-use reqwest::Client;
+use crate::settings::{NetworkSettings, DEFAULT_LIBRARIES_URL, DEFAULT_META_URL, DEFAULT_RESOURCES_URL};
+use reqwest::header::{HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Client, Proxy, StatusCode, Url};
 use serde::de::DeserializeOwned;
 
-use ratatui::Terminal;
-use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
-use ratatui::backend::CrosstermBackend;
 
 
 pub type ProgressCallback = Box<dyn Fn(u64, u64) + Send + Sync>;
 
+// Worth trying another host for: the file is missing there, the host is
+// down or slow, or it served something other than what was asked for.
+fn is_retryable(error: &Error) -> bool {
+    match error {
+        Error::Network(e) => {
+            e.is_timeout() || e.is_connect() || e.status().is_some_and(|status| status == StatusCode::NOT_FOUND || status.is_server_error())
+        }
+        Error::HashMismatch { .. } => true,
+        _ => false,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct NetworkManager {
     client: Client,
     cache: Cache,
     max_concurrent_downloads: usize,
+    max_concurrent_asset_downloads: usize,
+    download_slots: DownloadSlots,
+    mirrors: Mirrors,
+    http_cache: Option<Arc<tokio::sync::Mutex<HttpCache>>>,
+}
+
+const HTTP_CACHE_MAX_SIZE: u64 = 256 * 1024 * 1024;
+const HTTP_CACHE_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+const META_HOSTS: [&str; 4] = [
+    "https://launchermeta.mojang.com",
+    "https://piston-meta.mojang.com",
+    "https://piston-data.mojang.com",
+    "https://launcher.mojang.com",
+];
+
+// Transfers running at once across every clone of the manager, so installs
+// and imports going on side by side stay within the configured limit
+// together. Each kind of download is already held to its own setting by the
+// stream that drives it; this pool caps all of them together at the larger
+// of the two settings, so a library install next to an asset download never
+// waits on the smaller limit.
+#[derive(Debug, Clone)]
+struct DownloadSlots {
+    semaphore: Arc<tokio::sync::Semaphore>,
+    state: Arc<std::sync::Mutex<SlotCount>>,
+}
+
+#[derive(Debug)]
+struct SlotCount {
+    size: usize,
+    // Slots removed by a shrink while downloads held them; each is dropped
+    // instead of returned when a download finishes.
+    owed: usize,
+}
+
+struct DownloadSlot<'a> {
+    permit: Option<tokio::sync::SemaphorePermit<'a>>,
+    state: &'a std::sync::Mutex<SlotCount>,
+}
+
+impl Drop for DownloadSlot<'_> {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        if state.owed > 0 {
+            state.owed -= 1;
+            if let Some(permit) = self.permit.take() {
+                permit.forget();
+            }
+        }
+    }
+}
+
+impl DownloadSlots {
+    fn new(size: usize) -> Self {
+        let size = size.max(1);
+        Self {
+            semaphore: Arc::new(tokio::sync::Semaphore::new(size)),
+            state: Arc::new(std::sync::Mutex::new(SlotCount { size, owed: 0 })),
+        }
+    }
+
+    // Free slots go at once; the ones running downloads hold are owed and
+    // go as those downloads finish. Growing pays off what is still owed
+    // before adding slots.
+    fn resize(&self, size: usize) {
+        let size = size.max(1);
+        let mut state = self.state.lock().unwrap();
+        if size > state.size {
+            let added = size - state.size;
+            let repaid = added.min(state.owed);
+            state.owed -= repaid;
+            self.semaphore.add_permits(added - repaid);
+        } else {
+            let removed = state.size - size;
+            state.owed += removed - self.semaphore.forget_permits(removed);
+        }
+        state.size = size;
+    }
+
+    async fn acquire(&self) -> DownloadSlot<'_> {
+        let permit = self.semaphore.acquire().await.expect("download slots are never closed");
+        DownloadSlot { permit: Some(permit), state: &self.state }
+    }
+}
+
+fn download_slot_count(settings: &NetworkSettings) -> usize {
+    settings.max_concurrent_downloads.max(settings.max_concurrent_asset_downloads) as usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum MirrorKind {
+    Resources,
+    Libraries,
+}
+
+#[derive(Debug, Clone)]
+struct Mirrors {
+    meta: Option<String>,
+    resources: Option<String>,
+    libraries: Option<String>,
+    resources_fallbacks: Vec<String>,
+    libraries_fallbacks: Vec<String>,
+    // The host that last served a file after the first choice failed. It is
+    // tried first from then on, until the settings change.
+    preferred: Arc<std::sync::Mutex<HashMap<MirrorKind, String>>>,
+}
+
+impl Mirrors {
+    fn from_settings(settings: &NetworkSettings) -> Self {
+        let mirror = |url: &str, default: &str| {
+            let url = url.trim().trim_end_matches('/');
+            (!url.is_empty() && url != default).then(|| url.to_string())
+        };
+        let hosts = |urls: &str| {
+            urls.split_whitespace().map(|url| url.trim_end_matches('/').to_string()).collect()
+        };
+        Self {
+            meta: mirror(&settings.meta_url, DEFAULT_META_URL),
+            resources: mirror(&settings.resources_url, DEFAULT_RESOURCES_URL),
+            libraries: mirror(&settings.libraries_url, DEFAULT_LIBRARIES_URL),
+            resources_fallbacks: hosts(&settings.fallback_resources_urls),
+            libraries_fallbacks: hosts(&settings.fallback_libraries_urls),
+            preferred: Arc::default(),
+        }
+    }
+
+    // Every URL a library or asset can be fetched from, paired with its host,
+    // in the order to try them: the host that worked last, the configured
+    // one, the official one, then the fallbacks. Other URLs only get their
+    // resolved form.
+    fn candidates(&self, url: &str) -> (Option<MirrorKind>, Vec<(String, String)>) {
+        let kinds = [
+            (MirrorKind::Resources, DEFAULT_RESOURCES_URL, &self.resources, &self.resources_fallbacks),
+            (MirrorKind::Libraries, DEFAULT_LIBRARIES_URL, &self.libraries, &self.libraries_fallbacks),
+        ];
+        for (kind, official, mirror, fallbacks) in kinds {
+            let Some(rest) = url.strip_prefix(official).filter(|rest| rest.starts_with('/')) else { continue };
+            let preferred = self.preferred.lock().unwrap().get(&kind).cloned();
+            let mut hosts: Vec<String> = Vec::new();
+            for host in preferred.into_iter()
+                .chain(mirror.clone())
+                .chain([official.to_string()])
+                .chain(fallbacks.iter().cloned())
+            {
+                if !hosts.contains(&host) {
+                    hosts.push(host);
+                }
+            }
+            let candidates = hosts.into_iter()
+                .map(|host| {
+                    let url = format!("{}{}", host, rest);
+                    (host, url)
+                })
+                .collect();
+            return (Some(kind), candidates);
+        }
+        (None, vec![(String::new(), self.resolve(url).into_owned())])
+    }
+
+    fn prefer(&self, kind: MirrorKind, host: &str) {
+        self.preferred.lock().unwrap().insert(kind, host.to_string());
+    }
+
+    fn resolve<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        let hosts = META_HOSTS.iter().map(|host| (*host, &self.meta))
+            .chain([(DEFAULT_RESOURCES_URL, &self.resources), (DEFAULT_LIBRARIES_URL, &self.libraries)]);
+        for (host, mirror) in hosts {
+            if let (Some(mirror), Some(rest)) = (mirror, url.strip_prefix(host)) {
+                if rest.is_empty() || rest.starts_with('/') {
+                    return Cow::Owned(format!("{}{}", mirror, rest));
+                }
+            }
+        }
+        Cow::Borrowed(url)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +222,8 @@ pub struct CacheEntry {
     pub expires_at: Option<u64>,
     pub etag: Option<String>,
     pub content_type: Option<String>,
+    #[serde(default)]
+    pub last_modified: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -56,43 +242,192 @@ pub struct DownloadProgress {
     pub eta: Option<Duration>,
 }
 
-impl NetworkManager {
-    pub fn new(_cache_dir: PathBuf, max_concurrent_downloads: usize) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
-
-        Self {
-            client,
-            cache: Cache::new(),
-            max_concurrent_downloads,
-        }
+fn build_client(settings: &NetworkSettings) -> Result<Client> {
+    let mut builder = Client::builder()
+        .timeout(Duration::from_secs(settings.timeout.max(1)))
+        .user_agent(settings.user_agent.as_str());
+    if settings.use_proxy {
+        builder = builder.proxy(build_proxy(settings)?);
     }
+    Ok(builder.build()?)
+}
+
+fn build_proxy(settings: &NetworkSettings) -> Result<Proxy> {
+    let scheme = match settings.proxy_type.as_str() {
+        "http" => "http",
+        "socks5" => "socks5h",
+        other => return Err(Error::Settings(format!("Unsupported proxy type: {}", other))),
+    };
+    let mut url = Url::parse(&format!("{}://{}:{}", scheme, settings.proxy_host, settings.proxy_port))
+        .map_err(|e| Error::Settings(format!("Invalid proxy address: {}", e)))?;
+    if let Some(username) = settings.proxy_username.as_deref().filter(|u| !u.is_empty()) {
+        url.set_username(username)
+            .map_err(|_| Error::Settings("Invalid proxy username".to_string()))?;
+        url.set_password(settings.proxy_password.as_deref())
+            .map_err(|_| Error::Settings("Invalid proxy password".to_string()))?;
+    }
+    Ok(Proxy::all(url)?)
+}
 
-    pub fn set_max_concurrent_downloads(&mut self, max_concurrent: usize) {
-        self.max_concurrent_downloads = max_concurrent;
+impl NetworkManager {
+    pub fn new(cache_dir: PathBuf, settings: &NetworkSettings) -> Result<Self> {
+        let http_cache = match HttpCache::new(cache_dir.join("http"), HTTP_CACHE_MAX_SIZE, HTTP_CACHE_MAX_AGE) {
+            Ok(cache) => Some(Arc::new(tokio::sync::Mutex::new(cache))),
+            Err(e) => {
+                tracing::warn!("HTTP cache is unavailable: {}", e);
+                None
+            }
+        };
+        Ok(Self {
+            client: build_client(settings)?,
+            cache: Cache::new(),
+            max_concurrent_downloads: settings.max_concurrent_downloads as usize,
+            max_concurrent_asset_downloads: settings.max_concurrent_asset_downloads as usize,
+            download_slots: DownloadSlots::new(download_slot_count(settings)),
+            mirrors: Mirrors::from_settings(settings),
+            http_cache,
+        })
+    }
+
+    pub fn apply_settings(&mut self, settings: &NetworkSettings) -> Result<()> {
+        self.client = build_client(settings)?;
+        self.max_concurrent_downloads = settings.max_concurrent_downloads as usize;
+        self.max_concurrent_asset_downloads = settings.max_concurrent_asset_downloads as usize;
+        self.download_slots.resize(download_slot_count(settings));
+        self.mirrors = Mirrors::from_settings(settings);
+        Ok(())
     }
 
     pub fn get_max_concurrent_downloads(&self) -> usize {
         self.max_concurrent_downloads
     }
 
+    pub fn get_max_concurrent_asset_downloads(&self) -> usize {
+        self.max_concurrent_asset_downloads
+    }
+
+    pub fn resolve_url<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        self.mirrors.resolve(url)
+    }
+
     pub async fn get(&self, url: &str) -> Result<String> {
-        let response = self.client.get(url).send().await?;
-        let text = response.text().await?;
-        Ok(text)
+        let (data, _) = self.fetch_cached(url).await?;
+        Ok(String::from_utf8_lossy(&data).into_owned())
     }
 
-    pub async fn get_json<T>(&self, url: &str) -> Result<T>
+    // Also returns whether the body is a cached copy served because the
+    // request failed, i.e. possibly out of date.
+    async fn fetch_cached(&self, url: &str) -> Result<(Vec<u8>, bool)> {
+        let url = self.resolve_url(url);
+        let Some(cache) = &self.http_cache else {
+            let response = self.client.get(url.as_ref()).send().await?.error_for_status()?;
+            return Ok((response.bytes().await?.to_vec(), false));
+        };
+
+        let cached = cache.lock().await.lookup(&url);
+        let mut request = self.client.get(url.as_ref());
+        if let Some(entry) = &cached {
+            if let Some(etag) = &entry.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = match request.send().await.and_then(|r| r.error_for_status()) {
+            Ok(response) => response,
+            Err(e) => {
+                let Some(entry) = cached else { return Err(e.into()) };
+                tracing::warn!("Request to {} failed, using cached copy: {}", url, e);
+                return Ok((tokio::fs::read(&entry.file_path).await?, true));
+            }
+        };
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(entry) = cached {
+                cache.lock().await.touch(&url);
+                return Ok((tokio::fs::read(&entry.file_path).await?, false));
+            }
+        }
+
+        let header = |headers: &HeaderMap, name| {
+            headers.get(name).and_then(|v| v.to_str().ok()).map(str::to_string)
+        };
+        let etag = header(response.headers(), ETAG);
+        let last_modified = header(response.headers(), LAST_MODIFIED);
+        let data = response.bytes().await?.to_vec();
+
+        if let Err(e) = cache.lock().await.store_bytes(&url, &data, etag, last_modified).await {
+            tracing::warn!("Failed to cache {}: {}", url, e);
+        }
+        Ok((data, false))
+    }
+
+    pub async fn probe(&self, url: &str) -> Result<reqwest::StatusCode> {
+        self.probe_within(url, Duration::from_secs(5)).await
+    }
+
+    pub async fn probe_within(&self, url: &str, timeout: Duration) -> Result<reqwest::StatusCode> {
+        let response = self.client
+            .head(url)
+            .timeout(timeout)
+            .send()
+            .await?;
+        Ok(response.status())
+    }
+
+    pub async fn post_json<B, T>(&self, url: &str, body: &B) -> Result<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        self.post_json_with_headers(url, &[], body).await
+    }
+
+    pub async fn post_json_with_headers<B, T>(&self, url: &str, headers: &[(&str, &str)], body: &B) -> Result<T>
     where
+        B: Serialize + ?Sized,
         T: DeserializeOwned,
     {
-        let text = self.get(url).await?;
-        let data = serde_json::from_str(&text)?;
+        let mut request = self.client.post(url).json(body);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let data = request.send().await?.error_for_status()?.json().await?;
         Ok(data)
     }
 
+    pub async fn get_json_with_headers<T>(&self, url: &str, headers: &[(&str, &str)]) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let mut request = self.client.get(self.resolve_url(url).as_ref());
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let data = request.send().await?.error_for_status()?.json().await?;
+        Ok(data)
+    }
+
+    pub async fn get_json<T>(&self, url: &str) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        Ok(self.get_json_or_cached(url).await?.0)
+    }
+
+    /// Like `get_json`, also telling whether the request failed and the
+    /// value is the last cached copy.
+    pub async fn get_json_or_cached<T>(&self, url: &str) -> Result<(T, bool)>
+    where
+        T: DeserializeOwned,
+    {
+        let (bytes, from_cache) = self.fetch_cached(url).await?;
+        let data = serde_json::from_slice(&bytes)?;
+        Ok((data, from_cache))
+    }
+
     pub async fn download_file(
         &self,
         url: &str,
@@ -113,41 +448,67 @@ impl NetworkManager {
             std::fs::create_dir_all(parent)?;
         }
 
-        let response = self.client.get(url).send().await?;
+        // Files are streamed to disk and never go through the HTTP cache,
+        // which is meant for small metadata, not JREs or launcher archives.
+        let _slot = self.download_slots.acquire().await;
+        let (kind, candidates) = self.mirrors.candidates(url);
+        let mut last_error = None;
+        for (index, (host, candidate)) in candidates.iter().enumerate() {
+            match self.fetch_to_file(candidate, path, expected_hash, progress_callback.as_ref()).await {
+                Ok(()) => {
+                    if let (Some(kind), true) = (kind, index > 0) {
+                        tracing::warn!("Downloaded {} from fallback host {}", url, host);
+                        self.mirrors.prefer(kind, host);
+                    }
+                    return Ok(());
+                }
+                Err(e) if index + 1 < candidates.len() && is_retryable(&e) => {
+                    tracing::warn!("Download from {} failed, trying the next host: {}", candidate, e);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| Error::Other(format!("No host to download {} from", url))))
+    }
+
+    async fn fetch_to_file(
+        &self,
+        url: &str,
+        path: &Path,
+        expected_hash: Option<&str>,
+        progress_callback: Option<&ProgressCallback>,
+    ) -> Result<()> {
+        let mut response = self.client.get(url).send().await?.error_for_status()?;
         let total_size = response.content_length().unwrap_or(0);
 
         let mut file = tokio::fs::File::create(path).await?;
         let mut downloaded = 0u64;
-
-        let bytes = response.bytes().await?;
-        let mut pos = 0;
-        let chunk_size = 8192;
-        
-        while pos < bytes.len() {
-            let end = std::cmp::min(pos + chunk_size, bytes.len());
-            let chunk = &bytes[pos..end];
-            
-            file.write_all(chunk).await?;
-            
-            downloaded += chunk.len() as u64;
-            
-            if let Some(ref callback) = progress_callback {
-                callback(downloaded, total_size);
+        let streamed: Result<()> = async {
+            while let Some(chunk) = response.chunk().await? {
+                file.write_all(&chunk).await?;
+                downloaded += chunk.len() as u64;
+                if let Some(callback) = progress_callback {
+                    callback(downloaded, total_size);
+                }
             }
-            
-                        pos = end;
+            Ok(file.flush().await?)
+        }.await;
+        // A cut-off transfer must not be left behind looking like the file.
+        if let Err(e) = streamed {
+            std::fs::remove_file(path).ok();
+            return Err(e);
         }
-        
-
-        file.flush().await?;
 
         if let Some(expected) = expected_hash {
             let actual_hash = self.calculate_file_hash(path).await?;
             if actual_hash != expected {
                 std::fs::remove_file(path).ok();
-                return Err(Error::Other(format!(
-                    "Hash mismatch: expected {}, got {}", expected, actual_hash
-                )));
+                return Err(Error::HashMismatch {
+                    path: path.to_path_buf(),
+                    expected: expected.to_string(),
+                    actual: actual_hash,
+                });
             }
         }
 
@@ -155,10 +516,7 @@ impl NetworkManager {
     }
 
     async fn calculate_file_hash(&self, path: &Path) -> Result<String> {
-        let contents = tokio::fs::read(path).await?;
-        let mut hasher = Sha1::new();
-        hasher.update(&contents);
-        Ok(hex::encode(hasher.finalize()))
+        crate::utils::file_sha1(path).await
     }
 
     pub async fn download_with_retries(
@@ -191,6 +549,15 @@ impl NetworkManager {
         Err(last_error.unwrap())
     }
 
+    pub async fn cleanup_expired_cache(&self) -> Result<(usize, u64)> {
+        let Some(cache) = &self.http_cache else { return Ok((0, 0)) };
+        let mut cache = cache.lock().await;
+        let (count, size, _) = cache.get_cache_info();
+        cache.cleanup_expired().await?;
+        let (remaining, remaining_size, _) = cache.get_cache_info();
+        Ok((count - remaining, size.saturating_sub(remaining_size)))
+    }
+
     pub fn get_cache(&self) -> &Cache {
         &self.cache
     }
@@ -198,156 +565,6 @@ impl NetworkManager {
     pub fn get_cache_mut(&mut self) -> &mut Cache {
         &mut self.cache
     }
-
-    pub async fn download_with_progress_dialog(
-        &self,
-        url: &str,
-        path: &Path,
-        expected_hash: Option<&str>,
-        filename: String,
-    ) -> Result<bool> {
-        if path.exists() {
-            if let Some(hash) = expected_hash {
-                let existing_hash = self.calculate_file_hash(path).await?;
-                if existing_hash == hash {
-                    return Ok(true);
-                }
-            }
-        }
-
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
-        enable_raw_mode()?;
-        let mut stdout = std::io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
-
-        let mut progress_dialog = ProgressDialog::new(filename);
-        
-        terminal.draw(|f| {
-            let area = f.size();
-            progress_dialog.draw(f, area);
-        })?;
-        
-        let response = self.client.get(url).send().await?;
-        let total_size = response.content_length().unwrap_or(0);
-        let mut file = tokio::fs::File::create(path).await?;
-        let mut downloaded = 0u64;
-
-        let bytes = response.bytes().await?;
-        let mut pos = 0;
-        let chunk_size = 8192;
-        
-        while pos < bytes.len() {
-            let end = std::cmp::min(pos + chunk_size, bytes.len());
-            let chunk = &bytes[pos..end];
-            
-            file.write_all(chunk).await?;
-            
-            downloaded += chunk.len() as u64;
-            progress_dialog.update_progress(downloaded, total_size);
-            
-            if !progress_dialog.handle_input() {
-                Self::cleanup_terminal(&mut terminal)?;
-                if path.exists() {
-                    std::fs::remove_file(path).ok();
-                }
-                return Ok(false);
-            }
-            
-            if let Err(_) = terminal.draw(|f| {
-                let area = f.size();
-                progress_dialog.draw(f, area);
-            }) {
-                Self::cleanup_terminal(&mut terminal)?;
-                if path.exists() {
-                    std::fs::remove_file(path).ok();
-                }
-                return Ok(false);
-            }
-            
-            pos = end;
-        }
-        
-        file.flush().await?;
-
-        if let Some(expected) = expected_hash {
-            let actual_hash = self.calculate_file_hash(path).await?;
-            if actual_hash != expected {
-                Self::cleanup_terminal(&mut terminal)?;
-                std::fs::remove_file(path).ok();
-                return Err(Error::Other(format!(
-                    "Hash mismatch: expected {}, got {}", expected, actual_hash
-                )));
-            }
-        }
-
-    
-        progress_dialog.update_progress(total_size, total_size);
-        terminal.draw(|f| {
-            let area = f.size();
-            progress_dialog.draw(f, area);
-        })?;
-        tokio::time::sleep(Duration::from_millis(1500)).await;
-
-        Self::cleanup_terminal(&mut terminal)?;
-        Ok(true)
-    }
-
-    pub async fn download_files_concurrent(
-        &self,
-        files: Vec<(String, PathBuf, Option<String>)>, // (url, path, expected_hash)
-    ) -> Result<Vec<bool>> {
-        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(self.max_concurrent_downloads));
-        let mut handles = Vec::new();
-
-        for (url, path, expected_hash) in files {
-            let permit = semaphore.clone();
-            let network = self.clone();
-            
-            let handle = tokio::spawn(async move {
-                let _permit = permit.acquire().await.unwrap();
-                
-                let filename = path.file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("file")
-                    .to_string();
-
-                network.download_with_progress_dialog(
-                    &url,
-                    &path,
-                    expected_hash.as_deref(),
-                    filename,
-                ).await
-            });
-            
-            handles.push(handle);
-        }
-
-        let mut results = Vec::new();
-        for handle in handles {
-            match handle.await {
-                Ok(result) => results.push(result?),
-                Err(e) => return Err(Error::Other(format!("Task join error: {}", e)).into()),
-            }
-        }
-
-        Ok(results)
-    }
-
-    fn cleanup_terminal(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> Result<()> {
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        )?;
-        terminal.show_cursor()?;
-        Ok(())
-    }
 }
 
 #[derive(Debug, Clone)]
@@ -365,6 +582,12 @@ pub struct Cache {
     current_size: u64,
 }
 
+impl Default for Cache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Cache {
     pub fn new() -> Self {
         Self {
@@ -473,10 +696,8 @@ impl HttpCache {
                 }
             }
             
-            if expected_hash.map_or(true, |h| h == entry.hash) {
-                if entry.file_path.exists() {
-                    return Ok(Some(entry.file_path.clone()));
-                }
+            if expected_hash.is_none_or(|h| h == entry.hash) && entry.file_path.exists() {
+                return Ok(Some(entry.file_path.clone()));
             }
         }
         
@@ -501,6 +722,7 @@ impl HttpCache {
             expires_at: Some(now + self.max_age.as_secs()),
             etag: None,
             content_type: None,
+            last_modified: None,
         };
         
         self.entries.insert(url_hash, entry);
@@ -510,6 +732,56 @@ impl HttpCache {
         Ok(())
     }
 
+    pub fn lookup(&self, url: &str) -> Option<CacheEntry> {
+        self.entries.get(&Self::hash_url(url))
+            .filter(|entry| entry.file_path.exists())
+            .cloned()
+    }
+
+    pub async fn store_bytes(
+        &mut self,
+        url: &str,
+        data: &[u8],
+        etag: Option<String>,
+        last_modified: Option<String>,
+    ) -> Result<()> {
+        let url_hash = Self::hash_url(url);
+        let cached_path = self.cache_dir.join(&url_hash);
+        tokio::fs::write(&cached_path, data).await?;
+
+        let mut hasher = Sha1::new();
+        hasher.update(data);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        let entry = CacheEntry {
+            url: url.to_string(),
+            file_path: cached_path,
+            hash: hex::encode(hasher.finalize()),
+            size: data.len() as u64,
+            created_at: now,
+            expires_at: Some(now + self.max_age.as_secs()),
+            etag,
+            content_type: None,
+            last_modified,
+        };
+
+        self.entries.insert(url_hash, entry);
+        self.save_metadata()?;
+        self.cleanup_if_needed().await?;
+
+        Ok(())
+    }
+
+    pub fn touch(&mut self, url: &str) {
+        let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) else { return };
+        if let Some(entry) = self.entries.get_mut(&Self::hash_url(url)) {
+            entry.expires_at = Some(now.as_secs() + self.max_age.as_secs());
+            if let Err(e) = self.save_metadata() {
+                tracing::warn!("Failed to save HTTP cache metadata: {}", e);
+            }
+        }
+    }
+
     pub async fn clear(&mut self) -> Result<()> {
         for entry in self.entries.values() {
             if entry.file_path.exists() {
@@ -541,10 +813,7 @@ impl HttpCache {
             self.entries.remove(&key);
         }
         
-        if !self.entries.is_empty() {
-            self.save_metadata()?;
-        }
-        
+        self.save_metadata()?;
         Ok(())
     }
 
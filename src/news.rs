@@ -0,0 +1,48 @@
+use serde::Deserialize;
+
+use crate::network::NetworkManager;
+use crate::Result;
+
+const PATCH_NOTES_URL: &str = "https://launchercontent.mojang.com/v2/javaPatchNotes.json";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchNote {
+    pub id: String,
+    pub title: String,
+    pub version: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub date: String,
+    #[serde(default)]
+    pub short_text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PatchNotes {
+    entries: Vec<PatchNote>,
+}
+
+impl PatchNote {
+    pub fn date_label(&self) -> &str {
+        self.date.get(..10).unwrap_or(&self.date)
+    }
+
+    pub fn summary(&self) -> String {
+        self.short_text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+// Served through the HTTP cache, so the last fetched notes stay readable
+// offline.
+pub async fn fetch_patch_notes(network: &NetworkManager) -> Result<Vec<PatchNote>> {
+    let notes: PatchNotes = network.get_json(PATCH_NOTES_URL).await?;
+    let mut entries = notes.entries;
+    entries.sort_by(|a, b| b.date.cmp(&a.date));
+    Ok(entries)
+}
+
+pub fn find_version(entries: &[PatchNote], version: &str) -> Option<usize> {
+    entries.iter().position(|entry| entry.version == version)
+}
@@ -0,0 +1,314 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::i18n::trf;
+use crate::assets::AssetIndex;
+use crate::import::{self, ImportKind, ImportPlan, ImportSource};
+use crate::instance::{InstanceLinks, ModLoader};
+use crate::version::{Artifact, Downloads, Library};
+use crate::{Error, Result};
+
+pub const PROFILES_FILE: &str = "launcher_profiles.json";
+const ACCOUNT_FILES: &[&str] = &["launcher_accounts_microsoft_store.json", "launcher_accounts.json"];
+
+/// Where the official launcher keeps its data on this platform.
+pub fn default_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        dirs::config_dir().map(|dir| dir.join(".minecraft"))
+    } else if cfg!(target_os = "macos") {
+        dirs::data_dir().map(|dir| dir.join("minecraft"))
+    } else {
+        dirs::home_dir().map(|dir| dir.join(".minecraft"))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LauncherProfiles {
+    #[serde(default)]
+    profiles: BTreeMap<String, LauncherProfile>,
+    // Launchers before 2.2 kept signed-in accounts here.
+    #[serde(default)]
+    authentication_database: BTreeMap<String, LegacyAccount>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LauncherProfile {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type", default)]
+    kind: String,
+    last_version_id: Option<String>,
+    game_dir: Option<PathBuf>,
+    java_args: Option<String>,
+    resolution: Option<Resolution>,
+    icon: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Resolution {
+    width: u32,
+    height: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LegacyAccount {
+    #[serde(default)]
+    profiles: BTreeMap<String, LegacyAccountProfile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LegacyAccountProfile {
+    display_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LauncherAccounts {
+    #[serde(default)]
+    accounts: BTreeMap<String, LauncherAccount>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LauncherAccount {
+    minecraft_profile: Option<MinecraftProfile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MinecraftProfile {
+    name: String,
+}
+
+// Just the parts of a version JSON needed to find its files; loader
+// versions point at the vanilla one through `inheritsFrom`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LocalVersion {
+    inherits_from: Option<String>,
+    downloads: Option<Downloads>,
+    asset_index: Option<AssetIndexId>,
+    #[serde(default)]
+    libraries: Vec<Library>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssetIndexId {
+    id: String,
+}
+
+/// What an official launcher installation holds.
+#[derive(Debug, Clone)]
+pub struct OfficialLauncher {
+    pub root: PathBuf,
+    /// One instance per launcher profile.
+    pub plans: Vec<ImportPlan>,
+    /// Names of the signed-in Minecraft profiles. Their tokens cannot be
+    /// carried over, so they are only reported.
+    pub account_hints: Vec<String>,
+}
+
+// "latest-release"/"latest-snapshot" profiles follow the manifest, which the
+// caller resolves.
+pub fn read(root: &Path, latest_release: Option<&str>, latest_snapshot: Option<&str>) -> Result<OfficialLauncher> {
+    let profiles_path = root.join(PROFILES_FILE);
+    if !profiles_path.is_file() {
+        return Err(Error::Instance(format!("{} not found in {}", PROFILES_FILE, root.display())));
+    }
+    let profiles: LauncherProfiles = serde_json::from_str(&std::fs::read_to_string(&profiles_path)?)?;
+
+    let mut plans = Vec::new();
+    for (key, profile) in &profiles.profiles {
+        let version_id = match (profile.kind.as_str(), profile.last_version_id.as_deref()) {
+            ("latest-release", _) | (_, Some("latest-release")) => latest_release,
+            ("latest-snapshot", _) | (_, Some("latest-snapshot")) => latest_snapshot,
+            (_, version) => version,
+        };
+        let Some(version_id) = version_id else {
+            tracing::warn!("{}", trf("log.official_profile_skipped", &[("name", &key)]));
+            continue;
+        };
+        plans.push(profile_plan(root, profile, version_id));
+    }
+
+    let mut hints: BTreeSet<String> = profiles.authentication_database
+        .values()
+        .flat_map(|account| account.profiles.values().map(|profile| profile.display_name.clone()))
+        .collect();
+    for file in ACCOUNT_FILES {
+        let Ok(content) = std::fs::read_to_string(root.join(file)) else { continue };
+        if let Ok(accounts) = serde_json::from_str::<LauncherAccounts>(&content) {
+            hints.extend(accounts.accounts.into_values().filter_map(|account| Some(account.minecraft_profile?.name)));
+        }
+    }
+
+    Ok(OfficialLauncher {
+        root: root.to_path_buf(),
+        plans,
+        account_hints: hints.into_iter().collect(),
+    })
+}
+
+fn profile_plan(root: &Path, profile: &LauncherProfile, version_id: &str) -> ImportPlan {
+    let local = read_local_version(root, version_id);
+    let loader = loader_from_version_id(version_id);
+    let minecraft_version = local.as_ref()
+        .and_then(|version| version.inherits_from.clone())
+        .or_else(|| loader.as_ref().and_then(|(_, _, minecraft)| minecraft.clone()))
+        .unwrap_or_else(|| version_id.to_string());
+    let name = match (profile.name.trim(), profile.kind.as_str()) {
+        ("", "latest-release") => "Latest release".to_string(),
+        ("", "latest-snapshot") => "Latest snapshot".to_string(),
+        ("", _) => version_id.to_string(),
+        (name, _) => name.to_string(),
+    };
+    let (memory_min, memory_max, java_args) = split_memory_args(profile.java_args.as_deref().unwrap_or_default());
+    let (mod_loader, mod_loader_version) = match loader {
+        Some((loader, version, _)) => (Some(loader), Some(version)),
+        None => (None, None),
+    };
+
+    ImportPlan {
+        kind: ImportKind::Official,
+        name,
+        pack_version: None,
+        minecraft_version,
+        mod_loader,
+        mod_loader_version,
+        java_args,
+        memory_min,
+        memory_max,
+        width: profile.resolution.as_ref().map(|r| r.width),
+        height: profile.resolution.as_ref().map(|r| r.height),
+        fullscreen: None,
+        notes: None,
+        icon: profile.icon.as_deref().and_then(launcher_icon).map(str::to_string),
+        pre_launch_command: None,
+        post_exit_command: None,
+        links: InstanceLinks::default(),
+        game_files: vec![ImportSource::LauncherRoot(profile.game_dir.clone().unwrap_or_else(|| root.to_path_buf()))],
+        pending_downloads: Vec::new(),
+    }
+}
+
+// Built-in icons are block names such as "Diamond_Block"; custom ones are
+// data URLs and get no glyph.
+fn launcher_icon(icon: &str) -> Option<&'static str> {
+    let block = icon.to_ascii_lowercase();
+    match block.split('_').next()? {
+        "furnace" | "campfire" => Some("🔥"),
+        "crafting" | "bookshelf" | "lectern" => Some("⚒"),
+        word => import::multimc_icon(word),
+    }
+}
+
+fn read_local_version(root: &Path, version_id: &str) -> Option<LocalVersion> {
+    let path = root.join("versions").join(version_id).join(format!("{}.json", version_id));
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+// Loader, loader version and, where the id carries it, the Minecraft version:
+// fabric-loader-0.15.7-1.20.4, quilt-loader-0.23.1-1.20.4, 1.20.1-forge-47.2.0,
+// neoforge-20.4.190.
+fn loader_from_version_id(id: &str) -> Option<(ModLoader, String, Option<String>)> {
+    let lower = id.to_ascii_lowercase();
+    for (prefix, loader) in [("fabric-loader-", ModLoader::Fabric), ("quilt-loader-", ModLoader::Quilt)] {
+        if !lower.starts_with(prefix) {
+            continue;
+        }
+        let (version, minecraft) = id[prefix.len()..].rsplit_once('-')?;
+        return Some((loader, version.to_string(), Some(minecraft.to_string())));
+    }
+    if lower.starts_with("neoforge-") {
+        return Some((ModLoader::NeoForge, id["neoforge-".len()..].to_string(), None));
+    }
+    for (marker, loader) in [("-neoforge-", ModLoader::NeoForge), ("-forge", ModLoader::Forge)] {
+        if let Some(index) = lower.find(marker) {
+            let version = id[index + marker.len()..].trim_start_matches('-');
+            return Some((loader, version.to_string(), Some(id[..index].to_string())));
+        }
+    }
+    None
+}
+
+// The official launcher puts the heap size in the JVM arguments; it maps to
+// the instance's memory fields instead.
+fn split_memory_args(args: &str) -> (Option<u32>, Option<u32>, Option<String>) {
+    let (mut memory_min, mut memory_max) = (None, None);
+    let mut rest = Vec::new();
+    for token in args.split_whitespace() {
+        let memory = |size: &str| crate::jvm_lint::parse_memory_mb(size).map(|mb| mb as u32);
+        if let Some(size) = token.strip_prefix("-Xmx").and_then(memory) {
+            memory_max = Some(size);
+        } else if let Some(size) = token.strip_prefix("-Xms").and_then(memory) {
+            memory_min = Some(size);
+        } else {
+            rest.push(token);
+        }
+    }
+    (memory_min, memory_max, (!rest.is_empty()).then(|| rest.join(" ")))
+}
+
+/// Links (or copies where links are not possible) the client, libraries and
+/// assets of a version the official launcher already downloaded into our
+/// own directories, so installing it later finds them in place. Files are
+/// only taken when their SHA-1 matches what the version lists; returns how
+/// many were.
+pub fn reuse_version(root: &Path, version_id: &str, versions_dir: &Path, assets_dir: &Path) -> Result<usize> {
+    let mut reused = 0;
+    let mut next = Some(version_id.to_string());
+    while let Some(id) = next.take() {
+        let Some(version) = read_local_version(root, &id) else { break };
+        if version.inherits_from.is_some() {
+            next = version.inherits_from;
+            continue;
+        }
+
+        let source_dir = root.join("versions").join(&id);
+        let target_dir = versions_dir.join(&id);
+        if let Some(client) = version.downloads.as_ref().and_then(|downloads| downloads.client.as_ref()) {
+            let jar = format!("{}.jar", id);
+            reused += reuse_file(&source_dir.join(&jar), &target_dir.join(&jar), Some(&client.sha1))? as usize;
+        }
+        let artifacts = version.libraries.iter().filter_map(|library| library.downloads.as_ref()?.artifact.as_ref());
+        for Artifact { path, sha1, .. } in artifacts {
+            let source = root.join("libraries").join(path);
+            reused += reuse_file(&source, &versions_dir.join("libraries").join(path), Some(sha1))? as usize;
+        }
+        if let Some(index) = &version.asset_index {
+            reused += reuse_assets(&root.join("assets"), &index.id, assets_dir)?;
+        }
+    }
+    Ok(reused)
+}
+
+fn reuse_assets(source_dir: &Path, index_id: &str, assets_dir: &Path) -> Result<usize> {
+    let Ok(index) = AssetIndex::load(source_dir, index_id) else { return Ok(0) };
+    let index_file = Path::new("indexes").join(format!("{}.json", index_id));
+    let mut reused = reuse_file(&source_dir.join(&index_file), &assets_dir.join(&index_file), None)? as usize;
+    for object in index.objects.values() {
+        let Some(prefix) = object.hash.get(..2) else { continue };
+        let path = Path::new("objects").join(prefix).join(&object.hash);
+        reused += reuse_file(&source_dir.join(&path), &assets_dir.join(&path), Some(&object.hash))? as usize;
+    }
+    Ok(reused)
+}
+
+// Existing files are left alone: they are ours and already checked.
+fn reuse_file(source: &Path, target: &Path, sha1: Option<&str>) -> Result<bool> {
+    if target.exists() || !source.is_file() {
+        return Ok(false);
+    }
+    if let Some(sha1) = sha1 {
+        if !import::sha1_hex(File::open(source)?)?.eq_ignore_ascii_case(sha1) {
+            return Ok(false);
+        }
+    }
+    crate::libraries::link_or_copy(source, target)?;
+    Ok(true)
+}
@@ -0,0 +1,265 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{Error, Result};
+
+const DISABLED_DIR: &str = ".disabled";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PackKind {
+    Resource,
+    Shader,
+}
+
+impl PackKind {
+    pub fn dir_name(&self) -> &'static str {
+        match self {
+            PackKind::Resource => "resourcepacks",
+            PackKind::Shader => "shaderpacks",
+        }
+    }
+
+    pub fn toggle(&self) -> Self {
+        match self {
+            PackKind::Resource => PackKind::Shader,
+            PackKind::Shader => PackKind::Resource,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Pack {
+    pub file_name: String,
+    pub kind: PackKind,
+    pub enabled: bool,
+    pub is_directory: bool,
+    pub size: u64,
+    pub pack_format: Option<u32>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackMcmeta {
+    pack: PackSection,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackSection {
+    pack_format: Option<u32>,
+    #[serde(default)]
+    description: serde_json::Value,
+}
+
+pub struct ResourcePackManager {
+    kind: PackKind,
+    packs_dir: PathBuf,
+    disabled_dir: PathBuf,
+}
+
+impl ResourcePackManager {
+    pub fn new(game_dir: &Path, kind: PackKind) -> Result<Self> {
+        let packs_dir = game_dir.join(kind.dir_name());
+        let disabled_dir = packs_dir.join(DISABLED_DIR);
+        std::fs::create_dir_all(&disabled_dir)?;
+        Ok(Self { kind, packs_dir, disabled_dir })
+    }
+
+    pub fn kind(&self) -> PackKind {
+        self.kind
+    }
+
+    pub fn list_packs(&self) -> Result<Vec<Pack>> {
+        let mut packs = self.scan_directory(&self.packs_dir, true)?;
+        packs.extend(self.scan_directory(&self.disabled_dir, false)?);
+        packs.sort_by_key(|pack| pack.file_name.to_lowercase());
+        Ok(packs)
+    }
+
+    fn scan_directory(&self, dir: &Path, enabled: bool) -> Result<Vec<Pack>> {
+        let mut packs = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let Some(file_name) = path.file_name().and_then(|n| n.to_str()).map(str::to_string) else {
+                continue;
+            };
+            if file_name.starts_with('.') {
+                continue;
+            }
+
+            let is_directory = path.is_dir();
+            if !is_directory && path.extension().is_none_or(|ext| ext != "zip") {
+                continue;
+            }
+
+            let (pack_format, description) = match self.kind {
+                PackKind::Resource => read_pack_metadata(&path).unwrap_or_default(),
+                PackKind::Shader => (None, None),
+            };
+            let size = if is_directory {
+                directory_size(&path)
+            } else {
+                std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
+            };
+
+            packs.push(Pack {
+                file_name,
+                kind: self.kind,
+                enabled,
+                is_directory,
+                size,
+                pack_format,
+                description,
+            });
+        }
+        Ok(packs)
+    }
+
+    fn pack_path(&self, pack: &Pack) -> PathBuf {
+        if pack.enabled {
+            self.packs_dir.join(&pack.file_name)
+        } else {
+            self.disabled_dir.join(&pack.file_name)
+        }
+    }
+
+    pub fn install_pack(&self, source: &Path) -> Result<String> {
+        let file_name = source.file_name()
+            .and_then(|n| n.to_str())
+            .map(str::to_string)
+            .ok_or_else(|| Error::Other(format!("Invalid pack path: {}", source.display())))?;
+
+        if source.is_file() && source.extension().is_none_or(|ext| ext != "zip") {
+            return Err(Error::Other(format!("{} is not a .zip pack", file_name)));
+        }
+        if self.kind == PackKind::Resource && read_pack_metadata(source).is_none() {
+            return Err(Error::Other(format!("{} has no pack.mcmeta", file_name)));
+        }
+
+        let target = self.packs_dir.join(&file_name);
+        if target.exists() || self.disabled_dir.join(&file_name).exists() {
+            return Err(Error::Other(format!("Pack {} is already installed", file_name)));
+        }
+
+        if source.is_dir() {
+            copy_directory(source, &target)?;
+        } else {
+            std::fs::copy(source, &target)?;
+        }
+        Ok(file_name)
+    }
+
+    pub fn set_enabled(&self, pack: &Pack, enabled: bool) -> Result<()> {
+        if pack.enabled == enabled {
+            return Ok(());
+        }
+        let target = if enabled {
+            self.packs_dir.join(&pack.file_name)
+        } else {
+            self.disabled_dir.join(&pack.file_name)
+        };
+        std::fs::rename(self.pack_path(pack), target)?;
+        Ok(())
+    }
+
+    pub fn remove_pack(&self, pack: &Pack) -> Result<()> {
+        let path = self.pack_path(pack);
+        if pack.is_directory {
+            std::fs::remove_dir_all(path)?;
+        } else {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+fn read_pack_metadata(path: &Path) -> Option<(Option<u32>, Option<String>)> {
+    let content = if path.is_dir() {
+        std::fs::read_to_string(path.join("pack.mcmeta")).ok()?
+    } else {
+        let file = std::fs::File::open(path).ok()?;
+        let mut archive = zip::ZipArchive::new(file).ok()?;
+        let mut entry = archive.by_name("pack.mcmeta").ok()?;
+        let mut content = String::new();
+        entry.read_to_string(&mut content).ok()?;
+        content
+    };
+
+    let meta: PackMcmeta = serde_json::from_str(content.trim_start_matches('\u{feff}')).ok()?;
+    let description = Some(text_component(&meta.pack.description)).filter(|d| !d.is_empty());
+    Some((meta.pack.pack_format, description))
+}
+
+fn text_component(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => strip_formatting(text),
+        serde_json::Value::Array(parts) => parts.iter().map(text_component).collect(),
+        serde_json::Value::Object(object) => {
+            let mut text = object.get("text").map(text_component).unwrap_or_default();
+            if let Some(extra) = object.get("extra") {
+                text.push_str(&text_component(extra));
+            }
+            text
+        }
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn strip_formatting(text: &str) -> String {
+    let mut result = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '§' {
+            chars.next();
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn directory_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+fn copy_directory(source: &Path, target: &Path) -> Result<()> {
+    for entry in walkdir::WalkDir::new(source) {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let destination = target.join(relative);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&destination)?;
+        } else {
+            std::fs::copy(entry.path(), &destination)?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct PackBrowser {
+    pub instance_id: Uuid,
+    pub kind: PackKind,
+    pub packs: Vec<Pack>,
+    pub path_input: Option<String>,
+}
+
+impl PackBrowser {
+    pub fn new(instance_id: Uuid) -> Self {
+        Self {
+            instance_id,
+            kind: PackKind::Resource,
+            packs: Vec::new(),
+            path_input: None,
+        }
+    }
+}
@@ -1,4 +1,9 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+use crate::i18n::trf;
+use crate::{Error, Result};
 
 pub fn get_default_java_paths() -> Vec<PathBuf> {
     let mut paths = Vec::new();
@@ -20,15 +25,386 @@ pub fn get_default_java_paths() -> Vec<PathBuf> {
         paths.push(PathBuf::from("/usr/lib/jvm"));
         paths.push(PathBuf::from("/usr/java"));
         paths.push(PathBuf::from("/opt/java"));
+        paths.extend(get_arch_java_paths());
     }
     
     paths
 }
 
+// Distributions name per-arch JDK directories after the Debian architecture
+// (java-17-openjdk-arm64) and some ARM boards ship vendor JDKs under /usr/lib64.
+pub fn get_arch_java_paths() -> Vec<PathBuf> {
+    let debian_arch = match Arch::current() {
+        Arch::Aarch64 => "arm64",
+        Arch::Arm => "armhf",
+        Arch::Riscv64 => "riscv64",
+        _ => return Vec::new(),
+    };
+
+    let mut paths = vec![PathBuf::from("/usr/lib64/jvm")];
+    for version in ["8", "11", "17", "21"] {
+        paths.push(PathBuf::from(format!("/usr/lib/jvm/java-{}-openjdk-{}", version, debian_arch)));
+    }
+    paths
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arch {
+    X86,
+    X86_64,
+    Aarch64,
+    Arm,
+    Riscv64,
+    Other,
+}
+
+impl Arch {
+    pub fn current() -> Self {
+        Self::parse(std::env::consts::ARCH)
+    }
+
+    // Accepts both Rust target names and Java `os.arch` values.
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "x86" | "i386" | "i486" | "i586" | "i686" => Arch::X86,
+            "x86_64" | "amd64" | "x64" => Arch::X86_64,
+            "aarch64" | "arm64" => Arch::Aarch64,
+            "arm" | "aarch32" | "armv7" | "armv7l" => Arch::Arm,
+            "riscv64" | "riscv64gc" => Arch::Riscv64,
+            _ => Arch::Other,
+        }
+    }
+
+    pub fn is_64bit(&self) -> bool {
+        matches!(self, Arch::X86_64 | Arch::Aarch64 | Arch::Riscv64)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Arch::X86 => "x86",
+            Arch::X86_64 => "x86_64",
+            Arch::Aarch64 => "arm64",
+            Arch::Arm => "arm32",
+            Arch::Riscv64 => "riscv64",
+            Arch::Other => std::env::consts::ARCH,
+        }
+    }
+
+    // Suffix used by LWJGL 3 natives classifiers (natives-linux-arm64 etc.);
+    // x86_64 is the unsuffixed default.
+    pub fn natives_suffix(&self) -> Option<&'static str> {
+        match self {
+            Arch::X86_64 | Arch::Other => None,
+            arch => Some(arch.as_str()),
+        }
+    }
+}
+
+pub fn minecraft_os_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "windows"
+    } else if cfg!(target_os = "macos") {
+        "osx"
+    } else {
+        "linux"
+    }
+}
+
+pub fn natives_classifier(natives: &HashMap<String, String>, has_classifier: impl Fn(&str) -> bool) -> Option<String> {
+    let current = Arch::current();
+    let base = natives.get(minecraft_os_name())?
+        .replace("${arch}", if current.is_64bit() { "64" } else { "32" });
+    if let Some(suffix) = current.natives_suffix() {
+        let specific = format!("{}-{}", base, suffix);
+        if has_classifier(&specific) {
+            return Some(specific);
+        }
+    }
+    Some(base)
+}
+
+pub fn natives_warning() -> Option<String> {
+    let arch = Arch::current();
+    match (minecraft_os_name(), arch) {
+        (_, Arch::Other) => Some(trf("log.arch_unsupported", &[("arch", &std::env::consts::ARCH)])),
+        ("linux", Arch::Aarch64 | Arch::Arm | Arch::Riscv64) => Some(trf("log.arch_no_natives", &[("arch", &arch.as_str())])),
+        _ => None,
+    }
+}
+
 pub fn get_classpath_separator() -> &'static str {
     if cfg!(windows) {
         ";"
     } else {
         ":"
     }
-} 
\ No newline at end of file
+}
+
+pub fn open_path(path: &Path) -> Result<()> {
+    open_target(&path.to_string_lossy())
+}
+
+pub fn open_url(url: &str) -> Result<()> {
+    let parsed = url::Url::parse(url)
+        .map_err(|e| Error::Platform(format!("Invalid URL '{}': {}", url, e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(Error::Platform(format!("Refusing to open non-web URL '{}'", url)));
+    }
+    open_target(parsed.as_str())
+}
+
+pub fn open_target(target: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    };
+
+    command
+        .arg(target)
+        .spawn()
+        .map_err(|e| Error::Platform(format!("Failed to open {}: {}", target, e)))?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpuInfo {
+    pub name: String,
+    pub driver: Option<String>,
+}
+
+/// Snapshot of the machine the launcher runs on, shown on the System Info
+/// screen and attached to crash reports. Every field is best effort: probes
+/// rely on tools such as vulkaninfo, glxinfo or wmic being installed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemInfo {
+    pub os: String,
+    pub kernel: Option<String>,
+    pub arch: Arch,
+    pub cpu: Option<String>,
+    pub cpu_threads: usize,
+    pub memory_mb: Option<u64>,
+    pub gpus: Vec<GpuInfo>,
+}
+
+impl SystemInfo {
+    // Spawns external tools; call from a blocking task.
+    pub fn detect() -> Self {
+        Self {
+            os: detect_os().unwrap_or_else(|| std::env::consts::OS.to_string()),
+            kernel: detect_kernel(),
+            arch: Arch::current(),
+            cpu: detect_cpu(),
+            cpu_threads: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            memory_mb: detect_memory_mb(),
+            gpus: detect_gpus(),
+        }
+    }
+
+    // What is known without spawning anything, for when probing fails.
+    pub fn detect_basic() -> Self {
+        Self {
+            os: std::env::consts::OS.to_string(),
+            kernel: None,
+            arch: Arch::current(),
+            cpu: None,
+            cpu_threads: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            memory_mb: None,
+            gpus: Vec::new(),
+        }
+    }
+
+    /// (locale key, value) pairs for display.
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        let unknown = || "?".to_string();
+        let mut rows = vec![
+            ("system.os", self.os.clone()),
+            ("system.kernel", self.kernel.clone().unwrap_or_else(unknown)),
+            ("system.arch", self.arch.as_str().to_string()),
+            ("system.cpu", self.cpu.clone().unwrap_or_else(unknown)),
+            ("system.threads", self.cpu_threads.to_string()),
+            ("system.memory", self.memory_mb.map(|mb| format!("{} MB", mb)).unwrap_or_else(unknown)),
+        ];
+        if self.gpus.is_empty() {
+            rows.push(("system.gpu", unknown()));
+        }
+        for gpu in &self.gpus {
+            rows.push(("system.gpu", gpu.label()));
+        }
+        rows
+    }
+
+    // Untranslated on purpose: these lines end up in bug reports.
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("OS: {} ({})", self.os, self.arch.as_str()),
+            format!("Kernel: {}", self.kernel.as_deref().unwrap_or("?")),
+            format!("CPU: {} ({} threads)", self.cpu.as_deref().unwrap_or("?"), self.cpu_threads),
+            format!("Memory: {}", self.memory_mb.map(|mb| format!("{} MB", mb)).unwrap_or_else(|| "?".to_string())),
+        ];
+        lines.extend(self.gpus.iter().map(|gpu| format!("GPU: {}", gpu.label())));
+        lines
+    }
+}
+
+impl GpuInfo {
+    pub fn label(&self) -> String {
+        match &self.driver {
+            Some(driver) => format!("{} [{}]", self.name, driver),
+            None => self.name.clone(),
+        }
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).stderr(std::process::Stdio::null()).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// Reads `key=value` / `key: value` output (wmic /value, sysctl, /proc files).
+fn field<'a>(text: &'a str, key: &str, separator: char) -> Option<&'a str> {
+    text.lines()
+        .filter_map(|line| line.split_once(separator))
+        .find(|(name, _)| name.trim() == key)
+        .map(|(_, value)| value.trim().trim_matches('"'))
+        .filter(|value| !value.is_empty())
+}
+
+fn fields<'a>(text: &'a str, key: &str, separator: char) -> Vec<&'a str> {
+    text.lines()
+        .filter_map(|line| line.split_once(separator))
+        .filter(|(name, _)| name.trim() == key)
+        .map(|(_, value)| value.trim())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+fn detect_os() -> Option<String> {
+    if cfg!(target_os = "windows") {
+        let output = command_output("wmic", &["os", "get", "Caption,Version", "/value"])?;
+        Some(format!("{} {}", field(&output, "Caption", '=')?, field(&output, "Version", '=').unwrap_or_default()))
+    } else if cfg!(target_os = "macos") {
+        let version = command_output("sw_vers", &["-productVersion"])?;
+        Some(format!("macOS {}", version.trim()))
+    } else {
+        let release = std::fs::read_to_string("/etc/os-release").ok()?;
+        field(&release, "PRETTY_NAME", '=').map(str::to_string)
+    }
+}
+
+/// The OS version as Java reports it in `os.version`, which is what the
+/// `os.version` rules of version JSONs are written against: "10.0" on
+/// Windows 10 and 11, the product version on macOS, the kernel release on
+/// Linux. Probed once and cached, since library rules are checked often.
+pub fn os_version() -> Option<String> {
+    static VERSION: OnceLock<Option<String>> = OnceLock::new();
+    VERSION.get_or_init(detect_os_version).clone()
+}
+
+fn detect_os_version() -> Option<String> {
+    if cfg!(target_os = "windows") {
+        let output = command_output("wmic", &["os", "get", "Version", "/value"])?;
+        let mut parts = field(&output, "Version", '=')?.split('.');
+        Some(format!("{}.{}", parts.next()?, parts.next().unwrap_or("0")))
+    } else if cfg!(target_os = "macos") {
+        command_output("sw_vers", &["-productVersion"]).map(|version| version.trim().to_string())
+    } else {
+        detect_kernel()
+    }
+}
+
+fn detect_kernel() -> Option<String> {
+    if cfg!(target_os = "windows") {
+        return None;
+    }
+    command_output("uname", &["-r"]).map(|kernel| kernel.trim().to_string())
+}
+
+fn detect_cpu() -> Option<String> {
+    if cfg!(target_os = "windows") {
+        let output = command_output("wmic", &["cpu", "get", "Name", "/value"])?;
+        field(&output, "Name", '=').map(str::to_string)
+    } else if cfg!(target_os = "macos") {
+        command_output("sysctl", &["-n", "machdep.cpu.brand_string"]).map(|cpu| cpu.trim().to_string())
+    } else {
+        let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+        // ARM kernels report "Hardware" or "Model" instead of "model name".
+        ["model name", "Hardware", "Model"].iter()
+            .find_map(|key| field(&cpuinfo, key, ':'))
+            .map(str::to_string)
+    }
+}
+
+fn detect_memory_mb() -> Option<u64> {
+    if cfg!(target_os = "windows") {
+        let output = command_output("wmic", &["computersystem", "get", "TotalPhysicalMemory", "/value"])?;
+        field(&output, "TotalPhysicalMemory", '=')?.parse::<u64>().ok().map(|bytes| bytes / 1024 / 1024)
+    } else if cfg!(target_os = "macos") {
+        command_output("sysctl", &["-n", "hw.memsize"])?.trim().parse::<u64>().ok().map(|bytes| bytes / 1024 / 1024)
+    } else {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        let kb = field(&meminfo, "MemTotal", ':')?.trim_end_matches("kB").trim().parse::<u64>().ok()?;
+        Some(kb / 1024)
+    }
+}
+
+fn detect_gpus() -> Vec<GpuInfo> {
+    if cfg!(target_os = "windows") {
+        let Some(output) = command_output("wmic", &["path", "win32_VideoController", "get", "Name,DriverVersion", "/value"]) else {
+            return Vec::new();
+        };
+        // wmic prints one blank-line separated block per adapter, DriverVersion first.
+        let drivers = fields(&output, "DriverVersion", '=');
+        return fields(&output, "Name", '=').into_iter().enumerate()
+            .map(|(index, name)| GpuInfo {
+                name: name.to_string(),
+                driver: drivers.get(index).map(|driver| driver.to_string()),
+            })
+            .collect();
+    }
+
+    if cfg!(target_os = "macos") {
+        let Some(output) = command_output("system_profiler", &["SPDisplaysDataType"]) else {
+            return Vec::new();
+        };
+        return fields(&output, "Chipset Model", ':').into_iter()
+            .map(|name| GpuInfo { name: name.to_string(), driver: field(&output, "Metal Support", ':').map(str::to_string) })
+            .collect();
+    }
+
+    // Vulkan lists every adapter with its driver; glxinfo only the one used
+    // for OpenGL, which is still what Minecraft renders with.
+    if let Some(output) = command_output("vulkaninfo", &["--summary"]) {
+        let names = fields(&output, "deviceName", '=');
+        let drivers = fields(&output, "driverInfo", '=');
+        let gpus: Vec<GpuInfo> = names.into_iter().enumerate()
+            .filter(|(_, name)| !name.contains("llvmpipe"))
+            .map(|(index, name)| GpuInfo {
+                name: name.to_string(),
+                driver: drivers.get(index).map(|driver| driver.to_string()),
+            })
+            .collect();
+        if !gpus.is_empty() {
+            return gpus;
+        }
+    }
+    if let Some(output) = command_output("glxinfo", &["-B"]) {
+        if let Some(renderer) = field(&output, "OpenGL renderer string", ':') {
+            return vec![GpuInfo {
+                name: renderer.to_string(),
+                driver: field(&output, "OpenGL version string", ':').map(str::to_string),
+            }];
+        }
+    }
+    command_output("lspci", &[]).map(|output| {
+        output.lines()
+            .filter(|line| line.contains("VGA compatible controller") || line.contains("3D controller"))
+            .filter_map(|line| line.splitn(3, ':').nth(2))
+            .map(|name| GpuInfo { name: name.trim().to_string(), driver: None })
+            .collect()
+    }).unwrap_or_default()
+}
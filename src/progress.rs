@@ -1,237 +1,340 @@
- 
-use std::time::{Duration, Instant};
-use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect, Alignment},
-    style::{Color, Style},
-    widgets::{Block, Borders, Gauge, Paragraph, Clear},
-    Frame,
-};
-use crossterm::event::{self, Event, KeyCode};
+use crate::tasks::{TaskId, TaskStage};
 use crate::utils;
 
-#[derive(Debug, Clone)]
-pub struct DownloadProgress {
-    pub downloaded: u64,
+/// A change to one task's progress, sent by whatever is doing the work.
+/// Paths name stages from the task down, e.g. `[Assets, AssetObjects]`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgressUpdate {
+    /// Declares the subtasks under `parent` (the task itself when empty) and
+    /// how much of the parent each one is worth.
+    Plan { parent: Vec<TaskStage>, stages: Vec<(TaskStage, u32)> },
+    Advance { path: Vec<TaskStage>, done: u64, total: u64 },
+}
+
+/// One stage of a task. A stage with subtasks is as far along as their
+/// weighted average; without them, as its own `done` out of `total`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressNode {
+    pub stage: TaskStage,
+    pub weight: u32,
+    pub done: u64,
     pub total: u64,
-    pub speed_bps: f64,
-    pub eta_seconds: f64,
-    pub filename: String,
-    pub status: String,
+    pub complete: bool,
+    pub children: Vec<ProgressNode>,
 }
 
-impl DownloadProgress {
-    pub fn new(filename: String) -> Self {
-        Self {
-            downloaded: 0,
-            total: 0,
-            speed_bps: 0.0,
-            eta_seconds: 0.0,
-            filename,
-            status: "Подготовка...".to_string(),
-        }
+impl ProgressNode {
+    fn new(stage: TaskStage, weight: u32) -> Self {
+        Self { stage, weight, done: 0, total: 0, complete: false, children: Vec::new() }
     }
 
-    pub fn update(&mut self, downloaded: u64, total: u64) {
-        self.downloaded = downloaded;
-        self.total = total;
+    pub fn fraction(&self) -> Option<f64> {
+        if self.complete {
+            Some(1.0)
+        } else if !self.children.is_empty() {
+            weighted(&self.children)
+        } else {
+            (self.total > 0).then(|| (self.done as f64 / self.total as f64).min(1.0))
+        }
     }
 
-    pub fn calculate_speed(&mut self, elapsed: Duration, bytes_since_last: u64) {
-        if elapsed.as_millis() > 0 {
-            self.speed_bps = (bytes_since_last as f64) / elapsed.as_secs_f64();
-            
-            if self.total > self.downloaded && self.speed_bps > 0.0 {
-                self.eta_seconds = (self.total - self.downloaded) as f64 / self.speed_bps;
-            } else {
-                self.eta_seconds = 0.0;
-            }
-        }
+    pub fn is_started(&self) -> bool {
+        self.complete || self.total > 0 || self.children.iter().any(ProgressNode::is_started)
     }
 
-    pub fn get_progress_percentage(&self) -> u16 {
-        if self.total == 0 {
-            0
-        } else {
-            ((self.downloaded as f64 / self.total as f64) * 100.0) as u16
+    /// `12/40`, or sizes for stages counted in bytes.
+    pub fn amount(&self) -> Option<String> {
+        if self.total == 0 || !self.children.is_empty() {
+            return None;
         }
+        Some(if self.stage.counts_bytes() {
+            format!("{} / {}", utils::format_size(self.done), utils::format_size(self.total))
+        } else {
+            format!("{}/{}", self.done, self.total)
+        })
     }
+}
 
-    pub fn format_progress(&self) -> String {
-        format!(
-            "{} / {}",
-            utils::format_size(self.downloaded),
-            utils::format_size(self.total)
-        )
+fn weighted(nodes: &[ProgressNode]) -> Option<f64> {
+    if !nodes.iter().any(ProgressNode::is_started) {
+        return None;
+    }
+    let total: u32 = nodes.iter().map(|node| node.weight).sum();
+    if total == 0 {
+        return None;
     }
+    let done: f64 = nodes.iter()
+        .map(|node| node.weight as f64 * node.fraction().unwrap_or(0.0))
+        .sum();
+    Some(done / total as f64)
+}
 
-    pub fn format_speed(&self) -> String {
-        if self.speed_bps > 0.0 {
-            let eta_str = if self.eta_seconds > 0.0 {
-                format_duration(self.eta_seconds)
-            } else {
-                "неизвестно".to_string()
-            };
-            format!(
-                "{}/s (осталось: {})",
-                utils::format_size(self.speed_bps as u64),
-                eta_str
-            )
-        } else {
-            "0 B/s".to_string()
+// Finds the node at `path`, adding stages nobody planned with a weight of
+// one. On the way down every stage before the one taken is marked complete,
+// since stages of a task run in the order they were planned.
+fn advance_to<'a>(nodes: &'a mut Vec<ProgressNode>, path: &[TaskStage]) -> Option<&'a mut ProgressNode> {
+    let (stage, rest) = path.split_first()?;
+    let index = match nodes.iter().position(|node| node.stage == *stage) {
+        Some(index) => index,
+        None => {
+            nodes.push(ProgressNode::new(*stage, 1));
+            nodes.len() - 1
         }
+    };
+    for earlier in &mut nodes[..index] {
+        earlier.complete = true;
+    }
+    let node = &mut nodes[index];
+    if rest.is_empty() {
+        Some(node)
+    } else {
+        advance_to(&mut node.children, rest)
+    }
+}
+
+fn find<'a>(nodes: &'a [ProgressNode], path: &[TaskStage]) -> Option<&'a ProgressNode> {
+    let (stage, rest) = path.split_first()?;
+    let node = nodes.iter().find(|node| node.stage == *stage)?;
+    if rest.is_empty() {
+        Some(node)
+    } else {
+        find(&node.children, rest)
     }
 }
 
-pub struct ProgressDialog {
-    progress: DownloadProgress,
-    last_update: Instant,
-    last_bytes: u64,
-    cancelled: bool,
+#[derive(Debug, Clone)]
+pub struct TrackedTask {
+    pub id: TaskId,
+    pub label: String,
+    pub stages: Vec<ProgressNode>,
+    current: Vec<TaskStage>,
 }
 
-impl ProgressDialog {
-    pub fn new(filename: String) -> Self {
-        Self {
-            progress: DownloadProgress::new(filename),
-            last_update: Instant::now(),
-            last_bytes: 0,
-            cancelled: false,
-        }
+impl TrackedTask {
+    pub fn fraction(&self) -> Option<f64> {
+        weighted(&self.stages)
     }
 
-    pub fn update_progress(&mut self, downloaded: u64, total: u64) {
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.last_update);
-        let bytes_since_last = downloaded.saturating_sub(self.last_bytes);
-
-        self.progress.update(downloaded, total);
-        self.progress.calculate_speed(elapsed, bytes_since_last);
+    /// The stage that reported last, with the stages above it.
+    pub fn current(&self) -> Option<(&[TaskStage], &ProgressNode)> {
+        find(&self.stages, &self.current).map(|node| (self.current.as_slice(), node))
+    }
 
-        if downloaded == total && total > 0 {
-            self.progress.status = "Завершено!".to_string();
-        } else if total > 0 {
-            self.progress.status = "Загружается...".to_string();
+    pub fn describe(&self) -> String {
+        let mut text = self.label.clone();
+        if let Some((path, node)) = self.current() {
+            let stages: Vec<&str> = path.iter().map(TaskStage::label).collect();
+            text.push(' ');
+            text.push_str(&stages.join(" › "));
+            if let Some(amount) = node.amount() {
+                text.push(' ');
+                text.push_str(&amount);
+            }
+        }
+        if let Some(fraction) = self.fraction() {
+            text.push_str(&format!(" ({}%)", (fraction * 100.0) as u16));
         }
+        text
+    }
 
-        self.last_update = now;
-        self.last_bytes = downloaded;
-    }
-
-    pub fn is_cancelled(&self) -> bool {
-        self.cancelled
-    }
-
-    pub fn draw(&self, f: &mut Frame, area: Rect) {
-        f.render_widget(Clear, area);
-        let popup_area = centered_rect(60, 30, area);
-
-        let main_block = Block::default()
-            .title("Загрузка файла")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan));
-
-        f.render_widget(main_block, popup_area);
-
-        let inner_area = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(1)
-            .constraints([
-                Constraint::Length(2), 
-                Constraint::Length(1), 
-                Constraint::Length(1), 
-                Constraint::Length(3), 
-                Constraint::Length(1), 
-                Constraint::Length(1), 
-                Constraint::Length(1), 
-                Constraint::Length(1), 
-                Constraint::Length(1), 
-            ])
-            .split(popup_area);
-
-        let filename = Paragraph::new(format!("Файл: {}", self.progress.filename))
-            .style(Style::default().fg(Color::White))
-            .alignment(Alignment::Center);
-        f.render_widget(filename, inner_area[0]);
-
-        let progress_text = Paragraph::new(self.progress.format_progress())
-            .style(Style::default().fg(Color::Yellow))
-            .alignment(Alignment::Center);
-        f.render_widget(progress_text, inner_area[2]);
-
-        let progress_bar = Gauge::default()
-            .block(Block::default().borders(Borders::ALL))
-            .gauge_style(Style::default().fg(Color::Green))
-            .percent(self.progress.get_progress_percentage())
-            .label(format!("{}%", self.progress.get_progress_percentage()));
-        f.render_widget(progress_bar, inner_area[3]);
-
-        let speed_text = Paragraph::new(self.progress.format_speed())
-            .style(Style::default().fg(Color::Cyan))
-            .alignment(Alignment::Center);
-        f.render_widget(speed_text, inner_area[4]);
-
-        let status = Paragraph::new(self.progress.status.clone())
-            .style(Style::default().fg(Color::Green))
-            .alignment(Alignment::Center);
-        f.render_widget(status, inner_area[6]);
-
-        let controls = Paragraph::new("Esc: Отмена")
-            .style(Style::default().fg(Color::Gray))
-            .alignment(Alignment::Center);
-        f.render_widget(controls, inner_area[8]);
-    }
-
-    pub fn handle_input(&mut self) -> bool {
-        if event::poll(Duration::from_millis(10)).unwrap_or(false) {
-            if let Ok(Event::Key(key)) = event::read() {
-                match key.code {
-                    KeyCode::Esc => {
-                        self.cancelled = true;
-                        return false;
+    fn apply(&mut self, update: ProgressUpdate) {
+        match update {
+            ProgressUpdate::Plan { parent, stages } => {
+                let nodes = if parent.is_empty() {
+                    &mut self.stages
+                } else {
+                    match advance_to(&mut self.stages, &parent) {
+                        Some(node) => &mut node.children,
+                        None => return,
+                    }
+                };
+                for (stage, weight) in stages {
+                    match nodes.iter_mut().find(|node| node.stage == stage) {
+                        Some(node) => node.weight = weight,
+                        None => nodes.push(ProgressNode::new(stage, weight)),
                     }
-                    _ => {}
+                }
+            }
+            ProgressUpdate::Advance { path, done, total } => {
+                if let Some(node) = advance_to(&mut self.stages, &path) {
+                    node.done = done;
+                    node.total = total;
+                    node.complete = false;
+                    self.current = path;
                 }
             }
         }
-        true
     }
 }
 
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
+/// Progress of every running task, each a tree of stages.
+#[derive(Debug, Default)]
+pub struct ProgressTracker {
+    tasks: Vec<TrackedTask>,
 }
 
-fn format_duration(seconds: f64) -> String {
-    if seconds < 0.0 || seconds.is_infinite() || seconds.is_nan() {
-        return "неизвестно".to_string();
+impl ProgressTracker {
+    pub fn start(&mut self, id: TaskId, label: String) {
+        self.tasks.push(TrackedTask { id, label, stages: Vec::new(), current: Vec::new() });
     }
 
-    let total_seconds = seconds as u64;
-    let hours = total_seconds / 3600;
-    let minutes = (total_seconds % 3600) / 60;
-    let secs = total_seconds % 60;
+    // Updates can arrive after their task finished; those are dropped.
+    pub fn apply(&mut self, id: TaskId, update: ProgressUpdate) {
+        if let Some(task) = self.tasks.iter_mut().find(|task| task.id == id) {
+            task.apply(update);
+        }
+    }
 
-    if hours > 0 {
-        format!("{}ч {}м {}с", hours, minutes, secs)
-    } else if minutes > 0 {
-        format!("{}м {}с", minutes, secs)
-    } else {
-        format!("{}с", secs)
+    pub fn finish(&mut self, id: TaskId) {
+        self.tasks.retain(|task| task.id != id);
+    }
+
+    pub fn tasks(&self) -> &[TrackedTask] {
+        &self.tasks
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Whether any task reports stages worth showing as a tree.
+    pub fn has_stages(&self) -> bool {
+        self.tasks.iter().any(|task| !task.stages.is_empty())
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(feature = "tui")]
+mod view {
+    use ratatui::{
+        layout::Rect,
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, Borders, Paragraph},
+        Frame,
+    };
+
+    use super::{ProgressNode, ProgressTracker, TrackedTask};
+    use crate::i18n::tr;
+
+    const SPINNER_FRAMES: [&str; 8] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧"];
+    const BAR_WIDTH: usize = 12;
+
+    pub struct StatusStrip<'a> {
+        tracker: &'a ProgressTracker,
+        idle: &'a str,
+        frame: Option<u64>,
+        badge: Option<&'a str>,
+    }
+
+    impl<'a> StatusStrip<'a> {
+        pub fn new(tracker: &'a ProgressTracker, idle: &'a str, frame: Option<u64>) -> Self {
+            Self { tracker, idle, frame, badge: None }
+        }
+
+        pub fn badge(mut self, badge: Option<&'a str>) -> Self {
+            self.badge = badge;
+            self
+        }
+
+        pub fn draw(&self, f: &mut Frame, area: Rect) {
+            let mut line = if self.tracker.is_empty() {
+                Line::from(Span::styled(self.idle, Style::default().fg(Color::Gray)))
+            } else {
+                let spinner = match self.frame {
+                    Some(frame) => SPINNER_FRAMES[frame as usize % SPINNER_FRAMES.len()],
+                    None => "…",
+                };
+                let tasks: Vec<String> = self.tracker.tasks().iter()
+                    .map(TrackedTask::describe)
+                    .collect();
+                Line::from(vec![
+                    Span::styled(format!("{} ", spinner), Style::default().fg(Color::Yellow)),
+                    Span::styled(tasks.join(" | "), Style::default().fg(Color::Cyan)),
+                ])
+            };
+            if let Some(badge) = self.badge {
+                line.spans.insert(0, Span::styled(format!(" {} ", badge), Style::default().fg(Color::Black).bg(Color::Yellow)));
+                line.spans.insert(1, Span::raw(" "));
+            }
+            f.render_widget(Paragraph::new(line), area);
+        }
+    }
+
+    /// Every running task with its stages indented below it.
+    pub struct ProgressTreeView<'a> {
+        tracker: &'a ProgressTracker,
+    }
+
+    impl<'a> ProgressTreeView<'a> {
+        pub fn new(tracker: &'a ProgressTracker) -> Self {
+            Self { tracker }
+        }
+
+        /// Rows needed to show the whole tree, borders included.
+        pub fn height(&self) -> u16 {
+            fn count(nodes: &[ProgressNode]) -> usize {
+                nodes.iter().map(|node| 1 + count(&node.children)).sum()
+            }
+            let rows: usize = self.tracker.tasks().iter().map(|task| 1 + count(&task.stages)).sum();
+            rows as u16 + 2
+        }
+
+        pub fn draw(&self, f: &mut Frame, area: Rect) {
+            let mut lines = Vec::new();
+            for task in self.tracker.tasks() {
+                lines.push(Line::from(vec![
+                    Span::styled(bar(task.fraction()), Style::default().fg(Color::Green)),
+                    Span::styled(format!(" {}", task.label), Style::default().add_modifier(Modifier::BOLD)),
+                ]));
+                push_nodes(&mut lines, &task.stages, 1);
+            }
+            let tree = Paragraph::new(lines)
+                .block(Block::default().borders(Borders::ALL).title(tr("progress.title")));
+            f.render_widget(tree, area);
+        }
+    }
+
+    fn push_nodes(lines: &mut Vec<Line<'static>>, nodes: &[ProgressNode], depth: usize) {
+        for node in nodes {
+            let (marker, color) = if node.complete {
+                ("✓", Color::Green)
+            } else if node.is_started() {
+                ("▸", Color::Yellow)
+            } else {
+                ("·", Color::DarkGray)
+            };
+            let mut spans = vec![
+                Span::raw("  ".repeat(depth)),
+                Span::styled(format!("{} {}", marker, node.stage.label()), Style::default().fg(color)),
+            ];
+            if !node.complete {
+                if let Some(amount) = node.amount() {
+                    spans.push(Span::styled(format!(" {}", amount), Style::default().fg(Color::Gray)));
+                }
+                if let Some(fraction) = node.fraction().filter(|_| node.is_started()) {
+                    spans.push(Span::styled(format!(" {}%", (fraction * 100.0) as u16), Style::default().fg(Color::Cyan)));
+                }
+            }
+            lines.push(Line::from(spans));
+            push_nodes(lines, &node.children, depth + 1);
+        }
+    }
+
+    fn bar(fraction: Option<f64>) -> String {
+        match fraction {
+            Some(fraction) => {
+                let filled = (fraction * BAR_WIDTH as f64).round() as usize;
+                format!(
+                    "{}{} {:>3}%",
+                    "█".repeat(filled),
+                    "░".repeat(BAR_WIDTH - filled.min(BAR_WIDTH)),
+                    (fraction * 100.0) as u16
+                )
+            }
+            None => format!("{} ...", "░".repeat(BAR_WIDTH)),
+        }
+    }
+}
+
+#[cfg(feature = "tui")]
+pub use view::{ProgressTreeView, StatusStrip};
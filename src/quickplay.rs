@@ -0,0 +1,195 @@
+use std::path::Path;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::trf;
+use crate::{Error, Result};
+
+const SERVERS_FILE: &str = "servers.dat";
+
+/// Where the game goes once it has started, skipping the title screen.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuickPlay {
+    /// A save folder name under `.minecraft/saves`.
+    World(String),
+    /// A server address, with an optional `:port`.
+    Server(String),
+}
+
+impl QuickPlay {
+    pub fn label(&self) -> String {
+        match self {
+            QuickPlay::World(name) => trf("quick_play.world", &[("name", name)]),
+            QuickPlay::Server(address) => trf("quick_play.server", &[("address", address)]),
+        }
+    }
+
+    // `supported` is whether the version has the quick play arguments (from
+    // 23w14a on). Older clients can still join a server through
+    // --server/--port, but have no way to open a world.
+    pub fn game_args(&self, supported: bool) -> Result<Vec<String>> {
+        match (self, supported) {
+            (QuickPlay::World(name), true) => Ok(vec!["--quickPlaySingleplayer".to_string(), name.clone()]),
+            (QuickPlay::Server(address), true) => Ok(vec!["--quickPlayMultiplayer".to_string(), address.clone()]),
+            (QuickPlay::World(_), false) => Err(Error::Launch(
+                "Launching straight into a world needs Minecraft 1.20 or newer".to_string(),
+            )),
+            (QuickPlay::Server(address), false) => {
+                let (host, port) = crate::settings::split_host_port(address)
+                    .map(|(host, port)| (host.to_string(), port.to_string()))
+                    .unwrap_or_else(|| (address.trim().to_string(), "25565".to_string()));
+                Ok(vec!["--server".to_string(), host, "--port".to_string(), port])
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerEntry {
+    pub name: String,
+    pub address: String,
+}
+
+/// Save folders in the game directory, most recently played first.
+pub fn list_worlds(game_dir: &Path) -> Vec<String> {
+    let mut worlds: Vec<(SystemTime, String)> = std::fs::read_dir(game_dir.join("saves"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let level = std::fs::metadata(entry.path().join("level.dat")).ok()?;
+            let played = level.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            Some((played, entry.file_name().into_string().ok()?))
+        })
+        .collect();
+    worlds.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    worlds.into_iter().map(|(_, name)| name).collect()
+}
+
+/// The multiplayer server list, in the order the game shows it. A missing or
+/// unreadable `servers.dat` gives an empty list.
+pub fn list_servers(game_dir: &Path) -> Vec<ServerEntry> {
+    std::fs::read(game_dir.join(SERVERS_FILE))
+        .ok()
+        .and_then(|data| NbtReader { data: &data, pos: 0 }.servers())
+        .unwrap_or_default()
+}
+
+// Just enough of the NBT format to pull `name` and `ip` out of the
+// uncompressed `servers.dat`; every other tag is skipped.
+struct NbtReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+const TAG_END: u8 = 0;
+const TAG_LIST: u8 = 9;
+const TAG_COMPOUND: u8 = 10;
+const TAG_STRING: u8 = 8;
+
+impl NbtReader<'_> {
+    fn servers(&mut self) -> Option<Vec<ServerEntry>> {
+        if self.u8()? != TAG_COMPOUND {
+            return None;
+        }
+        self.string()?;
+        loop {
+            let tag = self.u8()?;
+            if tag == TAG_END {
+                return Some(Vec::new());
+            }
+            let name = self.string()?;
+            if tag == TAG_LIST && name == "servers" {
+                return self.server_list();
+            }
+            self.skip(tag)?;
+        }
+    }
+
+    fn server_list(&mut self) -> Option<Vec<ServerEntry>> {
+        let element = self.u8()?;
+        let len = self.i32()?.max(0) as usize;
+        if element != TAG_COMPOUND {
+            return (len == 0).then(Vec::new);
+        }
+        let mut servers = Vec::with_capacity(len.min(256));
+        for _ in 0..len {
+            let (mut name, mut address) = (None, None);
+            loop {
+                let tag = self.u8()?;
+                if tag == TAG_END {
+                    break;
+                }
+                let key = self.string()?;
+                match (tag, key.as_str()) {
+                    (TAG_STRING, "name") => name = Some(self.string()?),
+                    (TAG_STRING, "ip") => address = Some(self.string()?),
+                    _ => self.skip(tag)?,
+                }
+            }
+            if let Some(address) = address.filter(|address| !address.trim().is_empty()) {
+                servers.push(ServerEntry { name: name.unwrap_or_else(|| address.clone()), address });
+            }
+        }
+        Some(servers)
+    }
+
+    fn skip(&mut self, tag: u8) -> Option<()> {
+        match tag {
+            1 => self.take(1).map(drop),
+            2 => self.take(2).map(drop),
+            3 | 5 => self.take(4).map(drop),
+            4 | 6 => self.take(8).map(drop),
+            7 => {
+                let len = self.i32()?.max(0) as usize;
+                self.take(len).map(drop)
+            }
+            TAG_STRING => self.string().map(drop),
+            TAG_LIST => {
+                let element = self.u8()?;
+                let len = self.i32()?.max(0);
+                (0..len).try_for_each(|_| self.skip(element))
+            }
+            TAG_COMPOUND => loop {
+                let tag = self.u8()?;
+                if tag == TAG_END {
+                    return Some(());
+                }
+                self.string()?;
+                self.skip(tag)?;
+            },
+            11 => {
+                let len = self.i32()?.max(0) as usize;
+                self.take(len.checked_mul(4)?).map(drop)
+            }
+            12 => {
+                let len = self.i32()?.max(0) as usize;
+                self.take(len.checked_mul(8)?).map(drop)
+            }
+            _ => None,
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Option<&[u8]> {
+        let bytes = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        self.take(4).map(|bytes| i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    // NBT strings are "modified UTF-8"; anything a server name could
+    // contain decodes fine as regular UTF-8.
+    fn string(&mut self) -> Option<String> {
+        let len = self.take(2).map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]) as usize)?;
+        self.take(len).map(|bytes| String::from_utf8_lossy(bytes).into_owned())
+    }
+}
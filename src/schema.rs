@@ -0,0 +1,129 @@
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::i18n::trf;
+use crate::{Error, Result};
+
+/// Top-level key holding the format version in every versioned file. Files
+/// written before versioning have none and count as version 0.
+pub const VERSION_KEY: &str = "schema_version";
+
+type Migration = fn(&mut Value) -> Result<()>;
+
+/// The format history of one kind of file. `migrations[n]` turns version
+/// `n` into `n + 1`, so the current version is the number of migrations.
+pub struct Schema {
+    name: &'static str,
+    migrations: &'static [Migration],
+    // Keys left out of backups, so a copy made before a migration does not
+    // keep tokens around after they move to the keyring.
+    secrets: &'static [&'static str],
+}
+
+pub const SETTINGS: Schema = Schema { name: "settings", migrations: &[stamp_only], secrets: &[] };
+pub const INSTANCE: Schema = Schema { name: "instance", migrations: &[stamp_only], secrets: &[] };
+pub const ACCOUNTS: Schema = Schema {
+    name: "accounts",
+    migrations: &[accounts_v1],
+    secrets: &["access_token", "refresh_token", "xbox_user_token", "xbox_api_token", "mojang_token"],
+};
+pub const PROFILES: Schema = Schema { name: "profiles", migrations: &[stamp_only], secrets: &[] };
+
+impl Schema {
+    pub fn current(&self) -> u32 {
+        self.migrations.len() as u32
+    }
+
+    /// Upgrades `value`, read from `path`, and deserializes it. Returns
+    /// whether a migration ran so the caller can save the new format.
+    ///
+    /// The original is copied next to the file before it is migrated, and
+    /// kept as well when it cannot be parsed, so a following save never
+    /// replaces the only copy of the user's data.
+    pub fn load<T: DeserializeOwned>(&self, path: &Path, mut value: Value) -> Result<(T, bool)> {
+        let migrated = self.upgrade(path, &mut value)?;
+        match serde_json::from_value(value) {
+            Ok(parsed) => Ok((parsed, migrated)),
+            Err(e) => {
+                if let Ok(backup) = self.backup(path, "unreadable") {
+                    tracing::warn!("{}", trf("log.schema_unreadable", &[("path", &path.display()), ("backup", &backup.display())]));
+                }
+                Err(e.into())
+            }
+        }
+    }
+
+    fn upgrade(&self, path: &Path, value: &mut Value) -> Result<bool> {
+        let version = value.get(VERSION_KEY).and_then(Value::as_u64).unwrap_or(0) as u32;
+        let current = self.current();
+        // Loading a newer format would drop the fields this build does not
+        // know about on the next save.
+        if version > current {
+            return Err(Error::Settings(format!(
+                "{} was written by a newer launcher (format {}, this version reads up to {})",
+                path.display(),
+                version,
+                current
+            )));
+        }
+        if version == current {
+            return Ok(false);
+        }
+
+        self.backup(path, &format!("v{}", version))?;
+        for migration in &self.migrations[version as usize..] {
+            migration(value)?;
+        }
+        self.stamp(value);
+        tracing::info!("{}", trf("log.schema_migrated", &[("name", &self.name), ("path", &path.display()), ("from", &version), ("to", &current)]));
+        Ok(true)
+    }
+
+    // Copies `path` to `<name>.<tag>.bak` beside it, without the secrets.
+    fn backup(&self, path: &Path, tag: &str) -> Result<PathBuf> {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let target = path.with_file_name(format!("{}.{}.bak", name, tag));
+        if self.secrets.is_empty() {
+            std::fs::copy(path, &target)?;
+        } else {
+            let mut value: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+            strip_keys(&mut value, self.secrets);
+            crate::utils::write_atomic(&target, serde_json::to_string_pretty(&value)?)?;
+        }
+        Ok(target)
+    }
+
+    /// Marks a serialized value with the current version before it is saved.
+    pub fn stamp(&self, value: &mut Value) {
+        if let Some(object) = value.as_object_mut() {
+            object.insert(VERSION_KEY.to_string(), Value::from(self.current()));
+        }
+    }
+}
+
+fn strip_keys(value: &mut Value, keys: &[&str]) {
+    match value {
+        Value::Object(object) => {
+            object.retain(|key, _| !keys.contains(&key.as_str()));
+            object.values_mut().for_each(|value| strip_keys(value, keys));
+        }
+        Value::Array(items) => items.iter_mut().for_each(|value| strip_keys(value, keys)),
+        _ => {}
+    }
+}
+
+// For formats that only gained the version field.
+fn stamp_only(_value: &mut Value) -> Result<()> {
+    Ok(())
+}
+
+// accounts.json used to be a bare array, which has nowhere to keep the
+// version.
+fn accounts_v1(value: &mut Value) -> Result<()> {
+    if value.is_array() {
+        *value = serde_json::json!({ "accounts": value.take() });
+    }
+    Ok(())
+}
@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
+use crate::i18n::tr;
+use crate::logs::LogRotation;
+use crate::schema;
+use crate::version::VersionChannels;
 use crate::{Error, Result};
 
 fn default_save_logs_to_file() -> bool {
@@ -8,28 +12,75 @@ fn default_save_logs_to_file() -> bool {
 }
 
 fn default_logs_directory() -> PathBuf {
-    dirs::data_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("mango-launcher")
-        .join("logs")
+    crate::utils::data_dir().join("logs")
 }
 
 fn default_log_retention_hours() -> u32 {
     24
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+fn default_log_max_file_mb() -> u32 {
+    10
+}
+
+fn default_backup_retention() -> u32 {
+    5
+}
+
+fn default_log_memory_limit_mb() -> u32 {
+    64
+}
+
+fn default_tick_rate_ms() -> u64 {
+    250
+}
+
+fn default_account_picker() -> bool {
+    true
+}
+
+fn default_max_concurrent_asset_downloads() -> u32 {
+    16
+}
+
+// Hashing is CPU-bound, so verify and repair leave half the cores free.
+fn default_max_concurrent_verifications() -> u32 {
+    std::thread::available_parallelism().map_or(2, |cores| (cores.get() / 2).max(1)) as u32
+}
+
+pub const DEFAULT_META_URL: &str = "https://launchermeta.mojang.com";
+pub const DEFAULT_RESOURCES_URL: &str = "https://resources.download.minecraft.net";
+pub const DEFAULT_LIBRARIES_URL: &str = "https://libraries.minecraft.net";
+
+fn default_meta_url() -> String {
+    DEFAULT_META_URL.to_string()
+}
+
+fn default_resources_url() -> String {
+    DEFAULT_RESOURCES_URL.to_string()
+}
+
+fn default_libraries_url() -> String {
+    DEFAULT_LIBRARIES_URL.to_string()
+}
+
+// Tried in order, separated by spaces, when the main host fails. Every file
+// is checked against its SHA-1, so a mirror cannot slip in other content.
+fn default_fallback_resources_urls() -> String {
+    "https://bmclapi2.bangbang93.com/assets".to_string()
+}
+
+fn default_fallback_libraries_urls() -> String {
+    "https://bmclapi2.bangbang93.com/maven https://repo1.maven.org/maven2".to_string()
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub enum Language {
+    #[default]
     Russian,
     English,
 }
 
-impl Default for Language {
-    fn default() -> Self {
-        Language::Russian
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
     pub general: GeneralSettings,
@@ -50,6 +101,14 @@ pub struct GeneralSettings {
     pub send_analytics: bool,
     pub maximize_on_launch: bool,
     pub close_launcher_on_game_start: bool,
+    #[serde(default)]
+    pub remember_instance_account: bool,
+    #[serde(default = "default_account_picker")]
+    pub account_picker: bool,
+    #[serde(default)]
+    pub install_updates: bool,
+    #[serde(default)]
+    pub shared_data_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +121,12 @@ pub struct JavaSettings {
     pub additional_args: String,
     pub auto_detect_installations: bool,
     pub download_missing_java: bool,
+    /// Installations added by hand or downloaded, kept across scans.
+    #[serde(default)]
+    pub custom_installations: Vec<PathBuf>,
+    /// Detected installations the user removed; scans skip them.
+    #[serde(default)]
+    pub hidden_installations: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,6 +138,8 @@ pub struct MinecraftSettings {
     pub pre_launch_command: Option<String>,
     pub post_exit_command: Option<String>,
     pub wrapper_command: Option<String>,
+    #[serde(default)]
+    pub legacy_resource_proxy: Option<String>,
     pub enable_console: bool,
     pub auto_close_console: bool,
 }
@@ -87,6 +154,20 @@ pub struct UiSettings {
     pub show_console: bool,
     pub icon_size: String,
     pub group_view: bool,
+    #[serde(default = "default_tick_rate_ms")]
+    pub tick_rate_ms: u64,
+    #[serde(default)]
+    pub rotate_art: bool,
+    #[serde(default)]
+    pub motd_url: Option<String>,
+    #[serde(default)]
+    pub reduced_motion: bool,
+    #[serde(default)]
+    pub show_snapshots: bool,
+    #[serde(default)]
+    pub show_legacy_versions: bool,
+    #[serde(default)]
+    pub show_april_fools: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -99,7 +180,23 @@ pub struct NetworkSettings {
     pub proxy_password: Option<String>,
     pub timeout: u64,
     pub max_concurrent_downloads: u32,
+    #[serde(default = "default_max_concurrent_asset_downloads")]
+    pub max_concurrent_asset_downloads: u32,
     pub user_agent: String,
+    #[serde(default = "default_meta_url")]
+    pub meta_url: String,
+    #[serde(default = "default_resources_url")]
+    pub resources_url: String,
+    #[serde(default = "default_libraries_url")]
+    pub libraries_url: String,
+    #[serde(default)]
+    pub curseforge_api_key: Option<String>,
+    #[serde(default = "default_max_concurrent_verifications")]
+    pub max_concurrent_verifications: u32,
+    #[serde(default = "default_fallback_resources_urls")]
+    pub fallback_resources_urls: String,
+    #[serde(default = "default_fallback_libraries_urls")]
+    pub fallback_libraries_urls: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -117,13 +214,90 @@ pub struct AdvancedSettings {
     pub logs_directory: PathBuf,
     #[serde(default = "default_log_retention_hours")]
     pub log_retention_hours: u32,
+    #[serde(default = "default_backup_retention")]
+    pub backup_retention: u32,
+    #[serde(default = "default_log_memory_limit_mb")]
+    pub log_memory_limit_mb: u32,
+    #[serde(default = "default_log_max_file_mb")]
+    pub log_max_file_mb: u32,
+    #[serde(default)]
+    pub compress_rotated_logs: bool,
+}
+
+impl Settings {
+    pub fn validate(&self) -> Result<()> {
+        if self.java.memory_min > self.java.memory_max {
+            return Err(Error::Settings(
+                "Minimum memory cannot be greater than maximum memory".to_string()
+            ));
+        }
+
+        if self.network.proxy_port == 0 {
+            return Err(Error::Settings("Proxy port cannot be 0".to_string()));
+        }
+
+        if self.network.use_proxy && self.network.proxy_host.trim().is_empty() {
+            return Err(Error::Settings("Proxy host is required when the proxy is enabled".to_string()));
+        }
+
+        crate::logging::LogFilter::parse(&self.advanced.log_level).map_err(Error::Settings)?;
+
+        if self.network.timeout == 0 {
+            return Err(Error::Settings("Network timeout cannot be 0".to_string()));
+        }
+
+        if self.network.user_agent.trim().is_empty() {
+            return Err(Error::Settings("User agent cannot be empty".to_string()));
+        }
+
+        for (name, url) in [
+            ("Version manifest host", &self.network.meta_url),
+            ("Resources host", &self.network.resources_url),
+            ("Libraries host", &self.network.libraries_url),
+        ] {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(Error::Settings(format!("{} must be an http(s) URL", name)));
+            }
+        }
+
+        for (name, urls) in [
+            ("Fallback resource hosts", &self.network.fallback_resources_urls),
+            ("Fallback library hosts", &self.network.fallback_libraries_urls),
+        ] {
+            if urls.split_whitespace().any(|url| !url.starts_with("http://") && !url.starts_with("https://")) {
+                return Err(Error::Settings(format!("{} must be http(s) URLs separated by spaces", name)));
+            }
+        }
+
+        if let Some(proxy) = &self.minecraft.legacy_resource_proxy {
+            if split_host_port(proxy).is_none() {
+                return Err(Error::Settings("Legacy resource proxy must be host:port".to_string()));
+            }
+        }
+
+        for (name, path) in [
+            ("Instances directory", &self.general.instances_directory),
+            ("Java directory", &self.general.java_directory),
+            ("Logs directory", &self.advanced.logs_directory),
+        ] {
+            if !path.is_absolute() {
+                return Err(Error::Settings(format!("{} must be an absolute path", name)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn split_host_port(value: &str) -> Option<(&str, u16)> {
+    let (host, port) = value.trim().rsplit_once(':')?;
+    let port = port.parse().ok().filter(|port| *port != 0)?;
+    (!host.is_empty()).then_some((host, port))
 }
 
 impl Default for Settings {
     fn default() -> Self {
-        let data_dir = dirs::data_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("mango-launcher");
+        let data_dir = crate::utils::data_dir();
 
         Self {
             general: GeneralSettings {
@@ -135,6 +309,10 @@ impl Default for Settings {
                 send_analytics: false,
                 maximize_on_launch: false,
                 close_launcher_on_game_start: false,
+                remember_instance_account: false,
+                account_picker: true,
+                install_updates: false,
+                shared_data_dir: None,
             },
             java: JavaSettings {
                 default_installation: None,
@@ -145,6 +323,8 @@ impl Default for Settings {
                 additional_args: String::new(),
                 auto_detect_installations: true,
                 download_missing_java: true,
+                custom_installations: Vec::new(),
+                hidden_installations: Vec::new(),
             },
             minecraft: MinecraftSettings {
                 default_width: 854,
@@ -154,6 +334,7 @@ impl Default for Settings {
                 pre_launch_command: None,
                 post_exit_command: None,
                 wrapper_command: None,
+                legacy_resource_proxy: None,
                 enable_console: true,
                 auto_close_console: false,
             },
@@ -166,6 +347,13 @@ impl Default for Settings {
                 show_console: false,
                 icon_size: "medium".to_string(),
                 group_view: true,
+                tick_rate_ms: 250,
+                rotate_art: false,
+                motd_url: None,
+                reduced_motion: false,
+                show_snapshots: false,
+                show_legacy_versions: false,
+                show_april_fools: false,
             },
             network: NetworkSettings {
                 use_proxy: false,
@@ -176,7 +364,15 @@ impl Default for Settings {
                 proxy_password: None,
                 timeout: 30,
                 max_concurrent_downloads: 4,
+                max_concurrent_asset_downloads: default_max_concurrent_asset_downloads(),
                 user_agent: "mango-launcher/1.0".to_string(),
+                meta_url: default_meta_url(),
+                resources_url: default_resources_url(),
+                libraries_url: default_libraries_url(),
+                curseforge_api_key: None,
+                max_concurrent_verifications: default_max_concurrent_verifications(),
+                fallback_resources_urls: default_fallback_resources_urls(),
+                fallback_libraries_urls: default_fallback_libraries_urls(),
             },
             advanced: AdvancedSettings {
                 enable_logging: true,
@@ -189,6 +385,10 @@ impl Default for Settings {
                 save_logs_to_file: true,
                 logs_directory: data_dir.join("logs"),
                 log_retention_hours: 24,
+                backup_retention: default_backup_retention(),
+                log_memory_limit_mb: default_log_memory_limit_mb(),
+                log_max_file_mb: 10,
+                compress_rotated_logs: false,
             },
         }
     }
@@ -198,6 +398,7 @@ pub struct SettingsManager {
     settings: Settings,
     settings_path: PathBuf,
     dirty: bool,
+    first_run: bool,
 }
 
 impl SettingsManager {
@@ -206,6 +407,7 @@ impl SettingsManager {
             settings: Settings::default(),
             settings_path,
             dirty: false,
+            first_run: false,
         };
 
         manager.load()?;
@@ -279,32 +481,87 @@ impl SettingsManager {
             std::fs::create_dir_all(parent)?;
         }
 
-        let content = toml::to_string_pretty(&self.settings)
-            .map_err(|e| Error::Settings(format!("Failed to serialize settings: {}", e)))?;
-
-        std::fs::write(&self.settings_path, content)?;
+        let content = self.serialize()?;
+        crate::utils::save_data_file(&self.settings_path, content)?;
         self.dirty = false;
 
-        log::info!("Settings saved to {:?}", self.settings_path);
+        tracing::info!("Settings saved to {:?}", self.settings_path);
         Ok(())
     }
 
     pub fn load(&mut self) -> Result<()> {
         if !self.settings_path.exists() {
+            self.first_run = true;
             self.dirty = true;
             return self.save();
         }
 
         let content = std::fs::read_to_string(&self.settings_path)?;
-        
-        self.settings = toml::from_str(&content)
-            .map_err(|e| Error::Settings(format!("Failed to parse settings: {}", e)))?;
+        let migrated = self.parse(&self.settings_path.clone(), &content)?;
 
         self.dirty = false;
-        log::info!("Settings loaded from {:?}", self.settings_path);
+        tracing::info!("Settings loaded from {:?}", self.settings_path);
+        if migrated {
+            self.dirty = true;
+            self.save()?;
+        }
         Ok(())
     }
 
+    // Directories inside the data folder are written relative to it, so the
+    // folder can be moved, or carried on a USB stick that comes up under
+    // another drive letter, without the settings pointing at the old place.
+    fn data_paths(settings: &mut Settings) -> [&mut PathBuf; 3] {
+        [
+            &mut settings.general.instances_directory,
+            &mut settings.general.java_directory,
+            &mut settings.advanced.logs_directory,
+        ]
+    }
+
+    fn base_dir(&self) -> PathBuf {
+        self.settings_path.parent().map(Path::to_path_buf).unwrap_or_default()
+    }
+
+    // The file is migrated as a generic document, so older layouts can be
+    // reshaped before they have to match `Settings`.
+    fn parse(&mut self, path: &Path, content: &str) -> Result<bool> {
+        let table: toml::Table = toml::from_str(content)
+            .map_err(|e| Error::Settings(format!("Failed to parse settings: {}", e)))?;
+        let (settings, migrated) = schema::SETTINGS.load(path, serde_json::to_value(table)?)?;
+        self.settings = settings;
+        self.resolve_data_paths();
+        Ok(migrated)
+    }
+
+    fn serialize(&self) -> Result<String> {
+        let mut table = toml::Table::try_from(self.stored_settings())
+            .map_err(|e| Error::Settings(format!("Failed to serialize settings: {}", e)))?;
+        table.insert(schema::VERSION_KEY.to_string(), toml::Value::Integer(schema::SETTINGS.current().into()));
+        toml::to_string_pretty(&table)
+            .map_err(|e| Error::Settings(format!("Failed to serialize settings: {}", e)))
+    }
+
+    fn stored_settings(&self) -> Settings {
+        let base = self.base_dir();
+        let mut stored = self.settings.clone();
+        for path in Self::data_paths(&mut stored) {
+            if let Ok(relative) = path.strip_prefix(&base) {
+                *path = relative.to_path_buf();
+            }
+        }
+        stored
+    }
+
+    fn resolve_data_paths(&mut self) {
+        let base = self.base_dir();
+        for path in Self::data_paths(&mut self.settings) {
+            if path.is_relative() {
+                *path = base.join(&*path);
+            }
+        }
+    }
+
     pub fn auto_save(&mut self) -> Result<()> {
         if self.dirty {
             self.save()?;
@@ -316,39 +573,32 @@ impl SettingsManager {
         self.dirty
     }
 
-    pub fn validate(&self) -> Result<()> {
-        if self.settings.java.memory_min > self.settings.java.memory_max {
-            return Err(Error::Settings(
-                "Minimum memory cannot be greater than maximum memory".to_string()
-            ));
-        }
-
-        if self.settings.network.proxy_port == 0 {
-            return Err(Error::Settings("Proxy port cannot be 0".to_string()));
-        }
+    pub fn is_first_run(&self) -> bool {
+        self.first_run
+    }
 
-        if !self.settings.general.instances_directory.is_absolute() {
-            return Err(Error::Settings(
-                "Instances directory must be an absolute path".to_string()
-            ));
-        }
+    pub fn validate(&self) -> Result<()> {
+        self.settings.validate()
+    }
 
-        Ok(())
+    pub fn update(&mut self, field: SettingField, value: SettingValue) -> Result<()> {
+        let mut candidate = self.settings.clone();
+        field.set(&mut candidate, value);
+        candidate.validate()?;
+        self.settings = candidate;
+        self.dirty = true;
+        self.save()
     }
 
     pub fn export_to_file(&self, path: &Path) -> Result<()> {
-        let content = toml::to_string_pretty(&self.settings)
-            .map_err(|e| Error::Settings(format!("Failed to serialize settings: {}", e)))?;
-
-        std::fs::write(path, content)?;
+        let content = self.serialize()?;
+        crate::utils::write_atomic(path, content)?;
         Ok(())
     }
 
     pub fn import_from_file(&mut self, path: &Path) -> Result<()> {
         let content = std::fs::read_to_string(path)?;
-        
-        self.settings = toml::from_str(&content)
-            .map_err(|e| Error::Settings(format!("Failed to parse settings: {}", e)))?;
+        self.parse(path, &content)?;
 
         self.validate()?;
         self.dirty = true;
@@ -358,9 +608,7 @@ impl SettingsManager {
 
 impl GeneralSettings {
     fn default() -> Self {
-        let data_dir = dirs::data_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("mango-launcher");
+        let data_dir = crate::utils::data_dir();
 
         Self {
             language: Language::Russian,
@@ -371,6 +619,10 @@ impl GeneralSettings {
             send_analytics: false,
             maximize_on_launch: false,
             close_launcher_on_game_start: false,
+            remember_instance_account: false,
+            account_picker: true,
+            install_updates: false,
+            shared_data_dir: None,
         }
     }
 }
@@ -386,6 +638,8 @@ impl JavaSettings {
             additional_args: String::new(),
             auto_detect_installations: true,
             download_missing_java: true,
+            custom_installations: Vec::new(),
+            hidden_installations: Vec::new(),
         }
     }
 }
@@ -400,6 +654,7 @@ impl MinecraftSettings {
             pre_launch_command: None,
             post_exit_command: None,
             wrapper_command: None,
+            legacy_resource_proxy: None,
             enable_console: true,
             auto_close_console: false,
         }
@@ -417,6 +672,23 @@ impl UiSettings {
             show_console: false,
             icon_size: "medium".to_string(),
             group_view: true,
+            tick_rate_ms: 250,
+            rotate_art: false,
+            motd_url: None,
+            reduced_motion: false,
+            show_snapshots: false,
+            show_legacy_versions: false,
+            show_april_fools: false,
+        }
+    }
+}
+
+impl UiSettings {
+    pub fn version_channels(&self) -> VersionChannels {
+        VersionChannels {
+            snapshots: self.show_snapshots,
+            legacy: self.show_legacy_versions,
+            april_fools: self.show_april_fools,
         }
     }
 }
@@ -432,16 +704,22 @@ impl NetworkSettings {
             proxy_password: None,
             timeout: 30,
             max_concurrent_downloads: 4,
+            max_concurrent_asset_downloads: default_max_concurrent_asset_downloads(),
             user_agent: "mango-launcher/1.0".to_string(),
+            meta_url: default_meta_url(),
+            resources_url: default_resources_url(),
+            libraries_url: default_libraries_url(),
+            curseforge_api_key: None,
+            max_concurrent_verifications: default_max_concurrent_verifications(),
+            fallback_resources_urls: default_fallback_resources_urls(),
+            fallback_libraries_urls: default_fallback_libraries_urls(),
         }
     }
 }
 
 impl AdvancedSettings {
     fn default() -> Self {
-        let data_dir = dirs::data_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("mango-launcher");
+        let data_dir = crate::utils::data_dir();
             
         Self {
             enable_logging: true,
@@ -454,6 +732,675 @@ impl AdvancedSettings {
             save_logs_to_file: true,
             logs_directory: data_dir.join("logs"),
             log_retention_hours: 24,
+            backup_retention: default_backup_retention(),
+            log_memory_limit_mb: default_log_memory_limit_mb(),
+            log_max_file_mb: 10,
+            compress_rotated_logs: false,
+        }
+    }
+} 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingKind {
+    Toggle,
+    Number { min: u64, max: u64 },
+    Text,
+    OptionalText,
+    Secret,
+    Path,
+    OptionalPath,
+    Choice(&'static [&'static str]),
+    Language,
+}
+
+impl AdvancedSettings {
+    pub fn log_rotation(&self) -> LogRotation {
+        LogRotation {
+            max_file_bytes: self.log_max_file_mb as u64 * 1024 * 1024,
+            retention_hours: self.log_retention_hours,
+            compress: self.compress_rotated_logs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingValue {
+    Bool(bool),
+    Number(u64),
+    Text(String),
+    OptionalText(Option<String>),
+    Path(PathBuf),
+    OptionalPath(Option<PathBuf>),
+    Language(Language),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingField {
+    Language,
+    Theme,
+    InstancesDirectory,
+    JavaDirectory,
+    SharedDataDir,
+    CheckForUpdates,
+    InstallUpdates,
+    SendAnalytics,
+    MaximizeOnLaunch,
+    CloseLauncherOnGameStart,
+    RememberInstanceAccount,
+    AccountPicker,
+    DefaultJava,
+    MemoryMin,
+    MemoryMax,
+    PermgenSize,
+    GcArgs,
+    AdditionalArgs,
+    AutoDetectJava,
+    DownloadMissingJava,
+    DefaultWidth,
+    DefaultHeight,
+    Fullscreen,
+    AutoLogin,
+    PreLaunchCommand,
+    PostExitCommand,
+    WrapperCommand,
+    LegacyResourceProxy,
+    EnableConsole,
+    AutoCloseConsole,
+    WindowWidth,
+    WindowHeight,
+    WindowMaximized,
+    InstanceViewType,
+    SortMode,
+    ShowConsole,
+    IconSize,
+    GroupView,
+    TickRate,
+    RotateArt,
+    MotdUrl,
+    ReducedMotion,
+    ShowSnapshots,
+    ShowLegacyVersions,
+    ShowAprilFools,
+    UseProxy,
+    ProxyType,
+    ProxyHost,
+    ProxyPort,
+    ProxyUsername,
+    ProxyPassword,
+    Timeout,
+    LibraryDownloads,
+    AssetDownloads,
+    Verifications,
+    UserAgent,
+    MetaUrl,
+    ResourcesUrl,
+    LibrariesUrl,
+    FallbackResourcesUrls,
+    FallbackLibrariesUrls,
+    CurseForgeApiKey,
+    EnableLogging,
+    LogLevel,
+    ConsoleMaxLines,
+    EnableProfiling,
+    DeveloperMode,
+    SaveLogsToFile,
+    LogsDirectory,
+    LogRetentionHours,
+    LogMaxFileSize,
+    CompressRotatedLogs,
+    BackupRetention,
+    LogMemoryLimit,
+}
+
+impl SettingField {
+    pub const ALL: [SettingField; 74] = [
+        SettingField::Language,
+        SettingField::Theme,
+        SettingField::InstancesDirectory,
+        SettingField::JavaDirectory,
+        SettingField::SharedDataDir,
+        SettingField::CheckForUpdates,
+        SettingField::InstallUpdates,
+        SettingField::SendAnalytics,
+        SettingField::MaximizeOnLaunch,
+        SettingField::CloseLauncherOnGameStart,
+        SettingField::RememberInstanceAccount,
+        SettingField::AccountPicker,
+        SettingField::DefaultJava,
+        SettingField::MemoryMin,
+        SettingField::MemoryMax,
+        SettingField::PermgenSize,
+        SettingField::GcArgs,
+        SettingField::AdditionalArgs,
+        SettingField::AutoDetectJava,
+        SettingField::DownloadMissingJava,
+        SettingField::DefaultWidth,
+        SettingField::DefaultHeight,
+        SettingField::Fullscreen,
+        SettingField::AutoLogin,
+        SettingField::PreLaunchCommand,
+        SettingField::PostExitCommand,
+        SettingField::WrapperCommand,
+        SettingField::LegacyResourceProxy,
+        SettingField::EnableConsole,
+        SettingField::AutoCloseConsole,
+        SettingField::WindowWidth,
+        SettingField::WindowHeight,
+        SettingField::WindowMaximized,
+        SettingField::InstanceViewType,
+        SettingField::SortMode,
+        SettingField::ShowConsole,
+        SettingField::IconSize,
+        SettingField::GroupView,
+        SettingField::TickRate,
+        SettingField::RotateArt,
+        SettingField::MotdUrl,
+        SettingField::ReducedMotion,
+        SettingField::ShowSnapshots,
+        SettingField::ShowLegacyVersions,
+        SettingField::ShowAprilFools,
+        SettingField::UseProxy,
+        SettingField::ProxyType,
+        SettingField::ProxyHost,
+        SettingField::ProxyPort,
+        SettingField::ProxyUsername,
+        SettingField::ProxyPassword,
+        SettingField::Timeout,
+        SettingField::LibraryDownloads,
+        SettingField::AssetDownloads,
+        SettingField::Verifications,
+        SettingField::UserAgent,
+        SettingField::MetaUrl,
+        SettingField::ResourcesUrl,
+        SettingField::LibrariesUrl,
+        SettingField::FallbackResourcesUrls,
+        SettingField::FallbackLibrariesUrls,
+        SettingField::CurseForgeApiKey,
+        SettingField::EnableLogging,
+        SettingField::LogLevel,
+        SettingField::ConsoleMaxLines,
+        SettingField::EnableProfiling,
+        SettingField::DeveloperMode,
+        SettingField::SaveLogsToFile,
+        SettingField::LogsDirectory,
+        SettingField::LogRetentionHours,
+        SettingField::LogMaxFileSize,
+        SettingField::CompressRotatedLogs,
+        SettingField::BackupRetention,
+        SettingField::LogMemoryLimit,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            SettingField::Language => "general.language",
+            SettingField::Theme => "general.theme",
+            SettingField::InstancesDirectory => "general.instances_directory",
+            SettingField::JavaDirectory => "general.java_directory",
+            SettingField::SharedDataDir => "general.shared_data_dir",
+            SettingField::CheckForUpdates => "general.check_for_updates",
+            SettingField::InstallUpdates => "general.install_updates",
+            SettingField::SendAnalytics => "general.send_analytics",
+            SettingField::MaximizeOnLaunch => "general.maximize_on_launch",
+            SettingField::CloseLauncherOnGameStart => "general.close_launcher_on_game_start",
+            SettingField::RememberInstanceAccount => "general.remember_instance_account",
+            SettingField::AccountPicker => "general.account_picker",
+            SettingField::DefaultJava => "java.default_installation",
+            SettingField::MemoryMin => "java.memory_min",
+            SettingField::MemoryMax => "java.memory_max",
+            SettingField::PermgenSize => "java.permgen_size",
+            SettingField::GcArgs => "java.gc_args",
+            SettingField::AdditionalArgs => "java.additional_args",
+            SettingField::AutoDetectJava => "java.auto_detect_installations",
+            SettingField::DownloadMissingJava => "java.download_missing_java",
+            SettingField::DefaultWidth => "minecraft.default_width",
+            SettingField::DefaultHeight => "minecraft.default_height",
+            SettingField::Fullscreen => "minecraft.fullscreen",
+            SettingField::AutoLogin => "minecraft.auto_login",
+            SettingField::PreLaunchCommand => "minecraft.pre_launch_command",
+            SettingField::PostExitCommand => "minecraft.post_exit_command",
+            SettingField::WrapperCommand => "minecraft.wrapper_command",
+            SettingField::LegacyResourceProxy => "minecraft.legacy_resource_proxy",
+            SettingField::EnableConsole => "minecraft.enable_console",
+            SettingField::AutoCloseConsole => "minecraft.auto_close_console",
+            SettingField::WindowWidth => "ui.window_width",
+            SettingField::WindowHeight => "ui.window_height",
+            SettingField::WindowMaximized => "ui.window_maximized",
+            SettingField::InstanceViewType => "ui.instance_view_type",
+            SettingField::SortMode => "ui.sort_mode",
+            SettingField::ShowConsole => "ui.show_console",
+            SettingField::IconSize => "ui.icon_size",
+            SettingField::GroupView => "ui.group_view",
+            SettingField::TickRate => "ui.tick_rate_ms",
+            SettingField::RotateArt => "ui.rotate_art",
+            SettingField::MotdUrl => "ui.motd_url",
+            SettingField::ReducedMotion => "ui.reduced_motion",
+            SettingField::ShowSnapshots => "ui.show_snapshots",
+            SettingField::ShowLegacyVersions => "ui.show_legacy_versions",
+            SettingField::ShowAprilFools => "ui.show_april_fools",
+            SettingField::UseProxy => "network.use_proxy",
+            SettingField::ProxyType => "network.proxy_type",
+            SettingField::ProxyHost => "network.proxy_host",
+            SettingField::ProxyPort => "network.proxy_port",
+            SettingField::ProxyUsername => "network.proxy_username",
+            SettingField::ProxyPassword => "network.proxy_password",
+            SettingField::Timeout => "network.timeout",
+            SettingField::LibraryDownloads => "network.max_concurrent_downloads",
+            SettingField::AssetDownloads => "network.max_concurrent_asset_downloads",
+            SettingField::Verifications => "network.max_concurrent_verifications",
+            SettingField::UserAgent => "network.user_agent",
+            SettingField::MetaUrl => "network.meta_url",
+            SettingField::ResourcesUrl => "network.resources_url",
+            SettingField::LibrariesUrl => "network.libraries_url",
+            SettingField::FallbackResourcesUrls => "network.fallback_resources_urls",
+            SettingField::FallbackLibrariesUrls => "network.fallback_libraries_urls",
+            SettingField::CurseForgeApiKey => "network.curseforge_api_key",
+            SettingField::EnableLogging => "advanced.enable_logging",
+            SettingField::LogLevel => "advanced.log_level",
+            SettingField::ConsoleMaxLines => "advanced.console_max_lines",
+            SettingField::EnableProfiling => "advanced.enable_profiling",
+            SettingField::DeveloperMode => "advanced.developer_mode",
+            SettingField::SaveLogsToFile => "advanced.save_logs_to_file",
+            SettingField::LogsDirectory => "advanced.logs_directory",
+            SettingField::LogRetentionHours => "advanced.log_retention_hours",
+            SettingField::LogMaxFileSize => "advanced.log_max_file_mb",
+            SettingField::CompressRotatedLogs => "advanced.compress_rotated_logs",
+            SettingField::BackupRetention => "advanced.backup_retention",
+            SettingField::LogMemoryLimit => "advanced.log_memory_limit_mb",
+        }
+    }
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            SettingField::Language => "setting.general.language",
+            SettingField::Theme => "setting.general.theme",
+            SettingField::InstancesDirectory => "setting.general.instances_directory",
+            SettingField::JavaDirectory => "setting.general.java_directory",
+            SettingField::SharedDataDir => "setting.general.shared_data_dir",
+            SettingField::CheckForUpdates => "setting.general.check_for_updates",
+            SettingField::InstallUpdates => "setting.general.install_updates",
+            SettingField::SendAnalytics => "setting.general.send_analytics",
+            SettingField::MaximizeOnLaunch => "setting.general.maximize_on_launch",
+            SettingField::CloseLauncherOnGameStart => "setting.general.close_launcher_on_game_start",
+            SettingField::RememberInstanceAccount => "setting.general.remember_instance_account",
+            SettingField::AccountPicker => "setting.general.account_picker",
+            SettingField::DefaultJava => "setting.java.default_installation",
+            SettingField::MemoryMin => "setting.java.memory_min",
+            SettingField::MemoryMax => "setting.java.memory_max",
+            SettingField::PermgenSize => "setting.java.permgen_size",
+            SettingField::GcArgs => "setting.java.gc_args",
+            SettingField::AdditionalArgs => "setting.java.additional_args",
+            SettingField::AutoDetectJava => "setting.java.auto_detect_installations",
+            SettingField::DownloadMissingJava => "setting.java.download_missing_java",
+            SettingField::DefaultWidth => "setting.minecraft.default_width",
+            SettingField::DefaultHeight => "setting.minecraft.default_height",
+            SettingField::Fullscreen => "setting.minecraft.fullscreen",
+            SettingField::AutoLogin => "setting.minecraft.auto_login",
+            SettingField::PreLaunchCommand => "setting.minecraft.pre_launch_command",
+            SettingField::PostExitCommand => "setting.minecraft.post_exit_command",
+            SettingField::WrapperCommand => "setting.minecraft.wrapper_command",
+            SettingField::LegacyResourceProxy => "setting.minecraft.legacy_resource_proxy",
+            SettingField::EnableConsole => "setting.minecraft.enable_console",
+            SettingField::AutoCloseConsole => "setting.minecraft.auto_close_console",
+            SettingField::WindowWidth => "setting.ui.window_width",
+            SettingField::WindowHeight => "setting.ui.window_height",
+            SettingField::WindowMaximized => "setting.ui.window_maximized",
+            SettingField::InstanceViewType => "setting.ui.instance_view_type",
+            SettingField::SortMode => "setting.ui.sort_mode",
+            SettingField::ShowConsole => "setting.ui.show_console",
+            SettingField::IconSize => "setting.ui.icon_size",
+            SettingField::GroupView => "setting.ui.group_view",
+            SettingField::TickRate => "setting.ui.tick_rate_ms",
+            SettingField::RotateArt => "setting.ui.rotate_art",
+            SettingField::MotdUrl => "setting.ui.motd_url",
+            SettingField::ReducedMotion => "setting.ui.reduced_motion",
+            SettingField::ShowSnapshots => "setting.ui.show_snapshots",
+            SettingField::ShowLegacyVersions => "setting.ui.show_legacy_versions",
+            SettingField::ShowAprilFools => "setting.ui.show_april_fools",
+            SettingField::UseProxy => "setting.network.use_proxy",
+            SettingField::ProxyType => "setting.network.proxy_type",
+            SettingField::ProxyHost => "setting.network.proxy_host",
+            SettingField::ProxyPort => "setting.network.proxy_port",
+            SettingField::ProxyUsername => "setting.network.proxy_username",
+            SettingField::ProxyPassword => "setting.network.proxy_password",
+            SettingField::Timeout => "setting.network.timeout",
+            SettingField::LibraryDownloads => "setting.network.max_concurrent_downloads",
+            SettingField::AssetDownloads => "setting.network.max_concurrent_asset_downloads",
+            SettingField::Verifications => "setting.network.max_concurrent_verifications",
+            SettingField::UserAgent => "setting.network.user_agent",
+            SettingField::MetaUrl => "setting.network.meta_url",
+            SettingField::ResourcesUrl => "setting.network.resources_url",
+            SettingField::LibrariesUrl => "setting.network.libraries_url",
+            SettingField::FallbackResourcesUrls => "setting.network.fallback_resources_urls",
+            SettingField::FallbackLibrariesUrls => "setting.network.fallback_libraries_urls",
+            SettingField::CurseForgeApiKey => "setting.network.curseforge_api_key",
+            SettingField::EnableLogging => "setting.advanced.enable_logging",
+            SettingField::LogLevel => "setting.advanced.log_level",
+            SettingField::ConsoleMaxLines => "setting.advanced.console_max_lines",
+            SettingField::EnableProfiling => "setting.advanced.enable_profiling",
+            SettingField::DeveloperMode => "setting.advanced.developer_mode",
+            SettingField::SaveLogsToFile => "setting.advanced.save_logs_to_file",
+            SettingField::LogsDirectory => "setting.advanced.logs_directory",
+            SettingField::LogRetentionHours => "setting.advanced.log_retention_hours",
+            SettingField::LogMaxFileSize => "setting.advanced.log_max_file_mb",
+            SettingField::CompressRotatedLogs => "setting.advanced.compress_rotated_logs",
+            SettingField::BackupRetention => "setting.advanced.backup_retention",
+            SettingField::LogMemoryLimit => "setting.advanced.log_memory_limit_mb",
+        }
+    }
+
+    pub fn section(&self) -> &'static str {
+        self.key().split('.').next().unwrap_or_default()
+    }
+
+    pub fn kind(&self) -> SettingKind {
+        match self {
+            SettingField::Language => SettingKind::Language,
+            SettingField::Theme => SettingKind::Choice(&["dark", "light"]),
+            SettingField::InstancesDirectory => SettingKind::Path,
+            SettingField::JavaDirectory => SettingKind::Path,
+            SettingField::SharedDataDir => SettingKind::OptionalPath,
+            SettingField::CheckForUpdates => SettingKind::Toggle,
+            SettingField::InstallUpdates => SettingKind::Toggle,
+            SettingField::SendAnalytics => SettingKind::Toggle,
+            SettingField::MaximizeOnLaunch => SettingKind::Toggle,
+            SettingField::CloseLauncherOnGameStart => SettingKind::Toggle,
+            SettingField::RememberInstanceAccount => SettingKind::Toggle,
+            SettingField::AccountPicker => SettingKind::Toggle,
+            SettingField::DefaultJava => SettingKind::OptionalPath,
+            SettingField::MemoryMin => SettingKind::Number { min: 256, max: 65536 },
+            SettingField::MemoryMax => SettingKind::Number { min: 512, max: 65536 },
+            SettingField::PermgenSize => SettingKind::Number { min: 32, max: 1024 },
+            SettingField::GcArgs => SettingKind::Text,
+            SettingField::AdditionalArgs => SettingKind::Text,
+            SettingField::AutoDetectJava => SettingKind::Toggle,
+            SettingField::DownloadMissingJava => SettingKind::Toggle,
+            SettingField::DefaultWidth => SettingKind::Number { min: 320, max: 7680 },
+            SettingField::DefaultHeight => SettingKind::Number { min: 240, max: 4320 },
+            SettingField::Fullscreen => SettingKind::Toggle,
+            SettingField::AutoLogin => SettingKind::Toggle,
+            SettingField::PreLaunchCommand => SettingKind::OptionalText,
+            SettingField::PostExitCommand => SettingKind::OptionalText,
+            SettingField::WrapperCommand => SettingKind::OptionalText,
+            SettingField::LegacyResourceProxy => SettingKind::OptionalText,
+            SettingField::EnableConsole => SettingKind::Toggle,
+            SettingField::AutoCloseConsole => SettingKind::Toggle,
+            SettingField::WindowWidth => SettingKind::Number { min: 320, max: 7680 },
+            SettingField::WindowHeight => SettingKind::Number { min: 240, max: 4320 },
+            SettingField::WindowMaximized => SettingKind::Toggle,
+            SettingField::InstanceViewType => SettingKind::Choice(&["icons", "list", "table"]),
+            SettingField::SortMode => SettingKind::Choice(&["name", "last_played", "version", "play_time"]),
+            SettingField::ShowConsole => SettingKind::Toggle,
+            SettingField::IconSize => SettingKind::Choice(&["small", "medium", "large"]),
+            SettingField::GroupView => SettingKind::Toggle,
+            SettingField::TickRate => SettingKind::Number { min: 16, max: 5000 },
+            SettingField::RotateArt => SettingKind::Toggle,
+            SettingField::MotdUrl => SettingKind::OptionalText,
+            SettingField::ReducedMotion => SettingKind::Toggle,
+            SettingField::ShowSnapshots => SettingKind::Toggle,
+            SettingField::ShowLegacyVersions => SettingKind::Toggle,
+            SettingField::ShowAprilFools => SettingKind::Toggle,
+            SettingField::UseProxy => SettingKind::Toggle,
+            SettingField::ProxyType => SettingKind::Choice(&["http", "socks5"]),
+            SettingField::ProxyHost => SettingKind::Text,
+            SettingField::ProxyPort => SettingKind::Number { min: 1, max: 65535 },
+            SettingField::ProxyUsername => SettingKind::OptionalText,
+            SettingField::ProxyPassword => SettingKind::Secret,
+            SettingField::Timeout => SettingKind::Number { min: 1, max: 600 },
+            SettingField::LibraryDownloads => SettingKind::Number { min: 1, max: 64 },
+            SettingField::AssetDownloads => SettingKind::Number { min: 1, max: 128 },
+            SettingField::Verifications => SettingKind::Number { min: 1, max: 64 },
+            SettingField::UserAgent => SettingKind::Text,
+            SettingField::MetaUrl => SettingKind::Text,
+            SettingField::ResourcesUrl => SettingKind::Text,
+            SettingField::LibrariesUrl => SettingKind::Text,
+            SettingField::FallbackResourcesUrls => SettingKind::Text,
+            SettingField::FallbackLibrariesUrls => SettingKind::Text,
+            SettingField::CurseForgeApiKey => SettingKind::Secret,
+            SettingField::EnableLogging => SettingKind::Toggle,
+            SettingField::LogLevel => SettingKind::Choice(&["error", "warn", "info", "debug", "trace"]),
+            SettingField::ConsoleMaxLines => SettingKind::Number { min: 100, max: 10000000 },
+            SettingField::EnableProfiling => SettingKind::Toggle,
+            SettingField::DeveloperMode => SettingKind::Toggle,
+            SettingField::SaveLogsToFile => SettingKind::Toggle,
+            SettingField::LogsDirectory => SettingKind::Path,
+            SettingField::LogRetentionHours => SettingKind::Number { min: 1, max: 8760 },
+            SettingField::LogMaxFileSize => SettingKind::Number { min: 1, max: 1024 },
+            SettingField::CompressRotatedLogs => SettingKind::Toggle,
+            SettingField::BackupRetention => SettingKind::Number { min: 1, max: 100 },
+            SettingField::LogMemoryLimit => SettingKind::Number { min: 4, max: 1024 },
+        }
+    }
+
+    pub fn get(&self, settings: &Settings) -> SettingValue {
+        match self {
+            SettingField::Language => SettingValue::Language(settings.general.language.clone()),
+            SettingField::Theme => SettingValue::Text(settings.general.theme.clone()),
+            SettingField::InstancesDirectory => SettingValue::Path(settings.general.instances_directory.clone()),
+            SettingField::JavaDirectory => SettingValue::Path(settings.general.java_directory.clone()),
+            SettingField::SharedDataDir => SettingValue::OptionalPath(settings.general.shared_data_dir.clone()),
+            SettingField::CheckForUpdates => SettingValue::Bool(settings.general.check_for_updates),
+            SettingField::InstallUpdates => SettingValue::Bool(settings.general.install_updates),
+            SettingField::SendAnalytics => SettingValue::Bool(settings.general.send_analytics),
+            SettingField::MaximizeOnLaunch => SettingValue::Bool(settings.general.maximize_on_launch),
+            SettingField::CloseLauncherOnGameStart => SettingValue::Bool(settings.general.close_launcher_on_game_start),
+            SettingField::RememberInstanceAccount => SettingValue::Bool(settings.general.remember_instance_account),
+            SettingField::AccountPicker => SettingValue::Bool(settings.general.account_picker),
+            SettingField::DefaultJava => SettingValue::OptionalPath(settings.java.default_installation.clone()),
+            SettingField::MemoryMin => SettingValue::Number(settings.java.memory_min as u64),
+            SettingField::MemoryMax => SettingValue::Number(settings.java.memory_max as u64),
+            SettingField::PermgenSize => SettingValue::Number(settings.java.permgen_size as u64),
+            SettingField::GcArgs => SettingValue::Text(settings.java.gc_args.clone()),
+            SettingField::AdditionalArgs => SettingValue::Text(settings.java.additional_args.clone()),
+            SettingField::AutoDetectJava => SettingValue::Bool(settings.java.auto_detect_installations),
+            SettingField::DownloadMissingJava => SettingValue::Bool(settings.java.download_missing_java),
+            SettingField::DefaultWidth => SettingValue::Number(settings.minecraft.default_width as u64),
+            SettingField::DefaultHeight => SettingValue::Number(settings.minecraft.default_height as u64),
+            SettingField::Fullscreen => SettingValue::Bool(settings.minecraft.fullscreen),
+            SettingField::AutoLogin => SettingValue::Bool(settings.minecraft.auto_login),
+            SettingField::PreLaunchCommand => SettingValue::OptionalText(settings.minecraft.pre_launch_command.clone()),
+            SettingField::PostExitCommand => SettingValue::OptionalText(settings.minecraft.post_exit_command.clone()),
+            SettingField::WrapperCommand => SettingValue::OptionalText(settings.minecraft.wrapper_command.clone()),
+            SettingField::LegacyResourceProxy => SettingValue::OptionalText(settings.minecraft.legacy_resource_proxy.clone()),
+            SettingField::EnableConsole => SettingValue::Bool(settings.minecraft.enable_console),
+            SettingField::AutoCloseConsole => SettingValue::Bool(settings.minecraft.auto_close_console),
+            SettingField::WindowWidth => SettingValue::Number(settings.ui.window_width as u64),
+            SettingField::WindowHeight => SettingValue::Number(settings.ui.window_height as u64),
+            SettingField::WindowMaximized => SettingValue::Bool(settings.ui.window_maximized),
+            SettingField::InstanceViewType => SettingValue::Text(settings.ui.instance_view_type.clone()),
+            SettingField::SortMode => SettingValue::Text(settings.ui.sort_mode.clone()),
+            SettingField::ShowConsole => SettingValue::Bool(settings.ui.show_console),
+            SettingField::IconSize => SettingValue::Text(settings.ui.icon_size.clone()),
+            SettingField::GroupView => SettingValue::Bool(settings.ui.group_view),
+            SettingField::TickRate => SettingValue::Number(settings.ui.tick_rate_ms),
+            SettingField::RotateArt => SettingValue::Bool(settings.ui.rotate_art),
+            SettingField::MotdUrl => SettingValue::OptionalText(settings.ui.motd_url.clone()),
+            SettingField::ReducedMotion => SettingValue::Bool(settings.ui.reduced_motion),
+            SettingField::ShowSnapshots => SettingValue::Bool(settings.ui.show_snapshots),
+            SettingField::ShowLegacyVersions => SettingValue::Bool(settings.ui.show_legacy_versions),
+            SettingField::ShowAprilFools => SettingValue::Bool(settings.ui.show_april_fools),
+            SettingField::UseProxy => SettingValue::Bool(settings.network.use_proxy),
+            SettingField::ProxyType => SettingValue::Text(settings.network.proxy_type.clone()),
+            SettingField::ProxyHost => SettingValue::Text(settings.network.proxy_host.clone()),
+            SettingField::ProxyPort => SettingValue::Number(settings.network.proxy_port as u64),
+            SettingField::ProxyUsername => SettingValue::OptionalText(settings.network.proxy_username.clone()),
+            SettingField::ProxyPassword => SettingValue::OptionalText(settings.network.proxy_password.clone()),
+            SettingField::Timeout => SettingValue::Number(settings.network.timeout),
+            SettingField::LibraryDownloads => SettingValue::Number(settings.network.max_concurrent_downloads as u64),
+            SettingField::AssetDownloads => SettingValue::Number(settings.network.max_concurrent_asset_downloads as u64),
+            SettingField::Verifications => SettingValue::Number(settings.network.max_concurrent_verifications as u64),
+            SettingField::UserAgent => SettingValue::Text(settings.network.user_agent.clone()),
+            SettingField::MetaUrl => SettingValue::Text(settings.network.meta_url.clone()),
+            SettingField::ResourcesUrl => SettingValue::Text(settings.network.resources_url.clone()),
+            SettingField::LibrariesUrl => SettingValue::Text(settings.network.libraries_url.clone()),
+            SettingField::FallbackResourcesUrls => SettingValue::Text(settings.network.fallback_resources_urls.clone()),
+            SettingField::FallbackLibrariesUrls => SettingValue::Text(settings.network.fallback_libraries_urls.clone()),
+            SettingField::CurseForgeApiKey => SettingValue::OptionalText(settings.network.curseforge_api_key.clone()),
+            SettingField::EnableLogging => SettingValue::Bool(settings.advanced.enable_logging),
+            SettingField::LogLevel => SettingValue::Text(settings.advanced.log_level.clone()),
+            SettingField::ConsoleMaxLines => SettingValue::Number(settings.advanced.console_max_lines as u64),
+            SettingField::EnableProfiling => SettingValue::Bool(settings.advanced.enable_profiling),
+            SettingField::DeveloperMode => SettingValue::Bool(settings.advanced.developer_mode),
+            SettingField::SaveLogsToFile => SettingValue::Bool(settings.advanced.save_logs_to_file),
+            SettingField::LogsDirectory => SettingValue::Path(settings.advanced.logs_directory.clone()),
+            SettingField::LogRetentionHours => SettingValue::Number(settings.advanced.log_retention_hours as u64),
+            SettingField::LogMaxFileSize => SettingValue::Number(settings.advanced.log_max_file_mb as u64),
+            SettingField::CompressRotatedLogs => SettingValue::Bool(settings.advanced.compress_rotated_logs),
+            SettingField::BackupRetention => SettingValue::Number(settings.advanced.backup_retention as u64),
+            SettingField::LogMemoryLimit => SettingValue::Number(settings.advanced.log_memory_limit_mb as u64),
         }
     }
-} 
\ No newline at end of file
+
+    pub fn set(&self, settings: &mut Settings, value: SettingValue) {
+        match (self, value) {
+            (SettingField::Language, SettingValue::Language(value)) => settings.general.language = value,
+            (SettingField::Theme, SettingValue::Text(value)) => settings.general.theme = value,
+            (SettingField::InstancesDirectory, SettingValue::Path(value)) => settings.general.instances_directory = value,
+            (SettingField::JavaDirectory, SettingValue::Path(value)) => settings.general.java_directory = value,
+            (SettingField::SharedDataDir, SettingValue::OptionalPath(value)) => settings.general.shared_data_dir = value,
+            (SettingField::CheckForUpdates, SettingValue::Bool(value)) => settings.general.check_for_updates = value,
+            (SettingField::InstallUpdates, SettingValue::Bool(value)) => settings.general.install_updates = value,
+            (SettingField::SendAnalytics, SettingValue::Bool(value)) => settings.general.send_analytics = value,
+            (SettingField::MaximizeOnLaunch, SettingValue::Bool(value)) => settings.general.maximize_on_launch = value,
+            (SettingField::CloseLauncherOnGameStart, SettingValue::Bool(value)) => settings.general.close_launcher_on_game_start = value,
+            (SettingField::RememberInstanceAccount, SettingValue::Bool(value)) => settings.general.remember_instance_account = value,
+            (SettingField::AccountPicker, SettingValue::Bool(value)) => settings.general.account_picker = value,
+            (SettingField::DefaultJava, SettingValue::OptionalPath(value)) => settings.java.default_installation = value,
+            (SettingField::MemoryMin, SettingValue::Number(value)) => settings.java.memory_min = value as u32,
+            (SettingField::MemoryMax, SettingValue::Number(value)) => settings.java.memory_max = value as u32,
+            (SettingField::PermgenSize, SettingValue::Number(value)) => settings.java.permgen_size = value as u32,
+            (SettingField::GcArgs, SettingValue::Text(value)) => settings.java.gc_args = value,
+            (SettingField::AdditionalArgs, SettingValue::Text(value)) => settings.java.additional_args = value,
+            (SettingField::AutoDetectJava, SettingValue::Bool(value)) => settings.java.auto_detect_installations = value,
+            (SettingField::DownloadMissingJava, SettingValue::Bool(value)) => settings.java.download_missing_java = value,
+            (SettingField::DefaultWidth, SettingValue::Number(value)) => settings.minecraft.default_width = value as u32,
+            (SettingField::DefaultHeight, SettingValue::Number(value)) => settings.minecraft.default_height = value as u32,
+            (SettingField::Fullscreen, SettingValue::Bool(value)) => settings.minecraft.fullscreen = value,
+            (SettingField::AutoLogin, SettingValue::Bool(value)) => settings.minecraft.auto_login = value,
+            (SettingField::PreLaunchCommand, SettingValue::OptionalText(value)) => settings.minecraft.pre_launch_command = value,
+            (SettingField::PostExitCommand, SettingValue::OptionalText(value)) => settings.minecraft.post_exit_command = value,
+            (SettingField::WrapperCommand, SettingValue::OptionalText(value)) => settings.minecraft.wrapper_command = value,
+            (SettingField::LegacyResourceProxy, SettingValue::OptionalText(value)) => settings.minecraft.legacy_resource_proxy = value,
+            (SettingField::EnableConsole, SettingValue::Bool(value)) => settings.minecraft.enable_console = value,
+            (SettingField::AutoCloseConsole, SettingValue::Bool(value)) => settings.minecraft.auto_close_console = value,
+            (SettingField::WindowWidth, SettingValue::Number(value)) => settings.ui.window_width = value as u32,
+            (SettingField::WindowHeight, SettingValue::Number(value)) => settings.ui.window_height = value as u32,
+            (SettingField::WindowMaximized, SettingValue::Bool(value)) => settings.ui.window_maximized = value,
+            (SettingField::InstanceViewType, SettingValue::Text(value)) => settings.ui.instance_view_type = value,
+            (SettingField::SortMode, SettingValue::Text(value)) => settings.ui.sort_mode = value,
+            (SettingField::ShowConsole, SettingValue::Bool(value)) => settings.ui.show_console = value,
+            (SettingField::IconSize, SettingValue::Text(value)) => settings.ui.icon_size = value,
+            (SettingField::GroupView, SettingValue::Bool(value)) => settings.ui.group_view = value,
+            (SettingField::TickRate, SettingValue::Number(value)) => settings.ui.tick_rate_ms = value,
+            (SettingField::RotateArt, SettingValue::Bool(value)) => settings.ui.rotate_art = value,
+            (SettingField::MotdUrl, SettingValue::OptionalText(value)) => settings.ui.motd_url = value,
+            (SettingField::ReducedMotion, SettingValue::Bool(value)) => settings.ui.reduced_motion = value,
+            (SettingField::ShowSnapshots, SettingValue::Bool(value)) => settings.ui.show_snapshots = value,
+            (SettingField::ShowLegacyVersions, SettingValue::Bool(value)) => settings.ui.show_legacy_versions = value,
+            (SettingField::ShowAprilFools, SettingValue::Bool(value)) => settings.ui.show_april_fools = value,
+            (SettingField::UseProxy, SettingValue::Bool(value)) => settings.network.use_proxy = value,
+            (SettingField::ProxyType, SettingValue::Text(value)) => settings.network.proxy_type = value,
+            (SettingField::ProxyHost, SettingValue::Text(value)) => settings.network.proxy_host = value,
+            (SettingField::ProxyPort, SettingValue::Number(value)) => settings.network.proxy_port = value as u16,
+            (SettingField::ProxyUsername, SettingValue::OptionalText(value)) => settings.network.proxy_username = value,
+            (SettingField::ProxyPassword, SettingValue::OptionalText(value)) => settings.network.proxy_password = value,
+            (SettingField::Timeout, SettingValue::Number(value)) => settings.network.timeout = value,
+            (SettingField::LibraryDownloads, SettingValue::Number(value)) => settings.network.max_concurrent_downloads = value as u32,
+            (SettingField::AssetDownloads, SettingValue::Number(value)) => settings.network.max_concurrent_asset_downloads = value as u32,
+            (SettingField::Verifications, SettingValue::Number(value)) => settings.network.max_concurrent_verifications = value as u32,
+            (SettingField::UserAgent, SettingValue::Text(value)) => settings.network.user_agent = value,
+            (SettingField::MetaUrl, SettingValue::Text(value)) => settings.network.meta_url = value,
+            (SettingField::ResourcesUrl, SettingValue::Text(value)) => settings.network.resources_url = value,
+            (SettingField::LibrariesUrl, SettingValue::Text(value)) => settings.network.libraries_url = value,
+            (SettingField::FallbackResourcesUrls, SettingValue::Text(value)) => settings.network.fallback_resources_urls = value,
+            (SettingField::FallbackLibrariesUrls, SettingValue::Text(value)) => settings.network.fallback_libraries_urls = value,
+            (SettingField::CurseForgeApiKey, SettingValue::OptionalText(value)) => settings.network.curseforge_api_key = value,
+            (SettingField::EnableLogging, SettingValue::Bool(value)) => settings.advanced.enable_logging = value,
+            (SettingField::LogLevel, SettingValue::Text(value)) => settings.advanced.log_level = value,
+            (SettingField::ConsoleMaxLines, SettingValue::Number(value)) => settings.advanced.console_max_lines = value as u32,
+            (SettingField::EnableProfiling, SettingValue::Bool(value)) => settings.advanced.enable_profiling = value,
+            (SettingField::DeveloperMode, SettingValue::Bool(value)) => settings.advanced.developer_mode = value,
+            (SettingField::SaveLogsToFile, SettingValue::Bool(value)) => settings.advanced.save_logs_to_file = value,
+            (SettingField::LogsDirectory, SettingValue::Path(value)) => settings.advanced.logs_directory = value,
+            (SettingField::LogRetentionHours, SettingValue::Number(value)) => settings.advanced.log_retention_hours = value as u32,
+            (SettingField::LogMaxFileSize, SettingValue::Number(value)) => settings.advanced.log_max_file_mb = value as u32,
+            (SettingField::CompressRotatedLogs, SettingValue::Bool(value)) => settings.advanced.compress_rotated_logs = value,
+            (SettingField::BackupRetention, SettingValue::Number(value)) => settings.advanced.backup_retention = value as u32,
+            (SettingField::LogMemoryLimit, SettingValue::Number(value)) => settings.advanced.log_memory_limit_mb = value as u32,
+            (field, value) => tracing::warn!("Ignoring {:?} for setting {}", value, field.key()),
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<SettingValue> {
+        let input = input.trim();
+        let optional = |input: &str| (!input.is_empty()).then(|| input.to_string());
+        match self.kind() {
+            SettingKind::Toggle => match input.to_lowercase().as_str() {
+                "true" | "on" | "yes" | "1" => Ok(SettingValue::Bool(true)),
+                "false" | "off" | "no" | "0" => Ok(SettingValue::Bool(false)),
+                _ => Err(Error::Settings(format!("{}: expected on or off", self.key()))),
+            },
+            SettingKind::Number { min, max } => {
+                let value: u64 = input.parse()
+                    .map_err(|_| Error::Settings(format!("{}: '{}' is not a number", self.key(), input)))?;
+                if !(min..=max).contains(&value) {
+                    return Err(Error::Settings(format!("{}: must be between {} and {}", self.key(), min, max)));
+                }
+                Ok(SettingValue::Number(value))
+            }
+            SettingKind::Text => Ok(SettingValue::Text(input.to_string())),
+            SettingKind::OptionalText | SettingKind::Secret => Ok(SettingValue::OptionalText(optional(input))),
+            SettingKind::Path if input.is_empty() => Err(Error::Settings(format!("{}: path cannot be empty", self.key()))),
+            SettingKind::Path => Ok(SettingValue::Path(PathBuf::from(input))),
+            SettingKind::OptionalPath => Ok(SettingValue::OptionalPath(optional(input).map(PathBuf::from))),
+            SettingKind::Choice(options) => options.iter()
+                .find(|option| option.eq_ignore_ascii_case(input))
+                .map(|option| SettingValue::Text(option.to_string()))
+                .ok_or_else(|| Error::Settings(format!("{}: expected one of {}", self.key(), options.join(", ")))),
+            SettingKind::Language => match input.to_lowercase().as_str() {
+                "ru" | "russian" | "русский" => Ok(SettingValue::Language(Language::Russian)),
+                "en" | "english" => Ok(SettingValue::Language(Language::English)),
+                _ => Err(Error::Settings(format!("{}: expected ru or en", self.key()))),
+            },
+        }
+    }
+
+    pub fn cycle(&self, settings: &Settings) -> Option<SettingValue> {
+        match (self.kind(), self.get(settings)) {
+            (SettingKind::Toggle, SettingValue::Bool(value)) => Some(SettingValue::Bool(!value)),
+            (SettingKind::Choice(options), SettingValue::Text(current)) => {
+                let next = options.iter()
+                    .position(|option| *option == current)
+                    .map(|index| (index + 1) % options.len())
+                    .unwrap_or(0);
+                Some(SettingValue::Text(options[next].to_string()))
+            }
+            (SettingKind::Language, SettingValue::Language(Language::Russian)) => Some(SettingValue::Language(Language::English)),
+            (SettingKind::Language, SettingValue::Language(Language::English)) => Some(SettingValue::Language(Language::Russian)),
+            _ => None,
+        }
+    }
+
+    pub fn edit_text(&self, settings: &Settings) -> String {
+        match self.get(settings) {
+            SettingValue::Bool(value) => value.to_string(),
+            SettingValue::Number(value) => value.to_string(),
+            SettingValue::Text(value) => value,
+            SettingValue::OptionalText(value) => value.unwrap_or_default(),
+            SettingValue::Path(path) => path.display().to_string(),
+            SettingValue::OptionalPath(path) => path.map(|p| p.display().to_string()).unwrap_or_default(),
+            SettingValue::Language(Language::Russian) => "ru".to_string(),
+            SettingValue::Language(Language::English) => "en".to_string(),
+        }
+    }
+
+    pub fn display(&self, settings: &Settings) -> String {
+        match (self.kind(), self.get(settings)) {
+            (SettingKind::Secret, SettingValue::OptionalText(Some(_))) => "••••••".to_string(),
+            (_, SettingValue::Bool(value)) => tr(if value { "common.on" } else { "common.off" }).to_string(),
+            (_, SettingValue::OptionalText(None)) | (_, SettingValue::OptionalPath(None)) => tr("common.none").to_string(),
+            (_, SettingValue::Text(value)) if value.is_empty() => tr("common.none").to_string(),
+            (_, SettingValue::Language(Language::Russian)) => "Русский".to_string(),
+            (_, SettingValue::Language(Language::English)) => "English".to_string(),
+            _ => self.edit_text(settings),
+        }
+    }
+}
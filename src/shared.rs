@@ -0,0 +1,134 @@
+use std::path::{Path, PathBuf};
+
+use crate::i18n::trf;
+use crate::utils::file_sha1;
+
+/// Data folders of other launchers (PrismLauncher, MultiMC) that use the
+/// same `libraries/` and `assets/objects/` layout as ours. Files found there
+/// are linked in rather than downloaded again, but only once their hash
+/// matches what the version asks for.
+#[derive(Debug, Clone, Default)]
+pub struct SharedSources {
+    roots: Vec<PathBuf>,
+}
+
+impl SharedSources {
+    pub fn new(roots: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self { roots: roots.into_iter().filter(|root| root.is_dir()).collect() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    pub async fn library(&self, path: &str, sha1: &str) -> Option<PathBuf> {
+        self.find(|root| root.join("libraries").join(path), sha1).await
+    }
+
+    pub async fn asset(&self, hash: &str) -> Option<PathBuf> {
+        let prefix = hash.get(..2)?;
+        self.find(|root| root.join("assets").join("objects").join(prefix).join(hash), hash).await
+    }
+
+    async fn find(&self, candidate: impl Fn(&Path) -> PathBuf, sha1: &str) -> Option<PathBuf> {
+        for root in &self.roots {
+            let path = candidate(root);
+            if !path.is_file() {
+                continue;
+            }
+            match file_sha1(&path).await {
+                Ok(actual) if actual.eq_ignore_ascii_case(sha1) => return Some(path),
+                Ok(_) => tracing::warn!("{}", trf("log.hash_mismatch", &[("path", &path.display())])),
+                Err(_) => {}
+            }
+        }
+        None
+    }
+}
+
+/// How `link_shared` put a file in place.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Link {
+    Hard,
+    Symbolic,
+    Copy,
+}
+
+/// Puts `source` at `target` without duplicating it: a hard link where both
+/// are on one filesystem, a symlink otherwise, and a copy as a last resort.
+pub fn link_shared(source: &Path, target: &Path) -> std::io::Result<Link> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if target.exists() || target.is_symlink() {
+        std::fs::remove_file(target)?;
+    }
+    std::fs::hard_link(source, target)
+        .map(|_| Link::Hard)
+        .or_else(|_| symlink(source, target).map(|_| Link::Symbolic))
+        .or_else(|_| std::fs::copy(source, target).map(|_| Link::Copy))
+}
+
+/// Whether `target` already is `source`, through either kind of link.
+pub fn is_linked(source: &Path, target: &Path) -> bool {
+    std::fs::read_link(target).is_ok_and(|link| link == source) || same_file(source, target)
+}
+
+#[cfg(unix)]
+fn same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (std::fs::metadata(a), std::fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+// std has no stable file ids elsewhere, so hard links go unrecognised and
+// are hashed and linked again, which is only slower.
+#[cfg(not(unix))]
+fn same_file(_a: &Path, _b: &Path) -> bool {
+    false
+}
+
+#[cfg(unix)]
+fn symlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(source, target)
+}
+
+#[cfg(windows)]
+fn symlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(source, target)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn symlink(_source: &Path, _target: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+}
+
+/// PrismLauncher's data folder at its standard location, if it exists.
+/// MultiMC is portable and has none.
+pub fn default_prism_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("PrismLauncher")).filter(|dir| dir.is_dir())
+}
+
+/// Instance folders in a PrismLauncher/MultiMC data folder. The instances
+/// folder can be moved in the launcher's settings, so its config is read
+/// for `InstanceDir` first.
+pub fn instance_dirs(data_dir: &Path) -> Vec<PathBuf> {
+    let configured = ["prismlauncher.cfg", "multimc.cfg"]
+        .iter()
+        .filter_map(|name| std::fs::read_to_string(data_dir.join(name)).ok())
+        .flat_map(|content| content.lines().map(str::to_string).collect::<Vec<_>>())
+        .find_map(|line| line.strip_prefix("InstanceDir=").map(|dir| dir.trim().to_string()));
+    let instances = data_dir.join(configured.as_deref().unwrap_or("instances"));
+
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(instances)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join("instance.cfg").is_file())
+        .collect();
+    dirs.sort();
+    dirs
+}
@@ -0,0 +1,145 @@
+use std::path::{Path, PathBuf};
+
+use crate::instance::Instance;
+use crate::utils::{data_dir, data_dir_source, sanitize_file_name, DataDirSource};
+use crate::{Error, Result};
+
+/// What "Create shortcut" writes: a script that can be double-clicked or
+/// called from other tools, or a freedesktop menu entry (Linux only).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShortcutKind {
+    Script,
+    Desktop,
+}
+
+impl ShortcutKind {
+    pub fn platform_default() -> Self {
+        if cfg!(target_os = "linux") {
+            ShortcutKind::Desktop
+        } else {
+            ShortcutKind::Script
+        }
+    }
+}
+
+// Both kinds call `mango launch <id>`, so renaming the instance does not
+// break an existing shortcut. A data directory picked by flag or environment
+// is passed along, since neither is around when the shortcut is opened.
+pub fn create_shortcut(instance: &Instance, kind: ShortcutKind, data_dir: &Path) -> Result<PathBuf> {
+    let executable = std::env::current_exe()
+        .map_err(|e| Error::Platform(format!("Cannot locate the launcher executable: {}", e)))?;
+    match kind {
+        ShortcutKind::Script => write_script(instance, &executable, &script_dir(data_dir)),
+        ShortcutKind::Desktop => write_desktop_entry(instance, &executable),
+    }
+}
+
+// Scripts go on the desktop when there is one, otherwise next to the
+// launcher's data so they can still be found.
+fn script_dir(data_dir: &Path) -> PathBuf {
+    dirs::desktop_dir()
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(|| data_dir.join("shortcuts"))
+}
+
+fn write_script(instance: &Instance, executable: &Path, dir: &Path) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let name = sanitize_file_name(&instance.name);
+    let (path, content) = if cfg!(windows) {
+        (
+            dir.join(format!("{}.bat", name)),
+            format!(
+                "@echo off\r\n\"{}\"{} launch {} %*\r\n",
+                executable.display(),
+                data_dir_arg(|dir| format!("\"{}\"", dir)),
+                instance.id
+            ),
+        )
+    } else {
+        (
+            dir.join(format!("{}.sh", name)),
+            format!(
+                "#!/bin/sh\n# {}\nexec {}{} launch {} \"$@\"\n",
+                instance.name.replace('\n', " "),
+                shell_quote(&executable.display().to_string()),
+                data_dir_arg(shell_quote),
+                instance.id
+            ),
+        )
+    };
+    std::fs::write(&path, content)?;
+    make_executable(&path)?;
+    Ok(path)
+}
+
+fn write_desktop_entry(instance: &Instance, executable: &Path) -> Result<PathBuf> {
+    if !cfg!(target_os = "linux") {
+        return Err(Error::Platform("Desktop entries are only supported on Linux".to_string()));
+    }
+    let dir = dirs::data_dir()
+        .ok_or_else(|| Error::Platform("Cannot locate the applications directory".to_string()))?
+        .join("applications");
+    std::fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!("mango-launcher-{}.desktop", instance.id));
+    let mut content = format!(
+        "[Desktop Entry]\nType=Application\nName={}\nComment=Minecraft {}\nExec={}{} launch {}\nTerminal=false\nCategories=Game;\n",
+        desktop_escape(&instance.name),
+        instance.minecraft_version,
+        desktop_exec_quote(&executable.to_string_lossy()),
+        data_dir_arg(desktop_exec_quote),
+        instance.id
+    );
+    if let Some(icon) = instance.icon.as_deref().filter(|icon| Path::new(icon).is_file()) {
+        content.push_str(&format!("Icon={}\n", icon));
+    }
+    std::fs::write(&path, content)?;
+    make_executable(&path)?;
+    Ok(path)
+}
+
+fn data_dir_arg(quote: impl Fn(&str) -> String) -> String {
+    match data_dir_source() {
+        DataDirSource::Flag | DataDirSource::Environment => {
+            format!(" --data-dir {}", quote(&data_dir().to_string_lossy()))
+        }
+        DataDirSource::Portable | DataDirSource::Chosen | DataDirSource::Platform => String::new(),
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn desktop_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', " ")
+}
+
+// Exec= arguments containing reserved characters must be double-quoted,
+// with `"`, `` ` ``, `$` and `\` escaped inside the quotes.
+fn desktop_exec_quote(value: &str) -> String {
+    if !value.contains(|c: char| c.is_whitespace() || "\"'\\><~|&;$*?#()`".contains(c)) {
+        return value.to_string();
+    }
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
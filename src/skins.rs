@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::auth::Account;
+use crate::network::NetworkManager;
+use crate::{Error, Result};
+
+const HEAD_URL: &str = "https://mc-heads.net/avatar";
+const HEAD_SIZE: u32 = 8;
+const HEAD_TTL_HOURS: i64 = 24;
+
+#[derive(Debug, Clone)]
+pub struct HeadImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+impl HeadImage {
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        self.pixels
+            .get((y * self.width + x) as usize)
+            .copied()
+            .unwrap_or([0, 0, 0, 0])
+    }
+
+    fn decode(data: &[u8]) -> Result<Self> {
+        let mut decoder = png::Decoder::new(data);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()
+            .map_err(|e| Error::Other(format!("Invalid skin head image: {}", e)))?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer)
+            .map_err(|e| Error::Other(format!("Invalid skin head image: {}", e)))?;
+
+        let channels = info.color_type.samples();
+        let pixels = buffer[..info.buffer_size()]
+            .chunks(channels)
+            .map(|px| match px {
+                [r, g, b, a] => [*r, *g, *b, *a],
+                [r, g, b] => [*r, *g, *b, 255],
+                [l, a] => [*l, *l, *l, *a],
+                [l] => [*l, *l, *l, 255],
+                _ => [0, 0, 0, 0],
+            })
+            .collect();
+
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HeadMeta {
+    key: String,
+    display_name: String,
+    fetched_at: DateTime<Utc>,
+}
+
+pub struct SkinCache {
+    cache_dir: PathBuf,
+    heads: HashMap<String, HeadImage>,
+}
+
+impl SkinCache {
+    pub fn new(cache_dir: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&cache_dir)?;
+        Ok(Self {
+            cache_dir,
+            heads: HashMap::new(),
+        })
+    }
+
+    fn cache_key(account: &Account) -> String {
+        account.uuid.clone().unwrap_or_else(|| account.username.clone())
+    }
+
+    pub fn get(&self, account: &Account) -> Option<&HeadImage> {
+        self.heads.get(&Self::cache_key(account))
+    }
+
+    pub fn is_fresh(&self, account: &Account) -> bool {
+        let key = Self::cache_key(account);
+        self.read_meta(&key).is_some_and(|meta| {
+            meta.display_name == account.display_name
+                && Utc::now() - meta.fetched_at < Duration::hours(HEAD_TTL_HOURS)
+        })
+    }
+
+    pub fn load_cached(&mut self, account: &Account) -> bool {
+        let key = Self::cache_key(account);
+        if self.heads.contains_key(&key) {
+            return true;
+        }
+        if self.read_meta(&key).is_none_or(|meta| meta.display_name != account.display_name) {
+            return false;
+        }
+
+        match std::fs::read(Self::image_path(&self.cache_dir, &key)).map_err(Error::from).and_then(|data| HeadImage::decode(&data)) {
+            Ok(head) => {
+                self.heads.insert(key, head);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    pub async fn fetch(&mut self, network: &NetworkManager, account: &Account) -> Result<()> {
+        let head = Self::download(network, &self.cache_dir, account).await?;
+        self.store(account, head);
+        Ok(())
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    pub async fn download(network: &NetworkManager, cache_dir: &Path, account: &Account) -> Result<HeadImage> {
+        let key = Self::cache_key(account);
+        let url = format!("{}/{}/{}", HEAD_URL, key, HEAD_SIZE);
+        let path = Self::image_path(cache_dir, &key);
+
+        network.download_file(&url, &path, None, None).await?;
+        let head = HeadImage::decode(&std::fs::read(&path)?)?;
+
+        let meta = HeadMeta {
+            key: key.clone(),
+            display_name: account.display_name.clone(),
+            fetched_at: Utc::now(),
+        };
+        std::fs::write(Self::meta_path(cache_dir, &key), serde_json::to_string_pretty(&meta)?)?;
+        Ok(head)
+    }
+
+    pub fn store(&mut self, account: &Account, head: HeadImage) {
+        self.heads.insert(Self::cache_key(account), head);
+    }
+
+    pub fn invalidate(&mut self, account: &Account) {
+        let key = Self::cache_key(account);
+        self.heads.remove(&key);
+        let _ = std::fs::remove_file(Self::meta_path(&self.cache_dir, &key));
+        let _ = std::fs::remove_file(Self::image_path(&self.cache_dir, &key));
+    }
+
+    fn read_meta(&self, key: &str) -> Option<HeadMeta> {
+        let content = std::fs::read_to_string(Self::meta_path(&self.cache_dir, key)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    fn image_path(cache_dir: &Path, key: &str) -> PathBuf {
+        cache_dir.join(format!("{}.png", crate::utils::sanitize_file_name(key)))
+    }
+
+    fn meta_path(cache_dir: &Path, key: &str) -> PathBuf {
+        cache_dir.join(format!("{}.json", crate::utils::sanitize_file_name(key)))
+    }
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures_util::future::join_all;
+
+use crate::network::NetworkManager;
+
+const MOJANG_ENDPOINTS: &[(&str, &str)] = &[
+    ("Launcher meta", "https://launchermeta.mojang.com/mc/game/version_manifest.json"),
+    ("Piston meta", "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json"),
+    ("Libraries", "https://libraries.minecraft.net/"),
+    ("Resources", "https://resources.download.minecraft.net/"),
+    ("Session server", "https://sessionserver.mojang.com/"),
+    ("Minecraft services", "https://api.minecraftservices.com/"),
+];
+
+// Everything the launcher downloads starts from these, so either answering is
+// enough to count as online.
+const META_ENDPOINTS: &[&str] = &[
+    "https://launchermeta.mojang.com/mc/game/version_manifest.json",
+    "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json",
+];
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+const CONNECTIVITY_ENDPOINTS: &[&str] = &[
+    "https://www.cloudflare.com/cdn-cgi/trace",
+    "https://www.google.com/generate_204",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Connectivity {
+    Unknown,
+    Online,
+    Offline,
+}
+
+impl Connectivity {
+    /// A quick look at whether Mojang's metadata servers answer at all. Any
+    /// HTTP response counts; only a failed connection or a timeout does not.
+    pub async fn probe(network: &NetworkManager) -> Self {
+        let probes = META_ENDPOINTS.iter().map(|url| network.probe_within(url, PROBE_TIMEOUT));
+        if join_all(probes).await.iter().any(|result| result.is_ok()) {
+            Connectivity::Online
+        } else {
+            Connectivity::Offline
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ServiceHealth {
+    Operational,
+    Degraded,
+    Down,
+    Offline,
+}
+
+#[derive(Debug, Clone)]
+pub struct EndpointStatus {
+    pub name: &'static str,
+    pub url: &'static str,
+    pub reachable: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct MojangStatus {
+    pub endpoints: Vec<EndpointStatus>,
+    pub internet_reachable: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl MojangStatus {
+    pub async fn check(network: &NetworkManager) -> Self {
+        let probes = MOJANG_ENDPOINTS.iter().map(|(name, url)| async move {
+            let (reachable, detail) = match network.probe(url).await {
+                Ok(status) if status.is_server_error() => (false, format!("HTTP {}", status.as_u16())),
+                Ok(status) => (true, format!("HTTP {}", status.as_u16())),
+                Err(e) => (false, e.to_string()),
+            };
+            EndpointStatus { name, url, reachable, detail }
+        });
+        let endpoints = join_all(probes).await;
+
+        let internet_reachable = if endpoints.iter().any(|e| e.reachable) {
+            true
+        } else {
+            join_all(CONNECTIVITY_ENDPOINTS.iter().map(|url| network.probe(url)))
+                .await
+                .iter()
+                .any(|result| result.is_ok())
+        };
+
+        Self {
+            endpoints,
+            internet_reachable,
+            checked_at: Utc::now(),
+        }
+    }
+
+    pub fn health(&self) -> ServiceHealth {
+        let failing = self.failing().len();
+        if failing == 0 {
+            ServiceHealth::Operational
+        } else if failing < self.endpoints.len() {
+            ServiceHealth::Degraded
+        } else if self.internet_reachable {
+            ServiceHealth::Down
+        } else {
+            ServiceHealth::Offline
+        }
+    }
+
+    pub fn failing(&self) -> Vec<&EndpointStatus> {
+        self.endpoints.iter().filter(|e| !e.reachable).collect()
+    }
+
+    pub fn summary(&self) -> String {
+        let failing = self.failing()
+            .iter()
+            .map(|e| e.name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        match self.health() {
+            ServiceHealth::Operational => "All Mojang services are reachable".to_string(),
+            ServiceHealth::Degraded => format!("Some Mojang services appear down: {}", failing),
+            ServiceHealth::Down => "Mojang services appear down".to_string(),
+            ServiceHealth::Offline => "No internet connection".to_string(),
+        }
+    }
+}
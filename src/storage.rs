@@ -0,0 +1,293 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::i18n::tr;
+
+const USAGE_TTL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StorageCategory {
+    Assets,
+    Versions,
+    Libraries,
+    Instances,
+    Cache,
+}
+
+impl StorageCategory {
+    pub const ALL: [StorageCategory; 5] = [
+        StorageCategory::Instances,
+        StorageCategory::Versions,
+        StorageCategory::Libraries,
+        StorageCategory::Assets,
+        StorageCategory::Cache,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        tr(match self {
+            StorageCategory::Assets => "storage.assets",
+            StorageCategory::Versions => "storage.versions",
+            StorageCategory::Libraries => "storage.libraries",
+            StorageCategory::Instances => "storage.instances",
+            StorageCategory::Cache => "storage.cache",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cleanup {
+    UnusedVersions,
+    OrphanedLibraries,
+    ExpiredCache,
+    StaleVirtualAssets,
+    DuplicateAssets,
+}
+
+impl Cleanup {
+    pub const ALL: [Cleanup; 5] = [
+        Cleanup::UnusedVersions,
+        Cleanup::OrphanedLibraries,
+        Cleanup::ExpiredCache,
+        Cleanup::StaleVirtualAssets,
+        Cleanup::DuplicateAssets,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        tr(match self {
+            Cleanup::UnusedVersions => "storage.cleanup_versions",
+            Cleanup::OrphanedLibraries => "storage.cleanup_libraries",
+            Cleanup::ExpiredCache => "storage.cleanup_cache",
+            Cleanup::StaleVirtualAssets => "storage.cleanup_virtual_assets",
+            Cleanup::DuplicateAssets => "storage.cleanup_duplicate_assets",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CleanupResult {
+    pub removed: usize,
+    pub freed: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UsageTarget {
+    Category(StorageCategory),
+    Instance(Uuid),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Measurement {
+    bytes: u64,
+    measured_at: Instant,
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageRoots {
+    pub assets: PathBuf,
+    pub versions: PathBuf,
+    pub libraries: PathBuf,
+    pub instances: PathBuf,
+    pub cache: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiskUsage {
+    roots: StorageRoots,
+    measurements: HashMap<UsageTarget, Measurement>,
+    invalidated: HashSet<UsageTarget>,
+    scanning: HashSet<UsageTarget>,
+}
+
+impl DiskUsage {
+    pub fn new(roots: StorageRoots) -> Self {
+        Self {
+            roots,
+            measurements: HashMap::new(),
+            invalidated: HashSet::new(),
+            scanning: HashSet::new(),
+        }
+    }
+
+    pub fn roots(&self) -> &StorageRoots {
+        &self.roots
+    }
+
+    pub fn set_instances_root(&mut self, instances: PathBuf) {
+        self.roots.instances = instances;
+        self.measurements.clear();
+    }
+
+    pub fn invalidate(&mut self, target: UsageTarget) {
+        self.invalidated.insert(target);
+    }
+
+    pub fn forget_instance(&mut self, id: Uuid) {
+        let target = UsageTarget::Instance(id);
+        self.measurements.remove(&target);
+        self.invalidated.remove(&target);
+    }
+
+    pub fn is_scanning(&self) -> bool {
+        !self.scanning.is_empty()
+    }
+
+    // Only targets that were never measured, were invalidated, or have
+    // expired are rescanned; everything else is served from the cache.
+    pub fn stale_targets(&self, instance_ids: &[Uuid]) -> Vec<UsageTarget> {
+        let categories = StorageCategory::ALL.iter()
+            .filter(|category| **category != StorageCategory::Instances)
+            .map(|category| UsageTarget::Category(*category));
+        let instances = instance_ids.iter().map(|id| UsageTarget::Instance(*id));
+
+        categories.chain(instances)
+            .filter(|target| !self.scanning.contains(target))
+            .filter(|target| {
+                self.invalidated.contains(target)
+                    || self.measurements.get(target).is_none_or(|m| m.measured_at.elapsed() >= USAGE_TTL)
+            })
+            .collect()
+    }
+
+    pub fn begin_scan(&mut self, target: UsageTarget) -> PathBuf {
+        self.scanning.insert(target);
+        self.invalidated.remove(&target);
+        match target {
+            UsageTarget::Category(StorageCategory::Assets) => self.roots.assets.clone(),
+            UsageTarget::Category(StorageCategory::Versions) => self.roots.versions.clone(),
+            UsageTarget::Category(StorageCategory::Libraries) => self.roots.libraries.clone(),
+            UsageTarget::Category(StorageCategory::Instances) => self.roots.instances.clone(),
+            UsageTarget::Category(StorageCategory::Cache) => self.roots.cache.clone(),
+            UsageTarget::Instance(id) => self.roots.instances.join(id.to_string()),
+        }
+    }
+
+    pub fn record(&mut self, target: UsageTarget, bytes: u64) {
+        self.scanning.remove(&target);
+        self.measurements.insert(target, Measurement { bytes, measured_at: Instant::now() });
+    }
+
+    pub fn size(&self, category: StorageCategory) -> Option<u64> {
+        if category == StorageCategory::Instances {
+            let sizes: Vec<u64> = self.measurements.iter()
+                .filter(|(target, _)| matches!(target, UsageTarget::Instance(_)))
+                .map(|(_, m)| m.bytes)
+                .collect();
+            return (!sizes.is_empty()).then(|| sizes.iter().sum());
+        }
+        self.measurements.get(&UsageTarget::Category(category)).map(|m| m.bytes)
+    }
+
+    pub fn instance_size(&self, id: Uuid) -> Option<u64> {
+        self.measurements.get(&UsageTarget::Instance(id)).map(|m| m.bytes)
+    }
+
+    pub fn is_pending(&self, category: StorageCategory) -> bool {
+        match category {
+            StorageCategory::Instances => self.scanning.iter().any(|t| matches!(t, UsageTarget::Instance(_))),
+            category => self.scanning.contains(&UsageTarget::Category(category)),
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        StorageCategory::ALL.iter().filter_map(|category| self.size(*category)).sum()
+    }
+}
+
+pub fn measure(target: UsageTarget, path: &Path, roots: &StorageRoots) -> u64 {
+    match target {
+        // Libraries live inside the versions directory; count them once.
+        UsageTarget::Category(StorageCategory::Versions) => directory_size_excluding(path, &[roots.libraries.as_path()]),
+        _ => directory_size(path),
+    }
+}
+
+pub fn directory_size(path: &Path) -> u64 {
+    directory_size_excluding(path, &[])
+}
+
+// Hard-linked files, like linked virtual assets, take their space once.
+fn directory_size_excluding(path: &Path, excluded: &[&Path]) -> u64 {
+    if !path.exists() {
+        return 0;
+    }
+    let mut seen = HashSet::new();
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_entry(|entry| !excluded.contains(&entry.path()))
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file() && first_link(metadata, &mut seen))
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+#[cfg(unix)]
+fn first_link(metadata: &std::fs::Metadata, seen: &mut HashSet<(u64, u64)>) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink() < 2 || seen.insert((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn first_link(_metadata: &std::fs::Metadata, _seen: &mut HashSet<(u64, u64)>) -> bool {
+    true
+}
+
+// Version directories are recognised by their `<id>/<id>.json` file, which
+// keeps the shared libraries directory out of the candidates.
+pub fn unused_versions(versions_dir: &Path, used: &HashSet<String>) -> Vec<PathBuf> {
+    subdirectories(versions_dir)
+        .filter(|(name, path)| !used.contains(name) && path.join(format!("{}.json", name)).exists())
+        .map(|(_, path)| path)
+        .collect()
+}
+
+pub fn orphaned_libraries(libraries_dir: &Path, referenced: &HashSet<PathBuf>) -> Vec<PathBuf> {
+    if !libraries_dir.exists() {
+        return Vec::new();
+    }
+    walkdir::WalkDir::new(libraries_dir)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| !referenced.contains(path))
+        .collect()
+}
+
+pub fn stale_virtual_assets(assets_dir: &Path, used: &HashSet<String>) -> Vec<PathBuf> {
+    subdirectories(&assets_dir.join("virtual"))
+        .filter(|(name, _)| !used.contains(name))
+        .map(|(_, path)| path)
+        .collect()
+}
+
+pub fn remove_paths(paths: &[PathBuf]) -> CleanupResult {
+    let mut result = CleanupResult::default();
+    for path in paths {
+        let size = directory_size(path);
+        let removed = if path.is_dir() {
+            std::fs::remove_dir_all(path)
+        } else {
+            std::fs::remove_file(path)
+        };
+        match removed {
+            Ok(()) => {
+                result.removed += 1;
+                result.freed += size;
+            }
+            Err(e) => tracing::warn!("Failed to remove {}: {}", path.display(), e),
+        }
+    }
+    result
+}
+
+fn subdirectories(path: &Path) -> impl Iterator<Item = (String, PathBuf)> {
+    std::fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| (entry.file_name().to_string_lossy().into_owned(), entry.path()))
+}
@@ -0,0 +1,191 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc::UnboundedSender;
+use uuid::Uuid;
+
+use crate::events::AppEvent;
+use crate::i18n::{tr, trf};
+use crate::progress::{ProgressTracker, ProgressUpdate};
+use crate::storage::Cleanup;
+
+pub type TaskId = u64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskKind {
+    ScanJava,
+    LoadVersions,
+    DownloadVersion(String),
+    VerifyVersion(String),
+    LoadNews,
+    LoadMotd,
+    LoadLoaderVersions,
+    DetectSystem,
+    CheckUpdate,
+    InstallUpdate,
+    Launch(Uuid),
+    ImportPack(String),
+    CheckConnectivity,
+    DownloadJava(u8),
+    CheckDependencies(Uuid),
+    InstallDependencies(Uuid),
+    Cleanup(Cleanup),
+}
+
+impl TaskKind {
+    pub fn label(&self) -> String {
+        match self {
+            TaskKind::ScanJava => tr("task.scan_java").to_string(),
+            TaskKind::LoadVersions => tr("task.load_versions").to_string(),
+            TaskKind::DownloadVersion(version_id) => trf("task.download_version", &[("version", version_id)]),
+            TaskKind::VerifyVersion(version_id) => trf("task.verify_version", &[("version", version_id)]),
+            TaskKind::LoadNews => tr("task.load_news").to_string(),
+            TaskKind::LoadMotd => tr("task.load_motd").to_string(),
+            TaskKind::LoadLoaderVersions => tr("task.load_loader_versions").to_string(),
+            TaskKind::DetectSystem => tr("task.detect_system").to_string(),
+            TaskKind::CheckUpdate => tr("task.check_update").to_string(),
+            TaskKind::InstallUpdate => tr("task.install_update").to_string(),
+            TaskKind::Launch(_) => tr("task.launch").to_string(),
+            TaskKind::ImportPack(name) => trf("task.import_pack", &[("name", name)]),
+            TaskKind::CheckConnectivity => tr("task.check_connectivity").to_string(),
+            TaskKind::DownloadJava(version) => trf("task.download_java", &[("version", version)]),
+            TaskKind::CheckDependencies(_) => tr("task.check_dependencies").to_string(),
+            TaskKind::InstallDependencies(_) => tr("task.install_dependencies").to_string(),
+            TaskKind::Cleanup(_) => tr("task.cleanup").to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskStage {
+    Metadata,
+    Client,
+    Libraries,
+    Assets,
+    Verify,
+    Repair,
+    AssetIndex,
+    AssetObjects,
+    Unpack,
+    PackFiles,
+    Runtime,
+}
+
+impl TaskStage {
+    pub fn label(&self) -> &'static str {
+        tr(match self {
+            TaskStage::Metadata => "task.stage_metadata",
+            TaskStage::Client => "task.stage_client",
+            TaskStage::Libraries => "task.stage_libraries",
+            TaskStage::Assets => "task.stage_assets",
+            TaskStage::Verify => "task.stage_verify",
+            TaskStage::Repair => "task.stage_repair",
+            TaskStage::AssetIndex => "task.stage_asset_index",
+            TaskStage::AssetObjects => "task.stage_asset_objects",
+            TaskStage::Unpack => "task.stage_unpack",
+            TaskStage::PackFiles => "task.stage_pack_files",
+            TaskStage::Runtime => "task.stage_runtime",
+        })
+    }
+
+    pub fn counts_bytes(&self) -> bool {
+        matches!(self, TaskStage::Client | TaskStage::Runtime)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BackgroundTask {
+    pub id: TaskId,
+    pub kind: TaskKind,
+    pub started_at: Instant,
+}
+
+#[derive(Debug, Default)]
+pub struct TaskRegistry {
+    next_id: TaskId,
+    tasks: Vec<BackgroundTask>,
+    progress: ProgressTracker,
+}
+
+impl TaskRegistry {
+    pub fn begin(&mut self, kind: TaskKind) -> TaskId {
+        self.next_id += 1;
+        self.progress.start(self.next_id, kind.label());
+        self.tasks.push(BackgroundTask {
+            id: self.next_id,
+            kind,
+            started_at: Instant::now(),
+        });
+        self.next_id
+    }
+
+    pub fn update(&mut self, id: TaskId, update: ProgressUpdate) {
+        self.progress.apply(id, update);
+    }
+
+    pub fn finish(&mut self, id: TaskId) -> Option<BackgroundTask> {
+        self.progress.finish(id);
+        let index = self.tasks.iter().position(|task| task.id == id)?;
+        Some(self.tasks.remove(index))
+    }
+
+    pub fn progress(&self) -> &ProgressTracker {
+        &self.progress
+    }
+
+    pub fn is_running(&self, kind: &TaskKind) -> bool {
+        self.tasks.iter().any(|task| task.kind == *kind)
+    }
+
+    pub fn active(&self) -> &[BackgroundTask] {
+        &self.tasks
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskReporter {
+    id: TaskId,
+    sender: UnboundedSender<AppEvent>,
+    scope: Vec<TaskStage>,
+}
+
+impl TaskReporter {
+    pub fn new(id: TaskId, sender: UnboundedSender<AppEvent>) -> Self {
+        Self { id, sender, scope: Vec::new() }
+    }
+
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// A reporter whose stages are subtasks of `stage`, for work that is one
+    /// step of something larger.
+    pub fn scoped(&self, stage: TaskStage) -> Self {
+        let mut scoped = self.clone();
+        scoped.scope.push(stage);
+        scoped
+    }
+
+    /// Lists the stages ahead with their relative weights, so the overall
+    /// progress does not jump when a long stage starts.
+    pub fn plan(&self, stages: &[(TaskStage, u32)]) {
+        self.update(ProgressUpdate::Plan { parent: self.scope.clone(), stages: stages.to_vec() });
+    }
+
+    pub fn report(&self, stage: TaskStage, done: u64, total: u64) {
+        let mut path = self.scope.clone();
+        path.push(stage);
+        self.update(ProgressUpdate::Advance { path, done, total });
+    }
+
+    fn update(&self, update: ProgressUpdate) {
+        let _ = self.sender.send(AppEvent::TaskProgress { task: self.id, update });
+    }
+
+    pub fn send(&self, event: AppEvent) {
+        let _ = self.sender.send(event);
+    }
+}
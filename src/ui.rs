@@ -3,94 +3,854 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     prelude::Alignment,
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, List, ListItem, Paragraph, ListState},
+    widgets::{block::{Position, Title}, Block, Borders, List, ListItem, Paragraph, ListState, Wrap},
     Frame,
 };
 use std::io::stdout;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
 use ratatui::prelude::*;
 use crossterm::{
+    cursor::Show,
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use chrono::Utc;
 
-use crate::app::{App, AppState};
-use crate::settings::Language;
+use crate::app::{App, AppState, LOG_SCROLLBACK};
+use crate::events::Readiness;
+use crate::settings::{Language, SettingField, SettingValue};
+use crate::backup::BackupReason;
+use crate::instance::{is_glyph_icon, Instance, InstanceLink, LaunchHook, ICON_MAX_CHARS, ICON_PRESETS};
+use crate::jvm_lint::GcPreset;
+use crate::logs::{LogLevel, LogManager};
+use crate::shortcut::ShortcutKind;
+use crate::jvm_lint::LintSeverity;
+use crate::packs::PackKind;
+use crate::storage::{Cleanup, StorageCategory};
+use crate::version::{VersionChannels, VersionKind};
+use crate::wizard::{WizardItem, WizardStep};
+use crate::progress::{ProgressTreeView, StatusStrip};
+use crate::status::Connectivity;
+use crate::tasks::TaskKind;
+use crate::i18n::{plural, tr, trf};
+use crate::utils::DataDirSource;
 
 use crate::Result;
 
-const MANGO_ART: [&str; 8] = [
-    "  ░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░",
-    "      ███╗   ███╗ ██████╗ ███╗   ██╗ ██████╗  ██████╗ ",
-    "      ████╗ ████║██╔══██╗████╗  ██║██╔════╝ ██╔═══██╗",
-    "      ██╔████╔██║███████║██╔██╗ ██║██║  ███╗██║   ██║",
-    "      ██║╚██╔╝██║██╔══██║██║╚██╗██║██║   ██║██║   ██║",
-    "      ██║ ╚═╝ ██║██║  ██║██║ ╚████║╚██████╔╝╚██████╔╝",
-    "      ╚═╝     ╚═╝╚═╝  ╚═╝╚═╝  ╚═══╝ ╚═════╝  ╚═════╝ ",
-    "  ░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░░",
-];
+// Offered when downloading Java; what current Minecraft versions need.
+const RECOMMENDED_JAVA: u8 = 21;
+// Compatible installations listed under a Java mismatch in the editor.
+const JAVA_SUGGESTIONS: usize = 3;
+
+pub struct TextInput {
+    pub title: String,
+    pub value: String,
+    cursor: usize,
+}
+
+pub enum TextInputOutcome {
+    Editing,
+    Submitted(String),
+    Cancelled,
+}
+
+impl TextInput {
+    pub fn new(title: impl Into<String>, value: impl Into<String>) -> Self {
+        let value = value.into();
+        let cursor = value.chars().count();
+        Self { title: title.into(), value, cursor }
+    }
+
+    fn byte_index(&self, cursor: usize) -> usize {
+        self.value.char_indices().nth(cursor).map(|(i, _)| i).unwrap_or(self.value.len())
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> TextInputOutcome {
+        match code {
+            KeyCode::Char(c) => {
+                let index = self.byte_index(self.cursor);
+                self.value.insert(index, c);
+                self.cursor += 1;
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                let index = self.byte_index(self.cursor);
+                self.value.remove(index);
+            }
+            KeyCode::Delete if self.cursor < self.value.chars().count() => {
+                let index = self.byte_index(self.cursor);
+                self.value.remove(index);
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.value.chars().count()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.value.chars().count(),
+            KeyCode::Enter => return TextInputOutcome::Submitted(self.value.trim().to_string()),
+            KeyCode::Esc => return TextInputOutcome::Cancelled,
+            _ => {}
+        }
+        TextInputOutcome::Editing
+    }
+
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let width = (area.width / 5 * 3).max(30).min(area.width);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + area.height.saturating_sub(3) / 2,
+            width,
+            height: 3.min(area.height),
+        };
+
+        let (before, after) = self.value.split_at(self.byte_index(self.cursor));
+        let mut after = after.chars();
+        let under_cursor = after.next().map(String::from).unwrap_or_else(|| " ".to_string());
+        let line = Line::from(vec![
+            Span::raw(before),
+            Span::styled(under_cursor, Style::default().add_modifier(Modifier::REVERSED)),
+            Span::raw(after.as_str()),
+        ]);
+
+        f.render_widget(ratatui::widgets::Clear, popup);
+        f.render_widget(
+            Paragraph::new(line).block(Block::default()
+                .title(self.title.as_str())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))),
+            popup,
+        );
+    }
+}
+
+enum TextPrompt {
+    NewInstance,
+    InstanceName,
+    InstanceIcon,
+    InstanceLink(InstanceLink),
+    AccountName(uuid::Uuid),
+    LaunchVariant(uuid::Uuid),
+    Setting(SettingField),
+    CloneName,
+    WizardAccount,
+    WizardInstance,
+    WizardDataDir,
+    ImportPath,
+    JavaPath,
+    JavaDownload,
+}
+
+fn apply_setting(app: &mut App, field: SettingField, value: SettingValue) {
+    match app.update_setting(field, value) {
+        Ok(()) => app.current_state = trf("status.setting_changed", &[
+            ("name", &tr(field.label_key())),
+            ("value", &field.display(app.get_settings())),
+        ]),
+        Err(e) => app.report_error(trf("status.setting_rejected", &[("error", &e)]), &e),
+    }
+}
+
+fn selected_java(app: &App, selected: usize) -> Option<String> {
+    app.java_manager.sorted_installations().get(selected).map(|(key, _)| key.to_string())
+}
+
+fn set_default_java(app: &mut App, selected: usize) {
+    let Some(key) = selected_java(app, selected) else { return };
+    match app.set_default_java(&key) {
+        Ok(()) => app.current_state = trf("status.java_default_set", &[("java", &key)]),
+        Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+    }
+}
+
+fn list_len(app: &App) -> usize {
+    match app.state {
+        AppState::MainMenu => 9,
+        AppState::InstanceList => app.displayed_instances().len(),
+        AppState::EditInstance => 17,
+        AppState::Settings => SettingField::ALL.len(),
+        AppState::Launcher => app.get_displayed_versions().len(),
+        AppState::AccountManager => app.auth_manager.list_accounts().len(),
+        AppState::CrashReport
+        | AppState::ModBisect
+        | AppState::CommandApproval
+        | AppState::LaunchConflict => 0,
+        AppState::Storage => Cleanup::ALL.len(),
+        AppState::LaunchVariants => app.launch_variant_names().len() + 1,
+        AppState::QuickPlay => app.quick_play.as_ref().map(|picker| picker.targets.len()).unwrap_or(0),
+        AppState::CloneInstance => 6,
+        AppState::AccountPicker => app.account_picker.as_ref().map(|picker| picker.accounts.len()).unwrap_or(0),
+        AppState::ModpackUpdate => app.modpack_update.as_ref().map(|u| u.conflicts.len()).unwrap_or(0) + 1,
+        AppState::Logs => app.log_view.entries(&app.log_manager).len(),
+        AppState::Console => app.console.as_ref().map(|(_, console)| console.len()).unwrap_or(0),
+        AppState::PackManager => app.pack_browser.as_ref().map(|b| b.packs.len()).unwrap_or(0),
+        AppState::ModList => app.installed_mod_list().len(),
+        AppState::Backups => app.backups.len(),
+        AppState::Wizard => app.wizard.as_ref().map(|w| w.step.items().len()).unwrap_or(0),
+        AppState::News => app.news.len(),
+        AppState::SystemInfo => app.system_info.as_ref().map(|info| info.rows().len()).unwrap_or(0),
+        AppState::ModBrowser => app.mod_browser.as_ref().map(|b| b.results.len()).unwrap_or(0),
+        AppState::LoaderVersions => app.loader_picker.as_ref().map(|p| p.visible().len()).unwrap_or(0),
+        AppState::Java => app.get_java_installations().len(),
+    }
+}
+
+fn move_selection(list_state: &mut ListState, len: usize, delta: isize) {
+    let selected = list_state.selected().unwrap_or(0);
+    let target = selected.saturating_add_signed(delta).min(len.saturating_sub(1));
+    list_state.select(Some(target));
+}
+
+// Lists shrink under the cursor (deletes, filters, reloads); keep the
+// selection on an existing row so the viewport never scrolls past the end.
+fn clamp_selection(list_state: &mut ListState, len: usize) {
+    let last = len.saturating_sub(1);
+    match list_state.selected() {
+        Some(selected) if selected > last => list_state.select(Some(last)),
+        None => list_state.select(Some(0)),
+        _ => {}
+    }
+    if list_state.offset() > last {
+        *list_state.offset_mut() = last;
+    }
+}
+
+// Set while the terminal is in raw mode on the alternate screen, so it is
+// given back once whichever way the UI ends.
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Keeps the terminal in raw mode on the alternate screen and restores it
+/// when dropped, also when `run_ui` returns an error or unwinds.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> Result<Self> {
+        enable_raw_mode()?;
+        TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+        let guard = Self;
+        execute!(stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+fn restore_terminal() {
+    if TERMINAL_ACTIVE.swap(false, Ordering::SeqCst) {
+        let _ = disable_raw_mode();
+        let _ = execute!(stdout(), LeaveAlternateScreen, DisableMouseCapture, Show);
+    }
+}
+
+// A panic on the UI thread ends the launcher, so the terminal is restored
+// before the message is printed. Panics in background tasks are caught by
+// tokio and only logged, since printing would tear through the UI. The
+// backtrace goes to the launcher log either way.
+fn install_panic_hook(log_manager: LogManager) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let ui_thread = std::thread::current().name() == Some("main");
+        if ui_thread {
+            restore_terminal();
+        }
+        let backtrace = std::backtrace::Backtrace::force_capture();
+        let logged = log_manager.log_panic(format!("{}\n{}", info, backtrace));
+        if ui_thread {
+            default_hook(info);
+            match logged {
+                Some(path) => eprintln!("{}", trf("crash.panic_logged", &[("path", &path.display())])),
+                None => eprintln!("{}", backtrace),
+            }
+        }
+    }));
+}
 
 pub async fn run_ui(mut app: App) -> Result<()> {
-    enable_raw_mode()?;
-    let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    let _guard = TerminalGuard::enter()?;
+    install_panic_hook(app.log_manager.clone());
+    let backend = CrosstermBackend::new(stdout());
     let mut terminal = Terminal::new(backend)?;
 
     let mut list_state = ListState::default();
     list_state.select(Some(0));
+    let mut prompt: Option<(TextPrompt, TextInput)> = None;
+    let mut page_size = 10;
+    let mut show_help = false;
+
+    let mut last_tick = Instant::now();
+    let mut redraw = true;
 
     loop {
-        terminal.draw(|f| draw(f, &mut app, &mut list_state))?;
+        if redraw {
+            app.sync_marks();
+            let len = list_len(&app);
+            clamp_selection(&mut list_state, len);
+            let follow = match app.state {
+                AppState::Logs => app.log_view.follow,
+                AppState::Console => app.console_follow,
+                _ => false,
+            };
+            if follow {
+                list_state.select(Some(len.saturating_sub(1)));
+            }
+            if app.state == AppState::News {
+                if let Some(index) = app.take_news_focus() {
+                    list_state.select(Some(index));
+                }
+            }
+            if app.state == AppState::AccountPicker {
+                if let Some(index) = app.take_account_picker_focus() {
+                    list_state.select(Some(index));
+                }
+            }
+            terminal.draw(|f| {
+                page_size = draw(f, &mut app, &mut list_state);
+                if show_help {
+                    draw_help(f, &app);
+                }
+                if let Some((_, input)) = &prompt {
+                    input.draw(f, f.size());
+                }
+            })?;
+        }
+
+        let timeout = app.tick_rate().saturating_sub(last_tick.elapsed());
+        if !event::poll(timeout)? {
+            last_tick = Instant::now();
+            redraw = app.on_tick();
+            redraw |= app.process_events();
+            redraw |= app.process_pending_relaunch();
+            continue;
+        }
+        redraw = true;
 
         if let Event::Key(key) = event::read()? {
+            app.error_hint = None;
+            if let Some((target, input)) = prompt.as_mut() {
+                match input.handle_key(key.code) {
+                    TextInputOutcome::Editing => {}
+                    TextInputOutcome::Cancelled => prompt = None,
+                    TextInputOutcome::Submitted(value) if value.is_empty() && !matches!(target, TextPrompt::Setting(_) | TextPrompt::InstanceLink(_) | TextPrompt::WizardDataDir | TextPrompt::ImportPath) => {
+                        app.current_state = tr("status.name_empty").to_string();
+                    }
+                    TextInputOutcome::Submitted(value) => {
+                        match target {
+                            TextPrompt::NewInstance => match app.create_instance(value.clone(), "1.21".to_string()) {
+                                Ok(_) => app.current_state = trf("status.instance_created", &[("name", &value)]),
+                                Err(e) => app.report_error(trf("status.create_failed", &[("error", &e)]), &e),
+                            },
+                            TextPrompt::InstanceName => {
+                                if let Some(instance) = app.get_editing_instance_mut() {
+                                    instance.name = value.clone();
+                                    app.current_state = trf("status.name_changed", &[("name", &value)]);
+                                }
+                            }
+                            TextPrompt::InstanceIcon => {
+                                let icon = value.trim().to_string();
+                                if !icon.is_empty() && !is_glyph_icon(&icon) {
+                                    app.current_state = trf("status.icon_too_long", &[("max", &ICON_MAX_CHARS)]);
+                                } else if let Some(instance) = app.get_editing_instance_mut() {
+                                    instance.icon = Some(icon).filter(|icon| !icon.is_empty());
+                                    app.current_state = trf("status.icon_changed", &[("icon", &instance.icon.as_deref().unwrap_or(tr("common.none")))]);
+                                }
+                            }
+                            TextPrompt::InstanceLink(link) => {
+                                let url = value.trim().to_string();
+                                if !url.is_empty() && !url.starts_with("https://") && !url.starts_with("http://") {
+                                    app.current_state = trf("status.link_invalid", &[("url", &url)]);
+                                } else if let Some(instance) = app.get_editing_instance_mut() {
+                                    instance.links.set(*link, Some(url).filter(|url| !url.is_empty()));
+                                    app.current_state = trf("status.link_changed", &[("url", &instance.links.get(*link).unwrap_or(tr("common.none")))]);
+                                }
+                            }
+                            TextPrompt::LaunchVariant(instance_id) => match app.save_launch_variant(*instance_id, value.clone()) {
+                                Ok(()) => app.current_state = trf("status.variant_saved", &[("name", &value)]),
+                                Err(e) => app.report_error(trf("status.save_failed", &[("error", &e)]), &e),
+                            },
+                            TextPrompt::CloneName => {
+                                if let Some(dialog) = app.clone_dialog.as_mut() {
+                                    dialog.name = value.clone();
+                                }
+                            }
+                            TextPrompt::Setting(field) => match field.parse(&value) {
+                                Ok(parsed) => apply_setting(&mut app, *field, parsed),
+                                Err(e) => app.report_error(trf("status.setting_rejected", &[("error", &e)]), &e),
+                            },
+                            TextPrompt::WizardAccount => {
+                                if let Err(e) = app.wizard_add_account(value.clone()) {
+                                    app.report_error(trf("status.add_failed", &[("error", &e)]), &e);
+                                }
+                            }
+                            TextPrompt::WizardInstance => match app.wizard_create_instance(value.clone()) {
+                                Ok(()) => app.current_state = trf("status.instance_created", &[("name", &value)]),
+                                Err(e) => app.report_error(trf("status.create_failed", &[("error", &e)]), &e),
+                            },
+                            TextPrompt::WizardDataDir => {
+                                let dir = Some(value.trim()).filter(|dir| !dir.is_empty()).map(crate::utils::expand_home);
+                                if let Err(e) = app.wizard_choose_data_dir(dir, false) {
+                                    app.report_error(trf("status.setting_rejected", &[("error", &e)]), &e);
+                                }
+                            }
+                            TextPrompt::ImportPath => {
+                                app.current_state = tr("status.importing").to_string();
+                                let path = Some(value.trim()).filter(|path| !path.is_empty()).map(crate::utils::expand_home);
+                                let result = match path {
+                                    Some(path) => app.import_or_review(&path).await.map(|id| usize::from(id.is_some())),
+                                    None => app.import_from_inbox().await,
+                                };
+                                match result {
+                                    Ok(_) if app.state == AppState::ModpackUpdate => {
+                                        list_state.select(Some(0));
+                                        let name = app.modpack_update.as_ref().map(|u| u.source.name.clone()).unwrap_or_default();
+                                        app.current_state = trf("status.import_matches_installed", &[("name", &name)]);
+                                    }
+                                    Ok(0) => app.current_state = trf("status.import_inbox_empty", &[("path", &app.import_dir().display())]),
+                                    Ok(count) => app.current_state = trf("status.instances_imported", &[("count", &count)]),
+                                    Err(e) => app.report_error(trf("status.import_failed", &[("error", &e)]), &e),
+                                }
+                            }
+                            TextPrompt::JavaPath => {
+                                app.current_state = tr("status.java_checking").to_string();
+                                match app.add_java_installation(crate::utils::expand_home(&value)).await {
+                                    Ok(key) => app.current_state = trf("status.java_added", &[("java", &key)]),
+                                    Err(e) => app.report_error(trf("status.java_add_failed", &[("error", &e)]), &e),
+                                }
+                            }
+                            TextPrompt::JavaDownload => match value.parse::<u8>() {
+                                Ok(version) => {
+                                    app.current_state = trf("status.java_downloading", &[("version", &version)]);
+                                    app.start_java_download(version);
+                                }
+                                Err(_) => app.current_state = trf("status.java_version_invalid", &[("value", &value)]),
+                            },
+                            TextPrompt::AccountName(account_id) => match app.change_account_name(*account_id, value.clone()) {
+                                Ok(_) => app.current_state = trf("status.nickname_changed", &[("name", &value)]),
+                                Err(e) => app.report_error(trf("status.nickname_failed", &[("error", &e)]), &e),
+                            },
+                        }
+                        prompt = None;
+                    }
+                }
+                if app.should_quit {
+                    break;
+                }
+                continue;
+            }
+
+            if app.state == AppState::Wizard {
+                let Some(step) = app.wizard.as_ref().map(|w| w.step) else {
+                    app.finish_wizard();
+                    continue;
+                };
+                match key.code {
+                    KeyCode::Up => move_selection(&mut list_state, list_len(&app), -1),
+                    KeyCode::Down => move_selection(&mut list_state, list_len(&app), 1),
+                    KeyCode::Char('q') => break,
+                    KeyCode::Esc if app.wizard.as_mut().is_some_and(|w| w.back()) => {
+                        list_state.select(Some(0));
+                    }
+                    KeyCode::Enter => {
+                        let selected = list_state.selected().unwrap_or(0);
+                        match step.items().get(selected).cloned() {
+                            Some(WizardItem::Language(language)) => {
+                                apply_setting(&mut app, SettingField::Language, SettingValue::Language(language));
+                                if let Some(wizard) = app.wizard.as_mut() {
+                                    wizard.advance();
+                                }
+                                list_state.select(Some(0));
+                            }
+                            Some(WizardItem::Setting(field)) => match field.cycle(app.get_settings()) {
+                                Some(value) => apply_setting(&mut app, field, value),
+                                None => {
+                                    let input = TextInput::new(tr(field.label_key()), field.edit_text(app.get_settings()));
+                                    prompt = Some((TextPrompt::Setting(field), input));
+                                }
+                            },
+                            Some(WizardItem::DataDir) => {
+                                let current = app.data_dir.display().to_string();
+                                prompt = Some((TextPrompt::WizardDataDir, TextInput::new(tr("prompt.data_dir"), current)));
+                            }
+                            Some(WizardItem::Portable) => {
+                                if let Err(e) = app.wizard_choose_data_dir(None, true) {
+                                    app.report_error(trf("status.setting_rejected", &[("error", &e)]), &e);
+                                }
+                            }
+                            Some(WizardItem::ScanJava) => {
+                                app.current_state = tr("wizard.java_scanning").to_string();
+                                app.start_java_scan();
+                            }
+                            Some(WizardItem::DownloadJava) => {
+                                let version = app.wizard_java_major();
+                                app.current_state = trf("status.java_downloading", &[("version", &version)]);
+                                app.start_java_download(version);
+                            }
+                            Some(WizardItem::AddOfflineAccount) => {
+                                prompt = Some((TextPrompt::WizardAccount, TextInput::new(tr("prompt.wizard_account"), "")));
+                            }
+                            Some(WizardItem::CreateInstance) => {
+                                prompt = Some((TextPrompt::WizardInstance, TextInput::new(tr("prompt.new_instance_name"), tr("wizard.starter_name"))));
+                            }
+                            Some(WizardItem::Continue) => {
+                                if let Some(wizard) = app.wizard.as_mut() {
+                                    wizard.advance();
+                                }
+                                list_state.select(Some(0));
+                            }
+                            Some(WizardItem::Finish) => {
+                                app.finish_wizard();
+                                list_state.select(Some(0));
+                            }
+                            None => {}
+                        }
+                    }
+                    _ => {}
+                }
+                if app.should_quit {
+                    break;
+                }
+                continue;
+            }
+
+            if app.state == AppState::ModBrowser && app.mod_browser.as_ref().is_some_and(|b| b.editing) {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        if let Some(browser) = app.mod_browser.as_mut() {
+                            browser.query.push(c);
+                        }
+                    }
+                    KeyCode::Backspace => {
+                        if let Some(browser) = app.mod_browser.as_mut() {
+                            browser.query.pop();
+                        }
+                    }
+                    KeyCode::Enter => {
+                        app.current_state = tr("status.modrinth_searching").to_string();
+                        match app.search_mod_browser(0).await {
+                            Ok(()) => {
+                                let found = app.mod_browser.as_ref().map(|b| b.total_hits).unwrap_or(0);
+                                app.current_state = plural("plural.projects_found", found as u64, &[]);
+                                list_state.select(Some(0));
+                            }
+                            Err(e) => app.report_error(trf("status.search_failed", &[("error", &e)]), &e),
+                        }
+                    }
+                    KeyCode::Esc => {
+                        if let Some(browser) = app.mod_browser.as_mut() {
+                            browser.editing = false;
+                        }
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if app.state == AppState::LoaderVersions && app.loader_picker.as_ref().is_some_and(|p| p.editing) {
+                if let Some(picker) = app.loader_picker.as_mut() {
+                    match key.code {
+                        KeyCode::Char(c) => {
+                            picker.query.push(c);
+                            list_state.select(Some(0));
+                        }
+                        KeyCode::Backspace => {
+                            picker.query.pop();
+                            list_state.select(Some(0));
+                        }
+                        KeyCode::Enter | KeyCode::Esc => picker.editing = false,
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+
+            if app.state == AppState::Console && app.console_input.is_some() {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        if let Some(input) = app.console_input.as_mut() {
+                            input.push(c);
+                        }
+                    }
+                    KeyCode::Backspace => {
+                        if let Some(input) = app.console_input.as_mut() {
+                            input.pop();
+                        }
+                    }
+                    KeyCode::Enter => {
+                        if let Err(e) = app.send_console_command() {
+                            app.report_error(trf("status.error", &[("error", &e)]), &e);
+                        }
+                    }
+                    KeyCode::Esc => app.console_input = None,
+                    _ => {}
+                }
+                continue;
+            }
+
+            if app.state == AppState::Logs && app.log_view.editing {
+                match key.code {
+                    KeyCode::Char(c) => app.log_view.query.push(c),
+                    KeyCode::Backspace => {
+                        app.log_view.query.pop();
+                    }
+                    KeyCode::Enter | KeyCode::Esc => app.log_view.editing = false,
+                    _ => {}
+                }
+                continue;
+            }
+
+            if app.state == AppState::Launcher && app.version_search_editing {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        app.version_filter.query.push(c);
+                        list_state.select(Some(0));
+                    }
+                    KeyCode::Backspace => {
+                        app.version_filter.query.pop();
+                        list_state.select(Some(0));
+                    }
+                    KeyCode::Enter | KeyCode::Esc => {
+                        app.version_search_editing = false;
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if app.state == AppState::InstanceList && app.instance_search_editing {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        app.instance_query.push(c);
+                        list_state.select(Some(0));
+                    }
+                    KeyCode::Backspace => {
+                        app.instance_query.pop();
+                        list_state.select(Some(0));
+                    }
+                    KeyCode::Enter | KeyCode::Esc => {
+                        app.instance_search_editing = false;
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if app.state == AppState::EditInstance && app.java_args_input.is_some() {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        if let Some(input) = app.java_args_input.as_mut() {
+                            input.push(c);
+                        }
+                    }
+                    KeyCode::Backspace => {
+                        if let Some(input) = app.java_args_input.as_mut() {
+                            input.pop();
+                        }
+                    }
+                    KeyCode::Enter => {
+                        let issues = app.apply_java_args_input();
+                        let errors = issues.iter().filter(|issue| issue.severity() == LintSeverity::Error).count();
+                        app.current_state = if issues.is_empty() {
+                            tr("status.java_args_changed").to_string()
+                        } else {
+                            trf("status.java_args_changed_with_issues", &[("errors", &errors), ("warnings", &(issues.len() - errors))])
+                        };
+                    }
+                    KeyCode::Esc => {
+                        app.java_args_input = None;
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if app.state == AppState::InstanceList && app.group_input.is_some() {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        if let Some(input) = app.group_input.as_mut() {
+                            input.push(c);
+                        }
+                    }
+                    KeyCode::Backspace => {
+                        if let Some(input) = app.group_input.as_mut() {
+                            input.pop();
+                        }
+                    }
+                    KeyCode::Enter => {
+                        let group = app.group_input.take().unwrap_or_default();
+                        match app.assign_group_to_marked(list_state.selected(), &group) {
+                            Ok(count) => app.current_state = trf("status.group_assigned", &[("count", &count)]),
+                            Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                        }
+                    }
+                    KeyCode::Esc => {
+                        app.group_input = None;
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if app.state == AppState::PackManager && app.pack_browser.as_ref().is_some_and(|b| b.path_input.is_some()) {
+                match key.code {
+                    KeyCode::Char(c) => {
+                        if let Some(input) = app.pack_browser.as_mut().and_then(|b| b.path_input.as_mut()) {
+                            input.push(c);
+                        }
+                    }
+                    KeyCode::Backspace => {
+                        if let Some(input) = app.pack_browser.as_mut().and_then(|b| b.path_input.as_mut()) {
+                            input.pop();
+                        }
+                    }
+                    KeyCode::Enter => {
+                        let path = app.pack_browser.as_mut().and_then(|b| b.path_input.take()).unwrap_or_default();
+                        match app.install_pack(&path) {
+                            Ok(installed) => app.current_state = trf("status.installed", &[("name", &installed)]),
+                            Err(e) => app.report_error(trf("status.install_failed", &[("error", &e)]), &e),
+                        }
+                    }
+                    KeyCode::Esc => {
+                        if let Some(browser) = app.pack_browser.as_mut() {
+                            browser.path_input = None;
+                        }
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            // The overlay only reads; any key closes it and is not passed on.
+            if show_help {
+                show_help = false;
+                continue;
+            }
+            if key.code == KeyCode::Char('?') {
+                show_help = true;
+                continue;
+            }
+
             match key.code {
                 KeyCode::Char('q') | KeyCode::Esc => {
                     match app.state {
                         AppState::MainMenu => break,
                         AppState::EditInstance => {
                             app.cancel_instance_editing();
-                            app.current_state = "Редактирование отменено".to_string();
+                            app.current_state = tr("status.editing_cancelled").to_string();
+                            list_state.select(Some(0));
+                        }
+                        AppState::CrashReport => {
+                            app.state = AppState::InstanceList;
+                            list_state.select(Some(0));
+                        }
+                        AppState::ModBrowser => {
+                            app.mod_browser = None;
+                            app.state = if app.editing_instance_id.is_some() { AppState::EditInstance } else { AppState::InstanceList };
+                            list_state.select(Some(0));
+                        }
+                        AppState::CommandApproval => {
+                            app.cancel_command_approval();
+                            list_state.select(Some(0));
+                        }
+                        AppState::AccountPicker => {
+                            app.cancel_account_picker();
+                            list_state.select(Some(0));
+                        }
+                        AppState::LaunchConflict => {
+                            if let Err(e) = app.resolve_launch_conflict(false) {
+                                app.report_error(trf("status.error", &[("error", &e)]), &e);
+                            }
+                            list_state.select(Some(0));
+                        }
+                        AppState::ModList => {
+                            app.close_installed_mods();
+                            app.state = if app.editing_instance_id.is_some() { AppState::EditInstance } else { AppState::InstanceList };
+                            list_state.select(Some(0));
+                        }
+                        AppState::Console => {
+                            app.close_console();
+                            list_state.select(Some(0));
+                        }
+                        AppState::News => {
+                            app.news_focus = None;
+                            app.state = app.news_return.clone();
+                            list_state.select(Some(0));
+                        }
+                        AppState::SystemInfo => {
+                            app.state = AppState::MainMenu;
+                            list_state.select(Some(0));
+                        }
+                        AppState::LaunchVariants => {
+                            app.variants_instance = None;
+                            app.state = AppState::InstanceList;
+                            list_state.select(Some(0));
+                        }
+                        AppState::QuickPlay => {
+                            app.close_quick_play();
+                            list_state.select(Some(0));
+                        }
+                        AppState::CloneInstance => {
+                            app.clone_dialog = None;
+                            app.state = AppState::InstanceList;
                             list_state.select(Some(0));
                         }
+                        AppState::ModpackUpdate => {
+                            app.modpack_update = None;
+                            app.state = AppState::InstanceList;
+                            list_state.select(Some(0));
+                        }
+                        AppState::Backups => {
+                            app.backups.clear();
+                            app.state = AppState::InstanceList;
+                            list_state.select(Some(0));
+                        }
+                        AppState::LoaderVersions => {
+                            app.close_loader_versions();
+                            list_state.select(Some(3));
+                        }
+                        AppState::PackManager => {
+                            app.pack_browser = None;
+                            app.state = if app.editing_instance_id.is_some() { AppState::EditInstance } else { AppState::InstanceList };
+                            list_state.select(Some(0));
+                        }
+                        AppState::ModBisect => {
+                            if app.bisect.as_ref().is_some_and(|s| s.running) {
+                                app.current_state = tr("status.wait_for_game").to_string();
+                            } else {
+                                app.abort_mod_bisect();
+                                app.state = AppState::InstanceList;
+                                list_state.select(Some(0));
+                            }
+                        }
                         _ => {
                             app.state = AppState::MainMenu;
                             list_state.select(Some(0));
                         }
                     }
                 }
-                KeyCode::Down => {
-                    let max_items = match app.state {
-                        AppState::MainMenu => 3,
-                        AppState::InstanceList => {
-                            let instances = app.instance_manager.list_instances().len();
-                            if instances == 0 { 0 } else { instances.saturating_sub(1) }
-                        },
-                        AppState::EditInstance => 10,
-                        AppState::Settings => 7, 
-                        AppState::Launcher => {
-                            let versions = app.get_displayed_versions().len();
-                            if versions == 0 { 0 } else { versions.saturating_sub(1) }
-                        },
-                        AppState::AccountManager => {
-                            let accounts = app.auth_manager.list_accounts().len();
-                            if accounts == 0 { 0 } else { accounts.saturating_sub(1) }
-                        },
-                    };
-                    if let Some(selected) = list_state.selected() {
-                        if selected < max_items {
-                            list_state.select(Some(selected + 1));
-                        }
+                KeyCode::Up | KeyCode::PageUp | KeyCode::Home if matches!(app.state, AppState::Logs | AppState::Console) => {
+                    if app.state == AppState::Logs {
+                        app.log_view.follow = false;
+                    } else {
+                        app.console_follow = false;
                     }
-                }
-                KeyCode::Up => {
-                    if let Some(selected) = list_state.selected() {
-                        if selected > 0 {
-                            list_state.select(Some(selected - 1));
-                        }
+                    match key.code {
+                        KeyCode::Up => move_selection(&mut list_state, list_len(&app), -1),
+                        KeyCode::PageUp => move_selection(&mut list_state, list_len(&app), -(page_size as isize)),
+                        _ => list_state.select(Some(0)),
                     }
                 }
+                KeyCode::End if app.state == AppState::Logs => app.log_view.follow = true,
+                KeyCode::End if app.state == AppState::Console => app.console_follow = true,
+                KeyCode::Down => move_selection(&mut list_state, list_len(&app), 1),
+                KeyCode::Up => move_selection(&mut list_state, list_len(&app), -1),
+                KeyCode::PageDown => move_selection(&mut list_state, list_len(&app), page_size as isize),
+                KeyCode::PageUp => move_selection(&mut list_state, list_len(&app), -(page_size as isize)),
+                KeyCode::Home => list_state.select(Some(0)),
+                KeyCode::End => list_state.select(Some(list_len(&app).saturating_sub(1))),
+                KeyCode::Char('[') if app.show_logs => app.scroll_logs(true, page_size),
+                KeyCode::Char(']') if app.show_logs => app.scroll_logs(false, page_size),
                 KeyCode::Enter => {
                     if let Some(selected) = list_state.selected() {
                         match app.state {
@@ -100,29 +860,46 @@ pub async fn run_ui(mut app: App) -> Result<()> {
                                     1 => app.state = AppState::Settings,
                                     2 => app.state = AppState::Launcher,
                                     3 => app.state = AppState::AccountManager,
+                                    4 => app.state = AppState::Java,
+                                    5 => app.open_storage(),
+                                    6 => {
+                                        app.log_view.follow = true;
+                                        app.state = AppState::Logs;
+                                    }
+                                    7 => app.open_news(None),
+                                    8 => app.state = AppState::SystemInfo,
                                     _ => {}
                                 }
                                 list_state.select(Some(0));
                             }
                             AppState::InstanceList => {
-                                let instances = app.instance_manager.list_instances();
-                                if let Some(instance) = instances.get(selected) {
-                                    app.current_state = format!("Запуск {}...", instance.name);
-                                    if let Err(e) = app.launch_instance(instance.id).await {
-                                        app.current_state = format!("Ошибка запуска: {}", e);
+                                let instance = app.displayed_instances().get(selected)
+                                    .map(|instance| (instance.id, instance.name.clone()));
+                                if let Some((id, name)) = instance {
+                                    app.current_state = trf("status.launching", &[("name", &name)]);
+                                    if let Err(e) = app.launch_instance(id) {
+                                        app.report_error(trf("status.launch_failed", &[("error", &e)]), &e);
                                     }
                                 }
                             }
                             AppState::EditInstance => {
-                                let versions = app.version_manager.get_installed_versions();
+                                let channels = app.get_editing_instance()
+                                    .map(|instance| app.version_channels_for(instance))
+                                    .unwrap_or_default();
+                                let versions: Vec<_> = app.version_manager.get_installed_versions()
+                                    .into_iter()
+                                    .filter(|version| channels.includes(version))
+                                    .collect();
                                 let java_installations: Vec<_> = app.get_java_installations().values().cloned().collect();
+                                let gc_presets = app.get_editing_instance()
+                                    .map(|instance| GcPreset::available(app.instance_java_major(instance)))
+                                    .unwrap_or_default();
                                 
                                 if let Some(instance) = app.get_editing_instance_mut() {
                                     match selected {
                                         0 => {
-                                            let new_name = format!("Экземпляр_{}", Utc::now().format("%H%M%S"));
-                                            instance.name = new_name.clone();
-                                            app.current_state = format!("Название изменено на: {}", new_name);
+                                            let title = tr("prompt.instance_name");
+                                            prompt = Some((TextPrompt::InstanceName, TextInput::new(title, instance.name.clone())));
                                         }
                                         1 => {
                                             if !versions.is_empty() {
@@ -131,9 +908,9 @@ pub async fn run_ui(mut app: App) -> Result<()> {
                                                     .unwrap_or(0);
                                                 let next_index = (current_index + 1) % versions.len();
                                                 instance.minecraft_version = versions[next_index].id.clone();
-                                                app.current_state = format!("Версия изменена на: {}", instance.minecraft_version);
+                                                app.current_state = trf("status.version_changed", &[("version", &instance.minecraft_version)]);
                                             } else {
-                                                app.current_state = "Нет скачанных версий! Скачайте версии в лаунчере".to_string();
+                                                app.current_state = tr("status.no_installed_versions").to_string();
                                             }
                                         }
                                         2 => {
@@ -147,16 +924,19 @@ pub async fn run_ui(mut app: App) -> Result<()> {
                                             };
                                             let loader_name = instance.mod_loader.as_ref()
                                                 .map(|ml| format!("{:?}", ml))
-                                                .unwrap_or_else(|| "Нет".to_string());
-                                            app.current_state = format!("Модлоадер: {}", loader_name);
+                                                .unwrap_or_else(|| tr("common.none").to_string());
+                                            // Builds of one loader mean nothing to another.
+                                            instance.mod_loader_version = None;
+                                            app.current_state = trf("status.mod_loader", &[("loader", &loader_name)]);
+                                        }
+                                        3 if instance.mod_loader.is_none() => {
+                                            app.current_state = tr("status.loader_versions_no_loader").to_string();
                                         }
                                         3 => {
-                                            let versions = ["latest", "recommended", "1.0.0", "0.15.11", "47.2.0"];
-                                            let current = instance.mod_loader_version.as_deref().unwrap_or("latest");
-                                            let current_index = versions.iter().position(|&v| v == current).unwrap_or(0);
-                                            let next_index = (current_index + 1) % versions.len();
-                                            instance.mod_loader_version = Some(versions[next_index].to_string());
-                                            app.current_state = format!("Версия модлоадера: {}", versions[next_index]);
+                                            if let Err(e) = app.open_loader_versions() {
+                                                app.report_error(trf("status.error", &[("error", &e)]), &e);
+                                            }
+                                            list_state.select(Some(0));
                                         }
                                         4 => {
                                             if !java_installations.is_empty() {
@@ -168,148 +948,133 @@ pub async fn run_ui(mut app: App) -> Result<()> {
                                                 
                                                 if next_index == java_installations.len() {
                                                     instance.java_path = None;
-                                                    app.current_state = "Java: По умолчанию".to_string();
+                                                    app.current_state = trf("status.java_selected_default", &[("default", &tr("common.default"))]);
                                                 } else {
                                                     instance.java_path = Some(java_installations[next_index].path.clone());
-                                                    app.current_state = format!("Java: {} {}", 
-                                                        java_installations[next_index].vendor, 
-                                                        java_installations[next_index].version);
+                                                    app.current_state = trf("status.java_selected", &[("vendor", &java_installations[next_index].vendor), ("version", &java_installations[next_index].version)]);
                                                 }
                                             } else {
-                                                app.current_state = "Запустите автопоиск Java в настройках (J)".to_string();
+                                                app.current_state = tr("status.java_scan_hint").to_string();
                                             }
                                         }
                                         5 => {
-                                            let args_options = [
-                                                "По умолчанию",
-                                                "-XX:+UseG1GC",
-                                                "-XX:+UseZGC", 
-                                                "-XX:+UseParallelGC",
-                                                "-Xmx4G -XX:+UseG1GC -XX:+UnlockExperimentalVMOptions"
-                                            ];
-                                            let current = instance.java_args.as_deref().unwrap_or("По умолчанию");
-                                            let current_index = args_options.iter().position(|&v| v == current).unwrap_or(0);
-                                            let next_index = (current_index + 1) % args_options.len();
-                                            
-                                            if args_options[next_index] == "По умолчанию" {
-                                                instance.java_args = None;
-                                            } else {
-                                                instance.java_args = Some(args_options[next_index].to_string());
-                                            }
-                                            app.current_state = format!("Аргументы Java: {}", args_options[next_index]);
+                                            // Presets the instance's Java cannot run are skipped.
+                                            let next = match instance.gc_preset.and_then(|current| gc_presets.iter().position(|p| *p == current)) {
+                                                Some(index) => gc_presets.get(index + 1).copied(),
+                                                None => gc_presets.first().copied(),
+                                            };
+                                            instance.gc_preset = next;
+                                            let label = next.map(|preset| preset.label()).unwrap_or_else(|| tr("common.default"));
+                                            app.current_state = trf("status.gc_preset", &[("preset", &label)]);
                                         }
                                         6 => {
+                                            // After the largest value the instance goes back to inheriting.
                                             let memory_options = [512, 1024, 2048, 4096, 6144, 8192];
-                                            let current = instance.memory_min.unwrap_or(1024);
-                                            let current_index = memory_options.iter().position(|&v| v == current).unwrap_or(1);
-                                            let next_index = (current_index + 1) % memory_options.len();
-                                            instance.memory_min = Some(memory_options[next_index]);
-                                            app.current_state = format!("Минимальная память: {} MB", memory_options[next_index]);
-                                        }
+                                            let next = match instance.memory_min.and_then(|current| memory_options.iter().position(|&v| v == current)) {
+                                                Some(index) => memory_options.get(index + 1).copied(),
+                                                None => memory_options.first().copied(),
+                                            };
+                                            instance.memory_min = next;
+                                            app.current_state = match next {
+                                                Some(memory) => trf("status.memory_min", &[("memory", &memory)]),
+                                                None => tr("status.memory_min_inherited").to_string(),
+                                            };
+                                        }
                                         7 => {
                                             let memory_options = [1024, 2048, 4096, 6144, 8192, 12288, 16384];
-                                            let current = instance.memory_max.unwrap_or(4096);
-                                            let current_index = memory_options.iter().position(|&v| v == current).unwrap_or(2);
-                                            let next_index = (current_index + 1) % memory_options.len();
-                                            instance.memory_max = Some(memory_options[next_index]);
-                                            app.current_state = format!("Максимальная память: {} MB", memory_options[next_index]);
+                                            let next = match instance.memory_max.and_then(|current| memory_options.iter().position(|&v| v == current)) {
+                                                Some(index) => memory_options.get(index + 1).copied(),
+                                                None => memory_options.first().copied(),
+                                            };
+                                            instance.memory_max = next;
+                                            app.current_state = match next {
+                                                Some(memory) => trf("status.memory_max", &[("memory", &memory)]),
+                                                None => tr("status.memory_max_inherited").to_string(),
+                                            };
                                         }
                                         8 => {
                                             let resolutions = [(854, 480), (1280, 720), (1920, 1080), (2560, 1440), (3840, 2160)];
-                                            let current = (instance.width.unwrap_or(854), instance.height.unwrap_or(480));
-                                            let current_index = resolutions.iter().position(|&v| v == current).unwrap_or(0);
-                                            let next_index = (current_index + 1) % resolutions.len();
-                                            let (new_width, new_height) = resolutions[next_index];
-                                            instance.width = Some(new_width);
-                                            instance.height = Some(new_height);
-                                            app.current_state = format!("Разрешение: {}x{}", new_width, new_height);
+                                            let next = match instance.width.zip(instance.height).and_then(|current| resolutions.iter().position(|&v| v == current)) {
+                                                Some(index) => resolutions.get(index + 1).copied(),
+                                                None => resolutions.first().copied(),
+                                            };
+                                            instance.width = next.map(|(width, _)| width);
+                                            instance.height = next.map(|(_, height)| height);
+                                            app.current_state = match next {
+                                                Some((width, height)) => trf("status.resolution", &[("width", &width), ("height", &height)]),
+                                                None => tr("status.resolution_inherited").to_string(),
+                                            };
                                         }
                                         9 => {
-                                            instance.fullscreen = !instance.fullscreen;
-                                            app.current_state = format!("Полноэкранный режим: {}", 
-                                                if instance.fullscreen { "Включен" } else { "Отключен" });
+                                            instance.fullscreen = match instance.fullscreen {
+                                                None => Some(true),
+                                                Some(true) => Some(false),
+                                                Some(false) => None,
+                                            };
+                                            app.current_state = match instance.fullscreen {
+                                                Some(on) => trf("status.fullscreen", &[("state", &tr(if on { "common.on" } else { "common.off" }))]),
+                                                None => tr("status.fullscreen_inherited").to_string(),
+                                            };
                                         }
                                         10 => {
-                                            let groups = ["Нет", "Модпаки", "Ванилла", "Снапшоты", "Тестирование"];
-                                            let current = instance.group.as_deref().unwrap_or("Нет");
+                                            let none = tr("common.none");
+                                            let groups = [none, tr("group.modpacks"), tr("group.vanilla"), tr("group.snapshots"), tr("group.testing")];
+                                            let current = instance.group.as_deref().unwrap_or(none);
                                             let current_index = groups.iter().position(|&v| v == current).unwrap_or(0);
                                             let next_index = (current_index + 1) % groups.len();
                                             
-                                            if groups[next_index] == "Нет" {
+                                            if groups[next_index] == none {
                                                 instance.group = None;
                                             } else {
                                                 instance.group = Some(groups[next_index].to_string());
                                             }
-                                            app.current_state = format!("Группа: {}", groups[next_index]);
+                                            app.current_state = trf("status.group", &[("group", &groups[next_index])]);
+                                        }
+                                        11 => {
+                                            let presets = [
+                                                None,
+                                                Some(VersionChannels::default()),
+                                                Some(VersionChannels { snapshots: true, ..VersionChannels::default() }),
+                                                Some(VersionChannels::ALL),
+                                            ];
+                                            let current_index = presets.iter().position(|p| *p == instance.version_channels).unwrap_or(0);
+                                            instance.version_channels = presets[(current_index + 1) % presets.len()];
+                                            let label = instance.version_channels
+                                                .map(|c| c.label())
+                                                .unwrap_or_else(|| tr("common.default").to_string());
+                                            app.current_state = trf("status.version_channels", &[("channels", &label)]);
+                                        }
+                                        12 => {
+                                            instance.demo_mode = !instance.demo_mode;
+                                            app.current_state = trf("status.demo_mode", &[("state", &tr(if instance.demo_mode { "common.on" } else { "common.off" }))]);
+                                        }
+                                        13 => {
+                                            // A custom icon restarts the cycle at the first preset.
+                                            let next = match instance.icon.as_deref().and_then(|icon| ICON_PRESETS.iter().position(|p| *p == icon)) {
+                                                Some(index) => ICON_PRESETS.get(index + 1).copied(),
+                                                None => ICON_PRESETS.first().copied(),
+                                            };
+                                            instance.icon = next.map(str::to_string);
+                                            app.current_state = trf("status.icon_changed", &[("icon", &next.unwrap_or(tr("common.none")))]);
+                                        }
+                                        14..=16 => {
+                                            let link = [InstanceLink::Project, InstanceLink::Issues, InstanceLink::Wiki][selected - 14];
+                                            let current = instance.links.get(link).unwrap_or_default().to_string();
+                                            prompt = Some((TextPrompt::InstanceLink(link), TextInput::new(tr("prompt.instance_link"), current)));
                                         }
                                         _ => {}
                                     }
                                 }
                             }
                             AppState::Settings => {
-                                match selected {
-                                    0 => {
-                                        app.language = if app.language == Language::Russian {
-                                            Language::English
-                                        } else {
-                                            Language::Russian
-                                        };
-                                        app.current_state = "Язык изменен".to_string();
-                                    }
-                                    2 => {
-                                        let settings = app.get_settings_mut();
-                                        if settings.java.memory_min >= settings.java.memory_max {
-                                            settings.java.memory_max = ((settings.java.memory_max + 1024) % 16384).max(2048);
-                                            app.current_state = format!("Максимальная память: {}MB", settings.java.memory_max);
-                                        } else {
-                                            settings.java.memory_min = ((settings.java.memory_min + 512) % 8192).max(512);
-                                            app.current_state = format!("Минимальная память: {}MB", settings.java.memory_min);
-                                        }
-                                    }
-                                    3 => {
-                                        let java_dirs = vec![
-                                            "/usr/bin".to_string(),
-                                            "/Library/Java/JavaVirtualMachines".to_string(),
-                                            "/opt/homebrew/opt".to_string(),
-                                            "/System/Library/Frameworks/JavaVM.framework/Versions".to_string(),
-                                        ];
-                                        let settings = app.get_settings_mut();
-                                        let current_dir = settings.general.java_directory.to_string_lossy().to_string();
-                                        let current_index = java_dirs.iter().position(|d| current_dir.contains(d)).unwrap_or(0);
-                                        let next_index = (current_index + 1) % java_dirs.len();
-                                        settings.general.java_directory = std::path::PathBuf::from(&java_dirs[next_index]);
-                                        app.current_state = format!("Java директория изменена, сканирую...");
-                                        let _ = app.save_settings();
-                                        if let Err(e) = app.scan_java_installations().await {
-                                            app.current_state = format!("Ошибка сканирования Java: {}", e);
-                                        } else {
-                                            let count = app.get_java_installations().len();
-                                            app.current_state = format!("Java директория: {} (найдено {})", java_dirs[next_index], count);
+                                if let Some(&field) = SettingField::ALL.get(selected) {
+                                    match field.cycle(app.get_settings()) {
+                                        Some(value) => apply_setting(&mut app, field, value),
+                                        None => {
+                                            let input = TextInput::new(tr(field.label_key()), field.edit_text(app.get_settings()));
+                                            prompt = Some((TextPrompt::Setting(field), input));
                                         }
                                     }
-                                    5 => {
-                                        let thread_options = [1, 2, 3, 4, 6, 8, 12, 16];
-                                        let settings = app.get_settings_mut();
-                                        let current = settings.network.max_concurrent_downloads;
-                                        let current_index = thread_options.iter().position(|&t| t == current).unwrap_or(3);
-                                        let next_index = (current_index + 1) % thread_options.len();
-                                        settings.network.max_concurrent_downloads = thread_options[next_index];
-                                        let _ = app.save_settings();
-                                        app.update_network_settings();
-                                        app.current_state = format!("Потоки загрузки: {}", thread_options[next_index]);
-                                    }
-                                    6 => {
-                                        let new_value = {
-                                            let settings = app.get_settings_mut();
-                                            settings.advanced.save_logs_to_file = !settings.advanced.save_logs_to_file;
-                                            settings.advanced.save_logs_to_file
-                                        };
-                                        let _ = app.save_settings();
-                                        app.update_file_logging();
-                                        app.current_state = format!("Сохранение логов: {}", 
-                                            if new_value { "Включено" } else { "Отключено" });
-                                    }
-                                    _ => {}
                                 }
                             }
                             AppState::AccountManager => {
@@ -318,70 +1083,199 @@ pub async fn run_ui(mut app: App) -> Result<()> {
                                     let account_id = account.id;
                                     match app.set_default_account(account_id) {
                                         Ok(_) => {
-                                            app.current_state = "Аккаунт установлен как основной".to_string();
+                                            app.current_state = tr("status.account_default").to_string();
                                         },
                                         Err(e) => {
-                                            app.current_state = format!("Ошибка: {}", e);
+                                            app.report_error(trf("status.error", &[("error", &e)]), &e);
+                                        }
+                                    }
+                                }
+                            }
+                            AppState::CrashReport => {
+                                if let Err(e) = app.open_crash_report() {
+                                    app.report_error(trf("status.error", &[("error", &e)]), &e);
+                                }
+                            }
+                            AppState::Java => set_default_java(&mut app, selected),
+                            AppState::LoaderVersions => {
+                                if let Some(version) = app.pick_loader_version(selected) {
+                                    app.current_state = trf("status.mod_loader_version", &[("version", &version)]);
+                                    list_state.select(Some(3));
+                                }
+                            }
+                            AppState::ModBrowser => {
+                                app.current_state = tr("status.installing_mod").to_string();
+                                match app.install_from_mod_browser(selected).await {
+                                    Ok(installed) => app.current_state = trf("status.installed", &[("name", &installed)]),
+                                    Err(e) => app.report_error(trf("status.install_failed", &[("error", &e)]), &e),
+                                }
+                            }
+                            AppState::Storage => {
+                                if let Some(&cleanup) = Cleanup::ALL.get(selected) {
+                                    match app.start_cleanup(cleanup) {
+                                        Ok(()) => app.current_state = tr("status.cleaning_up").to_string(),
+                                        Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                                    }
+                                }
+                            }
+                            AppState::CommandApproval | AppState::LaunchConflict | AppState::Logs | AppState::Wizard | AppState::News | AppState::SystemInfo => {}
+                            AppState::Console => app.console_input = Some(String::new()),
+                            AppState::ModList => {
+                                match app.toggle_installed_mod(selected) {
+                                    Ok(true) => app.current_state = tr("status.mod_enabled").to_string(),
+                                    Ok(false) => app.current_state = tr("status.mod_disabled").to_string(),
+                                    Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                                }
+                            }
+                            AppState::QuickPlay => {
+                                if let Err(e) = app.launch_quick_play(selected) {
+                                    app.report_error(trf("status.launch_failed", &[("error", &e)]), &e);
+                                }
+                            }
+                            AppState::LaunchVariants => {
+                                if let Some(id) = app.variants_instance {
+                                    let variant = selected.checked_sub(1).and_then(|i| app.launch_variant_names().get(i).cloned());
+                                    app.state = AppState::InstanceList;
+                                    app.variants_instance = None;
+                                    if let Err(e) = app.launch_instance_variant(id, variant) {
+                                        app.report_error(trf("status.launch_failed", &[("error", &e)]), &e);
+                                    }
+                                }
+                            }
+                            AppState::CloneInstance => {
+                                if let Some(dialog) = app.clone_dialog.as_mut() {
+                                    match selected {
+                                        0 => dialog.options.mods = !dialog.options.mods,
+                                        1 => dialog.options.configs = !dialog.options.configs,
+                                        2 => dialog.options.saves = !dialog.options.saves,
+                                        3 => dialog.options.resource_packs = !dialog.options.resource_packs,
+                                        4 => {
+                                            let input = TextInput::new(tr("prompt.clone_name"), dialog.name.clone());
+                                            prompt = Some((TextPrompt::CloneName, input));
                                         }
+                                        _ => match app.confirm_clone() {
+                                            Ok(_) => {
+                                                app.current_state = tr("status.instance_cloned").to_string();
+                                                list_state.select(Some(0));
+                                            }
+                                            Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                                        },
+                                    }
+                                }
+                            }
+                            AppState::AccountPicker => {
+                                if let Err(e) = app.pick_launch_account(selected) {
+                                    app.report_error(trf("status.launch_failed", &[("error", &e)]), &e);
+                                }
+                            }
+                            AppState::ModpackUpdate => {
+                                if let Some(conflict) = app.modpack_update.as_mut().and_then(|u| u.conflicts.get_mut(selected)) {
+                                    conflict.keep_local = !conflict.keep_local;
+                                } else if let Some(update) = app.modpack_update.take() {
+                                    app.state = AppState::InstanceList;
+                                    list_state.select(Some(0));
+                                    app.current_state = tr("status.updating_modpack").to_string();
+                                    if let Err(e) = app.start_modpack_update(update) {
+                                        app.report_error(trf("status.modpack_update_failed", &[("error", &e)]), &e);
                                     }
                                 }
                             }
+                            AppState::Backups => {
+                                match app.restore_backup(selected) {
+                                    Ok(name) => app.current_state = trf("status.instance_restored", &[("name", &name)]),
+                                    Err(e) => app.report_error(trf("status.restore_failed", &[("error", &e)]), &e),
+                                }
+                            }
+                            AppState::PackManager => {
+                                match app.toggle_pack(selected) {
+                                    Ok(true) => app.current_state = tr("status.pack_enabled").to_string(),
+                                    Ok(false) => app.current_state = tr("status.pack_disabled").to_string(),
+                                    Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                                }
+                            }
+                            AppState::ModBisect => {
+                                if let Err(e) = app.launch_bisect_round() {
+                                    app.report_error(trf("status.launch_failed", &[("error", &e)]), &e);
+                                }
+                            }
+                            AppState::Launcher if !app.marked.is_empty() => {
+                                match app.start_marked_downloads(Some(selected)) {
+                                    Ok(queued) => app.current_state = trf("status.downloads_queued", &[("count", &queued)]),
+                                    Err(e) => app.report_error(trf("status.download_failed", &[("error", &e)]), &e),
+                                }
+                            }
                             AppState::Launcher => {
                                 let versions = app.get_displayed_versions();
                                 if let Some(version) = versions.get(selected) {
                                     let version_id = version.id.clone();
                                     if app.show_installed_only {
-                                        app.current_state = format!("Версия {} уже скачана", version_id);
-                                    } else {
-                                        app.current_state = format!("Загрузка версии {}...", version_id);
-                                        if let Err(e) = app.download_version(&version_id).await {
-                                            app.current_state = format!("Ошибка загрузки: {}", e);
-                                        } else {
-                                            app.current_state = format!("Версия {} загружена!", version_id);
-                                        }
+                                        app.current_state = trf("status.version_already_installed", &[("version", &version_id)]);
+                                    } else if let Err(e) = app.start_version_download(&version_id) {
+                                        app.report_error(trf("status.download_failed", &[("error", &e)]), &e);
                                     }
                                 }
                             }
                         }
                     }
                 }
-                KeyCode::Char('n') => {
-                    match app.state {
-                        AppState::InstanceList => {
-                            let name = format!("Экземпляр {}", Utc::now().format("%H-%M-%S"));
-                            match app.create_instance(name.clone(), "1.21".to_string()) {
-                                Ok(_) => {
-                                    app.current_state = format!("Создан экземпляр: {}", name);
-                                },
-                                Err(e) => {
-                                    app.current_state = format!("Ошибка создания: {}", e);
-                                }
-                            }
+                KeyCode::Char(c @ '1'..='9') if app.state == AppState::MainMenu => {
+                    let index = c as usize - '1' as usize;
+                    let instance = app.recent_instances().get(index)
+                        .map(|instance| (instance.id, instance.name.clone()));
+                    if let Some((id, name)) = instance {
+                        app.current_state = trf("status.launching", &[("name", &name)]);
+                        if let Err(e) = app.launch_instance(id) {
+                            app.report_error(trf("status.launch_failed", &[("error", &e)]), &e);
                         }
-                        _ => {}
                     }
                 }
+                KeyCode::Char(c @ '1'..='9') if app.state == AppState::AccountPicker => {
+                    let index = c as usize - '1' as usize;
+                    if let Err(e) = app.pick_launch_account(index) {
+                        app.report_error(trf("status.launch_failed", &[("error", &e)]), &e);
+                    }
+                }
+                KeyCode::Char('n') if app.state == AppState::InstanceList => {
+                    let title = tr("prompt.new_instance_name");
+                    let name = trf("instances.default_name", &[("time", &Utc::now().format("%H-%M-%S"))]);
+                    prompt = Some((TextPrompt::NewInstance, TextInput::new(title, name)));
+                }
                 KeyCode::Char('d') => {
                     match app.state {
+                        AppState::Java => {
+                            let key = list_state.selected().and_then(|selected| selected_java(&app, selected));
+                            if let Some(key) = key {
+                                match app.remove_java_installation(&key) {
+                                    Ok(()) => app.current_state = trf("status.java_removed", &[("java", &key)]),
+                                    Err(e) => app.report_error(trf("status.delete_failed", &[("error", &e)]), &e),
+                                }
+                            }
+                        }
                         AppState::InstanceList => {
                             if let Some(selected) = list_state.selected() {
-                                let instances = app.instance_manager.list_instances();
-                                if let Some(instance) = instances.get(selected) {
-                                    let instance_id = instance.id;
-                                    match app.delete_instance(instance_id) {
-                                        Ok(_) => {
-                                            app.current_state = "Экземпляр удален".to_string();
-                                            let remaining = app.instance_manager.list_instances().len();
-                                            if remaining == 0 {
-                                                list_state.select(Some(0));
-                                            } else if selected >= remaining {
-                                                list_state.select(Some(remaining.saturating_sub(1)));
-                                            }
-                                        },
-                                        Err(e) => {
-                                            app.current_state = format!("Ошибка удаления: {}", e);
-                                        }
-                                    }
+                                match app.delete_marked_instances(Some(selected)) {
+                                    Ok(0) => {}
+                                    Ok(1) => app.current_state = tr("status.instance_deleted").to_string(),
+                                    Ok(count) => app.current_state = trf("status.instances_deleted", &[("count", &count)]),
+                                    Err(e) => app.report_error(trf("status.delete_failed", &[("error", &e)]), &e),
+                                }
+                                let remaining = app.displayed_instances().len();
+                                if remaining == 0 {
+                                    list_state.select(Some(0));
+                                } else if selected >= remaining {
+                                    list_state.select(Some(remaining.saturating_sub(1)));
+                                }
+                            }
+                        }
+                        AppState::ModList => {
+                            if let Some(selected) = list_state.selected() {
+                                match app.delete_marked_mods(Some(selected)) {
+                                    Ok(count) => app.current_state = trf("status.mods_deleted", &[("count", &count)]),
+                                    Err(e) => app.report_error(trf("status.delete_failed", &[("error", &e)]), &e),
+                                }
+                                let remaining = app.installed_mod_list().len();
+                                if selected >= remaining {
+                                    list_state.select(Some(remaining.saturating_sub(1)));
                                 }
                             }
                         }
@@ -392,7 +1286,6 @@ pub async fn run_ui(mut app: App) -> Result<()> {
                                     let account_id = account.id;
                                     match app.remove_account(account_id) {
                                         Ok(_) => {
-                                            app.current_state = "Аккаунт удален".to_string();
                                             let remaining = app.auth_manager.list_accounts().len();
                                             if remaining == 0 {
                                                 list_state.select(Some(0));
@@ -401,17 +1294,67 @@ pub async fn run_ui(mut app: App) -> Result<()> {
                                             }
                                         },
                                         Err(e) => {
-                                            app.current_state = format!("Ошибка удаления: {}", e);
+                                            app.report_error(trf("status.delete_failed", &[("error", &e)]), &e);
                                         }
                                     }
                                 }
                             }
                         }
+                        AppState::LaunchVariants => {
+                            if let (Some(id), Some(index)) = (app.variants_instance, list_state.selected().and_then(|s| s.checked_sub(1))) {
+                                match app.delete_launch_variant(id, index) {
+                                    Ok(name) => {
+                                        app.current_state = trf("status.variant_deleted", &[("name", &name)]);
+                                        list_state.select(Some(index.min(app.launch_variant_names().len())));
+                                    }
+                                    Err(e) => app.report_error(trf("status.delete_failed", &[("error", &e)]), &e),
+                                }
+                            }
+                        }
+                        AppState::Backups => {
+                            if let Some(selected) = list_state.selected() {
+                                match app.delete_backup(selected) {
+                                    Ok(()) => {
+                                        app.current_state = tr("status.backup_deleted").to_string();
+                                        if selected >= app.backups.len() {
+                                            list_state.select(Some(app.backups.len().saturating_sub(1)));
+                                        }
+                                    }
+                                    Err(e) => app.report_error(trf("status.delete_failed", &[("error", &e)]), &e),
+                                }
+                            }
+                        }
+                        AppState::PackManager => {
+                            if let Some(selected) = list_state.selected() {
+                                match app.remove_pack(selected) {
+                                    Ok(name) => {
+                                        app.current_state = trf("status.deleted", &[("name", &name)]);
+                                        let remaining = app.pack_browser.as_ref().map(|b| b.packs.len()).unwrap_or(0);
+                                        if selected >= remaining {
+                                            list_state.select(Some(remaining.saturating_sub(1)));
+                                        }
+                                    }
+                                    Err(e) => app.report_error(trf("status.delete_failed", &[("error", &e)]), &e),
+                                }
+                            }
+                        }
                         _ => {}
                     }
                 }
                 KeyCode::Char('s') => {
                     match app.state {
+                        AppState::Java => {
+                            if let Some(selected) = list_state.selected() {
+                                set_default_java(&mut app, selected);
+                            }
+                        }
+                        AppState::InstanceList => {
+                            match app.cycle_instance_sort() {
+                                Ok(sort) => app.current_state = trf("status.instances_sorted", &[("sort", &sort.label())]),
+                                Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                            }
+                            list_state.select(Some(0));
+                        }
                         AppState::AccountManager => {
                             if let Some(selected) = list_state.selected() {
                                 let accounts = app.auth_manager.list_accounts();
@@ -419,10 +1362,10 @@ pub async fn run_ui(mut app: App) -> Result<()> {
                                     let account_id = account.id;
                                     match app.set_default_account(account_id) {
                                         Ok(_) => {
-                                            app.current_state = "Аккаунт установлен как основной".to_string();
+                                            app.current_state = tr("status.account_default").to_string();
                                         },
                                         Err(e) => {
-                                            app.current_state = format!("Ошибка: {}", e);
+                                            app.report_error(trf("status.error", &[("error", &e)]), &e);
                                         }
                                     }
                                 }
@@ -432,187 +1375,586 @@ pub async fn run_ui(mut app: App) -> Result<()> {
                             match app.save_instance_changes() {
                                 Ok(_) => {
                                     app.state = AppState::InstanceList;
-                                    app.current_state = "Изменения сохранены".to_string();
+                                    app.current_state = tr("status.changes_saved").to_string();
                                     list_state.select(Some(0));
                                 },
                                 Err(e) => {
-                                    app.current_state = format!("Ошибка сохранения: {}", e);
+                                    app.report_error(trf("status.save_failed", &[("error", &e)]), &e);
                                 }
                             }
                         }
-                        _ => {}
-                    }
-                }
-                KeyCode::Char('r') => {
-                    match app.state {
-                        AppState::Launcher => {
-                            app.current_state = "Обновление списка версий...".to_string();
-                            if let Err(e) = app.init().await {
-                                app.current_state = format!("Ошибка обновления: {}", e);
-                            } else {
-                                app.current_state = "Список версий обновлен!".to_string();
+                        AppState::Console => {
+                            match app.save_console_log() {
+                                Ok(path) => app.current_state = trf("status.log_saved", &[("path", &path.display())]),
+                                Err(e) => app.report_error(trf("status.save_failed", &[("error", &e)]), &e),
                             }
                         }
-                        _ => {}
-                    }
-                }
-                KeyCode::Char('f') | KeyCode::Char('F') => {
-                    match app.state {
-                        AppState::Launcher => {
-                            app.current_state = "Принудительное обновление списка версий...".to_string();
-                            if let Err(e) = app.force_refresh_versions().await {
-                                app.current_state = format!("Ошибка принудительного обновления: {}", e);
-                            } else {
-                                app.current_state = "Список версий принудительно обновлен!".to_string();
+                        AppState::QuickPlay => {
+                            if let Some(selected) = list_state.selected() {
+                                let name = app.quick_play.as_ref().map(|picker| picker.instance_name.clone()).unwrap_or_default();
+                                match app.toggle_default_quick_play(selected) {
+                                    Ok(Some(target)) => app.current_state = trf("status.quick_play_default", &[("name", &name), ("target", &target.label())]),
+                                    Ok(None) => app.current_state = trf("status.quick_play_cleared", &[("name", &name)]),
+                                    Err(e) => app.report_error(trf("status.save_failed", &[("error", &e)]), &e),
+                                }
+                            }
+                        }
+                        AppState::LaunchVariants => {
+                            if let Some(id) = app.variants_instance {
+                                let title = tr("prompt.save_variant");
+                                prompt = Some((TextPrompt::LaunchVariant(id), TextInput::new(title, "")));
                             }
                         }
                         _ => {}
                     }
                 }
+                KeyCode::Char('r') | KeyCode::Char('R') if app.state == AppState::SystemInfo => {
+                    app.system_info = None;
+                    app.start_system_detection();
+                }
+                KeyCode::Char('r') | KeyCode::Char('R') if app.state == AppState::News => {
+                    app.current_state = tr("status.news_loading").to_string();
+                    app.start_news_load();
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') if app.state == AppState::Launcher => {
+                    let version = list_state.selected()
+                        .and_then(|selected| app.get_displayed_versions().get(selected).map(|v| v.id.clone()));
+                    app.open_news(version);
+                    list_state.select(Some(0));
+                }
+                KeyCode::Char('r') | KeyCode::Char('R') if app.state == AppState::Java => {
+                    app.current_state = tr("status.java_scanning").to_string();
+                    app.start_java_scan();
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') if app.state == AppState::Java => {
+                    prompt = Some((TextPrompt::JavaPath, TextInput::new(tr("prompt.java_path"), "")));
+                }
+                KeyCode::Char('g') | KeyCode::Char('G') if app.state == AppState::Java => {
+                    let version = RECOMMENDED_JAVA.to_string();
+                    prompt = Some((TextPrompt::JavaDownload, TextInput::new(tr("prompt.java_download"), version)));
+                }
+                KeyCode::Char('r') if app.state == AppState::Storage => {
+                    app.current_state = tr("status.storage_rescanning").to_string();
+                    app.rescan_disk_usage();
+                }
+                KeyCode::Char('r') if app.state == AppState::Launcher => {
+                    app.start_version_load(false);
+                }
+                KeyCode::Char('f') | KeyCode::Char('F') if app.state == AppState::Launcher => {
+                    app.start_version_load(true);
+                }
                 KeyCode::Char('l') | KeyCode::Char('L') => {
                     app.toggle_logs();
                 }
+                KeyCode::F(5) => {
+                    app.start_connectivity_check();
+                }
                 KeyCode::Char('a') | KeyCode::Char('A') => {
                     app.state = AppState::AccountManager;
                 }
-                KeyCode::Char('o') => {
-                    match app.state {
-                        AppState::AccountManager => {
-                            let username = format!("Player_{}", Utc::now().format("%H%M%S"));
-                            match app.add_offline_account(username.clone()) {
-                                Ok(_) => {
-                                    app.current_state = format!("Добавлен offline аккаунт: {}", username);
-                                },
-                                Err(e) => {
-                                    app.current_state = format!("Ошибка добавления: {}", e);
-                                }
+                KeyCode::Char('i') | KeyCode::Char('I') if app.state == AppState::ModpackUpdate && app.modpack_update.as_ref().is_some_and(|u| u.from_import) => {
+                    if let Some(update) = app.modpack_update.take() {
+                        app.state = AppState::InstanceList;
+                        list_state.select(Some(0));
+                        app.current_state = tr("status.importing").to_string();
+                        match app.import_instance(&update.archive).await {
+                            Ok(_) => app.current_state = trf("status.instances_imported", &[("count", &1)]),
+                            Err(e) => app.report_error(trf("status.import_failed", &[("error", &e)]), &e),
+                        }
+                    }
+                }
+                KeyCode::Char('o') | KeyCode::Char('O') if app.state == AppState::CrashReport => {
+                    if let Err(e) = app.open_crash_report() {
+                        app.report_error(trf("status.error", &[("error", &e)]), &e);
+                    }
+                }
+                KeyCode::Char('o') if app.state == AppState::AccountManager => {
+                    let username = format!("Player_{}", Utc::now().format("%H%M%S"));
+                    match app.add_offline_account(username.clone()) {
+                        Ok(_) => app.start_head_refresh(),
+                        Err(e) => {
+                            app.report_error(trf("status.add_failed", &[("error", &e)]), &e);
+                        }
+                    }
+                }
+                KeyCode::Char('m') | KeyCode::Char('M') if app.state == AppState::InstanceList => {
+                    if let Some(selected) = list_state.selected() {
+                        let instances = app.displayed_instances();
+                        if let Some(instance) = instances.get(selected) {
+                            if let Err(e) = app.launch_instance_safe_mode(instance.id) {
+                                app.report_error(trf("status.launch_failed", &[("error", &e)]), &e);
                             }
                         }
-                        _ => {}
                     }
                 }
-                KeyCode::Char('e') | KeyCode::Char('E') => {
-                    match app.state {
-                        AppState::InstanceList => {
-                            if let Some(selected) = list_state.selected() {
-                                let instances = app.instance_manager.list_instances();
-                                if let Some(instance) = instances.get(selected) {
-                                    let instance_id = instance.id;
-                                    let instance_name = instance.name.clone();
-                                    match app.start_editing_instance(instance_id) {
-                                        Ok(_) => {
-                                            app.current_state = format!("Редактирование экземпляра '{}'", instance_name);
-                                            list_state.select(Some(0));
-                                        },
-                                        Err(e) => {
-                                            app.current_state = format!("Ошибка: {}", e);
-                                        }
-                                    }
-                                }
+                KeyCode::Char('w') | KeyCode::Char('W') if app.state == AppState::InstanceList => {
+                    if let Some(selected) = list_state.selected() {
+                        let instances = app.displayed_instances();
+                        if let Some(instance) = instances.get(selected) {
+                            match app.toggle_dev_watch(instance.id) {
+                                Ok(true) => app.current_state = tr("status.dev_watch_enabled").to_string(),
+                                Ok(false) => app.current_state = tr("status.dev_watch_disabled").to_string(),
+                                Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
                             }
                         }
-                        _ => {}
                     }
                 }
-                KeyCode::Char('j') | KeyCode::Char('J') => {
-                    match app.state {
-                        AppState::Settings => {
-                            app.current_state = "Сканирование Java...".to_string();
-                            if let Err(e) = app.scan_java_installations().await {
-                                app.current_state = format!("Ошибка сканирования Java: {}", e);
+                KeyCode::Char('f') | KeyCode::Char('F') if app.state == AppState::InstanceList => {
+                    if let Some(selected) = list_state.selected() {
+                        let instances = app.displayed_instances();
+                        if let Some(instance) = instances.get(selected) {
+                            match app.create_shortcut(instance.id, ShortcutKind::platform_default()) {
+                                Ok(path) => app.current_state = trf("status.shortcut_created", &[("path", &path.display())]),
+                                Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                            }
+                        }
+                    }
+                }
+                KeyCode::Char('N') if app.state == AppState::InstanceList => {
+                    if let Some(selected) = list_state.selected() {
+                        let instances = app.displayed_instances();
+                        if let Some(instance) = instances.get(selected) {
+                            match app.open_modpack_update(instance.id) {
+                                Ok(()) => list_state.select(Some(0)),
+                                Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                            }
+                        }
+                    }
+                }
+                KeyCode::Char('r') | KeyCode::Char('R') if app.state == AppState::InstanceList => {
+                    if let Some(selected) = list_state.selected() {
+                        let instances = app.displayed_instances();
+                        if let Some(instance) = instances.get(selected) {
+                            if let Err(e) = app.relaunch_instance(instance.id) {
+                                app.report_error(trf("status.launch_failed", &[("error", &e)]), &e);
+                            }
+                        }
+                    }
+                }
+                KeyCode::Char('x') | KeyCode::Char('X') | KeyCode::Char('p') | KeyCode::Char('P') if app.state == AppState::InstanceList => {
+                    let mrpack = matches!(key.code, KeyCode::Char('p') | KeyCode::Char('P'));
+                    if let Some(selected) = list_state.selected() {
+                        let instance = app.displayed_instances().get(selected)
+                            .map(|instance| (instance.id, instance.name.clone()));
+                        if let Some((id, name)) = instance {
+                            app.current_state = trf("status.exporting", &[("name", &name)]);
+                            match app.export_instance(id, mrpack).await {
+                                Ok(path) => app.current_state = trf("status.exported", &[("path", &path.display())]),
+                                Err(e) => app.report_error(trf("status.export_failed", &[("error", &e)]), &e),
+                            }
+                        }
+                    }
+                }
+                KeyCode::Char('u') | KeyCode::Char('U') | KeyCode::Char('i') | KeyCode::Char('I') | KeyCode::Char('k') | KeyCode::Char('K') if app.state == AppState::InstanceList => {
+                    let link = match key.code {
+                        KeyCode::Char('u') | KeyCode::Char('U') => InstanceLink::Project,
+                        KeyCode::Char('i') | KeyCode::Char('I') => InstanceLink::Issues,
+                        _ => InstanceLink::Wiki,
+                    };
+                    if let Some(selected) = list_state.selected() {
+                        let instances = app.displayed_instances();
+                        if let Some(instance) = instances.get(selected) {
+                            if let Err(e) = app.open_instance_link(instance.id, link) {
+                                app.report_error(trf("status.error", &[("error", &e)]), &e);
+                            }
+                        }
+                    }
+                }
+                KeyCode::Char('O') if app.state == AppState::InstanceList => {
+                    app.current_state = tr("status.importing").to_string();
+                    match app.import_official_launcher(None) {
+                        Ok(summary) if summary.account_hints.is_empty() => app.current_state = trf("status.official_imported", &[
+                            ("count", &summary.instances),
+                            ("files", &summary.reused_files),
+                        ]),
+                        Ok(summary) => app.current_state = trf("status.official_imported_accounts", &[
+                            ("count", &summary.instances),
+                            ("files", &summary.reused_files),
+                            ("accounts", &summary.account_hints.join(", ")),
+                        ]),
+                        Err(e) => app.report_error(trf("status.import_failed", &[("error", &e)]), &e),
+                    }
+                }
+                KeyCode::Char('o') if app.state == AppState::InstanceList => {
+                    let title = trf("prompt.import_path", &[("path", &app.import_dir().display())]);
+                    prompt = Some((TextPrompt::ImportPath, TextInput::new(title, "")));
+                }
+                KeyCode::Char('c') if app.state == AppState::InstanceList => {
+                    if let Some(selected) = list_state.selected() {
+                        let instances = app.displayed_instances();
+                        if let Some(instance) = instances.get(selected) {
+                            let id = instance.id;
+                            app.open_launch_variants(id);
+                            list_state.select(Some(0));
+                        }
+                    }
+                }
+                KeyCode::Char('j') | KeyCode::Char('J') if app.state == AppState::InstanceList => {
+                    if let Some(selected) = list_state.selected() {
+                        let worlds = key.code == KeyCode::Char('j');
+                        let id = app.displayed_instances().get(selected).map(|instance| instance.id);
+                        if let Some(id) = id {
+                            if app.open_quick_play(id, worlds) > 0 {
+                                list_state.select(Some(0));
                             } else {
-                                let count = app.get_java_installations().len();
-                                app.current_state = format!("Найдено {} установок Java", count);
+                                app.current_state = tr(if worlds { "status.quick_play_no_worlds" } else { "status.quick_play_no_servers" }).to_string();
                             }
                         }
-                        _ => {}
                     }
                 }
-                KeyCode::Char('t') | KeyCode::Char('T') => {
-                    match app.state {
-                        AppState::Launcher => {
-                            app.toggle_version_mode();
+                KeyCode::Char('y') | KeyCode::Char('Y') if app.state == AppState::InstanceList => {
+                    if let Some(selected) = list_state.selected() {
+                        let instances = app.displayed_instances();
+                        if let Some(instance) = instances.get(selected) {
+                            let id = instance.id;
+                            app.open_clone_dialog(id);
                             list_state.select(Some(0));
                         }
-                        _ => {}
                     }
                 }
-                KeyCode::Char('c') | KeyCode::Char('C') => {
-                    match app.state {
-                        AppState::AccountManager => {
-                            if let Some(selected) = list_state.selected() {
-                                let accounts = app.auth_manager.list_accounts();
-                                if let Some(account) = accounts.get(selected) {
-                                    let account_id = account.id;
-                                    let new_name = format!("Player_{}", Utc::now().format("%H%M%S"));
-                                    match app.change_account_name(account_id, new_name.clone()) {
-                                        Ok(_) => {
-                                            app.current_state = format!("Ник изменен на: {}", new_name);
-                                        },
-                                        Err(e) => {
-                                            app.current_state = format!("Ошибка изменения ника: {}", e);
-                                        }
-                                    }
-                                }
+                KeyCode::Char('v') | KeyCode::Char('V') if app.state == AppState::InstanceList => {
+                    match app.open_backups() {
+                        Ok(()) => list_state.select(Some(0)),
+                        Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                    }
+                }
+                KeyCode::Char('b') | KeyCode::Char('B') if app.state == AppState::InstanceList => {
+                    if let Some(selected) = list_state.selected() {
+                        let instances = app.displayed_instances();
+                        if let Some(instance) = instances.get(selected) {
+                            if let Err(e) = app.start_mod_bisect(instance.id) {
+                                app.report_error(trf("status.error", &[("error", &e)]), &e);
                             }
                         }
-                        _ => {}
                     }
                 }
-                _ => {}
-            }
-        }
-
-        if app.should_quit {
-            break;
-        }
-    }
-
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
-
-    Ok(())
-}
-
-pub fn draw(f: &mut Frame, app: &mut App, list_state: &mut ListState) {
-    let main_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .margin(1)
-        .constraints([
-            Constraint::Ratio(1, 3),
-            Constraint::Ratio(2, 3),
-        ])
-        .split(f.size());
-
-    let left_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(0),
-            Constraint::Length(3),
-        ])
+                KeyCode::Char('m') | KeyCode::Char('M') if app.state == AppState::EditInstance => {
+                    if let Some(instance_id) = app.editing_instance_id {
+                        match app.open_mod_browser(instance_id) {
+                            Ok(()) => list_state.select(Some(0)),
+                            Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                        }
+                    }
+                }
+                KeyCode::Char('i') | KeyCode::Char('I') if app.state == AppState::EditInstance => {
+                    let current = app.get_editing_instance().and_then(|instance| instance.icon_glyph()).unwrap_or("").to_string();
+                    prompt = Some((TextPrompt::InstanceIcon, TextInput::new(tr("prompt.instance_icon"), current)));
+                }
+                KeyCode::Char('j') | KeyCode::Char('J') if app.state == AppState::EditInstance => {
+                    let current = app.get_editing_instance().and_then(|instance| instance.java_args.clone());
+                    app.java_args_input = Some(current.unwrap_or_default());
+                }
+                KeyCode::Char('p') | KeyCode::Char('P') if app.state == AppState::EditInstance => {
+                    if let Some(instance_id) = app.editing_instance_id {
+                        match app.open_pack_manager(instance_id) {
+                            Ok(()) => list_state.select(Some(0)),
+                            Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                        }
+                    }
+                }
+                KeyCode::Char(' ') if matches!(app.state, AppState::InstanceList | AppState::Launcher | AppState::ModList) => {
+                    if let Some(selected) = list_state.selected() {
+                        if app.toggle_mark(selected) && selected + 1 < list_len(&app) {
+                            list_state.select(Some(selected + 1));
+                        }
+                    }
+                }
+                KeyCode::Char('g') | KeyCode::Char('G') if app.state == AppState::InstanceList => {
+                    let current = list_state.selected()
+                        .and_then(|selected| app.displayed_instances().get(selected).and_then(|i| i.group.clone()))
+                        .filter(|_| app.marked.is_empty());
+                    app.group_input = Some(current.unwrap_or_default());
+                }
+                KeyCode::Char('+') | KeyCode::Char('-') if app.state == AppState::ModList => {
+                    let enable = key.code == KeyCode::Char('+');
+                    match app.set_marked_mods_enabled(list_state.selected(), enable) {
+                        Ok(count) if enable => app.current_state = trf("status.mods_enabled", &[("count", &count)]),
+                        Ok(count) => app.current_state = trf("status.mods_disabled", &[("count", &count)]),
+                        Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                    }
+                }
+                KeyCode::Char('o') | KeyCode::Char('O') if app.state == AppState::EditInstance => {
+                    if let Some(instance_id) = app.editing_instance_id {
+                        match app.open_installed_mods(instance_id) {
+                            Ok(()) => list_state.select(Some(0)),
+                            Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                        }
+                    }
+                }
+                KeyCode::Tab if app.state == AppState::PackManager => {
+                    match app.switch_pack_kind() {
+                        Ok(()) => list_state.select(Some(0)),
+                        Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                    }
+                }
+                KeyCode::Char('c') | KeyCode::Char('C') if app.state == AppState::ModList => {
+                    if let Some(instance_id) = app.installed_mods.as_ref().map(|(id, _)| *id) {
+                        app.current_state = tr("status.checking_dependencies").to_string();
+                        if let Err(e) = app.start_dependency_check(instance_id) {
+                            app.report_error(trf("status.error", &[("error", &e)]), &e);
+                        }
+                    }
+                }
+                KeyCode::Char('i') | KeyCode::Char('I') if app.state == AppState::ModList => {
+                    if let Some(instance_id) = app.installed_mods.as_ref().map(|(id, _)| *id) {
+                        app.current_state = tr("status.installing_dependencies").to_string();
+                        if let Err(e) = app.start_dependency_install(instance_id) {
+                            app.report_error(trf("status.error", &[("error", &e)]), &e);
+                        }
+                    }
+                }
+                KeyCode::Char('i') | KeyCode::Char('I') if app.state == AppState::PackManager => {
+                    if let Some(browser) = app.pack_browser.as_mut() {
+                        browser.path_input = Some(String::new());
+                    }
+                }
+                KeyCode::Char('u') if app.state == AppState::EditInstance => {
+                    if let Some(instance_id) = app.editing_instance_id {
+                        app.current_state = tr("status.checking_mod_updates").to_string();
+                        match app.check_mod_updates(instance_id).await {
+                            Ok(0) => app.current_state = tr("status.mods_up_to_date").to_string(),
+                            Ok(count) => app.current_state = trf("status.mod_updates_available", &[("count", &count)]),
+                            Err(e) => app.report_error(trf("status.mod_update_check_failed", &[("error", &e)]), &e),
+                        }
+                    }
+                }
+                KeyCode::Char('U') if app.state == AppState::EditInstance => {
+                    if let Some(instance_id) = app.editing_instance_id {
+                        app.current_state = tr("status.updating_mods").to_string();
+                        match app.apply_mod_updates(instance_id).await {
+                            Ok((applied, 0)) => app.current_state = trf("status.mods_updated", &[("count", &applied)]),
+                            Ok((applied, failed)) => app.current_state = trf("status.mods_updated_with_failures", &[("count", &applied), ("failed", &failed)]),
+                            Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                        }
+                    }
+                }
+                KeyCode::Char('t') | KeyCode::Char('T') if app.state == AppState::InstanceList => {
+                    if let Some(selected) = list_state.selected() {
+                        let instances = app.displayed_instances();
+                        if let Some(instance) = instances.get(selected) {
+                            let id = instance.id;
+                            if let Err(e) = app.open_console(id) {
+                                app.report_error(trf("status.error", &[("error", &e)]), &e);
+                            }
+                        }
+                    }
+                }
+                KeyCode::Char('i') | KeyCode::Char('/') if app.state == AppState::Console => {
+                    app.console_input = Some(String::new());
+                }
+                KeyCode::Char('f') | KeyCode::Char('F') if app.state == AppState::Console => {
+                    app.console_follow = !app.console_follow;
+                }
+                KeyCode::Char('/') if app.state == AppState::Logs => {
+                    app.log_view.editing = true;
+                }
+                KeyCode::Tab if app.state == AppState::Logs => {
+                    app.log_view.cycle_level();
+                }
+                KeyCode::Char('i') | KeyCode::Char('I') if app.state == AppState::Logs => {
+                    let entries = app.log_manager.get_entries();
+                    let names: Vec<String> = app.get_instances()
+                        .into_iter()
+                        .filter(|instance| entries.iter().any(|entry| entry.is_from_instance(&instance.name)))
+                        .map(|instance| instance.name.clone())
+                        .collect();
+                    app.log_view.cycle_instance(&names);
+                    list_state.select(Some(0));
+                }
+                KeyCode::Char('f') | KeyCode::Char('F') if app.state == AppState::Logs => {
+                    app.log_view.follow = !app.log_view.follow;
+                }
+                KeyCode::Char('/') if app.state == AppState::InstanceList => {
+                    app.instance_search_editing = true;
+                }
+                KeyCode::Char('/') if app.state == AppState::Launcher => {
+                    app.version_search_editing = true;
+                }
+                KeyCode::Char(c @ '1'..='5') if app.state == AppState::Launcher => {
+                    let kind = VersionKind::ALL[c as usize - '1' as usize];
+                    app.version_filter.toggle(kind);
+                    list_state.select(Some(0));
+                }
+                KeyCode::Char('x') | KeyCode::Char('X') if app.state == AppState::Launcher => {
+                    match app.toggle_experimental_versions() {
+                        Ok(channels) if channels.is_experimental() => {
+                            app.current_state = tr("status.experimental_shown").to_string();
+                        }
+                        Ok(_) => app.current_state = tr("status.experimental_hidden").to_string(),
+                        Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                    }
+                    list_state.select(Some(0));
+                }
+                KeyCode::Char('/') if app.state == AppState::LoaderVersions => {
+                    if let Some(picker) = app.loader_picker.as_mut() {
+                        picker.editing = true;
+                    }
+                }
+                KeyCode::Char('/') if app.state == AppState::ModBrowser => {
+                    if let Some(browser) = app.mod_browser.as_mut() {
+                        browser.editing = true;
+                    }
+                }
+                KeyCode::Left | KeyCode::Right if app.state == AppState::ModBrowser => {
+                    let target = app.mod_browser.as_ref().and_then(|b| {
+                        if key.code == KeyCode::Right {
+                            let next = b.offset + crate::modrinth::SEARCH_PAGE_SIZE;
+                            (next < b.total_hits).then_some(next)
+                        } else {
+                            b.offset.checked_sub(crate::modrinth::SEARCH_PAGE_SIZE)
+                        }
+                    });
+                    if let Some(offset) = target {
+                        match app.search_mod_browser(offset).await {
+                            Ok(()) => list_state.select(Some(0)),
+                            Err(e) => app.report_error(trf("status.search_failed", &[("error", &e)]), &e),
+                        }
+                    }
+                }
+                KeyCode::Char('y') | KeyCode::Char('Y') if app.state == AppState::ModBisect => {
+                    app.answer_bisect(true);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') if app.state == AppState::ModBisect => {
+                    app.answer_bisect(false);
+                }
+                KeyCode::Char('y') | KeyCode::Char('Y') if app.state == AppState::CommandApproval => {
+                    if let Err(e) = app.resolve_command_approval(true) {
+                        app.report_error(trf("status.launch_failed", &[("error", &e)]), &e);
+                    }
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') if app.state == AppState::CommandApproval => {
+                    if let Err(e) = app.resolve_command_approval(false) {
+                        app.report_error(trf("status.launch_failed", &[("error", &e)]), &e);
+                    }
+                }
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Char('n') | KeyCode::Char('N') if app.state == AppState::LaunchConflict => {
+                    let proceed = matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y'));
+                    if let Err(e) = app.resolve_launch_conflict(proceed) {
+                        app.report_error(trf("status.launch_failed", &[("error", &e)]), &e);
+                    }
+                }
+                KeyCode::Char('e') | KeyCode::Char('E') if app.state == AppState::InstanceList => {
+                    if let Some(selected) = list_state.selected() {
+                        let instances = app.displayed_instances();
+                        if let Some(instance) = instances.get(selected) {
+                            let instance_id = instance.id;
+                            let instance_name = instance.name.clone();
+                            match app.start_editing_instance(instance_id) {
+                                Ok(_) => {
+                                    app.current_state = trf("status.editing_instance", &[("name", &instance_name)]);
+                                    list_state.select(Some(0));
+                                },
+                                Err(e) => {
+                                    app.report_error(trf("status.error", &[("error", &e)]), &e);
+                                }
+                            }
+                        }
+                    }
+                }
+                KeyCode::Char('j') | KeyCode::Char('J') if app.state == AppState::Settings => {
+                    app.start_java_scan();
+                }
+                KeyCode::Char('t') | KeyCode::Char('T') if app.state == AppState::Launcher => {
+                    app.toggle_version_mode();
+                    list_state.select(Some(0));
+                }
+                KeyCode::Char('v') | KeyCode::Char('V') if app.state == AppState::Launcher && app.show_installed_only => {
+                    let version_id = list_state.selected()
+                        .and_then(|selected| app.get_displayed_versions().get(selected).map(|version| version.id.clone()));
+                    if let Some(version_id) = version_id {
+                        match app.start_version_verify(&version_id) {
+                            Ok(()) => app.current_state = trf("status.verify_started", &[("version", &version_id)]),
+                            Err(e) => app.report_error(trf("status.error", &[("error", &e)]), &e),
+                        }
+                    }
+                }
+                KeyCode::Char('c') | KeyCode::Char('C') if app.state == AppState::AccountManager => {
+                    if let Some(selected) = list_state.selected() {
+                        let accounts = app.auth_manager.list_accounts();
+                        if let Some(account) = accounts.get(selected) {
+                            let title = tr("prompt.new_nickname");
+                            prompt = Some((TextPrompt::AccountName(account.id), TextInput::new(title, account.display_name.clone())));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if app.should_quit {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+// Lines are padded to one width, so centering keeps the art's shape.
+fn art_lines(app: &App) -> Vec<String> {
+    let animate = app.animations_enabled() && app.get_settings().ui.rotate_art;
+    let art = app.motd.art();
+    let art_width = art.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+    art.into_iter()
+        .map(|line| {
+            let line = if !animate || !line.trim_start().starts_with('░') {
+                line.to_string()
+            } else {
+                let indent = line.len() - line.trim_start().len();
+                let width = line.chars().count() - indent;
+                let shift = (app.tick as usize) % 4;
+                let pattern: String = (0..width)
+                    .map(|i| if (i + shift).is_multiple_of(4) { '▒' } else { '░' })
+                    .collect();
+                format!("{}{}", &line[..indent], pattern)
+            };
+            format!("{:<width$}", line, width = art_width)
+        })
+        .collect()
+}
+
+pub fn draw(f: &mut Frame, app: &mut App, list_state: &mut ListState) -> usize {
+    let main_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .margin(1)
+        .constraints([
+            Constraint::Ratio(1, 3),
+            Constraint::Ratio(2, 3),
+        ])
+        .split(f.size());
+
+    let left_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
         .split(main_chunks[0]);
 
     if app.show_logs {
         draw_logs_panel(f, app, left_chunks[0]);
         
-        let toggle_hint = Paragraph::new("L: Переключить логи")
+        let toggle_hint = Paragraph::new(tr("main.hide_logs_hint"))
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::NONE));
         f.render_widget(toggle_hint, left_chunks[1]);
     } else {
-    let art = Paragraph::new(MANGO_ART.join("\n"))
+    // Running tasks with stages take the space under the art.
+    let mut art_area = left_chunks[0];
+    if app.tasks.progress().has_stages() {
+        let tree = ProgressTreeView::new(app.tasks.progress());
+        let height = tree.height().min(art_area.height / 2);
+        let tree_area = Rect { y: art_area.bottom() - height, height, ..art_area };
+        art_area.height -= height;
+        tree.draw(f, tree_area);
+    }
+    let art = Paragraph::new(art_lines(app).join("\n"))
         .style(Style::default().fg(Color::Yellow))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::NONE));
-    f.render_widget(art, left_chunks[0]);
+    f.render_widget(art, art_area);
 
-        let motd_with_toggle = format!("{}\n\nL: Показать логи", app.current_motd);
+        let motd_with_toggle = format!("{}\n\n{}", app.motd.text(), tr("main.show_logs_hint"));
         let motd = Paragraph::new(motd_with_toggle)
         .style(Style::default().fg(Color::Cyan))
         .alignment(Alignment::Center)
@@ -624,6 +1966,7 @@ pub fn draw(f: &mut Frame, app: &mut App, list_state: &mut ListState) {
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Min(0),
+            Constraint::Length(1),
             Constraint::Length(3),
         ])
         .split(main_chunks[1]);
@@ -635,331 +1978,744 @@ pub fn draw(f: &mut Frame, app: &mut App, list_state: &mut ListState) {
         AppState::Launcher => draw_launcher(f, app, right_chunks[0], list_state),
         AppState::AccountManager => draw_account_manager(f, app, right_chunks[0], list_state),
         AppState::EditInstance => draw_edit_instance(f, app, right_chunks[0], list_state),
+        AppState::CrashReport => draw_crash_report(f, app, right_chunks[0]),
+        AppState::ModBisect => draw_mod_bisect(f, app, right_chunks[0]),
+        AppState::ModBrowser => draw_mod_browser(f, app, right_chunks[0], list_state),
+        AppState::LoaderVersions => draw_loader_versions(f, app, right_chunks[0], list_state),
+        AppState::Java => draw_java(f, app, right_chunks[0], list_state),
+        AppState::CommandApproval => draw_command_approval(f, app, right_chunks[0]),
+        AppState::AccountPicker => draw_account_picker(f, app, right_chunks[0], list_state),
+        AppState::LaunchConflict => draw_launch_conflict(f, app, right_chunks[0]),
+        AppState::PackManager => draw_pack_manager(f, app, right_chunks[0], list_state),
+        AppState::ModList => draw_mod_list(f, app, right_chunks[0], list_state),
+        AppState::Backups => draw_backups(f, app, right_chunks[0], list_state),
+        AppState::Storage => draw_storage(f, app, right_chunks[0], list_state),
+        AppState::LaunchVariants => draw_launch_variants(f, app, right_chunks[0], list_state),
+        AppState::QuickPlay => draw_quick_play(f, app, right_chunks[0], list_state),
+        AppState::CloneInstance => draw_clone_instance(f, app, right_chunks[0], list_state),
+        AppState::ModpackUpdate => draw_modpack_update(f, app, right_chunks[0], list_state),
+        AppState::Logs => draw_logs_view(f, app, right_chunks[0], list_state),
+        AppState::Console => draw_console(f, app, right_chunks[0], list_state),
+        AppState::Wizard => draw_wizard(f, app, right_chunks[0], list_state),
+        AppState::News => draw_news(f, app, right_chunks[0], list_state),
+        AppState::SystemInfo => draw_system_info(f, app, right_chunks[0]),
     }
 
+    let controls = controls_text(app);
+
+    // A fresh error takes over the footer until the next key press.
+    let footer = match &app.error_hint {
+        Some(hint) => Paragraph::new(Line::from(vec![
+            Span::styled(format!("⚠ {} ", hint.hint), Style::default().fg(Color::Red)),
+            Span::styled(format!("→ {}", hint.action), Style::default().fg(Color::Yellow)),
+        ])),
+        None => {
+            let update = match &app.update {
+                Some(update) if app.update_installed => trf("status.update_ready", &[("version", &update.version)]),
+                Some(update) => trf("status.update_available", &[("version", &update.version)]),
+                None => String::new(),
+            };
+            Paragraph::new(Line::from(vec![
+                connectivity_span(app),
+                Span::styled(update, Style::default().fg(Color::Green)),
+                Span::styled(controls, Style::default().fg(Color::White)),
+            ]))
+        }
+    };
+    let footer = footer
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, right_chunks[2]);
+
+    let frame = app.animations_enabled().then_some(app.tick);
+    StatusStrip::new(app.tasks.progress(), &app.current_state, frame)
+        .badge(app.offline_mode.then(|| tr("status.offline_badge")))
+        .draw(f, right_chunks[1]);
+
+    right_chunks[0].height.saturating_sub(2).max(1) as usize
+}
+
+// The keys shown in the footer for the current screen, without the marked
+// count, which is also what the help overlay lists.
+fn state_controls(app: &App) -> String {
     let controls = match app.state {
-        AppState::MainMenu => {
-            if app.language == Language::Russian {
-                "↑↓: Навигация | Enter: Выбрать | Esc: Выход"
+        AppState::MainMenu => tr("controls.main_menu"),
+        AppState::InstanceList => tr("controls.instance_list"),
+        AppState::Settings => tr("controls.settings"),
+        AppState::Launcher => {
+            if app.show_installed_only {
+                tr("controls.launcher_installed")
             } else {
-                "↑↓: Navigate | Enter: Select | Esc: Exit"
+                tr("controls.launcher")
             }
         }
-        AppState::InstanceList => {
-            if app.language == Language::Russian {
-                "↑↓: Навигация | Enter: Запустить | E: Изменить | N: Создать | D: Удалить | Esc: Назад"
+        AppState::AccountManager => tr("controls.accounts"),
+        AppState::EditInstance => tr("controls.edit_instance"),
+        AppState::CrashReport => tr("controls.crash_report"),
+        AppState::ModList => tr("controls.mod_list"),
+        AppState::PackManager => {
+            let typing = app.pack_browser.as_ref().is_some_and(|b| b.path_input.is_some());
+            if typing {
+                tr("controls.pack_path_input")
             } else {
-                "↑↓: Navigate | Enter: Launch | E: Edit | N: Create | D: Delete | Esc: Back"
+                tr("controls.pack_manager")
             }
         }
-        AppState::Settings => {
-            if app.language == Language::Russian {
-                "↑↓: Навигация | Enter: Изменить | J: Найти Java | Esc: Назад"
+        AppState::Backups => tr("controls.backups"),
+        AppState::Console => {
+            if app.console_input.is_some() {
+                tr("controls.console_input")
             } else {
-                "↑↓: Navigate | Enter: Change | J: Find Java | Esc: Back"
+                tr("controls.console")
             }
         }
-        AppState::Launcher => {
-            if app.language == Language::Russian {
-                if app.show_installed_only {
-                    "↑↓: Навигация | T: Все версии | R: Обновить | F: Принуд. обн. | Esc: Назад"
-                } else {
-                    "↑↓: Навигация | Enter: Скачать | T: Скачанные | R: Обновить | F: Принуд. | Esc: Назад"
-                }
+        AppState::Logs => tr("controls.logs"),
+        AppState::LaunchVariants => tr("controls.launch_variants"),
+        AppState::QuickPlay => tr("controls.quick_play"),
+        AppState::CloneInstance => tr("controls.clone_instance"),
+        AppState::ModpackUpdate if app.modpack_update.as_ref().is_some_and(|u| u.from_import) => tr("controls.modpack_import"),
+        AppState::ModpackUpdate => tr("controls.modpack_update"),
+        AppState::Wizard => tr("controls.wizard"),
+        AppState::News => tr("controls.news"),
+        AppState::SystemInfo => tr("controls.system_info"),
+        AppState::Storage => tr("controls.storage"),
+        AppState::LaunchConflict => tr("controls.launch_conflict"),
+        AppState::CommandApproval => tr("controls.command_approval"),
+        AppState::AccountPicker => tr("controls.account_picker"),
+        AppState::ModBisect => tr("controls.mod_bisect"),
+        AppState::Java => tr("controls.java"),
+        AppState::LoaderVersions => {
+            let editing = app.loader_picker.as_ref().is_some_and(|p| p.editing);
+            if editing {
+                tr("controls.loader_versions_query")
             } else {
-                if app.show_installed_only {
-                    "↑↓: Navigate | T: All Versions | R: Refresh | F: Force | Esc: Back"
-                } else {
-                    "↑↓: Navigate | Enter: Download | T: Downloaded | R: Refresh | F: Force | Esc: Back"
-                }
+                tr("controls.loader_versions")
             }
         }
-        AppState::AccountManager => {
-            if app.language == Language::Russian {
-                "↑↓: Навигация | Enter: Выбрать | S: Установить | C: Изменить ник | O: Добавить | D: Удалить | Esc: Назад"
+        AppState::ModBrowser => {
+            let editing = app.mod_browser.as_ref().is_some_and(|b| b.editing);
+            if editing {
+                tr("controls.mod_browser_query")
             } else {
-                "↑↓: Navigate | Enter: Select | S: Set Default | C: Change Name | O: Add Offline | D: Delete | Esc: Back"
+                tr("controls.mod_browser")
             }
         }
-        AppState::EditInstance => {
-            if app.language == Language::Russian {
-                "↑↓: Навигация | Enter: Изменить поле | S: Сохранить | Esc: Отмена"
-            } else {
-                "↑↓: Navigate | Enter: Cycle Field | S: Save | Esc: Cancel"
-            }
+    };
+
+    if app.state == AppState::EditInstance && app.java_args_input.is_some() {
+        tr("controls.java_args_input").to_string()
+    } else if app.state == AppState::InstanceList && app.group_input.is_some() {
+        tr("controls.group_input").to_string()
+    } else {
+        controls.to_string()
+    }
+}
+
+fn controls_text(app: &App) -> String {
+    let controls = state_controls(app);
+    let controls = if help_available(app) {
+        trf("help.footer", &[("controls", &controls)])
+    } else {
+        controls
+    };
+    if app.marked.is_empty() {
+        controls
+    } else {
+        trf("controls.marked", &[("count", &app.marked.len()), ("controls", &controls)])
+    }
+}
+
+// '?' is typed into a field while one is being edited, and the wizard takes
+// all keys itself.
+fn help_available(app: &App) -> bool {
+    let typing = match app.state {
+        AppState::Wizard => true,
+        AppState::ModBrowser => app.mod_browser.as_ref().is_some_and(|b| b.editing),
+        AppState::LoaderVersions => app.loader_picker.as_ref().is_some_and(|p| p.editing),
+        AppState::Console => app.console_input.is_some(),
+        AppState::Logs => app.log_view.editing,
+        AppState::Launcher => app.version_search_editing,
+        AppState::InstanceList => app.instance_search_editing || app.group_input.is_some(),
+        AppState::EditInstance => app.java_args_input.is_some(),
+        AppState::PackManager => app.pack_browser.as_ref().is_some_and(|b| b.path_input.is_some()),
+        _ => false,
+    };
+    !typing
+}
+
+/// One `Key: Action` entry of a controls line. Entries without a key, like
+/// "Type a command", keep their text as the action.
+struct Binding<'a> {
+    keys: Option<&'a str>,
+    action: &'a str,
+}
+
+fn bindings(controls: &str) -> Vec<Binding<'_>> {
+    controls
+        .split(" | ")
+        .map(|entry| match entry.split_once(": ") {
+            Some((keys, action)) => Binding { keys: Some(keys), action },
+            None => Binding { keys: None, action: entry },
+        })
+        .collect()
+}
+
+// Every key of the current screen and the ones that work everywhere, in a
+// popup over the whole terminal.
+fn draw_help(f: &mut Frame, app: &App) {
+    let controls = state_controls(app);
+    let global = tr("help.global");
+    let sections = [(tr("help.current"), bindings(&controls)), (tr("help.everywhere"), bindings(global))];
+
+    let key_width = sections.iter()
+        .flat_map(|(_, bindings)| bindings.iter())
+        .filter_map(|binding| binding.keys.map(|keys| keys.chars().count()))
+        .max()
+        .unwrap_or(0);
+    let mut lines = Vec::new();
+    for (title, bindings) in &sections {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
         }
+        lines.push(Line::from(Span::styled(*title, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))));
+        for binding in bindings {
+            lines.push(match binding.keys {
+                Some(keys) => Line::from(vec![
+                    Span::styled(format!("  {:>width$}  ", keys, width = key_width), Style::default().fg(Color::Yellow)),
+                    Span::raw(binding.action),
+                ]),
+                None => Line::from(Span::styled(format!("  {}", binding.action), Style::default().fg(Color::Gray))),
+            });
+        }
+    }
+
+    let area = f.size();
+    let content_width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+    let width = (content_width + 4).min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
     };
+    f.render_widget(ratatui::widgets::Clear, popup);
+    f.render_widget(
+        Paragraph::new(lines).block(Block::default()
+            .title(tr("help.title"))
+            .title(Title::from(tr("help.close")).position(Position::Bottom).alignment(Alignment::Right))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))),
+        popup,
+    );
+}
 
-    let footer = Paragraph::new(controls)
-        .style(Style::default().fg(Color::White))
-        .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL));
-    f.render_widget(footer, right_chunks[1]);
+fn connectivity_span(app: &App) -> Span<'static> {
+    let (key, color) = if app.tasks.is_running(&TaskKind::CheckConnectivity) {
+        ("status.connectivity_checking", Color::Yellow)
+    } else {
+        match app.connectivity {
+            Connectivity::Online => ("status.connectivity_online", Color::Green),
+            Connectivity::Offline => ("status.connectivity_offline", Color::Red),
+            Connectivity::Unknown => return Span::raw(""),
+        }
+    };
+    Span::styled(format!("{}  ", tr(key)), Style::default().fg(color))
 }
 
-fn draw_main_menu(f: &mut Frame, app: &mut App, area: Rect, list_state: &mut ListState) {
-    let menu_items = if app.language == Language::Russian {
-        vec![
-            "Экземпляры игры",
-            "Настройки",
-            "Лаунчер",
-            "Аккаунты",
-        ]
+fn draw_main_menu(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let recent = app.recent_instances();
+    let area = if recent.is_empty() {
+        area
     } else {
-        vec![
-            "Game Instances",
-            "Settings",
-            "Launcher",
-            "Accounts",
-        ]
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(recent.len() as u16 + 2)])
+            .split(area);
+        draw_recent_instances(f, &recent, chunks[1]);
+        chunks[0]
     };
 
+    let menu_items = [
+        tr("menu.instances"),
+        tr("menu.settings"),
+        tr("menu.launcher"),
+        tr("menu.accounts"),
+        tr("menu.java"),
+        tr("menu.storage"),
+        tr("menu.logs"),
+        tr("menu.news"),
+        tr("menu.system_info"),
+    ];
+
     let items: Vec<ListItem> = menu_items
         .iter()
         .map(|&i| ListItem::new(i.to_string()))
         .collect();
 
     let menu = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title(if app.language == Language::Russian {
-            "Главное меню"
-        } else {
-            "Main Menu"
-        }))
+        .block(Block::default().borders(Borders::ALL).title(tr("menu.title")))
         .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
         .highlight_symbol("> ");
 
     f.render_stateful_widget(menu, area, list_state);
 }
 
+fn draw_recent_instances(f: &mut Frame, recent: &[&Instance], area: Rect) {
+    let lines: Vec<Line> = recent.iter().enumerate().map(|(index, instance)| {
+        let version = match &instance.mod_loader {
+            Some(loader) => format!("{} {:?}", instance.minecraft_version, loader),
+            None => instance.minecraft_version.clone(),
+        };
+        let played = instance.last_played
+            .map(|time| time.with_timezone(&chrono::Local).format("%d.%m %H:%M").to_string())
+            .unwrap_or_default();
+        Line::from(vec![
+            Span::styled(format!("{}  ", index + 1), Style::default().fg(Color::Yellow)),
+            Span::styled(instance.name.clone(), Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(format!("  {}  {}", version, played), Style::default().fg(Color::Gray)),
+        ])
+    }).collect();
+    let block = Block::default().borders(Borders::ALL).title(tr("menu.recent"));
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_instance_filter(f: &mut Frame, app: &App, area: Rect) {
+    let style = if app.instance_search_editing {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    let cursor = if app.instance_search_editing { "█" } else { "" };
+    let line = Line::from(vec![
+        Span::styled(format!("/ {}{}", app.instance_query, cursor), style),
+        Span::raw("   "),
+        Span::styled(trf("instances.sorted_by", &[("sort", &app.instance_sort().label())]), Style::default().fg(Color::Cyan)),
+    ]);
+    let filter = Paragraph::new(line)
+        .block(Block::default().title(tr("instances.search_title")).borders(Borders::ALL));
+    f.render_widget(filter, area);
+}
+
 fn draw_instance_list(f: &mut Frame, app: &mut App, area: Rect, list_state: &mut ListState) {
-    let instances = app.instance_manager.list_instances();
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+    draw_instance_filter(f, app, layout[0]);
+    let area = layout[1];
+    let instances = app.displayed_instances();
     
     if instances.is_empty() {
-        let empty_message = if app.language == Language::Russian {
-            "Нет экземпляров игры.\nНажмите 'N' для создания нового экземпляра."
+        let empty_message = if app.instance_query.trim().is_empty() {
+            tr("instances.empty")
         } else {
-            "No game instances.\nPress 'N' to create a new instance."
+            tr("instances.no_matches")
         };
 
         let empty_paragraph = Paragraph::new(empty_message)
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center)
             .block(Block::default()
-                .title(if app.language == Language::Russian {
-                    "Экземпляры игры"
-            } else {
-                    "Game Instances"
-                })
+                .title(tr("instances.title"))
                 .borders(Borders::ALL));
 
         f.render_widget(empty_paragraph, area);
     } else {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(area);
         let items: Vec<ListItem> = instances
             .iter()
             .map(|instance| {
-                ListItem::new(format!("{} (v{})", instance.name, instance.minecraft_version))
-                    .style(Style::default().fg(Color::White))
+                let dev = match (instance.dev_watch, app.dev_changes.get(&instance.id)) {
+                    (true, Some(changes)) => trf("instances.dev_changes", &[("count", changes)]),
+                    (true, None) => " [DEV]".to_string(),
+                    (false, _) => String::new(),
+                };
+                let mark = if app.marked.contains(&instance.id.to_string()) { "● " } else { "" };
+                let demo = if instance.demo_mode { tr("instances.demo_marker") } else { "" };
+                let icon = instance.icon_glyph().map(|icon| format!("{} ", icon)).unwrap_or_default();
+                let running = app.launch_manager.is_running(instance.id);
+                let marker = if running { tr("instances.running_marker") } else { "" };
+                ListItem::new(format!("{}{}{} (v{}){}{}{}", mark, icon, instance.name, instance.minecraft_version, demo, dev, marker))
+                    .style(Style::default().fg(if running { Color::Green } else { Color::White }))
         })
         .collect();
 
         let instances_list = List::new(items)
             .block(Block::default()
-                .title(if app.language == Language::Russian {
-                    format!("Экземпляры игры ({})", instances.len())
-            } else {
-                    format!("Game Instances ({})", instances.len())
-                })
+                .title(trf("instances.title_count", &[("count", &instances.len())]))
                 .borders(Borders::ALL))
             .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
             .highlight_symbol("> ");
 
-        f.render_stateful_widget(instances_list, area, list_state);
+        f.render_stateful_widget(instances_list, columns[0], list_state);
+
+        let selected = list_state.selected().and_then(|i| instances.get(i)).copied();
+        if let Some(instance) = selected {
+            draw_instance_details(f, app, instance, columns[1]);
+        }
     }
 }
 
-fn draw_settings(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
-    let settings_items = if app.language == Language::Russian {
-        vec![
-            format!("Язык: {}", match app.language {
-                Language::Russian => "Русский",
-                Language::English => "English",
-            }),
-            format!("Статус: {}", app.current_state),
-            format!("Память: {}MB - {}MB", 
-                app.get_settings().java.memory_min,
-                app.get_settings().java.memory_max
-            ),
-            format!("Java директория: {}", 
-                app.get_settings().general.java_directory.display()
-            ),
-            format!("Директория экземпляров: {}", 
-                app.get_settings().general.instances_directory.display()
-            ),
-            format!("Потоки загрузки: {}", 
-                app.get_settings().network.max_concurrent_downloads
-            ),
-            format!("Сохранение логов: {}", 
-                if app.get_settings().advanced.save_logs_to_file { "Включено" } else { "Отключено" }
-            ),
-            format!("Директория логов: {}", 
-                app.get_settings().advanced.logs_directory.display()
-            ),
-        ]
-            } else {
-        vec![
-            format!("Language: {}", match app.language {
-                Language::Russian => "Русский",
-                Language::English => "English",
-            }),
-            format!("Status: {}", app.current_state),
-            format!("Memory: {}MB - {}MB", 
-                app.get_settings().java.memory_min,
-                app.get_settings().java.memory_max
-            ),
-            format!("Java directory: {}", 
-                app.get_settings().general.java_directory.display()
-            ),
-            format!("Instances directory: {}", 
-                app.get_settings().general.instances_directory.display()
-            ),
-            format!("Download threads: {}", 
-                app.get_settings().network.max_concurrent_downloads
-            ),
-            format!("Save logs: {}", 
-                if app.get_settings().advanced.save_logs_to_file { "Enabled" } else { "Disabled" }
-            ),
-            format!("Logs directory: {}", 
-                app.get_settings().advanced.logs_directory.display()
-            ),
-        ]
+fn draw_instance_details(f: &mut Frame, app: &App, instance: &Instance, area: Rect) {
+    let last_played = match instance.last_played {
+        Some(time) => time.with_timezone(&chrono::Local).format("%d.%m.%Y %H:%M").to_string(),
+        None => tr("instances.never_played").to_string(),
+    };
+    let loader = match &instance.mod_loader {
+        Some(loader) => format!("{:?} {}", loader, instance.mod_loader_version.as_deref().unwrap_or("latest")),
+        None => tr("instances.vanilla").to_string(),
     };
+    let disk = app.disk_usage.instance_size(instance.id)
+        .map(crate::utils::format_size)
+        .unwrap_or_else(|| "…".to_string());
+    let java = match app.instance_java(instance) {
+        Some(java) if instance.java_path.is_some() => java.version.clone(),
+        Some(java) => trf("instances.java_default", &[("version", &java.version)]),
+        None => tr("instances.java_missing").to_string(),
+    };
+
+    let mut rows = vec![
+        ("instances.detail_version", instance.minecraft_version.clone()),
+        ("instances.detail_loader", loader),
+        ("instances.detail_mods", app.instance_mod_count(instance.id).to_string()),
+        ("instances.detail_last_played", last_played),
+        ("instances.detail_play_time", crate::utils::format_duration(instance.play_time)),
+        ("instances.detail_disk", disk),
+        ("instances.detail_java", java),
+    ];
+    if let Some(group) = &instance.group {
+        rows.push(("instances.detail_group", group.clone()));
+    }
+    if let Some(modpack) = &instance.modpack {
+        rows.push(("instances.detail_modpack", format!("{} {} ({})", modpack.name, modpack.display_version(), modpack.format.label())));
+    }
+
+    let mut lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(key, value)| Line::from(vec![
+            Span::styled(format!("{:<18}", tr(key)), Style::default().fg(Color::Cyan)),
+            Span::raw(value),
+        ]))
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(tr("instances.actions"), Style::default().add_modifier(Modifier::BOLD))));
+    let mut actions = vec![
+        ("Enter", "instances.action_launch"),
+        ("M", "instances.action_safe_mode"),
+        ("E", "instances.action_edit"),
+        ("T", "instances.action_console"),
+        ("V", "instances.action_backups"),
+        ("X/P", "instances.action_export"),
+    ];
+    if instance.modpack.is_some() {
+        actions.push(("Shift+N", "instances.action_update_modpack"));
+    }
+    for (key, label) in actions {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<8}", key), Style::default().fg(Color::Yellow)),
+            Span::raw(tr(label)),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default()
+            .title(match instance.icon_glyph() {
+                Some(icon) => format!("{} {}", icon, instance.name),
+                None => instance.name.clone(),
+            })
+            .borders(Borders::ALL));
+    f.render_widget(paragraph, area);
+}
 
-    let items: Vec<ListItem> = settings_items
+fn draw_settings(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let settings = app.get_settings();
+    let items: Vec<ListItem> = SettingField::ALL
         .iter()
-        .map(|item| {
-            ListItem::new(item.clone())
-                .style(Style::default().fg(Color::White))
+        .map(|field| {
+            let line = Line::from(vec![
+                Span::styled(format!("{:<10} ", tr(section_key(field.section()))), Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("{}: ", tr(field.label_key())), Style::default().fg(Color::White)),
+                Span::styled(field.display(settings), Style::default().fg(Color::Cyan)),
+            ]);
+            ListItem::new(line)
         })
         .collect();
 
-    let settings_list = List::new(items)
+    let list = List::new(items)
         .block(Block::default()
-            .title(if app.language == Language::Russian {
-                "Настройки"
-        } else {
-                "Settings"
-            })
+            .title(tr("settings.title"))
             .borders(Borders::ALL))
         .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
         .highlight_symbol("> ");
 
-    f.render_stateful_widget(settings_list, area, list_state);
+    f.render_stateful_widget(list, area, list_state);
 }
 
-fn draw_launcher(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
-    let versions = app.get_displayed_versions();
-    
-    let chunks = Layout::default()
+fn draw_wizard(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let Some(wizard) = app.wizard.as_ref() else {
+        return;
+    };
+    let settings = app.get_settings();
+    let layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Min(0),
-            Constraint::Length(3),
-        ])
+        .constraints([Constraint::Length(6), Constraint::Min(0)])
         .split(area);
 
-    if versions.is_empty() {
-        let empty_message = if app.show_installed_only {
-            if app.language == Language::Russian {
-                "Нет скачанных версий.\nНажмите 'T' для переключения или 'R' для обновления списка."
+    let title = trf("wizard.title", &[
+        ("step", &(wizard.step.index() + 1)),
+        ("total", &WizardStep::ALL.len()),
+        ("name", &wizard.step.title()),
+    ]);
+    let mut hint = wizard.step.hint().to_string();
+    match wizard.step {
+        WizardStep::Java => {
+            let status = if app.java_ready.is_pending() {
+                tr("edit.java_scanning").to_string()
             } else {
-                "No downloaded versions.\nPress 'T' to toggle or 'R' to refresh list."
+                plural("plural.java_found", app.get_java_installations().len() as u64, &[])
+            };
+            hint = format!("{}\n{}", hint, status);
+        }
+        WizardStep::Account => {
+            if let Some(account) = &wizard.account {
+                hint = format!("{}\n{}", hint, trf("wizard.account_added", &[("name", account)]));
             }
-        } else {
-            if app.language == Language::Russian {
-                "Список версий пуст.\nНажмите 'R' для обновления."
-            } else {
-                "Version list is empty.\nPress 'R' to refresh."
+        }
+        WizardStep::Instance => {
+            if let Some(instance) = &wizard.instance {
+                hint = format!("{}\n{}", hint, trf("wizard.instance_created", &[("name", instance)]));
             }
-        };
+        }
+        WizardStep::Finish => {
+            let summary = trf("wizard.summary", &[
+                ("language", &SettingField::Language.display(settings)),
+                ("instances", &SettingField::InstancesDirectory.display(settings)),
+                ("memory_min", &settings.java.memory_min),
+                ("memory_max", &settings.java.memory_max),
+                ("account", &wizard.account.as_deref().unwrap_or(tr("common.none"))),
+                ("instance", &wizard.instance.as_deref().unwrap_or(tr("common.none"))),
+            ]);
+            hint = format!("{}\n{}", hint, summary);
+        }
+        _ => {}
+    }
+    let header = Paragraph::new(hint)
+        .style(Style::default().fg(Color::Cyan))
+        .wrap(Wrap { trim: true })
+        .block(Block::default().title(title).borders(Borders::ALL));
+    f.render_widget(header, layout[0]);
 
-        let empty_paragraph = Paragraph::new(empty_message)
+    let items: Vec<ListItem> = wizard.step.items()
+        .iter()
+        .map(|item| {
+            let line = match item {
+                WizardItem::Language(language) => {
+                    let marker = if settings.general.language == *language { "● " } else { "○ " };
+                    let name = match language {
+                        Language::Russian => "Русский",
+                        Language::English => "English",
+                    };
+                    Line::from(format!("{}{}", marker, name))
+                }
+                WizardItem::Setting(field) => Line::from(vec![
+                    Span::styled(format!("{}: ", tr(field.label_key())), Style::default().fg(Color::White)),
+                    Span::styled(field.display(settings), Style::default().fg(Color::Cyan)),
+                ]),
+                WizardItem::DataDir => {
+                    let key = match crate::utils::data_dir_source() {
+                        DataDirSource::Flag | DataDirSource::Environment => "wizard.data_dir_fixed",
+                        _ => "wizard.data_dir",
+                    };
+                    Line::from(trf(key, &[("path", &app.data_dir.display())]))
+                }
+                WizardItem::Portable => {
+                    let portable = crate::utils::data_dir_source() == DataDirSource::Portable;
+                    Line::from(format!("{}{}", if portable { "● " } else { "○ " }, tr("wizard.portable")))
+                }
+                WizardItem::ScanJava => Line::from(tr("wizard.scan_java")),
+                WizardItem::DownloadJava => {
+                    let version = app.wizard_java_major();
+                    let present = app.get_java_installations().values().any(|java| java.major_version() == Some(u32::from(version)));
+                    Line::from(trf(if present { "wizard.java_present" } else { "wizard.download_java" }, &[("version", &version)]))
+                }
+                WizardItem::AddOfflineAccount => Line::from(tr("wizard.add_account")),
+                WizardItem::CreateInstance => Line::from(tr("wizard.create_instance")),
+                WizardItem::Continue => Line::from(Span::styled(tr("wizard.continue"), Style::default().fg(Color::Green))),
+                WizardItem::Finish => Line::from(Span::styled(tr("wizard.finish"), Style::default().fg(Color::Green))),
+            };
+            ListItem::new(line)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, layout[1], list_state);
+}
+
+fn draw_news(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(8)])
+        .split(area);
+
+    if app.news.is_empty() {
+        let message = match &app.news_ready {
+            Readiness::Pending => tr("news.loading").to_string(),
+            Readiness::Failed(e) => trf("news.failed", &[("error", e)]),
+            Readiness::Ready => tr("news.empty").to_string(),
+        };
+        let empty = Paragraph::new(message)
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center)
-            .block(Block::default()
-                .title(if app.language == Language::Russian {
-                    if app.show_installed_only {
-                        "Скачанные версии Minecraft"
-                    } else {
-                        "Версии Minecraft"
-                    }
-                } else {
-                    if app.show_installed_only {
-                        "Downloaded Minecraft Versions"
-                    } else {
-                        "Minecraft Versions"
-                    }
-                })
-                .borders(Borders::ALL));
-
+            .block(Block::default().title(tr("news.title")).borders(Borders::ALL));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app.news
+        .iter()
+        .map(|entry| {
+            let color = if entry.kind == "release" { Color::Yellow } else { Color::Cyan };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{}  ", entry.date_label()), Style::default().fg(Color::DarkGray)),
+                Span::styled(entry.title.clone(), Style::default().fg(color)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().title(tr("news.title")).borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, layout[0], list_state);
+
+    let selected = list_state.selected().and_then(|index| app.news.get(index));
+    let (title, summary) = match selected {
+        Some(entry) => (
+            format!("{} ({}, {})", entry.version, entry.kind, entry.date_label()),
+            entry.summary(),
+        ),
+        None => (String::new(), String::new()),
+    };
+    let details = Paragraph::new(summary)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().title(title).borders(Borders::ALL));
+    f.render_widget(details, layout[1]);
+}
+
+fn draw_system_info(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default().title(tr("system.title")).borders(Borders::ALL);
+    let Some(info) = &app.system_info else {
+        let pending = Paragraph::new(tr("system.detecting"))
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(pending, area);
+        return;
+    };
+
+    let lines: Vec<Line> = info.rows()
+        .into_iter()
+        .map(|(key, value)| Line::from(vec![
+            Span::styled(format!("{:<14}", tr(key)), Style::default().fg(Color::Cyan)),
+            Span::raw(value),
+        ]))
+        .collect();
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(block);
+    f.render_widget(paragraph, area);
+}
+
+fn section_key(section: &str) -> &'static str {
+    match section {
+        "general" => "settings.section_general",
+        "java" => "settings.section_java",
+        "minecraft" => "settings.section_minecraft",
+        "ui" => "settings.section_ui",
+        "network" => "settings.section_network",
+        _ => "settings.section_advanced",
+    }
+
+}
+
+fn draw_launcher(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let versions = app.get_displayed_versions();
+    
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(area);
+    draw_version_filter(f, app, layout[0]);
+    let chunks = &layout[1..];
+
+    if versions.is_empty() {
+        let empty_message = if app.versions_ready.is_pending() && !app.show_installed_only {
+            tr("versions.loading")
+        } else if app.version_filter.is_active() {
+            tr("versions.no_matches")
+        } else if app.show_installed_only {
+            tr("versions.none_installed")
+        } else {
+            tr("versions.empty")
+        };
+
+        let empty_paragraph = Paragraph::new(empty_message)
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center)
+            .block(Block::default()
+                .title(if app.show_installed_only {
+                    tr("versions.title_installed")
+                } else {
+                    tr("versions.title")
+                })
+                .borders(Borders::ALL));
+
         f.render_widget(empty_paragraph, chunks[0]);
     } else {
         let items: Vec<ListItem> = versions
             .iter()
-            .take(20)
             .map(|version| {
                 let is_installed = app.version_manager.is_version_installed(&version.id);
                 let installed_marker = if is_installed { " ✓" } else { "" };
                 
-                let version_text = format!("{}{} ({})", 
-                    version.id, 
+                let mark = if app.marked.contains(&version.id) { "● " } else { "" };
+                let kind = VersionKind::of(version);
+                let version_text = format!("{}{}{} ({})",
+                    mark,
+                    version.id,
                     installed_marker,
-                    version.r#type
+                    kind.map(|k| k.label()).unwrap_or(version.r#type.as_str())
                 );
                 
-                let color = if is_installed {
-                    Color::Green
-                } else {
-                    match version.r#type.as_str() {
-                        "release" => Color::Yellow,
-                        "snapshot" => Color::Cyan,
-                        "old_beta" => Color::Blue,
-                        "old_alpha" => Color::Magenta,
-                        _ => Color::White,
-                    }
-                };
+                let color = kind.map(version_kind_color).unwrap_or(Color::White);
                 ListItem::new(version_text).style(Style::default().fg(color))
             })
             .collect();
 
         let mode_text = if app.show_installed_only {
-        if app.language == Language::Russian {
-                "скачанных"
-            } else {
-                "downloaded"
-            }
+            tr("versions.mode_downloaded")
         } else {
-            if app.language == Language::Russian {
-                "доступно"
-            } else {
-                "available"
-            }
+            tr("versions.mode_available")
         };
 
         let versions_list = List::new(items)
             .block(Block::default()
-                .title(if app.language == Language::Russian {
-                    if app.show_installed_only {
-                        format!("Скачанные версии Minecraft ({} {})", versions.len(), mode_text)
-                    } else {
-                        format!("Версии Minecraft ({} {})", versions.len(), mode_text)
-                    }
+                .title(format!("{} ({} {})", if app.show_installed_only {
+                    tr("versions.title_installed")
                 } else {
-                    if app.show_installed_only {
-                        format!("Downloaded Minecraft Versions ({} {})", versions.len(), mode_text)
-                    } else {
-                        format!("Minecraft Versions ({} {})", versions.len(), mode_text)
-                    }
-                })
+                    tr("versions.title")
+                }, versions.len(), mode_text))
                 .borders(Borders::ALL))
             .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
             .highlight_symbol("> ");
@@ -969,11 +2725,7 @@ fn draw_launcher(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListStat
 
     let status = Paragraph::new(format!(
         "{}: {}",
-        if app.language == Language::Russian {
-            "Статус"
-        } else {
-            "Status"
-        },
+        tr("versions.status_title"),
         app.current_state
     ))
     .style(Style::default().fg(Color::Cyan))
@@ -982,17 +2734,140 @@ fn draw_launcher(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListStat
     f.render_widget(status, chunks[1]);
 }
 
+fn version_kind_color(kind: VersionKind) -> Color {
+    match kind {
+        VersionKind::Release => Color::Yellow,
+        VersionKind::Snapshot => Color::Cyan,
+        VersionKind::Beta => Color::Blue,
+        VersionKind::Alpha => Color::Magenta,
+        VersionKind::AprilFools => Color::LightRed,
+    }
+}
+
+fn draw_version_filter(f: &mut Frame, app: &App, area: Rect) {
+    let filter = &app.version_filter;
+    let style = if app.version_search_editing {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    let cursor = if app.version_search_editing { "█" } else { "" };
+
+    let mut spans = vec![Span::styled(format!("/ {}{}", filter.query, cursor), style), Span::raw("   ")];
+    for (index, kind) in VersionKind::ALL.iter().enumerate() {
+        let (mark, color) = if filter.shows(*kind) { ("■", version_kind_color(*kind)) } else { ("□", Color::DarkGray) };
+        spans.push(Span::styled(format!("{} {} {}  ", index + 1, mark, kind.label()), Style::default().fg(color)));
+    }
+
+    let search = Paragraph::new(Line::from(spans))
+        .block(Block::default()
+            .title(tr("versions.search_title"))
+            .borders(Borders::ALL));
+    f.render_widget(search, area);
+}
+
+fn draw_console(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let Some((id, console)) = &app.console else { return };
+    let name = app.instance_manager.get_instance(*id).map(|i| i.name.as_str()).unwrap_or("?");
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(area);
+
+    let items: Vec<ListItem> = console.lines()
+        .into_iter()
+        .map(|line| {
+            let color = if line.stderr { Color::Red } else if line.text.starts_with("> ") { Color::Cyan } else { Color::White };
+            ListItem::new(line.text).style(Style::default().fg(color))
+        })
+        .collect();
+
+    let status = if console.is_running() { tr("console.running") } else { tr("console.exited") };
+    let title = trf("console.title", &[("name", &name), ("status", &status), ("lines", &items.len())]);
+    let list = List::new(items)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+    f.render_stateful_widget(list, chunks[0], list_state);
+
+    let input = match &app.console_input {
+        Some(input) => Paragraph::new(format!("> {}█", input)).style(Style::default().fg(Color::Yellow)),
+        None => Paragraph::new(tr("console.input_hint"))
+            .style(Style::default().fg(Color::Gray)),
+    };
+    f.render_widget(input.block(Block::default().borders(Borders::ALL)), chunks[1]);
+}
+
+fn log_level_color(level: &LogLevel) -> Color {
+    match level {
+        LogLevel::Info => Color::White,
+        LogLevel::Warning => Color::Yellow,
+        LogLevel::Error => Color::Red,
+        LogLevel::Debug => Color::Gray,
+        LogLevel::Launcher => Color::Cyan,
+    }
+}
+
+fn draw_logs_view(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let view = &app.log_view;
+    let entries = view.entries(&app.log_manager);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let level = view.level.as_ref().map(|level| level.as_str()).unwrap_or(tr("logs.all_levels"));
+    let instance = view.instance.as_deref().unwrap_or(tr("logs.all_instances"));
+    let cursor = if view.editing { "█" } else { "" };
+    let follow = trf("logs.follow", &[("state", &tr(if view.follow { "common.on" } else { "common.off" }))]);
+    let filter = Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!("/ {}{}", view.query, cursor),
+            Style::default().fg(if view.editing { Color::Yellow } else { Color::White }),
+        ),
+        Span::raw("   "),
+        Span::styled(format!("{}: {}", tr("logs.level"), level), Style::default().fg(Color::Cyan)),
+        Span::raw("   "),
+        Span::styled(format!("{}: {}", tr("logs.instance"), instance), Style::default().fg(Color::Magenta)),
+        Span::raw("   "),
+        Span::styled(follow, Style::default().fg(if view.follow { Color::Green } else { Color::DarkGray })),
+    ]))
+    .block(Block::default().title(tr("logs.filter_title")).borders(Borders::ALL));
+    f.render_widget(filter, chunks[0]);
+
+    let items: Vec<ListItem> = entries
+        .iter()
+        .map(|entry| ListItem::new(entry.format()).style(Style::default().fg(log_level_color(&entry.level))))
+        .collect();
+
+    let title = trf("logs.title_filtered", &[("shown", &entries.len()), ("total", &app.log_manager.count())]);
+    let list = List::new(items)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    f.render_stateful_widget(list, chunks[1], list_state);
+}
+
 fn draw_logs_panel(f: &mut Frame, app: &App, area: Rect) {
     
-    let logs = app.log_manager.get_recent_entries(50);
+    let entries = app.log_manager.get_recent_entries(LOG_SCROLLBACK);
+    let visible = area.height.saturating_sub(2) as usize;
+    let end = entries.len().saturating_sub(app.log_scroll);
+    let logs = &entries[end.saturating_sub(visible)..end];
     
     if logs.is_empty() {
-        let empty_message = "Логи пусты\nСобытия будут отображаться здесь";
+        let empty_message = tr("logs.empty");
         let empty_paragraph = Paragraph::new(empty_message)
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center)
             .block(Block::default()
-                .title("Логи лаунчера")
+                .title(tr("logs.title"))
                 .borders(Borders::ALL));
         f.render_widget(empty_paragraph, area);
         return;
@@ -1014,13 +2889,17 @@ fn draw_logs_panel(f: &mut Frame, app: &App, area: Rect) {
             );
             
             ListItem::new(formatted)
-                .style(Style::default().fg(entry.level.color()))
+                .style(Style::default().fg(log_level_color(&entry.level)))
         })
         .collect();
 
     let logs_list = List::new(log_items)
         .block(Block::default()
-            .title(format!("Логи лаунчера ({})", logs.len()))
+            .title(if app.log_scroll > 0 {
+                trf("logs.title_scrolled", &[("count", &entries.len()), ("scroll", &app.log_scroll)])
+            } else {
+                trf("logs.title_count", &[("count", &entries.len())])
+            })
             .borders(Borders::ALL))
         .style(Style::default().fg(Color::White));
 
@@ -1033,21 +2912,13 @@ fn draw_account_manager(f: &mut Frame, app: &App, area: Rect, list_state: &mut L
     let accounts = app.auth_manager.list_accounts();
     
     if accounts.is_empty() {
-        let empty_message = if app.language == Language::Russian {
-            "Нет аккаунтов.\nНажмите 'O' для создания offline аккаунта."
-        } else {
-            "No accounts.\nPress 'O' to create an offline account."
-        };
+        let empty_message = tr("accounts.empty");
 
         let empty_paragraph = Paragraph::new(empty_message)
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center)
             .block(Block::default()
-                .title(if app.language == Language::Russian {
-                    "Управление аккаунтами"
-                } else {
-                    "Account Management"
-                })
+                .title(tr("accounts.title"))
                 .borders(Borders::ALL));
 
         f.render_widget(empty_paragraph, area);
@@ -1058,17 +2929,22 @@ fn draw_account_manager(f: &mut Frame, app: &App, area: Rect, list_state: &mut L
             .iter()
             .map(|account| {
                 let account_type_str = match account.account_type {
-                    AccountType::Offline => if app.language == Language::Russian { "Offline" } else { "Offline" },
-                    AccountType::Microsoft => if app.language == Language::Russian { "Microsoft" } else { "Microsoft" },
+                    AccountType::Offline => "Offline",
+                    AccountType::Microsoft => "Microsoft",
                 };
                 
                 let is_default = default_account.map(|def| def.id == account.id).unwrap_or(false);
                 let default_indicator = if is_default { " [★]" } else { "" };
                 
-                let display_text = format!("{} ({}){}", 
+                let last_used = match account.last_used {
+                    Some(time) => time.with_timezone(&chrono::Local).format("%d.%m.%Y %H:%M").to_string(),
+                    None => tr("accounts.never_used").to_string(),
+                };
+                let display_text = format!("{} ({}){} — {}", 
                     account.display_name, 
                     account_type_str,
-                    default_indicator
+                    default_indicator,
+                    trf("accounts.usage", &[("last_used", &last_used), ("count", &account.launch_count)])
                 );
                 
                 let color = match account.account_type {
@@ -1089,50 +2965,114 @@ fn draw_account_manager(f: &mut Frame, app: &App, area: Rect, list_state: &mut L
 
         let accounts_list = List::new(items)
             .block(Block::default()
-                .title(if app.language == Language::Russian {
-                    format!("Управление аккаунтами ({})", accounts.len())
-                } else {
-                    format!("Account Management ({})", accounts.len())
-                })
+                .title(trf("accounts.title_count", &[("count", &accounts.len())]))
                 .borders(Borders::ALL))
             .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
             .highlight_symbol("> ");
 
-        f.render_stateful_widget(accounts_list, area, list_state);
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(20),
+            ])
+            .split(area);
+
+        f.render_stateful_widget(accounts_list, chunks[0], list_state);
+
+        let head = list_state.selected()
+            .and_then(|selected| accounts.get(selected))
+            .and_then(|account| app.skin_cache.get(account));
+        let avatar = match head {
+            Some(head) => Paragraph::new(head_lines(head)).alignment(Alignment::Center),
+            None => Paragraph::new(tr("accounts.no_avatar"))
+                .style(Style::default().fg(Color::Gray))
+                .alignment(Alignment::Center),
+        };
+        f.render_widget(avatar.block(Block::default().borders(Borders::ALL)), chunks[1]);
     }
 }
 
+fn head_lines(head: &crate::skins::HeadImage) -> Vec<Line<'static>> {
+    let color = |[r, g, b, a]: [u8; 4]| if a == 0 { Color::Reset } else { Color::Rgb(r, g, b) };
+
+    (0..head.height)
+        .step_by(2)
+        .map(|y| {
+            let spans: Vec<Span> = (0..head.width)
+                .map(|x| {
+                    let top = color(head.pixel(x, y));
+                    let bottom = color(head.pixel(x, y + 1));
+                    Span::styled("▀▀", Style::default().fg(top).bg(bottom))
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect()
+}
+
 fn draw_edit_instance(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
     if let Some(instance) = app.get_editing_instance() {
+    let issues = app.editing_java_args_lint();
+    let lint_height = if app.java_args_input.is_some() || !issues.is_empty() {
+        issues.len().min(6) as u16 + app.java_args_input.is_some() as u16 + 2
+    } else {
+        0
+    };
+    let mismatch = app.java_mismatch(instance);
+    let java_height = mismatch.as_ref()
+        .map(|mismatch| mismatch.suggestions.len().min(JAVA_SUGGESTIONS) as u16 + 4)
+        .unwrap_or(0);
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
                 Constraint::Min(0),
+            Constraint::Length(lint_height),
+            Constraint::Length(java_height),
             Constraint::Length(3),
         ])
         .split(area);
 
+        let default = tr("common.default");
+        let none = tr("common.none");
+        let effective = app.effective_settings(instance);
         let fields = vec![
-            format!("Название: {} ⚡", instance.name),
-            format!("Версия Minecraft: {} ⚡", instance.minecraft_version),
-            format!("Модлоадер: {} ⚡", instance.mod_loader.as_ref()
+            trf("edit.field_name", &[("value", &instance.name)]),
+            trf("edit.field_version", &[("value", &instance.minecraft_version)]),
+            trf("edit.field_loader", &[("value", &instance.mod_loader.as_ref()
                 .map(|ml| format!("{:?}", ml))
-                .unwrap_or_else(|| "Нет".to_string())),
-            format!("Версия модлоадера: {} ⚡", instance.mod_loader_version.as_deref().unwrap_or("latest")),
-            format!("Путь к Java: {} ⚡", instance.java_path.as_ref()
-                .map(|p| {
-            
-                    p.file_name().and_then(|n| n.to_str()).unwrap_or("java")
-                })
-                .unwrap_or_else(|| "По умолчанию")),
-            format!("Аргументы Java: {} ⚡", instance.java_args.as_deref().unwrap_or("По умолчанию")),
-            format!("Память мин: {} MB ⚡", instance.memory_min.unwrap_or(1024)),
-            format!("Память макс: {} MB ⚡", instance.memory_max.unwrap_or(4096)),
-            format!("Разрешение: {}x{} ⚡", 
-                instance.width.unwrap_or(854), 
-                instance.height.unwrap_or(480)),
-            format!("Полноэкранный режим: {} ⚡", if instance.fullscreen { "Да" } else { "Нет" }),
-            format!("Группа: {} ⚡", instance.group.as_deref().unwrap_or("Нет")),
+                .unwrap_or_else(|| none.to_string()))]),
+            trf("edit.field_loader_version", &[("value", &instance.mod_loader_version.as_deref().unwrap_or("latest"))]),
+            trf("edit.field_java_path", &[("value", &instance.java_path.as_ref()
+                .map(|p| p.file_name().and_then(|n| n.to_str()).unwrap_or("java"))
+                .unwrap_or(default))]),
+            trf("edit.field_java_args", &[
+                ("preset", &instance.gc_preset.map(|preset| preset.label()).unwrap_or(default)),
+                ("value", &effective.java_args.value),
+                ("source", &effective.java_args.source.marker()),
+                ("issues", &if issues.is_empty() { String::new() } else { format!(" ⚠ {}", issues.len()) }),
+            ]),
+            trf("edit.field_memory_min", &[("value", &effective.memory_min.value), ("source", &effective.memory_min.source.marker())]),
+            trf("edit.field_memory_max", &[("value", &effective.memory_max.value), ("source", &effective.memory_max.source.marker())]),
+            trf("edit.field_resolution", &[
+                ("width", &effective.resolution.value.0),
+                ("height", &effective.resolution.value.1),
+                ("source", &effective.resolution.source.marker()),
+            ]),
+            trf("edit.field_fullscreen", &[
+                ("value", &tr(if effective.fullscreen.value { "common.yes" } else { "common.no" })),
+                ("source", &effective.fullscreen.source.marker()),
+            ]),
+            trf("edit.field_group", &[("value", &instance.group.as_deref().unwrap_or(none))]),
+            trf("edit.field_channels", &[("value", &match instance.version_channels {
+                Some(channels) => channels.label(),
+                None => format!("{} ({})", default, app.version_channels_for(instance).label()),
+            })]),
+            trf("edit.field_demo", &[("value", &tr(if instance.demo_mode { "common.yes" } else { "common.no" }))]),
+            trf("edit.field_icon", &[("value", &instance.icon_glyph().unwrap_or(none))]),
+            trf("edit.field_project_url", &[("value", &instance.links.project_url.as_deref().unwrap_or(none))]),
+            trf("edit.field_issues_url", &[("value", &instance.links.issues_url.as_deref().unwrap_or(none))]),
+            trf("edit.field_wiki_url", &[("value", &instance.links.wiki_url.as_deref().unwrap_or(none))]),
         ];
 
         let items: Vec<ListItem> = fields
@@ -1152,64 +3092,837 @@ fn draw_edit_instance(f: &mut Frame, app: &App, area: Rect, list_state: &mut Lis
 
         let instance_settings = List::new(items)
             .block(Block::default()
-                .title(if app.language == Language::Russian {
-                    format!("Редактирование экземпляра: {}", instance.name)
-        } else {
-                    format!("Editing Instance: {}", instance.name)
-                })
+                .title(trf("edit.title", &[("name", &instance.name)]))
                 .borders(Borders::ALL))
             .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
             .highlight_symbol("> ");
 
         f.render_stateful_widget(instance_settings, chunks[0], list_state);
 
+        if lint_height > 0 {
+            let mut lines = Vec::new();
+            if let Some(input) = &app.java_args_input {
+                lines.push(Line::from(Span::styled(format!("{}█", input), Style::default().fg(Color::White))));
+            }
+            for issue in issues.iter().take(6) {
+                let (marker, color) = match issue.severity() {
+                    LintSeverity::Error => ("✗", Color::Red),
+                    LintSeverity::Warning => ("⚠", Color::Yellow),
+                };
+                lines.push(Line::from(Span::styled(format!("{} {}", marker, issue.describe()), Style::default().fg(color))));
+            }
+            let lint = Paragraph::new(lines)
+                .block(Block::default()
+                    .title(tr("edit.java_args_title"))
+                    .borders(Borders::ALL));
+            f.render_widget(lint, chunks[1]);
+        }
+
+        if let Some(mismatch) = &mismatch {
+            let current = match mismatch.current {
+                Some(java) => trf("edit.java_mismatch_current", &[("vendor", &java.vendor), ("version", &java.version)]),
+                None => tr("edit.java_mismatch_none").to_string(),
+            };
+            let mut lines = vec![Line::from(Span::styled(
+                trf("edit.java_mismatch", &[
+                    ("version", &instance.minecraft_version),
+                    ("required", &mismatch.required),
+                    ("current", &current),
+                ]),
+                Style::default().fg(Color::Yellow),
+            ))];
+            let hint = if mismatch.suggestions.is_empty() {
+                trf("edit.java_mismatch_download", &[("required", &mismatch.required)])
+            } else {
+                tr("edit.java_mismatch_pick").to_string()
+            };
+            lines.push(Line::from(Span::styled(hint, Style::default().fg(Color::Gray))));
+            for java in mismatch.suggestions.iter().take(JAVA_SUGGESTIONS) {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  ✓ {} {}", java.vendor, java.version), Style::default().fg(Color::Green)),
+                    Span::styled(format!("  {}", java.path.display()), Style::default().fg(Color::DarkGray)),
+                ]));
+            }
+            let warning = Paragraph::new(lines)
+                .block(Block::default()
+                    .title(tr("edit.java_mismatch_title"))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Yellow)));
+            f.render_widget(warning, chunks[2]);
+        }
+
             
-        let help_text = if app.language == Language::Russian {
-            format!(
-                "Используйте Enter для циклического изменения полей\n\
-                Текущая Java: {}\n\
-                Не забудьте сохранить изменения клавишей S",
-                if let Some(java) = app.get_default_java() {
-                    format!("{} {}", java.vendor, java.version)
-        } else {
-                    "Не найдена (J для поиска)".to_string()
-                }
-            )
+        let current_java = if let Some(java) = app.get_default_java() {
+            format!("{} {}", java.vendor, java.version)
+        } else if app.java_ready.is_pending() {
+            tr("edit.java_scanning").to_string()
         } else {
-            format!(
-                "Use Enter to cycle through field values\n\
-                Current Java: {}\n\
-                Don't forget to save changes with S",
-                if let Some(java) = app.get_default_java() {
-                    format!("{} {}", java.vendor, java.version)
-        } else {
-                    "Not found (J to search)".to_string()
-                }
-            )
+            tr("edit.java_not_found").to_string()
         };
+        let help_text = trf("edit.help", &[("java", &current_java)]);
 
         let info = Paragraph::new(help_text)
             .style(Style::default().fg(Color::Cyan))
             .wrap(ratatui::widgets::Wrap { trim: true })
             .block(Block::default()
-                .title("Справка")
+                .title(tr("edit.help_title"))
                 .borders(Borders::ALL));
 
-        f.render_widget(info, chunks[1]);
+        f.render_widget(info, chunks[3]);
     } else {
-        let error_text = if app.language == Language::Russian {
-            "Ошибка: экземпляр не найден"
-        } else {
-            "Error: instance not found"
-        };
+        let error_text = tr("edit.instance_not_found");
 
         let error_paragraph = Paragraph::new(error_text)
             .style(Style::default().fg(Color::Red))
             .alignment(Alignment::Center)
             .block(Block::default()
-                .title("Ошибка")
+                .title(tr("common.error"))
                 .borders(Borders::ALL));
 
         f.render_widget(error_paragraph, area);
     }
-}
\ No newline at end of file
+}
+
+fn draw_crash_report(f: &mut Frame, app: &App, area: Rect) {
+    let Some(report) = &app.last_crash else {
+        let empty = Paragraph::new(tr("crash.no_data"))
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(6),
+            Constraint::Min(0),
+            Constraint::Length(8),
+            Constraint::Length(report.system.len().min(6) as u16 + 2),
+        ])
+        .split(area);
+
+    let none = tr("crash.not_found");
+    let header = vec![
+        Line::from(Span::styled(report.headline(), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))),
+        Line::from(format!("{}: {}",
+            tr("crash.report"),
+            report.report_path.as_ref().map(|p| p.display().to_string()).unwrap_or_else(|| none.to_string()))),
+        Line::from(format!("{}: {}",
+            tr("crash.log"),
+            report.log_path.as_ref().map(|p| p.display().to_string()).unwrap_or_else(|| none.to_string()))),
+    ];
+    let header_block = Paragraph::new(header)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(Block::default()
+            .title(trf("crash.title", &[("name", &report.instance_name)]))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red)));
+    f.render_widget(header_block, chunks[0]);
+
+    let summary_text = if report.summary.is_empty() {
+        tr("crash.no_report").to_string()
+    } else {
+        report.summary.join("\n")
+    };
+    let summary = Paragraph::new(summary_text)
+        .style(Style::default().fg(Color::White))
+        .block(Block::default()
+            .title(tr("crash.summary_title"))
+            .borders(Borders::ALL));
+    f.render_widget(summary, chunks[1]);
+
+    let log_items: Vec<ListItem> = report.log_excerpt
+        .iter()
+        .map(|line| ListItem::new(line.clone()).style(Style::default().fg(Color::Yellow)))
+        .collect();
+    let log_list = List::new(log_items)
+        .block(Block::default()
+            .title("latest.log")
+            .borders(Borders::ALL));
+    f.render_widget(log_list, chunks[2]);
+
+    if !report.system.is_empty() {
+        let system = Paragraph::new(report.system.join("\n"))
+            .style(Style::default().fg(Color::Gray))
+            .block(Block::default()
+                .title(tr("crash.system_title"))
+                .borders(Borders::ALL));
+        f.render_widget(system, chunks[3]);
+    }
+}
+
+fn draw_launch_conflict(f: &mut Frame, app: &App, area: Rect) {
+    let Some(conflict) = &app.launch_conflict else { return };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            tr("conflict.description"),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(tr("conflict.warning")),
+        Line::from(""),
+    ];
+
+    for item in &conflict.conflicts {
+        lines.push(Line::from(Span::styled(
+            format!("{}:", item.instance_name),
+            Style::default().fg(Color::Cyan),
+        )));
+        lines.push(Line::from(Span::styled(
+            format!("  {}", item.directory.display()),
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    let block = Paragraph::new(lines)
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .block(Block::default()
+            .title(trf("conflict.title", &[("name", &conflict.instance_name)]))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red)));
+    f.render_widget(block, area);
+}
+
+fn draw_command_approval(f: &mut Frame, app: &App, area: Rect) {
+    let Some(approval) = &app.command_approval else {
+        let empty = Paragraph::new(tr("approval.empty"))
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            tr("approval.description"),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(tr("approval.warning")),
+        Line::from(""),
+    ];
+
+    for (hook, command) in &approval.commands {
+        let label = match hook {
+            LaunchHook::PreLaunch => tr("approval.pre_launch"),
+            LaunchHook::PostExit => tr("approval.post_exit"),
+        };
+        lines.push(Line::from(Span::styled(format!("{}:", label), Style::default().fg(Color::Cyan))));
+        lines.push(Line::from(Span::styled(format!("  {}", command), Style::default().fg(Color::White).add_modifier(Modifier::BOLD))));
+    }
+
+    let block = Paragraph::new(lines)
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .block(Block::default()
+            .title(trf("approval.title", &[("name", &approval.instance_name)]))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)));
+    f.render_widget(block, area);
+}
+
+fn draw_account_picker(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    use crate::auth::AccountType;
+    let Some(picker) = &app.account_picker else { return };
+    let remembered = app.instance_manager.get_instance(picker.instance_id).and_then(|instance| instance.last_account);
+    let items: Vec<ListItem> = picker.accounts
+        .iter()
+        .filter_map(|id| app.auth_manager.get_account(*id))
+        .enumerate()
+        .map(|(i, account)| {
+            let kind = match account.account_type {
+                AccountType::Offline => "Offline",
+                AccountType::Microsoft => "Microsoft",
+            };
+            let last = if remembered == Some(account.id) { tr("accounts.picker_last") } else { "" };
+            ListItem::new(format!("{}. {} ({}){}", i + 1, account.display_name, kind, last))
+        })
+        .collect();
+
+    let width = (area.width / 4 * 3).max(30).min(area.width);
+    let height = (items.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+    let list = List::new(items)
+        .block(Block::default()
+            .title(trf("accounts.picker_title", &[("name", &picker.instance_name)]))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_widget(ratatui::widgets::Clear, popup);
+    f.render_stateful_widget(list, popup, list_state);
+}
+
+fn draw_mod_bisect(f: &mut Frame, app: &App, area: Rect) {
+    let Some(session) = &app.bisect else {
+        let empty = Paragraph::new(tr("bisect.empty"))
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(6),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let status = if session.finished {
+        match &session.culprit {
+            Some(name) => Line::from(Span::styled(trf("bisect.culprit", &[("name", name)]), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))),
+            None => Line::from(tr("bisect.no_culprit")),
+        }
+    } else if session.running {
+        Line::from(tr("bisect.running"))
+    } else if session.tested {
+        let detected = match session.detected_crash {
+            Some(true) => tr("bisect.crash_detected"),
+            Some(false) => tr("bisect.exited_cleanly"),
+            None => "",
+        };
+        Line::from(Span::styled(
+            trf("bisect.question", &[("detected", &detected)]),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ))
+    } else {
+        Line::from(tr("bisect.launch_hint"))
+    };
+
+    let header = vec![
+        Line::from(trf("bisect.round", &[
+            ("round", &session.round),
+            ("suspects", &session.suspects.len()),
+            ("total", &session.total_mods),
+            ("remaining", &session.remaining_rounds()),
+        ])),
+        status,
+    ];
+    let header_block = Paragraph::new(header)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(Block::default()
+            .title(trf("bisect.title", &[("name", &session.instance_name)]))
+            .borders(Borders::ALL));
+    f.render_widget(header_block, chunks[0]);
+
+    let items: Vec<ListItem> = session.suspects
+        .iter()
+        .map(|name| {
+            if session.testing.contains(name) && !session.finished {
+                ListItem::new(format!("[+] {}", name)).style(Style::default().fg(Color::Green))
+            } else {
+                ListItem::new(format!("[ ] {}", name)).style(Style::default().fg(Color::Gray))
+            }
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default()
+            .title(tr("bisect.suspects_title"))
+            .borders(Borders::ALL));
+    f.render_widget(list, chunks[1]);
+}
+
+fn draw_mod_list(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let mods = app.installed_mod_list();
+
+    let items: Vec<ListItem> = mods
+        .iter()
+        .map(|m| {
+            let mark = if app.marked.contains(&m.filename) { "● " } else { "" };
+            let state = if m.enabled { "[x]" } else { "[ ]" };
+            ListItem::new(format!("{}{} {} {} — {}", mark, state, m.name, m.version, m.filename))
+                .style(Style::default().fg(if m.enabled { Color::White } else { Color::DarkGray }))
+        })
+        .collect();
+
+    let enabled = mods.iter().filter(|m| m.enabled).count();
+    let list = List::new(items)
+        .block(Block::default()
+            .title(trf("mods.title", &[("count", &mods.len()), ("enabled", &enabled)]))
+            .borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, area, list_state);
+}
+
+fn draw_pack_manager(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let Some(browser) = &app.pack_browser else { return };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(5),
+        ])
+        .split(area);
+
+    let title = match browser.kind {
+        PackKind::Resource => tr("packs.resource_title"),
+        PackKind::Shader => tr("packs.shader_title"),
+    };
+    let input = match &browser.path_input {
+        Some(path) => Paragraph::new(format!("{}█", path)).style(Style::default().fg(Color::Yellow)),
+        None => Paragraph::new(tr("packs.install_hint"))
+            .style(Style::default().fg(Color::Gray)),
+    };
+    f.render_widget(input.block(Block::default()
+        .title(tr("packs.path_title"))
+        .borders(Borders::ALL)), chunks[0]);
+
+    let items: Vec<ListItem> = browser.packs
+        .iter()
+        .map(|pack| {
+            let marker = if pack.enabled { "[x]" } else { "[ ]" };
+            let format = pack.pack_format
+                .map(|format| format!(" (format {})", format))
+                .unwrap_or_default();
+            ListItem::new(format!("{} {}{} — {}", marker, pack.file_name, format, crate::utils::format_size(pack.size)))
+                .style(Style::default().fg(if pack.enabled { Color::White } else { Color::DarkGray }))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default()
+            .title(format!("{} ({})", title, browser.packs.len()))
+            .borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, chunks[1], list_state);
+
+    let description = list_state.selected()
+        .and_then(|i| browser.packs.get(i))
+        .and_then(|pack| pack.description.clone())
+        .unwrap_or_default();
+    let details = Paragraph::new(description)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .style(Style::default().fg(Color::Gray))
+        .block(Block::default()
+            .title(tr("common.description"))
+            .borders(Borders::ALL));
+    f.render_widget(details, chunks[2]);
+}
+
+fn draw_backups(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let items: Vec<ListItem> = app.backups
+        .iter()
+        .map(|backup| {
+            let reason = tr(match backup.reason {
+                Some(BackupReason::Delete) => "backups.reason_delete",
+                Some(BackupReason::LoaderChange) => "backups.reason_loader_change",
+                Some(BackupReason::Import) => "backups.reason_import",
+                Some(BackupReason::Restore) => "backups.reason_restore",
+                Some(BackupReason::ModpackUpdate) => "backups.reason_modpack",
+                None => "backups.reason_unknown",
+            });
+            let exists = app.instance_manager.get_instance(backup.instance_id).is_some();
+            ListItem::new(format!(
+                "{} — {} ({}) — {}",
+                backup.created_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+                backup.instance_name,
+                reason,
+                crate::utils::format_size(backup.size),
+            ))
+            .style(Style::default().fg(if exists { Color::White } else { Color::Yellow }))
+        })
+        .collect();
+
+    let title = trf("backups.title", &[
+        ("count", &app.backups.len()),
+        ("retention", &app.settings_manager.get().advanced.backup_retention),
+    ]);
+    let list = List::new(items)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, area, list_state);
+}
+
+fn draw_quick_play(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let Some(picker) = &app.quick_play else { return };
+    let current = app.instance_manager.get_instance(picker.instance_id).and_then(|instance| instance.quick_play.as_ref());
+    let items: Vec<ListItem> = picker.targets
+        .iter()
+        .map(|(label, target)| {
+            if current == Some(target) {
+                ListItem::new(Line::from(vec![
+                    Span::raw(label.clone()),
+                    Span::styled(format!(" ★ {}", tr("quick_play.default")), Style::default().fg(Color::Yellow)),
+                ]))
+            } else {
+                ListItem::new(label.clone())
+            }
+        })
+        .collect();
+    let title = trf(if picker.worlds { "quick_play.worlds_title" } else { "quick_play.servers_title" }, &[("name", &picker.instance_name)]);
+    let list = List::new(items)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, area, list_state);
+}
+
+fn draw_launch_variants(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let Some(instance) = app.variants_instance.and_then(|id| app.instance_manager.get_instance(id)) else { return };
+
+    let effective = app.effective_settings(instance);
+    let describe = |width: Option<u32>, height: Option<u32>, memory_max: Option<u32>| {
+        let (width, height) = width.zip(height).unwrap_or(effective.resolution.value);
+        format!("{}x{}, {} MB", width, height, memory_max.unwrap_or(effective.memory_max.value))
+    };
+
+    let mut items = vec![ListItem::new(format!(
+        "{} — {}",
+        tr("common.default"),
+        describe(instance.width, instance.height, instance.memory_max),
+    ))];
+    items.extend(instance.launch_variants.iter().map(|variant| {
+        let mut line = format!(
+            "{} — {}",
+            variant.name,
+            describe(variant.width.or(instance.width), variant.height.or(instance.height), variant.memory_max.or(instance.memory_max)),
+        );
+        if let Some(wrapper) = &variant.wrapper_command {
+            line.push_str(&format!(" | {}", wrapper));
+        }
+        if !variant.env.is_empty() {
+            line.push_str(&format!(" | env: {}", variant.env.keys().cloned().collect::<Vec<_>>().join(", ")));
+        }
+        ListItem::new(line)
+    }));
+
+    let title = trf("variants.title", &[("name", &instance.name)]);
+    let list = List::new(items)
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, area, list_state);
+}
+
+fn draw_clone_instance(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let Some(dialog) = &app.clone_dialog else { return };
+    let Some(source) = app.instance_manager.get_instance(dialog.source) else { return };
+
+    let toggle = |label: &str, enabled: bool| {
+        ListItem::new(format!("[{}] {}", if enabled { "x" } else { " " }, label))
+    };
+    let items = vec![
+        toggle(tr("clone.mods"), dialog.options.mods),
+        toggle(tr("clone.configs"), dialog.options.configs),
+        toggle(tr("clone.saves"), dialog.options.saves),
+        toggle(tr("clone.resource_packs"), dialog.options.resource_packs),
+        ListItem::new(trf("clone.name", &[("name", &dialog.name)])),
+        ListItem::new(Span::styled(tr("clone.create"), Style::default().fg(Color::Green))),
+    ];
+
+    let list = List::new(items)
+        .block(Block::default().title(trf("clone.title", &[("name", &source.name)])).borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, area, list_state);
+}
+
+fn draw_modpack_update(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let Some(update) = &app.modpack_update else { return };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(5), Constraint::Min(0)])
+        .split(area);
+
+    let lines = vec![
+        Line::from(trf("modpack.mods", &[
+            ("added", &update.added()),
+            ("updated", &update.updated),
+            ("removed", &update.removed.len()),
+        ])),
+        Line::from(trf("modpack.configs", &[
+            ("updated", &update.overrides.len()),
+            ("removed", &update.dropped_overrides.len()),
+        ])),
+        Line::from(Span::styled(
+            if update.conflicts.is_empty() { tr("modpack.no_conflicts") } else { tr("modpack.conflicts") },
+            Style::default().fg(if update.conflicts.is_empty() { Color::Gray } else { Color::Yellow }),
+        )),
+    ];
+    let summary = Paragraph::new(lines)
+        .block(Block::default()
+            .title(trf("modpack.title", &[
+                ("name", &update.source.name),
+                ("from", &update.from_version.as_deref().unwrap_or("?")),
+                ("to", &update.source.display_version()),
+            ]))
+            .borders(Borders::ALL));
+    f.render_widget(summary, chunks[0]);
+
+    let mut items: Vec<ListItem> = update.conflicts
+        .iter()
+        .map(|conflict| ListItem::new(trf(
+            if conflict.keep_local { "modpack.keep_local" } else { "modpack.use_pack" },
+            &[("path", &conflict.path)],
+        )))
+        .collect();
+    items.push(ListItem::new(Span::styled(tr("modpack.apply"), Style::default().fg(Color::Green))));
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, chunks[1], list_state);
+}
+
+fn draw_storage(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let usage = &app.disk_usage;
+    let format_usage = |size: Option<u64>, pending: bool| match (size, pending) {
+        (Some(bytes), true) => format!("{} …", crate::utils::format_size(bytes)),
+        (Some(bytes), false) => crate::utils::format_size(bytes),
+        (None, _) => "…".to_string(),
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(StorageCategory::ALL.len() as u16 + 3),
+            Constraint::Length(Cleanup::ALL.len() as u16 + 2),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let mut lines: Vec<Line> = StorageCategory::ALL
+        .iter()
+        .map(|category| Line::from(format!(
+            "{:<20} {}",
+            category.label(),
+            format_usage(usage.size(*category), usage.is_pending(*category)),
+        )))
+        .collect();
+    lines.push(Line::from(Span::styled(
+        format!("{:<20} {}", tr("storage.total"), crate::utils::format_size(usage.total())),
+        Style::default().add_modifier(Modifier::BOLD),
+    )));
+
+    let title = if usage.is_scanning() { tr("storage.title_scanning") } else { tr("storage.title") };
+    f.render_widget(
+        Paragraph::new(lines).block(Block::default().title(title).borders(Borders::ALL)),
+        chunks[0],
+    );
+
+    let mut instances: Vec<_> = app.instance_manager.list_instances()
+        .into_iter()
+        .map(|instance| (instance.name.clone(), usage.instance_size(instance.id)))
+        .collect();
+    instances.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+
+    let items: Vec<ListItem> = instances
+        .into_iter()
+        .map(|(name, size)| ListItem::new(format!("{} — {}", name, format_usage(size, false))))
+        .collect();
+    let cleanups: Vec<ListItem> = Cleanup::ALL
+        .iter()
+        .map(|cleanup| ListItem::new(cleanup.label()))
+        .collect();
+    let cleanup_list = List::new(cleanups)
+        .block(Block::default().title(tr("storage.cleanup_title")).borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(cleanup_list, chunks[1], list_state);
+
+    let list = List::new(items)
+        .block(Block::default().title(tr("storage.instances_title")).borders(Borders::ALL));
+    f.render_widget(list, chunks[2]);
+}
+
+fn draw_mod_browser(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let Some(browser) = &app.mod_browser else { return };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(6),
+        ])
+        .split(area);
+
+    let filters = [browser.loader.as_deref(), browser.game_version.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(", ");
+    let cursor = if browser.editing { "█" } else { "" };
+    let search = Paragraph::new(format!("{}{}", browser.query, cursor))
+        .style(Style::default().fg(if browser.editing { Color::Yellow } else { Color::White }))
+        .block(Block::default()
+            .title(trf("browser.search_title", &[("filters", &filters)]))
+            .borders(Borders::ALL));
+    f.render_widget(search, chunks[0]);
+
+    let items: Vec<ListItem> = browser.results
+        .iter()
+        .map(|hit| {
+            ListItem::new(format!("{} — {} ⬇ {}", hit.title, hit.author, hit.downloads))
+                .style(Style::default().fg(Color::White))
+        })
+        .collect();
+    let results = List::new(items)
+        .block(Block::default()
+            .title(trf("browser.results_title", &[
+                ("count", &browser.total_hits),
+                ("page", &browser.page()),
+                ("pages", &browser.page_count()),
+            ]))
+            .borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(results, chunks[1], list_state);
+
+    let description = list_state.selected()
+        .and_then(|i| browser.results.get(i))
+        .map(|hit| format!("{}\n{}", hit.description, hit.categories.join(", ")))
+        .unwrap_or_default();
+    let details = Paragraph::new(description)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .style(Style::default().fg(Color::Gray))
+        .block(Block::default()
+            .title(tr("common.description"))
+            .borders(Borders::ALL));
+    f.render_widget(details, chunks[2]);
+}
+
+fn draw_loader_versions(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let Some(picker) = &app.loader_picker else { return };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+
+    let cursor = if picker.editing { "█" } else { "" };
+    let search = Paragraph::new(format!("{}{}", picker.query, cursor))
+        .style(Style::default().fg(if picker.editing { Color::Yellow } else { Color::White }))
+        .block(Block::default()
+            .title(trf("loaders.search_title", &[
+                ("loader", &format!("{:?}", picker.loader)),
+                ("version", &picker.game_version),
+            ]))
+            .borders(Borders::ALL));
+    f.render_widget(search, chunks[0]);
+
+    let visible = picker.visible();
+    if visible.is_empty() {
+        let message = match &picker.ready {
+            Readiness::Pending => tr("loaders.loading").to_string(),
+            Readiness::Failed(e) => trf("loaders.failed", &[("error", e)]),
+            Readiness::Ready => tr("loaders.empty").to_string(),
+        };
+        let empty = Paragraph::new(message)
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center)
+            .block(Block::default().title(tr("loaders.title")).borders(Borders::ALL));
+        f.render_widget(empty, chunks[1]);
+        return;
+    }
+
+    let current = app.get_editing_instance().and_then(|instance| instance.mod_loader_version.as_deref());
+    let items: Vec<ListItem> = visible
+        .iter()
+        .map(|version| {
+            let style = if version.stable { Style::default().fg(Color::White) } else { Style::default().fg(Color::DarkGray) };
+            let mut spans = vec![Span::styled(version.id.clone(), style)];
+            if picker.is_latest(version) {
+                spans.push(Span::styled(format!("  {}", tr("loaders.latest")), Style::default().fg(Color::Cyan)));
+            }
+            if version.recommended {
+                spans.push(Span::styled(format!("  {}", tr("loaders.recommended")), Style::default().fg(Color::Green)));
+            }
+            if current == Some(version.id.as_str()) {
+                spans.push(Span::styled(format!("  {}", tr("loaders.current")), Style::default().fg(Color::Yellow)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default()
+            .title(trf("loaders.list_title", &[("count", &visible.len())]))
+            .borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, chunks[1], list_state);
+}
+
+fn draw_java(f: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let installations = app.java_manager.sorted_installations();
+    if installations.is_empty() {
+        let message = match &app.java_ready {
+            Readiness::Pending => tr("java.scanning").to_string(),
+            _ => tr("java.empty").to_string(),
+        };
+        let empty = Paragraph::new(message)
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Block::default().title(tr("java.title")).borders(Borders::ALL));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(6)])
+        .split(area);
+
+    let items: Vec<ListItem> = installations
+        .iter()
+        .map(|(_, java)| {
+            let major = java.major_version().map(|major| major.to_string()).unwrap_or_else(|| "?".to_string());
+            let mut spans = vec![
+                Span::styled(format!("Java {:<3}", major), Style::default().fg(Color::Yellow)),
+                Span::raw(format!(" {} {} ({})", java.vendor, java.version, java.architecture)),
+            ];
+            if java.is_default {
+                spans.push(Span::styled(format!("  {}", tr("java.default_marker")), Style::default().fg(Color::Green)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::default()
+            .title(trf("java.list_title", &[("count", &installations.len())]))
+            .borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, chunks[0], list_state);
+
+    let selected = list_state.selected().and_then(|index| installations.get(index));
+    let details = match selected {
+        Some((_, java)) => {
+            let custom = app.get_settings().java.custom_installations.contains(&java.path);
+            vec![
+                Line::from(vec![
+                    Span::styled(format!("{}: ", tr("java.path")), Style::default().fg(Color::Gray)),
+                    Span::raw(java.path.display().to_string()),
+                ]),
+                Line::from(vec![
+                    Span::styled(format!("{}: ", tr("java.source")), Style::default().fg(Color::Gray)),
+                    Span::raw(tr(if custom { "java.source_custom" } else { "java.source_detected" })),
+                ]),
+                Line::from(vec![
+                    Span::styled(format!("{}: ", tr("java.bits")), Style::default().fg(Color::Gray)),
+                    Span::raw(if java.is_64bit { "64-bit" } else { "32-bit" }),
+                ]),
+            ]
+        }
+        None => Vec::new(),
+    };
+    let details = Paragraph::new(details)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().title(tr("java.details")).borders(Borders::ALL));
+    f.render_widget(details, chunks[1]);
+}
@@ -0,0 +1,171 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::i18n::trf;
+use crate::github::{GitHubAsset, GitHubClient, GitHubRelease, GitHubSource};
+use crate::network::NetworkManager;
+use crate::{Error, Result};
+
+const REPOSITORY: &str = "MangoLauncher/MangoLauncher";
+const PREVIOUS_SUFFIX: &str = "old";
+
+/// A published launcher release newer than the running one.
+#[derive(Debug, Clone)]
+pub struct Update {
+    pub version: String,
+    pub release: GitHubRelease,
+    /// The archive built for this platform, if the release has one.
+    pub asset: Option<GitHubAsset>,
+}
+
+// Release archives are named after the Rust target they were built for
+// (see .github/workflows), e.g. mango-launcher-x86_64-unknown-linux-gnu.tar.gz.
+fn target_triple() -> Option<String> {
+    let arch = std::env::consts::ARCH;
+    match std::env::consts::OS {
+        "linux" => Some(format!("{}-unknown-linux-gnu", arch)),
+        "windows" => Some(format!("{}-pc-windows-msvc", arch)),
+        "macos" => Some(format!("{}-apple-darwin", arch)),
+        _ => None,
+    }
+}
+
+fn version_parts(version: &str) -> Vec<u64> {
+    version.trim_start_matches('v')
+        .split(['-', '+'])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+/// Whether `tag` names a later version than `current`. Tags that are not
+/// dotted numbers never count as newer.
+pub fn is_newer(tag: &str, current: &str) -> bool {
+    let (tag, current) = (version_parts(tag), version_parts(current));
+    let len = tag.len().max(current.len());
+    let pad = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+    !tag.is_empty()
+        && (0..len)
+            .map(|i| pad(&tag, i).cmp(&pad(&current, i)))
+            .find(|ordering| ordering.is_ne())
+            == Some(std::cmp::Ordering::Greater)
+}
+
+/// Looks up the latest stable release; `None` when this build is current.
+pub async fn check(network: &NetworkManager) -> Result<Option<Update>> {
+    let target = target_triple().unwrap_or_default();
+    let source = GitHubSource::parse(REPOSITORY, Some(&format!("mango-launcher-{}.*", target)), false)?;
+    let release = GitHubClient::new(network.clone()).latest_release(&source).await?;
+    if !is_newer(&release.tag_name, crate::VERSION) {
+        return Ok(None);
+    }
+    let asset = if target.is_empty() { None } else { GitHubClient::find_asset(&release, &source).cloned() };
+    Ok(Some(Update {
+        version: release.tag_name.trim_start_matches('v').to_string(),
+        release,
+        asset,
+    }))
+}
+
+/// Downloads the update and puts it in place of the running binary, which
+/// is kept next to it with an `.old` extension. The new version is used from
+/// the next start. Releases without a checksum for the build are refused.
+pub async fn install(network: &NetworkManager, update: &Update) -> Result<PathBuf> {
+    let asset = update.asset.as_ref().ok_or_else(|| Error::Other(format!(
+        "Release {} has no build for this platform", update.release.tag_name
+    )))?;
+    let exe = std::env::current_exe()?;
+    let exe_name = exe.file_name()
+        .ok_or_else(|| Error::Other(format!("Unexpected executable path {}", exe.display())))?
+        .to_string_lossy()
+        .into_owned();
+
+    let staging = std::env::temp_dir().join(format!("mango-update-{}", update.version));
+    std::fs::create_dir_all(&staging)?;
+    let download = GitHubClient::new(network.clone())
+        .download_asset(&update.release, asset, &staging)
+        .await;
+    let download = match download {
+        Ok(download) if download.verified => download,
+        Ok(_) => {
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(Error::Other(format!(
+                "Release {} publishes no checksum for {}, not installing it", update.release.tag_name, asset.name
+            )));
+        }
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
+
+    // Extracted next to the executable so the final rename stays on one
+    // filesystem.
+    let replacement = exe.with_file_name(format!("{}.new", exe_name));
+    let extracted = extract_binary(&download.path, &exe_name, &replacement);
+    let _ = std::fs::remove_dir_all(&staging);
+    extracted?;
+    make_executable(&replacement)?;
+
+    let previous = exe.with_extension(PREVIOUS_SUFFIX);
+    let _ = std::fs::remove_file(&previous);
+    std::fs::rename(&exe, &previous)?;
+    if let Err(e) = std::fs::rename(&replacement, &exe) {
+        let _ = std::fs::rename(&previous, &exe);
+        let _ = std::fs::remove_file(&replacement);
+        return Err(e.into());
+    }
+    tracing::info!("{}", trf("log.launcher_updated", &[("version", &update.version), ("path", &previous.display())]));
+    Ok(exe)
+}
+
+// Copies the file called `binary` out of a .tar.gz or .zip release archive.
+fn extract_binary(archive: &Path, binary: &str, target: &Path) -> Result<()> {
+    let is_binary = |path: &Path| path.file_name().is_some_and(|name| name.to_string_lossy() == binary);
+
+    if archive.extension().is_some_and(|ext| ext == "zip") {
+        let mut zip = zip::ZipArchive::new(File::open(archive)?)?;
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i)?;
+            if entry.is_file() && entry.enclosed_name().is_some_and(is_binary) {
+                let mut data = Vec::new();
+                entry.read_to_end(&mut data)?;
+                std::fs::write(target, data)?;
+                return Ok(());
+            }
+        }
+    } else {
+        let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(File::open(archive)?));
+        for entry in tar.entries()? {
+            let mut entry = entry?;
+            if entry.header().entry_type().is_file() && is_binary(&entry.path()?) {
+                entry.unpack(target)?;
+                return Ok(());
+            }
+        }
+    }
+    Err(Error::Other(format!("{} does not contain {}", archive.display(), binary)))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// Removes the binary a previous update replaced. On Windows it cannot be
+/// deleted while it is still running, so this is done at the next start.
+pub fn cleanup_previous() {
+    if let Ok(exe) = std::env::current_exe() {
+        let _ = std::fs::remove_file(exe.with_extension(PREVIOUS_SUFFIX));
+    }
+}
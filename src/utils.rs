@@ -1,4 +1,10 @@
+use std::cell::Cell;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use sha1::{Digest, Sha1};
 use crate::Result;
 
 pub fn format_size(bytes: u64) -> String {
@@ -18,6 +24,79 @@ pub fn format_size(bytes: u64) -> String {
     }
 }
 
+pub fn format_duration(seconds: u64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+pub fn split_command_line(command: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quote: Option<char> = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', Some('\'')) => current.push(c),
+            ('\\', _) => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_token = true;
+            }
+            (q, None) if q == '"' || q == '\'' => {
+                quote = Some(q);
+                in_token = true;
+            }
+            (q, Some(open)) if q == open => quote = None,
+            (c, None) if c.is_whitespace() => {
+                if in_token {
+                    args.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            (c, _) => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+
+    if in_token {
+        args.push(current);
+    }
+    args
+}
+
+pub fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == '.' { c } else { '_' })
+        .collect();
+    let trimmed = sanitized.trim_matches('.');
+    if trimmed.is_empty() {
+        "unnamed".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+pub fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/").zip(dirs::home_dir()) {
+        Some((rest, home)) => home.join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
 pub fn ensure_dir_exists(path: &Path) -> Result<()> {
     if !path.exists() {
         std::fs::create_dir_all(path)?;
@@ -25,9 +104,216 @@ pub fn ensure_dir_exists(path: &Path) -> Result<()> {
     Ok(())
 }
 
-pub fn get_data_dir() -> Result<PathBuf> {
-    let data_dir = dirs::data_dir()
+// Hashes on the blocking pool and streams the file, so a large jar neither
+// stalls the async runtime nor gets loaded into memory whole.
+pub async fn file_sha1(path: &Path) -> Result<String> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut file = std::fs::File::open(&path)?;
+        let mut hasher = Sha1::new();
+        std::io::copy(&mut file, &mut hasher)?;
+        Ok(hex::encode(hasher.finalize()))
+    })
+    .await
+    .map_err(|e| crate::Error::Other(format!("Hashing task failed: {}", e)))?
+}
+
+pub const DATA_DIR_ENV: &str = "MANGO_LAUNCHER_HOME";
+/// An empty file next to the executable that switches on portable mode.
+pub const PORTABLE_MARKER: &str = "portable.txt";
+const PORTABLE_DATA_DIR: &str = "data";
+// Left in the platform's data directory when first-run setup picked another
+// folder; holds that folder's path.
+const DATA_DIR_POINTER: &str = "data-dir.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataDirSource {
+    Flag,
+    Environment,
+    Portable,
+    Chosen,
+    Platform,
+}
+
+static DATA_DIR: OnceLock<(PathBuf, DataDirSource)> = OnceLock::new();
+static RESTART: AtomicBool = AtomicBool::new(false);
+
+/// Picks the data directory for the rest of the process, as `--data-dir`
+/// does. Has to run before anything asks for it.
+pub fn set_data_dir(path: &Path, source: DataDirSource) -> Result<()> {
+    let path = std::path::absolute(path)?;
+    DATA_DIR.set((path, source))
+        .map_err(|_| crate::Error::Settings("The data directory is already in use".to_string()))
+}
+
+/// Where settings, instances, versions and everything else live: the
+/// `--data-dir` flag, then `MANGO_LAUNCHER_HOME`, then a portable folder
+/// next to the executable, then the folder chosen during first-run setup,
+/// then the platform's data directory.
+pub fn data_dir() -> PathBuf {
+    data_dir_choice().0.clone()
+}
+
+pub fn data_dir_source() -> DataDirSource {
+    data_dir_choice().1
+}
+
+fn data_dir_choice() -> &'static (PathBuf, DataDirSource) {
+    DATA_DIR.get_or_init(|| {
+        if let Some(dir) = std::env::var_os(DATA_DIR_ENV).filter(|dir| !dir.is_empty()) {
+            let dir = PathBuf::from(dir);
+            return (std::path::absolute(&dir).unwrap_or(dir), DataDirSource::Environment);
+        }
+        if let Some(dir) = portable_dir().filter(|dir| dir.with_file_name(PORTABLE_MARKER).is_file()) {
+            return (dir, DataDirSource::Portable);
+        }
+        let dir = platform_data_dir();
+        let chosen = std::fs::read_to_string(dir.join(DATA_DIR_POINTER)).ok()
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty());
+        match chosen {
+            Some(path) => (PathBuf::from(path), DataDirSource::Chosen),
+            None => (dir, DataDirSource::Platform),
+        }
+    })
+}
+
+/// The data folder used when nothing else picks one.
+pub fn platform_data_dir() -> PathBuf {
+    dirs::data_dir()
         .unwrap_or_else(|| PathBuf::from("."))
-        .join("mango-launcher");
-    Ok(data_dir)
-} 
\ No newline at end of file
+        .join("mango-launcher")
+}
+
+/// Makes later starts use `dir`, or the platform's data directory when
+/// `dir` is `None`. Portable mode is switched off, since it would win.
+pub fn choose_data_dir(dir: Option<&Path>) -> Result<()> {
+    let pointer = platform_data_dir().join(DATA_DIR_POINTER);
+    if let Some(marker) = portable_dir().map(|dir| dir.with_file_name(PORTABLE_MARKER)).filter(|marker| marker.is_file()) {
+        std::fs::remove_file(marker)?;
+    }
+    match dir.map(std::path::absolute).transpose()?.filter(|dir| *dir != platform_data_dir()) {
+        Some(dir) => {
+            std::fs::create_dir_all(&dir)?;
+            std::fs::create_dir_all(platform_data_dir())?;
+            std::fs::write(pointer, dir.to_string_lossy().as_bytes())?;
+        }
+        None if pointer.exists() => std::fs::remove_file(pointer)?,
+        None => {}
+    }
+    Ok(())
+}
+
+/// The data folder portable mode uses, next to the executable.
+pub fn portable_dir() -> Option<PathBuf> {
+    let executable = std::env::current_exe().ok()?;
+    Some(executable.parent()?.join(PORTABLE_DATA_DIR))
+}
+
+/// Leaves the marker next to the executable so later starts stay portable
+/// without the flag, and switches this process over as well.
+pub fn enable_portable() -> Result<()> {
+    let dir = write_portable_marker()?;
+    set_data_dir(&dir, DataDirSource::Portable)
+}
+
+/// Leaves the portable marker for later starts only; returns the data
+/// folder they will use.
+pub fn write_portable_marker() -> Result<PathBuf> {
+    let dir = portable_dir()
+        .ok_or_else(|| crate::Error::Platform("Cannot locate the launcher executable".to_string()))?;
+    std::fs::write(dir.with_file_name(PORTABLE_MARKER), "")?;
+    Ok(dir)
+}
+
+/// Asks for the launcher to be started again once the interface closes,
+/// e.g. so a newly chosen data directory takes effect.
+pub fn request_restart() {
+    RESTART.store(true, Ordering::Relaxed);
+}
+
+pub fn restart_requested() -> bool {
+    RESTART.load(Ordering::Relaxed)
+}
+
+pub fn get_data_dir() -> Result<PathBuf> {
+    Ok(data_dir())
+}
+
+const LOCK_FILE: &str = "launcher.lock";
+
+/// Held while a data file is read, changed and written back, so launchers
+/// sharing a data directory (the TUI next to a game started from a
+/// shortcut) take turns instead of overwriting each other's saves. Nested
+/// acquires on the same thread reuse the lock already held.
+#[derive(Debug)]
+pub struct DataDirLock {
+    _file: Option<File>,
+}
+
+thread_local! {
+    static LOCK_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+impl DataDirLock {
+    pub fn acquire(dir: &Path) -> Result<Self> {
+        if LOCK_DEPTH.get() > 0 {
+            LOCK_DEPTH.set(LOCK_DEPTH.get() + 1);
+            return Ok(Self { _file: None });
+        }
+        std::fs::create_dir_all(dir)?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(dir.join(LOCK_FILE))?;
+        file.lock()?;
+        LOCK_DEPTH.set(1);
+        Ok(Self { _file: Some(file) })
+    }
+}
+
+impl Drop for DataDirLock {
+    fn drop(&mut self) {
+        LOCK_DEPTH.set(LOCK_DEPTH.get() - 1);
+    }
+}
+
+pub fn lock_data_dir() -> Result<DataDirLock> {
+    DataDirLock::acquire(&data_dir())
+}
+
+/// `write_atomic` for the launcher's own files, under the data directory
+/// lock.
+pub fn save_data_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    let _lock = lock_data_dir()?;
+    write_atomic(path, contents)?;
+    Ok(())
+}
+
+/// Replaces `path` with `contents` so that a crash or a full disk mid-save
+/// leaves either the old file or the new one, never a mix of both.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let name = path.file_name()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "path has no file name"))?;
+    let temp = path.with_file_name(format!(".{}.tmp", name.to_string_lossy()));
+
+    let written = File::create(&temp)
+        .and_then(|mut file| {
+            file.write_all(contents.as_ref())?;
+            file.sync_all()
+        })
+        .and_then(|_| std::fs::rename(&temp, path));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    written?;
+
+    // The rename itself is only durable once the directory is flushed.
+    #[cfg(unix)]
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        let _ = File::open(parent).and_then(|dir| dir.sync_all());
+    }
+    Ok(())
+}
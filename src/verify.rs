@@ -0,0 +1,169 @@
+use std::process::Stdio;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::app::App;
+use crate::launch::LaunchManager;
+use crate::{Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyReport {
+    pub instance: String,
+    pub instance_id: Uuid,
+    pub minecraft_version: String,
+    pub ok: bool,
+    pub checks: Vec<CheckResult>,
+    pub command: Option<Vec<String>>,
+}
+
+impl VerifyReport {
+    fn check(&mut self, name: &'static str, status: CheckStatus, detail: impl Into<String>) {
+        self.checks.push(CheckResult { name, status, detail: detail.into() });
+    }
+}
+
+pub async fn verify_instance(app: &mut App, selector: &str, spawn: bool) -> Result<VerifyReport> {
+    let mut instance = app.find_instance(selector)
+        .cloned()
+        .ok_or_else(|| Error::Instance(format!("Instance '{}' not found", selector)))?;
+    instance.apply_inherited(app.profile_manager.get_active_profile(), app.settings_manager.get());
+
+    let mut report = VerifyReport {
+        instance: instance.name.clone(),
+        instance_id: instance.id,
+        minecraft_version: instance.minecraft_version.clone(),
+        ok: false,
+        checks: Vec::new(),
+        command: None,
+    };
+
+    let version_details = match app.version_manager.get_version_details(&instance.minecraft_version) {
+        Ok(details) => {
+            let jar = instance.client_jar()
+                .unwrap_or_else(|| app.version_manager.get_version_jar_path(&instance.minecraft_version));
+            if jar.exists() {
+                report.check("version_files", CheckStatus::Pass, format!("{} present", jar.display()));
+            } else {
+                report.check("version_files", CheckStatus::Fail, format!("client jar missing: {}", jar.display()));
+            }
+            Some(details)
+        }
+        Err(e) => {
+            report.check("version_files", CheckStatus::Fail, format!("version metadata unavailable: {}", e));
+            None
+        }
+    };
+
+    match &version_details {
+        Some(details) => {
+            let libraries_dir = app.version_manager.get_libraries_dir();
+            let artifacts: Vec<_> = details.libraries
+                .iter()
+                .flatten()
+                .flat_map(|library| library.platform_artifacts())
+                .collect();
+            let missing: Vec<_> = artifacts
+                .iter()
+                .filter(|artifact| !libraries_dir.join(&artifact.path).exists())
+                .map(|artifact| artifact.path.clone())
+                .collect();
+            if missing.is_empty() {
+                report.check("libraries", CheckStatus::Pass, format!("{} libraries present", artifacts.len()));
+            } else {
+                report.check("libraries", CheckStatus::Fail, format!("{} of {} missing: {}", missing.len(), artifacts.len(), missing.join(", ")));
+            }
+
+            match &details.assets {
+                Some(assets_id) => match app.assets_manager.verify_assets(assets_id) {
+                    Ok((total, 0)) => report.check("assets", CheckStatus::Pass, format!("{} objects present", total)),
+                    Ok((total, missing)) => report.check("assets", CheckStatus::Fail, format!("{} of {} objects missing", missing, total)),
+                    Err(e) => report.check("assets", CheckStatus::Fail, format!("asset index {} unavailable: {}", assets_id, e)),
+                },
+                None => report.check("assets", CheckStatus::Warn, "version does not declare an asset index"),
+            }
+        }
+        None => {
+            report.check("libraries", CheckStatus::Skip, "version metadata unavailable");
+            report.check("assets", CheckStatus::Skip, "version metadata unavailable");
+        }
+    }
+
+    let java = app.java_manager.get_default_installation().cloned();
+    let required_java = version_details.as_ref()
+        .and_then(|d| d.java_version.as_ref())
+        .map(|v| v.major_version.max(0) as u32);
+    match (&java, required_java) {
+        (None, _) => report.check("java", CheckStatus::Fail, "no Java installation found"),
+        (Some(java), Some(required)) => match java.major_version() {
+            Some(major) if major >= required => report.check("java", CheckStatus::Pass, format!("Java {} at {} (requires {})", major, java.path.display(), required)),
+            Some(major) => report.check("java", CheckStatus::Fail, format!("Java {} at {} is older than required {}", major, java.path.display(), required)),
+            None => report.check("java", CheckStatus::Warn, format!("could not parse Java version '{}'", java.version)),
+        },
+        (Some(java), None) => report.check("java", CheckStatus::Warn, format!("{} found, version requirement unknown", java.path.display())),
+    }
+
+    let account = app.launch_account_for(&instance).cloned();
+    match &account {
+        Some(account) if account.is_valid() => report.check("account", CheckStatus::Pass, format!("{} ({:?})", account.display_name, account.account_type)),
+        Some(account) => report.check("account", CheckStatus::Fail, format!("{} has an expired or missing token", account.display_name)),
+        None => report.check("account", CheckStatus::Fail, "no default account set"),
+    }
+
+    let command = match (&java, &account) {
+        (Some(java), Some(account)) => {
+            match LaunchManager::build_command(&instance, account, java, &app.version_manager, &app.data_dir) {
+                Ok(command) => {
+                    report.check("launch_command", CheckStatus::Pass, format!("main class {}", command.main_class));
+                    report.command = Some(command.redacted());
+                    Some(command)
+                }
+                Err(e) => {
+                    report.check("launch_command", CheckStatus::Fail, e.to_string());
+                    None
+                }
+            }
+        }
+        _ => {
+            report.check("launch_command", CheckStatus::Skip, "requires Java and an account");
+            None
+        }
+    };
+
+    if spawn {
+        match command {
+            Some(command) => {
+                let output = tokio::process::Command::new(&command.java)
+                    .args(&command.jvm_args)
+                    .arg("-version")
+                    .stdin(Stdio::null())
+                    .output()
+                    .await;
+                match output {
+                    Ok(output) if output.status.success() => report.check("spawn", CheckStatus::Pass, "JVM accepted launch arguments"),
+                    Ok(output) => report.check("spawn", CheckStatus::Fail, String::from_utf8_lossy(&output.stderr).trim().to_string()),
+                    Err(e) => report.check("spawn", CheckStatus::Fail, e.to_string()),
+                }
+            }
+            None => report.check("spawn", CheckStatus::Skip, "launch command unavailable"),
+        }
+    }
+
+    report.ok = report.checks.iter().all(|c| c.status != CheckStatus::Fail);
+    Ok(report)
+}
@@ -6,10 +6,20 @@ use tokio::fs;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 
-use crate::network::NetworkManager;
-use std::collections::HashMap;
-
-const MANIFEST_URL: &str = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
+use crate::assets::AssetIndex;
+use crate::events::{LauncherEvent, Notifier};
+use crate::launch::args::Environment;
+use crate::libraries::LibraryStore;
+use crate::network::{NetworkManager, ProgressCallback};
+use crate::tasks::{TaskReporter, TaskStage};
+use futures_util::stream::{self, StreamExt};
+use sha1::{Digest, Sha1};
+use std::collections::{BTreeMap, HashMap};
+
+const MANIFEST_URL: &str = "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+const MANIFEST_CACHE_FILE: &str = "version_manifest_v2.json";
+// Cache of the v1 manifest, which has no hashes; removed once the v2 one is saved.
+const LEGACY_MANIFEST_CACHE_FILE: &str = "version_manifest.json";
 const RECENT_VERSIONS_LIMIT: usize = 5;
 
 
@@ -25,13 +35,169 @@ pub struct MinecraftVersion {
     pub sha1: Option<String>,
 }
 
+// April Fools releases are published as regular snapshots (or, for the
+// oldest ones, releases) in the manifest, so they can only be told apart by id.
+pub const APRIL_FOOLS_VERSIONS: &[&str] = &[
+    "2.0",
+    "15w14a",
+    "1.RV-Pre1",
+    "3D Shareware v1.34",
+    "20w14infinite",
+    "22w13oneblockatatime",
+    "23w13a_or_b",
+    "24w14potato",
+    "25w14craftmine",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VersionKind {
+    Release,
+    Snapshot,
+    Beta,
+    Alpha,
+    AprilFools,
+}
+
+impl VersionKind {
+    pub const ALL: [VersionKind; 5] = [
+        VersionKind::Release,
+        VersionKind::Snapshot,
+        VersionKind::Beta,
+        VersionKind::Alpha,
+        VersionKind::AprilFools,
+    ];
+
+    pub fn of(version: &MinecraftVersion) -> Option<Self> {
+        if APRIL_FOOLS_VERSIONS.contains(&version.id.as_str()) {
+            return Some(VersionKind::AprilFools);
+        }
+        match version.r#type.as_str() {
+            "release" => Some(VersionKind::Release),
+            "snapshot" => Some(VersionKind::Snapshot),
+            "old_beta" => Some(VersionKind::Beta),
+            "old_alpha" => Some(VersionKind::Alpha),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VersionKind::Release => "release",
+            VersionKind::Snapshot => "snapshot",
+            VersionKind::Beta => "beta",
+            VersionKind::Alpha => "alpha",
+            VersionKind::AprilFools => "april fools",
+        }
+    }
+
+    fn rank(version: &MinecraftVersion) -> usize {
+        VersionKind::of(version)
+            .and_then(|kind| VersionKind::ALL.iter().position(|k| *k == kind))
+            .unwrap_or(VersionKind::ALL.len())
+    }
+}
+
+/// Which non-release channels a version picker offers. Releases are always shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct VersionChannels {
+    pub snapshots: bool,
+    pub legacy: bool,
+    pub april_fools: bool,
+}
+
+impl VersionChannels {
+    pub const ALL: VersionChannels = VersionChannels { snapshots: true, legacy: true, april_fools: true };
+
+    pub fn allows(&self, kind: VersionKind) -> bool {
+        match kind {
+            VersionKind::Release => true,
+            VersionKind::Snapshot => self.snapshots,
+            VersionKind::Beta | VersionKind::Alpha => self.legacy,
+            VersionKind::AprilFools => self.april_fools,
+        }
+    }
+
+    pub fn includes(&self, version: &MinecraftVersion) -> bool {
+        VersionKind::of(version).is_none_or(|kind| self.allows(kind))
+    }
+
+    pub fn is_experimental(&self) -> bool {
+        self.snapshots || self.legacy || self.april_fools
+    }
+
+    pub fn label(&self) -> String {
+        let kinds: Vec<&str> = VersionKind::ALL.iter()
+            .filter(|kind| self.allows(**kind))
+            .map(|kind| kind.label())
+            .collect();
+        kinds.join(", ")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VersionFilter {
+    pub query: String,
+    pub kinds: Vec<VersionKind>,
+}
+
+impl Default for VersionFilter {
+    fn default() -> Self {
+        Self { query: String::new(), kinds: VersionKind::ALL.to_vec() }
+    }
+}
+
+impl VersionFilter {
+    pub fn with_channels(channels: VersionChannels) -> Self {
+        Self { query: String::new(), kinds: Self::channel_kinds(channels) }
+    }
+
+    pub fn set_channels(&mut self, channels: VersionChannels) {
+        self.kinds = Self::channel_kinds(channels);
+    }
+
+    fn channel_kinds(channels: VersionChannels) -> Vec<VersionKind> {
+        VersionKind::ALL.into_iter().filter(|kind| channels.allows(*kind)).collect()
+    }
+
+    pub fn toggle(&mut self, kind: VersionKind) {
+        match self.kinds.iter().position(|k| *k == kind) {
+            Some(index) => { self.kinds.remove(index); }
+            None => self.kinds.push(kind),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.query.trim().is_empty() || self.kinds.len() < VersionKind::ALL.len()
+    }
+
+    pub fn shows(&self, kind: VersionKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    pub fn matches(&self, version: &MinecraftVersion) -> bool {
+        // Unknown manifest types are only hidden by a search query, never by the type toggles.
+        let kind_visible = VersionKind::of(version).is_none_or(|kind| self.shows(kind));
+        let query = self.query.trim().to_lowercase();
+        kind_visible && (query.is_empty() || version.id.to_lowercase().contains(&query))
+    }
+}
+
+// Groups versions by channel in `VersionKind::ALL` order, keeping the manifest
+// order (newest first) inside each group.
+pub fn group_by_channel(versions: &mut [MinecraftVersion]) {
+    versions.sort_by_key(VersionKind::rank);
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VersionDetails {
     pub id: String,
     pub r#type: String,
     pub time: Option<String>,
+    #[serde(alias = "releaseTime")]
     pub release_time: Option<String>,
+    #[serde(alias = "mainClass")]
     pub main_class: Option<String>,
+    #[serde(alias = "minecraftArguments")]
     pub minecraft_arguments: Option<String>,
     pub arguments: Option<Arguments>,
     pub libraries: Option<Vec<Library>>,
@@ -39,9 +205,28 @@ pub struct VersionDetails {
     pub assets: Option<String>,
     #[serde(rename = "assetIndex")]
     pub asset_index: Option<AssetIndexInfo>,
+    #[serde(alias = "javaVersion")]
     pub java_version: Option<JavaVersion>,
 }
 
+impl VersionDetails {
+    // Versions from before 1.6 still boot through the applet wrapper and read
+    // their sounds from `resources/` next to the game directory.
+    // Clients from 23w14a on declare the quick play arguments in their
+    // game arguments.
+    pub fn supports_quick_play(&self) -> bool {
+        self.arguments.iter().flat_map(|arguments| &arguments.game).any(|argument| match argument {
+            Argument::String(value) => value.starts_with("--quickPlay"),
+            Argument::Object { value: ArgumentValue::String(value), .. } => value.starts_with("--quickPlay"),
+            Argument::Object { value: ArgumentValue::Array(values), .. } => values.iter().any(|value| value.starts_with("--quickPlay")),
+        })
+    }
+
+    pub fn is_applet_era(&self) -> bool {
+        matches!(self.r#type.as_str(), "old_alpha" | "old_beta") || self.assets.as_deref() == Some("pre-1.6")
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetIndexInfo {
     pub id: String,
@@ -78,6 +263,7 @@ pub enum ArgumentValue {
 pub struct Rule {
     pub action: String,
     pub os: Option<OsRule>,
+    pub features: Option<HashMap<String, bool>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -95,6 +281,40 @@ pub struct Library {
     pub natives: Option<HashMap<String, String>>,
 }
 
+impl Library {
+    pub fn is_allowed(&self) -> bool {
+        self.is_allowed_in(&Environment::current())
+    }
+
+    pub fn is_allowed_in(&self, environment: &Environment) -> bool {
+        self.rules.as_deref().is_none_or(|rules| crate::launch::args::rules_allow(rules, environment))
+    }
+
+    pub fn native_artifact(&self) -> Option<&Artifact> {
+        let natives = self.natives.as_ref()?;
+        let classifiers = self.downloads.as_ref()?.classifiers.as_ref()?;
+        let classifier = crate::platform::natives_classifier(natives, |name| classifiers.contains_key(name))?;
+        classifiers.get(&classifier)
+    }
+
+    pub fn all_artifacts(&self) -> Vec<&Artifact> {
+        let Some(downloads) = &self.downloads else { return Vec::new() };
+        downloads.artifact.iter()
+            .chain(downloads.classifiers.iter().flat_map(|classifiers| classifiers.values()))
+            .collect()
+    }
+
+    // Artifacts needed on this machine: the main jar plus the natives jar for
+    // the current OS and architecture, skipping libraries excluded by rules.
+    pub fn platform_artifacts(&self) -> Vec<&Artifact> {
+        if !self.is_allowed() {
+            return Vec::new();
+        }
+        let artifact = self.downloads.as_ref().and_then(|d| d.artifact.as_ref());
+        artifact.into_iter().chain(self.native_artifact()).collect()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LibraryDownloads {
     pub artifact: Option<Artifact>,
@@ -128,9 +348,16 @@ pub struct DownloadInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JavaVersion {
     pub component: String,
+    #[serde(alias = "majorVersion")]
     pub major_version: i32,
 }
 
+#[derive(Debug)]
+pub struct LoadedManifest {
+    pub manifest: VersionManifest,
+    pub stale: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VersionManifest {
     pub latest: Option<VersionLatest>,
@@ -158,14 +385,296 @@ impl Default for VersionHistory {
     }
 }
 
+const INSTALL_INDEX_FILE: &str = "installed.json";
+
+/// Versions known to be completely installed, kept in `versions/installed.json`
+/// so listing versions does not re-read every version JSON and stat every
+/// library. Written on install and repair; a full check only happens when
+/// the file is missing or the user asks for verification.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct InstallIndex {
+    pub versions: BTreeMap<String, DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+    pub checked: usize,
+    pub missing: Vec<PathBuf>,
+    pub corrupt: Vec<PathBuf>,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl VerifyReport {
+    pub fn broken(&self) -> usize {
+        self.missing.len() + self.corrupt.len()
+    }
+
+    pub fn repaired(&self) -> usize {
+        self.broken() - self.failed.len()
+    }
+}
+
+struct CheckedFile {
+    url: String,
+    path: PathBuf,
+    sha1: String,
+}
+
+enum FileState {
+    Intact,
+    Missing,
+    Corrupt,
+}
+
+async fn check_file(file: &CheckedFile) -> FileState {
+    if !file.path.is_file() {
+        return FileState::Missing;
+    }
+    match crate::utils::file_sha1(&file.path).await {
+        Ok(hash) if hash == file.sha1 => FileState::Intact,
+        Ok(_) => FileState::Corrupt,
+        Err(_) => FileState::Missing,
+    }
+}
+
+#[derive(Clone)]
+pub struct VersionInstaller {
+    versions_dir: PathBuf,
+    libraries: LibraryStore,
+    network: NetworkManager,
+    notifier: Notifier,
+    max_concurrent_verifications: usize,
+}
+
+impl VersionInstaller {
+    pub async fn install(&self, version: &MinecraftVersion, progress: &TaskReporter) -> Result<VersionDetails> {
+        self.notifier.emit(LauncherEvent::DownloadStarted { version_id: version.id.clone() });
+        let result = self.install_files(version, progress).await;
+        self.notifier.emit(match &result {
+            Ok(_) => LauncherEvent::VersionInstalled { version_id: version.id.clone() },
+            Err(e) => LauncherEvent::VersionInstallFailed { version_id: version.id.clone(), error: e.to_string() },
+        });
+        result
+    }
+
+    async fn install_files(&self, version: &MinecraftVersion, progress: &TaskReporter) -> Result<VersionDetails> {
+        let version_dir = self.versions_dir.join(&version.id);
+        std::fs::create_dir_all(&version_dir)?;
+
+        progress.report(TaskStage::Metadata, 0, 0);
+        let version_file = version_dir.join(format!("{}.json", version.id));
+        let version_details: VersionDetails = match &version.sha1 {
+            // Kept byte for byte so the manifest hash can validate it later; a
+            // cached copy with the right hash is not downloaded again.
+            Some(sha1) => {
+                self.network.download_file(&version.url, &version_file, Some(sha1), None).await?;
+                serde_json::from_str(&fs::read_to_string(&version_file).await?)?
+            }
+            None => {
+                let version_details = self.network.get_json(&version.url).await?;
+                std::fs::write(&version_file, serde_json::to_string_pretty(&version_details)?)?;
+                version_details
+            }
+        };
+
+        if let Some(client) = version_details.downloads.as_ref().and_then(|d| d.client.as_ref()) {
+            let client_path = version_dir.join(format!("{}.jar", version.id));
+            let reporter = progress.clone();
+            let callback: ProgressCallback = Box::new(move |done, total| reporter.report(TaskStage::Client, done, total));
+            self.network.download_file(&client.url, &client_path, Some(&client.sha1), Some(callback)).await?;
+
+            if !Self::verify_jar_integrity(&client_path)? {
+                std::fs::remove_file(&client_path).ok();
+                return Err(crate::Error::Other("The JAR file is corrupt or not a valid archive".to_string()).into());
+            }
+        }
+
+        self.download_libraries(&version_details, progress).await?;
+        Ok(version_details)
+    }
+
+    // `listed` is the version's manifest entry, when the manifest is loaded;
+    // its hash lets a damaged version JSON be caught and fetched again.
+    pub async fn verify(
+        &self,
+        version_id: &str,
+        listed: Option<&MinecraftVersion>,
+        assets_dir: &Path,
+        progress: &TaskReporter,
+    ) -> Result<VerifyReport> {
+        let version_dir = self.versions_dir.join(version_id);
+        let version_file = version_dir.join(format!("{}.json", version_id));
+        if !version_file.exists() {
+            return Err(crate::Error::Version(format!("Version {} not installed", version_id)).into());
+        }
+        progress.plan(&[(TaskStage::Metadata, 1), (TaskStage::Verify, 5), (TaskStage::Repair, 4)]);
+        progress.report(TaskStage::Metadata, 0, 0);
+        let mut report = VerifyReport::default();
+        if let Some((url, sha1)) = listed.and_then(|version| Some((&version.url, version.sha1.as_ref()?))) {
+            report.checked += 1;
+            if crate::utils::file_sha1(&version_file).await? != *sha1 {
+                report.corrupt.push(version_file.clone());
+                if let Err(e) = self.network.download_file(url, &version_file, Some(sha1), None).await {
+                    report.failed.push((version_file.clone(), e.to_string()));
+                }
+            }
+        }
+        let details: VersionDetails = serde_json::from_str(&std::fs::read_to_string(&version_file)?)?;
+
+        let mut files = Vec::new();
+        if let Some(client) = details.downloads.as_ref().and_then(|d| d.client.as_ref()) {
+            files.push(CheckedFile {
+                url: client.url.clone(),
+                path: version_dir.join(format!("{}.jar", version_id)),
+                sha1: client.sha1.clone(),
+            });
+        }
+
+        for artifact in details.libraries.iter().flatten().flat_map(|library| library.platform_artifacts()) {
+            files.push(CheckedFile {
+                url: artifact.url.clone(),
+                path: self.libraries.path_of(artifact),
+                sha1: artifact.sha1.clone(),
+            });
+        }
+
+        if let Some(index) = &details.asset_index {
+            let index_path = assets_dir.join("indexes").join(format!("{}.json", index.id));
+            if !index_path.exists() {
+                self.network.download_file(&index.url, &index_path, Some(&index.sha1), None).await?;
+            }
+            let asset_index: AssetIndex = serde_json::from_str(&std::fs::read_to_string(&index_path)?)?;
+            for object in asset_index.objects.values() {
+                let prefix = &object.hash[..2];
+                files.push(CheckedFile {
+                    url: format!("https://resources.download.minecraft.net/{}/{}", prefix, object.hash),
+                    path: assets_dir.join("objects").join(prefix).join(&object.hash),
+                    sha1: object.hash.clone(),
+                });
+            }
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        files.dedup_by(|a, b| a.path == b.path);
+
+        let total = files.len() as u64;
+        report.checked += files.len();
+        let mut broken = Vec::new();
+        progress.report(TaskStage::Verify, 0, total);
+        let mut checks = stream::iter(files)
+            .map(|file| async move {
+                let state = check_file(&file).await;
+                (file, state)
+            })
+            .buffer_unordered(self.max_concurrent_verifications.max(1));
+
+        let mut done = 0;
+        while let Some((file, state)) = checks.next().await {
+            match state {
+                FileState::Intact => {}
+                FileState::Missing => {
+                    report.missing.push(file.path.clone());
+                    broken.push(file);
+                }
+                FileState::Corrupt => {
+                    report.corrupt.push(file.path.clone());
+                    std::fs::remove_file(&file.path).ok();
+                    broken.push(file);
+                }
+            }
+            done += 1;
+            progress.report(TaskStage::Verify, done, total);
+        }
+
+        let total = broken.len() as u64;
+        progress.report(TaskStage::Repair, 0, total);
+        let network = &self.network;
+        let mut repairs = stream::iter(broken)
+            .map(|file| async move {
+                let result = network.download_file(&file.url, &file.path, Some(&file.sha1), None).await;
+                (file.path, result)
+            })
+            .buffer_unordered(self.network.get_max_concurrent_downloads().max(1));
+
+        let mut done = 0;
+        while let Some((path, result)) = repairs.next().await {
+            if let Err(e) = result {
+                report.failed.push((path, e.to_string()));
+            }
+            done += 1;
+            progress.report(TaskStage::Repair, done, total);
+        }
+        Ok(report)
+    }
+
+    async fn download_libraries(&self, version_details: &VersionDetails, progress: &TaskReporter) -> Result<()> {
+        let Some(libraries) = &version_details.libraries else {
+            return Ok(());
+        };
+        let artifacts: Vec<&Artifact> = libraries.iter()
+            .flat_map(|library| library.platform_artifacts())
+            .collect();
+        self.libraries.install(&artifacts, &self.network, progress).await
+    }
+
+    fn verify_jar_integrity(jar_path: &Path) -> Result<bool> {
+        if !jar_path.exists() {
+            return Ok(false);
+        }
+
+
+        let metadata = std::fs::metadata(jar_path)?;
+        if metadata.len() == 0 {
+            return Ok(false);
+        }
+
+
+        match std::fs::File::open(jar_path) {
+            Ok(file) => {
+                match zip::ZipArchive::new(file) {
+                    Ok(mut archive) => {
+                
+                        let _expected_files = [
+                            "META-INF/MANIFEST.MF",
+                            "net/minecraft/client/main/Main.class",
+                        ];
+                        
+                        let mut found_main = false;
+                        for i in 0..archive.len() {
+                            if let Ok(file) = archive.by_index(i) {
+                                let name = file.name();
+                                if name.contains("Main.class") || name.contains("MinecraftServer.class") {
+                                    found_main = true;
+                                    break;
+                                }
+                            }
+                        }
+                        
+                        if !found_main {
+                                    return Ok(archive.len() > 100);
+                        }
+                        
+                        Ok(true)
+                    }
+                    Err(_) => Ok(false)
+                }
+            }
+            Err(_) => Ok(false)
+        }
+    }
+}
+
 pub struct VersionManager {
     versions_dir: PathBuf,
+    libraries: LibraryStore,
     network: NetworkManager,
+    notifier: Notifier,
     cached_manifest: Option<VersionManifest>,
     history: VersionHistory,
     current_view: VersionView,
     versions: Vec<MinecraftVersion>,
-    max_concurrent_downloads: usize,
+    max_concurrent_verifications: usize,
+    using_stale_manifest: bool,
+    install_index: InstallIndex,
 }
 
 #[derive(Debug, PartialEq)]
@@ -176,18 +685,76 @@ pub enum VersionView {
 }
 
 impl VersionManager {
-    pub fn new(versions_dir: PathBuf, network: NetworkManager, max_concurrent_downloads: usize) -> Result<Self> {
+    pub fn new(versions_dir: PathBuf, network: NetworkManager) -> Result<Self> {
         std::fs::create_dir_all(&versions_dir)?;
         
-        Ok(Self {
+        let mut manager = Self {
+            libraries: LibraryStore::new(versions_dir.join("libraries")),
             versions_dir,
             network,
+            notifier: Notifier::new(),
             cached_manifest: None,
             history: VersionHistory::default(),
             current_view: VersionView::Recent,
             versions: Vec::new(),
-            max_concurrent_downloads,
-        })
+            max_concurrent_verifications: 1,
+            using_stale_manifest: false,
+            install_index: InstallIndex::default(),
+        };
+        manager.load_install_index();
+        Ok(manager)
+    }
+
+    // An index that is missing or unreadable is rebuilt once by checking
+    // every version directory the slow way.
+    fn load_install_index(&mut self) {
+        let index_path = self.versions_dir.join(INSTALL_INDEX_FILE);
+        if let Some(index) = std::fs::read_to_string(&index_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+        {
+            self.install_index = index;
+            return;
+        }
+
+        let now = Utc::now();
+        let installed: Vec<String> = std::fs::read_dir(&self.versions_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|id| self.verify_version_files(id))
+            .collect();
+        self.install_index.versions = installed.into_iter().map(|id| (id, now)).collect();
+        let _ = self.save_install_index();
+    }
+
+    fn save_install_index(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(&self.install_index)?;
+        std::fs::write(self.versions_dir.join(INSTALL_INDEX_FILE), content)?;
+        Ok(())
+    }
+
+    /// Records whether a version is completely installed, after an install or
+    /// a verification.
+    pub fn set_version_installed(&mut self, version_id: &str, installed: bool) -> Result<()> {
+        if installed {
+            self.install_index.versions.insert(version_id.to_string(), Utc::now());
+        } else if self.install_index.versions.remove(version_id).is_none() {
+            return Ok(());
+        }
+        self.save_install_index()
+    }
+
+    /// Drops versions whose directory has been deleted from the index.
+    pub fn prune_install_index(&mut self) -> Result<()> {
+        let before = self.install_index.versions.len();
+        let versions_dir = &self.versions_dir;
+        self.install_index.versions.retain(|id, _| versions_dir.join(id).is_dir());
+        if self.install_index.versions.len() == before {
+            return Ok(());
+        }
+        self.save_install_index()
     }
 
     pub async fn init(&mut self) -> Result<()> {
@@ -277,148 +844,40 @@ impl VersionManager {
         Ok(())
     }
 
-    pub async fn download_version(&self, version: &MinecraftVersion) -> Result<()> {
-        let version_dir = self.versions_dir.join(&version.id);
-        std::fs::create_dir_all(&version_dir)?;
-
-        let version_details: VersionDetails = self.network.get_json(&version.url).await?;
-        
-        let version_file = version_dir.join(format!("{}.json", version.id));
-        let version_json = serde_json::to_string_pretty(&version_details)?;
-        std::fs::write(version_file, version_json)?;
-
-        if let Some(downloads) = &version_details.downloads {
-            if let Some(client) = &downloads.client {
-                let client_path = version_dir.join(format!("{}.jar", version.id));
-                let filename = format!("minecraft-{}.jar", version.id);
-                
-                let success = self.network.download_with_progress_dialog(
-                    &client.url,
-                    &client_path,
-                    Some(&client.sha1),
-                    filename,
-                ).await?;
-                
-                if !success {
-                    return Err(crate::Error::Other("Загрузка отменена пользователем".to_string()).into());
-                }
-
-                if !self.verify_jar_integrity(&client_path).await? {
-                    std::fs::remove_file(&client_path).ok();
-                    return Err(crate::Error::Other("JAR файл поврежден или не является корректным архивом".to_string()).into());
-                }
-            }
+    pub fn installer(&self) -> VersionInstaller {
+        VersionInstaller {
+            versions_dir: self.versions_dir.clone(),
+            libraries: self.libraries.clone(),
+            network: self.network.clone(),
+            notifier: self.notifier.clone(),
+            max_concurrent_verifications: self.max_concurrent_verifications,
         }
-
-        self.download_libraries_with_settings(&version_details).await?;
-
-        Ok(())
     }
 
-
-
-    pub async fn download_libraries_with_settings(&self, version_details: &VersionDetails) -> Result<()> {
-        if let Some(libraries) = &version_details.libraries {
-            let libraries_dir = self.get_libraries_dir();
-            std::fs::create_dir_all(&libraries_dir)?;
-
-            let mut download_tasks = Vec::new();
-            
-            for library in libraries {
-                if let Some(downloads) = &library.downloads {
-                    if let Some(artifact) = &downloads.artifact {
-                        let lib_path = libraries_dir.join(&artifact.path);
-                        
-                        if !lib_path.exists() {
-                            download_tasks.push((
-                                artifact.url.clone(),
-                                lib_path,
-                                Some(artifact.sha1.clone()),
-                            ));
-                        }
-                    }
-                    
-
-                    if let Some(classifiers) = &downloads.classifiers {
-                        for (classifier, artifact) in classifiers {
-                            let lib_path = libraries_dir.join(&artifact.path);
-                            
-                            if !lib_path.exists() {
-                                download_tasks.push((
-                                    artifact.url.clone(),
-                                    lib_path,
-                                    Some(artifact.sha1.clone()),
-                                ));
-                            }
-                        }
-                    }
-                }
-            }
-
-            if !download_tasks.is_empty() {
-                let results = self.network.download_files_concurrent(download_tasks).await?;
-                
-                for success in results {
-                    if !success {
-                        return Err(crate::Error::Other("Загрузка библиотеки отменена".to_string()).into());
-                    }
-                }
-            }
-        }
-        Ok(())
+    pub fn set_network(&mut self, network: NetworkManager) {
+        self.network = network;
     }
 
-    pub fn set_max_concurrent_downloads(&mut self, max_concurrent: usize) {
-        self.max_concurrent_downloads = max_concurrent;
+    pub fn set_notifier(&mut self, notifier: Notifier) {
+        self.notifier = notifier;
     }
 
-    async fn verify_jar_integrity(&self, jar_path: &Path) -> Result<bool> {
-        if !jar_path.exists() {
-            return Ok(false);
-        }
-
-
-        let metadata = std::fs::metadata(jar_path)?;
-        if metadata.len() == 0 {
-            return Ok(false);
-        }
-
-
-        match std::fs::File::open(jar_path) {
-            Ok(file) => {
-                match zip::ZipArchive::new(file) {
-                    Ok(mut archive) => {
-                
-                        let _expected_files = [
-                            "META-INF/MANIFEST.MF",
-                            "net/minecraft/client/main/Main.class",
-                        ];
-                        
-                        let mut found_main = false;
-                        for i in 0..archive.len() {
-                            if let Ok(file) = archive.by_index(i) {
-                                let name = file.name();
-                                if name.contains("Main.class") || name.contains("MinecraftServer.class") {
-                                    found_main = true;
-                                    break;
-                                }
-                            }
-                        }
-                        
-                        if !found_main {
-                                    return Ok(archive.len() > 100);
-                        }
-                        
-                        Ok(true)
-                    }
-                    Err(_) => Ok(false)
-                }
-            }
-            Err(_) => Ok(false)
-        }
+    pub fn set_max_concurrent_verifications(&mut self, max_concurrent: usize) {
+        self.max_concurrent_verifications = max_concurrent;
     }
 
+    /// Whether the index lists the version and its JSON and client jar are
+    /// still there. Cheap enough to call for every row of the version list.
     pub fn is_version_installed(&self, version_id: &str) -> bool {
+        let version_dir = self.versions_dir.join(version_id);
+        self.install_index.versions.contains_key(version_id)
+            && version_dir.join(format!("{}.json", version_id)).exists()
+            && version_dir.join(format!("{}.jar", version_id)).exists()
+    }
+
+    /// Checks the version JSON, client jar and every library on disk,
+    /// ignoring the index.
+    pub fn verify_version_files(&self, version_id: &str) -> bool {
         let version_dir = self.versions_dir.join(version_id);
         let version_json = version_dir.join(format!("{}.json", version_id));
         let version_jar = version_dir.join(format!("{}.jar", version_id));
@@ -432,21 +891,10 @@ impl VersionManager {
                 let libraries_dir = self.get_libraries_dir();
                 
                 for library in libraries {
-                    if let Some(downloads) = &library.downloads {
-                        if let Some(artifact) = &downloads.artifact {
-                            let lib_path = libraries_dir.join(&artifact.path);
-                            if !lib_path.exists() {
-                                return false;
-                            }
-                        }
-                        
-                        if let Some(classifiers) = &downloads.classifiers {
-                            for (_, artifact) in classifiers {
-                                let lib_path = libraries_dir.join(&artifact.path);
-                                if !lib_path.exists() {
-                                    return false;
-                                }
-                            }
+                    for artifact in library.platform_artifacts() {
+                        let lib_path = libraries_dir.join(&artifact.path);
+                        if !lib_path.exists() {
+                            return false;
                         }
                     }
                 }
@@ -475,7 +923,7 @@ impl VersionManager {
     }
 
     pub fn get_libraries_dir(&self) -> PathBuf {
-        self.versions_dir.join("libraries")
+        self.libraries.root().to_path_buf()
     }
 
     pub async fn get_version_manifest(&mut self) -> Result<&VersionManifest> {
@@ -487,10 +935,22 @@ impl VersionManager {
     }
 
     pub async fn load_versions(&mut self) -> Result<()> {
-        let manifest_path = self.versions_dir.join("version_manifest.json");
-        let cache_time_path = self.versions_dir.join("manifest_cache_time");
+        let loaded = Self::fetch_manifest(self.network.clone(), self.versions_dir.clone(), false).await?;
+        self.apply_manifest(loaded);
+        Ok(())
+    }
+
+    pub fn manifest_source(&self) -> (NetworkManager, PathBuf) {
+        (self.network.clone(), self.versions_dir.clone())
+    }
+
+    pub async fn fetch_manifest(network: NetworkManager, versions_dir: PathBuf, force: bool) -> Result<LoadedManifest> {
+        let manifest_path = versions_dir.join(MANIFEST_CACHE_FILE);
+        let cache_time_path = versions_dir.join("manifest_cache_time");
         
-        let should_update = if manifest_path.exists() && cache_time_path.exists() {
+        let should_update = if force {
+            true
+        } else if manifest_path.exists() && cache_time_path.exists() {
             if let Ok(cache_time_str) = std::fs::read_to_string(&cache_time_path) {
                 if let Ok(cache_time) = cache_time_str.parse::<i64>() {
                     let cache_datetime = DateTime::from_timestamp(cache_time, 0).unwrap_or_default();
@@ -508,39 +968,106 @@ impl VersionManager {
         };
 
         if should_update {
-            let manifest_url = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
-            let manifest: VersionManifest = self.network.get_json(manifest_url).await?;
+            let manifest: VersionManifest = match network.get_json_or_cached(MANIFEST_URL).await {
+                // The HTTP cache answered for a failed request: usable, but
+                // possibly out of date, so not recorded as a fresh fetch.
+                Ok((manifest, true)) if !force => return Ok(LoadedManifest { manifest, stale: true }),
+                Ok((_, true)) => return Err(crate::Error::Other("Version manifest could not be refreshed".to_string()).into()),
+                Ok((manifest, false)) => manifest,
+                Err(e) if !force && manifest_path.exists() => {
+                    tracing::warn!("Failed to refresh version manifest, using cached copy: {}", e);
+                    let manifest_content = std::fs::read_to_string(&manifest_path)?;
+                    let manifest: VersionManifest = serde_json::from_str(&manifest_content)?;
+                    return Ok(LoadedManifest { manifest, stale: true });
+                }
+                Err(e) if !force => {
+                    let manifest = Self::installed_manifest(&versions_dir);
+                    if manifest.versions.is_empty() {
+                        return Err(e.into());
+                    }
+                    tracing::warn!("Failed to fetch version manifest, listing installed versions only: {}", e);
+                    return Ok(LoadedManifest { manifest, stale: true });
+                }
+                Err(e) => return Err(e.into()),
+            };
             
             let manifest_json = serde_json::to_string_pretty(&manifest)?;
             std::fs::write(&manifest_path, manifest_json)?;
             std::fs::write(&cache_time_path, Utc::now().timestamp().to_string())?;
+            std::fs::remove_file(versions_dir.join(LEGACY_MANIFEST_CACHE_FILE)).ok();
             
-            self.versions = manifest.versions.clone();
-            self.cached_manifest = Some(manifest);
+            Ok(LoadedManifest { manifest, stale: false })
         } else {
             let manifest_content = std::fs::read_to_string(&manifest_path)?;
             let manifest: VersionManifest = serde_json::from_str(&manifest_content)?;
-            self.versions = manifest.versions.clone();
-            self.cached_manifest = Some(manifest);
+            Ok(LoadedManifest { manifest, stale: false })
         }
-        
-        Ok(())
     }
 
-    pub async fn force_refresh_manifest(&mut self) -> Result<()> {
-        let manifest_url = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
-        let manifest: VersionManifest = self.network.get_json(manifest_url).await?;
-        
-        let manifest_path = self.versions_dir.join("version_manifest.json");
-        let cache_time_path = self.versions_dir.join("manifest_cache_time");
-        
-        let manifest_json = serde_json::to_string_pretty(&manifest)?;
-        std::fs::write(&manifest_path, manifest_json)?;
-        std::fs::write(&cache_time_path, Utc::now().timestamp().to_string())?;
-        
-        self.versions = manifest.versions.clone();
-        self.cached_manifest = Some(manifest);
-        Ok(())
+    fn read_installed_details(versions_dir: &Path) -> Vec<VersionDetails> {
+        std::fs::read_dir(versions_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let id = entry.file_name().to_string_lossy().into_owned();
+                let content = std::fs::read_to_string(entry.path().join(format!("{}.json", id))).ok()?;
+                serde_json::from_str(&content).ok()
+            })
+            .collect()
+    }
+
+    pub fn installed_details(&self) -> Vec<VersionDetails> {
+        Self::read_installed_details(&self.versions_dir)
+    }
+
+    pub fn get_versions_dir(&self) -> &Path {
+        &self.versions_dir
+    }
+
+    fn installed_manifest(versions_dir: &Path) -> VersionManifest {
+        let mut versions: Vec<MinecraftVersion> = Self::read_installed_details(versions_dir)
+            .into_iter()
+            .map(|details| MinecraftVersion {
+                id: details.id,
+                r#type: details.r#type,
+                url: String::new(),
+                time: details.time,
+                release_time: details.release_time,
+                compliance_level: None,
+                sha1: None,
+            })
+            .collect();
+        versions.sort_by(|a, b| b.release_time.cmp(&a.release_time));
+        VersionManifest { latest: None, versions }
+    }
+
+    pub fn apply_manifest(&mut self, loaded: LoadedManifest) {
+        self.versions = loaded.manifest.versions.clone();
+        self.cached_manifest = Some(loaded.manifest);
+        self.using_stale_manifest = loaded.stale;
+    }
+
+    pub fn latest_release(&self) -> Option<&str> {
+        self.cached_manifest.as_ref()
+            .and_then(|manifest| manifest.latest.as_ref())
+            .and_then(|latest| latest.release.as_deref())
+            .or_else(|| self.versions.iter().find(|v| v.r#type == "release").map(|v| v.id.as_str()))
+    }
+
+    pub fn latest_snapshot(&self) -> Option<&str> {
+        self.cached_manifest.as_ref()
+            .and_then(|manifest| manifest.latest.as_ref())
+            .and_then(|latest| latest.snapshot.as_deref())
+            .or_else(|| self.versions.iter().find(|v| v.r#type == "snapshot").map(|v| v.id.as_str()))
+    }
+
+    pub fn set_shared_sources(&self, shared: crate::shared::SharedSources) {
+        self.libraries.set_shared(shared);
+    }
+
+    pub fn is_using_stale_manifest(&self) -> bool {
+        self.using_stale_manifest
     }
 
     pub fn get_versions(&self) -> &[MinecraftVersion] {
@@ -0,0 +1,98 @@
+// Watches folders by comparing listings once a second. A mods or config
+// folder holds a few hundred files at most, so a rescan costs less than an
+// OS watcher thread per folder, behaves the same on every platform and
+// network drive, and needs nothing torn down when a watch is dropped.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const IGNORED_DIRS: &[&str] = &[".safe-mode"];
+
+type Snapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+#[derive(Debug, Clone)]
+pub struct DirWatcher {
+    base: PathBuf,
+    roots: Vec<PathBuf>,
+    snapshot: Snapshot,
+    pending: Option<Snapshot>,
+    last_poll: Instant,
+}
+
+impl DirWatcher {
+    pub fn new(base: PathBuf, roots: &[&str]) -> Self {
+        let roots: Vec<PathBuf> = roots.iter().map(|root| base.join(root)).collect();
+        let snapshot = scan(&roots);
+        Self {
+            base,
+            roots,
+            snapshot,
+            pending: None,
+            last_poll: Instant::now(),
+        }
+    }
+
+    pub fn poll(&mut self) -> Option<Vec<PathBuf>> {
+        if self.last_poll.elapsed() < POLL_INTERVAL {
+            return None;
+        }
+        self.last_poll = Instant::now();
+
+        let current = scan(&self.roots);
+        if current == self.snapshot {
+            self.pending = None;
+            return None;
+        }
+
+        // Wait for one quiet interval so half-copied jars aren't reported.
+        if self.pending.as_ref() != Some(&current) {
+            self.pending = Some(current);
+            return None;
+        }
+
+        let changed = diff(&self.snapshot, &current)
+            .into_iter()
+            .map(|path| path.strip_prefix(&self.base).map(Path::to_path_buf).unwrap_or(path))
+            .collect();
+        self.snapshot = current;
+        self.pending = None;
+        Some(changed)
+    }
+
+    pub fn reset(&mut self) {
+        self.snapshot = scan(&self.roots);
+        self.pending = None;
+    }
+}
+
+fn scan(roots: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for root in roots.iter().filter(|root| root.exists()) {
+        let entries = walkdir::WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|entry| {
+                !IGNORED_DIRS.iter().any(|dir| entry.file_name() == *dir)
+            })
+            .flatten()
+            .filter(|entry| entry.file_type().is_file());
+
+        for entry in entries {
+            if let Ok(metadata) = entry.metadata() {
+                snapshot.insert(entry.into_path(), (metadata.modified().ok(), metadata.len()));
+            }
+        }
+    }
+    snapshot
+}
+
+fn diff(old: &Snapshot, new: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = new.iter()
+        .filter(|(path, meta)| old.get(*path) != Some(meta))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(old.keys().filter(|path| !new.contains_key(*path)).cloned());
+    changed.sort();
+    changed
+}
@@ -0,0 +1,147 @@
+use crate::i18n::tr;
+use crate::settings::{Language, SettingField};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WizardStep {
+    Language,
+    Directories,
+    Memory,
+    Java,
+    Account,
+    Instance,
+    Finish,
+}
+
+impl WizardStep {
+    pub const ALL: [WizardStep; 7] = [
+        WizardStep::Language,
+        WizardStep::Directories,
+        WizardStep::Memory,
+        WizardStep::Java,
+        WizardStep::Account,
+        WizardStep::Instance,
+        WizardStep::Finish,
+    ];
+
+    pub fn index(&self) -> usize {
+        Self::ALL.iter().position(|step| step == self).unwrap_or(0)
+    }
+
+    pub fn next(&self) -> Option<Self> {
+        Self::ALL.get(self.index() + 1).copied()
+    }
+
+    pub fn previous(&self) -> Option<Self> {
+        self.index().checked_sub(1).map(|index| Self::ALL[index])
+    }
+
+    pub fn title(&self) -> &'static str {
+        tr(match self {
+            WizardStep::Language => "wizard.step_language",
+            WizardStep::Directories => "wizard.step_directories",
+            WizardStep::Memory => "wizard.step_memory",
+            WizardStep::Java => "wizard.step_java",
+            WizardStep::Account => "wizard.step_account",
+            WizardStep::Instance => "wizard.step_instance",
+            WizardStep::Finish => "wizard.step_finish",
+        })
+    }
+
+    pub fn hint(&self) -> &'static str {
+        tr(match self {
+            WizardStep::Language => "wizard.hint_language",
+            WizardStep::Directories => "wizard.hint_directories",
+            WizardStep::Memory => "wizard.hint_memory",
+            WizardStep::Java => "wizard.hint_java",
+            WizardStep::Account => "wizard.hint_account",
+            WizardStep::Instance => "wizard.hint_instance",
+            WizardStep::Finish => "wizard.hint_finish",
+        })
+    }
+
+    pub fn items(&self) -> Vec<WizardItem> {
+        let mut items = match self {
+            WizardStep::Language => vec![
+                WizardItem::Language(Language::Russian),
+                WizardItem::Language(Language::English),
+            ],
+            WizardStep::Directories => vec![
+                WizardItem::DataDir,
+                WizardItem::Portable,
+                WizardItem::Setting(SettingField::InstancesDirectory),
+                WizardItem::Setting(SettingField::JavaDirectory),
+            ],
+            WizardStep::Memory => vec![
+                WizardItem::Setting(SettingField::MemoryMin),
+                WizardItem::Setting(SettingField::MemoryMax),
+            ],
+            WizardStep::Java => vec![
+                WizardItem::ScanJava,
+                WizardItem::DownloadJava,
+                WizardItem::Setting(SettingField::DownloadMissingJava),
+            ],
+            WizardStep::Account => vec![WizardItem::AddOfflineAccount],
+            WizardStep::Instance => vec![WizardItem::CreateInstance],
+            WizardStep::Finish => Vec::new(),
+        };
+        items.push(if *self == WizardStep::Finish { WizardItem::Finish } else { WizardItem::Continue });
+        items
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WizardItem {
+    Language(Language),
+    Setting(SettingField),
+    DataDir,
+    Portable,
+    ScanJava,
+    DownloadJava,
+    AddOfflineAccount,
+    CreateInstance,
+    Continue,
+    Finish,
+}
+
+#[derive(Debug, Clone)]
+pub struct Wizard {
+    pub step: WizardStep,
+    pub account: Option<String>,
+    pub instance: Option<String>,
+}
+
+impl Wizard {
+    pub fn new() -> Self {
+        Self {
+            step: WizardStep::Language,
+            account: None,
+            instance: None,
+        }
+    }
+
+    pub fn advance(&mut self) -> bool {
+        match self.step.next() {
+            Some(step) => {
+                self.step = step;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn back(&mut self) -> bool {
+        match self.step.previous() {
+            Some(step) => {
+                self.step = step;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Default for Wizard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
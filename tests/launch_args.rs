@@ -0,0 +1,260 @@
+// Renders the launch arguments of real version JSONs, trimmed in
+// tests/fixtures/versions to the fields the launcher reads, and checks the
+// command lines the game would get.
+
+use mango_launcher::launch::args::{self, Environment, CUSTOM_RESOLUTION, DEMO_USER};
+use mango_launcher::platform::Arch;
+use mango_launcher::version::VersionDetails;
+
+fn fixture(id: &str) -> VersionDetails {
+    let path = format!("{}/tests/fixtures/versions/{}.json", env!("CARGO_MANIFEST_DIR"), id);
+    let json = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+    serde_json::from_str(&json).unwrap_or_else(|e| panic!("{}: {}", path, e))
+}
+
+fn placeholders() -> Vec<(&'static str, String)> {
+    [
+        ("${auth_player_name}", "Steve Jobs"),
+        ("${auth_session}", "token:abc:0123"),
+        ("${auth_uuid}", "0123"),
+        ("${auth_access_token}", "abc"),
+        ("${auth_xuid}", "0"),
+        ("${clientid}", ""),
+        ("${user_type}", "msa"),
+        ("${user_properties}", "{}"),
+        ("${version_name}", "test"),
+        ("${version_type}", "release"),
+        ("${resolution_width}", "1280"),
+        ("${resolution_height}", "720"),
+        ("${launcher_name}", "mango-launcher"),
+        ("${launcher_version}", "2.0.0"),
+        ("${game_directory}", "/data/My Instance/.minecraft"),
+        ("${game_assets}", "/data/assets"),
+        ("${assets_root}", "/data/assets"),
+        ("${assets_index_name}", "idx"),
+        ("${natives_directory}", "/data/natives"),
+        ("${library_directory}", "/data/libraries"),
+        ("${classpath_separator}", ":"),
+        ("${classpath}", "a.jar:b.jar"),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key, value.to_string()))
+    .collect()
+}
+
+fn linux() -> Environment {
+    Environment::new("linux", Arch::X86_64)
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn game(id: &str, environment: &Environment) -> Vec<String> {
+    args::game_arguments(&fixture(id), environment, &placeholders())
+}
+
+fn jvm(id: &str, environment: &Environment) -> Vec<String> {
+    args::jvm_arguments(&fixture(id), environment, &placeholders())
+}
+
+fn value_after<'a>(arguments: &'a [String], flag: &str) -> Option<&'a str> {
+    let index = arguments.iter().position(|argument| argument == flag)?;
+    arguments.get(index + 1).map(String::as_str)
+}
+
+#[test]
+fn legacy_1_8_9_game_arguments() {
+    assert_eq!(game("1.8.9", &linux()), strings(&[
+        "--username", "Steve Jobs",
+        "--version", "test",
+        "--gameDir", "/data/My Instance/.minecraft",
+        "--assetsDir", "/data/assets",
+        "--assetIndex", "idx",
+        "--uuid", "0123",
+        "--accessToken", "abc",
+        "--userProperties", "{}",
+        "--userType", "msa",
+    ]));
+}
+
+#[test]
+fn legacy_1_12_2_game_arguments() {
+    let arguments = game("1.12.2", &linux());
+    assert_eq!(value_after(&arguments, "--versionType"), Some("release"));
+    assert_eq!(value_after(&arguments, "--userProperties"), None);
+    assert_eq!(arguments.len(), 18);
+}
+
+#[test]
+fn legacy_versions_get_feature_arguments_appended() {
+    let environment = linux().with_feature(DEMO_USER).with_feature(CUSTOM_RESOLUTION);
+    for id in ["1.8.9", "1.12.2"] {
+        let arguments = game(id, &environment);
+        assert!(arguments.contains(&"--demo".to_string()), "{}", id);
+        assert_eq!(value_after(&arguments, "--width"), Some("1280"), "{}", id);
+        assert_eq!(value_after(&arguments, "--height"), Some("720"), "{}", id);
+    }
+}
+
+#[test]
+fn legacy_versions_use_default_jvm_arguments() {
+    for id in ["1.8.9", "1.12.2"] {
+        assert_eq!(jvm(id, &linux()), strings(&[
+            "-Djava.library.path=/data/natives",
+            "-cp",
+            "a.jar:b.jar",
+        ]), "{}", id);
+    }
+}
+
+#[test]
+fn modern_1_16_5_game_arguments() {
+    assert_eq!(game("1.16.5", &linux()), strings(&[
+        "--username", "Steve Jobs",
+        "--version", "test",
+        "--gameDir", "/data/My Instance/.minecraft",
+        "--assetsDir", "/data/assets",
+        "--assetIndex", "idx",
+        "--uuid", "0123",
+        "--accessToken", "abc",
+        "--userType", "msa",
+        "--versionType", "release",
+    ]));
+}
+
+#[test]
+fn modern_feature_rules() {
+    for id in ["1.16.5", "1.20.4"] {
+        let plain = game(id, &linux());
+        assert!(!plain.contains(&"--demo".to_string()), "{}", id);
+        assert!(!plain.contains(&"--width".to_string()), "{}", id);
+
+        let demo = game(id, &linux().with_feature(DEMO_USER));
+        assert_eq!(demo.len(), plain.len() + 1, "{}", id);
+        assert_eq!(demo.last().map(String::as_str), Some("--demo"), "{}", id);
+
+        let sized = game(id, &linux().with_feature(CUSTOM_RESOLUTION));
+        assert_eq!(sized[plain.len()..], strings(&["--width", "1280", "--height", "720"]), "{}", id);
+    }
+}
+
+#[test]
+fn modern_1_20_4_game_arguments() {
+    let arguments = game("1.20.4", &linux());
+    assert_eq!(value_after(&arguments, "--clientId"), Some(""));
+    assert_eq!(value_after(&arguments, "--xuid"), Some("0"));
+    // Quick play is added by the launcher itself, never through the rules.
+    assert!(arguments.iter().all(|argument| !argument.starts_with("--quickPlay")));
+    assert!(args::unresolved(&arguments).is_empty());
+    assert!(fixture("1.20.4").supports_quick_play());
+    assert!(!fixture("1.16.5").supports_quick_play());
+}
+
+#[test]
+fn modern_1_16_5_jvm_arguments_per_os() {
+    let common = [
+        "-Djava.library.path=/data/natives",
+        "-Dminecraft.launcher.brand=mango-launcher",
+        "-Dminecraft.launcher.version=2.0.0",
+        "-cp",
+        "a.jar:b.jar",
+    ];
+    assert_eq!(jvm("1.16.5", &linux()), strings(&common));
+
+    let mac = jvm("1.16.5", &Environment::new("osx", Arch::Aarch64));
+    assert_eq!(mac[0], "-XstartOnFirstThread");
+    assert_eq!(mac[1..], strings(&common));
+
+    let windows = jvm("1.16.5", &Environment::new("windows", Arch::X86));
+    assert_eq!(windows[..2], strings(&[
+        "-XX:HeapDumpPath=MojangTricksIntelDriversForPerformance_javaw.exe_minecraft.exe.heapdump",
+        "-Xss1M",
+    ]));
+    assert_eq!(windows[2..], strings(&common));
+
+    let windows_10 = jvm("1.16.5", &Environment {
+        os_version: Some("10.0".to_string()),
+        ..Environment::new("windows", Arch::X86_64)
+    });
+    assert!(windows_10.contains(&"-Dos.name=Windows 10".to_string()));
+    assert!(!windows_10.contains(&"-Xss1M".to_string()));
+}
+
+#[test]
+fn modern_1_20_4_jvm_arguments() {
+    let arguments = jvm("1.20.4", &linux());
+    assert_eq!(arguments, strings(&[
+        "-Djava.library.path=/data/natives",
+        "-Djna.tmpdir=/data/natives",
+        "-Dorg.lwjgl.system.SharedLibraryExtractPath=/data/natives",
+        "-Dio.netty.native.workdir=/data/natives",
+        "-Dminecraft.launcher.brand=mango-launcher",
+        "-Dminecraft.launcher.version=2.0.0",
+        "-cp",
+        "a.jar:b.jar",
+    ]));
+}
+
+#[test]
+fn every_fixture_resolves_all_placeholders() {
+    let environment = linux().with_feature(DEMO_USER).with_feature(CUSTOM_RESOLUTION);
+    for id in ["1.8.9", "1.12.2", "1.16.5", "1.20.4"] {
+        let arguments = [game(id, &environment), jvm(id, &environment)].concat();
+        assert_eq!(args::unresolved(&arguments), Vec::<&str>::new(), "{}", id);
+    }
+}
+
+#[test]
+fn unknown_placeholders_are_kept_and_reported() {
+    let rendered = args::render_legacy("--path ${quickPlayPath} --name ${auth_player_name}", &placeholders());
+    assert_eq!(rendered, strings(&["--path", "${quickPlayPath}", "--name", "Steve Jobs"]));
+    assert_eq!(args::unresolved(&rendered), vec!["${quickPlayPath}"]);
+}
+
+#[test]
+fn placeholder_values_are_not_expanded_again() {
+    let placeholders: Vec<(&str, String)> = placeholders().into_iter()
+        .map(|(key, value)| match key {
+            "${auth_player_name}" => (key, "${auth_access_token}".to_string()),
+            "${assets_root}" => (key, "/data/${game_directory}".to_string()),
+            _ => (key, value),
+        })
+        .collect();
+    let legacy = args::game_arguments(&fixture("1.8.9"), &linux(), &placeholders);
+    assert_eq!(value_after(&legacy, "--username"), Some("${auth_access_token}"));
+    assert_eq!(value_after(&legacy, "--accessToken"), Some("abc"));
+
+    let modern = args::game_arguments(&fixture("1.20.4"), &linux(), &placeholders);
+    assert_eq!(value_after(&modern, "--username"), Some("${auth_access_token}"));
+    assert_eq!(value_after(&modern, "--assetsDir"), Some("/data/${game_directory}"));
+}
+
+#[test]
+fn library_rules() {
+    let allowed = |id: &str, environment: &Environment| -> Vec<String> {
+        fixture(id).libraries.unwrap_or_default().into_iter()
+            .filter(|library| library.is_allowed_in(environment))
+            .map(|library| library.name)
+            .collect()
+    };
+
+    assert_eq!(allowed("1.8.9", &linux()), strings(&[
+        "org.lwjgl.lwjgl:lwjgl:2.9.4-nightly-20150209",
+        "com.mojang:authlib:1.5.21",
+    ]));
+    assert_eq!(allowed("1.8.9", &Environment::new("osx", Arch::X86_64)), strings(&[
+        "tv.twitch:twitch-platform:6.5",
+        "org.lwjgl.lwjgl:lwjgl:2.9.2-nightly-20140822",
+        "com.mojang:authlib:1.5.21",
+    ]));
+    assert_eq!(allowed("1.16.5", &Environment::new("osx", Arch::X86_64)), strings(&["org.lwjgl:lwjgl:3.2.1"]));
+    assert_eq!(allowed("1.20.4", &Environment::new("osx", Arch::Aarch64)), strings(&[
+        "org.lwjgl:lwjgl:3.3.2:natives-macos-arm64",
+        "com.mojang:authlib:6.0.52",
+    ]));
+    assert_eq!(allowed("1.20.4", &Environment::new("windows", Arch::X86_64)), strings(&[
+        "org.lwjgl:lwjgl:3.3.2:natives-windows-x86",
+        "com.mojang:authlib:6.0.52",
+    ]));
+}